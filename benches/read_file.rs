@@ -23,6 +23,11 @@ macro_rules! test_read_file {
 						.read()
 						.unwrap());
 				}
+
+				#[library_benchmark]
+				fn [<$NAME:lower _duration>]() {
+					black_box(lofty::duration($path).unwrap());
+				}
 			}
 		)+
 	}
@@ -65,4 +70,11 @@ library_benchmark_group!(
 	name = file_reading;
 	benchmarks = aac, aiff, ape, flac, mp4, mp3, mpc, opus, riff, speex, vorbis, wavpack
 );
-main!(library_benchmark_groups = file_reading);
+
+library_benchmark_group!(
+	name = duration_reading;
+	benchmarks = aac_duration, aiff_duration, ape_duration, flac_duration, mp4_duration, mp3_duration,
+		mpc_duration, opus_duration, riff_duration, speex_duration, vorbis_duration, wavpack_duration
+);
+
+main!(library_benchmark_groups = file_reading, duration_reading);
@@ -0,0 +1,52 @@
+#![allow(missing_docs)]
+
+use lofty::ape::{ApeItem, ApeTag};
+
+use std::hint::black_box;
+
+use iai_callgrind::{library_benchmark, library_benchmark_group, main};
+
+const ITEM_COUNT: usize = 50;
+
+fn ape_tag() -> (ApeTag, Vec<String>) {
+	let mut tag = ApeTag::new();
+	for i in 0..ITEM_COUNT {
+		tag.insert(ApeItem::text(format!("Item Key {i:02}"), format!("Value {i}")).unwrap());
+	}
+
+	// Look up every key, with a different case than it was stored with
+	let keys = (0..ITEM_COUNT)
+		.map(|i| format!("ITEM KEY {i:02}"))
+		.collect::<Vec<_>>();
+
+	(tag, keys)
+}
+
+// The linear scan `ApeTag::get` used before it kept a key index
+#[library_benchmark]
+#[bench::fifty_items(ape_tag())]
+fn apev2_get_linear(input: (ApeTag, Vec<String>)) -> usize {
+	let (tag, keys) = input;
+	black_box(
+		keys.iter()
+			.filter(|key| {
+				(&tag)
+					.into_iter()
+					.any(|item| item.key().eq_ignore_ascii_case(key))
+			})
+			.count(),
+	)
+}
+
+#[library_benchmark]
+#[bench::fifty_items(ape_tag())]
+fn apev2_get_indexed(input: (ApeTag, Vec<String>)) -> usize {
+	let (tag, keys) = input;
+	black_box(keys.iter().filter(|key| tag.get(key).is_some()).count())
+}
+
+library_benchmark_group!(
+	name = tag_lookup;
+	benchmarks = apev2_get_linear, apev2_get_indexed
+);
+main!(library_benchmark_groups = tag_lookup);
@@ -10,6 +10,32 @@ use std::io::{Read, Seek, SeekFrom};
 // If they aren't equal, something is broken.
 pub(super) const HEADER_MASK: u32 = 0xFFFF_FFE0;
 
+/// Check if `header` starts with a plausible ADTS frame header
+///
+/// This only checks the fixed header fields available in the first 3 bytes, see [`ADTSHeader::read`]
+/// for the layout. MPEG audio frame headers share the same frame sync, but never use layer `0b00`.
+pub(crate) fn is_adts_header(header: &[u8]) -> bool {
+	let [0xFF, byte2, byte3, ..] = *header else {
+		return false;
+	};
+
+	// 12 bit frame sync and a layer of 0b00
+	if byte2 & 0xF6 != 0xF0 {
+		return false;
+	}
+
+	// Sampling frequency indices 13 and 14 are reserved, 15 is forbidden
+	let sample_rate_idx = (byte3 >> 2) & 0b1111;
+	if sample_rate_idx > 12 {
+		return false;
+	}
+
+	// MPEG-2 only defines the first 3 profiles (Main, LC, SSR)
+	let is_mpeg2 = (byte2 >> 3) & 0b1 == 1;
+	let profile = (byte3 >> 6) & 0b11;
+	!(is_mpeg2 && profile == 3)
+}
+
 #[derive(Copy, Clone)]
 pub(crate) struct ADTSHeader {
 	pub(crate) version: MpegVersion,
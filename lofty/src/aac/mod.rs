@@ -2,7 +2,7 @@
 
 // TODO: Currently we only support ADTS, might want to look into ADIF in the future.
 
-mod header;
+pub(crate) mod header;
 mod properties;
 mod read;
 
@@ -7,6 +7,7 @@ use std::time::Duration;
 
 /// An AAC file's audio properties
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AACProperties {
 	pub(crate) version: MpegVersion,
 	pub(crate) audio_object_type: AudioObjectType,
@@ -1,12 +1,15 @@
 use super::header::{ADTSHeader, HEADER_MASK};
 use super::AacFile;
-use crate::config::{ParseOptions, ParsingMode};
+use crate::config::{IoHint, ParseOptions, ParsingMode};
 use crate::error::Result;
 use crate::id3::v2::header::Id3v2Header;
 use crate::id3::v2::read::parse_id3v2;
-use crate::id3::{find_id3v1, ID3FindResults};
+use crate::id3::{
+	find_id3v1, handle_stacked_id3v2, record_id3v2_location, ID3FindResults, MAX_STACKED_ID3V2_TAGS,
+};
 use crate::macros::{decode_err, err, parse_mode_choice};
 use crate::mpeg::header::{cmp_header, search_for_frame_sync, HeaderCmpResult};
+use crate::tag::TagPlacement;
 
 use std::io::{Read, Seek, SeekFrom};
 
@@ -34,18 +37,24 @@ where
 
 	reader.seek(SeekFrom::Start(pos))?;
 
+	let mut id3v2_tag_count = 0;
 	let mut header = [0; 4];
 
 	while let Ok(()) = reader.read_exact(&mut header) {
 		match header {
 			// [I, D, 3, ver_major, ver_minor, flags, size (4 bytes)]
-			[b'I', b'D', b'3', ..] => {
+			//
+			// Some software writes a new tag in front of the existing one, so there may be multiple.
+			[b'I', b'D', b'3', ..] if id3v2_tag_count < MAX_STACKED_ID3V2_TAGS => {
 				// Seek back to read the tag in full
 				reader.seek(SeekFrom::Current(-4))?;
 
+				let offset = reader.stream_position()?;
 				let header = Id3v2Header::parse(reader)?;
 				let skip_footer = header.flags.footer;
 
+				id3v2_tag_count += 1;
+
 				let Some(new_stream_len) = stream_len.checked_sub(u64::from(header.size)) else {
 					err!(SizeMismatch);
 				};
@@ -53,18 +62,17 @@ where
 				stream_len = new_stream_len;
 
 				if parse_options.read_tags {
+					record_id3v2_location(&header, offset, TagPlacement::Head);
+
 					let id3v2 = parse_id3v2(reader, header, parse_options)?;
-					if let Some(existing_tag) = &mut file.id3v2_tag {
-						log::warn!("Duplicate ID3v2 tag found, appending frames to previous tag");
-
-						// https://github.com/Serial-ATA/lofty-rs/issues/87
-						// Duplicate tags should have their frames appended to the previous
-						for frame in id3v2.frames {
-							existing_tag.insert(frame);
-						}
-						continue;
+					match &mut file.id3v2_tag {
+						Some(existing_tag) => {
+							handle_stacked_id3v2(existing_tag, id3v2, offset, parse_options)?
+						},
+						None => file.id3v2_tag = Some(id3v2),
 					}
-					file.id3v2_tag = Some(id3v2);
+				} else {
+					reader.seek(SeekFrom::Current(i64::from(header.size)))?;
 				}
 
 				// Skip over the footer
@@ -103,16 +111,18 @@ where
 		}
 	}
 
-	#[allow(unused_variables)]
-	let ID3FindResults(header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
+	if parse_options.io_hint != IoHint::StreamingRemote {
+		#[allow(unused_variables)]
+		let ID3FindResults(header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
 
-	if header.is_some() {
-		let Some(new_stream_len) = stream_len.checked_sub(128) else {
-			err!(SizeMismatch);
-		};
+		if let Some(id3v1_size) = header {
+			let Some(new_stream_len) = stream_len.checked_sub(id3v1_size) else {
+				err!(SizeMismatch);
+			};
 
-		stream_len = new_stream_len;
-		file.id3v1_tag = id3v1;
+			stream_len = new_stream_len;
+			file.id3v1_tag = id3v1;
+		}
 	}
 
 	if parse_options.read_properties {
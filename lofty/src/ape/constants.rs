@@ -2,3 +2,39 @@ pub(super) const INVALID_KEYS: [&str; 4] = ["ID3", "TAG", "OGGS", "MP+"];
 
 // https://wiki.hydrogenaud.io/index.php?title=APE_Tags_Header
 pub(crate) const APE_PREAMBLE: &[u8; 8] = b"APETAGEX";
+
+// The standard keys from the APEv2 spec, with their recommended capitalization
+//
+// https://wiki.hydrogenaud.io/index.php?title=APE_key
+pub(crate) const STANDARD_KEYS: [&str; 30] = [
+	"Title",
+	"Subtitle",
+	"Artist",
+	"Album",
+	"Debut Album",
+	"Publisher",
+	"Conductor",
+	"Track",
+	"Composer",
+	"Comment",
+	"Copyright",
+	"Publicationright",
+	"File",
+	"EAN/UPC",
+	"ISBN",
+	"Catalog",
+	"LC",
+	"Year",
+	"Record Date",
+	"Record Location",
+	"Genre",
+	"Media",
+	"Index",
+	"Related",
+	"ISRC",
+	"Abstract",
+	"Language",
+	"Bibliography",
+	"Introplay",
+	"Dummy",
+];
@@ -7,46 +7,117 @@ use std::ops::Neg;
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
-#[derive(Copy, Clone)]
-pub(crate) struct ApeHeader {
+// Set in a version 2 footer if the tag also has a header
+const HEADER_PRESENT_FLAG: u32 = 1 << 31;
+
+/// An `APE` tag header (or footer)
+///
+/// This is needed to walk the items of a tag with an [`ApeItemIter`](crate::ape::ApeItemIter).
+#[derive(Copy, Clone, Debug)]
+pub struct ApeHeader {
 	pub(crate) size: u32,
 	pub(crate) item_count: u32,
+	pub(crate) has_header: bool,
 }
 
-pub(crate) fn read_ape_header<R>(data: &mut R, footer: bool) -> Result<ApeHeader>
-where
-	R: Read + Seek,
-{
-	let version = data.read_u32::<LittleEndian>()?;
+impl ApeHeader {
+	/// Parse an `APE` tag header or footer
+	///
+	/// NOTE: This expects the `"APETAGEX"` preamble to have already been read.
+	///
+	/// Upon return, the reader will be positioned at the first item, regardless of whether a header
+	/// or footer was read.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `data`
+	/// * The tag size is invalid
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeHeader;
+	/// use std::io::{Cursor, Read};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// // An empty APEv2 tag, starting with the header
+	/// let mut bytes = Vec::new();
+	/// bytes.extend(b"APETAGEX");
+	/// bytes.extend(2000_u32.to_le_bytes()); // Version
+	/// bytes.extend(32_u32.to_le_bytes()); // Size
+	/// bytes.extend(0_u32.to_le_bytes()); // Item count
+	/// bytes.extend([0; 12]); // Flags + reserved
+	/// # let footer = bytes.clone();
+	/// # bytes.extend(footer);
+	///
+	/// let mut reader = Cursor::new(bytes);
+	///
+	/// let mut preamble = [0; 8];
+	/// reader.read_exact(&mut preamble)?;
+	///
+	/// let header = ApeHeader::parse(&mut reader, false)?;
+	/// assert_eq!(header.item_count(), 0);
+	/// # Ok(()) }
+	/// ```
+	pub fn parse<R>(data: &mut R, footer: bool) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		let version = data.read_u32::<LittleEndian>()?;
 
-	let mut size = data.read_u32::<LittleEndian>()?;
+		let mut size = data.read_u32::<LittleEndian>()?;
 
-	if size < 32 {
-		// If the size is < 32, something went wrong during encoding
-		// The size includes the footer and all items
-		decode_err!(@BAIL Ape, "APE tag has an invalid size (< 32)");
-	}
+		if size < 32 {
+			// If the size is < 32, something went wrong during encoding
+			// The size includes the footer and all items
+			decode_err!(@BAIL @TAG Ape, "APE tag has an invalid size (< 32)");
+		}
+
+		let item_count = data.read_u32::<LittleEndian>()?;
+
+		// Only present in version 2
+		let flags = data.read_u32::<LittleEndian>()?;
 
-	let item_count = data.read_u32::<LittleEndian>()?;
+		if footer {
+			// No point in reading the rest of the footer, just seek back to the end of the header
+			data.seek(SeekFrom::Current(i64::from(size - 8).neg()))?;
+		} else {
+			// There are 8 bytes remaining in the header
+			// Reserved (8)
+			data.seek(SeekFrom::Current(8))?;
+		}
 
-	if footer {
-		// No point in reading the rest of the footer, just seek back to the end of the header
-		data.seek(SeekFrom::Current(i64::from(size - 12).neg()))?;
-	} else {
-		// There are 12 bytes remaining in the header
-		// Flags (4)
-		// Reserved (8)
-		data.seek(SeekFrom::Current(12))?;
+		// Version 1 doesn't include a header, and it's optional in version 2
+		let has_header = version == 2000 && (!footer || flags & HEADER_PRESENT_FLAG != 0);
+		if has_header {
+			size = size.saturating_add(32);
+		}
+
+		if u64::from(size) > data.stream_len_hack()? {
+			decode_err!(@BAIL @TAG Ape, "APE tag has an invalid size (> file size)");
+		}
+
+		Ok(ApeHeader {
+			size,
+			item_count,
+			has_header,
+		})
 	}
 
-	// Version 1 doesn't include a header
-	if version == 2000 {
-		size = size.saturating_add(32);
+	/// The size of the tag, including the footer and header (if present)
+	pub fn size(&self) -> u32 {
+		self.size
 	}
 
-	if u64::from(size) > data.stream_len_hack()? {
-		decode_err!(@BAIL Ape, "APE tag has an invalid size (> file size)");
+	/// The number of items in the tag
+	pub fn item_count(&self) -> u32 {
+		self.item_count
 	}
 
-	Ok(ApeHeader { size, item_count })
+	/// Whether the tag has a header
+	///
+	/// Version 1 tags never have a header.
+	pub fn has_header(&self) -> bool {
+		self.has_header
+	}
 }
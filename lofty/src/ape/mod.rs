@@ -19,8 +19,10 @@ use lofty_attr::LoftyFile;
 // Exports
 
 pub use crate::picture::APE_PICTURE_TYPES;
+pub use header::ApeHeader;
 pub use properties::ApeProperties;
 pub use tag::item::ApeItem;
+pub use tag::read::ApeItemIter;
 pub use tag::ApeTag;
 
 /// An APE file
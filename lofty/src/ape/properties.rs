@@ -10,6 +10,7 @@ use byteorder::{LittleEndian, ReadBytesExt};
 
 /// An APE file's audio properties
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct ApeProperties {
 	pub(crate) version: u16,
@@ -83,7 +84,7 @@ where
 {
 	let version = data
 		.read_u16::<LittleEndian>()
-		.map_err(|_| decode_err!(Ape, "Unable to read APE tag version"))?;
+		.map_err(|e| decode_err!(Ape, "Unable to read APE version", source: e))?;
 
 	// Property reading differs between versions
 	if version >= 3980 {
@@ -105,10 +106,11 @@ where
 {
 	// First read the file descriptor
 	let mut descriptor = [0; 46];
-	data.read_exact(&mut descriptor).map_err(|_| {
+	data.read_exact(&mut descriptor).map_err(|e| {
 		decode_err!(
 			Ape,
-			"Not enough data left in reader to finish file descriptor"
+			"Not enough data left in reader to finish file descriptor",
+			source: e
 		)
 	})?;
 
@@ -125,8 +127,13 @@ where
 
 	// Move on to the header
 	let mut header = [0; 24];
-	data.read_exact(&mut header)
-		.map_err(|_| decode_err!(Ape, "Not enough data left in reader to finish MAC header"))?;
+	data.read_exact(&mut header).map_err(|e| {
+		decode_err!(
+			Ape,
+			"Not enough data left in reader to finish MAC header",
+			source: e
+		)
+	})?;
 
 	let mut properties = ApeProperties::default();
 	properties.version = version;
@@ -174,8 +181,13 @@ where
 {
 	// Versions < 3980 don't have a descriptor
 	let mut header = [0; 26];
-	data.read_exact(&mut header)
-		.map_err(|_| decode_err!(Ape, "Not enough data left in reader to finish MAC header"))?;
+	data.read_exact(&mut header).map_err(|e| {
+		decode_err!(
+			Ape,
+			"Not enough data left in reader to finish MAC header",
+			source: e
+		)
+	})?;
 
 	let mut properties = ApeProperties::default();
 	properties.version = version;
@@ -1,14 +1,14 @@
-use super::header::read_ape_header;
+use super::header::ApeHeader;
 use super::tag::ApeTag;
 use super::{ApeFile, ApeProperties};
-use crate::ape::tag::read::{read_ape_tag, read_ape_tag_with_header};
+use crate::ape::tag::read::{read_ape_tag, read_ape_tag_with_header, record_ape_location};
 use crate::config::ParseOptions;
 use crate::error::Result;
 use crate::id3::v1::tag::Id3v1Tag;
-use crate::id3::v2::read::parse_id3v2;
 use crate::id3::v2::tag::Id3v2Tag;
 use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, FindId3v2Config, ID3FindResults};
 use crate::macros::{decode_err, err};
+use crate::tag::TagPlacement;
 
 use std::io::{Read, Seek, SeekFrom};
 
@@ -34,22 +34,16 @@ where
 	};
 
 	// ID3v2 tags are unsupported in APE files, but still possible
-	if let ID3FindResults(Some(header), content) = find_id3v2(data, find_id3v2_config)? {
+	let id3v2_tags = find_id3v2(data, find_id3v2_config)?;
+	if !id3v2_tags.is_empty() {
 		log::warn!("Encountered an ID3v2 tag. This tag cannot be rewritten to the APE file!");
 
-		let Some(new_stream_length) = stream_len.checked_sub(u64::from(header.full_tag_size()))
-		else {
+		let Some(new_stream_length) = stream_len.checked_sub(id3v2_tags.size()) else {
 			err!(SizeMismatch);
 		};
 
 		stream_len = new_stream_length;
-
-		if let Some(content) = content {
-			let reader = &mut &*content;
-
-			let id3v2 = parse_id3v2(reader, header, parse_options)?;
-			id3v2_tag = Some(id3v2);
-		}
+		id3v2_tag = id3v2_tags.parse(parse_options)?;
 	}
 
 	let mut found_mac = false;
@@ -74,18 +68,19 @@ where
 
 				// Get the remaining part of the ape tag
 				let mut remaining = [0; 4];
-				data.read_exact(&mut remaining).map_err(|_| {
+				data.read_exact(&mut remaining).map_err(|e| {
 					decode_err!(
-						Ape,
-						"Found partial APE tag, but there isn't enough data left in the reader"
+						@TAG Ape,
+						"Found partial APE tag, but there isn't enough data left in the reader",
+						source: e
 					)
 				})?;
 
 				if &remaining[..4] != b"AGEX" {
-					decode_err!(@BAIL Ape, "Found incomplete APE tag");
+					decode_err!(@BAIL @TAG Ape, "Found incomplete APE tag");
 				}
 
-				let ape_header = read_ape_header(data, false)?;
+				let ape_header = ApeHeader::parse(data, false)?;
 				let Some(new_stream_length) = stream_len.checked_sub(u64::from(ape_header.size))
 				else {
 					err!(SizeMismatch);
@@ -93,6 +88,8 @@ where
 				stream_len = new_stream_length;
 
 				if parse_options.read_tags {
+					record_ape_location(ape_header, data.stream_position()?, TagPlacement::Head);
+
 					let ape = read_ape_tag_with_header(data, ape_header, parse_options)?;
 					ape_tag = Some(ape);
 				}
@@ -110,9 +107,9 @@ where
 	#[allow(unused_variables)]
 	let ID3FindResults(id3v1_header, id3v1) = find_id3v1(data, parse_options.read_tags)?;
 
-	if id3v1_header.is_some() {
+	if let Some(id3v1_size) = id3v1_header {
 		id3v1_tag = id3v1;
-		let Some(new_stream_length) = stream_len.checked_sub(128) else {
+		let Some(new_stream_length) = stream_len.checked_sub(id3v1_size) else {
 			err!(SizeMismatch);
 		};
 
@@ -1,9 +1,12 @@
 use crate::ape::constants::INVALID_KEYS;
 use crate::error::{LoftyError, Result};
 use crate::macros::decode_err;
+use crate::picture::Picture;
 use crate::tag::item::ItemValueRef;
 use crate::tag::{ItemValue, TagItem, TagType};
 
+use std::borrow::Cow;
+
 /// Represents an `APE` tag item
 ///
 /// The restrictions for `APE` lie in the key rather than the value,
@@ -19,29 +22,153 @@ pub struct ApeItem {
 impl ApeItem {
 	/// Create an [`ApeItem`]
 	///
+	/// NOTE: The value is not validated, see [`ApeItem::locator`] for validated locators.
+	///
+	/// # Errors
+	///
+	/// See [`ApeItem::validate_key`]
+	pub fn new(key: String, value: ItemValue) -> Result<Self> {
+		Self::validate_key(&key)?;
+
+		Ok(Self {
+			read_only: false,
+			key,
+			value,
+		})
+	}
+
+	/// Create a text [`ApeItem`]
+	///
+	/// Multiple values can be stored by separating them with null terminators.
+	///
+	/// # Errors
+	///
+	/// See [`ApeItem::validate_key`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeItem;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let item = ApeItem::text(String::from("Title"), String::from("Foo title"))?;
+	/// assert_eq!(item.key(), "Title");
+	/// # Ok(()) }
+	/// ```
+	pub fn text(key: String, value: String) -> Result<Self> {
+		Self::new(key, ItemValue::Text(value))
+	}
+
+	/// Create a binary [`ApeItem`]
+	///
+	/// # Errors
+	///
+	/// See [`ApeItem::validate_key`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeItem;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let item = ApeItem::binary(String::from("Foo"), vec![1, 2, 3])?;
+	/// assert_eq!(item.key(), "Foo");
+	/// # Ok(()) }
+	/// ```
+	pub fn binary(key: String, value: Vec<u8>) -> Result<Self> {
+		Self::new(key, ItemValue::Binary(value))
+	}
+
+	/// Create a locator [`ApeItem`]
+	///
+	/// A locator is a link to external information, such as a URL or file path.
+	///
+	/// # Errors
+	///
+	/// * See [`ApeItem::validate_key`]
+	/// * `value` is empty
+	/// * `value` contains characters not allowed in a URI (including null terminators and non-ASCII
+	///   characters, which need to be percent-encoded)
+	/// * `value` contains a '%' that isn't followed by two hexadecimal digits
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeItem;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let item = ApeItem::locator(
+	/// 	String::from("Buy URL"),
+	/// 	String::from("https://example.com/album?id=1"),
+	/// )?;
+	/// assert_eq!(item.key(), "Buy URL");
+	///
+	/// // Spaces need to be percent-encoded
+	/// let with_space = String::from("https://example.com/my album");
+	/// assert!(ApeItem::locator(String::from("Buy URL"), with_space).is_err());
+	/// # Ok(()) }
+	/// ```
+	pub fn locator(key: String, value: String) -> Result<Self> {
+		if value.is_empty() {
+			decode_err!(@BAIL @TAG Ape, "APE tag locator item is empty");
+		}
+
+		let mut chars = value.chars();
+		while let Some(c) = chars.next() {
+			if c == '%' {
+				let (Some(hi), Some(lo)) = (chars.next(), chars.next()) else {
+					decode_err!(@BAIL @TAG Ape, "APE tag locator item contains an incomplete percent-encoding");
+				};
+
+				if !hi.is_ascii_hexdigit() || !lo.is_ascii_hexdigit() {
+					decode_err!(@BAIL @TAG Ape, "APE tag locator item contains an invalid percent-encoding");
+				}
+
+				continue;
+			}
+
+			if !is_uri_char(c) {
+				decode_err!(@BAIL @TAG Ape, "APE tag locator item contains invalid characters");
+			}
+		}
+
+		Self::new(key, ItemValue::Locator(value))
+	}
+
+	/// Verify that `key` is a valid item key
+	///
 	/// # Errors
 	///
 	/// * `key` is illegal ("ID3", "TAG", "OGGS", "MP+")
 	/// * `key` has a bad length (must be 2 to 255, inclusive)
 	/// * `key` contains invalid characters (must be in the range 0x20 to 0x7E, inclusive)
-	pub fn new(key: String, value: ItemValue) -> Result<Self> {
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeItem;
+	///
+	/// assert!(ApeItem::validate_key("Title").is_ok());
+	///
+	/// // Too short
+	/// assert!(ApeItem::validate_key("T").is_err());
+	/// // Reserved
+	/// assert!(ApeItem::validate_key("TAG").is_err());
+	/// ```
+	pub fn validate_key(key: &str) -> Result<()> {
 		if INVALID_KEYS.contains(&&*key.to_uppercase()) {
-			decode_err!(@BAIL Ape, "APE tag item contains an illegal key");
+			decode_err!(@BAIL @TAG Ape, "APE tag item contains an illegal key");
 		}
 
 		if !(2..=255).contains(&key.len()) {
-			decode_err!(@BAIL Ape, "APE tag item key has an invalid length (< 2 || > 255)");
+			decode_err!(@BAIL @TAG Ape, "APE tag item key has an invalid length (< 2 || > 255)");
 		}
 
 		if key.chars().any(|c| !(0x20..=0x7E).contains(&(c as u32))) {
-			decode_err!(@BAIL Ape, "APE tag item key contains invalid characters");
+			decode_err!(@BAIL @TAG Ape, "APE tag item key contains invalid characters");
 		}
 
-		Ok(Self {
-			read_only: false,
-			key,
-			value,
-		})
+		Ok(())
 	}
 
 	/// Returns the item key
@@ -54,8 +181,20 @@ impl ApeItem {
 		&self.value
 	}
 
+	/// Consumes the item, returning its value
+	pub fn into_value(self) -> ItemValue {
+		self.value
+	}
+
+	/// Replace the item value
+	///
+	/// NOTE: Like [`ApeItem::new`], the value is not validated.
+	pub fn set_value(&mut self, value: ItemValue) {
+		self.value = value;
+	}
+
 	// Used internally, has no correctness checks
-	pub(crate) fn text(key: &str, value: String) -> Self {
+	pub(crate) fn text_unchecked(key: &str, value: String) -> Self {
 		Self {
 			read_only: false,
 			key: String::from(key),
@@ -64,6 +203,35 @@ impl ApeItem {
 	}
 }
 
+// The unreserved and reserved characters of RFC 3986, '%' is handled separately
+fn is_uri_char(c: char) -> bool {
+	const URI_PUNCTUATION: &str = "-._~:/?#[]@!$&'()*+,;=";
+
+	c.is_ascii_alphanumeric() || URI_PUNCTUATION.contains(c)
+}
+
+impl TryFrom<&Picture> for ApeItem {
+	type Error = LoftyError;
+
+	/// Create a cover art item from a [`Picture`]
+	///
+	/// The key is determined by the picture's type, see [`PictureType::as_ape_key`](crate::picture::PictureType::as_ape_key).
+	/// For the item's value, see [`Picture::as_ape_bytes`].
+	fn try_from(picture: &Picture) -> std::result::Result<Self, Self::Error> {
+		picture.verify_has_data()?;
+
+		let key = picture
+			.pic_type()
+			.as_ape_key()
+			.map(str::to_string)
+			.ok_or_else(
+				|| decode_err!(@TAG Ape, "Attempted to convert an unsupported picture type"),
+			)?;
+
+		Self::new(key, ItemValue::Binary(picture.as_ape_bytes()))
+	}
+}
+
 impl TryFrom<TagItem> for ApeItem {
 	type Error = LoftyError;
 
@@ -72,7 +240,9 @@ impl TryFrom<TagItem> for ApeItem {
 			value
 				.item_key
 				.map_key(TagType::Ape, false)
-				.ok_or_else(|| decode_err!(Ape, "Attempted to convert an unsupported item key"))?
+				.ok_or_else(
+					|| decode_err!(@TAG Ape, "Attempted to convert an unsupported item key"),
+				)?
 				.to_string(),
 			value.item_value,
 		)
@@ -81,7 +251,7 @@ impl TryFrom<TagItem> for ApeItem {
 
 pub(crate) struct ApeItemRef<'a> {
 	pub read_only: bool,
-	pub key: &'a str,
+	pub key: Cow<'a, str>,
 	pub value: ItemValueRef<'a>,
 }
 
@@ -89,7 +259,7 @@ impl<'a> Into<ApeItemRef<'a>> for &'a ApeItem {
 	fn into(self) -> ApeItemRef<'a> {
 		ApeItemRef {
 			read_only: self.read_only,
-			key: self.key(),
+			key: Cow::Borrowed(self.key()),
 			value: (&self.value).into(),
 		}
 	}
@@ -2,23 +2,39 @@ pub(crate) mod item;
 pub(crate) mod read;
 mod write;
 
+use crate::ape::constants::APE_PREAMBLE;
+use crate::ape::header::ApeHeader;
 use crate::ape::tag::item::{ApeItem, ApeItemRef};
-use crate::config::WriteOptions;
+use crate::config::{ParseOptions, PrettyPrintOptions, WriteOptions};
+use crate::error::ParseDiagnostic;
 use crate::error::{LoftyError, Result};
-use crate::id3::v2::util::pairs::{format_number_pair, set_number, NUMBER_PAIR_KEYS};
+use crate::id3::v2::util::pairs::{
+	format_number_pair, set_number, NUMBER_PAIR_KEYS, NUMBER_PAIR_SEPARATOR,
+};
+use crate::macros::decode_err;
+use crate::picture::{Picture, PictureType, APE_PICTURE_TYPES};
 use crate::tag::item::ItemValueRef;
+use crate::tag::items::Popularimeter;
 use crate::tag::{
 	try_parse_year, Accessor, ItemKey, ItemValue, MergeTag, SplitTag, Tag, TagExt, TagItem, TagType,
 };
-use crate::util::flag_item;
 use crate::util::io::{FileLike, Truncate};
+use crate::util::pretty_print::{self, PrettyPrinter, Value};
+use crate::util::{diagnostics, flag_item};
 
 use std::borrow::Cow;
-use std::io::Write;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{Read, Seek, Write};
 use std::ops::Deref;
 
 use lofty_attr::tag;
 
+// The key used for the disc number pair
+const DISC_KEY: &str = "Disc";
+// A common misspelling of `DISC_KEY`, which was also written by older versions of Lofty
+const LEGACY_DISC_KEY: &str = "Disk";
+
 macro_rules! impl_accessor {
 	($($name:ident => $($key:literal)|+;)+) => {
 		paste::paste! {
@@ -68,19 +84,25 @@ macro_rules! impl_accessor {
 ///
 /// Any [`ApeItem`] with an [`ItemKey`] mapping will have a 1:1 conversion to [`TagItem`].
 ///
+/// Binary items with a cover art key (see [`APE_PICTURE_TYPES`](crate::ape::APE_PICTURE_TYPES)) are
+/// converted to [`Picture`]s, with the [`PictureType`] determined by their key.
+///
 /// ### From `Tag`
 ///
-/// When converting pictures, any of type [`PictureType::Undefined`](crate::picture::PictureType::Undefined) will be discarded.
+/// When converting pictures, any of type [`PictureType::Undefined`] will be discarded. Only one picture of
+/// each type can be stored, so any duplicates are discarded as well.
 /// For items, see [`ApeItem::new`].
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
 #[tag(
 	description = "An `APE` tag",
-	supported_formats(Ape, Mpeg, Mpc, WavPack)
+	supported_formats(Ape, Mpeg, Mpc, Shn, Tta, WavPack)
 )]
 pub struct ApeTag {
 	/// Whether or not to mark the tag as read only
 	pub read_only: bool,
 	pub(super) items: Vec<ApeItem>,
+	// Indices into `items`, sorted by their case-folded keys
+	index: Vec<usize>,
 }
 
 impl ApeTag {
@@ -99,6 +121,70 @@ impl ApeTag {
 		Self::default()
 	}
 
+	/// Parse an `ApeTag` from a reader
+	///
+	/// This accepts exactly what [`TagExt::dump_to`] produces: an APEv2 header, followed by the
+	/// items and a footer. The reader is expected to be positioned at the start of the header
+	/// (the `"APETAGEX"` preamble), and will be positioned after the footer upon return.
+	///
+	/// NOTE: An empty tag is written as nothing at all, so there is nothing to parse in that case.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * The preamble is missing
+	/// * The header or any of the items are invalid
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeTag;
+	/// use lofty::config::{ParseOptions, WriteOptions};
+	/// use lofty::tag::{Accessor, TagExt};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut ape_tag = ApeTag::new();
+	/// ape_tag.set_title(String::from("Foo title"));
+	///
+	/// let mut bytes = Vec::new();
+	/// ape_tag.dump_to(&mut bytes, WriteOptions::default())?;
+	///
+	/// let parsed_tag = ApeTag::parse(&mut Cursor::new(bytes), ParseOptions::new())?;
+	/// assert_eq!(parsed_tag.title().as_deref(), Some("Foo title"));
+	/// # Ok(()) }
+	/// ```
+	pub fn parse<R>(reader: &mut R, parse_options: ParseOptions) -> Result<Self>
+	where
+		R: Read + Seek,
+	{
+		let mut preamble = [0; 8];
+		reader.read_exact(&mut preamble)?;
+
+		if &preamble != APE_PREAMBLE {
+			decode_err!(@BAIL @TAG Ape, "Expected an APE tag header");
+		}
+
+		let header = ApeHeader::parse(reader, false)?;
+		read::read_ape_tag_with_header(reader, header, parse_options)
+	}
+
+	/// Same as [`ApeTag::parse`], also returning any [`ParseDiagnostic`]s
+	///
+	/// # Errors
+	///
+	/// See [`ApeTag::parse`]
+	pub fn parse_with_diagnostics<R>(
+		reader: &mut R,
+		parse_options: ParseOptions,
+	) -> Result<(Self, Vec<ParseDiagnostic>)>
+	where
+		R: Read + Seek,
+	{
+		let (tag, diagnostics) = diagnostics::collect(|| Self::parse(reader, parse_options));
+		Ok((tag?, diagnostics))
+	}
+
 	/// Get an [`ApeItem`] by key
 	///
 	/// NOTE: While `APE` items are supposed to be case-sensitive,
@@ -118,16 +204,23 @@ impl ApeTag {
 	/// assert!(title.is_some());
 	/// ```
 	pub fn get(&self, key: &str) -> Option<&ApeItem> {
-		self.items
-			.iter()
-			.find(|i| i.key().eq_ignore_ascii_case(key))
+		let pos = self.find(key).ok()?;
+		Some(&self.items[self.index[pos]])
 	}
 
 	/// Insert an [`ApeItem`]
 	///
 	/// This will remove any item with the same key prior to insertion
 	pub fn insert(&mut self, value: ApeItem) {
-		self.remove(value.key());
+		let pos = match self.find(value.key()) {
+			Ok(pos) => {
+				self.take_at(pos);
+				pos
+			},
+			Err(pos) => pos,
+		};
+
+		self.index.insert(pos, self.items.len());
 		self.items.push(value);
 	}
 
@@ -155,7 +248,58 @@ impl ApeTag {
 	/// assert!(title.is_none());
 	/// ```
 	pub fn remove(&mut self, key: &str) {
-		self.items.retain(|i| !i.key().eq_ignore_ascii_case(key));
+		let _ = self.take(key);
+	}
+
+	/// Remove an [`ApeItem`] by key, and return it
+	///
+	/// NOTE: Like [`ApeTag::get`], this is not case-sensitive
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ape::ApeTag;
+	/// use lofty::tag::Accessor;
+	///
+	/// let mut ape_tag = ApeTag::new();
+	/// ape_tag.set_title(String::from("Foo title"));
+	///
+	/// let title = ape_tag.take("Title");
+	/// assert!(title.is_some());
+	/// assert!(ape_tag.get("Title").is_none());
+	/// ```
+	pub fn take(&mut self, key: &str) -> Option<ApeItem> {
+		let pos = self.find(key).ok()?;
+		Some(self.take_at(pos))
+	}
+
+	/// Retain only the items matching `f`, keeping their order
+	pub(super) fn retain<F>(&mut self, f: F)
+	where
+		F: FnMut(&ApeItem) -> bool,
+	{
+		self.items.retain(f);
+
+		self.index = (0..self.items.len()).collect();
+		self.index
+			.sort_by(|a, b| cmp_ignore_ascii_case(self.items[*a].key(), self.items[*b].key()));
+	}
+
+	// Binary search the index for `key`, returning its position in the index
+	fn find(&self, key: &str) -> std::result::Result<usize, usize> {
+		self.index
+			.binary_search_by(|idx| cmp_ignore_ascii_case(self.items[*idx].key(), key))
+	}
+
+	fn take_at(&mut self, pos: usize) -> ApeItem {
+		let idx = self.index.remove(pos);
+		for i in &mut self.index {
+			if *i > idx {
+				*i -= 1;
+			}
+		}
+
+		self.items.remove(idx)
 	}
 
 	fn insert_item(&mut self, item: TagItem) {
@@ -176,7 +320,7 @@ impl ApeTag {
 				};
 
 				let value = u8::from(flag).to_string();
-				self.insert(ApeItem::text("Compilation", value));
+				self.insert(ApeItem::text_unchecked("Compilation", value));
 			},
 			_ => {
 				if let Ok(item) = item.try_into() {
@@ -186,22 +330,100 @@ impl ApeTag {
 		};
 	}
 
-	fn split_num_pair(&self, key: &str) -> (Option<u32>, Option<u32>) {
-		if let Some(ApeItem {
-			value: ItemValue::Text(ref text),
-			..
-		}) = self.get(key)
-		{
-			let mut split = text.split('/').flat_map(str::parse::<u32>);
-			return (split.next(), split.next());
+	/// Writes a line-oriented listing of the items to `writer`
+	///
+	/// Binary items that hold a picture (ex. `Cover Art (Front)`) are shown as pictures.
+	///
+	/// See [`PrettyPrintOptions`] for the format.
+	///
+	/// # Errors
+	///
+	/// * Writing to `writer` fails
+	pub fn pretty_print(
+		&self,
+		writer: &mut impl fmt::Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		let pictures = self
+			.items
+			.iter()
+			.map(|item| match (picture_key(item.key()), item.value()) {
+				(Some(picture_key), ItemValue::Binary(value)) => {
+					Picture::from_ape_bytes(picture_key, value).ok()
+				},
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+
+		let mut printer = PrettyPrinter::default();
+		for (item, picture) in self.items.iter().zip(&pictures) {
+			let (ty, value) = match picture {
+				Some(picture) => ("Picture", Value::Picture(picture)),
+				None => pretty_print::item_value(item.value()),
+			};
+
+			printer.push(item.key(), ty, value);
 		}
 
-		(None, None)
+		printer.finish(writer, options)
+	}
+
+	// Rename a `Disk` item to `Disc`, merging the two if both are present
+	//
+	// When merging, the values of the `Disc` item take precedence.
+	pub(crate) fn migrate_legacy_disc_item(&mut self) {
+		let Some(legacy) = self.take(LEGACY_DISC_KEY) else {
+			return;
+		};
+
+		if self.get(DISC_KEY).is_none() {
+			self.insert(ApeItem {
+				key: String::from(DISC_KEY),
+				..legacy
+			});
+
+			diagnostics::report(ParseDiagnostic::LegacyApeDiscItem { merged: false });
+			return;
+		}
+
+		let (number, total) = self.split_num_pair(DISC_KEY);
+		let (legacy_number, legacy_total) = split_num_pair_value(legacy.value());
+		if let Some(value) = format_number_pair(number.or(legacy_number), total.or(legacy_total)) {
+			let read_only = self.get(DISC_KEY).is_some_and(|item| item.read_only);
+			self.insert(ApeItem {
+				read_only,
+				key: String::from(DISC_KEY),
+				value: ItemValue::Text(value),
+			});
+		}
+
+		diagnostics::report(ParseDiagnostic::LegacyApeDiscItem { merged: true });
+	}
+
+	// The disc number pair, falling back to a `Disk` item
+	fn disc_pair(&self) -> (Option<u32>, Option<u32>) {
+		if self.get(DISC_KEY).is_some() {
+			return self.split_num_pair(DISC_KEY);
+		}
+
+		self.split_num_pair(LEGACY_DISC_KEY)
+	}
+
+	fn insert_disc_pair(&mut self, number: Option<u32>, total: Option<u32>) {
+		self.remove(LEGACY_DISC_KEY);
+		self.insert_number_pair(DISC_KEY, number, total);
+	}
+
+	fn split_num_pair(&self, key: &str) -> (Option<u32>, Option<u32>) {
+		match self.get(key) {
+			Some(item) => split_num_pair_value(item.value()),
+			None => (None, None),
+		}
 	}
 
 	fn insert_number_pair(&mut self, key: &'static str, number: Option<u32>, total: Option<u32>) {
 		if let Some(value) = format_number_pair(number, total) {
-			self.insert(ApeItem::text(key, value));
+			self.insert(ApeItem::text_unchecked(key, value));
 		} else {
 			log::warn!("{key} is not set. number: {number:?}, total: {total:?}");
 		}
@@ -260,36 +482,37 @@ impl Accessor for ApeTag {
 		self.remove("Track");
 
 		if let Some(track) = existing_track_number {
-			self.insert(ApeItem::text("Track", track.to_string()));
+			self.insert(ApeItem::text_unchecked("Track", track.to_string()));
 		}
 	}
 
 	fn disk(&self) -> Option<u32> {
-		self.split_num_pair("Disc").0
+		self.disc_pair().0
 	}
 
 	fn set_disk(&mut self, value: u32) {
-		self.insert_number_pair("Disc", Some(value), self.disk_total());
+		self.insert_disc_pair(Some(value), self.disk_total());
 	}
 
 	fn remove_disk(&mut self) {
-		self.remove("Disc");
+		self.remove(DISC_KEY);
+		self.remove(LEGACY_DISC_KEY);
 	}
 
 	fn disk_total(&self) -> Option<u32> {
-		self.split_num_pair("Disc").1
+		self.disc_pair().1
 	}
 
 	fn set_disk_total(&mut self, value: u32) {
-		self.insert_number_pair("Disc", self.disk(), Some(value));
+		self.insert_disc_pair(self.disk(), Some(value));
 	}
 
 	fn remove_disk_total(&mut self) {
-		let existing_track_number = self.track();
-		self.remove("Disc");
+		let existing_disc_number = self.disk();
+		self.remove_disk();
 
-		if let Some(track) = existing_track_number {
-			self.insert(ApeItem::text("Disc", track.to_string()));
+		if let Some(disc) = existing_disc_number {
+			self.insert(ApeItem::text_unchecked(DISC_KEY, disc.to_string()));
 		}
 	}
 
@@ -306,12 +529,35 @@ impl Accessor for ApeTag {
 	}
 
 	fn set_year(&mut self, value: u32) {
-		self.insert(ApeItem::text("Year", value.to_string()));
+		self.insert(ApeItem::text_unchecked("Year", value.to_string()));
 	}
 
 	fn remove_year(&mut self) {
 		self.remove("Year");
 	}
+
+	fn popularimeter(&self) -> Option<Popularimeter> {
+		if let Some(ApeItem {
+			value: ItemValue::Text(ref text),
+			..
+		}) = self.get("Rating")
+		{
+			return Popularimeter::from_text(text);
+		}
+
+		None
+	}
+
+	fn set_popularimeter(&mut self, value: Popularimeter) {
+		self.insert(ApeItem::text_unchecked(
+			"Rating",
+			value.rating().to_string(),
+		));
+	}
+
+	fn remove_popularimeter(&mut self) {
+		self.remove("Rating");
+	}
 }
 
 impl TagExt for ApeTag {
@@ -328,7 +574,7 @@ impl TagExt for ApeTag {
 	}
 
 	fn contains<'a>(&'a self, key: Self::RefKey<'a>) -> bool {
-		self.items.iter().any(|i| i.key().eq_ignore_ascii_case(key))
+		self.find(key).is_ok()
 	}
 
 	fn is_empty(&self) -> bool {
@@ -374,8 +620,24 @@ impl TagExt for ApeTag {
 		.dump_to(writer, write_options)
 	}
 
+	/// Gets the size of the tag, as it would be written by [`ApeTag::dump_to`]
+	///
+	/// This includes both the header and footer.
+	///
+	/// # Errors
+	///
+	/// * [`ErrorKind::TooMuchData`](crate::error::ErrorKind::TooMuchData)
+	fn serialized_len(&self, _write_options: WriteOptions) -> std::result::Result<u64, Self::Err> {
+		ApeTagRef {
+			read_only: self.read_only,
+			items: self.items.iter().map(Into::into),
+		}
+		.serialized_len()
+	}
+
 	fn clear(&mut self) {
 		self.items.clear();
+		self.index.clear();
 	}
 }
 
@@ -406,7 +668,7 @@ impl SplitTag for ApeTag {
 			current_key: ItemKey,
 			total_key: ItemKey,
 		) -> Option<()> {
-			let mut split = content.splitn(2, '/');
+			let mut split = content.splitn(2, NUMBER_PAIR_SEPARATOR);
 			let current = split.next()?.to_string();
 			tag.items
 				.push(TagItem::new(current_key, ItemValue::Text(current)));
@@ -421,8 +683,25 @@ impl SplitTag for ApeTag {
 
 		let mut tag = Tag::new(TagType::Ape);
 
+		self.index.clear();
 		for item in std::mem::take(&mut self.items) {
+			if let (Some(picture_key), ItemValue::Binary(value)) =
+				(picture_key(item.key()), item.value())
+			{
+				match Picture::from_ape_bytes(picture_key, value) {
+					Ok(picture) => {
+						tag.pictures.push(picture);
+						continue;
+					},
+					Err(_) => log::warn!(
+						"APE: Failed to parse the picture in '{}', keeping it as an item",
+						item.key()
+					),
+				}
+			}
+
 			let item_key = ItemKey::from_key(TagType::Ape, item.key());
+			tag.record_original_key(&item_key, item.key());
 
 			// The text pairs need some special treatment
 			match (item_key, item.value()) {
@@ -462,20 +741,33 @@ impl SplitTag for ApeTag {
 impl MergeTag for SplitTagRemainder {
 	type Merged = ApeTag;
 
-	fn merge_tag(self, tag: Tag) -> Self::Merged {
+	fn merge_tag(self, mut tag: Tag) -> Self::Merged {
 		let Self(mut merged) = self;
 
-		for item in tag.items {
+		for item in std::mem::take(&mut tag.items) {
 			merged.insert_item(item);
 		}
 
+		// Respell the keys, see `GlobalOptions::key_style`. This can change more than the casing
+		// with `KeyStyle::Preserve`, so the items need to be reinserted.
+		merged.index.clear();
+		for mut item in std::mem::take(&mut merged.items) {
+			if picture_key(item.key()).is_none() {
+				let key = style_key(&tag, item.key()).into_owned();
+				item.key = key;
+			}
+
+			merged.insert(item);
+		}
+
+		let mut seen_types = Vec::new();
 		for pic in tag.pictures {
-			if let Some(key) = pic.pic_type.as_ape_key() {
-				if let Ok(item) =
-					ApeItem::new(key.to_string(), ItemValue::Binary(pic.as_ape_bytes()))
-				{
-					merged.insert(item)
-				}
+			if picture_item_key(&pic, &mut seen_types).is_none() {
+				continue;
+			}
+
+			if let Ok(item) = ApeItem::try_from(&pic) {
+				merged.insert(item)
 			}
 		}
 
@@ -495,6 +787,65 @@ impl From<Tag> for ApeTag {
 	}
 }
 
+// The standard spelling of `key`, if it is a cover art key
+// Respell an item key according to `GlobalOptions::key_style`
+fn style_key<'a>(tag: &'a Tag, key: &'a str) -> Cow<'a, str> {
+	let item_key = ItemKey::from_key(TagType::Ape, key);
+	item_key.style_key(key, tag.original_key(TagType::Ape, &item_key))
+}
+
+fn picture_key(key: &str) -> Option<&'static str> {
+	APE_PICTURE_TYPES
+		.iter()
+		.copied()
+		.find(|picture_key| picture_key.eq_ignore_ascii_case(key))
+}
+
+// The item key to store `picture` under, if it can be stored at all
+//
+// Only one picture of each type can be stored, so any types already in `seen_types` are discarded.
+fn picture_item_key<'a>(
+	picture: &'a Picture,
+	seen_types: &mut Vec<PictureType>,
+) -> Option<&'a str> {
+	let pic_type = picture.pic_type();
+	let Some(key) = picture.pic_type.as_ape_key() else {
+		log::warn!("APE: Discarding picture of type {pic_type:?}, it has no item key");
+		return None;
+	};
+
+	if picture.verify_has_data().is_err() {
+		log::warn!("APE: Discarding picture for '{key}', its data was never read");
+		return None;
+	}
+
+	if seen_types.contains(&pic_type) {
+		log::warn!("APE: Discarding duplicate picture for '{key}'");
+		return None;
+	}
+
+	seen_types.push(pic_type);
+	Some(key)
+}
+
+fn split_num_pair_value(value: &ItemValue) -> (Option<u32>, Option<u32>) {
+	let ItemValue::Text(text) = value else {
+		return (None, None);
+	};
+
+	let mut split = text
+		.split(NUMBER_PAIR_SEPARATOR)
+		.flat_map(str::parse::<u32>);
+	(split.next(), split.next())
+}
+
+// APE item keys are ASCII, so there's no need for full case folding
+fn cmp_ignore_ascii_case(a: &str, b: &str) -> Ordering {
+	let a = a.bytes().map(|b| b.to_ascii_lowercase());
+	let b = b.bytes().map(|b| b.to_ascii_lowercase());
+	a.cmp(b)
+}
+
 pub(crate) struct ApeTagRef<'a, I>
 where
 	I: Iterator<Item = ApeItemRef<'a>>,
@@ -525,13 +876,17 @@ where
 
 		Ok(())
 	}
+
+	pub(crate) fn serialized_len(&mut self) -> Result<u64> {
+		write::ape_tag_len(self)
+	}
 }
 
 pub(crate) fn tagitems_into_ape(tag: &Tag) -> impl Iterator<Item = ApeItemRef<'_>> {
 	fn create_apeitemref_for_number_pair<'a>(
 		number: Option<&str>,
 		total: Option<&str>,
-		key: &'a str,
+		key: Cow<'a, str>,
 	) -> Option<ApeItemRef<'a>> {
 		format_number_pair(number, total).map(|value| ApeItemRef {
 			read_only: false,
@@ -543,35 +898,103 @@ pub(crate) fn tagitems_into_ape(tag: &Tag) -> impl Iterator<Item = ApeItemRef<'_
 	tag.items()
 		.filter(|item| !NUMBER_PAIR_KEYS.contains(item.key()))
 		.filter_map(|i| {
-			i.key().map_key(TagType::Ape, true).map(|key| ApeItemRef {
-				read_only: false,
-				key,
-				value: (&i.item_value).into(),
-			})
+			tag.styled_key(i.key(), TagType::Ape, true)
+				.map(|key| ApeItemRef {
+					read_only: false,
+					key,
+					value: (&i.item_value).into(),
+				})
 		})
 		.chain(create_apeitemref_for_number_pair(
 			tag.get_string(&ItemKey::TrackNumber),
 			tag.get_string(&ItemKey::TrackTotal),
-			"Track",
+			style_key(tag, "Track"),
 		))
 		.chain(create_apeitemref_for_number_pair(
 			tag.get_string(&ItemKey::DiscNumber),
 			tag.get_string(&ItemKey::DiscTotal),
-			"Disk",
+			style_key(tag, DISC_KEY),
 		))
+		.chain(pictures_into_ape(tag.pictures()))
+}
+
+fn pictures_into_ape(pictures: &[Picture]) -> impl Iterator<Item = ApeItemRef<'_>> {
+	let mut seen_types = Vec::new();
+	pictures.iter().filter_map(move |picture| {
+		picture_item_key(picture, &mut seen_types).map(|key| ApeItemRef {
+			read_only: false,
+			key: Cow::Borrowed(key),
+			value: ItemValueRef::Binary(Cow::Owned(picture.as_ape_bytes())),
+		})
+	})
 }
 
 #[cfg(test)]
 mod tests {
 	use crate::ape::{ApeItem, ApeTag};
-	use crate::config::{ParseOptions, WriteOptions};
+	use crate::config::{ParseOptions, ParsingMode, WriteOptions};
+	use crate::error::ErrorKind;
 	use crate::id3::v2::util::pairs::DEFAULT_NUMBER_IN_PAIR;
 	use crate::prelude::*;
+	use crate::tag::items::Popularimeter;
 	use crate::tag::{ItemValue, Tag, TagItem, TagType};
 
 	use crate::picture::{MimeType, Picture, PictureType};
 	use std::io::Cursor;
 
+	#[test_log::test]
+	fn item_constructors() {
+		let text = ApeItem::text(String::from("Title"), String::from("Foo\0Bar")).unwrap();
+		assert_eq!(text.value(), &ItemValue::Text(String::from("Foo\0Bar")));
+
+		let binary = ApeItem::binary(String::from("Cover Art (Front)"), vec![0, 1, 2]).unwrap();
+		assert_eq!(binary.value(), &ItemValue::Binary(vec![0, 1, 2]));
+
+		for locator in [
+			"https://example.com/album?id=1&lang=en#tracks",
+			"file:///C:/M%C3%BAsica/foo.flac",
+			"mailto:foo@example.com",
+		] {
+			let item = ApeItem::locator(String::from("Buy URL"), String::from(locator)).unwrap();
+			assert_eq!(item.into_value(), ItemValue::Locator(String::from(locator)));
+		}
+
+		// Every constructor validates the key
+		for key in ["T", "TAG", "oggs", "Tïtle", "Foo\0", &"a".repeat(256)] {
+			assert!(ApeItem::validate_key(key).is_err(), "{key:?}");
+			assert!(ApeItem::text(String::from(key), String::from("Foo")).is_err());
+			assert!(ApeItem::binary(String::from(key), Vec::new()).is_err());
+			assert!(
+				ApeItem::locator(String::from(key), String::from("https://example.com")).is_err()
+			);
+		}
+
+		for locator in [
+			"",
+			"https://example.com/my album",
+			"https://example.com/\0https://example.org",
+			"file:///C:/Música/foo.flac",
+			"https://example.com/100%",
+			"https://example.com/%G0",
+			"<https://example.com>",
+		] {
+			assert!(
+				ApeItem::locator(String::from("Buy URL"), String::from(locator)).is_err(),
+				"{locator:?}"
+			);
+		}
+	}
+
+	#[test_log::test]
+	fn item_set_value() {
+		let mut item = ApeItem::text(String::from("Title"), String::from("Foo title")).unwrap();
+		item.set_value(ItemValue::Text(String::from("Bar title")));
+
+		let mut tag = ApeTag::new();
+		tag.insert(item);
+		assert_eq!(tag.title().as_deref(), Some("Bar title"));
+	}
+
 	#[test_log::test]
 	fn parse_ape() {
 		let mut expected_tag = ApeTag::default();
@@ -664,6 +1087,156 @@ mod tests {
 		assert_eq!(parsed_tag, temp_parsed_tag);
 	}
 
+	#[test_log::test]
+	fn case_insensitive_keys() {
+		let mut tag = ApeTag::default();
+		tag.insert(ApeItem::text_unchecked("Title", String::from("Foo title")));
+		tag.insert(ApeItem::text_unchecked(
+			"Artist",
+			String::from("Bar artist"),
+		));
+		tag.insert(ApeItem::text_unchecked("Album", String::from("Baz album")));
+
+		assert!(tag.contains("TITLE"));
+		assert_eq!(
+			tag.get("tItLe").map(ApeItem::value),
+			Some(&ItemValue::Text(String::from("Foo title")))
+		);
+
+		// Inserting a colliding key replaces the existing item, and moves it to the end
+		tag.insert(ApeItem::text_unchecked("TITLE", String::from("Qux title")));
+		assert_eq!(tag.len(), 3);
+
+		let keys = tag.items.iter().map(ApeItem::key).collect::<Vec<_>>();
+		assert_eq!(keys, ["Artist", "Album", "TITLE"]);
+		assert_eq!(
+			tag.get("title").map(ApeItem::value),
+			Some(&ItemValue::Text(String::from("Qux title")))
+		);
+
+		// Removing by any case removes the item, and leaves the others reachable
+		let taken = tag.take("artist").unwrap();
+		assert_eq!(taken.key(), "Artist");
+		assert!(!tag.contains("Artist"));
+		assert!(tag.contains("album"));
+		assert!(tag.contains("Title"));
+
+		tag.remove("ALBUM");
+		assert_eq!(tag.len(), 1);
+		assert!(tag.get("Album").is_none());
+		assert_eq!(tag.get("Title").unwrap().key(), "TITLE");
+
+		tag.clear();
+		assert!(tag.get("Title").is_none());
+	}
+
+	#[test_log::test]
+	fn normalized_items_are_deterministic() {
+		fn tag(items: &[(&str, ItemValue)]) -> ApeTag {
+			let mut tag = ApeTag::default();
+			for (key, value) in items {
+				tag.insert(ApeItem::new(String::from(*key), value.clone()).unwrap());
+			}
+
+			tag
+		}
+
+		fn dump(tag: &ApeTag, write_options: WriteOptions) -> Vec<u8> {
+			let mut bytes = Vec::new();
+			tag.dump_to(&mut bytes, write_options).unwrap();
+			bytes
+		}
+
+		let cover = ItemValue::Binary(vec![1, 2, 3, 4]);
+		let items = [
+			("TITLE", ItemValue::Text(String::from("Foo title"))),
+			("Cover Art (front)", cover.clone()),
+			("year", ItemValue::Text(String::from("1984"))),
+			("Artist", ItemValue::Text(String::from("Bar artist"))),
+			("Track", ItemValue::Text(String::from("1"))),
+			("MUSICBRAINZ_TRACKID", ItemValue::Text(String::from("abc"))),
+		];
+
+		let mut reordered = items.clone();
+		reordered.reverse();
+
+		let first = tag(&items);
+		let second = tag(&reordered);
+
+		// Insertion order is kept by default
+		assert_ne!(
+			dump(&first, WriteOptions::default()),
+			dump(&second, WriteOptions::default())
+		);
+
+		let write_options = WriteOptions::default().normalize_ape_items(true);
+		let normalized = dump(&first, write_options);
+		assert_eq!(normalized, dump(&second, write_options));
+
+		let (Some(parsed), _) = crate::ape::tag::read::read_ape_tag(
+			&mut Cursor::new(normalized),
+			false,
+			ParseOptions::new(),
+		)
+		.unwrap() else {
+			unreachable!()
+		};
+
+		let keys = parsed.items.iter().map(ApeItem::key).collect::<Vec<_>>();
+		assert_eq!(
+			keys,
+			[
+				"Track",
+				"Year",
+				"Artist",
+				"MUSICBRAINZ_TRACKID",
+				"Title",
+				"Cover Art (Front)",
+			]
+		);
+	}
+
+	#[test_log::test]
+	fn item_iter_early_exit() {
+		use crate::ape::{ApeHeader, ApeItemIter};
+		use crate::tag::utils::test_utils::{read_path, CountingReader};
+
+		use std::io::Read;
+
+		let tag_bytes = read_path("tests/tags/assets/test.apev2");
+		let mut reader = CountingReader::new(Cursor::new(&tag_bytes));
+
+		let mut preamble = [0; 8];
+		reader.read_exact(&mut preamble).unwrap();
+		let header = ApeHeader::parse(&mut reader, false).unwrap();
+		let header_len = reader.bytes_read;
+		let first_item_pos = reader.inner.position();
+
+		let mut items = ApeItemIter::new(&mut reader, header, ParseOptions::new());
+		let first = items.next().unwrap().unwrap();
+
+		// Only the header and first item should have been read
+		assert!(reader.bytes_read > header_len);
+		assert!(reader.bytes_read < u64::from(header.size()));
+
+		let (Some(parsed_tag), _) = crate::ape::tag::read::read_ape_tag(
+			&mut Cursor::new(&tag_bytes),
+			false,
+			ParseOptions::new(),
+		)
+		.unwrap() else {
+			unreachable!();
+		};
+		assert_eq!(parsed_tag.items.first(), Some(&first));
+
+		let mut reader = Cursor::new(&tag_bytes);
+		reader.set_position(first_item_pos);
+		let all_items = ApeItemIter::new(reader, header, ParseOptions::new())
+			.collect::<crate::error::Result<Vec<_>>>()
+			.unwrap();
+		assert_eq!(all_items, parsed_tag.items);
+	}
+
 	#[test_log::test]
 	fn ape_to_tag() {
 		let tag_bytes = crate::tag::utils::test_utils::read_path("tests/tags/assets/test.apev2");
@@ -911,6 +1484,84 @@ mod tests {
 		assert_eq!(tag.disk_total().unwrap(), disk_total);
 	}
 
+	#[test_log::test]
+	fn disk_number_and_disk_total_tag_dump_to_ape() {
+		let mut tag = Tag::new(TagType::Ape);
+		tag.push(TagItem::new(
+			ItemKey::DiscNumber,
+			ItemValue::Text(String::from("1")),
+		));
+		tag.push(TagItem::new(
+			ItemKey::DiscTotal,
+			ItemValue::Text(String::from("2")),
+		));
+
+		let mut bytes = Vec::new();
+		tag.dump_to(&mut bytes, WriteOptions::default()).unwrap();
+
+		let ape = ApeTag::parse(&mut Cursor::new(bytes), ParseOptions::new()).unwrap();
+		assert_eq!(ape.len(), 1);
+		assert_eq!(ape.items[0].key(), "Disc");
+		assert_eq!(ape.disk(), Some(1));
+		assert_eq!(ape.disk_total(), Some(2));
+	}
+
+	#[test_log::test]
+	fn legacy_disk_item() {
+		use crate::error::ParseDiagnostic;
+		use crate::util::diagnostics;
+
+		fn parse(items: &[(&str, &str)]) -> (ApeTag, Vec<ParseDiagnostic>) {
+			let mut ape = ApeTag::new();
+			for (key, value) in items {
+				ape.insert(ApeItem::text(String::from(*key), String::from(*value)).unwrap());
+			}
+
+			let mut bytes = Vec::new();
+			ape.dump_to(&mut bytes, WriteOptions::default()).unwrap();
+
+			let (ape, diagnostics) = diagnostics::collect(|| {
+				ApeTag::parse(&mut Cursor::new(bytes), ParseOptions::new())
+			});
+			(ape.unwrap(), diagnostics)
+		}
+
+		// A lone `Disk` item is renamed
+		let (ape, diagnostics) = parse(&[("DISK", "1/3")]);
+		assert_eq!(ape.len(), 1);
+		assert_eq!(ape.get("Disc").unwrap().key(), "Disc");
+		assert_eq!(ape.disk(), Some(1));
+		assert_eq!(ape.disk_total(), Some(3));
+		assert_eq!(
+			diagnostics,
+			[ParseDiagnostic::LegacyApeDiscItem { merged: false }]
+		);
+
+		// With both present, `Disc` takes precedence, with missing values taken from `Disk`
+		let (ape, diagnostics) = parse(&[("Disk", "1/3"), ("Disc", "2")]);
+		assert_eq!(ape.len(), 1);
+		assert!(ape.get("Disk").is_none());
+		assert_eq!(ape.disk(), Some(2));
+		assert_eq!(ape.disk_total(), Some(3));
+		assert_eq!(
+			diagnostics,
+			[ParseDiagnostic::LegacyApeDiscItem { merged: true }]
+		);
+
+		// Setting the disc number never leaves a `Disk` item behind
+		let mut ape = ApeTag::new();
+		ape.insert(ApeItem::text(String::from("Disk"), String::from("1/3")).unwrap());
+		assert_eq!(ape.disk(), Some(1));
+
+		ape.set_disk(2);
+		assert_eq!(ape.len(), 1);
+		assert_eq!(ape.get("Disc").unwrap().value().text(), Some("2/3"));
+
+		ape.remove_disk_total();
+		assert_eq!(ape.disk(), Some(2));
+		assert_eq!(ape.disk_total(), None);
+	}
+
 	#[test_log::test]
 	fn skip_reading_cover_art() {
 		let p = Picture::new_unchecked(
@@ -940,4 +1591,227 @@ mod tests {
 
 		assert_eq!(ape.len(), 1);
 	}
+
+	#[test_log::test]
+	fn size_limits() {
+		let mut tag = ApeTag::default();
+		tag.set_artist(String::from("Foo artist"));
+		tag.set_title("A".repeat(100));
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::new()).unwrap();
+
+		let read = |parse_options| {
+			crate::ape::tag::read::read_ape_tag(&mut Cursor::new(&writer), false, parse_options)
+		};
+
+		// Items exceeding the limit are skipped, unless in strict mode
+		let item_limit = || ParseOptions::new().max_item_size(64);
+		let err = read(item_limit().parsing_mode(ParsingMode::Strict)).unwrap_err();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::TooLarge {
+				limit: 64,
+				requested: 100
+			}
+		));
+
+		let (Some(ape), _) = read(item_limit()).unwrap() else {
+			unreachable!()
+		};
+		assert_eq!(ape.len(), 1);
+		assert_eq!(ape.artist().as_deref(), Some("Foo artist"));
+
+		// The same goes for the entire tag
+		let tag_limit = || ParseOptions::new().max_tag_size(16);
+		let err = read(tag_limit().parsing_mode(ParsingMode::Strict)).unwrap_err();
+		assert!(matches!(err.kind(), ErrorKind::TooLarge { limit: 16, .. }));
+
+		let (Some(ape), _) = read(tag_limit()).unwrap() else {
+			unreachable!()
+		};
+		assert!(ape.is_empty());
+	}
+
+	fn test_picture(pic_type: PictureType, marker: u8) -> Picture {
+		Picture::new_unchecked(
+			pic_type,
+			Some(MimeType::Png),
+			None,
+			vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, marker],
+		)
+	}
+
+	#[test_log::test]
+	fn pictures_round_trip() {
+		let pictures = [
+			test_picture(PictureType::CoverFront, 1),
+			test_picture(PictureType::CoverBack, 2),
+			test_picture(PictureType::Media, 3),
+		];
+
+		let mut ape = ApeTag::new();
+		ape.set_artist(String::from("Foo artist"));
+		for picture in &pictures {
+			ape.insert(ApeItem::try_from(picture).unwrap());
+		}
+
+		let tag: Tag = ape.clone().into();
+		assert_eq!(tag.item_count(), 1);
+		assert_eq!(tag.picture_count(), 3);
+		for (picture, expected) in tag.pictures().iter().zip(&pictures) {
+			assert_eq!(picture.pic_type(), expected.pic_type());
+			assert_eq!(picture.mime_type(), expected.mime_type());
+			assert_eq!(picture.data(), expected.data());
+		}
+
+		let ape_re_converted: ApeTag = tag.clone().into();
+		for key in ["Cover Art (Front)", "Cover Art (Back)", "Cover Art (Media)"] {
+			assert_eq!(ape_re_converted.get(key), ape.get(key));
+		}
+
+		// Writing the `Tag` directly should produce the same pictures
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::new()).unwrap();
+
+		let (Some(ape_re_read), _) = crate::ape::tag::read::read_ape_tag(
+			&mut Cursor::new(writer),
+			false,
+			ParseOptions::new(),
+		)
+		.unwrap() else {
+			unreachable!()
+		};
+
+		assert_eq!(ape_re_read.len(), 4);
+		let re_read_tag: Tag = ape_re_read.into();
+		assert_eq!(re_read_tag.pictures(), tag.pictures());
+	}
+
+	#[test_log::test]
+	fn picture_keys_case_insensitive() {
+		let picture = test_picture(PictureType::CoverBack, 1);
+
+		let mut ape = ApeTag::new();
+		ape.insert(
+			ApeItem::binary(String::from("cover art (back)"), picture.as_ape_bytes()).unwrap(),
+		);
+
+		let tag: Tag = ape.into();
+		assert_eq!(tag.item_count(), 0);
+		assert_eq!(tag.pictures()[0].pic_type(), PictureType::CoverBack);
+	}
+
+	#[test_log::test]
+	fn invalid_picture_items_kept() {
+		let mut ape = ApeTag::new();
+		ape.insert(ApeItem::binary(String::from("Cover Art (Back)"), vec![1, 2, 3]).unwrap());
+
+		let tag: Tag = ape.into();
+		assert_eq!(tag.picture_count(), 0);
+		assert_eq!(tag.item_count(), 1);
+	}
+
+	#[test_log::test]
+	fn unsupported_pictures_discarded() {
+		let mut tag = Tag::new(TagType::Ape);
+		tag.push_picture(test_picture(PictureType::Undefined(50), 1));
+		tag.push_picture(test_picture(PictureType::CoverBack, 2));
+		tag.push_picture(test_picture(PictureType::CoverBack, 3));
+
+		let ape: ApeTag = tag.clone().into();
+		assert_eq!(ape.len(), 1);
+		assert_eq!(
+			ape.get("Cover Art (Back)"),
+			Some(&ApeItem::try_from(&test_picture(PictureType::CoverBack, 2)).unwrap())
+		);
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::new()).unwrap();
+
+		let (Some(ape_re_read), _) = crate::ape::tag::read::read_ape_tag(
+			&mut Cursor::new(writer),
+			false,
+			ParseOptions::new(),
+		)
+		.unwrap() else {
+			unreachable!()
+		};
+
+		assert_eq!(ape_re_read, ape);
+	}
+
+	#[test_log::test]
+	fn key_style() {
+		use crate::config::{apply_global_options, GlobalOptions, KeyStyle};
+
+		fn contains(bytes: &[u8], key: &str) -> bool {
+			let needle = [key.as_bytes(), b"\0"].concat();
+			bytes.windows(needle.len()).any(|w| w == needle)
+		}
+
+		let mut ape = ApeTag::new();
+		for (key, value) in [
+			("ALBUM ARTIST", "Foo artist"),
+			("track", "1"),
+			("replaygain_track_gain", "-1.00 dB"),
+		] {
+			ape.insert(ApeItem::text(String::from(key), String::from(value)).unwrap());
+		}
+		let tag = Tag::from(ape);
+
+		for (key_style, keys) in [
+			(
+				KeyStyle::Format,
+				["Album Artist", "Track", "REPLAYGAIN_TRACK_GAIN"],
+			),
+			(
+				KeyStyle::Uppercase,
+				["ALBUM ARTIST", "TRACK", "REPLAYGAIN_TRACK_GAIN"],
+			),
+			(
+				KeyStyle::TitleCase,
+				["Album Artist", "Track", "Replaygain_Track_Gain"],
+			),
+			(
+				KeyStyle::Preserve,
+				["ALBUM ARTIST", "track", "replaygain_track_gain"],
+			),
+		] {
+			apply_global_options(GlobalOptions::new().key_style(key_style));
+
+			let mut written = Vec::new();
+			tag.dump_to(&mut written, WriteOptions::default()).unwrap();
+
+			let mut merged = Vec::new();
+			ApeTag::from(tag.clone())
+				.dump_to(&mut merged, WriteOptions::default())
+				.unwrap();
+
+			for bytes in [written, merged] {
+				for key in keys {
+					assert!(contains(&bytes, key), "{key_style:?}: {key}");
+				}
+			}
+		}
+	}
+
+	#[test_log::test]
+	fn popularimeter_accessor() {
+		let mut tag = ApeTag::default();
+		assert_eq!(tag.popularimeter(), None);
+
+		tag.set_popularimeter(Popularimeter::new(String::from("foo@bar.com"), 60, 5));
+		assert_eq!(
+			tag.get("Rating").map(ApeItem::value),
+			Some(&ItemValue::Text(String::from("60")))
+		);
+		assert_eq!(
+			tag.popularimeter(),
+			Some(Popularimeter::new(String::new(), 60, 0))
+		);
+
+		tag.remove_popularimeter();
+		assert_eq!(tag.popularimeter(), None);
+	}
 }
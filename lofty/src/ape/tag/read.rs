@@ -1,40 +1,106 @@
 use super::item::ApeItem;
 use super::ApeTag;
 use crate::ape::constants::{APE_PREAMBLE, INVALID_KEYS};
-use crate::ape::header::{self, ApeHeader};
+use crate::ape::header::ApeHeader;
 use crate::ape::APE_PICTURE_TYPES;
-use crate::config::ParseOptions;
-use crate::error::Result;
+use crate::config::{ParseOptions, PictureDataPolicy};
+use crate::error::{ParseDiagnostic, Result};
 use crate::macros::{decode_err, err, try_vec};
-use crate::tag::ItemValue;
+use crate::picture::{ape_filename_description, Picture, PictureType};
+use crate::tag::{ItemValue, TagLocation, TagPlacement, TagType};
 use crate::util::text::utf8_decode;
+use crate::util::{diagnostics, picture_sink, tag_locations};
 
-use std::io::{Read, Seek, SeekFrom};
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom, Take};
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
-pub(crate) fn read_ape_tag_with_header<R>(
-	data: &mut R,
-	header: ApeHeader,
+/// An iterator over the items of an `APE` tag
+///
+/// This reads a single [`ApeItem`] at a time, without building an [`ApeTag`]. This is useful
+/// for when only a few items are of interest, as iteration can stop at any point without reading
+/// the remainder of the tag.
+///
+/// Items that are skipped during parsing (e.g. items with invalid keys, or cover art when
+/// [`ParseOptions::read_cover_art`] is disabled) are not yielded.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::ape::{ApeHeader, ApeItemIter, ApeTag};
+/// use lofty::config::{ParseOptions, WriteOptions};
+/// use lofty::prelude::*;
+/// use std::io::{Cursor, Read};
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let mut tag = ApeTag::new();
+/// # tag.set_title(String::from("Foo title"));
+/// # tag.set_artist(String::from("Bar artist"));
+/// # let mut tag_bytes = Vec::new();
+/// # tag.dump_to(&mut tag_bytes, WriteOptions::default())?;
+/// let mut reader = Cursor::new(tag_bytes);
+///
+/// let mut preamble = [0; 8];
+/// reader.read_exact(&mut preamble)?;
+///
+/// let header = ApeHeader::parse(&mut reader, false)?;
+/// let mut items = ApeItemIter::new(&mut reader, header, ParseOptions::new());
+///
+/// // Stop as soon as we find the artist
+/// let artist = items.find_map(|item| match item {
+/// 	Ok(item) if item.key() == "Artist" => Some(item),
+/// 	_ => None,
+/// });
+///
+/// assert!(artist.is_some());
+/// # Ok(()) }
+/// ```
+pub struct ApeItemIter<R> {
+	reader: R,
 	parse_options: ParseOptions,
-) -> Result<ApeTag>
+	remaining_size: u32,
+	remaining_items: u32,
+	// The tag size is verified on the first call to `next()`, as `new()` cannot fail
+	unverified_tag_size: Option<u32>,
+	skip_values: bool,
+}
+
+impl<R> ApeItemIter<R>
 where
 	R: Read + Seek,
 {
-	let mut tag = ApeTag::default();
-	let mut remaining_size = header.size;
-
-	for _ in 0..header.item_count {
-		if remaining_size < 11 {
-			break;
+	/// Create a new [`ApeItemIter`]
+	///
+	/// This expects the reader to be positioned at the first item, as it will be after [`ApeHeader::parse`].
+	pub fn new(reader: R, header: ApeHeader, parse_options: ParseOptions) -> Self {
+		Self {
+			reader,
+			parse_options,
+			remaining_size: header.size,
+			remaining_items: header.item_count,
+			unverified_tag_size: Some(header.size),
+			skip_values: false,
 		}
+	}
+
+	/// Extract the reader, discarding the [`ApeItemIter`]
+	///
+	/// NOTE: The reader will be positioned wherever iteration stopped, which is not necessarily the end of the tag.
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+
+	fn read_item(&mut self) -> Result<Option<ApeItem>> {
+		let data = &mut self.reader;
+		let offset = data.stream_position()?;
 
 		let value_size = data.read_u32::<LittleEndian>()?;
-		if value_size > remaining_size {
+		if value_size > self.remaining_size {
 			err!(SizeMismatch);
 		}
 
-		remaining_size -= 4;
+		self.remaining_size -= 4;
 		let flags = data.read_u32::<LittleEndian>()?;
 
 		let mut key = Vec::new();
@@ -45,16 +111,20 @@ where
 			key_char = data.read_u8()?;
 		}
 
-		let key = utf8_decode(key)
-			.map_err(|_| decode_err!(Ape, "APE tag item contains a non UTF-8 key"))?;
+		let key = utf8_decode(key).map_err(
+			|e| decode_err!(@TAG Ape, "APE tag item contains a non UTF-8 key", source: e),
+		)?;
 
 		if INVALID_KEYS.contains(&&*key.to_uppercase()) {
-			decode_err!(@BAIL Ape, "APE tag item contains an illegal key");
+			decode_err!(@BAIL @TAG Ape, "APE tag item contains an illegal key");
 		}
 
-		if APE_PICTURE_TYPES.contains(&&*key) && !parse_options.read_cover_art {
+		if (APE_PICTURE_TYPES.contains(&&*key) && !self.parse_options.read_cover_art)
+			|| self.skip_values
+			|| !self.parse_options.verify_item_size(u64::from(value_size))?
+		{
 			data.seek(SeekFrom::Current(i64::from(value_size)))?;
-			continue;
+			return Ok(None);
 		}
 
 		let read_only = (flags & 1) == 1;
@@ -62,22 +132,37 @@ where
 
 		if value_size == 0 || key.len() < 2 || key.len() > 255 {
 			log::warn!("APE: Encountered invalid item key '{}'", key);
+			diagnostics::report(ParseDiagnostic::InvalidApeItemKey { key, offset });
 			data.seek(SeekFrom::Current(i64::from(value_size)))?;
-			continue;
+			return Ok(None);
+		}
+
+		// Items can't hold a picture without its data, so they are only handed to the picture sink
+		if APE_PICTURE_TYPES.contains(&&*key)
+			&& self.parse_options.picture_data_policy == PictureDataPolicy::Skip
+		{
+			let end = data.stream_position()? + u64::from(value_size);
+			skip_picture(&mut data.by_ref().take(u64::from(value_size)), &key)?;
+			data.seek(SeekFrom::Start(end))?;
+			return Ok(None);
 		}
 
 		let mut value = try_vec![0; value_size as usize];
 		data.read_exact(&mut value)?;
 
 		let parsed_value = match item_type {
-			0 => ItemValue::Text(utf8_decode(value).map_err(|_| {
-				decode_err!(Ape, "Failed to convert text item into a UTF-8 string")
-			})?),
+			0 => ItemValue::Text(utf8_decode(value).map_err(
+				|e| decode_err!(@TAG Ape, "Failed to convert text item into a UTF-8 string", source: e),
+			)?),
 			1 => ItemValue::Binary(value),
-			2 => ItemValue::Locator(utf8_decode(value).map_err(|_| {
-				decode_err!(Ape, "Failed to convert locator item into a UTF-8 string")
+			2 => ItemValue::Locator(utf8_decode(value).map_err(|e| {
+				decode_err!(
+					@TAG Ape,
+					"Failed to convert locator item into a UTF-8 string",
+					source: e
+				)
 			})?),
-			_ => decode_err!(@BAIL Ape, "APE tag item contains an invalid item type"),
+			_ => decode_err!(@BAIL @TAG Ape, "APE tag item contains an invalid item type"),
 		};
 
 		let mut item = ApeItem::new(key, parsed_value)?;
@@ -86,8 +171,95 @@ where
 			item.read_only = true;
 		}
 
-		tag.insert(item);
+		Ok(Some(item))
+	}
+}
+
+impl<R> Iterator for ApeItemIter<R>
+where
+	R: Read + Seek,
+{
+	type Item = Result<ApeItem>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(tag_size) = self.unverified_tag_size.take() {
+			match self.parse_options.verify_tag_size(u64::from(tag_size)) {
+				// Items still need to be walked, to keep the reader positioned correctly
+				Ok(allowed) => self.skip_values = !allowed,
+				Err(e) => {
+					self.remaining_items = 0;
+					return Some(Err(e));
+				},
+			}
+		}
+
+		while self.remaining_items > 0 {
+			if self.remaining_size < 11 {
+				break;
+			}
+
+			self.remaining_items -= 1;
+			match self.read_item() {
+				Ok(Some(item)) => return Some(Ok(item)),
+				Ok(None) => {},
+				Err(e) => {
+					self.remaining_items = 0;
+					return Some(Err(e));
+				},
+			}
+		}
+
+		self.remaining_items = 0;
+		None
 	}
+}
+
+fn skip_picture<R>(value: &mut Take<R>, key: &str) -> Result<()>
+where
+	R: Read,
+{
+	// The picture data is prefixed with its (null terminated) filename
+	let mut filename = Vec::new();
+	loop {
+		match value.read_u8() {
+			Ok(0) => break,
+			Ok(b) => filename.push(b),
+			Err(_) => {
+				log::warn!("APE: Cover art item is missing a filename, discarding");
+				return Ok(());
+			},
+		}
+	}
+
+	let mut identifier = Vec::with_capacity(8);
+	value.by_ref().take(8).read_to_end(&mut identifier)?;
+
+	let picture = Picture {
+		pic_type: PictureType::from_ape_key(key),
+		mime_type: Picture::mimetype_from_bin(&identifier).ok(),
+		description: ape_filename_description(&filename),
+		data: Cow::Owned(Vec::new()),
+		skipped_data_len: Some(identifier.len() as u64 + value.limit()),
+	};
+
+	picture_sink::consume(&picture, &mut (&identifier[..]).chain(value))
+}
+
+pub(crate) fn read_ape_tag_with_header<R>(
+	data: &mut R,
+	header: ApeHeader,
+	parse_options: ParseOptions,
+) -> Result<ApeTag>
+where
+	R: Read + Seek,
+{
+	let mut tag = ApeTag::default();
+
+	for item in ApeItemIter::new(&mut *data, header, parse_options) {
+		tag.insert(item?);
+	}
+
+	tag.migrate_legacy_disc_item();
 
 	// Skip over footer
 	data.seek(SeekFrom::Current(32))?;
@@ -95,6 +267,22 @@ where
 	Ok(tag)
 }
 
+/// Record the location of an `APE` tag, with its first item at `items_start`
+pub(crate) fn record_ape_location(header: ApeHeader, items_start: u64, placement: TagPlacement) {
+	let offset = if header.has_header {
+		items_start.saturating_sub(32)
+	} else {
+		items_start
+	};
+
+	// The footer is always expected, see `read_ape_tag_with_header`
+	tag_locations::record(
+		TagLocation::new(TagType::Ape, offset, u64::from(header.size), placement)
+			.with_header(header.has_header)
+			.with_footer(true),
+	);
+}
+
 pub(crate) fn read_ape_tag<R: Read + Seek>(
 	reader: &mut R,
 	footer: bool,
@@ -105,8 +293,15 @@ pub(crate) fn read_ape_tag<R: Read + Seek>(
 
 	let mut ape_tag = None;
 	if &ape_preamble == APE_PREAMBLE {
-		let ape_header = header::read_ape_header(reader, footer)?;
+		let ape_header = ApeHeader::parse(reader, footer)?;
 		if parse_options.read_tags {
+			let placement = if footer {
+				TagPlacement::Tail
+			} else {
+				TagPlacement::Head
+			};
+			record_ape_location(ape_header, reader.stream_position()?, placement);
+
 			ape_tag = Some(read_ape_tag_with_header(reader, ape_header, parse_options)?);
 		}
 
@@ -1,15 +1,18 @@
 use super::item::ApeItemRef;
 use super::ApeTagRef;
-use crate::ape::constants::APE_PREAMBLE;
+use crate::ape::constants::{APE_PREAMBLE, STANDARD_KEYS};
 use crate::ape::tag::read;
 use crate::config::{ParseOptions, WriteOptions};
 use crate::error::{LoftyError, Result};
 use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, FindId3v2Config};
 use crate::macros::{decode_err, err};
+use crate::picture::APE_PICTURE_TYPES;
 use crate::probe::Probe;
 use crate::tag::item::ItemValueRef;
 use crate::util::io::{FileLike, Truncate};
 
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::io::{Cursor, Seek, SeekFrom, Write};
 
 use byteorder::{LittleEndian, WriteBytesExt};
@@ -53,7 +56,7 @@ where
 		(Some(mut existing_tag), Some(header)) => {
 			if write_options.respect_read_only {
 				// Only keep metadata around that's marked read only
-				existing_tag.items.retain(|i| i.read_only);
+				existing_tag.retain(|i| i.read_only);
 
 				if !existing_tag.items.is_empty() {
 					read_only = Some(existing_tag)
@@ -86,12 +89,14 @@ where
 		read::read_ape_tag(file, true, ParseOptions::new())?
 	{
 		if write_options.respect_read_only {
-			existing_tag.items.retain(|i| i.read_only);
+			existing_tag.retain(|i| i.read_only);
 
 			if !existing_tag.items.is_empty() {
 				read_only = match read_only {
 					Some(mut read_only) => {
-						read_only.items.extend(existing_tag.items);
+						for item in existing_tag {
+							read_only.insert(item);
+						}
 						Some(read_only)
 					},
 					None => Some(existing_tag),
@@ -153,14 +158,17 @@ where
 	I: Iterator<Item = ApeItemRef<'a>>,
 	R: Iterator<Item = ApeItemRef<'b>>,
 {
-	let items = &mut tag.items;
-	let mut peek = items.peekable();
+	let mut items = tag.items.by_ref().collect::<Vec<_>>();
 
 	// Unnecessary to write anything if there's no metadata
-	if peek.peek().is_none() {
+	if items.is_empty() {
 		return Ok(Vec::<u8>::new());
 	}
 
+	if write_options.normalize_ape_items {
+		normalize_items(&mut items);
+	}
+
 	if read_only.next().is_some() && write_options.respect_read_only {
 		// TODO: Implement retaining read only items
 		log::warn!("Retaining read only items is not supported yet");
@@ -171,12 +179,12 @@ where
 
 	let mut item_count = 0_u32;
 
-	for item in peek {
+	for item in items {
 		let (mut flags, value) = match item.value {
-			ItemValueRef::Binary(value) => {
+			ItemValueRef::Binary(ref value) => {
 				tag_write.write_u32::<LittleEndian>(value.len() as u32)?;
 
-				(1_u32 << 1, value)
+				(1_u32 << 1, &**value)
 			},
 			ItemValueRef::Text(ref value) => {
 				tag_write.write_u32::<LittleEndian>(value.len() as u32)?;
@@ -257,3 +265,93 @@ where
 
 	Ok(tag_write)
 }
+
+/// Get the size of the tag that [`create_ape_tag`] would produce, without keeping it in memory
+///
+/// Normalizing the items only changes the casing of the keys and their order, so it doesn't affect
+/// the size.
+pub(super) fn ape_tag_len<'a, I>(tag: &mut ApeTagRef<'a, I>) -> Result<u64>
+where
+	I: Iterator<Item = ApeItemRef<'a>>,
+{
+	let mut size = 0_u64;
+	let mut has_items = false;
+
+	for item in tag.items.by_ref() {
+		let value_len = match item.value {
+			ItemValueRef::Binary(ref value) => value.len(),
+			ItemValueRef::Text(ref value) => value.len(),
+			ItemValueRef::Locator(value) => value.len(),
+		};
+
+		// Value size (4), Flags (4), Key, Null terminator (1), Value
+		size += 8 + item.key.len() as u64 + 1 + value_len as u64;
+		has_items = true;
+	}
+
+	// Unnecessary to write anything if there's no metadata
+	if !has_items {
+		return Ok(0);
+	}
+
+	if size + 32 > u64::from(u32::MAX) {
+		err!(TooMuchData);
+	}
+
+	// Header (32), Footer (32)
+	Ok(size + 64)
+}
+
+/// Sort `items` into a canonical order, and normalize the casing of standard keys
+///
+/// See [`WriteOptions::normalize_ape_items`].
+fn normalize_items(items: &mut [ApeItemRef<'_>]) {
+	for item in items.iter_mut() {
+		if let Some(standard_key) = STANDARD_KEYS
+			.iter()
+			.chain(APE_PICTURE_TYPES.iter())
+			.find(|key| key.eq_ignore_ascii_case(&item.key))
+		{
+			item.key = Cow::Borrowed(standard_key);
+		}
+	}
+
+	items.sort_by(|a, b| {
+		sort_group(a)
+			.cmp(&sort_group(b))
+			.then_with(|| cmp_ignore_ascii_case(&a.key, &b.key))
+			.then_with(|| a.key.cmp(&b.key))
+			.then_with(|| value_bytes(a).cmp(value_bytes(b)))
+	});
+}
+
+// Track, Disc, and Year are tiny, so they go first. Binary items are usually large, so they go last.
+fn sort_group(item: &ApeItemRef<'_>) -> u8 {
+	const SMALL_ITEMS: [&str; 3] = ["Track", "Disc", "Year"];
+
+	if let Some(pos) = SMALL_ITEMS
+		.iter()
+		.position(|key| key.eq_ignore_ascii_case(&item.key))
+	{
+		return pos as u8;
+	}
+
+	match item.value {
+		ItemValueRef::Binary(_) => SMALL_ITEMS.len() as u8 + 1,
+		_ => SMALL_ITEMS.len() as u8,
+	}
+}
+
+fn cmp_ignore_ascii_case(a: &str, b: &str) -> Ordering {
+	a.bytes()
+		.map(|b| b.to_ascii_lowercase())
+		.cmp(b.bytes().map(|b| b.to_ascii_lowercase()))
+}
+
+fn value_bytes<'a>(item: &'a ApeItemRef<'_>) -> &'a [u8] {
+	match &item.value {
+		ItemValueRef::Text(text) => text.as_bytes(),
+		ItemValueRef::Locator(locator) => locator.as_bytes(),
+		ItemValueRef::Binary(binary) => binary,
+	}
+}
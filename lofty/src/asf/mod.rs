@@ -0,0 +1,28 @@
+//! ASF (WMA) specific items
+//!
+//! ## File notes
+//!
+//! * See [`AsfFile`]
+pub(crate) mod object;
+mod properties;
+mod read;
+pub(crate) mod tag;
+
+use lofty_attr::LoftyFile;
+
+// Exports
+pub use properties::{AsfCodec, AsfProperties};
+pub use tag::{AsfAttributeValue, AsfTag};
+
+/// An ASF file, such as a Windows Media Audio (WMA) file
+///
+/// NOTE: The tag is currently **read only**.
+#[derive(LoftyFile, Default)]
+#[lofty(read_fn = "read::read_from")]
+pub struct AsfFile {
+	/// The ASF attributes
+	#[lofty(tag_type = "Asf")]
+	pub(crate) asf_tag: Option<AsfTag>,
+	/// The file's audio properties
+	pub(crate) properties: AsfProperties,
+}
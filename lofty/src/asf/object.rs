@@ -0,0 +1,153 @@
+use crate::error::Result;
+use crate::macros::decode_err;
+
+use std::io::Read;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+/// The size of an object header (GUID + size)
+pub(crate) const OBJECT_HEADER_SIZE: u64 = 24;
+
+/// A GUID, as stored in an ASF file
+///
+/// The first three fields are stored as little endian, so the bytes don't match the order
+/// of the usual string representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Guid([u8; 16]);
+
+impl Guid {
+	pub(crate) const HEADER: Self = Self::new(
+		0x75B2_2630,
+		0x668E,
+		0x11CF,
+		[0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C],
+	);
+	pub(crate) const FILE_PROPERTIES: Self = Self::new(
+		0x8CAB_DCA1,
+		0xA947,
+		0x11CF,
+		[0x8E, 0xE4, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65],
+	);
+	pub(crate) const STREAM_PROPERTIES: Self = Self::new(
+		0xB7DC_0791,
+		0xA9B7,
+		0x11CF,
+		[0x8E, 0xE6, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65],
+	);
+	pub(crate) const HEADER_EXTENSION: Self = Self::new(
+		0x5FBF_03B5,
+		0xA92E,
+		0x11CF,
+		[0x8E, 0xE3, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65],
+	);
+	pub(crate) const CONTENT_DESCRIPTION: Self = Self::new(
+		0x75B2_2633,
+		0x668E,
+		0x11CF,
+		[0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C],
+	);
+	pub(crate) const EXTENDED_CONTENT_DESCRIPTION: Self = Self::new(
+		0xD2D0_A440,
+		0xE307,
+		0x11D2,
+		[0x97, 0xF0, 0x00, 0xA0, 0xC9, 0x5E, 0xA8, 0x50],
+	);
+	pub(crate) const METADATA: Self = Self::new(
+		0xC5F8_CBEA,
+		0x5BAF,
+		0x4877,
+		[0x84, 0x67, 0xAA, 0x8C, 0x44, 0xFA, 0x4C, 0xCA],
+	);
+	pub(crate) const METADATA_LIBRARY: Self = Self::new(
+		0x4423_1C94,
+		0x9498,
+		0x49D1,
+		[0xA1, 0x41, 0x1D, 0x13, 0x4E, 0x45, 0x70, 0x54],
+	);
+	pub(crate) const AUDIO_MEDIA: Self = Self::new(
+		0xF869_9E40,
+		0x5B4D,
+		0x11CF,
+		[0xA8, 0xFD, 0x00, 0x80, 0x5F, 0x5C, 0x44, 0x2B],
+	);
+
+	const fn new(data1: u32, data2: u16, data3: u16, data4: [u8; 8]) -> Self {
+		let data1 = data1.to_le_bytes();
+		let data2 = data2.to_le_bytes();
+		let data3 = data3.to_le_bytes();
+
+		Self([
+			data1[0], data1[1], data1[2], data1[3], data2[0], data2[1], data3[0], data3[1],
+			data4[0], data4[1], data4[2], data4[3], data4[4], data4[5], data4[6], data4[7],
+		])
+	}
+
+	pub(crate) fn read<R>(reader: &mut R) -> Result<Self>
+	where
+		R: Read,
+	{
+		let mut guid = [0; 16];
+		reader.read_exact(&mut guid)?;
+
+		Ok(Self(guid))
+	}
+
+	pub(crate) const fn as_bytes(&self) -> &[u8; 16] {
+		&self.0
+	}
+}
+
+/// The header of an ASF object
+pub(crate) struct ObjectHeader {
+	pub(crate) guid: Guid,
+	/// The size of the object's content, excluding the header
+	pub(crate) content_size: u64,
+}
+
+impl ObjectHeader {
+	pub(crate) fn read<R>(reader: &mut R) -> Result<Self>
+	where
+		R: Read,
+	{
+		let guid = Guid::read(reader)?;
+		let size = reader.read_u64::<LittleEndian>()?;
+
+		let Some(content_size) = size.checked_sub(OBJECT_HEADER_SIZE) else {
+			decode_err!(@BAIL Asf, "Found an object with an invalid size");
+		};
+
+		Ok(Self { guid, content_size })
+	}
+}
+
+/// Iterate over the objects in `content`, yielding their GUIDs and content
+///
+/// This stops at the first object that doesn't fit in `content`.
+pub(crate) fn objects(mut content: &[u8]) -> impl Iterator<Item = Result<(Guid, &[u8])>> {
+	std::iter::from_fn(move || {
+		if content.is_empty() {
+			return None;
+		}
+
+		let header = match ObjectHeader::read(&mut content) {
+			Ok(header) => header,
+			Err(e) => {
+				content = &[];
+				return Some(Err(e));
+			},
+		};
+
+		if header.content_size > content.len() as u64 {
+			content = &[];
+			return Some(Err(decode_err!(
+				Asf,
+				"Found an object that extends past its parent"
+			)));
+		}
+
+		let (object_content, remaining) = content.split_at(header.content_size as usize);
+		content = remaining;
+
+		Some(Ok((header.guid, object_content)))
+	})
+}
@@ -0,0 +1,209 @@
+use super::object::Guid;
+use crate::error::Result;
+use crate::macros::decode_err;
+use crate::properties::FileProperties;
+
+use std::time::Duration;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+/// An ASF file's audio codec
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum AsfCodec {
+	/// An unknown codec
+	#[default]
+	Unknown,
+	/// Windows Media Audio 1
+	WmaV1,
+	/// Windows Media Audio 2
+	WmaV2,
+	/// Windows Media Audio 9 Professional
+	WmaPro,
+	/// Windows Media Audio 9 Lossless
+	WmaLossless,
+	/// Windows Media Audio 9 Voice
+	WmaVoice,
+}
+
+impl AsfCodec {
+	fn from_format_tag(format_tag: u16) -> Self {
+		match format_tag {
+			0x0160 => Self::WmaV1,
+			0x0161 => Self::WmaV2,
+			0x0162 => Self::WmaPro,
+			0x0163 => Self::WmaLossless,
+			0x000A => Self::WmaVoice,
+			_ => Self::Unknown,
+		}
+	}
+}
+
+/// An ASF file's audio properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct AsfProperties {
+	pub(crate) codec: AsfCodec,
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+}
+
+impl From<AsfProperties> for FileProperties {
+	fn from(input: AsfProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: (input.bit_depth > 0).then_some(input.bit_depth),
+			channels: Some(input.channels),
+			channel_mask: None,
+		}
+	}
+}
+
+impl AsfProperties {
+	/// Audio codec
+	pub fn codec(&self) -> AsfCodec {
+		self.codec
+	}
+
+	/// Duration of the audio
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	///
+	/// This will be 0 if the codec doesn't specify one.
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+}
+
+/// The parts of the File Properties Object needed for the audio properties
+pub(super) struct FilePropertiesObject {
+	/// The duration in 100ns units, including the preroll
+	play_duration: u64,
+	/// The preroll in milliseconds
+	preroll: u64,
+	/// Whether the file is being broadcast, in which case the duration is invalid
+	broadcast: bool,
+}
+
+impl FilePropertiesObject {
+	pub(super) fn parse(mut content: &[u8]) -> Result<Self> {
+		if content.len() < 80 {
+			decode_err!(@BAIL Asf, "File Properties Object is too small");
+		}
+
+		// File ID (16), File size (8), Creation date (8), Data packets count (8)
+		content = &content[40..];
+
+		let play_duration = content.read_u64::<LittleEndian>()?;
+		let _send_duration = content.read_u64::<LittleEndian>()?;
+		let preroll = content.read_u64::<LittleEndian>()?;
+		let flags = content.read_u32::<LittleEndian>()?;
+
+		Ok(Self {
+			play_duration,
+			preroll,
+			broadcast: flags & 1 == 1,
+		})
+	}
+}
+
+/// Parse a Stream Properties Object, returning the audio format if it describes an audio stream
+///
+/// The audio format is a `WAVEFORMATEX` structure, see <https://learn.microsoft.com/en-us/windows/win32/api/mmreg/ns-mmreg-waveformatex>.
+pub(super) fn parse_stream_properties(mut content: &[u8]) -> Result<Option<AsfProperties>> {
+	if content.len() < 54 {
+		decode_err!(@BAIL Asf, "Stream Properties Object is too small");
+	}
+
+	let stream_type = Guid::read(&mut content)?;
+	if stream_type != Guid::AUDIO_MEDIA {
+		return Ok(None);
+	}
+
+	// Error correction type (16), Time offset (8)
+	content = &content[24..];
+
+	let type_specific_data_len = content.read_u32::<LittleEndian>()?;
+
+	// Error correction data length (4), Flags (2), Reserved (4)
+	content = &content[10..];
+
+	if (content.len() as u64) < u64::from(type_specific_data_len) || type_specific_data_len < 16 {
+		decode_err!(@BAIL Asf, "Stream Properties Object has an invalid audio format");
+	}
+
+	let format_tag = content.read_u16::<LittleEndian>()?;
+	let channels = content.read_u16::<LittleEndian>()?;
+	let sample_rate = content.read_u32::<LittleEndian>()?;
+	let average_bytes_per_second = content.read_u32::<LittleEndian>()?;
+	let _block_align = content.read_u16::<LittleEndian>()?;
+	let bits_per_sample = content.read_u16::<LittleEndian>()?;
+
+	Ok(Some(AsfProperties {
+		codec: AsfCodec::from_format_tag(format_tag),
+		sample_rate,
+		bit_depth: bits_per_sample as u8,
+		channels: channels as u8,
+		audio_bitrate: ((u64::from(average_bytes_per_second) * 8 + 500) / 1000) as u32,
+		..AsfProperties::default()
+	}))
+}
+
+pub(super) fn read_properties(
+	file_properties: Option<&FilePropertiesObject>,
+	stream_properties: Option<AsfProperties>,
+	file_length: u64,
+) -> AsfProperties {
+	let mut properties = stream_properties.unwrap_or_default();
+
+	let Some(file_properties) = file_properties else {
+		log::warn!("No File Properties Object found, unable to calculate duration");
+		return properties;
+	};
+
+	if file_properties.broadcast {
+		log::warn!("File is a broadcast, unable to calculate duration");
+		return properties;
+	}
+
+	let length = (file_properties.play_duration / 10_000).saturating_sub(file_properties.preroll);
+	properties.duration = Duration::from_millis(length);
+
+	if let Some(overall_bitrate) = (file_length * 8).checked_div(length) {
+		properties.overall_bitrate = overall_bitrate as u32;
+	}
+
+	properties
+}
@@ -0,0 +1,121 @@
+use super::object::{objects, Guid, ObjectHeader, OBJECT_HEADER_SIZE};
+use super::properties::{self, FilePropertiesObject};
+use super::tag::{read as tag_read, AsfTag};
+use super::AsfFile;
+use crate::config::ParseOptions;
+use crate::error::Result;
+use crate::macros::{decode_err, err, try_vec};
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+/// The size of the Header Object's fields, following the object header
+const HEADER_OBJECT_FIELDS_SIZE: u64 = 6;
+
+/// The size of the Header Extension Object's fields, following the object header
+const HEADER_EXTENSION_FIELDS_SIZE: usize = 22;
+
+pub(super) fn read_from<R>(reader: &mut R, parse_options: ParseOptions) -> Result<AsfFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let header = ObjectHeader::read(reader)?;
+	if header.guid != Guid::HEADER {
+		err!(UnknownFormat);
+	}
+
+	if header.content_size + OBJECT_HEADER_SIZE > file_length {
+		decode_err!(@BAIL Asf, "Header Object is larger than the file");
+	}
+
+	let Some(objects_size) = header.content_size.checked_sub(HEADER_OBJECT_FIELDS_SIZE) else {
+		decode_err!(@BAIL Asf, "Header Object is too small");
+	};
+
+	// Number of header objects (4), Reserved (2)
+	let _object_count = reader.read_u32::<LittleEndian>()?;
+	let _reserved = reader.read_u16::<LittleEndian>()?;
+
+	let mut content = try_vec![0; objects_size as usize];
+	reader.read_exact(&mut content)?;
+
+	let mut file_properties = None;
+	let mut stream_properties = None;
+	let mut tag = None;
+
+	for object in objects(&content) {
+		let (guid, object_content) = object?;
+
+		match guid {
+			Guid::FILE_PROPERTIES if parse_options.read_properties => {
+				file_properties = Some(FilePropertiesObject::parse(object_content)?);
+			},
+			// Only the first audio stream is used
+			Guid::STREAM_PROPERTIES
+				if parse_options.read_properties && stream_properties.is_none() =>
+			{
+				stream_properties = properties::parse_stream_properties(object_content)?;
+			},
+			Guid::CONTENT_DESCRIPTION if parse_options.read_tags => {
+				tag_read::parse_content_description(
+					object_content,
+					tag.get_or_insert_with(AsfTag::default),
+				)?;
+			},
+			Guid::EXTENDED_CONTENT_DESCRIPTION if parse_options.read_tags => {
+				tag_read::parse_extended_content_description(
+					object_content,
+					tag.get_or_insert_with(AsfTag::default),
+					parse_options,
+				)?;
+			},
+			Guid::HEADER_EXTENSION if parse_options.read_tags => {
+				read_header_extension(object_content, &mut tag, parse_options)?;
+			},
+			_ => {},
+		}
+	}
+
+	let mut properties = properties::AsfProperties::default();
+	if parse_options.read_properties {
+		properties =
+			properties::read_properties(file_properties.as_ref(), stream_properties, file_length);
+	}
+
+	Ok(AsfFile {
+		asf_tag: tag,
+		properties,
+	})
+}
+
+// The Header Extension Object holds the Metadata and Metadata Library Objects, which store
+// attributes that don't fit in the Extended Content Description Object
+fn read_header_extension(
+	content: &[u8],
+	tag: &mut Option<AsfTag>,
+	parse_options: ParseOptions,
+) -> Result<()> {
+	if content.len() < HEADER_EXTENSION_FIELDS_SIZE {
+		decode_err!(@BAIL Asf, "Header Extension Object is too small");
+	}
+
+	// Reserved field 1 (16), Reserved field 2 (2), Header extension data size (4)
+	for object in objects(&content[HEADER_EXTENSION_FIELDS_SIZE..]) {
+		let (guid, object_content) = object?;
+
+		if guid == Guid::METADATA || guid == Guid::METADATA_LIBRARY {
+			tag_read::parse_metadata(
+				object_content,
+				tag.get_or_insert_with(AsfTag::default),
+				parse_options,
+			)?;
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,665 @@
+pub(super) mod read;
+
+use crate::config::WriteOptions;
+use crate::error::LoftyError;
+use crate::macros::err;
+use crate::picture::Picture;
+use crate::tag::{
+	try_parse_year, Accessor, ItemKey, ItemValue, MergeTag, SplitTag, Tag, TagExt, TagItem, TagType,
+};
+use crate::util::io::{FileLike, Length, Truncate};
+
+use std::borrow::Cow;
+use std::io::Write;
+
+use lofty_attr::tag;
+
+const TRACK_NUMBER_KEY: &str = "WM/TrackNumber";
+const PART_OF_SET_KEY: &str = "WM/PartOfSet";
+pub(super) const PICTURE_KEY: &str = "WM/Picture";
+
+macro_rules! impl_accessor {
+	($($name:ident => $key:literal;)+) => {
+		paste::paste! {
+			$(
+				fn $name(&self) -> Option<Cow<'_, str>> {
+					self.get($key).and_then(AsfAttributeValue::text).map(Cow::Borrowed)
+				}
+
+				fn [<set_ $name>](&mut self, value: String) {
+					self.insert(String::from($key), AsfAttributeValue::String(value))
+				}
+
+				fn [<remove_ $name>](&mut self) {
+					let _ = self.remove($key);
+				}
+			)+
+		}
+	}
+}
+
+/// The value of an ASF attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AsfAttributeValue {
+	/// A UTF-16 string
+	String(String),
+	/// Binary data
+	Binary(Vec<u8>),
+	/// A boolean
+	Bool(bool),
+	/// A 32-bit integer
+	DWord(u32),
+	/// A 64-bit integer
+	QWord(u64),
+	/// A 16-bit integer
+	Word(u16),
+	/// A GUID
+	Guid([u8; 16]),
+}
+
+impl AsfAttributeValue {
+	/// Returns the string, if the value is a [`AsfAttributeValue::String`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::asf::AsfAttributeValue;
+	///
+	/// let value = AsfAttributeValue::String(String::from("Foo"));
+	/// assert_eq!(value.text(), Some("Foo"));
+	///
+	/// let value = AsfAttributeValue::DWord(1);
+	/// assert_eq!(value.text(), None);
+	/// ```
+	pub fn text(&self) -> Option<&str> {
+		match self {
+			Self::String(text) => Some(text),
+			_ => None,
+		}
+	}
+
+	/// Returns the value as an integer, if possible
+	///
+	/// This will attempt to parse a [`AsfAttributeValue::String`], as numbers are commonly
+	/// stored as strings.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::asf::AsfAttributeValue;
+	///
+	/// let value = AsfAttributeValue::DWord(1);
+	/// assert_eq!(value.as_u64(), Some(1));
+	///
+	/// let value = AsfAttributeValue::String(String::from("2"));
+	/// assert_eq!(value.as_u64(), Some(2));
+	/// ```
+	pub fn as_u64(&self) -> Option<u64> {
+		match self {
+			Self::String(text) => text.trim().parse().ok(),
+			Self::DWord(value) => Some(u64::from(*value)),
+			Self::QWord(value) => Some(*value),
+			Self::Word(value) => Some(u64::from(*value)),
+			_ => None,
+		}
+	}
+}
+
+/// ## Item storage
+///
+/// ASF stores its metadata across several objects:
+///
+/// * The Content Description Object, which holds the title, author, copyright, description, and
+///   rating. These are stored in the tag as the attributes `Title`, `Author`, `Copyright`,
+///   `Description`, and `Rating`.
+/// * The Extended Content Description Object, which holds the remaining attributes, such as `WM/AlbumTitle`
+/// * The Metadata and Metadata Library Objects, which hold attributes that are too large for the
+///   Extended Content Description Object, such as large `WM/Picture` attributes
+///
+/// All of them are merged into a single `AsfTag`.
+///
+/// ## Pictures
+///
+/// Pictures are stored in `WM/Picture` attributes, which are extracted into [`AsfTag::pictures`].
+///
+/// ## Conversions
+///
+/// ### To `Tag`
+///
+/// All attributes will be converted to a [`TagItem`], with all unknown keys being stored with
+/// [`ItemKey::Unknown`]. Numeric and boolean values are converted to text, and a
+/// `WM/PartOfSet` in the form `current/total` is split into [`ItemKey::DiscNumber`] and
+/// [`ItemKey::DiscTotal`].
+///
+/// ### From `Tag`
+///
+/// All [`TagItem`]s with a mapping or an [`ItemKey::Unknown`] key will be converted to
+/// attributes, with text values stored as [`AsfAttributeValue::String`].
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+#[tag(description = "ASF attributes", supported_formats(read_only(Asf)))]
+pub struct AsfTag {
+	pub(crate) items: Vec<(String, AsfAttributeValue)>,
+	pub(crate) pictures: Vec<Picture>,
+}
+
+impl AsfTag {
+	/// Create a new empty `AsfTag`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::asf::AsfTag;
+	/// use lofty::tag::TagExt;
+	///
+	/// let asf_tag = AsfTag::new();
+	/// assert!(asf_tag.is_empty());
+	/// ```
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Gets the first attribute with the name
+	///
+	/// NOTE: The name is case-insensitive
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::asf::{AsfAttributeValue, AsfTag};
+	///
+	/// let mut tag = AsfTag::new();
+	/// tag.push(
+	/// 	String::from("WM/AlbumTitle"),
+	/// 	AsfAttributeValue::String(String::from("Foo album")),
+	/// );
+	///
+	/// let album = tag.get("WM/AlbumTitle").and_then(AsfAttributeValue::text);
+	/// assert_eq!(album, Some("Foo album"));
+	/// ```
+	pub fn get(&self, name: &str) -> Option<&AsfAttributeValue> {
+		self.items
+			.iter()
+			.find(|(k, _)| k.eq_ignore_ascii_case(name))
+			.map(|(_, v)| v)
+	}
+
+	/// Gets all attributes with the name
+	///
+	/// NOTE: The name is case-insensitive
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::asf::{AsfAttributeValue, AsfTag};
+	///
+	/// let mut tag = AsfTag::new();
+	///
+	/// // ASF allows multiple attributes with the same name, such as artist
+	/// tag.push(
+	/// 	String::from("Author"),
+	/// 	AsfAttributeValue::String(String::from("Foo artist")),
+	/// );
+	/// tag.push(
+	/// 	String::from("Author"),
+	/// 	AsfAttributeValue::String(String::from("Bar artist")),
+	/// );
+	///
+	/// assert_eq!(tag.get_all("Author").count(), 2);
+	/// ```
+	pub fn get_all<'a>(
+		&'a self,
+		name: &'a str,
+	) -> impl Iterator<Item = &'a AsfAttributeValue> + Clone + 'a {
+		self.items
+			.iter()
+			.filter_map(move |(k, v)| (k.eq_ignore_ascii_case(name)).then_some(v))
+	}
+
+	/// Inserts an attribute
+	///
+	/// This is the same as [`AsfTag::push`], except it will remove any attributes with the same name.
+	pub fn insert(&mut self, name: String, value: AsfAttributeValue) {
+		self.items.retain(|(k, _)| !k.eq_ignore_ascii_case(&name));
+		self.items.push((name, value))
+	}
+
+	/// Appends an attribute
+	///
+	/// NOTE: This will do nothing if the name is empty
+	pub fn push(&mut self, name: String, value: AsfAttributeValue) {
+		if name.is_empty() {
+			return;
+		}
+
+		self.items.push((name, value))
+	}
+
+	/// Removes all attributes with the name, returning an iterator
+	///
+	/// NOTE: The name is case-insensitive
+	pub fn remove(&mut self, name: &str) -> impl Iterator<Item = AsfAttributeValue> + '_ {
+		let mut split_idx = 0_usize;
+
+		for read_idx in 0..self.items.len() {
+			if self.items[read_idx].0.eq_ignore_ascii_case(name) {
+				self.items.swap(split_idx, read_idx);
+				split_idx += 1;
+			}
+		}
+
+		self.items.drain(..split_idx).map(|(_, v)| v)
+	}
+
+	/// Returns the stored [`Picture`]s as a slice
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::asf::AsfTag;
+	///
+	/// let tag = AsfTag::new();
+	/// assert!(tag.pictures().is_empty());
+	/// ```
+	pub fn pictures(&self) -> &[Picture] {
+		&self.pictures
+	}
+
+	/// Appends a [`Picture`]
+	pub fn push_picture(&mut self, picture: Picture) {
+		self.pictures.push(picture)
+	}
+
+	/// Removes all [`Picture`]s
+	pub fn remove_pictures(&mut self) {
+		self.pictures.clear()
+	}
+
+	// `WM/PartOfSet` is a string in the form "current/total"
+	fn part_of_set(&self) -> (Option<u32>, Option<u32>) {
+		let Some(part_of_set) = self.get(PART_OF_SET_KEY) else {
+			return (None, None);
+		};
+
+		if let Some(text) = part_of_set.text() {
+			let mut split = text.split('/').map(|part| part.trim().parse::<u32>().ok());
+			return (split.next().flatten(), split.next().flatten());
+		}
+
+		(part_of_set.as_u64().map(|disk| disk as u32), None)
+	}
+
+	fn set_part_of_set(&mut self, disk: Option<u32>, disk_total: Option<u32>) {
+		let value = match (disk, disk_total) {
+			(Some(disk), Some(disk_total)) => format!("{disk}/{disk_total}"),
+			(Some(disk), None) => disk.to_string(),
+			(None, Some(disk_total)) => format!("0/{disk_total}"),
+			(None, None) => {
+				let _ = self.remove(PART_OF_SET_KEY);
+				return;
+			},
+		};
+
+		self.insert(
+			String::from(PART_OF_SET_KEY),
+			AsfAttributeValue::String(value),
+		);
+	}
+}
+
+impl Accessor for AsfTag {
+	impl_accessor!(
+		artist  => "Author";
+		title   => "Title";
+		album   => "WM/AlbumTitle";
+		genre   => "WM/Genre";
+		comment => "Description";
+	);
+
+	fn track(&self) -> Option<u32> {
+		self.get(TRACK_NUMBER_KEY)
+			.and_then(AsfAttributeValue::as_u64)
+			.map(|track| track as u32)
+	}
+
+	fn set_track(&mut self, value: u32) {
+		self.insert(
+			String::from(TRACK_NUMBER_KEY),
+			AsfAttributeValue::DWord(value),
+		);
+	}
+
+	fn remove_track(&mut self) {
+		let _ = self.remove(TRACK_NUMBER_KEY);
+	}
+
+	fn disk(&self) -> Option<u32> {
+		self.part_of_set().0
+	}
+
+	fn set_disk(&mut self, value: u32) {
+		self.set_part_of_set(Some(value), self.disk_total());
+	}
+
+	fn remove_disk(&mut self) {
+		self.set_part_of_set(None, self.disk_total());
+	}
+
+	fn disk_total(&self) -> Option<u32> {
+		self.part_of_set().1
+	}
+
+	fn set_disk_total(&mut self, value: u32) {
+		self.set_part_of_set(self.disk(), Some(value));
+	}
+
+	fn remove_disk_total(&mut self) {
+		self.set_part_of_set(self.disk(), None);
+	}
+
+	fn year(&self) -> Option<u32> {
+		self.get("WM/Year")
+			.and_then(AsfAttributeValue::text)
+			.and_then(try_parse_year)
+	}
+
+	fn set_year(&mut self, value: u32) {
+		self.insert(
+			String::from("WM/Year"),
+			AsfAttributeValue::String(value.to_string()),
+		);
+	}
+
+	fn remove_year(&mut self) {
+		let _ = self.remove("WM/Year");
+	}
+}
+
+impl IntoIterator for AsfTag {
+	type Item = (String, AsfAttributeValue);
+	type IntoIter = std::vec::IntoIter<Self::Item>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a> IntoIterator for &'a AsfTag {
+	type Item = &'a (String, AsfAttributeValue);
+	type IntoIter = std::slice::Iter<'a, (String, AsfAttributeValue)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
+
+impl TagExt for AsfTag {
+	type Err = LoftyError;
+	type RefKey<'a> = &'a str;
+
+	#[inline]
+	fn tag_type(&self) -> TagType {
+		TagType::Asf
+	}
+
+	fn len(&self) -> usize {
+		self.items.len() + self.pictures.len()
+	}
+
+	fn contains<'a>(&'a self, key: Self::RefKey<'a>) -> bool {
+		if key.eq_ignore_ascii_case(PICTURE_KEY) {
+			return !self.pictures.is_empty();
+		}
+
+		self.items
+			.iter()
+			.any(|(item_key, _)| item_key.eq_ignore_ascii_case(key))
+	}
+
+	fn is_empty(&self) -> bool {
+		self.items.is_empty() && self.pictures.is_empty()
+	}
+
+	/// Writing ASF attributes is not yet supported
+	///
+	/// # Errors
+	///
+	/// This will always return [`ErrorKind::UnsupportedTag`](crate::error::ErrorKind::UnsupportedTag)
+	fn save_to<F>(
+		&self,
+		_file: &mut F,
+		_write_options: WriteOptions,
+	) -> std::result::Result<(), Self::Err>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		err!(UnsupportedTag)
+	}
+
+	/// Writing ASF attributes is not yet supported
+	///
+	/// # Errors
+	///
+	/// This will always return [`ErrorKind::UnsupportedTag`](crate::error::ErrorKind::UnsupportedTag)
+	fn dump_to<W: Write>(
+		&self,
+		_writer: &mut W,
+		_write_options: WriteOptions,
+	) -> std::result::Result<(), Self::Err> {
+		err!(UnsupportedTag)
+	}
+
+	fn clear(&mut self) {
+		self.items.clear();
+		self.pictures.clear();
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SplitTagRemainder;
+
+impl SplitTag for AsfTag {
+	type Remainder = SplitTagRemainder;
+
+	fn split_tag(self) -> (Self::Remainder, Tag) {
+		let mut tag = Tag::new(TagType::Asf);
+
+		for (key, value) in self.items {
+			// `WM/PartOfSet` maps to both `DiscNumber` and `DiscTotal`
+			if key.eq_ignore_ascii_case(PART_OF_SET_KEY) {
+				let part_of_set = match value {
+					AsfAttributeValue::String(text) => text,
+					value => match value.as_u64() {
+						Some(disk) => disk.to_string(),
+						None => continue,
+					},
+				};
+
+				let (disk, disk_total) = match part_of_set.split_once('/') {
+					Some((disk, disk_total)) => (disk.trim(), Some(disk_total.trim())),
+					None => (part_of_set.trim(), None),
+				};
+
+				tag.items.push(TagItem::new(
+					ItemKey::DiscNumber,
+					ItemValue::Text(disk.to_owned()),
+				));
+				if let Some(disk_total) = disk_total {
+					tag.items.push(TagItem::new(
+						ItemKey::DiscTotal,
+						ItemValue::Text(disk_total.to_owned()),
+					));
+				}
+
+				continue;
+			}
+
+			let item_value = match value {
+				AsfAttributeValue::String(text) => ItemValue::Text(text),
+				AsfAttributeValue::Binary(data) => ItemValue::Binary(data),
+				AsfAttributeValue::Bool(value) => ItemValue::Text(u8::from(value).to_string()),
+				AsfAttributeValue::DWord(value) => ItemValue::Text(value.to_string()),
+				AsfAttributeValue::QWord(value) => ItemValue::Text(value.to_string()),
+				AsfAttributeValue::Word(value) => ItemValue::Text(value.to_string()),
+				AsfAttributeValue::Guid(guid) => ItemValue::Binary(guid.to_vec()),
+			};
+
+			tag.items.push(TagItem::new(
+				ItemKey::from_key(TagType::Asf, &key),
+				item_value,
+			));
+		}
+
+		for picture in self.pictures {
+			tag.push_picture(picture);
+		}
+
+		(SplitTagRemainder, tag)
+	}
+}
+
+impl MergeTag for SplitTagRemainder {
+	type Merged = AsfTag;
+
+	fn merge_tag(self, mut tag: Tag) -> Self::Merged {
+		let mut merged = AsfTag::default();
+
+		let disk = tag.take_strings(&ItemKey::DiscNumber).next();
+		let disk_total = tag.take_strings(&ItemKey::DiscTotal).next();
+		match (disk, disk_total) {
+			(Some(disk), Some(disk_total)) => merged.items.push((
+				String::from(PART_OF_SET_KEY),
+				AsfAttributeValue::String(format!("{disk}/{disk_total}")),
+			)),
+			(Some(disk), None) => merged.items.push((
+				String::from(PART_OF_SET_KEY),
+				AsfAttributeValue::String(disk),
+			)),
+			_ => {},
+		}
+
+		for item in tag.items {
+			let key = match item.item_key {
+				ItemKey::Unknown(unknown) if !unknown.is_empty() => unknown,
+				item_key => match item_key.map_key(TagType::Asf, false) {
+					Some(mapped_key) => mapped_key.to_string(),
+					None => continue, // No mapping exists, discard the item
+				},
+			};
+
+			let value = match item.item_value {
+				ItemValue::Text(text) | ItemValue::Locator(text) => AsfAttributeValue::String(text),
+				ItemValue::Binary(data) => AsfAttributeValue::Binary(data),
+			};
+
+			merged.items.push((key, value));
+		}
+
+		merged.pictures = tag.pictures;
+		merged
+	}
+}
+
+impl From<AsfTag> for Tag {
+	fn from(input: AsfTag) -> Self {
+		input.split_tag().1
+	}
+}
+
+impl From<Tag> for AsfTag {
+	fn from(input: Tag) -> Self {
+		SplitTagRemainder.merge_tag(input)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::asf::{AsfAttributeValue, AsfTag};
+	use crate::picture::{MimeType, Picture, PictureType};
+	use crate::prelude::*;
+	use crate::tag::{ItemValue, Tag, TagItem, TagType};
+
+	fn string(value: &str) -> AsfAttributeValue {
+		AsfAttributeValue::String(String::from(value))
+	}
+
+	#[test_log::test]
+	fn asf_to_tag() {
+		let mut asf = AsfTag::new();
+		asf.push(String::from("Title"), string("Foo title"));
+		asf.push(String::from("Author"), string("Bar artist"));
+		asf.push(String::from("WM/AlbumTitle"), string("Baz album"));
+		asf.push(String::from("Description"), string("Qux comment"));
+		asf.push(String::from("WM/TrackNumber"), AsfAttributeValue::DWord(1));
+		asf.push(String::from("WM/PartOfSet"), string("1/2"));
+		asf.push(String::from("WM/Year"), string("1984"));
+		asf.push(String::from("WM/Genre"), string("Classical"));
+		asf.push(
+			String::from("WM/IsCompilation"),
+			AsfAttributeValue::Bool(true),
+		);
+		asf.push_picture(Picture::new_unchecked(
+			PictureType::CoverFront,
+			Some(MimeType::Png),
+			None,
+			vec![1; 16],
+		));
+
+		let tag: Tag = asf.into();
+
+		crate::tag::utils::test_utils::verify_tag(&tag, true, true);
+		assert_eq!(tag.disk(), Some(1));
+		assert_eq!(tag.disk_total(), Some(2));
+		assert_eq!(tag.year(), Some(1984));
+		assert_eq!(tag.genre().as_deref(), Some("Classical"));
+		assert_eq!(
+			tag.get(&ItemKey::FlagCompilation).map(TagItem::value),
+			Some(&ItemValue::Text(String::from("1")))
+		);
+		assert_eq!(tag.picture_count(), 1);
+	}
+
+	#[test_log::test]
+	fn tag_to_asf() {
+		let mut tag = crate::tag::utils::test_utils::create_tag(TagType::Asf);
+		tag.set_disk(1);
+		tag.set_disk_total(2);
+
+		let asf: AsfTag = tag.into();
+
+		assert_eq!(asf.title().as_deref(), Some("Foo title"));
+		assert_eq!(asf.artist().as_deref(), Some("Bar artist"));
+		assert_eq!(asf.album().as_deref(), Some("Baz album"));
+		assert_eq!(asf.comment().as_deref(), Some("Qux comment"));
+		assert_eq!(asf.track(), Some(1));
+		assert_eq!(asf.disk(), Some(1));
+		assert_eq!(asf.disk_total(), Some(2));
+		assert_eq!(asf.get("WM/PartOfSet"), Some(&string("1/2")));
+	}
+
+	#[test_log::test]
+	fn accessors() {
+		let mut asf = AsfTag::new();
+
+		asf.set_track(5);
+		assert_eq!(
+			asf.get("WM/TrackNumber"),
+			Some(&AsfAttributeValue::DWord(5))
+		);
+
+		// Track numbers are also commonly stored as strings
+		asf.insert(String::from("WM/TrackNumber"), string("6"));
+		assert_eq!(asf.track(), Some(6));
+
+		asf.set_disk_total(3);
+		assert_eq!(asf.get("WM/PartOfSet"), Some(&string("0/3")));
+		asf.set_disk(2);
+		assert_eq!(asf.get("WM/PartOfSet"), Some(&string("2/3")));
+		asf.remove_disk_total();
+		assert_eq!(asf.get("WM/PartOfSet"), Some(&string("2")));
+		asf.remove_disk();
+		assert!(asf.get("WM/PartOfSet").is_none());
+	}
+}
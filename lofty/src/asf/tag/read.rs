@@ -0,0 +1,184 @@
+use super::{AsfAttributeValue, AsfTag, PICTURE_KEY};
+use crate::config::{ParseOptions, ParsingMode};
+use crate::error::Result;
+use crate::macros::decode_err;
+use crate::picture::{MimeType, Picture, PictureType};
+use crate::util::text::{read_to_terminator, utf16_decode_bytes, TextEncoding};
+
+use std::io::Read;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+const CONTENT_DESCRIPTION_NAMES: [&str; 5] =
+	["Title", "Author", "Copyright", "Description", "Rating"];
+
+/// Parse the Content Description Object
+pub(in crate::asf) fn parse_content_description(
+	mut content: &[u8],
+	tag: &mut AsfTag,
+) -> Result<()> {
+	let mut lengths = [0; 5];
+	for length in &mut lengths {
+		*length = content.read_u16::<LittleEndian>()?;
+	}
+
+	for (name, length) in CONTENT_DESCRIPTION_NAMES.into_iter().zip(lengths) {
+		let value = read_utf16(&mut content, u64::from(length))?;
+		if !value.is_empty() {
+			tag.items
+				.push((String::from(name), AsfAttributeValue::String(value)));
+		}
+	}
+
+	Ok(())
+}
+
+/// Parse the Extended Content Description Object
+pub(in crate::asf) fn parse_extended_content_description(
+	mut content: &[u8],
+	tag: &mut AsfTag,
+	parse_options: ParseOptions,
+) -> Result<()> {
+	let descriptor_count = content.read_u16::<LittleEndian>()?;
+	for _ in 0..descriptor_count {
+		let name_len = content.read_u16::<LittleEndian>()?;
+		let name = read_utf16(&mut content, u64::from(name_len))?;
+
+		let data_type = content.read_u16::<LittleEndian>()?;
+		let value_len = content.read_u16::<LittleEndian>()?;
+		let value = take(&mut content, u64::from(value_len))?;
+
+		push_attribute(tag, name, data_type, value, parse_options)?;
+	}
+
+	Ok(())
+}
+
+/// Parse the Metadata Object or Metadata Library Object, which share the same layout
+pub(in crate::asf) fn parse_metadata(
+	mut content: &[u8],
+	tag: &mut AsfTag,
+	parse_options: ParseOptions,
+) -> Result<()> {
+	let record_count = content.read_u16::<LittleEndian>()?;
+	for _ in 0..record_count {
+		// Language list index (2), Stream number (2)
+		let _ = take(&mut content, 4)?;
+
+		let name_len = content.read_u16::<LittleEndian>()?;
+		let data_type = content.read_u16::<LittleEndian>()?;
+		let data_len = content.read_u32::<LittleEndian>()?;
+
+		let name = read_utf16(&mut content, u64::from(name_len))?;
+		let value = take(&mut content, u64::from(data_len))?;
+
+		push_attribute(tag, name, data_type, value, parse_options)?;
+	}
+
+	Ok(())
+}
+
+fn push_attribute(
+	tag: &mut AsfTag,
+	name: String,
+	data_type: u16,
+	value: &[u8],
+	parse_options: ParseOptions,
+) -> Result<()> {
+	if name.is_empty() {
+		log::warn!("Skipping an attribute with no name");
+		return Ok(());
+	}
+
+	if name == PICTURE_KEY && data_type == 1 {
+		if !parse_options.read_cover_art {
+			return Ok(());
+		}
+
+		match parse_picture(value) {
+			Ok(picture) => tag.pictures.push(picture),
+			Err(e) => {
+				if parse_options.parsing_mode == ParsingMode::Strict {
+					return Err(e);
+				}
+
+				log::warn!("Skipping an invalid WM/Picture attribute");
+			},
+		}
+
+		return Ok(());
+	}
+
+	let Some(value) = parse_value(data_type, value)? else {
+		if parse_options.parsing_mode == ParsingMode::Strict {
+			decode_err!(@BAIL Asf, "Found an attribute with an unknown data type");
+		}
+
+		log::warn!("Skipping attribute {name:?}, unknown data type {data_type}");
+		return Ok(());
+	};
+
+	tag.items.push((name, value));
+	Ok(())
+}
+
+fn parse_value(data_type: u16, mut value: &[u8]) -> Result<Option<AsfAttributeValue>> {
+	let value = match data_type {
+		0 => AsfAttributeValue::String(utf16_decode_bytes(value, u16::from_le_bytes)?),
+		1 => AsfAttributeValue::Binary(value.to_vec()),
+		// This is 4 bytes in the Extended Content Description Object, and 2 bytes in the
+		// Metadata (Library) Object
+		2 => AsfAttributeValue::Bool(value.iter().any(|b| *b != 0)),
+		3 => AsfAttributeValue::DWord(value.read_u32::<LittleEndian>()?),
+		4 => AsfAttributeValue::QWord(value.read_u64::<LittleEndian>()?),
+		5 => AsfAttributeValue::Word(value.read_u16::<LittleEndian>()?),
+		6 => {
+			let mut guid = [0; 16];
+			value.read_exact(&mut guid)?;
+			AsfAttributeValue::Guid(guid)
+		},
+		_ => return Ok(None),
+	};
+
+	Ok(Some(value))
+}
+
+/// Parse a `WM/Picture` attribute
+///
+/// This is a `WM_PICTURE` structure, see <https://learn.microsoft.com/en-us/windows/win32/wmformat/wm-picture>.
+fn parse_picture(mut value: &[u8]) -> Result<Picture> {
+	let pic_type = PictureType::from_u8(value.read_u8()?);
+	let data_len = value.read_u32::<LittleEndian>()?;
+
+	let (mime_type, _) = read_to_terminator(&mut value, TextEncoding::UTF16);
+	let mime_type = utf16_decode_bytes(&mime_type, u16::from_le_bytes)?;
+
+	let (description, _) = read_to_terminator(&mut value, TextEncoding::UTF16);
+	let description = utf16_decode_bytes(&description, u16::from_le_bytes)?;
+
+	if u64::from(data_len) > value.len() as u64 {
+		decode_err!(@BAIL Asf, "WM/Picture has an invalid data length");
+	}
+
+	Ok(Picture::new_unchecked(
+		pic_type,
+		(!mime_type.is_empty()).then(|| MimeType::from_str(&mime_type)),
+		(!description.is_empty()).then_some(description),
+		value[..data_len as usize].to_vec(),
+	))
+}
+
+fn take<'a>(content: &mut &'a [u8], len: u64) -> Result<&'a [u8]> {
+	if len > content.len() as u64 {
+		decode_err!(@BAIL Asf, "Found an attribute that extends past its object");
+	}
+
+	let (taken, remaining) = content.split_at(len as usize);
+	*content = remaining;
+
+	Ok(taken)
+}
+
+fn read_utf16(content: &mut &[u8], len: u64) -> Result<String> {
+	utf16_decode_bytes(take(content, len)?, u16::from_le_bytes)
+}
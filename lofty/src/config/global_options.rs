@@ -25,6 +25,7 @@ pub struct GlobalOptions {
 	pub(crate) use_custom_resolvers: bool,
 	pub(crate) allocation_limit: usize,
 	pub(crate) preserve_format_specific_items: bool,
+	pub(crate) key_style: KeyStyle,
 }
 
 impl GlobalOptions {
@@ -48,6 +49,7 @@ impl GlobalOptions {
 			use_custom_resolvers: true,
 			allocation_limit: Self::DEFAULT_ALLOCATION_LIMIT,
 			preserve_format_specific_items: true,
+			key_style: KeyStyle::Format,
 		}
 	}
 
@@ -112,6 +114,31 @@ impl GlobalOptions {
 		self.preserve_format_specific_items = preserve_format_specific_items;
 		*self
 	}
+
+	/// The spelling of keys when converting a [`Tag`] to a format with free-form keys
+	///
+	/// This applies to [`VorbisComments`] field names and [`ApeTag`] item keys, when merging a
+	/// [`Tag`] into either of them, as well as when writing a [`Tag`] directly.
+	///
+	/// See [`KeyStyle`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{apply_global_options, GlobalOptions, KeyStyle};
+	///
+	/// // The rest of my library uses `AlbumArtist`, not `ALBUMARTIST`
+	/// let global_options = GlobalOptions::new().key_style(KeyStyle::TitleCase);
+	/// apply_global_options(global_options);
+	/// ```
+	///
+	/// [`Tag`]: crate::tag::Tag
+	/// [`VorbisComments`]: crate::ogg::VorbisComments
+	/// [`ApeTag`]: crate::ape::ApeTag
+	pub fn key_style(&mut self, key_style: KeyStyle) -> Self {
+		self.key_style = key_style;
+		*self
+	}
 }
 
 impl Default for GlobalOptions {
@@ -124,6 +151,7 @@ impl Default for GlobalOptions {
 	/// 	use_custom_resolvers: true,
 	/// 	allocation_limit: Self::DEFAULT_ALLOCATION_LIMIT,
 	/// 	preserve_format_specific_items: true,
+	/// 	key_style: KeyStyle::Format,
 	/// }
 	/// ```
 	fn default() -> Self {
@@ -131,6 +159,37 @@ impl Default for GlobalOptions {
 	}
 }
 
+/// How to spell keys when converting a [`Tag`] to a format with free-form keys
+///
+/// This can be set with [`GlobalOptions::key_style`].
+///
+/// [`Tag`]: crate::tag::Tag
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum KeyStyle {
+	/// Use the spelling from the format's own mapping (ex. `ALBUMARTIST` in Vorbis Comments)
+	#[default]
+	Format,
+	/// Uppercase the entire key (ex. `ALBUMARTIST`)
+	Uppercase,
+	/// Capitalize the first letter of every word, and lowercase the rest (ex. `AlbumArtist`)
+	///
+	/// Words are separated by spaces, underscores, and hyphens. Keys without any separators are
+	/// split at the word boundaries of their [`ItemKey`] variant when the letters match, so
+	/// `ALBUMARTIST` becomes `AlbumArtist`, while `ARTIST` ([`ItemKey::TrackArtist`]) becomes `Artist`.
+	///
+	/// [`ItemKey`]: crate::tag::ItemKey
+	/// [`ItemKey::TrackArtist`]: crate::tag::ItemKey::TrackArtist
+	TitleCase,
+	/// Keep the spelling of the key as it was read, falling back to [`KeyStyle::Format`]
+	///
+	/// The original spelling is only known for items of a [`Tag`] that was converted from the
+	/// same format it is being converted back to.
+	///
+	/// [`Tag`]: crate::tag::Tag
+	Preserve,
+}
+
 /// Applies the given `GlobalOptions` to the current thread
 ///
 /// # Examples
@@ -1,11 +1,17 @@
 //! Various configuration options to control Lofty
 
 mod global_options;
+mod normalize_options;
 mod parse_options;
+mod pretty_print_options;
 mod write_options;
 
-pub use global_options::{apply_global_options, GlobalOptions};
-pub use parse_options::{ParseOptions, ParsingMode};
-pub use write_options::WriteOptions;
+pub use global_options::{apply_global_options, GlobalOptions, KeyStyle};
+pub use normalize_options::NormalizeOptions;
+pub use parse_options::{
+	DuplicateTagPolicy, IoHint, ParseOptions, ParsingMode, PictureDataPolicy, TextDecodeFallback,
+};
+pub use pretty_print_options::PrettyPrintOptions;
+pub use write_options::{InvalidFieldNamePolicy, RiffInfoPlacement, WriteOptions};
 
 pub(crate) use global_options::global_options;
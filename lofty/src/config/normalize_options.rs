@@ -0,0 +1,170 @@
+/// Options to control how [`Tag::normalize`](crate::tag::Tag::normalize) cleans up a tag
+///
+/// Binary values and pictures are never modified, regardless of these options.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct NormalizeOptions {
+	pub(crate) trim_whitespace: bool,
+	pub(crate) remove_empty: bool,
+	pub(crate) canonicalize_numbers: bool,
+	pub(crate) fix_zero_padded_dates: bool,
+	pub(crate) remove_duplicates: bool,
+}
+
+impl NormalizeOptions {
+	/// Creates a new `NormalizeOptions`, alias for `Default` implementation
+	///
+	/// See also: [`NormalizeOptions::default`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	///
+	/// let normalize_options = NormalizeOptions::new();
+	/// ```
+	pub const fn new() -> Self {
+		Self {
+			trim_whitespace: true,
+			remove_empty: true,
+			canonicalize_numbers: true,
+			fix_zero_padded_dates: true,
+			remove_duplicates: true,
+		}
+	}
+
+	/// Whether to trim leading and trailing whitespace from text values
+	///
+	/// Trailing null characters, which are left behind by some taggers, are trimmed as well.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.set_genre(String::from("  Rock\0"));
+	///
+	/// tag.normalize(NormalizeOptions::new());
+	/// assert_eq!(tag.genre().as_deref(), Some("Rock"));
+	/// ```
+	pub fn trim_whitespace(mut self, trim_whitespace: bool) -> Self {
+		self.trim_whitespace = trim_whitespace;
+		self
+	}
+
+	/// Whether to remove items with empty text values
+	///
+	/// With [`NormalizeOptions::trim_whitespace`], this includes values made up entirely of whitespace.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.set_comment(String::from("   "));
+	///
+	/// tag.normalize(NormalizeOptions::new());
+	/// assert!(tag.is_empty());
+	/// ```
+	pub fn remove_empty(mut self, remove_empty: bool) -> Self {
+		self.remove_empty = remove_empty;
+		self
+	}
+
+	/// Whether to canonicalize track, disc, and movement numbers
+	///
+	/// This strips leading zeros (`"03"` becomes `"3"`), and splits combined values such as `"3/12"`
+	/// into their number and total. An existing total is never replaced.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	/// use lofty::tag::{ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.insert_text(ItemKey::TrackNumber, String::from("03/12"));
+	///
+	/// tag.normalize(NormalizeOptions::new());
+	/// assert_eq!(tag.get_string(&ItemKey::TrackNumber), Some("3"));
+	/// assert_eq!(tag.get_string(&ItemKey::TrackTotal), Some("12"));
+	/// ```
+	pub fn canonicalize_numbers(mut self, canonicalize_numbers: bool) -> Self {
+		self.canonicalize_numbers = canonicalize_numbers;
+		self
+	}
+
+	/// Whether to remove zeroed components from dates
+	///
+	/// Some taggers pad unknown date components with zeros, such as `"2004-00-00"`. These are
+	/// truncated to the known components, so `"2004-00-00"` becomes `"2004"`, and `"2004-05-00"`
+	/// becomes `"2004-05"`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	/// use lofty::tag::{ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.insert_text(ItemKey::RecordingDate, String::from("2004-00-00"));
+	///
+	/// tag.normalize(NormalizeOptions::new());
+	/// assert_eq!(tag.get_string(&ItemKey::RecordingDate), Some("2004"));
+	/// ```
+	pub fn fix_zero_padded_dates(mut self, fix_zero_padded_dates: bool) -> Self {
+		self.fix_zero_padded_dates = fix_zero_padded_dates;
+		self
+	}
+
+	/// Whether to remove duplicate items
+	///
+	/// An item is a duplicate if an earlier item has the same key, value, language, and description.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	/// use lofty::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// for _ in 0..2 {
+	/// 	tag.push(TagItem::new(
+	/// 		ItemKey::Genre,
+	/// 		ItemValue::Text(String::from("Rock")),
+	/// 	));
+	/// }
+	///
+	/// tag.normalize(NormalizeOptions::new());
+	/// assert_eq!(tag.item_count(), 1);
+	/// ```
+	pub fn remove_duplicates(mut self, remove_duplicates: bool) -> Self {
+		self.remove_duplicates = remove_duplicates;
+		self
+	}
+}
+
+impl Default for NormalizeOptions {
+	/// The default implementation for `NormalizeOptions`
+	///
+	/// The defaults are as follows:
+	///
+	/// ```rust,ignore
+	/// NormalizeOptions {
+	/// 	trim_whitespace: true,
+	/// 	remove_empty: true,
+	/// 	canonicalize_numbers: true,
+	/// 	fix_zero_padded_dates: true,
+	/// 	remove_duplicates: true,
+	/// }
+	/// ```
+	fn default() -> Self {
+		Self::new()
+	}
+}
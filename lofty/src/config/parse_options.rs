@@ -1,3 +1,6 @@
+use crate::error::{ErrorKind, LoftyError, ParseDiagnostic, Result};
+use crate::util::diagnostics;
+
 /// Options to control how Lofty parses a file
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -8,6 +11,13 @@ pub struct ParseOptions {
 	pub(crate) max_junk_bytes: usize,
 	pub(crate) read_cover_art: bool,
 	pub(crate) implicit_conversions: bool,
+	pub(crate) max_tag_size: Option<u64>,
+	pub(crate) max_item_size: Option<u64>,
+	pub(crate) mpeg_sync_frames: u8,
+	pub(crate) text_decode_fallback: TextDecodeFallback,
+	pub(crate) duplicate_tag_policy: DuplicateTagPolicy,
+	pub(crate) io_hint: IoHint,
+	pub(crate) picture_data_policy: PictureDataPolicy,
 }
 
 impl Default for ParseOptions {
@@ -23,6 +33,13 @@ impl Default for ParseOptions {
 	///     max_junk_bytes: 1024,
 	///     read_cover_art: true,
 	///     implicit_conversions: true,
+	///     max_tag_size: None,
+	///     max_item_size: None,
+	///     mpeg_sync_frames: 3,
+	///     text_decode_fallback: TextDecodeFallback::None,
+	///     duplicate_tag_policy: DuplicateTagPolicy::Merge,
+	///     io_hint: IoHint::Local,
+	///     picture_data_policy: PictureDataPolicy::Load,
 	/// }
 	/// ```
 	fn default() -> Self {
@@ -37,6 +54,9 @@ impl ParseOptions {
 	/// Default number of junk bytes to read
 	pub const DEFAULT_MAX_JUNK_BYTES: usize = 1024;
 
+	/// Default number of consecutive MPEG frames required for sync
+	pub const DEFAULT_MPEG_SYNC_FRAMES: u8 = 3;
+
 	/// Creates a new `ParseOptions`, alias for `Default` implementation
 	///
 	/// See also: [`ParseOptions::default`]
@@ -57,6 +77,13 @@ impl ParseOptions {
 			max_junk_bytes: Self::DEFAULT_MAX_JUNK_BYTES,
 			read_cover_art: true,
 			implicit_conversions: true,
+			max_tag_size: None,
+			max_item_size: None,
+			mpeg_sync_frames: Self::DEFAULT_MPEG_SYNC_FRAMES,
+			text_decode_fallback: TextDecodeFallback::None,
+			duplicate_tag_policy: DuplicateTagPolicy::Merge,
+			io_hint: IoHint::Local,
+			picture_data_policy: PictureDataPolicy::Load,
 		}
 	}
 
@@ -162,6 +189,320 @@ impl ParseOptions {
 		self.implicit_conversions = implicit_conversions;
 		*self
 	}
+
+	/// The maximum size of a tag, in bytes
+	///
+	/// Tags that claim to be larger than this will not be read. With [`ParsingMode::Strict`], this
+	/// results in an [`ErrorKind::TooLarge`](crate::error::ErrorKind::TooLarge) error, otherwise
+	/// the tag is skipped.
+	///
+	/// This applies to the tag as a whole, see [`ParseOptions::max_item_size`] for limiting individual items.
+	///
+	/// By default, there is no limit.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	///
+	/// // I don't trust my input, no tag should be larger than 16 MiB
+	/// let parsing_options = ParseOptions::new().max_tag_size(16 * 1024 * 1024);
+	/// ```
+	pub fn max_tag_size(&mut self, max_tag_size: u64) -> Self {
+		self.max_tag_size = Some(max_tag_size);
+		*self
+	}
+
+	/// The maximum size of a single tag item, in bytes
+	///
+	/// This applies to APE item values, ID3v2 frames, FLAC metadata blocks, and Vorbis Comments fields.
+	/// Items that claim to be larger than this will not be read. With [`ParsingMode::Strict`], this
+	/// results in an [`ErrorKind::TooLarge`](crate::error::ErrorKind::TooLarge) error, otherwise
+	/// the item is skipped.
+	///
+	/// By default, there is no limit.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	///
+	/// // I don't trust my input, no item should be larger than 1 MiB
+	/// let parsing_options = ParseOptions::new().max_item_size(1024 * 1024);
+	/// ```
+	pub fn max_item_size(&mut self, max_item_size: u64) -> Self {
+		self.max_item_size = Some(max_item_size);
+		*self
+	}
+
+	/// The number of consecutive, matching MPEG frame headers required before accepting a frame sync
+	///
+	/// This also applies to the ADTS frame headers of AAC files.
+	///
+	/// Files may start with junk (e.g. radio rips, or files truncated at the start), which can contain
+	/// bytes that look like a frame header. Requiring multiple frames makes it far less likely to sync
+	/// on a false header, which would result in wildly incorrect properties. When probing, the junk is
+	/// only searched for up to [`ParseOptions::max_junk_bytes`] bytes.
+	///
+	/// If the stream ends before this many frames are found, the available frames are accepted.
+	///
+	/// NOTE: This will be clamped to a minimum of 2
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	///
+	/// // My files are full of junk, be extra sure
+	/// let parsing_options = ParseOptions::new().mpeg_sync_frames(5);
+	/// ```
+	pub fn mpeg_sync_frames(&mut self, mpeg_sync_frames: u8) -> Self {
+		self.mpeg_sync_frames = mpeg_sync_frames.max(2);
+		*self
+	}
+
+	/// How to decode ID3v2 text that doesn't match its declared encoding
+	///
+	/// Some software writes text in a legacy encoding (e.g. GBK or Windows-1251), or mislabels
+	/// UTF-8 and UTF-16 text. By default, text that can't be decoded with its declared encoding
+	/// is an error. See [`TextDecodeFallback`] for the alternatives.
+	///
+	/// Regardless of this option, UTF-16 text without a byte order mark is read as little-endian.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, TextDecodeFallback};
+	///
+	/// // My files were tagged by old software, I'd rather have some text than none
+	/// let parsing_options = ParseOptions::new().text_decode_fallback(TextDecodeFallback::Lossy);
+	/// ```
+	pub fn text_decode_fallback(&mut self, text_decode_fallback: TextDecodeFallback) -> Self {
+		self.text_decode_fallback = text_decode_fallback;
+		*self
+	}
+
+	/// How to handle a file with multiple tags of the same type
+	///
+	/// Some software adds a new tag rather than updating the existing one. See [`DuplicateTagPolicy`]
+	/// for the options.
+	///
+	/// NOTE: This currently only applies to `ID3 ` chunks in WAV and AIFF files, and to consecutive
+	/// ID3v2 tags at the start of a file (up to 5). When the file is saved, all of the chunks or tags are
+	/// replaced with a single one.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{DuplicateTagPolicy, ParseOptions};
+	///
+	/// // My broadcast software appends a new tag on every edit, the last one is the most recent
+	/// let parsing_options = ParseOptions::new().duplicate_tag_policy(DuplicateTagPolicy::LastWins);
+	/// ```
+	pub fn duplicate_tag_policy(&mut self, duplicate_tag_policy: DuplicateTagPolicy) -> Self {
+		self.duplicate_tag_policy = duplicate_tag_policy;
+		*self
+	}
+
+	/// How expensive it is to seek in the reader
+	///
+	/// By default, readers seek freely, for example to the end of the file to look for trailing tags.
+	/// When every seek is costly (e.g. a file served over HTTP range requests), [`IoHint::StreamingRemote`]
+	/// can be used to skip any seeks that only gather optional information. See [`IoHint`] for what
+	/// is unavailable in that case.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{IoHint, ParseOptions};
+	///
+	/// // My files are on a server, and every seek is a new request
+	/// let parsing_options = ParseOptions::new().io_hint(IoHint::StreamingRemote);
+	/// ```
+	pub fn io_hint(&mut self, io_hint: IoHint) -> Self {
+		self.io_hint = io_hint;
+		*self
+	}
+
+	/// How the data of embedded pictures is handled
+	///
+	/// Unlike [`ParseOptions::read_cover_art`], [`PictureDataPolicy::Skip`] still reads everything but
+	/// the picture data itself, so the pictures remain available. This avoids holding large pictures
+	/// in memory when they aren't needed. See [`PictureDataPolicy`] for the supported formats.
+	///
+	/// To extract the skipped data, see [`Probe::pictures_to_writers`](crate::probe::Probe::pictures_to_writers).
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, PictureDataPolicy};
+	///
+	/// // I only want to know which pictures exist
+	/// let parsing_options = ParseOptions::new().picture_data_policy(PictureDataPolicy::Skip);
+	/// ```
+	pub fn picture_data_policy(&mut self, picture_data_policy: PictureDataPolicy) -> Self {
+		self.picture_data_policy = picture_data_policy;
+		*self
+	}
+
+	/// Whether a tag of `size` bytes should be read
+	///
+	/// Returns `Ok(false)` if the tag should be skipped.
+	pub(crate) fn verify_tag_size(&self, size: u64) -> Result<bool> {
+		self.verify_size(self.max_tag_size, size)
+	}
+
+	/// Whether an item of `size` bytes should be read
+	///
+	/// Returns `Ok(false)` if the item should be skipped.
+	pub(crate) fn verify_item_size(&self, size: u64) -> Result<bool> {
+		self.verify_size(self.max_item_size, size)
+	}
+
+	fn verify_size(&self, limit: Option<u64>, requested: u64) -> Result<bool> {
+		let Some(limit) = limit else {
+			return Ok(true);
+		};
+
+		if requested <= limit {
+			return Ok(true);
+		}
+
+		if self.parsing_mode == ParsingMode::Strict {
+			return Err(LoftyError::new(ErrorKind::TooLarge { limit, requested }));
+		}
+
+		log::warn!("Skipping {requested} bytes, exceeding the configured limit of {limit} bytes");
+		diagnostics::report(ParseDiagnostic::TooLarge { limit, requested });
+		Ok(false)
+	}
+}
+
+/// How to handle a file with multiple tags of the same type
+///
+/// This can be set with [`ParseOptions::duplicate_tag_policy`].
+///
+/// Whenever duplicates are found, a [`ParseDiagnostic::DuplicateId3Chunk`](crate::error::ParseDiagnostic::DuplicateId3Chunk)
+/// or [`ParseDiagnostic::StackedId3v2Tag`](crate::error::ParseDiagnostic::StackedId3v2Tag) is reported.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum DuplicateTagPolicy {
+	/// Append the frames of each additional tag to the first, replacing any conflicting frames
+	#[default]
+	Merge,
+	/// Keep the first tag, and discard the rest
+	FirstWins,
+	/// Keep the last tag, and discard the rest
+	LastWins,
+	/// Duplicate tags are an error
+	Error,
+}
+
+/// How the data of embedded pictures is handled
+///
+/// This can be set with [`ParseOptions::picture_data_policy`].
+///
+/// NOTE: This currently applies to FLAC picture blocks (in both FLAC and Ogg FLAC files), ID3v2 `APIC`
+///       frames, and APE cover art items.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum PictureDataPolicy {
+	/// Read the picture data into memory
+	#[default]
+	Load,
+	/// Skip over the picture data, only reading the type, MIME type, and description
+	///
+	/// The resulting [`Picture`](crate::picture::Picture)s have no data, their original size is
+	/// available through [`Picture::skipped_data_len`](crate::picture::Picture::skipped_data_len).
+	/// Such pictures cannot be written, doing so will result in [`ErrorKind::SkippedPictureData`].
+	///
+	/// APE stores cover art as generic binary items, which cannot represent a skipped picture.
+	/// Those items are left out of the tag entirely, though their data is still streamed when using
+	/// [`Probe::pictures_to_writers`](crate::probe::Probe::pictures_to_writers).
+	Skip,
+}
+
+/// How expensive it is to seek in a reader
+///
+/// This can be set with [`ParseOptions::io_hint`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum IoHint {
+	/// Seeking is cheap, such as in a local file or an in-memory buffer
+	#[default]
+	Local,
+	/// Seeking is expensive, such as in a file served over HTTP range requests
+	///
+	/// Readers will avoid any seeks that aren't needed to read the primary tag and the audio properties,
+	/// at the cost of the following:
+	///
+	/// * MPEG: ID3v1, Lyrics3v2, and APE tags at the end of the file are not searched for
+	/// * AAC (ADTS): ID3v1 tags are not searched for
+	/// * FLAC: [`FlacProperties::overall_bitrate`] and [`FlacProperties::audio_bitrate`] are 0, as they
+	///   require the length of the file. The duration is still taken from `STREAMINFO`.
+	///
+	/// NOTE: This has no effect on formats with a primary tag at the end of the file (APE, WavPack, and Musepack).
+	///
+	/// [`FlacProperties::overall_bitrate`]: crate::flac::FlacProperties::overall_bitrate
+	/// [`FlacProperties::audio_bitrate`]: crate::flac::FlacProperties::audio_bitrate
+	StreamingRemote,
+}
+
+/// How to decode text that doesn't match its declared encoding
+///
+/// This can be set with [`ParseOptions::text_decode_fallback`].
+///
+/// Whenever a fallback is used, a [`ParseDiagnostic::TextDecodeFallback`](crate::error::ParseDiagnostic::TextDecodeFallback) is reported.
+///
+/// NOTE: This currently only applies to ID3v2 frames.
+#[derive(Copy, Clone, Debug, Eq, Default)]
+#[non_exhaustive]
+pub enum TextDecodeFallback {
+	/// Text that can't be decoded is an error
+	///
+	/// Depending on the [`ParsingMode`], the item may be discarded, or the entire tag.
+	#[default]
+	None,
+	/// Decode the text as ISO-8859-1, which never fails
+	Latin1,
+	/// Decode the text with its declared encoding, replacing any invalid sequences with `U+FFFD`
+	Lossy,
+	/// Decode the text with a custom function
+	///
+	/// Since ISO-8859-1 text can always be decoded, this is also used for any ISO-8859-1 text
+	/// that isn't plain ASCII. This makes it possible to read text written in a legacy code
+	/// page, which is often mislabeled as ISO-8859-1.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, TextDecodeFallback};
+	///
+	/// fn decode_cp1251(bytes: &[u8]) -> String {
+	/// 	// A real decoder would map all of Windows-1251
+	/// 	bytes
+	/// 		.iter()
+	/// 		.map(|&b| match b {
+	/// 			0xC0..=0xFF => char::from_u32(0x0410 + u32::from(b - 0xC0)).unwrap(),
+	/// 			_ => char::from(b),
+	/// 		})
+	/// 		.collect()
+	/// }
+	///
+	/// let parsing_options =
+	/// 	ParseOptions::new().text_decode_fallback(TextDecodeFallback::Custom(decode_cp1251));
+	/// ```
+	Custom(fn(&[u8]) -> String),
+}
+
+impl PartialEq for TextDecodeFallback {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			// Function pointers are compared by address, which is the best we can do
+			(Self::Custom(a), Self::Custom(b)) => *a as usize == *b as usize,
+			_ => std::mem::discriminant(self) == std::mem::discriminant(other),
+		}
+	}
 }
 
 /// The parsing strictness mode
@@ -214,5 +555,9 @@ pub enum ParsingMode {
 	///
 	/// * Unable to decode text - The entire item is discarded and the parser moves on
 	/// * Unable to determine the sample rate - The sample rate will be 0
+	/// * Unable to read the audio properties (e.g. due to a truncated stream) - The tags are still
+	///   returned, with partial or zeroed properties. See [`ParseDiagnostic::IncompleteProperties`].
+	///
+	/// [`ParseDiagnostic::IncompleteProperties`]: crate::error::ParseDiagnostic::IncompleteProperties
 	Relaxed,
 }
@@ -0,0 +1,139 @@
+/// Options to control the output of the `pretty_print` methods
+///
+/// The following types can be pretty printed:
+///
+/// * [`Tag::pretty_print`](crate::tag::Tag::pretty_print)
+/// * [`ApeTag::pretty_print`](crate::ape::ApeTag::pretty_print)
+/// * [`Id3v2Tag::pretty_print`](crate::id3::v2::Id3v2Tag::pretty_print)
+/// * [`VorbisComments::pretty_print`](crate::ogg::VorbisComments::pretty_print)
+/// * [`Ilst::pretty_print`](crate::mp4::Ilst::pretty_print)
+/// * [`FileProperties::pretty_print`](crate::properties::FileProperties::pretty_print)
+///
+/// # Format
+///
+/// The output is line-oriented, with each line ending in `\n`. Tags print one line per item (and picture),
+/// consisting of three tab-separated columns:
+///
+/// ```text
+/// <key>\t<type>\t<value>
+/// ```
+///
+/// * `key` - The key as it is stored in the tag. ID3v2 frames use their frame ID, followed by the language
+///   and/or description if the frame has one (ex. `COMM:eng:Description`, `TXXX:BARCODE`).
+/// * `type` - The type of the value, such as `Text` or `Binary`. ID3v2 frames use the name of their
+///   [`Frame`](crate::id3::v2::Frame) variant, and MP4 atoms use the name of their
+///   [`AtomData`](crate::mp4::AtomData) variant.
+/// * `value` - The value. Text is escaped (`\\`, `\t`, `\n`, `\r`, `\0`, and `\u{..}` for any other control
+///   characters) and truncated to [`PrettyPrintOptions::max_value_len`] characters, with `...` appended if
+///   anything was cut off. Binary values are shown as their size (ex. `16 bytes`), and pictures as their
+///   type, MIME type, and size (ex. `CoverFront, image/png, 16 bytes`).
+///
+/// [`FileProperties`](crate::properties::FileProperties) prints one line per known property, with
+/// only two columns:
+///
+/// ```text
+/// <property>\t<value>
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::config::PrettyPrintOptions;
+/// use lofty::prelude::*;
+/// use lofty::tag::{Tag, TagType};
+///
+/// # fn main() -> std::fmt::Result {
+/// let mut tag = Tag::new(TagType::VorbisComments);
+/// tag.set_title(String::from("Foo title"));
+/// tag.set_artist(String::from("Bar artist"));
+///
+/// let mut output = String::new();
+/// tag.pretty_print(&mut output, PrettyPrintOptions::new().sort_keys(true))?;
+///
+/// assert_eq!(
+/// 	output,
+/// 	"TrackArtist\tText\tBar artist\nTrackTitle\tText\tFoo title\n"
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PrettyPrintOptions {
+	pub(crate) sort_keys: bool,
+	pub(crate) max_value_len: usize,
+}
+
+impl PrettyPrintOptions {
+	/// Default maximum number of characters to print for a value
+	pub const DEFAULT_MAX_VALUE_LEN: usize = 64;
+
+	/// Creates a new `PrettyPrintOptions`, alias for `Default` implementation
+	///
+	/// See also: [`PrettyPrintOptions::default`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::PrettyPrintOptions;
+	///
+	/// let pretty_print_options = PrettyPrintOptions::new();
+	/// ```
+	pub const fn new() -> Self {
+		Self {
+			sort_keys: false,
+			max_value_len: Self::DEFAULT_MAX_VALUE_LEN,
+		}
+	}
+
+	/// Whether to sort the lines by their key
+	///
+	/// By default, lines are printed in the order they are stored in the tag. Sorting gives the same
+	/// output for tags with the same content, which is useful for comparing or parsing the output.
+	///
+	/// Keys are compared byte-wise, and lines with the same key keep their original order.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::PrettyPrintOptions;
+	///
+	/// let pretty_print_options = PrettyPrintOptions::new().sort_keys(true);
+	/// ```
+	pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+		self.sort_keys = sort_keys;
+		self
+	}
+
+	/// The maximum number of characters to print for a text value
+	///
+	/// This is counted before escaping. Use `usize::MAX` to never truncate.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::PrettyPrintOptions;
+	///
+	/// // I want to see the full lyrics
+	/// let pretty_print_options = PrettyPrintOptions::new().max_value_len(usize::MAX);
+	/// ```
+	pub fn max_value_len(mut self, max_value_len: usize) -> Self {
+		self.max_value_len = max_value_len;
+		self
+	}
+}
+
+impl Default for PrettyPrintOptions {
+	/// The default implementation for `PrettyPrintOptions`
+	///
+	/// The defaults are as follows:
+	///
+	/// ```rust,ignore
+	/// PrettyPrintOptions {
+	/// 	sort_keys: false,
+	/// 	max_value_len: Self::DEFAULT_MAX_VALUE_LEN,
+	/// }
+	/// ```
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -10,12 +10,26 @@ pub struct WriteOptions {
 	pub(crate) respect_read_only: bool,
 	pub(crate) uppercase_id3v2_chunk: bool,
 	pub(crate) use_id3v23: bool,
+	pub(crate) use_id3v22: bool,
+	pub(crate) drop_invalid_icons: bool,
+	pub(crate) id3v23_separator: &'static str,
+	pub(crate) normalize_ape_items: bool,
+	pub(crate) invalid_field_name_policy: InvalidFieldNamePolicy,
+	pub(crate) strict_id3v1: bool,
+	pub(crate) preserve_modified_time: bool,
+	pub(crate) riff_info_placement: RiffInfoPlacement,
+	pub(crate) unsynchronisation: bool,
+	pub(crate) fix_picture_mime_types: bool,
+	pub(crate) preserve_id3v2_size: bool,
 }
 
 impl WriteOptions {
-	/// Default preferred padding size in bytes
+	/// Default preferred padding size in bytes, used when [`WriteOptions::preferred_padding`] is not set
 	pub const DEFAULT_PREFERRED_PADDING: u32 = 1024;
 
+	/// Default separator for multi-value ID3v2.3 text frames
+	pub const DEFAULT_ID3V23_SEPARATOR: &'static str = "/";
+
 	/// Creates a new `WriteOptions`, alias for `Default` implementation
 	///
 	/// See also: [`WriteOptions::default`]
@@ -29,11 +43,22 @@ impl WriteOptions {
 	/// ```
 	pub const fn new() -> Self {
 		Self {
-			preferred_padding: Some(Self::DEFAULT_PREFERRED_PADDING),
+			preferred_padding: None,
 			remove_others: false,
 			respect_read_only: true,
 			uppercase_id3v2_chunk: true,
 			use_id3v23: false,
+			use_id3v22: false,
+			drop_invalid_icons: false,
+			id3v23_separator: Self::DEFAULT_ID3V23_SEPARATOR,
+			normalize_ape_items: false,
+			invalid_field_name_policy: InvalidFieldNamePolicy::Skip,
+			strict_id3v1: false,
+			preserve_modified_time: false,
+			riff_info_placement: RiffInfoPlacement::PreserveExisting,
+			unsynchronisation: false,
+			fix_picture_mime_types: false,
+			preserve_id3v2_size: false,
 		}
 	}
 
@@ -46,6 +71,11 @@ impl WriteOptions {
 	///
 	/// * Not all tag formats support padding
 	/// * The actual padding size may be different from this value, depending on tag size limitations
+	/// * If this is never set, [`WriteOptions::DEFAULT_PREFERRED_PADDING`] bytes are used, except
+	///   when rewriting FLAC files (see below)
+	/// * FLAC files will reuse any existing padding if the new metadata fits, to avoid rewriting the
+	///   entire file. This value is only used when the file has to be rewritten, and if it is never
+	///   set, 4 KiB of padding is written instead.
 	///
 	/// # Examples
 	///
@@ -59,10 +89,7 @@ impl WriteOptions {
 	/// let options = WriteOptions::new().preferred_padding(0);
 	/// ```
 	pub fn preferred_padding(mut self, preferred_padding: u32) -> Self {
-		match preferred_padding {
-			0 => self.preferred_padding = None,
-			_ => self.preferred_padding = Some(preferred_padding),
-		}
+		self.preferred_padding = Some(preferred_padding);
 		self
 	}
 
@@ -156,6 +183,10 @@ impl WriteOptions {
 	///
 	/// By default, Lofty will save ID3v2.4 tags. This option allows you to save ID3v2.3 tags instead.
 	///
+	/// Regardless of the version the tag was read from, frames are converted to the target version
+	/// where possible (e.g. `TDRC` <-> `TYER`/`TDAT`/`TIME`, `TDOR` <-> `TORY`, and `TIPL`/`TMCL` <-> `IPLS`).
+	/// Frames without an equivalent in the target version are discarded.
+	///
 	/// # Examples
 	///
 	/// ```rust,no_run
@@ -177,6 +208,372 @@ impl WriteOptions {
 		self.use_id3v23 = use_id3v23;
 		*self
 	}
+
+	/// Whether or not to use ID3v2.2 when saving [`TagType::Id3v2`](crate::tag::TagType::Id3v2)
+	/// or [`Id3v2Tag`](crate::id3::v2::Id3v2Tag)
+	///
+	/// This is only meant for software that can't read anything newer. ID3v2.2 frames are converted
+	/// the same as with [`WriteOptions::use_id3v23`], and are then written with their 3 character
+	/// IDs (e.g. `TIT2` -> `TT2`). Frames without an ID3v2.2 equivalent are discarded, as are pictures
+	/// that aren't PNG or JPEG.
+	///
+	/// NOTE: ID3v2.2 tags have no extended header, footer, or frame flags, so the CRC, restrictions,
+	///       and frame flags (other than unsynchronisation) are not written.
+	///
+	/// This takes precedence over [`WriteOptions::use_id3v23`].
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Tag::new(TagType::Id3v2);
+	///
+	/// // ...
+	///
+	/// // My car stereo only reads ID3v2.2 tags
+	/// let options = WriteOptions::new().use_id3v22(true);
+	/// id3v2_tag.save_to_path("test.mp3", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn use_id3v22(mut self, use_id3v22: bool) -> Self {
+		self.use_id3v22 = use_id3v22;
+		self
+	}
+
+	/// Whether to drop invalid file icons when writing FLAC pictures
+	///
+	/// FLAC (and Vorbis Comments, which use the same picture format) only allow for one
+	/// [`PictureType::Icon`] and one [`PictureType::OtherIcon`]. Additionally, a [`PictureType::Icon`]
+	/// must be a 32x32 PNG.
+	///
+	/// By default, writing pictures that break these rules will result in an error. If set to `true`,
+	/// any extra icons (after the first of each type) and invalid [`PictureType::Icon`]s will be
+	/// discarded instead.
+	///
+	/// [`PictureType::Icon`]: crate::picture::PictureType::Icon
+	/// [`PictureType::OtherIcon`]: crate::picture::PictureType::OtherIcon
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut vorbis_comments = Tag::new(TagType::VorbisComments);
+	///
+	/// // ...
+	///
+	/// // I don't care about extra icons, just get rid of them
+	/// let options = WriteOptions::new().drop_invalid_icons(true);
+	/// vorbis_comments.save_to_path("test.flac", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn drop_invalid_icons(mut self, drop_invalid_icons: bool) -> Self {
+		self.drop_invalid_icons = drop_invalid_icons;
+		self
+	}
+
+	/// The separator to use for multi-value text frames when saving ID3v2.3 tags
+	///
+	/// ID3v2.4 allows text frames to hold multiple values, separated by null terminators.
+	/// ID3v2.3 has no such concept, so when [`WriteOptions::use_id3v23`] is set, multiple
+	/// values will instead be joined with this separator.
+	///
+	/// NOTE: This has no effect on `TCON` (genres), which has its own format in ID3v2.3.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{ItemValue, Tag, TagItem, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Tag::new(TagType::Id3v2);
+	/// id3v2_tag.push(TagItem::new(ItemKey::TrackArtist, ItemValue::Text(String::from("Foo"))));
+	/// id3v2_tag.push(TagItem::new(ItemKey::TrackArtist, ItemValue::Text(String::from("Bar"))));
+	///
+	/// // The artist will be written as "Foo; Bar"
+	/// let options = WriteOptions::new().use_id3v23(true).id3v23_separator("; ");
+	/// id3v2_tag.save_to_path("test.mp3", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn id3v23_separator(mut self, separator: &'static str) -> Self {
+		self.id3v23_separator = separator;
+		self
+	}
+
+	/// Whether to write APE items in a canonical order, with normalized key casing
+	///
+	/// By default, APE items are written in the order they appear in the tag, which depends on
+	/// the order they were read and inserted in. If set to `true`, the same items will always
+	/// produce the same bytes:
+	///
+	/// * `Track`, `Disc`, and `Year` are written first, as the spec recommends writing smaller items first
+	/// * Other text and locator items follow, sorted case-insensitively by key
+	/// * Binary items (such as pictures) are written last, sorted the same way
+	/// * Standard keys are written with the spec's capitalization (e.g. "TITLE" becomes "Title",
+	///   and "cover art (front)" becomes "Cover Art (Front)")
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::ape::ApeTag;
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut ape_tag = ApeTag::new();
+	///
+	/// // ...
+	///
+	/// // I compare my files byte-for-byte, so the output needs to be stable
+	/// let options = WriteOptions::new().normalize_ape_items(true);
+	/// ape_tag.save_to_path("test.ape", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn normalize_ape_items(mut self, normalize_ape_items: bool) -> Self {
+		self.normalize_ape_items = normalize_ape_items;
+		self
+	}
+
+	/// How to handle Vorbis Comments field names that are invalid
+	///
+	/// Field names may only consist of ASCII 0x20 through 0x7D, excluding '='. [`VorbisComments`]
+	/// will never hold such names, but a generic [`Tag`] with [`ItemKey::Unknown`] keys, or a tag read
+	/// with [`ParsingMode::Relaxed`], may.
+	///
+	/// See [`InvalidFieldNamePolicy`].
+	///
+	/// [`VorbisComments`]: crate::ogg::VorbisComments
+	/// [`Tag`]: crate::tag::Tag
+	/// [`ItemKey::Unknown`]: crate::tag::ItemKey::Unknown
+	/// [`ParsingMode::Relaxed`]: crate::config::ParsingMode::Relaxed
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::{InvalidFieldNamePolicy, WriteOptions};
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{ItemValue, Tag, TagItem, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// tag.push_unchecked(TagItem::new(
+	/// 	ItemKey::Unknown(String::from("MY=KEY")),
+	/// 	ItemValue::Text(String::from("Foo")),
+	/// ));
+	///
+	/// // The field will be written as "MY_KEY=Foo"
+	/// let options = WriteOptions::new().invalid_field_name_policy(InvalidFieldNamePolicy::Sanitize);
+	/// tag.save_to_path("test.flac", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn invalid_field_name_policy(mut self, policy: InvalidFieldNamePolicy) -> Self {
+		self.invalid_field_name_policy = policy;
+		self
+	}
+
+	/// Whether to error when ID3v1 fields can't be written as-is
+	///
+	/// ID3v1 fields are fixed-size Latin-1 strings. By default, text that doesn't fit is truncated
+	/// at a character boundary, and characters outside of Latin-1 are transliterated (e.g. "…" becomes
+	/// "...") or dropped.
+	///
+	/// If set to `true`, writing will instead fail with [`ErrorKind::LossyId3v1Fields`], listing the
+	/// affected fields.
+	///
+	/// [`ErrorKind::LossyId3v1Fields`]: crate::error::ErrorKind::LossyId3v1Fields
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v1_tag = Tag::new(TagType::Id3v1);
+	/// id3v1_tag.set_title(String::from("A title far too long to fit in an ID3v1 tag"));
+	///
+	/// // I'd rather know when my titles get cut off
+	/// let options = WriteOptions::new().strict_id3v1(true);
+	/// assert!(id3v1_tag.save_to_path("test.mp3", options).is_err());
+	/// # Ok(()) }
+	/// ```
+	pub fn strict_id3v1(mut self, strict_id3v1: bool) -> Self {
+		self.strict_id3v1 = strict_id3v1;
+		self
+	}
+
+	/// Whether to keep the modification time of the original file
+	///
+	/// NOTE: This only applies to [`AudioFile::save_to_path_atomic`], as the file is replaced
+	///       rather than written in place.
+	///
+	/// [`AudioFile::save_to_path_atomic`]: crate::file::AudioFile::save_to_path_atomic
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::file::AudioFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let tagged_file = lofty::read_from_path(path)?;
+	///
+	/// // My library is sorted by modification time, leave it alone
+	/// let options = WriteOptions::new().preserve_modified_time(true);
+	/// tagged_file.save_to_path_atomic(path, options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn preserve_modified_time(mut self, preserve_modified_time: bool) -> Self {
+		self.preserve_modified_time = preserve_modified_time;
+		self
+	}
+
+	/// Where to place the RIFF INFO `LIST` chunk when writing WAV files
+	///
+	/// See [`RiffInfoPlacement`].
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::{RiffInfoPlacement, WriteOptions};
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut riff_info = Tag::new(TagType::RiffInfo);
+	///
+	/// // ...
+	///
+	/// // My sampler only reads the INFO chunk if it comes before the audio
+	/// let options = WriteOptions::new().riff_info_placement(RiffInfoPlacement::BeforeData);
+	/// riff_info.save_to_path("test.wav", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn riff_info_placement(mut self, riff_info_placement: RiffInfoPlacement) -> Self {
+		self.riff_info_placement = riff_info_placement;
+		self
+	}
+
+	/// Whether to apply unsynchronisation when writing ID3v2 tags
+	///
+	/// Unsynchronisation inserts a `0x00` after any `0xFF` byte that could be mistaken for an MPEG
+	/// frame sync, which some very old players and hardware require. Modern software handles
+	/// tags without it, so it only makes the tag larger.
+	///
+	/// * ID3v2.3 - The entire tag (following the header) is unsynchronised
+	/// * ID3v2.4 - Each frame is unsynchronised separately, and flagged as such
+	///
+	/// NOTE: Tags with [`Id3v2TagFlags::unsynchronisation`](crate::id3::v2::Id3v2TagFlags::unsynchronisation)
+	///       set (such as those read from an unsynchronised tag) are always written unsynchronised.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Tag::new(TagType::Id3v2);
+	///
+	/// // ...
+	///
+	/// // My car stereo only reads unsynchronised ID3v2.3 tags
+	/// let options = WriteOptions::new().use_id3v23(true).unsynchronisation(true);
+	/// id3v2_tag.save_to_path("test.mp3", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn unsynchronisation(mut self, unsynchronisation: bool) -> Self {
+		self.unsynchronisation = unsynchronisation;
+		self
+	}
+
+	/// Whether to correct the declared MIME type of pictures when writing
+	///
+	/// By default, pictures are written with the MIME type they were read or created with, even if
+	/// it doesn't match the data (see [`Picture::verify`]).
+	///
+	/// If set to `true`, the MIME type detected from the data (see [`Picture::detect_mime`]) is
+	/// written instead. When it can't be detected, nonstandard names of known MIME types are
+	/// normalized (e.g. "image/jpg" becomes "image/jpeg").
+	///
+	/// This also applies to FLAC pictures with the `-->` (URL) MIME type that contain binary data,
+	/// which are otherwise rejected with [`ErrorKind::BinaryPictureUrl`].
+	///
+	/// NOTE: This applies to ID3v2 `APIC` frames, FLAC picture blocks, and Vorbis Comments pictures.
+	///       The other formats either don't store a MIME type, or already derive it from the data.
+	///
+	/// [`Picture::verify`]: crate::picture::Picture::verify
+	/// [`Picture::detect_mime`]: crate::picture::Picture::detect_mime
+	/// [`ErrorKind::BinaryPictureUrl`]: crate::error::ErrorKind::BinaryPictureUrl
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::file::AudioFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let tagged_file = lofty::read_from_path(path)?;
+	///
+	/// // My player refuses PNG covers labeled as JPEG
+	/// let options = WriteOptions::new().fix_picture_mime_types(true);
+	/// tagged_file.save_to_path(path, options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn fix_picture_mime_types(mut self, fix_picture_mime_types: bool) -> Self {
+		self.fix_picture_mime_types = fix_picture_mime_types;
+		self
+	}
+
+	/// Whether to keep the size of an existing ID3v2 tag when writing
+	///
+	/// If set to `true`, and the new ID3v2 tag fits in the space of the existing one, it will be
+	/// padded up to the existing size and written over it. This leaves the rest of the file
+	/// untouched, rather than moving the audio data.
+	///
+	/// When the new tag doesn't fit, or the file has more than one ID3v2 tag, it is written with
+	/// [`WriteOptions::preferred_padding`] as usual.
+	///
+	/// NOTES:
+	///
+	/// * This applies to MPEG, AAC, AIFF, and WAV files. For AIFF and WAV, the new tag is padded up to
+	///   the size of the existing `ID3 ` chunk.
+	/// * Tags with a footer can't be padded, so they are only written in place if the size is an exact match.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Tag::new(TagType::Id3v2);
+	///
+	/// // ...
+	///
+	/// // My files are seeded, so only the tag should change
+	/// let options = WriteOptions::new().preserve_id3v2_size(true);
+	/// id3v2_tag.save_to_path("test.mp3", options)?;
+	/// # Ok(()) }
+	/// ```
+	pub fn preserve_id3v2_size(mut self, preserve_id3v2_size: bool) -> Self {
+		self.preserve_id3v2_size = preserve_id3v2_size;
+		self
+	}
 }
 
 impl Default for WriteOptions {
@@ -186,14 +583,64 @@ impl Default for WriteOptions {
 	///
 	/// ```rust,ignore
 	/// WriteOptions {
-	///     preferred_padding: 1024,
+	///     preferred_padding: None, // 1024 bytes, or 4 KiB when rewriting FLAC files
 	///     remove_others: false,
 	///     respect_read_only: true,
 	///     uppercase_id3v2_chunk: true,
 	///     use_id3v23: false,
+	///     use_id3v22: false,
+	///     drop_invalid_icons: false,
+	///     id3v23_separator: "/",
+	///     normalize_ape_items: false,
+	///     invalid_field_name_policy: InvalidFieldNamePolicy::Skip,
+	///     strict_id3v1: false,
+	///     preserve_modified_time: false,
+	///     riff_info_placement: RiffInfoPlacement::PreserveExisting,
+	///     unsynchronisation: false,
+	///     fix_picture_mime_types: false,
+	///     preserve_id3v2_size: false,
 	/// }
 	/// ```
 	fn default() -> Self {
 		Self::new()
 	}
 }
+
+/// How to handle invalid field names when writing
+///
+/// This can be set with [`WriteOptions::invalid_field_name_policy`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum InvalidFieldNamePolicy {
+	/// Discard the field, logging a warning
+	#[default]
+	Skip,
+	/// Replace every invalid character with '_'
+	///
+	/// Empty field names can't be sanitized, and are always discarded.
+	Sanitize,
+	/// Invalid field names are an error
+	///
+	/// See [`ErrorKind::InvalidVorbisFieldName`](crate::error::ErrorKind::InvalidVorbisFieldName).
+	Error,
+}
+
+/// Where to place the RIFF INFO `LIST` chunk in a WAV file
+///
+/// Some software expects the INFO chunk to come before the `data` chunk, as it may stop reading
+/// once it reaches the audio. All other chunks are left untouched, regardless of the placement.
+///
+/// This can be set with [`WriteOptions::riff_info_placement`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[non_exhaustive]
+pub enum RiffInfoPlacement {
+	/// Replace an existing INFO chunk in place, otherwise append it to the end of the file
+	#[default]
+	PreserveExisting,
+	/// Place the INFO chunk directly before the `data` chunk
+	///
+	/// NOTE: This requires shifting the audio data when the chunk is created or moved.
+	BeforeData,
+	/// Place the INFO chunk directly after the `data` chunk
+	AfterData,
+}
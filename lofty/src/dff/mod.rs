@@ -0,0 +1,31 @@
+//! DSDIFF specific items
+mod properties;
+mod read;
+
+use crate::id3::v2::tag::Id3v2Tag;
+
+use lofty_attr::LoftyFile;
+
+// Exports
+pub use properties::{DffCompressionType, DffProperties};
+
+/// A DSDIFF (DFF) file
+///
+/// DSDIFF has no official support for ID3v2, though many applications store a tag in an
+/// `ID3 ` chunk. Along with that tag, the following chunks will be mapped to ID3v2 frames
+/// when they are not already present in the tag:
+///
+/// * `DIIN` - The artist (`DIAR`) and title (`DITI`)
+/// * `COMT` - The comments
+///
+/// NOTE: The tag is currently **read only**.
+#[derive(LoftyFile, Default)]
+#[lofty(read_fn = "read::read_from")]
+#[lofty(internal_write_module_do_not_use_anywhere_else)]
+pub struct DffFile {
+	/// An ID3v2 tag
+	#[lofty(tag_type = "Id3v2")]
+	pub(crate) id3v2_tag: Option<Id3v2Tag>,
+	/// The file's audio properties
+	pub(crate) properties: DffProperties,
+}
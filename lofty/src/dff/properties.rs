@@ -0,0 +1,89 @@
+use crate::properties::{ChannelMask, FileProperties};
+
+use std::time::Duration;
+
+/// The compression type of a DSDIFF file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DffCompressionType {
+	/// Uncompressed DSD
+	#[default]
+	Dsd,
+	/// Direct Stream Transfer (lossless compression)
+	Dst,
+}
+
+/// A DSDIFF file's audio properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct DffProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u16,
+	pub(crate) channel_mask: Option<ChannelMask>,
+	pub(crate) compression_type: DffCompressionType,
+}
+
+impl From<DffProperties> for FileProperties {
+	fn from(input: DffProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bit_depth),
+			channels: Some(input.channels as u8),
+			channel_mask: input.channel_mask,
+		}
+	}
+}
+
+impl DffProperties {
+	/// Duration of the audio
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	///
+	/// This will be a multiple of 44.1 kHz, such as 2822400 (DSD64) or 5644800 (DSD128).
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	///
+	/// DSD audio is always 1 bit.
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u16 {
+		self.channels
+	}
+
+	/// Channel mask
+	pub fn channel_mask(&self) -> Option<ChannelMask> {
+		self.channel_mask
+	}
+
+	/// Compression type
+	pub fn compression_type(&self) -> DffCompressionType {
+		self.compression_type
+	}
+}
@@ -0,0 +1,354 @@
+use super::properties::{DffCompressionType, DffProperties};
+use super::DffFile;
+use crate::config::ParseOptions;
+use crate::error::Result;
+use crate::id3::v2::header::Id3v2Header;
+use crate::id3::v2::read::parse_id3v2;
+use crate::id3::v2::tag::Id3v2Tag;
+use crate::macros::{decode_err, err, try_vec};
+use crate::properties::ChannelMask;
+use crate::tag::{Accessor, TagLocation, TagPlacement, TagType};
+use crate::util::tag_locations;
+use crate::util::text::{latin1_decode, utf8_decode_str};
+
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+/// The size of a chunk header (ID + 64-bit size)
+const CHUNK_HEADER_SIZE: u64 = 12;
+
+fn read_chunk_header<R>(reader: &mut R) -> Result<([u8; 4], u64)>
+where
+	R: Read,
+{
+	let mut id = [0; 4];
+	reader.read_exact(&mut id)?;
+
+	let size = reader.read_u64::<BigEndian>()?;
+	Ok((id, size))
+}
+
+fn read_chunk_content<R>(reader: &mut R, size: u64) -> Result<Vec<u8>>
+where
+	R: Read,
+{
+	let mut content = try_vec![0; size as usize];
+	reader.read_exact(&mut content)?;
+	Ok(content)
+}
+
+// The spec only allows for ASCII, but UTF-8 is commonly used
+fn decode_text(bytes: &[u8]) -> String {
+	match utf8_decode_str(bytes) {
+		Ok(text) => text.to_string(),
+		Err(_) => latin1_decode(bytes),
+	}
+}
+
+#[derive(Default)]
+struct TextChunks {
+	artist: Option<String>,
+	title: Option<String>,
+	comments: Vec<String>,
+}
+
+pub(super) fn read_from<R>(reader: &mut R, parse_options: ParseOptions) -> Result<DffFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let (id, form_size) = read_chunk_header(reader)?;
+
+	let mut form_type = [0; 4];
+	reader.read_exact(&mut form_type)?;
+
+	if &id != b"FRM8" || &form_type != b"DSD " {
+		err!(UnknownFormat);
+	}
+
+	let form_end = start + file_length.min(form_size.saturating_add(CHUNK_HEADER_SIZE));
+
+	let mut properties = DffProperties::default();
+	let mut stream_length = 0;
+	let mut dst_frames = None;
+
+	let mut id3v2_tag: Option<Id3v2Tag> = None;
+	let mut text_chunks = TextChunks::default();
+
+	let mut pos = reader.stream_position()?;
+	while pos + CHUNK_HEADER_SIZE <= form_end {
+		let (id, size) = read_chunk_header(reader)?;
+
+		let content_start = pos + CHUNK_HEADER_SIZE;
+		let Some(content_end) = content_start
+			.checked_add(size)
+			.filter(|end| *end <= form_end)
+		else {
+			log::warn!(
+				"DFF chunk {:?} extends past the end of the file, stopping",
+				String::from_utf8_lossy(&id)
+			);
+			break;
+		};
+
+		match &id {
+			b"PROP" if parse_options.read_properties => {
+				let content = read_chunk_content(reader, size)?;
+				read_prop_chunk(&mut &*content, &mut properties)?;
+			},
+			b"DSD " => stream_length = size,
+			b"DST " => {
+				stream_length = size;
+				properties.compression_type = DffCompressionType::Dst;
+
+				// The "FRTE" chunk is required to be the first chunk in the "DST " chunk
+				if parse_options.read_properties && size >= CHUNK_HEADER_SIZE + 6 {
+					let (sub_id, _) = read_chunk_header(reader)?;
+					if &sub_id == b"FRTE" {
+						let num_frames = reader.read_u32::<BigEndian>()?;
+						let frame_rate = reader.read_u16::<BigEndian>()?;
+						dst_frames = Some((num_frames, frame_rate));
+					}
+				}
+			},
+			b"COMT" if parse_options.read_tags => {
+				let content = read_chunk_content(reader, size)?;
+				text_chunks.comments = read_comments(&mut &*content)?;
+			},
+			b"DIIN" if parse_options.read_tags => {
+				let content = read_chunk_content(reader, size)?;
+				read_diin_chunk(&mut &*content, &mut text_chunks)?;
+			},
+			b"ID3 " | b"id3 " if parse_options.read_tags => {
+				if id3v2_tag.is_some() {
+					log::warn!("Duplicate ID3v2 tag found, ignoring");
+				} else {
+					let content = read_chunk_content(reader, size)?;
+					let reader = &mut &*content;
+
+					let header = Id3v2Header::parse(reader)?;
+					tag_locations::record(
+						TagLocation::new(
+							TagType::Id3v2,
+							pos,
+							CHUNK_HEADER_SIZE + size,
+							TagPlacement::Embedded,
+						)
+						.with_header(true)
+						.with_footer(header.flags.footer),
+					);
+
+					id3v2_tag = Some(parse_id3v2(reader, header, parse_options)?);
+				}
+			},
+			_ => {},
+		}
+
+		// Chunks are padded to an even size
+		pos = content_end + (size & 1);
+		reader.seek(SeekFrom::Start(pos))?;
+	}
+
+	if parse_options.read_properties {
+		if properties.sample_rate == 0 || properties.channels == 0 {
+			decode_err!(@BAIL Dff, "File does not contain a valid \"PROP\" chunk");
+		}
+
+		properties.bit_depth = 1;
+		finalize_properties(&mut properties, stream_length, dst_frames, file_length);
+	}
+
+	if parse_options.read_tags {
+		merge_text_chunks(&mut id3v2_tag, text_chunks);
+	}
+
+	Ok(DffFile {
+		id3v2_tag,
+		properties,
+	})
+}
+
+fn read_prop_chunk(content: &mut &[u8], properties: &mut DffProperties) -> Result<()> {
+	let mut property_type = [0; 4];
+	content.read_exact(&mut property_type)?;
+
+	if &property_type != b"SND " {
+		decode_err!(@BAIL Dff, "File has an unknown \"PROP\" chunk type");
+	}
+
+	while content.len() as u64 >= CHUNK_HEADER_SIZE {
+		let (id, size) = read_chunk_header(content)?;
+
+		// Chunks are padded to an even size
+		let padded_size = size.saturating_add(size & 1);
+		if padded_size > content.len() as u64 {
+			decode_err!(@BAIL Dff, "File has an invalid \"PROP\" chunk");
+		}
+
+		let (mut chunk, remaining) = content.split_at(padded_size as usize);
+		*content = remaining;
+
+		match &id {
+			b"FS  " => properties.sample_rate = chunk.read_u32::<BigEndian>()?,
+			b"CHNL" => {
+				properties.channels = chunk.read_u16::<BigEndian>()?;
+
+				// Unknown channel IDs ("Cxxx") can't be represented in a channel mask
+				let mut channel_mask = Some(ChannelMask::default());
+				for _ in 0..properties.channels {
+					let mut channel_id = [0; 4];
+					chunk.read_exact(&mut channel_id)?;
+
+					let channel = match &channel_id {
+						b"SLFT" | b"MLFT" => Some(ChannelMask::FRONT_LEFT),
+						b"SRGT" | b"MRGT" => Some(ChannelMask::FRONT_RIGHT),
+						b"LS  " => Some(ChannelMask::BACK_LEFT),
+						b"RS  " => Some(ChannelMask::BACK_RIGHT),
+						b"C   " => Some(ChannelMask::FRONT_CENTER),
+						b"LFE " => Some(ChannelMask::LOW_FREQUENCY),
+						_ => None,
+					};
+
+					channel_mask = channel_mask.zip(channel).map(|(mask, c)| mask | c);
+				}
+
+				properties.channel_mask =
+					channel_mask.filter(|mask| *mask != ChannelMask::default());
+			},
+			b"CMPR" => {
+				let mut compression_type = [0; 4];
+				chunk.read_exact(&mut compression_type)?;
+
+				match &compression_type {
+					b"DSD " => properties.compression_type = DffCompressionType::Dsd,
+					b"DST " => properties.compression_type = DffCompressionType::Dst,
+					_ => decode_err!(@BAIL Dff, "File has an unknown compression type"),
+				}
+			},
+			_ => {},
+		}
+	}
+
+	Ok(())
+}
+
+fn finalize_properties(
+	properties: &mut DffProperties,
+	stream_length: u64,
+	dst_frames: Option<(u32, u16)>,
+	file_length: u64,
+) {
+	let length = match properties.compression_type {
+		DffCompressionType::Dsd => {
+			let sample_count = stream_length * 8 / u64::from(properties.channels);
+			(sample_count as f64 * 1000.0) / f64::from(properties.sample_rate)
+		},
+		DffCompressionType::Dst => match dst_frames {
+			Some((num_frames, frame_rate)) if frame_rate > 0 => {
+				(f64::from(num_frames) * 1000.0) / f64::from(frame_rate)
+			},
+			_ => {
+				log::warn!("Unable to calculate duration, no valid \"FRTE\" chunk found");
+				0.0
+			},
+		},
+	};
+
+	properties.duration = Duration::from_millis(length as u64);
+	if length > 0.0 {
+		properties.overall_bitrate = ((file_length as f64 * 8.0) / length) as u32;
+	}
+
+	properties.audio_bitrate = match properties.compression_type {
+		DffCompressionType::Dsd => {
+			(u64::from(properties.sample_rate) * u64::from(properties.channels) / 1000) as u32
+		},
+		DffCompressionType::Dst if length > 0.0 => ((stream_length as f64 * 8.0) / length) as u32,
+		DffCompressionType::Dst => 0,
+	};
+}
+
+fn read_comments(content: &mut &[u8]) -> Result<Vec<String>> {
+	let num_comments = content.read_u16::<BigEndian>()?;
+
+	let mut comments = Vec::with_capacity(usize::from(num_comments).min(content.len()));
+	for _ in 0..num_comments {
+		// Timestamp (year, month, day, hour, minutes), comment type, and comment reference
+		let mut comment_info = [0; 10];
+		content.read_exact(&mut comment_info)?;
+
+		let count = content.read_u32::<BigEndian>()? as usize;
+		if count > content.len() {
+			decode_err!(@BAIL Dff, "File has an invalid \"COMT\" chunk");
+		}
+
+		let (text, remaining) = content.split_at(count);
+		comments.push(decode_text(text));
+
+		// Comments are padded to an even size
+		*content = remaining.get(count & 1..).unwrap_or_default();
+	}
+
+	Ok(comments)
+}
+
+fn read_diin_chunk(content: &mut &[u8], text_chunks: &mut TextChunks) -> Result<()> {
+	while content.len() as u64 >= CHUNK_HEADER_SIZE {
+		let (id, size) = read_chunk_header(content)?;
+
+		let padded_size = size.saturating_add(size & 1);
+		if padded_size > content.len() as u64 {
+			decode_err!(@BAIL Dff, "File has an invalid \"DIIN\" chunk");
+		}
+
+		let (mut chunk, remaining) = content.split_at(padded_size as usize);
+		*content = remaining;
+
+		let target = match &id {
+			b"DIAR" => &mut text_chunks.artist,
+			b"DITI" => &mut text_chunks.title,
+			_ => continue,
+		};
+
+		let count = chunk.read_u32::<BigEndian>()? as usize;
+		let Some(text) = chunk.get(..count) else {
+			decode_err!(@BAIL Dff, "File has an invalid \"DIIN\" chunk");
+		};
+
+		*target = Some(decode_text(text));
+	}
+
+	Ok(())
+}
+
+// The text chunks only fill in what is missing from the ID3v2 tag
+fn merge_text_chunks(id3v2_tag: &mut Option<Id3v2Tag>, text_chunks: TextChunks) {
+	let TextChunks {
+		artist,
+		title,
+		comments,
+	} = text_chunks;
+
+	if artist.is_none() && title.is_none() && comments.is_empty() {
+		return;
+	}
+
+	let tag = id3v2_tag.get_or_insert_with(Id3v2Tag::default);
+
+	if let Some(artist) = artist.filter(|_| tag.artist().is_none()) {
+		tag.set_artist(artist);
+	}
+
+	if let Some(title) = title.filter(|_| tag.title().is_none()) {
+		tag.set_title(title);
+	}
+
+	if !comments.is_empty() && tag.comment().is_none() {
+		tag.set_comment(comments.join("\0"));
+	}
+}
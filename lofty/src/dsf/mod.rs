@@ -0,0 +1,22 @@
+//! DSF specific items
+mod properties;
+pub(crate) mod read;
+
+use crate::id3::v2::tag::Id3v2Tag;
+
+use lofty_attr::LoftyFile;
+
+// Exports
+pub use properties::DsfProperties;
+
+/// A DSF (DSD Stream File) file
+#[derive(LoftyFile, Default)]
+#[lofty(read_fn = "read::read_from")]
+#[lofty(internal_write_module_do_not_use_anywhere_else)]
+pub struct DsfFile {
+	/// An ID3v2 tag
+	#[lofty(tag_type = "Id3v2")]
+	pub(crate) id3v2_tag: Option<Id3v2Tag>,
+	/// The file's audio properties
+	pub(crate) properties: DsfProperties,
+}
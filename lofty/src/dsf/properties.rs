@@ -0,0 +1,169 @@
+use crate::error::Result;
+use crate::macros::decode_err;
+use crate::properties::{ChannelMask, FileProperties};
+
+use std::io::Read;
+use std::time::Duration;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+/// A DSF file's audio properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct DsfProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+	pub(crate) channel_mask: Option<ChannelMask>,
+	pub(crate) sample_count: u64,
+}
+
+impl From<DsfProperties> for FileProperties {
+	fn from(input: DsfProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bit_depth),
+			channels: Some(input.channels),
+			channel_mask: input.channel_mask,
+		}
+	}
+}
+
+impl DsfProperties {
+	/// Duration of the audio
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	///
+	/// This will be a multiple of 44.1 kHz, such as 2822400 (DSD64) or 5644800 (DSD128).
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	///
+	/// DSD audio is always 1 bit.
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Channel mask
+	pub fn channel_mask(&self) -> Option<ChannelMask> {
+		self.channel_mask
+	}
+
+	/// Number of samples per channel
+	pub fn sample_count(&self) -> u64 {
+		self.sample_count
+	}
+}
+
+// The DSF channel types, see the "Channel Type" field of the "fmt " chunk
+fn channel_mask_from_channel_type(channel_type: u32) -> Option<ChannelMask> {
+	let mask = match channel_type {
+		// Mono
+		1 => ChannelMask::mono(),
+		// Stereo
+		2 => ChannelMask::stereo(),
+		// 3 channels
+		3 => ChannelMask::linear_surround(),
+		// Quad
+		4 => ChannelMask::stereo() | ChannelMask::BACK_LEFT | ChannelMask::BACK_RIGHT,
+		// 4 channels
+		5 => ChannelMask::linear_surround() | ChannelMask::LOW_FREQUENCY,
+		// 5 channels
+		6 => ChannelMask::linear_surround() | ChannelMask::BACK_LEFT | ChannelMask::BACK_RIGHT,
+		// 5.1 channels
+		7 => {
+			ChannelMask::linear_surround()
+				| ChannelMask::LOW_FREQUENCY
+				| ChannelMask::BACK_LEFT
+				| ChannelMask::BACK_RIGHT
+		},
+		_ => return None,
+	};
+
+	Some(mask)
+}
+
+pub(super) fn read_properties<R>(fmt_chunk: &mut R, file_length: u64) -> Result<DsfProperties>
+where
+	R: Read,
+{
+	let format_version = fmt_chunk.read_u32::<LittleEndian>()?;
+	if format_version != 1 {
+		log::warn!("Unknown DSF format version: {format_version}");
+	}
+
+	let format_id = fmt_chunk.read_u32::<LittleEndian>()?;
+	if format_id != 0 {
+		decode_err!(@BAIL Dsf, "File has an unsupported format ID (expected DSD raw)");
+	}
+
+	let channel_type = fmt_chunk.read_u32::<LittleEndian>()?;
+	let channel_num = fmt_chunk.read_u32::<LittleEndian>()?;
+	let sample_rate = fmt_chunk.read_u32::<LittleEndian>()?;
+
+	// This is 1 for LSB first, and 8 for MSB first. Either way, the audio is 1 bit.
+	let bits_per_sample = fmt_chunk.read_u32::<LittleEndian>()?;
+	if bits_per_sample != 1 && bits_per_sample != 8 {
+		decode_err!(@BAIL Dsf, "File has an invalid bits per sample (expected 1 or 8)");
+	}
+
+	let sample_count = fmt_chunk.read_u64::<LittleEndian>()?;
+
+	if channel_num == 0 || channel_num > 6 {
+		decode_err!(@BAIL Dsf, "File has an invalid channel count");
+	}
+
+	let mut properties = DsfProperties {
+		channels: channel_num as u8,
+		channel_mask: channel_mask_from_channel_type(channel_type),
+		sample_rate,
+		bit_depth: 1,
+		sample_count,
+		..DsfProperties::default()
+	};
+
+	if sample_rate == 0 || sample_count == 0 {
+		log::warn!("Unable to calculate duration, sample rate or sample count is 0");
+		return Ok(properties);
+	}
+
+	let length = (sample_count as f64 * 1000.0) / f64::from(sample_rate);
+
+	properties.duration = Duration::from_millis(length as u64);
+	if length > 0.0 {
+		properties.overall_bitrate = ((file_length as f64 * 8.0) / length) as u32;
+	}
+
+	// The audio data is padded to the block size, so the bitrate is calculated from the
+	// sample rate instead
+	properties.audio_bitrate = (u64::from(sample_rate) * u64::from(channel_num) / 1000) as u32;
+
+	Ok(properties)
+}
@@ -0,0 +1,112 @@
+use super::properties::DsfProperties;
+use super::DsfFile;
+use crate::config::ParseOptions;
+use crate::error::Result;
+use crate::id3::record_id3v2_location;
+use crate::id3::v2::header::Id3v2Header;
+use crate::id3::v2::read::parse_id3v2;
+use crate::macros::{decode_err, err, try_vec};
+use crate::tag::TagPlacement;
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+/// The size of the "DSD " chunk, including its ID and size
+pub(crate) const DSD_CHUNK_SIZE: u64 = 28;
+
+/// The "DSD " chunk, found at the start of every DSF file
+pub(crate) struct DsdChunk {
+	/// The total size of the file
+	pub(crate) file_size: u64,
+	/// The offset of the metadata (ID3v2) chunk, or 0 if there is none
+	pub(crate) metadata_offset: u64,
+}
+
+impl DsdChunk {
+	/// The position of the "total file size" field, which is followed by the metadata offset
+	pub(crate) const FILE_SIZE_POS: u64 = 12;
+
+	pub(crate) fn read<R>(reader: &mut R) -> Result<Self>
+	where
+		R: Read,
+	{
+		let mut id = [0; 4];
+		reader.read_exact(&mut id)?;
+
+		if &id != b"DSD " {
+			err!(UnknownFormat);
+		}
+
+		let chunk_size = reader.read_u64::<LittleEndian>()?;
+		if chunk_size != DSD_CHUNK_SIZE {
+			decode_err!(@BAIL Dsf, "File has an invalid \"DSD \" chunk size (expected 28)");
+		}
+
+		let file_size = reader.read_u64::<LittleEndian>()?;
+		let metadata_offset = reader.read_u64::<LittleEndian>()?;
+
+		Ok(Self {
+			file_size,
+			metadata_offset,
+		})
+	}
+}
+
+pub(super) fn read_from<R>(reader: &mut R, parse_options: ParseOptions) -> Result<DsfFile>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let file_length = reader.seek(SeekFrom::End(0))? - start;
+	reader.seek(SeekFrom::Start(start))?;
+
+	let dsd_chunk = DsdChunk::read(reader)?;
+	if dsd_chunk.file_size != file_length {
+		log::warn!(
+			"DSF file size mismatch, expected {} bytes, found {}",
+			dsd_chunk.file_size,
+			file_length
+		);
+	}
+
+	let mut id = [0; 4];
+	reader.read_exact(&mut id)?;
+	if &id != b"fmt " {
+		decode_err!(@BAIL Dsf, "File does not contain a \"fmt \" chunk");
+	}
+
+	let fmt_chunk_size = reader.read_u64::<LittleEndian>()?;
+	let Some(fmt_content_size) = fmt_chunk_size.checked_sub(12) else {
+		decode_err!(@BAIL Dsf, "File has an invalid \"fmt \" chunk size");
+	};
+
+	let mut properties = DsfProperties::default();
+	if parse_options.read_properties {
+		let mut fmt_content = try_vec![0; fmt_content_size as usize];
+		reader.read_exact(&mut fmt_content)?;
+
+		properties = super::properties::read_properties(&mut &*fmt_content, file_length)?;
+	} else {
+		reader.seek(SeekFrom::Current(fmt_content_size as i64))?;
+	}
+
+	let mut id3v2_tag = None;
+	if parse_options.read_tags && dsd_chunk.metadata_offset != 0 {
+		if dsd_chunk.metadata_offset >= file_length {
+			decode_err!(@BAIL Dsf, "File has an invalid metadata offset");
+		}
+
+		let offset = start + dsd_chunk.metadata_offset;
+		reader.seek(SeekFrom::Start(offset))?;
+
+		let header = Id3v2Header::parse(reader)?;
+		record_id3v2_location(&header, offset, TagPlacement::Tail);
+		id3v2_tag = Some(parse_id3v2(reader, header, parse_options)?);
+	}
+
+	Ok(DsfFile {
+		id3v2_tag,
+		properties,
+	})
+}
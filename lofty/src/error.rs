@@ -2,12 +2,19 @@
 //!
 //! The primary error is [`LoftyError`]. The type of error is determined by [`ErrorKind`],
 //! which can be extended at any time.
+//!
+//! For a coarser, stable grouping of errors (e.g. for user-facing messages), see [`LoftyError::category`].
+//! The underlying cause of an error, such as an [`std::io::Error`], is available through
+//! [`std::error::Error::source`].
 
 use crate::file::FileType;
 use crate::id3::v2::FrameId;
-use crate::tag::ItemKey;
+use crate::picture::{MimeType, PictureType};
+use crate::tag::{ItemKey, TagType};
+use crate::TextEncoding;
 
 use std::collections::TryReserveError;
+use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 
 use ogg_pager::PageError;
@@ -15,6 +22,70 @@ use ogg_pager::PageError;
 /// Alias for `Result<T, LoftyError>`
 pub type Result<T> = std::result::Result<T, LoftyError>;
 
+/// The underlying cause of an error
+type Source = Box<dyn Error + Send + Sync + 'static>;
+
+/// A stable grouping of [`ErrorKind`]s
+///
+/// Unlike [`ErrorKind`], which describes exactly what went wrong, this describes the *kind* of
+/// problem, and is intended for deciding how to handle an error (or what to tell a user).
+///
+/// See [`LoftyError::category`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+	/// An IO error occurred while reading or writing
+	///
+	/// The [`std::io::Error`] is available through [`LoftyError::source`](std::error::Error::source).
+	Io,
+	/// The format of the input could not be determined, or it isn't supported
+	UnsupportedFormat,
+	/// The file itself is corrupt or invalid
+	MalformedFile {
+		/// The type of the file, if known
+		file_type: Option<FileType>,
+	},
+	/// A tag within the file is corrupt or invalid
+	MalformedTag {
+		/// The type of the tag, if known
+		tag_type: Option<TagType>,
+	},
+	/// The input is too large to be read, or exceeds a configured limit
+	TooLarge,
+	/// The data could not be written to the file, as the format or the tag can't represent it
+	NotWritable,
+	/// Anything else
+	Other,
+}
+
+impl ErrorCategory {
+	/// A short, stable code for the category
+	///
+	/// These codes will not change between releases, making them suitable for logs and
+	/// translations.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::error::{ErrorCategory, ErrorKind, LoftyError};
+	///
+	/// let unknown_format = LoftyError::new(ErrorKind::UnknownFormat);
+	/// assert_eq!(unknown_format.category(), ErrorCategory::UnsupportedFormat);
+	/// assert_eq!(unknown_format.category().code(), "unsupported_format");
+	/// ```
+	pub fn code(self) -> &'static str {
+		match self {
+			ErrorCategory::Io => "io",
+			ErrorCategory::UnsupportedFormat => "unsupported_format",
+			ErrorCategory::MalformedFile { .. } => "malformed_file",
+			ErrorCategory::MalformedTag { .. } => "malformed_tag",
+			ErrorCategory::TooLarge => "too_large",
+			ErrorCategory::NotWritable => "not_writable",
+			ErrorCategory::Other => "other",
+		}
+	}
+}
+
 /// The types of errors that can occur
 #[derive(Debug)]
 #[non_exhaustive]
@@ -22,10 +93,24 @@ pub enum ErrorKind {
 	// File format related errors
 	/// Unable to guess the format
 	UnknownFormat,
+	/// Stream verification isn't supported for this format
+	///
+	/// See [`Probe::verify_stream`](crate::probe::Probe::verify_stream).
+	UnsupportedVerification(FileType),
 
 	// File data related errors
 	/// Attempting to read/write an abnormally large amount of data
 	TooMuchData,
+	/// A tag or item exceeded a size limit set in [`ParseOptions`](crate::config::ParseOptions)
+	///
+	/// See [`ParseOptions::max_tag_size`](crate::config::ParseOptions::max_tag_size) and
+	/// [`ParseOptions::max_item_size`](crate::config::ParseOptions::max_item_size).
+	TooLarge {
+		/// The configured limit
+		limit: u64,
+		/// The size claimed by the input
+		requested: u64,
+	},
 	/// Expected the data to be a different size than provided
 	///
 	/// This occurs when the size of an item is written as one value, but that size is either too
@@ -42,12 +127,49 @@ pub enum ErrorKind {
 	NotAPicture,
 	/// Attempted to write a picture that the format does not support
 	UnsupportedPicture,
+	/// Attempted to write multiple pictures of types that may only appear once
+	///
+	/// This contains each duplicated [`PictureType`].
+	DuplicatePictureTypes(Vec<PictureType>),
+	/// Attempted to write a [`PictureType::Icon`] that is not a 32x32 PNG
+	InvalidFileIcon,
+	/// Attempted to write a picture whose data was skipped while reading
+	///
+	/// See [`PictureDataPolicy::Skip`](crate::config::PictureDataPolicy::Skip).
+	SkippedPictureData,
+	/// A picture's declared MIME type doesn't match its data
+	///
+	/// See [`Picture::verify`](crate::picture::Picture::verify).
+	PictureMimeMismatch {
+		/// The MIME type stored with the picture
+		declared: Option<MimeType>,
+		/// The MIME type detected from the picture data, if it could be detected
+		detected: Option<MimeType>,
+	},
+	/// Attempted to write a FLAC picture with the `-->` (URL) MIME type, whose data isn't a URL
+	///
+	/// See [`WriteOptions::fix_picture_mime_types`](crate::config::WriteOptions::fix_picture_mime_types).
+	BinaryPictureUrl,
 
 	// Tag related errors
 	/// Arises when writing a tag to a file type that doesn't support it
 	UnsupportedTag,
 	/// Arises when a tag is expected (Ex. found an "ID3 " chunk in a WAV file), but isn't found
 	FakeTag,
+	/// Attempted to write ID3v1 fields that would have to be truncated or transliterated
+	///
+	/// This contains the name of each affected field. See [`WriteOptions::strict_id3v1`](crate::config::WriteOptions::strict_id3v1).
+	LossyId3v1Fields(Vec<&'static str>),
+	/// Attempted to write a Vorbis Comments field with an invalid name
+	///
+	/// See [`WriteOptions::invalid_field_name_policy`](crate::config::WriteOptions::invalid_field_name_policy).
+	InvalidVorbisFieldName(String),
+	/// Unable to replace the original file with the new one, while saving with
+	/// [`AudioFile::save_to_path_atomic`](crate::file::AudioFile::save_to_path_atomic)
+	///
+	/// This happens when the file system doesn't support renaming over an existing file. The
+	/// original file is left unchanged.
+	AtomicReplace(std::io::Error),
 	/// Errors that arise while decoding text
 	TextDecode(&'static str),
 	/// Arises when decoding OR encoding a problematic [`Timestamp`](crate::tag::items::Timestamp)
@@ -57,6 +179,8 @@ pub enum ErrorKind {
 
 	/// Arises when an atom contains invalid data
 	BadAtom(&'static str),
+	/// Arises when parsing an invalid [`CueSheet`](crate::flac::CueSheet)
+	BadCueSheet(&'static str),
 	/// Arises when attempting to use [`Atom::merge`](crate::mp4::Atom::merge) with mismatching identifiers
 	AtomMismatch,
 
@@ -91,6 +215,14 @@ pub enum Id3v2ErrorKind {
 	V2Compression,
 	/// Arises when an extended header has an invalid size (must be >= 6 bytes and less than the total tag size)
 	BadExtendedHeaderSize,
+	/// Arises when the CRC-32 in an extended header doesn't match the tag's frame data
+	/// with [ParsingMode::Strict](crate::config::ParsingMode::Strict)
+	CrcMismatch {
+		/// The CRC stated in the extended header
+		expected: u32,
+		/// The CRC of the frame data
+		actual: u32,
+	},
 
 	// Frame
 	/// Arises when a frame ID contains invalid characters (must be within `'A'..'Z'` or `'0'..'9'`)
@@ -103,7 +235,7 @@ pub enum Id3v2ErrorKind {
 	BadFrameLength,
 	/// Arises when a frame with no content is parsed with [ParsingMode::Strict](crate::config::ParsingMode::Strict)
 	EmptyFrame(FrameId<'static>),
-	/// Arises when reading/writing a compressed or encrypted frame with no data length indicator
+	/// Arises when writing a compressed, encrypted frame with no data length indicator
 	MissingDataLengthIndicator,
 	/// Arises when a frame or tag has its unsynchronisation flag set, but the content is not actually synchsafe
 	///
@@ -121,11 +253,33 @@ pub enum Id3v2ErrorKind {
 	BadRva2ChannelType,
 	/// Arises when decoding a [`TimestampFormat`](crate::id3::v2::TimestampFormat) with an invalid type
 	BadTimestampFormat,
+	/// Arises when the events of an [`EventTimingCodesFrame`](crate::id3::v2::EventTimingCodesFrame)
+	/// aren't in chronological order with [ParsingMode::Strict](crate::config::ParsingMode::Strict)
+	UnsortedEtcoEvents,
+	/// Arises when decoding an [`OwnershipFrame`](crate::id3::v2::OwnershipFrame) with a date of purchase
+	/// that isn't an 8 character date string (YYYYMMDD)
+	BadOwnershipDate,
+	/// Arises when decoding a [`CommercialFrame`](crate::id3::v2::CommercialFrame) with an unknown
+	/// [`ReceivedAs`](crate::id3::v2::ReceivedAs) with [ParsingMode::Strict](crate::config::ParsingMode::Strict)
+	BadCommercialReceivedAs(u8),
+	/// Arises when a [`ChapterFrame`](crate::id3::v2::ChapterFrame) or
+	/// [`TableOfContentsFrame`](crate::id3::v2::TableOfContentsFrame) is embedded in another
+	/// with [ParsingMode::Strict](crate::config::ParsingMode::Strict)
+	NestedChapterFrame(FrameId<'static>),
 
 	// Compression
 	#[cfg(feature = "id3v2_compression_support")]
 	/// Arises when a compressed frame is unable to be decompressed
 	Decompression(flate2::DecompressError),
+	#[cfg(feature = "id3v2_compression_support")]
+	/// Arises when a compressed frame's content doesn't match its stated decompressed size
+	/// with [ParsingMode::Strict](crate::config::ParsingMode::Strict)
+	DecompressedSizeMismatch {
+		/// The decompressed size stated in the frame
+		expected: u32,
+		/// The actual size of the decompressed content
+		actual: usize,
+	},
 	#[cfg(not(feature = "id3v2_compression_support"))]
 	/// Arises when a compressed frame is encountered, but support is disabled
 	CompressedFrameEncountered,
@@ -152,6 +306,11 @@ impl Display for Id3v2ErrorKind {
 			Self::BadExtendedHeaderSize => {
 				write!(f, "Found an extended header with an invalid size")
 			},
+			Self::CrcMismatch { expected, actual } => write!(
+				f,
+				"Extended header CRC ({expected:#010X}) doesn't match the frame data \
+				 ({actual:#010X})"
+			),
 
 			// Frame
 			Self::BadFrameId(frame_id) => write!(f, "Failed to parse a frame ID: 0x{frame_id:x?}"),
@@ -165,7 +324,7 @@ impl Display for Id3v2ErrorKind {
 			Self::EmptyFrame(id) => write!(f, "Frame `{id}` is empty"),
 			Self::MissingDataLengthIndicator => write!(
 				f,
-				"Encountered an encrypted frame without a data length indicator"
+				"Encountered a compressed, encrypted frame without a data length indicator"
 			),
 			Self::InvalidUnsynchronisation => write!(f, "Encountered an invalid unsynchronisation"),
 			Self::V2InvalidTextEncoding => {
@@ -181,10 +340,32 @@ impl Display for Id3v2ErrorKind {
 				f,
 				"Encountered an invalid timestamp format in a synchronized frame"
 			),
+			Self::UnsortedEtcoEvents => {
+				write!(f, "Encountered out of order events in ETCO frame")
+			},
+			Self::BadOwnershipDate => {
+				write!(f, "Encountered invalid date of purchase in OWNE frame")
+			},
+			Self::BadCommercialReceivedAs(received_as) => write!(
+				f,
+				"Encountered an unknown received as type ({received_as}) in COMR frame"
+			),
+			Self::NestedChapterFrame(id) => {
+				write!(
+					f,
+					"Encountered a nested `{id}` frame, chapters can't be nested"
+				)
+			},
 
 			// Compression
 			#[cfg(feature = "id3v2_compression_support")]
 			Self::Decompression(err) => write!(f, "Failed to decompress frame: {err}"),
+			#[cfg(feature = "id3v2_compression_support")]
+			Self::DecompressedSizeMismatch { expected, actual } => write!(
+				f,
+				"Compressed frame has a stated decompressed size of {expected}, but decompressed \
+				 to {actual} bytes"
+			),
 			#[cfg(not(feature = "id3v2_compression_support"))]
 			Self::CompressedFrameEncountered => write!(
 				f,
@@ -211,13 +392,23 @@ impl Display for Id3v2ErrorKind {
 /// An error that arises while interacting with an ID3v2 tag
 pub struct Id3v2Error {
 	kind: Id3v2ErrorKind,
+	source: Option<Source>,
 }
 
 impl Id3v2Error {
 	/// Create a new `ID3v2Error` from an [`Id3v2ErrorKind`]
 	#[must_use]
 	pub const fn new(kind: Id3v2ErrorKind) -> Self {
-		Self { kind }
+		Self { kind, source: None }
+	}
+
+	/// Attach the underlying cause of the error
+	///
+	/// This will be available through [`Error::source`].
+	#[must_use]
+	pub fn with_source(mut self, source: impl Into<Source>) -> Self {
+		self.source = Some(source.into());
+		self
 	}
 
 	/// Returns the [`Id3v2ErrorKind`]
@@ -226,6 +417,15 @@ impl Id3v2Error {
 	}
 }
 
+impl Error for Id3v2Error {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match &self.source {
+			Some(source) => Some(&**source),
+			None => None,
+		}
+	}
+}
+
 impl Debug for Id3v2Error {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		write!(f, "ID3v2: {:?}", self.kind)
@@ -241,7 +441,9 @@ impl Display for Id3v2Error {
 /// An error that arises while decoding a file
 pub struct FileDecodingError {
 	format: Option<FileType>,
+	tag_type: Option<TagType>,
 	description: &'static str,
+	source: Option<Source>,
 }
 
 impl FileDecodingError {
@@ -250,7 +452,22 @@ impl FileDecodingError {
 	pub const fn new(format: FileType, description: &'static str) -> Self {
 		Self {
 			format: Some(format),
+			tag_type: None,
+			description,
+			source: None,
+		}
+	}
+
+	/// Create a `FileDecodingError` for a tag, rather than a file
+	///
+	/// As tags can appear in many file types, this isn't bound to a [`FileType`].
+	#[must_use]
+	pub const fn from_tag(tag_type: TagType, description: &'static str) -> Self {
+		Self {
+			format: None,
+			tag_type: Some(tag_type),
 			description,
+			source: None,
 		}
 	}
 
@@ -258,25 +475,53 @@ impl FileDecodingError {
 	pub fn from_description(description: &'static str) -> Self {
 		Self {
 			format: None,
+			tag_type: None,
 			description,
+			source: None,
 		}
 	}
 
+	/// Attach the underlying cause of the error
+	///
+	/// This will be available through [`Error::source`].
+	#[must_use]
+	pub fn with_source(mut self, source: impl Into<Source>) -> Self {
+		self.source = Some(source.into());
+		self
+	}
+
 	/// Returns the associated [`FileType`], if one exists
 	pub fn format(&self) -> Option<FileType> {
 		self.format
 	}
 
+	/// Returns the associated [`TagType`], if the error occurred while decoding a tag
+	pub fn tag_type(&self) -> Option<TagType> {
+		self.tag_type
+	}
+
 	/// Returns the error description
 	pub fn description(&self) -> &str {
 		self.description
 	}
 }
 
+impl Error for FileDecodingError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match &self.source {
+			Some(source) => Some(&**source),
+			None => None,
+		}
+	}
+}
+
 impl Debug for FileDecodingError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		// The source is available through `Error::source()`, so it isn't repeated here
 		if let Some(format) = self.format {
 			write!(f, "{:?}: {:?}", format, self.description)
+		} else if let Some(tag_type) = self.tag_type {
+			write!(f, "{:?}: {:?}", tag_type, self.description)
 		} else {
 			write!(f, "{:?}", self.description)
 		}
@@ -287,6 +532,8 @@ impl Display for FileDecodingError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		if let Some(format) = self.format {
 			write!(f, "{:?}: {}", format, self.description)
+		} else if let Some(tag_type) = self.tag_type {
+			write!(f, "{:?}: {}", tag_type, self.description)
 		} else {
 			write!(f, "{}", self.description)
 		}
@@ -377,6 +624,8 @@ impl FileEncodingError {
 	}
 }
 
+impl Error for FileEncodingError {}
+
 impl Debug for FileEncodingError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		if let Some(format) = self.format {
@@ -432,9 +681,103 @@ impl LoftyError {
 	pub fn kind(&self) -> &ErrorKind {
 		&self.kind
 	}
+
+	/// Returns the [`ErrorCategory`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::error::ErrorCategory;
+	/// use lofty::read_from_path;
+	///
+	/// # fn main() {
+	/// match read_from_path("tests/files/assets/minimal/full_test.mp3") {
+	/// 	Ok(_) => {},
+	/// 	Err(e) => match e.category() {
+	/// 		ErrorCategory::Io => eprintln!("Unable to read the file: {e}"),
+	/// 		ErrorCategory::UnsupportedFormat => eprintln!("Not an audio file"),
+	/// 		ErrorCategory::MalformedTag { tag_type } => {
+	/// 			eprintln!("The file has a corrupt tag ({tag_type:?}): {e}")
+	/// 		},
+	/// 		_ => eprintln!("The file is corrupt: {e}"),
+	/// 	},
+	/// }
+	/// # }
+	/// ```
+	pub fn category(&self) -> ErrorCategory {
+		match self.kind {
+			ErrorKind::Io(_) | ErrorKind::AtomicReplace(_) => ErrorCategory::Io,
+
+			ErrorKind::UnknownFormat | ErrorKind::UnsupportedVerification(_) => {
+				ErrorCategory::UnsupportedFormat
+			},
+
+			ErrorKind::TooMuchData | ErrorKind::TooLarge { .. } | ErrorKind::Alloc(_) => {
+				ErrorCategory::TooLarge
+			},
+
+			ErrorKind::FileDecoding(ref err) => match err.tag_type {
+				Some(tag_type) => ErrorCategory::MalformedTag {
+					tag_type: Some(tag_type),
+				},
+				None => ErrorCategory::MalformedFile {
+					file_type: err.format,
+				},
+			},
+			ErrorKind::SizeMismatch | ErrorKind::OggPage(_) => {
+				ErrorCategory::MalformedFile { file_type: None }
+			},
+			ErrorKind::BadAtom(_) => ErrorCategory::MalformedFile {
+				file_type: Some(FileType::Mp4),
+			},
+			ErrorKind::BadCueSheet(_) => ErrorCategory::MalformedFile {
+				file_type: Some(FileType::Flac),
+			},
+
+			ErrorKind::Id3v2(_) => ErrorCategory::MalformedTag {
+				tag_type: Some(TagType::Id3v2),
+			},
+			ErrorKind::NotAPicture
+			| ErrorKind::PictureMimeMismatch { .. }
+			| ErrorKind::FakeTag
+			| ErrorKind::TextDecode(_)
+			| ErrorKind::BadTimestamp(_)
+			| ErrorKind::StringFromUtf8(_)
+			| ErrorKind::StrFromUtf8(_) => ErrorCategory::MalformedTag { tag_type: None },
+
+			ErrorKind::FileEncoding(_)
+			| ErrorKind::UnsupportedPicture
+			| ErrorKind::DuplicatePictureTypes(_)
+			| ErrorKind::InvalidFileIcon
+			| ErrorKind::SkippedPictureData
+			| ErrorKind::BinaryPictureUrl
+			| ErrorKind::UnsupportedTag
+			| ErrorKind::LossyId3v1Fields(_)
+			| ErrorKind::InvalidVorbisFieldName(_) => ErrorCategory::NotWritable,
+
+			ErrorKind::AtomMismatch | ErrorKind::Fmt(_) | ErrorKind::Infallible(_) => {
+				ErrorCategory::Other
+			},
+		}
+	}
 }
 
-impl std::error::Error for LoftyError {}
+impl Error for LoftyError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self.kind {
+			ErrorKind::Io(ref err) | ErrorKind::AtomicReplace(ref err) => Some(err),
+			ErrorKind::OggPage(ref err) => Some(err),
+			ErrorKind::StringFromUtf8(ref err) => Some(err),
+			ErrorKind::StrFromUtf8(ref err) => Some(err),
+			ErrorKind::Fmt(ref err) => Some(err),
+			ErrorKind::Alloc(ref err) => Some(err),
+			// These are already described by our `Display` implementation, so skip straight to their cause
+			ErrorKind::FileDecoding(ref err) => err.source(),
+			ErrorKind::Id3v2(ref err) => err.source(),
+			_ => None,
+		}
+	}
+}
 
 impl Debug for LoftyError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -536,21 +879,70 @@ impl Display for LoftyError {
 			ErrorKind::UnknownFormat => {
 				write!(f, "No format could be determined from the provided file")
 			},
+			ErrorKind::UnsupportedVerification(file_type) => write!(
+				f,
+				"Stream verification is not supported for {file_type:?} files"
+			),
 			ErrorKind::NotAPicture => write!(f, "Picture: Encountered invalid data"),
 			ErrorKind::UnsupportedPicture => {
 				write!(f, "Picture: attempted to write an unsupported picture")
 			},
+			ErrorKind::DuplicatePictureTypes(ref types) => write!(
+				f,
+				"Picture: attempted to write multiple pictures of types that may only appear \
+				 once: {types:?}"
+			),
+			ErrorKind::InvalidFileIcon => {
+				write!(f, "Picture: file icons must be 32x32 PNG images")
+			},
+			ErrorKind::SkippedPictureData => write!(
+				f,
+				"Picture: attempted to write a picture whose data was skipped while reading"
+			),
+			ErrorKind::PictureMimeMismatch {
+				ref declared,
+				ref detected,
+			} => {
+				let mime = |mime: &Option<MimeType>| match mime {
+					Some(mime) => format!("\"{mime}\""),
+					None => String::from("none"),
+				};
+
+				write!(
+					f,
+					"Picture: declared MIME type ({}) doesn't match the data ({})",
+					mime(declared),
+					mime(detected)
+				)
+			},
+			ErrorKind::BinaryPictureUrl => write!(
+				f,
+				"Picture: attempted to write a URL picture (`-->`) that contains binary data"
+			),
 			ErrorKind::UnsupportedTag => write!(
 				f,
 				"Attempted to write a tag to a format that does not support it"
 			),
 			ErrorKind::FakeTag => write!(f, "Reading: Expected a tag, found invalid data"),
+			ErrorKind::AtomicReplace(ref err) => write!(
+				f,
+				"Writing: Unable to replace the original file, it was left unchanged: {err}"
+			),
+			ErrorKind::LossyId3v1Fields(ref fields) => write!(
+				f,
+				"ID3v1: Fields can't be written without truncation or transliteration: {}",
+				fields.join(", ")
+			),
+			ErrorKind::InvalidVorbisFieldName(ref key) => {
+				write!(f, "Vorbis Comments: Invalid field name {key:?}")
+			},
 			ErrorKind::TextDecode(message) => write!(f, "Text decoding: {message}"),
 			ErrorKind::BadTimestamp(message) => {
 				write!(f, "Encountered an invalid timestamp: {message}")
 			},
 			ErrorKind::Id3v2(ref id3v2_err) => write!(f, "{id3v2_err}"),
 			ErrorKind::BadAtom(message) => write!(f, "MP4 Atom: {message}"),
+			ErrorKind::BadCueSheet(message) => write!(f, "Cue sheet: {message}"),
 			ErrorKind::AtomMismatch => write!(
 				f,
 				"MP4 Atom: Attempted to use `Atom::merge()` with mismatching identifiers"
@@ -561,6 +953,10 @@ impl Display for LoftyError {
 				f,
 				"Attempted to read/write an abnormally large amount of data"
 			),
+			ErrorKind::TooLarge { limit, requested } => write!(
+				f,
+				"Attempted to read {requested} bytes, exceeding the configured limit of {limit} bytes"
+			),
 			ErrorKind::SizeMismatch => write!(
 				f,
 				"Encountered an invalid item size, either too big or too small to be valid"
@@ -572,3 +968,280 @@ impl Display for LoftyError {
 		}
 	}
 }
+
+/// A recoverable issue encountered while parsing a file
+///
+/// Outside of [`ParsingMode::Strict`](crate::config::ParsingMode::Strict), Lofty will attempt to
+/// work around invalid data, usually by discarding it. Each time this happens, a diagnostic is recorded
+/// and made available through [`TaggedFile::diagnostics`](crate::file::TaggedFile::diagnostics).
+///
+/// When reading a file or tag directly, the diagnostics can be collected with
+/// [`AudioFile::read_from_with_diagnostics`](crate::file::AudioFile::read_from_with_diagnostics), or
+/// the `parse_with_diagnostics` method of the tag (e.g. [`Id3v2Tag::parse_with_diagnostics`](crate::id3::v2::Id3v2Tag::parse_with_diagnostics)).
+///
+/// Offsets are relative to the start of the input, unless stated otherwise, and are only provided
+/// where they are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseDiagnostic {
+	/// An APE item with an invalid key or an empty value was discarded
+	InvalidApeItemKey {
+		/// The item key
+		key: String,
+		/// The offset of the item
+		offset: u64,
+	},
+	/// An APE tag used the non-standard `Disk` key for the disc number, and the item was renamed
+	/// to `Disc`
+	LegacyApeDiscItem {
+		/// Whether the tag also contained a `Disc` item, which the `Disk` item was merged into
+		///
+		/// The values of the `Disc` item take precedence.
+		merged: bool,
+	},
+	/// An ID3v2 frame header could not be read, and the frame was skipped
+	InvalidId3v2FrameHeader {
+		/// The offset of the frame header from the start of the tag, if known
+		///
+		/// This is unknown for frames embedded in chapters, and for tags that are unsynchronised as
+		/// a whole.
+		offset: Option<u64>,
+		/// The error encountered while reading the header
+		message: String,
+	},
+	/// An ID3v2 frame claimed to be larger than the remainder of the tag, so its content
+	/// may be truncated
+	OversizedFrame {
+		/// The frame ID
+		id: String,
+		/// The offset of the frame header from the start of the tag, if known
+		///
+		/// See [`ParseDiagnostic::InvalidId3v2FrameHeader::offset`].
+		offset: Option<u64>,
+		/// The size of the frame
+		size: u32,
+		/// The number of bytes remaining in the tag
+		remaining: u64,
+	},
+	/// An ID3v2 tag contained multiple frames that are not allowed to coexist, and one was discarded
+	DuplicateId3v2Frame {
+		/// The frame ID
+		id: String,
+	},
+	/// The CRC-32 in an ID3v2 extended header didn't match the tag's frame data
+	Id3v2CrcMismatch {
+		/// The CRC stated in the extended header
+		expected: u32,
+		/// The CRC of the frame data
+		actual: u32,
+	},
+	/// A FLAC stream contained multiple Vorbis Comments blocks, only the last one was kept
+	MultipleVorbisCommentBlocks {
+		/// The offset of the additional block's header
+		offset: u64,
+	},
+	/// A FLAC picture block could not be read, and was discarded
+	InvalidFlacPicture {
+		/// The offset of the block's header
+		offset: u64,
+	},
+	/// A FLAC metadata block claimed to be larger than the remainder of the stream, and was
+	/// truncated
+	///
+	/// No further blocks are read after a truncated block.
+	OversizedFlacBlock {
+		/// The offset of the block's header
+		offset: u64,
+		/// The size of the block
+		size: u32,
+		/// The number of bytes remaining in the stream
+		remaining: u64,
+	},
+	/// A Vorbis Comments field was discarded, due to a missing separator, an invalid key, or a
+	/// value that isn't valid UTF-8
+	InvalidVorbisCommentField {
+		/// The field key, if it could be read
+		key: Option<String>,
+	},
+	/// A Vorbis Comments block declared more fields than could fit in it, so only as many fields as
+	/// could fit were read
+	TooManyVorbisCommentFields {
+		/// The declared number of fields
+		declared: u32,
+		/// The maximum number of fields that could fit in the block
+		max: u32,
+	},
+	/// A Vorbis Comments field claimed to be larger than the remainder of the block, and was discarded
+	///
+	/// No further fields are read after an oversized field.
+	OversizedVorbisCommentField {
+		/// The size of the field
+		size: u32,
+		/// The number of bytes remaining in the block
+		remaining: u64,
+	},
+	/// A Vorbis Comments field with an invalid name was kept, as [`ParsingMode::Relaxed`](crate::config::ParsingMode::Relaxed)
+	/// is in use
+	InvalidVorbisFieldName {
+		/// The field name, lossily decoded as UTF-8
+		key: String,
+	},
+	/// A WAV or AIFF file contained multiple `ID3 ` chunks, which were handled according to the
+	/// [`DuplicateTagPolicy`](crate::config::DuplicateTagPolicy)
+	DuplicateId3Chunk {
+		/// The offset of the additional chunk's header
+		offset: u64,
+	},
+	/// An ID3v2 tag was directly followed by another, which was handled according to the
+	/// [`DuplicateTagPolicy`](crate::config::DuplicateTagPolicy)
+	StackedId3v2Tag {
+		/// The offset of the additional tag's header
+		offset: u64,
+	},
+	/// An ID3v2 UTF-16 string had no byte order mark, so it was assumed to be little-endian
+	MissingUtf16Bom,
+	/// An ID3v2 string could not be decoded with its declared encoding, and was decoded with the
+	/// [`TextDecodeFallback`](crate::config::TextDecodeFallback) instead
+	TextDecodeFallback {
+		/// The declared encoding
+		encoding: TextEncoding,
+	},
+	/// A tag or item exceeded a size limit set in [`ParseOptions`](crate::config::ParseOptions),
+	/// and was skipped
+	TooLarge {
+		/// The configured limit
+		limit: u64,
+		/// The size claimed by the input
+		requested: u64,
+	},
+	/// The audio properties could not be read, most likely due to a truncated stream
+	///
+	/// This is only reported in [`ParsingMode::Relaxed`](crate::config::ParsingMode::Relaxed), where
+	/// the properties are left partially read or zeroed instead of failing. Values such as the
+	/// duration are unreliable.
+	IncompleteProperties {
+		/// The error encountered while reading the properties
+		message: String,
+	},
+}
+
+impl Display for ParseDiagnostic {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::InvalidApeItemKey { key, offset } => {
+				write!(
+					f,
+					"APE: Discarded item with invalid key {key:?} at offset {offset}"
+				)
+			},
+			Self::LegacyApeDiscItem { merged: false } => {
+				write!(f, "APE: Renamed item `Disk` to `Disc`")
+			},
+			Self::LegacyApeDiscItem { merged: true } => {
+				write!(f, "APE: Merged item `Disk` into `Disc`")
+			},
+			Self::InvalidId3v2FrameHeader {
+				offset: Some(offset),
+				message,
+			} => write!(
+				f,
+				"ID3v2: Skipped frame with invalid header at offset {offset}: {message}"
+			),
+			Self::InvalidId3v2FrameHeader {
+				offset: None,
+				message,
+			} => write!(f, "ID3v2: Skipped frame with invalid header: {message}"),
+			Self::OversizedFrame {
+				id,
+				offset: Some(offset),
+				size,
+				remaining,
+			} => write!(
+				f,
+				"ID3v2: Frame `{id}` at offset {offset} has a size of {size}, but only {remaining} \
+				 bytes remain in the tag"
+			),
+			Self::OversizedFrame {
+				id,
+				offset: None,
+				size,
+				remaining,
+			} => write!(
+				f,
+				"ID3v2: Frame `{id}` has a size of {size}, but only {remaining} bytes remain in the \
+				 tag"
+			),
+			Self::DuplicateId3v2Frame { id } => {
+				write!(f, "ID3v2: Discarded duplicate frame `{id}`")
+			},
+			Self::Id3v2CrcMismatch { expected, actual } => write!(
+				f,
+				"ID3v2: Extended header CRC ({expected:#010X}) doesn't match the frame data \
+				 ({actual:#010X})"
+			),
+			Self::MultipleVorbisCommentBlocks { offset } => write!(
+				f,
+				"FLAC: Found an additional Vorbis Comments block at offset {offset}"
+			),
+			Self::InvalidFlacPicture { offset } => {
+				write!(
+					f,
+					"FLAC: Discarded invalid picture block at offset {offset}"
+				)
+			},
+			Self::OversizedFlacBlock {
+				offset,
+				size,
+				remaining,
+			} => write!(
+				f,
+				"FLAC: Block at offset {offset} has a size of {size}, but only {remaining} bytes \
+				 remain in the stream"
+			),
+			Self::InvalidVorbisCommentField { key: Some(key) } => {
+				write!(f, "Vorbis Comments: Discarded invalid field {key:?}")
+			},
+			Self::InvalidVorbisCommentField { key: None } => {
+				write!(f, "Vorbis Comments: Discarded invalid field")
+			},
+			Self::TooManyVorbisCommentFields { declared, max } => write!(
+				f,
+				"Vorbis Comments: Block declares {declared} fields, but only {max} can fit"
+			),
+			Self::OversizedVorbisCommentField { size, remaining } => write!(
+				f,
+				"Vorbis Comments: Field has a size of {size}, but only {remaining} bytes remain in \
+				 the block"
+			),
+			Self::InvalidVorbisFieldName { key } => {
+				write!(
+					f,
+					"Vorbis Comments: Kept field with an invalid name {key:?}"
+				)
+			},
+			Self::DuplicateId3Chunk { offset } => {
+				write!(
+					f,
+					"WAV/AIFF: Found an additional ID3 chunk at offset {offset}"
+				)
+			},
+			Self::StackedId3v2Tag { offset } => {
+				write!(f, "ID3v2: Found an additional tag at offset {offset}")
+			},
+			Self::MissingUtf16Bom => {
+				write!(f, "ID3v2: UTF-16 string has no BOM, assuming little-endian")
+			},
+			Self::TextDecodeFallback { encoding } => write!(
+				f,
+				"ID3v2: Failed to decode {encoding:?} string, decoded with the fallback instead"
+			),
+			Self::TooLarge { limit, requested } => write!(
+				f,
+				"Skipped {requested} bytes, exceeding the configured limit of {limit} bytes"
+			),
+			Self::IncompleteProperties { message } => {
+				write!(f, "Properties may be incomplete: {message}")
+			},
+		}
+	}
+}
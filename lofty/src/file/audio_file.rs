@@ -1,7 +1,8 @@
 use super::tagged_file::TaggedFile;
 use crate::config::{ParseOptions, WriteOptions};
-use crate::error::{LoftyError, Result};
+use crate::error::{LoftyError, ParseDiagnostic, Result};
 use crate::tag::TagType;
+use crate::util::diagnostics;
 
 use crate::util::io::{FileLike, Length, Truncate};
 use std::fs::OpenOptions;
@@ -25,6 +26,83 @@ pub trait AudioFile: Into<TaggedFile> {
 		R: Read + Seek,
 		Self: Sized;
 
+	/// Read a file from a reader, collecting any [`ParseDiagnostic`]s
+	///
+	/// This is the same as [`AudioFile::read_from`], but also returns the recoverable issues
+	/// encountered while parsing. See [`ParseDiagnostic`] for more information.
+	///
+	/// # Errors
+	///
+	/// See [`AudioFile::read_from`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::mpeg::MpegFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let mut file = std::fs::File::open(path)?;
+	///
+	/// let (mpeg_file, diagnostics) =
+	/// 	MpegFile::read_from_with_diagnostics(&mut file, ParseOptions::new())?;
+	/// for diagnostic in diagnostics {
+	/// 	eprintln!("{diagnostic}");
+	/// }
+	/// # Ok(()) }
+	/// ```
+	fn read_from_with_diagnostics<R>(
+		reader: &mut R,
+		parse_options: ParseOptions,
+	) -> Result<(Self, Vec<ParseDiagnostic>)>
+	where
+		R: Read + Seek,
+		Self: Sized,
+	{
+		let (file, diagnostics) = diagnostics::collect(|| Self::read_from(reader, parse_options));
+		Ok((file?, diagnostics))
+	}
+
+	/// Read a file from an async reader
+	///
+	/// This is the async equivalent of [`AudioFile::read_from`]. Parsing itself is not async, the
+	/// data is fetched from the reader ahead of time, as it is needed.
+	///
+	/// # Errors
+	///
+	/// See [`AudioFile::read_from`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::flac::FlacFile;
+	///
+	/// # #[tokio::main(flavor = "current_thread")]
+	/// # async fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.flac";
+	/// let mut file = tokio::fs::File::open(path).await?;
+	///
+	/// let flac_file = FlacFile::read_from_async(&mut file, ParseOptions::new()).await?;
+	/// # Ok(()) }
+	/// ```
+	#[cfg(feature = "async")]
+	fn read_from_async<R>(
+		reader: &mut R,
+		parse_options: ParseOptions,
+	) -> impl std::future::Future<Output = Result<Self>> + Send
+	where
+		R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin + Send,
+		Self: Sized + Send,
+	{
+		crate::util::async_io::read_from_async(reader, move |reader| {
+			Self::read_from(reader, parse_options)
+		})
+	}
+
 	/// Attempts to write all tags to a path
 	///
 	/// # Errors
@@ -55,6 +133,52 @@ pub trait AudioFile: Into<TaggedFile> {
 		)
 	}
 
+	/// Attempts to write all tags to a path, without ever leaving a partially written file behind
+	///
+	/// Unlike [`AudioFile::save_to_path`], this writes to a temporary copy next to the original,
+	/// syncs it to disk, and then renames it over the original. If anything fails (or the process
+	/// crashes) along the way, the original file is left untouched.
+	///
+	/// The permissions of the original file are kept. To also keep its modification time, see
+	/// [`WriteOptions::preserve_modified_time`].
+	///
+	/// NOTE: This requires write access to the directory containing `path`, and temporarily needs
+	///       enough space for a second copy of the file.
+	///
+	/// # Errors
+	///
+	/// * `path` does not exist
+	/// * The temporary file could not be created
+	/// * [`ErrorKind::AtomicReplace`](crate::error::ErrorKind::AtomicReplace) if the original could not be replaced
+	/// * See [`AudioFile::save_to`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::WriteOptions;
+	/// use lofty::file::{AudioFile, TaggedFileExt};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let mut tagged_file = lofty::read_from_path(path)?;
+	///
+	/// // Edit the tags
+	///
+	/// tagged_file.save_to_path_atomic(path, WriteOptions::default())?;
+	/// # Ok(()) }
+	/// ```
+	fn save_to_path_atomic(
+		&self,
+		path: impl AsRef<Path>,
+		write_options: WriteOptions,
+	) -> Result<()> {
+		crate::util::io::save_atomic(
+			path.as_ref(),
+			write_options.preserve_modified_time,
+			|file| self.save_to(file, write_options),
+		)
+	}
+
 	/// Attempts to write all tags to a file
 	///
 	/// # Errors
@@ -5,6 +5,9 @@ use crate::tag::TagType;
 use std::ffi::OsStr;
 use std::path::Path;
 
+/// The frame sync of AC-3 and E-AC-3 streams
+pub(crate) const AC3_SYNC: [u8; 2] = [0x0B, 0x77];
+
 /// The type of file read
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[allow(missing_docs)]
@@ -13,13 +16,19 @@ pub enum FileType {
 	Aac,
 	Aiff,
 	Ape,
+	Asf,
+	Dff,
+	Dsf,
 	Flac,
 	Mpeg,
 	Mp4,
 	Mpc,
+	OggFlac,
 	Opus,
 	Vorbis,
 	Speex,
+	Shn,
+	Tta,
 	Wav,
 	WavPack,
 	Custom(&'static str),
@@ -28,12 +37,13 @@ pub enum FileType {
 impl FileType {
 	/// Returns the file type's "primary" [`TagType`], or the one most likely to be used in the target format
 	///
-	/// | [`FileType`]                      | [`TagType`]      |
-	/// |-----------------------------------|------------------|
-	/// | `Aac`, `Aiff`, `Mp3`, `Wav`       | `Id3v2`          |
-	/// | `Ape` , `Mpc`, `WavPack`          | `Ape`            |
-	/// | `Flac`, `Opus`, `Vorbis`, `Speex` | `VorbisComments` |
-	/// | `Mp4`                             | `Mp4Ilst`        |
+	/// | [`FileType`]                                  | [`TagType`]      |
+	/// |-----------------------------------------------|------------------|
+	/// | `Aac`, `Aiff`, `Dff`, `Dsf`, `Mp3`, `Wav`     | `Id3v2`          |
+	/// | `Ape` , `Mpc`, `Shn`, `Tta`, `WavPack`        | `Ape`            |
+	/// | `Flac`, `OggFlac`, `Opus`, `Vorbis`, `Speex`  | `VorbisComments` |
+	/// | `Mp4`                                         | `Mp4Ilst`        |
+	/// | `Asf`                                         | `Asf`            |
 	///
 	/// # Panics
 	///
@@ -50,12 +60,22 @@ impl FileType {
 	/// ```
 	pub fn primary_tag_type(&self) -> TagType {
 		match self {
-			FileType::Aac | FileType::Aiff | FileType::Mpeg | FileType::Wav => TagType::Id3v2,
-			FileType::Ape | FileType::Mpc | FileType::WavPack => TagType::Ape,
-			FileType::Flac | FileType::Opus | FileType::Vorbis | FileType::Speex => {
-				TagType::VorbisComments
+			FileType::Aac
+			| FileType::Aiff
+			| FileType::Dff
+			| FileType::Dsf
+			| FileType::Mpeg
+			| FileType::Wav => TagType::Id3v2,
+			FileType::Ape | FileType::Mpc | FileType::Shn | FileType::Tta | FileType::WavPack => {
+				TagType::Ape
 			},
+			FileType::Flac
+			| FileType::OggFlac
+			| FileType::Opus
+			| FileType::Vorbis
+			| FileType::Speex => TagType::VorbisComments,
 			FileType::Mp4 => TagType::Mp4Ilst,
+			FileType::Asf => TagType::Asf,
 			FileType::Custom(c) => {
 				let resolver = crate::resolve::lookup_resolver(c);
 				resolver.primary_tag_type()
@@ -65,6 +85,8 @@ impl FileType {
 
 	/// Returns if the target `FileType` supports a [`TagType`]
 	///
+	/// This includes tags that can only be read, see [`TagType::supports_write_for`].
+	///
 	/// NOTE: This is feature dependent, meaning if you do not have the
 	///       `id3v2` feature enabled, [`FileType::Mpeg`] will return `false` for
 	///        [`TagType::Id3v2`].
@@ -88,15 +110,68 @@ impl FileType {
 			return resolver.supported_tag_types().contains(&tag_type);
 		}
 
-		match tag_type {
-			TagType::Ape => crate::ape::ApeTag::SUPPORTED_FORMATS.contains(self),
-			TagType::Id3v1 => crate::id3::v1::Id3v1Tag::SUPPORTED_FORMATS.contains(self),
-			TagType::Id3v2 => crate::id3::v2::Id3v2Tag::SUPPORTED_FORMATS.contains(self),
-			TagType::Mp4Ilst => crate::mp4::Ilst::SUPPORTED_FORMATS.contains(self),
-			TagType::VorbisComments => crate::ogg::VorbisComments::SUPPORTED_FORMATS.contains(self),
-			TagType::RiffInfo => crate::iff::wav::RiffInfoList::SUPPORTED_FORMATS.contains(self),
-			TagType::AiffText => crate::iff::aiff::AiffTextChunks::SUPPORTED_FORMATS.contains(self),
+		tag_type.supported_formats().contains(self)
+	}
+
+	/// Returns the [`TagType`]s that can be read from the target `FileType`
+	///
+	/// Not all of these can be written, see [`FileType::writable_tag_types`].
+	///
+	/// # Panics
+	///
+	/// If an unregistered `FileType` ([`FileType::Custom`]) is encountered. See [`register_custom_resolver`](crate::resolve::register_custom_resolver).
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::file::FileType;
+	/// use lofty::tag::TagType;
+	///
+	/// let file_type = FileType::Flac;
+	/// assert_eq!(
+	/// 	file_type.supported_tag_types(),
+	/// 	[TagType::Id3v2, TagType::VorbisComments]
+	/// );
+	/// ```
+	pub fn supported_tag_types(&self) -> Vec<TagType> {
+		if let FileType::Custom(c) = self {
+			let resolver = crate::resolve::lookup_resolver(c);
+			return resolver.supported_tag_types().to_vec();
+		}
+
+		TagType::ALL
+			.into_iter()
+			.filter(|tag_type| self.supports_tag_type(*tag_type))
+			.collect()
+	}
+
+	/// Returns the [`TagType`]s that can be written to the target `FileType`
+	///
+	/// See [`TagType::supports_write_for`].
+	///
+	/// # Panics
+	///
+	/// If an unregistered `FileType` ([`FileType::Custom`]) is encountered. See [`register_custom_resolver`](crate::resolve::register_custom_resolver).
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::file::FileType;
+	/// use lofty::tag::TagType;
+	///
+	/// let file_type = FileType::Flac;
+	/// assert_eq!(file_type.writable_tag_types(), [TagType::VorbisComments]);
+	/// ```
+	pub fn writable_tag_types(&self) -> Vec<TagType> {
+		if let FileType::Custom(c) = self {
+			let resolver = crate::resolve::lookup_resolver(c);
+			return resolver.supported_tag_types().to_vec();
 		}
+
+		TagType::ALL
+			.into_iter()
+			.filter(|tag_type| tag_type.supports_write_for(*self))
+			.collect()
 	}
 
 	/// Attempts to extract a [`FileType`] from an extension
@@ -131,15 +206,21 @@ impl FileType {
 			"aac" => Some(Self::Aac),
 			"ape" => Some(Self::Ape),
 			"aiff" | "aif" | "afc" | "aifc" => Some(Self::Aiff),
+			"wma" | "asf" => Some(Self::Asf),
+			"dff" => Some(Self::Dff),
+			"dsf" => Some(Self::Dsf),
 			"mp3" | "mp2" | "mp1" => Some(Self::Mpeg),
 			"wav" | "wave" => Some(Self::Wav),
 			"wv" => Some(Self::WavPack),
 			"opus" => Some(Self::Opus),
 			"flac" => Some(Self::Flac),
 			"ogg" => Some(Self::Vorbis),
+			"oga" => Some(Self::OggFlac),
 			"mp4" | "m4a" | "m4b" | "m4p" | "m4r" | "m4v" | "3gp" => Some(Self::Mp4),
 			"mpc" | "mp+" | "mpp" => Some(Self::Mpc),
 			"spx" => Some(Self::Speex),
+			"shn" => Some(Self::Shn),
+			"tta" => Some(Self::Tta),
 			_ => None,
 		}
 	}
@@ -212,6 +293,13 @@ impl FileType {
 			return ret;
 		}
 
+		// AC-3 and E-AC-3 streams are unsupported, but their data can contain bytes that look like
+		// an MPEG frame sync. Don't search through them.
+		if buf.starts_with(&AC3_SYNC) {
+			log::debug!("Found an AC-3 frame sync, which is unsupported");
+			return ret;
+		}
+
 		match Self::quick_type_guess(buf) {
 			Some(f_ty) => ret = Some(FileTypeGuessResult::Determined(f_ty)),
 			// Special case for ID3, gets checked in `Probe::guess_file_type`
@@ -232,6 +320,7 @@ impl FileType {
 	}
 
 	fn quick_type_guess(buf: &[u8]) -> Option<Self> {
+		use crate::aac::header::is_adts_header;
 		use crate::mpeg::header::verify_frame_sync;
 
 		// Safe to index, since we return early on an empty buffer
@@ -264,13 +353,21 @@ impl FileType {
 
 				// So, if we have a version 1 (0b11) or version 2 (0b10) MPEG frame AND a layer of 0b00,
 				// we can assume we have an ADTS header. Awesome!
+				//
+				// A layer of 0b00 is reserved in MPEG, so an invalid ADTS header isn't MPEG either.
+				// The frame sync may just be junk, which is searched through later.
 
 				if buf[1] & 0b10000 > 0 && buf[1] & 0b110 == 0 {
-					return Some(Self::Aac);
+					return is_adts_header(buf).then_some(Self::Aac);
 				}
 
 				Some(Self::Mpeg)
 			},
+			48 if buf.starts_with(crate::asf::object::Guid::HEADER.as_bytes()) => Some(Self::Asf),
+			68 if buf.starts_with(b"DSD ") => Some(Self::Dsf),
+			70 if buf.len() >= 16 && &buf[..4] == b"FRM8" && &buf[12..16] == b"DSD " => {
+				Some(Self::Dff)
+			},
 			70 if buf.len() >= 12 && &buf[..4] == b"FORM" => {
 				let id = &buf[8..12];
 
@@ -287,10 +384,14 @@ impl FileType {
 					return Some(Self::Opus);
 				} else if &buf[28..36] == b"Speex   " {
 					return Some(Self::Speex);
+				} else if &buf[28..33] == b"\x7FFLAC" {
+					return Some(Self::OggFlac);
 				}
 
 				None
 			},
+			84 if buf.starts_with(b"TTA1") => Some(Self::Tta),
+			97 if buf.starts_with(b"ajkg") => Some(Self::Shn),
 			102 if buf.starts_with(b"fLaC") => Some(Self::Flac),
 			82 if buf.len() >= 12 && &buf[..4] == b"RIFF" => {
 				if &buf[8..12] == b"WAVE" {
@@ -3,9 +3,11 @@
 mod audio_file;
 mod file_type;
 mod tagged_file;
+pub(crate) mod write_plan;
 
 pub use audio_file::AudioFile;
 pub use file_type::FileType;
 pub use tagged_file::{BoundTaggedFile, TaggedFile, TaggedFileExt};
 
-pub(crate) use file_type::FileTypeGuessResult;
+pub(crate) use file_type::{FileTypeGuessResult, AC3_SYNC};
+pub(crate) use write_plan::WritePlan;
@@ -1,14 +1,62 @@
 use super::audio_file::AudioFile;
 use super::file_type::FileType;
+use super::write_plan::{self, PlannedTag};
 use crate::config::{ParseOptions, WriteOptions};
-use crate::error::{LoftyError, Result};
+use crate::error::{LoftyError, ParseDiagnostic, Result};
 use crate::properties::FileProperties;
-use crate::tag::{Tag, TagExt, TagType};
+use crate::tag::items::Popularimeter;
+use crate::tag::{Accessor, Tag, TagExt, TagLocation, TagType};
 
 use crate::util::io::{FileLike, Length, Truncate};
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::{Read, Seek};
 
+// Implements `Accessor` for a file, given the following methods:
+//
+// * `tags_by_precedence()` - The tags to check for each getter, in order
+// * `primary_tag_or_insert()` - The tag for setters to write to
+// * `tags_mut()` - The tags for removals to apply to
+macro_rules! impl_accessor_fallback {
+	($($name:ident => $ty:ty, $owned_ty:ty);+ $(;)?) => {
+		paste::paste! {
+			$(
+				fn $name(&self) -> Option<$ty> {
+					self.tags_by_precedence().find_map(|tag| tag.$name())
+				}
+
+				fn [<set_ $name>](&mut self, value: $owned_ty) {
+					self.primary_tag_or_insert().[<set_ $name>](value);
+				}
+
+				fn [<remove_ $name>](&mut self) {
+					for tag in self.tags_mut() {
+						tag.[<remove_ $name>]();
+					}
+				}
+			)+
+		}
+	};
+}
+
+macro_rules! accessor_fallback_methods {
+	() => {
+		impl_accessor_fallback!(
+			artist      => Cow<'_, str>, String;
+			title       => Cow<'_, str>, String;
+			album       => Cow<'_, str>, String;
+			genre       => Cow<'_, str>, String;
+			track       => u32, u32;
+			track_total => u32, u32;
+			disk        => u32, u32;
+			disk_total  => u32, u32;
+			year        => u32, u32;
+			comment     => Cow<'_, str>, String;
+			popularimeter => Popularimeter, Popularimeter;
+		);
+	};
+}
+
 /// Provides a common interface between [`TaggedFile`] and [`BoundTaggedFile`]
 pub trait TaggedFileExt {
 	/// Returns the file's [`FileType`]
@@ -266,6 +314,12 @@ pub trait TaggedFileExt {
 
 	/// Removes a specific [`TagType`] and returns it
 	///
+	/// When the file is saved, the tag will also be removed from the file, unless another tag of the
+	/// same [`TagType`] is inserted in the meantime. See [`AudioFile::save_to`].
+	///
+	/// NOTE: If the file type can't be written at all (e.g. [`FileType::Asf`]), the tag is only
+	///       removed from the `TaggedFile`, and the file is left untouched.
+	///
 	/// # Examples
 	///
 	/// ```rust
@@ -289,6 +343,8 @@ pub trait TaggedFileExt {
 
 	/// Removes all tags from the file
 	///
+	/// Like [`TaggedFileExt::remove`], the tags will also be removed from the file when it is saved.
+	///
 	/// # Examples
 	///
 	/// ```rust
@@ -309,6 +365,42 @@ pub trait TaggedFileExt {
 /// A generic representation of a file
 ///
 /// This is used when the [`FileType`] has to be guessed
+///
+/// A `TaggedFile` holds at most one [`Tag`] per [`TagType`]. When saving, every tag is written as
+/// its own [`TagType`], replacing the existing tag of that type in the file. Tags of other types
+/// are left untouched, unless they were removed with [`TaggedFileExt::remove`] or
+/// [`TaggedFileExt::clear`].
+///
+/// `TaggedFile` also implements [`Accessor`], for access to common items regardless of which tag
+/// they are stored in:
+///
+/// * Getters check each tag in order, see [`TaggedFile::tag_precedence`]
+/// * Setters write to the primary tag, creating it if necessary (see [`FileType::primary_tag_type`])
+/// * Removals apply to all tags
+///
+/// For example, to edit an MP3 file with both an ID3v2 and an APE tag, keeping only the ID3v2 tag:
+///
+/// ```rust,no_run
+/// use lofty::config::WriteOptions;
+/// use lofty::file::{AudioFile, TaggedFileExt};
+/// use lofty::tag::{Accessor, TagType};
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// let mut tagged_file = lofty::read_from_path(path)?;
+///
+/// // The edits will only ever be written to the ID3v2 tag
+/// let mut id3v2 = tagged_file.remove(TagType::Id3v2).unwrap();
+/// id3v2.set_title(String::from("Foo title"));
+/// tagged_file.insert_tag(id3v2);
+///
+/// // The APE tag will be removed from the file on save
+/// tagged_file.remove(TagType::Ape);
+///
+/// // Both changes are written when saving
+/// tagged_file.save_to_path(path, WriteOptions::default())?;
+/// # Ok(()) }
+/// ```
 pub struct TaggedFile {
 	/// The file's type
 	pub(crate) ty: FileType,
@@ -316,6 +408,14 @@ pub struct TaggedFile {
 	pub(crate) properties: FileProperties,
 	/// A collection of the file's tags
 	pub(crate) tags: Vec<Tag>,
+	/// The tag types that were removed, and need to be removed from the file on save
+	pub(crate) removed_tags: Vec<TagType>,
+	/// The order to check tags in for `Accessor` getters, see `TaggedFile::set_tag_precedence`
+	pub(crate) tag_precedence: Vec<TagType>,
+	/// Any recoverable issues encountered while parsing
+	pub(crate) diagnostics: Vec<ParseDiagnostic>,
+	/// The locations of the tags found while parsing
+	pub(crate) tag_locations: Vec<TagLocation>,
 }
 
 impl TaggedFile {
@@ -327,9 +427,91 @@ impl TaggedFile {
 			ty,
 			properties,
 			tags,
+			removed_tags: Vec::new(),
+			tag_precedence: Vec::new(),
+			diagnostics: Vec::new(),
+			tag_locations: Vec::new(),
 		}
 	}
 
+	/// Returns any recoverable issues encountered while parsing the file
+	///
+	/// When using [`ParsingMode::Strict`], most issues will result in an error instead.
+	///
+	/// See [`ParseDiagnostic`].
+	///
+	/// [`ParsingMode::Strict`]: crate::config::ParsingMode::Strict
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, ParsingMode};
+	/// use lofty::probe::Probe;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path_to_flac = "tests/files/assets/minimal/full_test.flac";
+	/// let parse_options = ParseOptions::new().parsing_mode(ParsingMode::Relaxed);
+	/// let tagged_file = Probe::open(path_to_flac)?.options(parse_options).read()?;
+	///
+	/// for diagnostic in tagged_file.diagnostics() {
+	/// 	eprintln!("{diagnostic}");
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn diagnostics(&self) -> &[ParseDiagnostic] {
+		&self.diagnostics
+	}
+
+	/// Returns the locations of the tags found while parsing the file, in the order they were read
+	///
+	/// These describe the file as it was read, they are not updated when tags are changed or the file is saved.
+	/// If the file contains multiple tags of the same type (e.g. stacked ID3v2 tags), each will have a location.
+	///
+	/// Locations are currently reported for:
+	///
+	/// * ID3v2 tags, whether at the start of the file or in a chunk
+	/// * ID3v1 tags
+	/// * APE tags
+	/// * Vorbis Comments in FLAC files
+	/// * RIFF INFO lists
+	/// * MP4 `ilst` atoms
+	///
+	/// Tags spread across multiple structures (e.g. Vorbis Comments in Ogg pages, ASF objects, or AIFF text chunks)
+	/// have no single location, and are not reported.
+	///
+	/// See [`TagLocation`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::probe::Probe;
+	/// use lofty::tag::{TagPlacement, TagType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path_to_mp3 = "tests/files/assets/minimal/full_test.mp3";
+	/// let tagged_file = Probe::open(path_to_mp3)?.read()?;
+	///
+	/// for location in tagged_file.tag_locations() {
+	/// 	println!(
+	/// 		"{:?}: {} bytes at offset {}",
+	/// 		location.tag_type(),
+	/// 		location.len(),
+	/// 		location.offset()
+	/// 	);
+	/// }
+	///
+	/// let id3v2 = tagged_file
+	/// 	.tag_locations()
+	/// 	.iter()
+	/// 	.find(|location| location.tag_type() == TagType::Id3v2)
+	/// 	.unwrap();
+	/// assert_eq!(id3v2.placement(), TagPlacement::Head);
+	/// # Ok(()) }
+	/// ```
+	pub fn tag_locations(&self) -> &[TagLocation] {
+		&self.tag_locations
+	}
+
 	/// Changes the [`FileType`]
 	///
 	/// NOTES:
@@ -361,6 +543,271 @@ impl TaggedFile {
 		self.properties = FileProperties::default();
 		self.tags
 			.retain(|t| self.ty.supports_tag_type(t.tag_type()));
+		self.removed_tags
+			.retain(|tag_type| self.ty.supports_tag_type(*tag_type));
+	}
+
+	/// Returns the order in which tags are checked by the [`Accessor`] getters
+	///
+	/// By default, the primary tag (see [`FileType::primary_tag_type`]) is checked first, followed by the
+	/// remaining tags in the order of [`FileType::supported_tag_types`]. For example, an MPEG file will be
+	/// checked in the order ID3v2, APE, and ID3v1.
+	///
+	/// See [`TaggedFile::set_tag_precedence`] to change the order.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::TagType;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path_to_mp3 = "tests/files/assets/minimal/full_test.mp3";
+	/// let tagged_file = lofty::read_from_path(path_to_mp3)?;
+	///
+	/// assert_eq!(
+	/// 	tagged_file.tag_precedence(),
+	/// 	[TagType::Id3v2, TagType::Ape, TagType::Id3v1]
+	/// );
+	/// # Ok(()) }
+	/// ```
+	pub fn tag_precedence(&self) -> Vec<TagType> {
+		let primary_tag_type = self.primary_tag_type();
+
+		let mut precedence = self.tag_precedence.clone();
+		if !precedence.contains(&primary_tag_type) {
+			precedence.push(primary_tag_type);
+		}
+
+		for tag_type in self.ty.supported_tag_types() {
+			if !precedence.contains(&tag_type) {
+				precedence.push(tag_type);
+			}
+		}
+
+		precedence
+	}
+
+	/// Change the order in which tags are checked by the [`Accessor`] getters
+	///
+	/// The tag types in `precedence` are checked first, in the given order. Any tag types that aren't
+	/// listed are checked afterward, in their default order (see [`TaggedFile::tag_precedence`]).
+	///
+	/// NOTE: This only affects the getters. Setters will always write to the primary tag, and removals
+	///       apply to all tags.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::TagType;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path_to_mp3 = "tests/files/assets/minimal/full_test.mp3";
+	/// let mut tagged_file = lofty::read_from_path(path_to_mp3)?;
+	///
+	/// // Prefer the APE tag over the ID3v2 tag
+	/// tagged_file.set_tag_precedence(&[TagType::Ape]);
+	///
+	/// assert_eq!(
+	/// 	tagged_file.tag_precedence(),
+	/// 	[TagType::Ape, TagType::Id3v2, TagType::Id3v1]
+	/// );
+	/// # Ok(()) }
+	/// ```
+	pub fn set_tag_precedence(&mut self, precedence: &[TagType]) {
+		self.tag_precedence.clear();
+		for tag_type in precedence {
+			if !self.tag_precedence.contains(tag_type) {
+				self.tag_precedence.push(*tag_type);
+			}
+		}
+	}
+
+	fn tags_by_precedence(&self) -> impl Iterator<Item = &Tag> + '_ {
+		self.tag_precedence()
+			.into_iter()
+			.filter_map(|tag_type| self.tag(tag_type))
+	}
+
+	fn primary_tag_or_insert(&mut self) -> &mut Tag {
+		let primary_tag_type = self.primary_tag_type();
+		if !self.contains_tag_type(primary_tag_type) {
+			self.insert_tag(Tag::new(primary_tag_type));
+		}
+
+		self.tag_mut(primary_tag_type)
+			.expect("the primary tag type should always be supported")
+	}
+
+	fn tags_mut(&mut self) -> impl Iterator<Item = &mut Tag> {
+		self.tags.iter_mut()
+	}
+
+	/// The removed tag types that haven't been replaced since, and can be removed from the file
+	fn pending_removals(&self) -> impl Iterator<Item = TagType> + '_ {
+		self.removed_tags.iter().copied().filter(|tag_type| {
+			!self.contains_tag_type(*tag_type) && tag_type.supports_removal_from(self.ty)
+		})
+	}
+
+	/// Remove multiple tags from a file at once
+	///
+	/// This is equivalent to calling [`TagType::remove_from`] for each [`TagType`], but the file is
+	/// only read once, and all of the tags are then cut out of it in a single pass. The file is never
+	/// held in memory as a whole.
+	///
+	/// FLAC metadata blocks holding a tag are turned into padding instead, so the audio doesn't have
+	/// to move.
+	///
+	/// Any [`TagType`]s not present in the file will be ignored. The removed [`TagType`]s are returned,
+	/// in the order they were provided.
+	///
+	/// # Errors
+	///
+	/// * It is unable to guess the file format
+	/// * It is unable to write to the file
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::file::TaggedFile;
+	/// use lofty::tag::TagType;
+	/// use std::fs::OpenOptions;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path_to_mp3 = "tests/files/assets/minimal/full_test.mp3";
+	/// let mut file = OpenOptions::new()
+	/// 	.read(true)
+	/// 	.write(true)
+	/// 	.open(path_to_mp3)?;
+	///
+	/// // An MP3 file with an ID3v1 and ID3v2 tag
+	/// let removed = TaggedFile::strip_tags(&mut file, &[TagType::Id3v1, TagType::Id3v2])?;
+	///
+	/// assert_eq!(removed, [TagType::Id3v1, TagType::Id3v2]);
+	/// # Ok(()) }
+	/// ```
+	pub fn strip_tags<F>(file: &mut F, tag_types: &[TagType]) -> Result<Vec<TagType>>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		Self::strip_tags_impl(file, Some(tag_types))
+	}
+
+	/// Remove all tags from a file
+	///
+	/// If the file type can't be written at all (e.g. [`FileType::Asf`]), nothing is removed.
+	///
+	/// See [`TaggedFile::strip_tags`].
+	///
+	/// # Errors
+	///
+	/// See [`TaggedFile::strip_tags`]
+	pub fn strip_all_tags<F>(file: &mut F) -> Result<Vec<TagType>>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		Self::strip_tags_impl(file, None)
+	}
+
+	// Writes `tags` and removes `removed_tags` at once, so the file only needs to be read and
+	// rewritten a single time. Writing an empty tag removes it from the file as well.
+	fn write_tags_to<F>(
+		file: &mut F,
+		tags: &[&Tag],
+		removed_tags: impl Iterator<Item = TagType>,
+		write_options: WriteOptions,
+	) -> Result<()>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		let removed_tags = removed_tags.collect::<Vec<_>>();
+		if tags.is_empty() && removed_tags.is_empty() {
+			return Ok(());
+		}
+
+		// The existing tags need to be located in order to replace them
+		file.rewind()?;
+		let current = Self::read_from(
+			file,
+			ParseOptions::new()
+				.read_properties(false)
+				.read_cover_art(false),
+		)?;
+		file.rewind()?;
+
+		let file_type = current.file_type();
+		let planned_tags = removed_tags
+			.into_iter()
+			.map(PlannedTag::empty)
+			.chain(tags.iter().map(|tag| PlannedTag::from_tag(tag, file_type)))
+			.collect::<Result<Vec<_>>>()?;
+
+		write_plan::write_tags(
+			file,
+			file_type,
+			current.tag_locations(),
+			&planned_tags,
+			write_options,
+		)
+	}
+
+	// With no `tag_types`, all of the tag types that can be removed from the file type are removed
+	fn strip_tags_impl<F>(file: &mut F, tag_types: Option<&[TagType]>) -> Result<Vec<TagType>>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		let tagged_file = Self::read_from(
+			file,
+			ParseOptions::new()
+				.read_properties(false)
+				.read_cover_art(false),
+		)?;
+		file.rewind()?;
+
+		let file_type = tagged_file.file_type();
+
+		let removable_tag_types;
+		let tag_types = match tag_types {
+			Some(tag_types) => tag_types,
+			None => {
+				removable_tag_types = TagType::ALL
+					.into_iter()
+					.filter(|tag_type| tag_type.supports_removal_from(file_type))
+					.collect::<Vec<_>>();
+				&removable_tag_types
+			},
+		};
+
+		let mut removed = Vec::new();
+		for tag_type in tag_types {
+			if tagged_file.contains_tag_type(*tag_type) && !removed.contains(tag_type) {
+				removed.push(*tag_type);
+			}
+		}
+
+		let removals = removed
+			.iter()
+			.map(|tag_type| PlannedTag::empty(*tag_type))
+			.collect::<Result<Vec<_>>>()?;
+
+		file.rewind()?;
+		write_plan::write_tags(
+			file,
+			file_type,
+			tagged_file.tag_locations(),
+			&removals,
+			WriteOptions::default(),
+		)?;
+
+		Ok(removed)
 	}
 }
 
@@ -399,6 +846,10 @@ impl TaggedFileExt for TaggedFile {
 	}
 
 	fn remove(&mut self, tag_type: TagType) -> Option<Tag> {
+		if self.supports_tag_type(tag_type) && !self.removed_tags.contains(&tag_type) {
+			self.removed_tags.push(tag_type);
+		}
+
 		self.tags
 			.iter()
 			.position(|t| t.tag_type() == tag_type)
@@ -406,10 +857,18 @@ impl TaggedFileExt for TaggedFile {
 	}
 
 	fn clear(&mut self) {
-		self.tags.clear()
+		for tag in self.tags.drain(..) {
+			if !self.removed_tags.contains(&tag.tag_type()) {
+				self.removed_tags.push(tag.tag_type());
+			}
+		}
 	}
 }
 
+impl Accessor for TaggedFile {
+	accessor_fallback_methods!();
+}
+
 impl AudioFile for TaggedFile {
 	type Properties = FileProperties;
 
@@ -430,14 +889,12 @@ impl AudioFile for TaggedFile {
 		LoftyError: From<<F as Truncate>::Error>,
 		LoftyError: From<<F as Length>::Error>,
 	{
-		for tag in &self.tags {
-			// TODO: This is a temporary solution. Ideally we should probe once and use
-			//       the format-specific writing to avoid these rewinds.
-			file.rewind()?;
-			tag.save_to(file, write_options)?;
-		}
-
-		Ok(())
+		Self::write_tags_to(
+			file,
+			&self.tags.iter().collect::<Vec<_>>(),
+			self.pending_removals(),
+			write_options,
+		)
 	}
 
 	fn properties(&self) -> &Self::Properties {
@@ -545,9 +1002,13 @@ impl BoundTaggedFile {
 	/// # Ok(()) }
 	/// ```
 	pub fn read_from(mut file: File, parse_options: ParseOptions) -> Result<Self> {
-		let inner = TaggedFile::read_from(&mut file, parse_options)?;
+		let mut inner = TaggedFile::read_from(&mut file, parse_options)?;
 		file.rewind()?;
 
+		for tag in &mut inner.tags {
+			tag.mark_clean();
+		}
+
 		Ok(Self {
 			inner,
 			file_handle: file,
@@ -556,6 +1017,9 @@ impl BoundTaggedFile {
 
 	/// Save the tags to the file stored internally
 	///
+	/// Only tags that have changed since they were read or last saved are written, see [`Tag::is_dirty`].
+	/// Tags removed with [`TaggedFileExt::remove`] or [`TaggedFileExt::clear`] are removed from the file.
+	///
 	/// # Errors
 	///
 	/// See [`TaggedFile::save_to`]
@@ -583,12 +1047,71 @@ impl BoundTaggedFile {
 	/// # Ok(()) }
 	/// ```
 	pub fn save(&mut self, write_options: WriteOptions) -> Result<()> {
-		self.inner.save_to(&mut self.file_handle, write_options)?;
+		let dirty_tags = self
+			.inner
+			.tags
+			.iter()
+			.filter(|tag| {
+				if !tag.is_dirty() {
+					log::debug!("Skipping unchanged tag: {:?}", tag.tag_type());
+				}
+
+				tag.is_dirty()
+			})
+			.collect::<Vec<_>>();
+
+		TaggedFile::write_tags_to(
+			&mut self.file_handle,
+			&dirty_tags,
+			self.inner.pending_removals(),
+			write_options,
+		)?;
+
+		self.inner.removed_tags.clear();
+		for tag in &mut self.inner.tags {
+			tag.mark_clean();
+		}
+
 		self.inner.tags.retain(|tag| !tag.is_empty());
 
 		Ok(())
 	}
 
+	/// Returns any recoverable issues encountered while parsing the file
+	///
+	/// See [`TaggedFile::diagnostics`]
+	pub fn diagnostics(&self) -> &[ParseDiagnostic] {
+		self.inner.diagnostics()
+	}
+
+	/// See [`TaggedFile::tag_locations`]
+	pub fn tag_locations(&self) -> &[TagLocation] {
+		self.inner.tag_locations()
+	}
+
+	/// See [`TaggedFile::tag_precedence`]
+	pub fn tag_precedence(&self) -> Vec<TagType> {
+		self.inner.tag_precedence()
+	}
+
+	/// See [`TaggedFile::set_tag_precedence`]
+	pub fn set_tag_precedence(&mut self, precedence: &[TagType]) {
+		self.inner.set_tag_precedence(precedence)
+	}
+
+	fn tags_by_precedence(&self) -> impl Iterator<Item = &Tag> + '_ {
+		self.inner.tags_by_precedence()
+	}
+
+	fn primary_tag_or_insert(&mut self) -> &mut Tag {
+		// A newly created tag is always dirty, so it will be written on save
+		self.inner.primary_tag_or_insert()
+	}
+
+	fn tags_mut(&mut self) -> impl Iterator<Item = &mut Tag> {
+		self.inner.tags_mut()
+	}
+
 	/// Consume this tagged file and return the internal file "buffer".
 	/// This allows you to reuse the internal file.
 	///
@@ -620,7 +1143,9 @@ impl TaggedFileExt for BoundTaggedFile {
 		self.inner.first_tag_mut()
 	}
 
-	fn insert_tag(&mut self, tag: Tag) -> Option<Tag> {
+	fn insert_tag(&mut self, mut tag: Tag) -> Option<Tag> {
+		// The tag may have been marked clean by another file
+		tag.mark_dirty();
 		self.inner.insert_tag(tag)
 	}
 
@@ -633,6 +1158,10 @@ impl TaggedFileExt for BoundTaggedFile {
 	}
 }
 
+impl Accessor for BoundTaggedFile {
+	accessor_fallback_methods!();
+}
+
 impl AudioFile for BoundTaggedFile {
 	type Properties = FileProperties;
 
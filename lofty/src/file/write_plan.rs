@@ -0,0 +1,752 @@
+//! Writing multiple tags to a file in a single pass
+
+use super::{AudioFile, FileType};
+use crate::aac::AacFile;
+use crate::ape::{ApeFile, ApeTag};
+use crate::config::{ParseOptions, RiffInfoPlacement, WriteOptions};
+use crate::dff::DffFile;
+use crate::dsf::DsfFile;
+use crate::error::{LoftyError, Result};
+use crate::flac::FlacFile;
+use crate::id3::find_lyrics3v2;
+use crate::id3::v1::Id3v1Tag;
+use crate::id3::v2::Id3v2Tag;
+use crate::iff::aiff::{AiffFile, AiffTextChunks};
+use crate::iff::chunk::Chunks;
+use crate::iff::wav::{RiffInfoList, WavFile};
+use crate::macros::err;
+use crate::mp4::{Ilst, Mp4File};
+use crate::mpeg::MpegFile;
+use crate::musepack::MpcFile;
+use crate::ogg::tag::VorbisCommentsRef;
+use crate::ogg::{OggFlacFile, OpusFile, SpeexFile, VorbisComments, VorbisFile};
+use crate::shn::ShnFile;
+use crate::tag::{MergeTag, SplitTag, Tag, TagExt, TagLocation, TagPlacement, TagType};
+use crate::tta::TtaFile;
+use crate::util::io::{splice_all, FileLike, Length, Truncate};
+use crate::wavpack::WavPackFile;
+
+use std::borrow::Cow;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+/// A set of changes to a file, applied in a single pass
+///
+/// All offsets refer to the file as it was before any of the changes.
+#[derive(Default)]
+pub(crate) struct WritePlan {
+	edits: Vec<(Range<u64>, Vec<u8>)>,
+}
+
+impl WritePlan {
+	/// Replace `range` with `content`
+	pub(crate) fn replace(&mut self, range: Range<u64>, content: Vec<u8>) {
+		if range.is_empty() && content.is_empty() {
+			return;
+		}
+
+		self.edits.push((range, content));
+	}
+
+	/// Remove `range`
+	pub(crate) fn remove(&mut self, range: Range<u64>) {
+		self.replace(range, Vec::new());
+	}
+
+	/// Insert `content` at `position`
+	///
+	/// Multiple insertions at the same position end up in the order they were planned, before
+	/// anything replacing the bytes at that position.
+	pub(crate) fn insert(&mut self, position: u64, content: Vec<u8>) {
+		self.replace(position..position, content);
+	}
+
+	/// The length of the file once the plan is applied
+	fn new_len(&self, file_len: u64) -> u64 {
+		self.edits.iter().fold(file_len, |len, (range, content)| {
+			len - (range.end - range.start) + content.len() as u64
+		})
+	}
+
+	/// Apply all of the changes at once
+	///
+	/// Everything in between the changes is moved in chunks, so the file is never fully loaded
+	/// into memory.
+	pub(crate) fn apply<F>(mut self, file: &mut F) -> Result<()>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		// The sort is stable, keeping insertions at the same position in order
+		self.edits
+			.sort_by_key(|(range, _)| (range.start, range.end));
+
+		if self
+			.edits
+			.windows(2)
+			.any(|edits| edits[0].0.end > edits[1].0.start)
+		{
+			log::error!("Attempted to write overlapping regions of the file");
+			err!(SizeMismatch);
+		}
+
+		splice_all(file, &self.edits)
+	}
+}
+
+/// A tag to write with [`write_tags`]
+///
+/// An empty tag is removed from the file.
+pub(crate) enum PlannedTag {
+	Ape(ApeTag),
+	Id3v1(Id3v1Tag),
+	Id3v2(Id3v2Tag),
+	Mp4Ilst(Ilst),
+	VorbisComments(VorbisComments),
+	RiffInfo(RiffInfoList),
+	AiffText(AiffTextChunks),
+}
+
+impl PlannedTag {
+	/// An empty tag of `tag_type`, which removes it from the file when written
+	pub(crate) fn empty(tag_type: TagType) -> Result<Self> {
+		Ok(match tag_type {
+			TagType::Ape => Self::Ape(ApeTag::default()),
+			TagType::Id3v1 => Self::Id3v1(Id3v1Tag::default()),
+			TagType::Id3v2 => Self::Id3v2(Id3v2Tag::default()),
+			TagType::Mp4Ilst => Self::Mp4Ilst(Ilst::default()),
+			TagType::VorbisComments => Self::VorbisComments(VorbisComments::default()),
+			TagType::RiffInfo => Self::RiffInfo(RiffInfoList::default()),
+			TagType::AiffText => Self::AiffText(AiffTextChunks::default()),
+			_ => err!(UnsupportedTag),
+		})
+	}
+
+	/// Convert `tag` to be written to `file_type`
+	///
+	/// Like with [`Tag::save_to`], writing an ID3v2 tag to a file type where it's read only
+	/// removes it.
+	pub(crate) fn from_tag(tag: &Tag, file_type: FileType) -> Result<Self> {
+		let tag_type = tag.tag_type();
+		if tag_type == TagType::Id3v2 && !tag_type.supports_write_for(file_type) {
+			return Self::empty(tag_type);
+		}
+
+		let tag = tag.clone();
+		Ok(match tag_type {
+			TagType::Ape => Self::Ape(tag.into()),
+			TagType::Id3v1 => Self::Id3v1(tag.into()),
+			TagType::Id3v2 => Self::Id3v2(tag.into()),
+			TagType::Mp4Ilst => Self::Mp4Ilst(tag.into()),
+			TagType::VorbisComments => Self::VorbisComments(tag.into()),
+			TagType::RiffInfo => Self::RiffInfo(tag.into()),
+			TagType::AiffText => Self::AiffText(tag.into()),
+			_ => err!(UnsupportedTag),
+		})
+	}
+
+	/// Edit the tag as a generic [`Tag`]
+	///
+	/// Unlike converting to and from a [`Tag`], anything it can't represent (e.g. unmapped ID3v2
+	/// frames) is kept regardless of [`GlobalOptions::preserve_format_specific_items`].
+	///
+	/// [`GlobalOptions::preserve_format_specific_items`]: crate::config::GlobalOptions::preserve_format_specific_items
+	pub(crate) fn edit<T>(&mut self, f: impl FnOnce(&mut Tag) -> T) -> T {
+		fn edit_split<S, T>(tag: &mut S, f: impl FnOnce(&mut Tag) -> T) -> T
+		where
+			S: SplitTag + Default,
+			S::Remainder: MergeTag<Merged = S>,
+		{
+			let (remainder, mut generic) = std::mem::take(tag).split_tag();
+			let ret = f(&mut generic);
+			*tag = remainder.merge_tag(generic);
+
+			ret
+		}
+
+		match self {
+			Self::Ape(tag) => edit_split(tag, f),
+			Self::Id3v1(tag) => edit_split(tag, f),
+			Self::Id3v2(tag) => edit_split(tag, f),
+			Self::Mp4Ilst(tag) => edit_split(tag, f),
+			Self::VorbisComments(tag) => edit_split(tag, f),
+			Self::RiffInfo(tag) => edit_split(tag, f),
+			Self::AiffText(tag) => edit_split(tag, f),
+		}
+	}
+
+	pub(crate) fn tag_type(&self) -> TagType {
+		match self {
+			Self::Ape(_) => TagType::Ape,
+			Self::Id3v1(_) => TagType::Id3v1,
+			Self::Id3v2(_) => TagType::Id3v2,
+			Self::Mp4Ilst(_) => TagType::Mp4Ilst,
+			Self::VorbisComments(_) => TagType::VorbisComments,
+			Self::RiffInfo(_) => TagType::RiffInfo,
+			Self::AiffText(_) => TagType::AiffText,
+		}
+	}
+
+	pub(crate) fn is_empty(&self) -> bool {
+		match self {
+			Self::Ape(tag) => tag.is_empty(),
+			Self::Id3v1(tag) => tag.is_empty(),
+			Self::Id3v2(tag) => tag.is_empty(),
+			Self::Mp4Ilst(tag) => tag.is_empty(),
+			Self::VorbisComments(tag) => tag.is_empty(),
+			Self::RiffInfo(tag) => tag.is_empty(),
+			Self::AiffText(tag) => tag.is_empty(),
+		}
+	}
+
+	// The tag as it's stored in the file, for tags that aren't dependent on the file's layout
+	fn dump(&self, write_options: WriteOptions) -> Result<Vec<u8>> {
+		let mut content = Vec::new();
+		match self {
+			Self::Ape(tag) => tag.dump_to(&mut content, write_options)?,
+			Self::Id3v1(tag) => tag.dump_to(&mut content, write_options)?,
+			Self::RiffInfo(tag) => tag.dump_to(&mut content, write_options)?,
+			Self::AiffText(tag) => tag.dump_to(&mut content, write_options)?,
+			_ => err!(UnsupportedTag),
+		}
+
+		Ok(content)
+	}
+
+	fn save_to<F>(&self, file: &mut F, write_options: WriteOptions) -> Result<()>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		match self {
+			Self::Ape(tag) => tag.save_to(file, write_options),
+			Self::Id3v1(tag) => tag.save_to(file, write_options),
+			Self::Id3v2(tag) => tag.save_to(file, write_options),
+			Self::Mp4Ilst(tag) => tag.save_to(file, write_options),
+			Self::VorbisComments(tag) => tag.save_to(file, write_options),
+			Self::RiffInfo(tag) => tag.save_to(file, write_options),
+			Self::AiffText(tag) => tag.save_to(file, write_options),
+		}
+	}
+}
+
+macro_rules! read_tags {
+	($file:expr; $($field:ident => $variant:ident),+) => {{
+		let file = $file;
+
+		let mut tags = Vec::new();
+		$(
+			if let Some(tag) = file.$field {
+				tags.push(PlannedTag::$variant(tag));
+			}
+		)+
+
+		tags
+	}};
+}
+
+/// Read the tags of `reader` as they are stored in the file, rather than as generic [`Tag`]s
+///
+/// Only file types with tags that can be planned are supported, so this doesn't include ASF or
+/// custom file types.
+pub(crate) fn read_tags<R>(
+	reader: &mut R,
+	file_type: FileType,
+	parse_options: ParseOptions,
+) -> Result<Vec<PlannedTag>>
+where
+	R: Read + Seek,
+{
+	let tags = match file_type {
+		FileType::Aac => read_tags!(AacFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, id3v1_tag => Id3v1),
+		FileType::Aiff => read_tags!(AiffFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, text_chunks_tag => AiffText),
+		FileType::Ape => read_tags!(ApeFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, ape_tag => Ape, id3v1_tag => Id3v1),
+		FileType::Dff => read_tags!(DffFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2),
+		FileType::Dsf => read_tags!(DsfFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2),
+		FileType::Flac => {
+			let mut flac_file = FlacFile::read_from(reader, parse_options)?;
+
+			// Pictures are written along with the Vorbis Comments, see `From<FlacFile> for TaggedFile`
+			if !flac_file.pictures.is_empty() {
+				flac_file
+					.vorbis_comments_tag
+					.get_or_insert_with(VorbisComments::default)
+					.pictures
+					.append(&mut flac_file.pictures);
+			}
+
+			read_tags!(flac_file; id3v2_tag => Id3v2, vorbis_comments_tag => VorbisComments)
+		},
+		FileType::Mpeg => read_tags!(MpegFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, ape_tag => Ape, id3v1_tag => Id3v1),
+		FileType::Mp4 => read_tags!(Mp4File::read_from(reader, parse_options)?;
+			ilst_tag => Mp4Ilst),
+		FileType::Mpc => read_tags!(MpcFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, ape_tag => Ape, id3v1_tag => Id3v1),
+		FileType::OggFlac => vec![PlannedTag::VorbisComments(
+			OggFlacFile::read_from(reader, parse_options)?.vorbis_comments_tag,
+		)],
+		FileType::Opus => vec![PlannedTag::VorbisComments(
+			OpusFile::read_from(reader, parse_options)?.vorbis_comments_tag,
+		)],
+		FileType::Speex => vec![PlannedTag::VorbisComments(
+			SpeexFile::read_from(reader, parse_options)?.vorbis_comments_tag,
+		)],
+		FileType::Vorbis => vec![PlannedTag::VorbisComments(
+			VorbisFile::read_from(reader, parse_options)?.vorbis_comments_tag,
+		)],
+		FileType::Shn => read_tags!(ShnFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, ape_tag => Ape, id3v1_tag => Id3v1),
+		FileType::Tta => read_tags!(TtaFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, ape_tag => Ape, id3v1_tag => Id3v1),
+		FileType::Wav => read_tags!(WavFile::read_from(reader, parse_options)?;
+			id3v2_tag => Id3v2, riff_info_tag => RiffInfo),
+		FileType::WavPack => read_tags!(WavPackFile::read_from(reader, parse_options)?;
+			ape_tag => Ape, id3v1_tag => Id3v1),
+		FileType::Asf | FileType::Custom(_) => err!(UnsupportedTag),
+	};
+
+	Ok(tags)
+}
+
+/// Write `tags` to `file` in a single pass
+///
+/// `locations` are the locations of the tags currently in the file, as collected while reading it
+/// (see [`TaggedFile::tag_locations`](super::TaggedFile::tag_locations)). Existing tags are replaced
+/// where they are, new tags are added where the format expects them, and empty tags are removed.
+/// All of these changes are then applied at once.
+///
+/// File types with a single writable tag type (MP4, Ogg, and DSF) are left to that tag's writer,
+/// which only needs a single pass to begin with.
+pub(crate) fn write_tags<F>(
+	file: &mut F,
+	file_type: FileType,
+	locations: &[TagLocation],
+	tags: &[PlannedTag],
+	write_options: WriteOptions,
+) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+{
+	// Nothing is written unless every tag can be
+	for tag in tags {
+		let tag_type = tag.tag_type();
+		if !file_type.supports_tag_type(tag_type)
+			|| (!tag.is_empty() && !tag_type.supports_write_for(file_type))
+		{
+			err!(UnsupportedTag);
+		}
+	}
+
+	let file_len = file.len()?;
+
+	let mut plan = WritePlan::default();
+	match file_type {
+		FileType::Aac
+		| FileType::Ape
+		| FileType::Mpc
+		| FileType::Mpeg
+		| FileType::Shn
+		| FileType::Tta
+		| FileType::WavPack => plan_stream_tags(
+			&mut plan,
+			file,
+			file_type,
+			file_len,
+			locations,
+			tags,
+			write_options,
+		)?,
+		FileType::Wav => plan_chunk_tags::<_, LittleEndian>(
+			&mut plan,
+			file,
+			file_len,
+			locations,
+			tags,
+			write_options,
+		)?,
+		FileType::Aiff => plan_chunk_tags::<_, BigEndian>(
+			&mut plan,
+			file,
+			file_len,
+			locations,
+			tags,
+			write_options,
+		)?,
+		FileType::Flac => plan_flac_tags(&mut plan, file, locations, tags, write_options)?,
+		_ => {
+			for tag in tags {
+				file.rewind()?;
+				tag.save_to(file, write_options)?;
+			}
+
+			return Ok(());
+		},
+	}
+
+	plan.apply(file)
+}
+
+fn location_range(location: &TagLocation) -> Range<u64> {
+	location.offset()..location.offset() + location.len()
+}
+
+fn locations_of(
+	locations: &[TagLocation],
+	tag_type: TagType,
+	placement: TagPlacement,
+) -> Vec<Range<u64>> {
+	locations
+		.iter()
+		.filter(|location| location.tag_type() == tag_type && location.placement() == placement)
+		.map(location_range)
+		.collect()
+}
+
+// The ID3v2 tags at the start of the file, and the offset of everything following them
+fn head_id3v2(locations: &[TagLocation]) -> (Vec<Range<u64>>, u64) {
+	let tags = locations_of(locations, TagType::Id3v2, TagPlacement::Head);
+	let end = tags.iter().map(|range| range.end).max().unwrap_or(0);
+
+	(tags, end)
+}
+
+// Replace the first of `existing` with `content` (or insert it at `position` if there are none),
+// and remove the rest
+fn replace_all(
+	plan: &mut WritePlan,
+	existing: &[Range<u64>],
+	content: Vec<u8>,
+	position: impl FnOnce() -> Result<u64>,
+) -> Result<()> {
+	match existing.split_first() {
+		Some((first, rest)) => {
+			plan.replace(first.clone(), content);
+			for range in rest {
+				plan.remove(range.clone());
+			}
+		},
+		None if !content.is_empty() => plan.insert(position()?, content),
+		None => {},
+	}
+
+	Ok(())
+}
+
+// File types with an ID3v2 tag at the start, and APE and ID3v1 tags at the end
+fn plan_stream_tags<R>(
+	plan: &mut WritePlan,
+	file: &mut R,
+	file_type: FileType,
+	file_len: u64,
+	locations: &[TagLocation],
+	tags: &[PlannedTag],
+	write_options: WriteOptions,
+) -> Result<()>
+where
+	R: Read + Seek,
+{
+	let id3v1 = locations_of(locations, TagType::Id3v1, TagPlacement::Tail);
+
+	for tag in tags {
+		match tag {
+			PlannedTag::Id3v2(id3v2) => {
+				let (existing, stream_start) = head_id3v2(locations);
+
+				// Only a single tag can be overwritten, stacked tags are merged into one
+				let preserved_len = match &*existing {
+					[range] if write_options.preserve_id3v2_size => Some(range.end - range.start),
+					_ => None,
+				};
+
+				let content = crate::id3::v2::write::create_planned_tag(
+					id3v2,
+					false,
+					write_options,
+					preserved_len,
+				)?;
+				let in_place = preserved_len == Some(content.len() as u64);
+
+				replace_all(plan, &existing, content, || Ok(0))?;
+				if in_place {
+					log::debug!("Writing ID3v2 tag in place");
+					continue;
+				}
+
+				// Some encoders include the ID3v2 tag in the VBR header's byte count
+				if file_type == FileType::Mpeg {
+					if let Some((offset, stream_len)) =
+						crate::mpeg::vbr::vbr_byte_count_update(file, stream_start)?
+					{
+						plan.replace(offset..offset + 4, stream_len.to_be_bytes().to_vec());
+					}
+				}
+			},
+			PlannedTag::Ape(_) => {
+				let existing = locations_of(locations, TagType::Ape, TagPlacement::Tail);
+
+				// An APE tag belongs before any Lyrics3v2 and ID3v1 tags
+				replace_all(plan, &existing, tag.dump(write_options)?, || {
+					let end = id3v1.first().map_or(file_len, |range| range.start);
+					if end < 15 {
+						return Ok(end);
+					}
+
+					file.seek(SeekFrom::Start(end))?;
+					find_lyrics3v2(file)?;
+					Ok(file.stream_position()?)
+				})?;
+
+				// An APE tag at the start of the file is against the spec, it's only ever removed
+				for range in locations_of(locations, TagType::Ape, TagPlacement::Head) {
+					plan.remove(range);
+				}
+			},
+			PlannedTag::Id3v1(id3v1_tag) => {
+				// An empty ID3v1 tag would still be written, since it has a fixed size
+				let content = if id3v1_tag.is_empty() {
+					Vec::new()
+				} else {
+					tag.dump(write_options)?
+				};
+
+				replace_all(plan, &id3v1, content, || Ok(file_len))?;
+			},
+			_ => err!(UnsupportedTag),
+		}
+	}
+
+	Ok(())
+}
+
+// WAV and AIFF files, which store their tags in chunks
+fn plan_chunk_tags<R, B>(
+	plan: &mut WritePlan,
+	file: &mut R,
+	file_len: u64,
+	locations: &[TagLocation],
+	tags: &[PlannedTag],
+	write_options: WriteOptions,
+) -> Result<()>
+where
+	R: Read + Seek,
+	B: ByteOrder,
+{
+	// Chunks are padded to an even size, which isn't included in the location
+	let padded = |range: Range<u64>| {
+		let end = range.end + (range.end - range.start) % 2;
+		range.start..end.min(file_len)
+	};
+
+	for tag in tags {
+		match tag {
+			PlannedTag::Id3v2(id3v2) => {
+				let existing = locations_of(locations, TagType::Id3v2, TagPlacement::Embedded);
+
+				// The chunk can only be overwritten if there's just one
+				let preserved_len = match &*existing {
+					[range] if write_options.preserve_id3v2_size => {
+						Some(range.end - range.start - 8)
+					},
+					_ => None,
+				};
+
+				let content = crate::id3::v2::write::create_planned_tag(
+					id3v2,
+					true,
+					write_options,
+					preserved_len,
+				)?;
+				if let (Some(preserved_len), [range]) = (preserved_len, &*existing) {
+					if preserved_len == content.len() as u64 {
+						log::debug!("Writing ID3v2 chunk in place");
+						plan.replace(range.start + 8..range.end, content);
+						continue;
+					}
+				}
+
+				if existing.len() > 1 {
+					log::warn!("Removing {} duplicate ID3v2 chunks", existing.len() - 1);
+				}
+
+				for range in existing {
+					plan.remove(padded(range));
+				}
+
+				if !content.is_empty() {
+					let fourcc = if write_options.uppercase_id3v2_chunk {
+						b"ID3 "
+					} else {
+						b"id3 "
+					};
+
+					plan.insert(file_len, chunk::<B>(*fourcc, &content));
+				}
+			},
+			PlannedTag::RiffInfo(_) => {
+				let existing = locations_of(locations, TagType::RiffInfo, TagPlacement::Embedded)
+					.into_iter()
+					.next()
+					.map(padded);
+				let content = tag.dump(write_options)?;
+
+				let target = match write_options.riff_info_placement {
+					RiffInfoPlacement::BeforeData | RiffInfoPlacement::AfterData => {
+						file.seek(SeekFrom::Start(12))?;
+						let data = crate::iff::wav::tag::write::find_chunks(file, file_len)?.data;
+
+						if write_options.riff_info_placement == RiffInfoPlacement::BeforeData {
+							data.map(|data| data.start)
+						} else {
+							data.map(|data| data.end)
+						}
+					},
+					_ => None,
+				};
+
+				match (existing, target) {
+					// The existing list is replaced in place when it's already where it should be,
+					// or when there's nowhere else to put it
+					(Some(info), Some(target)) if info.end == target || info.start == target => {
+						plan.replace(info, content);
+					},
+					(Some(info), None) => plan.replace(info, content),
+					(Some(info), Some(target)) => {
+						plan.remove(info);
+						plan.insert(target, content);
+					},
+					(None, target) => plan.insert(target.unwrap_or(file_len), content),
+				}
+			},
+			PlannedTag::AiffText(_) => {
+				let (existing, comm_end) = find_aiff_text_chunks(file, file_len)?;
+
+				// New chunks are placed right after the COMM chunk
+				replace_all(plan, &existing, tag.dump(write_options)?, || {
+					Ok(comm_end.unwrap_or(file_len))
+				})?;
+			},
+			_ => err!(UnsupportedTag),
+		}
+	}
+
+	if plan.edits.is_empty() {
+		return Ok(());
+	}
+
+	// The size of the RIFF/FORM chunk covers the entire file, other than its own header
+	let new_len = plan.new_len(file_len);
+	let Ok(size) = u32::try_from(new_len.saturating_sub(8)) else {
+		err!(TooMuchData);
+	};
+
+	let mut size_bytes = [0; 4];
+	B::write_u32(&mut size_bytes, size);
+	plan.replace(4..8, size_bytes.to_vec());
+
+	Ok(())
+}
+
+fn chunk<B: ByteOrder>(fourcc: [u8; 4], content: &[u8]) -> Vec<u8> {
+	let mut chunk = Vec::with_capacity(8 + content.len() + 1);
+	chunk.extend(fourcc);
+
+	let mut size = [0; 4];
+	B::write_u32(&mut size, content.len() as u32);
+	chunk.extend(size);
+	chunk.extend(content);
+
+	// It is required an odd length chunk be padded with a 0
+	// The 0 isn't included in the chunk size, however
+	if !content.len().is_multiple_of(2) {
+		chunk.push(0);
+	}
+
+	chunk
+}
+
+// AIFF text chunks are spread across multiple chunks, and have no location of their own. This
+// returns their ranges (including padding), along with the end of the COMM chunk.
+fn find_aiff_text_chunks<R>(file: &mut R, file_len: u64) -> Result<(Vec<Range<u64>>, Option<u64>)>
+where
+	R: Read + Seek,
+{
+	file.seek(SeekFrom::Start(12))?;
+
+	let mut text_chunks = Vec::new();
+	let mut comm_end = None;
+
+	let mut chunks = Chunks::<BigEndian>::new(file_len.saturating_sub(12));
+	while chunks.next(file).is_ok() {
+		let start = file.stream_position()? - 8;
+		let end = (start + 8 + u64::from(chunks.size) + u64::from(chunks.size % 2)).min(file_len);
+
+		match &chunks.fourcc {
+			b"NAME" | b"AUTH" | b"(c) " | b"ANNO" | b"COMT" => text_chunks.push(start..end),
+			b"COMM" if comm_end.is_none() => comm_end = Some(end),
+			_ => {},
+		}
+
+		chunks.skip(file)?;
+	}
+
+	Ok((text_chunks, comm_end))
+}
+
+fn plan_flac_tags<R>(
+	plan: &mut WritePlan,
+	file: &mut R,
+	locations: &[TagLocation],
+	tags: &[PlannedTag],
+	write_options: WriteOptions,
+) -> Result<()>
+where
+	R: Read + Seek,
+{
+	let (id3v2, stream_start) = head_id3v2(locations);
+
+	for tag in tags {
+		match tag {
+			// ID3v2 tags can only be removed, see `write_tags`
+			PlannedTag::Id3v2(_) => {
+				for range in &id3v2 {
+					plan.remove(range.clone());
+				}
+			},
+			PlannedTag::VorbisComments(vorbis_comments) => {
+				file.seek(SeekFrom::Start(stream_start))?;
+
+				// Removing the tag leaves padding behind, so the audio doesn't have to move
+				if vorbis_comments.is_empty() {
+					crate::flac::write::plan_tag_block_removal(file, plan)?;
+					continue;
+				}
+
+				let mut comments_ref = VorbisCommentsRef {
+					vendor: Cow::from(vorbis_comments.vendor.as_str()),
+					items: vorbis_comments
+						.items
+						.iter()
+						.map(|(k, v)| (k.as_str(), v.as_str())),
+					pictures: vorbis_comments.pictures.iter().map(|(p, i)| (p, *i)),
+				};
+
+				crate::flac::write::plan_metadata(file, &mut comments_ref, write_options, plan)?;
+			},
+			_ => err!(UnsupportedTag),
+		}
+	}
+
+	Ok(())
+}
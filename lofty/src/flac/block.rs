@@ -1,19 +1,26 @@
 #![allow(dead_code)]
 
-use crate::error::Result;
-use crate::macros::try_vec;
+use crate::config::ParsingMode;
+use crate::error::{ParseDiagnostic, Result};
+use crate::macros::err;
+use crate::util::alloc::VecFallibleCapacity;
+use crate::util::diagnostics;
 
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
 
 use byteorder::{BigEndian, ReadBytesExt};
 
-pub(in crate::flac) const BLOCK_ID_STREAMINFO: u8 = 0;
-pub(in crate::flac) const BLOCK_ID_PADDING: u8 = 1;
-pub(in crate::flac) const BLOCK_ID_SEEKTABLE: u8 = 3;
-pub(in crate::flac) const BLOCK_ID_VORBIS_COMMENTS: u8 = 4;
-pub(in crate::flac) const BLOCK_ID_PICTURE: u8 = 6;
+pub(crate) const BLOCK_ID_STREAMINFO: u8 = 0;
+pub(crate) const BLOCK_ID_PADDING: u8 = 1;
+pub(crate) const BLOCK_ID_SEEKTABLE: u8 = 3;
+pub(crate) const BLOCK_ID_VORBIS_COMMENTS: u8 = 4;
+pub(crate) const BLOCK_ID_CUESHEET: u8 = 5;
+pub(crate) const BLOCK_ID_PICTURE: u8 = 6;
 
-const BLOCK_HEADER_SIZE: u64 = 4;
+pub(super) const BLOCK_HEADER_SIZE: u64 = 4;
+
+// Block content is read incrementally, so a bogus size can't cause a large allocation up front
+const READ_CHUNK_SIZE: usize = 64 * 1024;
 
 pub(crate) struct Block {
 	pub(super) byte: u8,
@@ -25,30 +32,58 @@ pub(crate) struct Block {
 }
 
 impl Block {
-	pub(crate) fn read<R, P>(data: &mut R, mut predicate: P) -> Result<Self>
+	/// Read a block, only reading its content if `predicate` returns `true`
+	///
+	/// A block that claims to be larger than the remainder of the stream is an error with
+	/// [`ParsingMode::Strict`]. Otherwise, it is truncated and marked as the last block.
+	pub(crate) fn read<R, P>(
+		data: &mut R,
+		parse_mode: ParsingMode,
+		mut predicate: P,
+	) -> Result<Self>
 	where
 		R: Read + Seek,
-		P: FnMut(u8) -> bool,
+		P: FnMut(u8, u32) -> Result<bool>,
 	{
 		let start = data.stream_position()?;
 
 		let byte = data.read_u8()?;
-		let last = (byte & 0x80) != 0;
+		let mut last = (byte & 0x80) != 0;
 		let ty = byte & 0x7F;
 
 		let size = data.read_u24::<BigEndian>()?;
 		log::trace!("Reading FLAC block, type: {ty}, size: {size}");
 
 		let mut content;
-		if predicate(ty) {
-			content = try_vec![0; size as usize];
-			data.read_exact(&mut content)?;
+		let available;
+		if predicate(ty, size)? {
+			content = Vec::try_with_capacity_stable((size as usize).min(READ_CHUNK_SIZE))?;
+			available = data.take(u64::from(size)).read_to_end(&mut content)? as u64;
 		} else {
 			content = Vec::new();
-			data.seek(SeekFrom::Current(i64::from(size)))?;
+			available = skip_content(data, size)?;
+		}
+
+		if available < u64::from(size) {
+			if parse_mode == ParsingMode::Strict {
+				err!(SizeMismatch);
+			}
+
+			log::warn!(
+				"FLAC block at offset {start} has a size of {size}, but only {available} bytes \
+				 remain, truncating"
+			);
+			diagnostics::report(ParseDiagnostic::OversizedFlacBlock {
+				offset: start,
+				size,
+				remaining: available,
+			});
+
+			// Nothing can follow a truncated block
+			last = true;
 		}
 
-		let end = start + u64::from(size) + BLOCK_HEADER_SIZE;
+		let end = start + BLOCK_HEADER_SIZE + available;
 
 		Ok(Self {
 			byte,
@@ -60,3 +95,26 @@ impl Block {
 		})
 	}
 }
+
+// Seek over the content of a block, returning how much of it is actually present in the stream
+fn skip_content<R>(data: &mut R, size: u32) -> Result<u64>
+where
+	R: Read + Seek,
+{
+	if size == 0 {
+		return Ok(0);
+	}
+
+	let content_start = data.stream_position()?;
+
+	// Seeking past the end of a stream isn't an error, so the last byte is read to verify the size
+	data.seek(SeekFrom::Current(i64::from(size) - 1))?;
+	match data.read_u8() {
+		Ok(_) => Ok(u64::from(size)),
+		Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+			let stream_len = data.seek(SeekFrom::End(0))?;
+			Ok(stream_len.saturating_sub(content_start))
+		},
+		Err(e) => Err(e.into()),
+	}
+}
@@ -0,0 +1,563 @@
+use crate::error::{ErrorKind, LoftyError, Result};
+
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Cue sheet positions are in CD frames, of which there are 75 per second
+const FRAMES_PER_SECOND: u128 = 75;
+
+/// A cue sheet
+///
+/// Cue sheets describe the layout of the tracks on a CD. They're commonly embedded in FLAC files, as
+/// a `CUESHEET` Vorbis Comment (see [`VorbisComments::cuesheet`](crate::ogg::VorbisComments::cuesheet)).
+///
+/// Only the commands relevant to audio are supported. Any others (e.g. `CDTEXTFILE`) are skipped
+/// when parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::flac::CueSheet;
+/// use std::time::Duration;
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// let cue_sheet = CueSheet::parse_text(
+/// 	r#"PERFORMER "Foo artist"
+/// TITLE "Bar album"
+/// FILE "album.wav" WAVE
+///   TRACK 01 AUDIO
+///     TITLE "Baz title"
+///     INDEX 01 00:00:00
+///   TRACK 02 AUDIO
+///     TITLE "Qux title"
+///     INDEX 01 03:15:00"#,
+/// )?;
+///
+/// assert_eq!(cue_sheet.title.as_deref(), Some("Bar album"));
+/// assert_eq!(cue_sheet.tracks.len(), 2);
+///
+/// let second_track = &cue_sheet.tracks[1];
+/// assert_eq!(second_track.title.as_deref(), Some("Qux title"));
+/// assert_eq!(second_track.start(), Some(Duration::from_secs(3 * 60 + 15)));
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CueSheet {
+	/// Comments (`REM`), which are commonly used for extra metadata, such as `GENRE` and `DATE`
+	pub comments: Vec<String>,
+	/// The Media Catalog Number (`CATALOG`)
+	pub catalog: Option<String>,
+	/// The album artist (`PERFORMER`)
+	pub performer: Option<String>,
+	/// The album title (`TITLE`)
+	pub title: Option<String>,
+	/// The album songwriter (`SONGWRITER`)
+	pub songwriter: Option<String>,
+	/// The audio files (`FILE`), referenced by [`CueSheetIndex::file`]
+	pub files: Vec<CueSheetFile>,
+	/// The tracks (`TRACK`)
+	pub tracks: Vec<CueSheetTrack>,
+}
+
+/// A file referenced by a [`CueSheet`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CueSheetFile {
+	/// The file name
+	pub name: String,
+	/// The file type, such as `WAVE` or `MP3`
+	pub file_type: String,
+}
+
+/// A track in a [`CueSheet`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CueSheetTrack {
+	/// The track number
+	pub number: u8,
+	/// The track's data type, such as `AUDIO`
+	pub track_type: String,
+	/// The track title (`TITLE`)
+	pub title: Option<String>,
+	/// The track artist (`PERFORMER`)
+	pub performer: Option<String>,
+	/// The track songwriter (`SONGWRITER`)
+	pub songwriter: Option<String>,
+	/// Comments (`REM`), such as ReplayGain information
+	pub comments: Vec<String>,
+	/// The subcode flags (`FLAGS`), such as `DCP` or `PRE`
+	pub flags: Vec<String>,
+	/// The International Standard Recording Code (`ISRC`)
+	pub isrc: Option<String>,
+	/// The length of silence to generate before the track (`PREGAP`)
+	///
+	/// This is only used for gaps that aren't part of any file.
+	pub pregap: Option<Duration>,
+	/// The length of silence to generate after the track (`POSTGAP`)
+	pub postgap: Option<Duration>,
+	/// The track's indices (`INDEX`)
+	///
+	/// Index 0 marks the start of the track's pregap, and index 1 the start of the track itself.
+	pub indices: Vec<CueSheetIndex>,
+}
+
+impl CueSheetTrack {
+	/// The start of the track (index 1)
+	///
+	/// This is relative to the start of the file referenced by the index.
+	pub fn start(&self) -> Option<Duration> {
+		self.indices
+			.iter()
+			.find(|index| index.number == 1)
+			.map(|index| index.position)
+	}
+}
+
+/// An index within a [`CueSheetTrack`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CueSheetIndex {
+	/// The index number
+	pub number: u8,
+	/// The position of [`CueSheet::files`] that this index refers to
+	///
+	/// A track's indices can refer to different files, such as when its pregap is stored at the end
+	/// of the previous track's file.
+	pub file: usize,
+	/// The position of the index, relative to the start of the file
+	///
+	/// Positions are stored in CD frames (1/75th of a second), so this is rounded to the nearest frame
+	/// when written.
+	pub position: Duration,
+}
+
+impl CueSheet {
+	/// Parse a cue sheet from its text format
+	///
+	/// # Errors
+	///
+	/// * A `TRACK` appears before any `FILE`
+	/// * An `INDEX`, `PREGAP`, `POSTGAP`, `FLAGS`, or `ISRC` appears before any `TRACK`
+	/// * A number or timestamp is invalid
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::flac::CueSheet;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let cue_sheet = CueSheet::parse_text(
+	/// 	"FILE \"album.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00",
+	/// )?;
+	///
+	/// assert_eq!(cue_sheet.files[0].name, "album.wav");
+	/// assert_eq!(cue_sheet.tracks[0].number, 1);
+	/// # Ok(()) }
+	/// ```
+	pub fn parse_text(text: &str) -> Result<Self> {
+		let mut cue_sheet = CueSheet::default();
+
+		let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+		for line in text.lines() {
+			let line = line.trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			let (command, value) = line
+				.split_once(char::is_whitespace)
+				.map_or((line, ""), |(command, value)| (command, value.trim()));
+
+			cue_sheet.parse_command(command, value)?;
+		}
+
+		Ok(cue_sheet)
+	}
+
+	fn parse_command(&mut self, command: &str, value: &str) -> Result<()> {
+		let in_track = self.tracks.last_mut();
+
+		match command.to_ascii_uppercase().as_str() {
+			"REM" => match in_track {
+				Some(track) => track.comments.push(value.to_owned()),
+				None => self.comments.push(value.to_owned()),
+			},
+			"CATALOG" => self.catalog = Some(value.to_owned()),
+			"TITLE" => match in_track {
+				Some(track) => track.title = Some(unquote(value).to_owned()),
+				None => self.title = Some(unquote(value).to_owned()),
+			},
+			"PERFORMER" => match in_track {
+				Some(track) => track.performer = Some(unquote(value).to_owned()),
+				None => self.performer = Some(unquote(value).to_owned()),
+			},
+			"SONGWRITER" => match in_track {
+				Some(track) => track.songwriter = Some(unquote(value).to_owned()),
+				None => self.songwriter = Some(unquote(value).to_owned()),
+			},
+			"FILE" => {
+				// The file name may contain spaces, but the type never does
+				let (name, file_type) = value
+					.rsplit_once(char::is_whitespace)
+					.ok_or_else(|| cue_sheet_err("FILE is missing a type"))?;
+
+				self.files.push(CueSheetFile {
+					name: unquote(name).to_owned(),
+					file_type: file_type.to_owned(),
+				});
+			},
+			"TRACK" => {
+				if self.files.is_empty() {
+					return Err(cue_sheet_err("Found a TRACK before any FILE"));
+				}
+
+				let (number, track_type) = value
+					.split_once(char::is_whitespace)
+					.ok_or_else(|| cue_sheet_err("TRACK is missing a type"))?;
+
+				self.tracks.push(CueSheetTrack {
+					number: number
+						.parse()
+						.map_err(|_| cue_sheet_err("Invalid TRACK number"))?,
+					track_type: track_type.trim().to_owned(),
+					..CueSheetTrack::default()
+				});
+			},
+			"INDEX" => {
+				let track =
+					in_track.ok_or_else(|| cue_sheet_err("Found an INDEX outside of a TRACK"))?;
+				let file = self
+					.files
+					.len()
+					.checked_sub(1)
+					.ok_or_else(|| cue_sheet_err("Found an INDEX before any FILE"))?;
+
+				let (number, position) = value
+					.split_once(char::is_whitespace)
+					.ok_or_else(|| cue_sheet_err("INDEX is missing a position"))?;
+
+				track.indices.push(CueSheetIndex {
+					number: number
+						.parse()
+						.map_err(|_| cue_sheet_err("Invalid INDEX number"))?,
+					file,
+					position: parse_position(position.trim())?,
+				});
+			},
+			"PREGAP" => {
+				let track =
+					in_track.ok_or_else(|| cue_sheet_err("Found a PREGAP outside of a TRACK"))?;
+				track.pregap = Some(parse_position(value)?);
+			},
+			"POSTGAP" => {
+				let track =
+					in_track.ok_or_else(|| cue_sheet_err("Found a POSTGAP outside of a TRACK"))?;
+				track.postgap = Some(parse_position(value)?);
+			},
+			"FLAGS" => {
+				let track =
+					in_track.ok_or_else(|| cue_sheet_err("Found FLAGS outside of a TRACK"))?;
+				track.flags = value.split_whitespace().map(str::to_owned).collect();
+			},
+			"ISRC" => {
+				let track =
+					in_track.ok_or_else(|| cue_sheet_err("Found an ISRC outside of a TRACK"))?;
+				track.isrc = Some(value.to_owned());
+			},
+			_ => log::warn!("Cue sheet: Skipping unsupported command {command:?}"),
+		}
+
+		Ok(())
+	}
+}
+
+impl Display for CueSheet {
+	/// Write the cue sheet in its text format
+	///
+	/// This uses the same layout and CRLF line endings as Exact Audio Copy.
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		for comment in &self.comments {
+			write!(f, "REM {comment}\r\n")?;
+		}
+
+		if let Some(catalog) = &self.catalog {
+			write!(f, "CATALOG {catalog}\r\n")?;
+		}
+
+		if let Some(performer) = &self.performer {
+			write!(f, "PERFORMER \"{performer}\"\r\n")?;
+		}
+
+		if let Some(title) = &self.title {
+			write!(f, "TITLE \"{title}\"\r\n")?;
+		}
+
+		if let Some(songwriter) = &self.songwriter {
+			write!(f, "SONGWRITER \"{songwriter}\"\r\n")?;
+		}
+
+		let mut current_file = None;
+		for track in &self.tracks {
+			// The file of the first index is written before the track, any changes within the track
+			// are written inline
+			let first_file = track
+				.indices
+				.first()
+				.map(|index| index.file)
+				.or(current_file)
+				.unwrap_or_default();
+			self.write_file(f, &mut current_file, first_file)?;
+
+			write!(f, "  TRACK {:02} {}\r\n", track.number, track.track_type)?;
+
+			if let Some(title) = &track.title {
+				write!(f, "    TITLE \"{title}\"\r\n")?;
+			}
+
+			if let Some(performer) = &track.performer {
+				write!(f, "    PERFORMER \"{performer}\"\r\n")?;
+			}
+
+			if let Some(songwriter) = &track.songwriter {
+				write!(f, "    SONGWRITER \"{songwriter}\"\r\n")?;
+			}
+
+			for comment in &track.comments {
+				write!(f, "    REM {comment}\r\n")?;
+			}
+
+			if !track.flags.is_empty() {
+				write!(f, "    FLAGS {}\r\n", track.flags.join(" "))?;
+			}
+
+			if let Some(isrc) = &track.isrc {
+				write!(f, "    ISRC {isrc}\r\n")?;
+			}
+
+			if let Some(pregap) = track.pregap {
+				write!(f, "    PREGAP {}\r\n", Position(pregap))?;
+			}
+
+			for index in &track.indices {
+				self.write_file(f, &mut current_file, index.file)?;
+				write!(
+					f,
+					"    INDEX {:02} {}\r\n",
+					index.number,
+					Position(index.position)
+				)?;
+			}
+
+			if let Some(postgap) = track.postgap {
+				write!(f, "    POSTGAP {}\r\n", Position(postgap))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl CueSheet {
+	fn write_file(
+		&self,
+		f: &mut Formatter<'_>,
+		current_file: &mut Option<usize>,
+		file: usize,
+	) -> std::fmt::Result {
+		if *current_file == Some(file) {
+			return Ok(());
+		}
+
+		*current_file = Some(file);
+		match self.files.get(file) {
+			Some(file) => write!(f, "FILE \"{}\" {}\r\n", file.name, file.file_type),
+			None => Ok(()),
+		}
+	}
+}
+
+/// Formats a [`Duration`] as a cue sheet position (MM:SS:FF)
+struct Position(Duration);
+
+impl Display for Position {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let frames = (self.0.as_nanos() * FRAMES_PER_SECOND + 500_000_000) / 1_000_000_000;
+
+		let minutes = frames / (60 * FRAMES_PER_SECOND);
+		let seconds = (frames / FRAMES_PER_SECOND) % 60;
+		let frames = frames % FRAMES_PER_SECOND;
+
+		write!(f, "{minutes:02}:{seconds:02}:{frames:02}")
+	}
+}
+
+// Parses a cue sheet position (MM:SS:FF)
+fn parse_position(position: &str) -> Result<Duration> {
+	let mut parts = position.splitn(3, ':').map(str::parse::<u32>);
+
+	let (Some(Ok(minutes)), Some(Ok(seconds)), Some(Ok(frames))) =
+		(parts.next(), parts.next(), parts.next())
+	else {
+		return Err(cue_sheet_err("Invalid position"));
+	};
+
+	if seconds >= 60 || u128::from(frames) >= FRAMES_PER_SECOND {
+		return Err(cue_sheet_err("Invalid position"));
+	}
+
+	let frames =
+		(u128::from(minutes) * 60 + u128::from(seconds)) * FRAMES_PER_SECOND + u128::from(frames);
+
+	Ok(Duration::from_nanos(
+		(frames * 1_000_000_000 / FRAMES_PER_SECOND) as u64,
+	))
+}
+
+// Strips the surrounding quotes from a value, if any
+//
+// There's no way to escape quotes, so any within the value are kept as-is.
+fn unquote(value: &str) -> &str {
+	value
+		.strip_prefix('"')
+		.and_then(|value| value.strip_suffix('"'))
+		.unwrap_or(value)
+}
+
+fn cue_sheet_err(message: &'static str) -> LoftyError {
+	LoftyError::new(ErrorKind::BadCueSheet(message))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{CueSheet, CueSheetFile, CueSheetIndex};
+
+	use std::time::Duration;
+
+	// Generated by Exact Audio Copy, using "Multiple WAV Files With Gaps... (Noncompliant)"
+	const EAC_MULTIPLE_FILES: &str = "REM GENRE Rock\r\n\
+		REM DATE 1997\r\n\
+		REM DISCID 2A0A6F04\r\n\
+		REM COMMENT \"ExactAudioCopy v1.6\"\r\n\
+		CATALOG 0724385583826\r\n\
+		PERFORMER \"Foo Artist\"\r\n\
+		TITLE \"Bar Album\"\r\n\
+		FILE \"01 - Intro.wav\" WAVE\r\n  \
+		TRACK 01 AUDIO\r\n    \
+		TITLE \"Intro\"\r\n    \
+		PERFORMER \"Foo Artist\"\r\n    \
+		FLAGS DCP\r\n    \
+		ISRC GBAYE9700001\r\n    \
+		INDEX 01 00:00:00\r\n  \
+		TRACK 02 AUDIO\r\n    \
+		TITLE \"Say \"Hello\" Again\"\r\n    \
+		PERFORMER \"Foo Artist\"\r\n    \
+		ISRC GBAYE9700002\r\n    \
+		INDEX 00 02:47:65\r\n\
+		FILE \"02 - Say Hello Again.wav\" WAVE\r\n    \
+		INDEX 01 00:00:00\r\n\
+		FILE \"03 - Outro.wav\" WAVE\r\n  \
+		TRACK 03 AUDIO\r\n    \
+		TITLE \"Outro\"\r\n    \
+		PERFORMER \"Foo Artist\"\r\n    \
+		PREGAP 00:02:00\r\n    \
+		INDEX 01 00:00:00\r\n";
+
+	#[test_log::test]
+	fn parse_eac() {
+		let cue_sheet = CueSheet::parse_text(EAC_MULTIPLE_FILES).unwrap();
+
+		assert_eq!(
+			cue_sheet.comments,
+			vec![
+				"GENRE Rock",
+				"DATE 1997",
+				"DISCID 2A0A6F04",
+				"COMMENT \"ExactAudioCopy v1.6\""
+			]
+		);
+		assert_eq!(cue_sheet.catalog.as_deref(), Some("0724385583826"));
+		assert_eq!(cue_sheet.performer.as_deref(), Some("Foo Artist"));
+		assert_eq!(cue_sheet.title.as_deref(), Some("Bar Album"));
+		assert_eq!(
+			cue_sheet.files,
+			vec![
+				CueSheetFile {
+					name: String::from("01 - Intro.wav"),
+					file_type: String::from("WAVE"),
+				},
+				CueSheetFile {
+					name: String::from("02 - Say Hello Again.wav"),
+					file_type: String::from("WAVE"),
+				},
+				CueSheetFile {
+					name: String::from("03 - Outro.wav"),
+					file_type: String::from("WAVE"),
+				},
+			]
+		);
+
+		assert_eq!(cue_sheet.tracks.len(), 3);
+
+		let first = &cue_sheet.tracks[0];
+		assert_eq!(first.number, 1);
+		assert_eq!(first.track_type, "AUDIO");
+		assert_eq!(first.title.as_deref(), Some("Intro"));
+		assert_eq!(first.flags, vec!["DCP"]);
+		assert_eq!(first.isrc.as_deref(), Some("GBAYE9700001"));
+		assert_eq!(first.start(), Some(Duration::ZERO));
+
+		// The pregap of the second track is at the end of the first file
+		let second = &cue_sheet.tracks[1];
+		assert_eq!(second.title.as_deref(), Some("Say \"Hello\" Again"));
+		assert_eq!(
+			second.indices,
+			vec![
+				CueSheetIndex {
+					number: 0,
+					file: 0,
+					position: Duration::from_nanos(
+						(2 * 60 * 75 + 47 * 75 + 65) * 1_000_000_000 / 75
+					),
+				},
+				CueSheetIndex {
+					number: 1,
+					file: 1,
+					position: Duration::ZERO,
+				},
+			]
+		);
+
+		let third = &cue_sheet.tracks[2];
+		assert_eq!(third.pregap, Some(Duration::from_secs(2)));
+		assert_eq!(third.indices[0].file, 2);
+	}
+
+	#[test_log::test]
+	fn round_trip_eac() {
+		let cue_sheet = CueSheet::parse_text(EAC_MULTIPLE_FILES).unwrap();
+		assert_eq!(cue_sheet.to_string(), EAC_MULTIPLE_FILES);
+
+		// Unusual formatting is normalized
+		let cue_sheet = CueSheet::parse_text(
+			"\u{FEFF}title \"Bar Album\"\nfile album.flac WAVE\ntrack 1 AUDIO\nindex 1 0:00:01\n",
+		)
+		.unwrap();
+		assert_eq!(
+			cue_sheet.to_string(),
+			"TITLE \"Bar Album\"\r\nFILE \"album.flac\" WAVE\r\n  TRACK 01 AUDIO\r\n    INDEX 01 \
+			 00:00:01\r\n"
+		);
+	}
+
+	#[test_log::test]
+	fn parse_invalid() {
+		for text in [
+			"TRACK 01 AUDIO",
+			"INDEX 01 00:00:00",
+			"FILE \"album.wav\" WAVE\nINDEX 01 00:00:00",
+			"FILE \"album.wav\" WAVE\nTRACK XX AUDIO",
+			"FILE \"album.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:60:00",
+			"FILE \"album.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:00:75",
+			"FILE \"album.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:00",
+		] {
+			assert!(CueSheet::parse_text(text).is_err(), "{text:?}");
+		}
+	}
+}
@@ -5,25 +5,30 @@
 //! * See [`FlacFile`]
 
 pub(crate) mod block;
+mod cue_sheet;
 pub(crate) mod properties;
-mod read;
+pub(crate) mod read;
+pub(crate) mod verify;
 pub(crate) mod write;
 
 use crate::config::WriteOptions;
 use crate::error::{LoftyError, Result};
 use crate::file::{FileType, TaggedFile};
 use crate::id3::v2::tag::Id3v2Tag;
+use crate::id3::v2::FrameId;
 use crate::ogg::tag::VorbisCommentsRef;
 use crate::ogg::{OggPictureStorage, VorbisComments};
-use crate::picture::{Picture, PictureInformation};
+use crate::picture::{Picture, PictureInformation, PictureType};
 use crate::tag::TagExt;
 use crate::util::io::{FileLike, Length, Truncate};
 
 use std::borrow::Cow;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 use lofty_attr::LoftyFile;
 
 // Exports
+pub use cue_sheet::{CueSheet, CueSheetFile, CueSheetIndex, CueSheetTrack};
 pub use properties::FlacProperties;
 
 /// A FLAC file
@@ -34,7 +39,9 @@ pub use properties::FlacProperties;
 /// * Pictures are stored in the `FlacFile` itself, rather than the tag. Any pictures inside the tag will
 ///   be extracted out and stored in their own picture blocks.
 /// * It is possible to put pictures inside of the tag, that will not be accessible using the available
-///   methods on `FlacFile` ([`FlacFile::pictures`], [`FlacFile::remove_picture_type`], etc.)
+///   methods on `FlacFile` ([`FlacFile::pictures`], [`FlacFile::remove_picture_type`], etc.). To work with
+///   the pictures in both places, see [`FlacFile::all_pictures`], [`FlacFile::replace_picture`], and
+///   [`FlacFile::remove_all_pictures_of_type`].
 /// * When converting to [`TaggedFile`], all pictures will be put inside of a [`VorbisComments`] tag, even if the
 ///   file did not originally contain one.
 #[derive(LoftyFile)]
@@ -49,11 +56,212 @@ pub struct FlacFile {
 	#[lofty(tag_type = "VorbisComments")]
 	pub(crate) vorbis_comments_tag: Option<VorbisComments>,
 	pub(crate) pictures: Vec<(Picture, PictureInformation)>,
+	pub(crate) cue_sheet_isrc: Option<String>,
 	/// The file's audio properties
 	pub(crate) properties: FlacProperties,
 }
 
+/// How [`FlacFile::replace_picture`] handles matching pictures in the [`VorbisComments`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum CommentPicturePolicy {
+	/// Remove the matching `METADATA_BLOCK_PICTURE` entries
+	#[default]
+	Remove,
+	/// Replace the matching `METADATA_BLOCK_PICTURE` entries with the new picture
+	///
+	/// This keeps the [`VorbisComments`] tag self-contained, for when it is used outside of the file.
+	/// The picture will still only be written once.
+	Update,
+}
+
 impl FlacFile {
+	/// Returns the pictures stored in both the picture blocks and the [`VorbisComments`]
+	///
+	/// Pictures can either be stored in their own blocks ([`FlacFile::pictures`]), or as `METADATA_BLOCK_PICTURE`
+	/// entries in the [`VorbisComments`]. Some files use both, so this returns the pictures from the picture blocks,
+	/// followed by those from the [`VorbisComments`]. Pictures with the same type and data are only returned once.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::flac::FlacFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut file = std::fs::File::open("tests/files/assets/minimal/full_test.flac")?;
+	/// let flac_file = FlacFile::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// for (picture, _information) in flac_file.all_pictures() {
+	/// 	println!("{:?}", picture.pic_type());
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn all_pictures(&self) -> Vec<&(Picture, PictureInformation)> {
+		let comment_pictures = self
+			.vorbis_comments_tag
+			.iter()
+			.flat_map(|vorbis_comments| vorbis_comments.pictures.iter());
+
+		let mut pictures: Vec<&(Picture, PictureInformation)> =
+			Vec::with_capacity(self.pictures.len());
+		let mut hashes = Vec::with_capacity(self.pictures.len());
+		for entry in self.pictures.iter().chain(comment_pictures) {
+			let hash = picture_hash(&entry.0);
+
+			let is_duplicate = pictures
+				.iter()
+				.zip(&hashes)
+				.any(|(existing, existing_hash)| {
+					*existing_hash == hash && same_picture(&existing.0, &entry.0)
+				});
+			if !is_duplicate {
+				pictures.push(entry);
+				hashes.push(hash);
+			}
+		}
+
+		pictures
+	}
+
+	/// Replaces all pictures of the same type with `picture`
+	///
+	/// The picture is stored in its own block, replacing any existing blocks of the same [`PictureType`].
+	/// Any `METADATA_BLOCK_PICTURE` entries of that type in the [`VorbisComments`] are handled according
+	/// to `policy`, so that no stale picture is left behind.
+	///
+	/// NOTE: If `information` is `None`, the [`PictureInformation`] will be inferred using [`PictureInformation::from_picture`].
+	///
+	/// # Errors
+	///
+	/// * See [`PictureInformation::from_picture`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::flac::{CommentPicturePolicy, FlacFile};
+	/// use lofty::ogg::{OggPictureStorage, VorbisComments};
+	/// use lofty::picture::{MimeType, Picture, PictureInformation, PictureType};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut file = std::fs::File::open("tests/files/assets/minimal/full_test.flac")?;
+	/// let mut flac_file = FlacFile::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// let cover = |data: &[u8]| {
+	/// 	Picture::new_unchecked(
+	/// 		PictureType::CoverFront,
+	/// 		Some(MimeType::Png),
+	/// 		None,
+	/// 		data.to_vec(),
+	/// 	)
+	/// };
+	///
+	/// // A front cover stored as a `METADATA_BLOCK_PICTURE` entry
+	/// let mut vorbis_comments = VorbisComments::default();
+	/// vorbis_comments.insert_picture(cover(b"old"), Some(PictureInformation::default()))?;
+	/// flac_file.set_vorbis_comments(vorbis_comments);
+	///
+	/// flac_file.replace_picture(
+	/// 	cover(b"new"),
+	/// 	Some(PictureInformation::default()),
+	/// 	CommentPicturePolicy::Remove,
+	/// )?;
+	///
+	/// let pictures = flac_file.all_pictures();
+	/// assert_eq!(pictures.len(), 1);
+	/// assert_eq!(pictures[0].0.data(), b"new");
+	/// # Ok(()) }
+	/// ```
+	pub fn replace_picture(
+		&mut self,
+		picture: Picture,
+		information: Option<PictureInformation>,
+		policy: CommentPicturePolicy,
+	) -> Result<()> {
+		let information = match information {
+			Some(information) => information,
+			None => PictureInformation::from_picture(&picture)?,
+		};
+
+		let picture_type = picture.pic_type;
+		self.remove_picture_type(picture_type);
+
+		if let Some(vorbis_comments) = &mut self.vorbis_comments_tag {
+			let first_match = vorbis_comments
+				.pictures
+				.iter()
+				.position(|(pic, _)| pic.pic_type == picture_type);
+
+			vorbis_comments
+				.pictures
+				.retain(|(pic, _)| pic.pic_type != picture_type);
+
+			if let (Some(pos), CommentPicturePolicy::Update) = (first_match, policy) {
+				vorbis_comments
+					.pictures
+					.insert(pos, (picture.clone(), information));
+			}
+		}
+
+		self.pictures.push((picture, information));
+		Ok(())
+	}
+
+	/// Removes all pictures of a [`PictureType`] from both the picture blocks and the [`VorbisComments`]
+	///
+	/// Unlike [`FlacFile::remove_picture_type`], this also removes the `METADATA_BLOCK_PICTURE` entries.
+	pub fn remove_all_pictures_of_type(&mut self, picture_type: PictureType) {
+		self.remove_picture_type(picture_type);
+
+		if let Some(vorbis_comments) = &mut self.vorbis_comments_tag {
+			vorbis_comments.remove_picture_type(picture_type);
+		}
+	}
+
+	/// Returns the International Standard Recording Code (ISRC) of the file
+	///
+	/// The tags take precedence, in the order of the [`VorbisComments`] (`ISRC`), then the
+	/// [`Id3v2Tag`] (`TSRC`). If neither has an ISRC, this falls back to the one in the `CUESHEET`
+	/// block, as long as the cue sheet describes a single track.
+	///
+	/// NOTE: The `CUESHEET` block is read-only. It is never changed when writing, and its ISRC is
+	///       not part of the tags when converting to a [`TaggedFile`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::flac::FlacFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut file = std::fs::File::open("tests/files/assets/minimal/full_test.flac")?;
+	/// let flac_file = FlacFile::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// if let Some(isrc) = flac_file.isrc() {
+	/// 	println!("ISRC: {isrc}");
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn isrc(&self) -> Option<&str> {
+		let vorbis_comments_isrc = self
+			.vorbis_comments_tag
+			.as_ref()
+			.and_then(|vorbis_comments| vorbis_comments.get("ISRC"));
+		let id3v2_isrc = || {
+			self.id3v2_tag
+				.as_ref()
+				.and_then(|id3v2| id3v2.get_text(&FrameId::Valid(Cow::Borrowed("TSRC"))))
+		};
+
+		vorbis_comments_isrc
+			.or_else(id3v2_isrc)
+			.or(self.cue_sheet_isrc.as_deref())
+	}
+
 	// We need a special write fn to append our pictures into a `VorbisComments` tag
 	fn write_to<F>(&self, file: &mut F, write_options: WriteOptions) -> Result<()>
 	where
@@ -77,6 +285,13 @@ impl FlacFile {
 				pictures: vorbis_comments
 					.pictures
 					.iter()
+					// Pictures stored in both places would otherwise be written twice
+					.filter(|(p, _)| {
+						!self
+							.pictures
+							.iter()
+							.any(|(existing, _)| same_picture(existing, p))
+					})
 					.map(|(p, i)| (p, *i))
 					.chain(self.pictures.iter().map(|(p, i)| (p, *i))),
 			}
@@ -97,6 +312,16 @@ impl FlacFile {
 	}
 }
 
+fn picture_hash(picture: &Picture) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	picture.data().hash(&mut hasher);
+	hasher.finish()
+}
+
+fn same_picture(a: &Picture, b: &Picture) -> bool {
+	a.pic_type == b.pic_type && a.data() == b.data()
+}
+
 impl OggPictureStorage for FlacFile {
 	fn pictures(&self) -> &[(Picture, PictureInformation)] {
 		&self.pictures
@@ -134,6 +359,10 @@ impl From<FlacFile> for TaggedFile {
 
 				tags
 			},
+			removed_tags: Vec::new(),
+			tag_precedence: Vec::new(),
+			diagnostics: Vec::new(),
+			tag_locations: Vec::new(),
 		}
 	}
 }
@@ -1,5 +1,5 @@
 use crate::error::Result;
-use crate::properties::FileProperties;
+use crate::properties::{ChannelMask, FileProperties};
 
 use std::io::Read;
 use std::time::Duration;
@@ -8,6 +8,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 
 /// A FLAC file's audio properties
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct FlacProperties {
 	pub(crate) duration: Duration,
@@ -16,6 +17,7 @@ pub struct FlacProperties {
 	pub(crate) sample_rate: u32,
 	pub(crate) bit_depth: u8,
 	pub(crate) channels: u8,
+	pub(crate) channel_mask: Option<ChannelMask>,
 	pub(crate) signature: u128,
 }
 
@@ -28,7 +30,7 @@ impl From<FlacProperties> for FileProperties {
 			sample_rate: Some(input.sample_rate),
 			bit_depth: Some(input.bit_depth),
 			channels: Some(input.channels),
-			channel_mask: None,
+			channel_mask: input.channel_mask,
 		}
 	}
 }
@@ -64,6 +66,14 @@ impl FlacProperties {
 		self.channels
 	}
 
+	/// Channel mask
+	///
+	/// This is taken from the `WAVEFORMATEXTENSIBLE_CHANNEL_MASK` Vorbis Comment if it exists
+	/// (and tags were read), otherwise it is derived from the channel count.
+	pub fn channel_mask(&self) -> Option<ChannelMask> {
+		self.channel_mask
+	}
+
 	/// MD5 signature of the unencoded audio data
 	pub fn signature(&self) -> u128 {
 		self.signature
@@ -108,19 +118,29 @@ where
 		sample_rate,
 		bit_depth: bits_per_sample as u8,
 		channels: channels as u8,
+		channel_mask: ChannelMask::from_vorbis_channels(channels as u8),
 		signature,
 		..FlacProperties::default()
 	};
 
-	if sample_rate > 0 && total_samples > 0 {
-		let length = (u64::from(total_samples) * 1000) / u64::from(sample_rate);
-		properties.duration = Duration::from_millis(length);
+	properties.set_length(u64::from(total_samples), stream_length, file_length);
+
+	Ok(properties)
+}
+
+impl FlacProperties {
+	// Calculate the duration and bitrates, this does nothing if `total_samples` is unknown (0)
+	pub(crate) fn set_length(&mut self, total_samples: u64, stream_length: u64, file_length: u64) {
+		if self.sample_rate == 0 || total_samples == 0 {
+			return;
+		}
+
+		let length = (total_samples * 1000) / u64::from(self.sample_rate);
+		self.duration = Duration::from_millis(length);
 
 		if length > 0 && file_length > 0 && stream_length > 0 {
-			properties.overall_bitrate = ((file_length * 8) / length) as u32;
-			properties.audio_bitrate = ((stream_length * 8) / length) as u32;
+			self.overall_bitrate = ((file_length * 8) / length) as u32;
+			self.audio_bitrate = ((stream_length * 8) / length) as u32;
 		}
 	}
-
-	Ok(properties)
 }
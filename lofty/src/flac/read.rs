@@ -1,16 +1,21 @@
-use super::block::Block;
+use super::block::{Block, BLOCK_HEADER_SIZE};
 use super::properties::FlacProperties;
 use super::FlacFile;
-use crate::config::{ParseOptions, ParsingMode};
-use crate::error::Result;
-use crate::flac::block::{BLOCK_ID_PICTURE, BLOCK_ID_STREAMINFO, BLOCK_ID_VORBIS_COMMENTS};
-use crate::id3::v2::read::parse_id3v2;
-use crate::id3::{find_id3v2, FindId3v2Config, ID3FindResults};
+use crate::config::{IoHint, ParseOptions, ParsingMode, PictureDataPolicy};
+use crate::error::{ParseDiagnostic, Result};
+use crate::flac::block::{
+	BLOCK_ID_CUESHEET, BLOCK_ID_PICTURE, BLOCK_ID_STREAMINFO, BLOCK_ID_VORBIS_COMMENTS,
+};
+use crate::id3::{find_id3v2, FindId3v2Config};
 use crate::macros::{decode_err, err};
 use crate::ogg::read::read_comments;
 use crate::picture::Picture;
+use crate::properties::{self, ChannelMask};
+use crate::tag::{TagLocation, TagPlacement, TagType};
+use crate::util::{diagnostics, picture_sink, tag_locations};
 
 use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
 
 pub(super) fn verify_flac<R>(data: &mut R) -> Result<Block>
 where
@@ -23,7 +28,7 @@ where
 		decode_err!(@BAIL Flac, "File missing \"fLaC\" stream marker");
 	}
 
-	let block = Block::read(data, |_| true)?;
+	let block = Block::read(data, ParsingMode::Strict, |_, _| Ok(true))?;
 
 	if block.ty != BLOCK_ID_STREAMINFO {
 		decode_err!(@BAIL Flac, "File missing mandatory STREAMINFO block");
@@ -41,6 +46,7 @@ where
 		id3v2_tag: None,
 		vorbis_comments_tag: None,
 		pictures: Vec::new(),
+		cue_sheet_isrc: None,
 		properties: FlacProperties::default(),
 	};
 
@@ -51,12 +57,8 @@ where
 	};
 
 	// It is possible for a FLAC file to contain an ID3v2 tag
-	if let ID3FindResults(Some(header), Some(content)) = find_id3v2(data, find_id3v2_config)? {
+	if let Some(id3v2) = find_id3v2(data, find_id3v2_config)?.parse(parse_options)? {
 		log::warn!("Encountered an ID3v2 tag. This tag cannot be rewritten to the FLAC file!");
-
-		let reader = &mut &*content;
-
-		let id3v2 = parse_id3v2(reader, header, parse_options)?;
 		flac_file.id3v2_tag = Some(id3v2);
 	}
 
@@ -67,16 +69,57 @@ where
 		decode_err!(@BAIL Flac, "File has an invalid STREAMINFO block size (< 18)");
 	}
 
+	let should_read_content = |block_type, size| match block_type {
+		BLOCK_ID_VORBIS_COMMENTS | BLOCK_ID_CUESHEET if parse_options.read_tags => {
+			parse_options.verify_tag_size(u64::from(size))
+		},
+		BLOCK_ID_PICTURE if parse_options.read_cover_art => Ok(parse_options
+			.verify_item_size(u64::from(size))?
+			&& parse_options.picture_data_policy == PictureDataPolicy::Load),
+		_ => Ok(false),
+	};
+
 	let mut last_block = stream_info.last;
 
 	while !last_block {
-		let block = Block::read(data, |block_type| {
-			(block_type == BLOCK_ID_VORBIS_COMMENTS && parse_options.read_tags)
-				|| (block_type == BLOCK_ID_PICTURE && parse_options.read_cover_art)
-		})?;
+		let block = Block::read(data, parse_options.parsing_mode, should_read_content)?;
 
 		last_block = block.last;
 
+		if block.ty == BLOCK_ID_PICTURE
+			&& parse_options.read_cover_art
+			&& parse_options.picture_data_policy == PictureDataPolicy::Skip
+		{
+			log::debug!("Encountered a FLAC picture block, parsing without its data");
+
+			data.seek(SeekFrom::Start(block.start + BLOCK_HEADER_SIZE))?;
+			let size = (block.end - block.start - BLOCK_HEADER_SIZE) as usize;
+			match Picture::from_flac_reader(
+				&mut data.by_ref().take(size as u64),
+				size,
+				parse_options.parsing_mode,
+				PictureDataPolicy::Skip,
+			) {
+				Ok(picture) => {
+					picture_sink::consume_seekable(&picture.0, data)?;
+					flac_file.pictures.push(picture);
+				},
+				Err(e) => {
+					if parse_options.parsing_mode == ParsingMode::Strict {
+						return Err(e);
+					}
+
+					log::warn!("Unable to read FLAC picture block, discarding");
+					diagnostics::report(ParseDiagnostic::InvalidFlacPicture {
+						offset: block.start,
+					});
+				},
+			}
+
+			data.seek(SeekFrom::Start(block.end))?;
+			continue;
+		}
+
 		if block.content.is_empty() {
 			continue;
 		}
@@ -99,6 +142,20 @@ where
 				decode_err!(@BAIL Flac, "Streams are only allowed one Vorbis Comments block per stream");
 			}
 
+			if flac_file.vorbis_comments_tag.is_some() {
+				log::warn!("Encountered multiple Vorbis Comments blocks, using the latest");
+				diagnostics::report(ParseDiagnostic::MultipleVorbisCommentBlocks {
+					offset: block.start,
+				});
+			}
+
+			tag_locations::record(TagLocation::new(
+				TagType::VorbisComments,
+				block.start,
+				block.end - block.start,
+				TagPlacement::Embedded,
+			));
+
 			let vorbis_comments = read_comments(
 				&mut &*block.content,
 				block.content.len() as u64,
@@ -109,6 +166,13 @@ where
 			continue;
 		}
 
+		if block.ty == BLOCK_ID_CUESHEET && parse_options.read_tags {
+			log::debug!("Encountered a cue sheet block, parsing");
+
+			flac_file.cue_sheet_isrc = read_cue_sheet_isrc(&block.content);
+			continue;
+		}
+
 		if block.ty == BLOCK_ID_PICTURE && parse_options.read_cover_art {
 			log::debug!("Encountered a FLAC picture block, parsing");
 
@@ -120,6 +184,9 @@ where
 					}
 
 					log::warn!("Unable to read FLAC picture block, discarding");
+					diagnostics::report(ParseDiagnostic::InvalidFlacPicture {
+						offset: block.start,
+					});
 					continue;
 				},
 			}
@@ -130,21 +197,117 @@ where
 		return Ok(flac_file);
 	}
 
-	let (stream_length, file_length) = {
-		let current = data.stream_position()?;
-		let end = data.seek(SeekFrom::End(0))?;
+	let properties = (|| {
+		// The bitrates can't be calculated without seeking to the end of the file
+		let (stream_length, file_length) = if parse_options.io_hint == IoHint::StreamingRemote {
+			(0, 0)
+		} else {
+			let current = data.stream_position()?;
+			let end = data.seek(SeekFrom::End(0))?;
+
+			// In the event that a block lies about its size, the current position could be
+			// completely wrong.
+			if current > end {
+				err!(SizeMismatch);
+			}
+
+			(end - current, end)
+		};
+
+		super::properties::read_properties(&mut &*stream_info.content, stream_length, file_length)
+	})();
 
-		// In the event that a block lies about its size, the current position could be
-		// completely wrong.
-		if current > end {
-			err!(SizeMismatch);
+	flac_file.properties = properties::or_incomplete(properties, parse_options.parsing_mode)?;
+
+	// Files with a non-default channel layout store it in a Vorbis Comment
+	if let Some(channel_mask) = flac_file
+		.vorbis_comments_tag
+		.as_ref()
+		.and_then(|tag| tag.get("WAVEFORMATEXTENSIBLE_CHANNEL_MASK"))
+	{
+		let digits = channel_mask
+			.strip_prefix("0x")
+			.or_else(|| channel_mask.strip_prefix("0X"))
+			.unwrap_or(channel_mask);
+
+		match u32::from_str_radix(digits, 16) {
+			Ok(channel_mask) => flac_file.properties.channel_mask = Some(ChannelMask(channel_mask)),
+			Err(_) => log::warn!("FLAC: Invalid channel mask {channel_mask:?}, ignoring"),
 		}
+	}
+
+	Ok(flac_file)
+}
+
+/// Read the duration of a FLAC file from its STREAMINFO block
+///
+/// None of the other metadata blocks are read.
+pub(crate) fn read_duration<R>(data: &mut R, parse_options: ParseOptions) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	find_id3v2(data, FindId3v2Config::NO_READ_TAG)?;
+
+	let stream_info = verify_flac(data)?;
+	if stream_info.end - stream_info.start < 18 {
+		decode_err!(@BAIL Flac, "File has an invalid STREAMINFO block size (< 18)");
+	}
 
-		(end - current, end)
+	// The duration doesn't depend on the stream or file length
+	let properties = super::properties::read_properties(&mut &*stream_info.content, 0, 0);
+	Ok(properties::or_incomplete(properties, parse_options.parsing_mode)?.duration)
+}
+
+// The ISRC of the only track in a `CUESHEET` block
+//
+// The cue sheet describes every track on the disc, so its ISRC only applies to the whole file if there
+// is a single track, ignoring the lead-out.
+//
+// <https://xiph.org/flac/format.html#metadata_block_cuesheet>
+fn read_cue_sheet_isrc(content: &[u8]) -> Option<String> {
+	// Media catalog number (128), lead-in samples (8), flags and reserved (259)
+	const HEADER_SIZE: usize = 395;
+	// Offset (8), number (1), ISRC (12), flags and reserved (14), index point count (1)
+	const TRACK_SIZE: usize = 36;
+	const INDEX_POINT_SIZE: usize = 12;
+	const LEAD_OUT_TRACK_NUMBERS: [u8; 2] = [170, 255];
+
+	let Some((&track_count, mut tracks)) = content.get(HEADER_SIZE..).and_then(<[u8]>::split_first)
+	else {
+		log::warn!("FLAC cue sheet block is too small, ignoring");
+		return None;
 	};
 
-	flac_file.properties =
-		super::properties::read_properties(&mut &*stream_info.content, stream_length, file_length)?;
+	let mut isrc = None;
+	let mut found_tracks = 0;
+	for _ in 0..track_count {
+		let Some(track) = tracks.get(..TRACK_SIZE) else {
+			log::warn!("FLAC cue sheet block has a truncated track, ignoring");
+			return None;
+		};
 
-	Ok(flac_file)
+		let index_points = usize::from(track[TRACK_SIZE - 1]);
+		tracks = tracks
+			.get(TRACK_SIZE + index_points * INDEX_POINT_SIZE..)
+			.unwrap_or_default();
+
+		if LEAD_OUT_TRACK_NUMBERS.contains(&track[8]) {
+			continue;
+		}
+
+		found_tracks += 1;
+		isrc = Some(&track[9..21]);
+	}
+
+	if found_tracks != 1 {
+		return None;
+	}
+
+	// The ISRC is padded with NUL characters, and is entirely NUL if there is none
+	let isrc = isrc?.split(|b| *b == 0).next()?;
+	if isrc.is_empty() {
+		return None;
+	}
+
+	String::from_utf8(isrc.to_vec()).ok()
 }
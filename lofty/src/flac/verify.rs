@@ -0,0 +1,354 @@
+use super::block::Block;
+use super::read::verify_flac;
+use crate::config::ParsingMode;
+use crate::error::Result;
+use crate::id3::{find_id3v2, FindId3v2Config};
+use crate::macros::decode_err;
+use crate::properties::StreamVerification;
+
+use std::io::{Read, Seek};
+use std::time::Duration;
+
+// The frame header is at most 16 bytes, with a 7 byte coded number and 16 bit block size and sample rate
+const MAX_FRAME_HEADER_LEN: usize = 16;
+const READ_CHUNK_SIZE: u64 = 64 * 1024;
+const MAX_SKIPPED_FRAMES: u64 = 16;
+
+const CRC_8_TABLE: [u8; 256] = crc_8_table();
+const CRC_16_TABLE: [u16; 256] = crc_16_table();
+
+// CRC-8, polynomial 0x07, used for the frame headers
+const fn crc_8_table() -> [u8; 256] {
+	let mut table = [0; 256];
+
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = i as u8;
+
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 0x80 == 0 {
+				crc << 1
+			} else {
+				(crc << 1) ^ 0x07
+			};
+			bit += 1;
+		}
+
+		table[i] = crc;
+		i += 1;
+	}
+
+	table
+}
+
+// CRC-16, polynomial 0x8005, used for the entire frame
+const fn crc_16_table() -> [u16; 256] {
+	let mut table = [0; 256];
+
+	let mut i = 0;
+	while i < 256 {
+		let mut crc = (i as u16) << 8;
+
+		let mut bit = 0;
+		while bit < 8 {
+			crc = if crc & 0x8000 == 0 {
+				crc << 1
+			} else {
+				(crc << 1) ^ 0x8005
+			};
+			bit += 1;
+		}
+
+		table[i] = crc;
+		i += 1;
+	}
+
+	table
+}
+
+fn crc_8(bytes: &[u8]) -> u8 {
+	bytes
+		.iter()
+		.fold(0, |crc, byte| CRC_8_TABLE[usize::from(crc ^ byte)])
+}
+
+fn update_crc_16(crc: u16, byte: u8) -> u16 {
+	(crc << 8) ^ CRC_16_TABLE[usize::from((crc >> 8) as u8 ^ byte)]
+}
+
+struct FrameHeader {
+	len: usize,
+	block_size: u64,
+	variable_block_size: bool,
+	// The frame number for fixed block sizes, otherwise the number of the first sample
+	number: u64,
+}
+
+impl FrameHeader {
+	fn parse(bytes: &[u8]) -> Option<Self> {
+		if bytes.len() < 5 || bytes[0] != 0xFF || bytes[1] & 0xFE != 0xF8 {
+			return None;
+		}
+
+		let variable_block_size = bytes[1] & 1 == 1;
+		let block_size_bits = bytes[2] >> 4;
+		let sample_rate_bits = bytes[2] & 0x0F;
+		let channel_bits = bytes[3] >> 4;
+		let sample_size_bits = (bytes[3] >> 1) & 0b111;
+
+		// Reserved values
+		if block_size_bits == 0
+			|| sample_rate_bits == 0b1111
+			|| channel_bits > 0b1010
+			|| sample_size_bits == 0b011
+			|| bytes[3] & 1 != 0
+		{
+			return None;
+		}
+
+		// The number is coded like UTF-8, extended to 36 bits
+		let first = bytes[4];
+		let extra_bytes = match first.leading_ones() {
+			0 => 0,
+			1 | 8.. => return None,
+			n => n as usize - 1,
+		};
+
+		let mut number = u64::from(first & (0x7F >> extra_bytes));
+		let mut pos = 5;
+		for _ in 0..extra_bytes {
+			let byte = *bytes.get(pos)?;
+			if byte & 0xC0 != 0x80 {
+				return None;
+			}
+
+			number = (number << 6) | u64::from(byte & 0x3F);
+			pos += 1;
+		}
+
+		let block_size = match block_size_bits {
+			0b0001 => 192,
+			0b0010..=0b0101 => 576 << (block_size_bits - 2),
+			0b0110 => {
+				pos += 1;
+				u64::from(*bytes.get(pos - 1)?) + 1
+			},
+			0b0111 => {
+				pos += 2;
+				u64::from(u16::from_be_bytes([
+					*bytes.get(pos - 2)?,
+					*bytes.get(pos - 1)?,
+				])) + 1
+			},
+			_ => 256 << (block_size_bits - 8),
+		};
+
+		match sample_rate_bits {
+			0b1100 => pos += 1,
+			0b1101 | 0b1110 => pos += 2,
+			_ => {},
+		}
+
+		let crc = *bytes.get(pos)?;
+		if crc_8(&bytes[..pos]) != crc {
+			return None;
+		}
+
+		Some(Self {
+			len: pos + 1,
+			block_size,
+			variable_block_size,
+			number,
+		})
+	}
+
+	fn next_number(&self) -> u64 {
+		if self.variable_block_size {
+			self.number + self.block_size
+		} else {
+			self.number + 1
+		}
+	}
+}
+
+// Appends the next chunk of the stream to `buf`, returning `false` at EOF
+fn fill<R>(reader: &mut R, buf: &mut Vec<u8>) -> Result<bool>
+where
+	R: Read,
+{
+	Ok(reader.take(READ_CHUNK_SIZE).read_to_end(buf)? > 0)
+}
+
+/// Walk the frames of a FLAC stream, comparing the sample count to `STREAMINFO`
+///
+/// FLAC frames don't store their size, so a frame ends at the next valid frame header that
+/// continues the frame (or sample) numbering.
+pub(crate) fn verify_stream<R>(reader: &mut R) -> Result<StreamVerification>
+where
+	R: Read + Seek,
+{
+	find_id3v2(reader, FindId3v2Config::NO_READ_TAG)?;
+
+	let stream_info = verify_flac(reader)?;
+	if stream_info.content.len() < 18 {
+		decode_err!(@BAIL Flac, "File has an invalid STREAMINFO block size (< 18)");
+	}
+
+	// Sample rate (20 bits), channels (3 bits), bits per sample (5 bits), and total samples (36 bits)
+	let info = u64::from_be_bytes(stream_info.content[10..18].try_into().unwrap());
+	let sample_rate = (info >> 44) as u32;
+	let total_samples = info & 0xF_FFFF_FFFF;
+
+	if sample_rate == 0 {
+		decode_err!(@BAIL Flac, "File has a sample rate of 0");
+	}
+
+	let mut last_block = stream_info.last;
+	while !last_block {
+		last_block = Block::read(reader, ParsingMode::BestAttempt, |_, _| Ok(false))?.last;
+	}
+
+	// The file offset of `buf[0]`
+	let mut buf_offset = reader.stream_position()?;
+	let mut buf = Vec::new();
+	let mut eof = false;
+
+	let mut found_samples = 0;
+	let mut corrupt_at = None;
+	let mut incomplete_last_frame = false;
+	let mut expected_number = None;
+
+	loop {
+		while buf.len() < MAX_FRAME_HEADER_LEN && !eof {
+			eof = !fill(reader, &mut buf)?;
+		}
+
+		if buf.is_empty() {
+			break;
+		}
+
+		let Some(header) = FrameHeader::parse(&buf) else {
+			log::warn!("FLAC: Invalid frame header at offset {buf_offset}");
+			corrupt_at.get_or_insert(buf_offset);
+			break;
+		};
+
+		// The frames before this one were damaged beyond recognition. They're still there, so their
+		// samples are counted.
+		if let Some((number, block_size)) = expected_number {
+			if header.number > number {
+				corrupt_at.get_or_insert(buf_offset);
+
+				found_samples += if header.variable_block_size {
+					header.number - number
+				} else {
+					(header.number - number) * block_size
+				};
+			}
+		}
+
+		let next_number = header.next_number();
+
+		// Allow a few frames to be skipped, in case the header of the next frame is damaged
+		let max_skipped = if header.variable_block_size {
+			MAX_SKIPPED_FRAMES * header.block_size
+		} else {
+			MAX_SKIPPED_FRAMES
+		};
+
+		// Search for the start of the next frame, updating the CRC up to it
+		let mut crc = 0;
+		let mut pos = 0;
+		let next_frame_start = loop {
+			if pos + 2 > buf.len() {
+				if eof {
+					break None;
+				}
+
+				eof = !fill(reader, &mut buf)?;
+				continue;
+			}
+
+			if pos >= header.len && buf[pos] == 0xFF && buf[pos + 1] & 0xFE == 0xF8 {
+				while buf.len() < pos + MAX_FRAME_HEADER_LEN && !eof {
+					eof = !fill(reader, &mut buf)?;
+				}
+
+				if let Some(next_header) = FrameHeader::parse(&buf[pos..]) {
+					if (next_number..=next_number + max_skipped).contains(&next_header.number)
+						&& next_header.variable_block_size == header.variable_block_size
+					{
+						break Some(pos);
+					}
+				}
+			}
+
+			crc = update_crc_16(crc, buf[pos]);
+			pos += 1;
+		};
+
+		// The frame's CRC is stored at the end, so the CRC of the entire frame is 0
+		let frame_end = match next_frame_start {
+			Some(next_frame_start) => {
+				if crc != 0 {
+					log::warn!("FLAC: CRC mismatch for frame at offset {buf_offset}");
+					corrupt_at.get_or_insert(buf_offset);
+				}
+
+				found_samples += header.block_size;
+				next_frame_start
+			},
+			None => {
+				let crc = buf[pos..]
+					.iter()
+					.fold(crc, |crc, byte| update_crc_16(crc, *byte));
+				if crc == 0 {
+					found_samples += header.block_size;
+				} else if total_samples > 0 && found_samples + header.block_size >= total_samples {
+					// The stream wasn't cut short, the final frame is just damaged
+					corrupt_at.get_or_insert(buf_offset);
+				} else {
+					incomplete_last_frame = true;
+				}
+
+				buf.len()
+			},
+		};
+
+		buf.drain(..frame_end);
+		buf_offset += frame_end as u64;
+		expected_number = Some((next_number, header.block_size));
+	}
+
+	let samples_to_duration =
+		|samples: u64| Duration::from_millis((samples * 1000) / u64::from(sample_rate));
+
+	let truncated = if total_samples > 0 {
+		found_samples < total_samples
+	} else {
+		incomplete_last_frame
+	};
+
+	Ok(StreamVerification {
+		expected: samples_to_duration(total_samples),
+		found: samples_to_duration(found_samples),
+		truncated,
+		corrupt_at,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{crc_8, update_crc_16};
+
+	#[test_log::test]
+	fn crc() {
+		assert_eq!(crc_8(b"123456789"), 0xF4);
+
+		let crc_16 = b"123456789"
+			.iter()
+			.fold(0, |crc, byte| update_crc_16(crc, *byte));
+		assert_eq!(crc_16, 0xFEE8);
+	}
+}
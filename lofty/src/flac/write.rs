@@ -1,21 +1,24 @@
 use super::block::{Block, BLOCK_ID_PADDING, BLOCK_ID_PICTURE, BLOCK_ID_VORBIS_COMMENTS};
 use super::read::verify_flac;
-use crate::config::WriteOptions;
-use crate::error::{LoftyError, Result};
+use crate::config::{ParsingMode, WriteOptions};
+use crate::error::{ErrorKind, LoftyError, Result};
+use crate::file::WritePlan;
 use crate::macros::{err, try_vec};
 use crate::ogg::tag::VorbisCommentsRef;
 use crate::ogg::write::create_comments;
-use crate::picture::{Picture, PictureInformation};
+use crate::picture::{MimeType, Picture, PictureInformation, PictureType};
 use crate::tag::{Tag, TagType};
 use crate::util::io::{FileLike, Length, Truncate};
 
 use std::borrow::Cow;
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
 
 const BLOCK_HEADER_SIZE: usize = 4;
-const MAX_BLOCK_SIZE: u32 = 16_777_215;
+pub(crate) const MAX_BLOCK_SIZE: u32 = 16_777_215;
+// The padding to leave when the file has to be rewritten, unless `WriteOptions::preferred_padding` is set
+const DEFAULT_REWRITE_PADDING: u32 = 4096;
 
 pub(crate) fn write_to<F>(file: &mut F, tag: &Tag, write_options: WriteOptions) -> Result<()>
 where
@@ -29,7 +32,7 @@ where
 
 			let mut comments_ref = VorbisCommentsRef {
 				vendor: Cow::from(vendor),
-				items,
+				items: items.iter().map(|(k, v)| (&**k, *v)),
 				pictures,
 			};
 
@@ -49,43 +52,48 @@ pub(crate) fn write_to_inner<'a, F, II, IP>(
 where
 	F: FileLike,
 	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
 	II: Iterator<Item = (&'a str, &'a str)>,
 	IP: Iterator<Item = (&'a Picture, PictureInformation)>,
 {
-	let stream_info = verify_flac(file)?;
-
-	let mut last_block = stream_info.last;
-
-	let mut file_bytes = Vec::new();
-	file.read_to_end(&mut file_bytes)?;
-
-	let mut cursor = Cursor::new(file_bytes);
+	let mut plan = WritePlan::default();
+	plan_metadata(file, tag, write_options, &mut plan)?;
+	plan.apply(file)
+}
 
-	// TODO: We need to actually use padding (https://github.com/Serial-ATA/lofty-rs/issues/445)
-	let mut end_padding_exists = false;
-	let mut last_block_info = (
-		stream_info.byte,
-		stream_info.start as usize,
-		stream_info.end as usize,
-	);
+/// Plan replacing the metadata blocks of the stream at the current position of `file`
+///
+/// The new blocks replace everything between STREAMINFO and the audio. If they don't fit in the
+/// existing space, the audio has to move.
+pub(crate) fn plan_metadata<'a, R, II, IP>(
+	file: &mut R,
+	tag: &mut VorbisCommentsRef<'a, II, IP>,
+	write_options: WriteOptions,
+	plan: &mut WritePlan,
+) -> Result<()>
+where
+	R: Read + Seek,
+	II: Iterator<Item = (&'a str, &'a str)>,
+	IP: Iterator<Item = (&'a Picture, PictureInformation)>,
+{
+	let stream_info = verify_flac(file)?;
 
-	let mut blocks_to_remove = Vec::new();
+	// All existing blocks other than STREAMINFO, VORBIS_COMMENT, PICTURE, and PADDING are kept. The new
+	// blocks are placed where the first VORBIS_COMMENT or PICTURE block was.
+	let mut blocks_before = Vec::new();
+	let mut blocks_after = Vec::new();
+	let mut found_tag_block = false;
 
+	let mut last_block = stream_info.last;
 	while !last_block {
-		let block = Block::read(&mut cursor, |block_ty| block_ty == BLOCK_ID_VORBIS_COMMENTS)?;
-		let start = block.start;
-		let end = block.end;
-
-		let block_type = block.ty;
+		let block = Block::read(file, ParsingMode::Strict, |block_ty, _| {
+			Ok(block_ty != BLOCK_ID_PICTURE && block_ty != BLOCK_ID_PADDING)
+		})?;
 		last_block = block.last;
 
-		if last_block {
-			last_block_info = (block.byte, (end - start) as usize, end as usize)
-		}
-
-		match block_type {
+		match block.ty {
 			BLOCK_ID_VORBIS_COMMENTS => {
-				blocks_to_remove.push((start, end));
+				found_tag_block = true;
 
 				// Retain the original vendor string
 				let reader = &mut &block.content[..];
@@ -103,77 +111,154 @@ where
 
 				tag.vendor = Cow::Owned(vendor_str);
 			},
-			BLOCK_ID_PICTURE => blocks_to_remove.push((start, end)),
-			BLOCK_ID_PADDING => {
-				if last_block {
-					end_padding_exists = true
+			BLOCK_ID_PICTURE => found_tag_block = true,
+			BLOCK_ID_PADDING => {},
+			_ => {
+				let blocks = if found_tag_block {
+					&mut blocks_after
 				} else {
-					blocks_to_remove.push((start, end))
-				}
+					&mut blocks_before
+				};
+
+				blocks.write_u8(block.ty)?;
+				blocks.write_u24::<BigEndian>(block.content.len() as u32)?;
+				blocks.write_all(&block.content)?;
 			},
-			_ => {},
 		}
 	}
 
-	let mut file_bytes = cursor.into_inner();
+	let audio_start = file.stream_position()?;
 
-	if !end_padding_exists {
-		if let Some(preferred_padding) = write_options.preferred_padding {
-			log::warn!("File is missing a PADDING block. Adding one");
-
-			let mut first_byte = 0_u8;
-			first_byte |= last_block_info.0 & 0x7F;
+	let mut comment_blocks = Cursor::new(Vec::new());
 
-			file_bytes[last_block_info.1] = first_byte;
+	create_comment_block(
+		&mut comment_blocks,
+		&tag.vendor,
+		&mut tag.items,
+		write_options,
+	)?;
 
-			let block_size = core::cmp::min(preferred_padding, MAX_BLOCK_SIZE);
-			let mut padding_block = try_vec![0; BLOCK_HEADER_SIZE + block_size as usize];
+	let mut comment_blocks = comment_blocks.into_inner();
 
-			let mut padding_byte = 0;
-			padding_byte |= 0x80;
-			padding_byte |= 1 & 0x7F;
+	create_picture_blocks(&mut comment_blocks, &mut tag.pictures, write_options)?;
+
+	let mut metadata = blocks_before;
+	metadata.append(&mut comment_blocks);
+	metadata.append(&mut blocks_after);
+
+	// The space currently taken up by every block after STREAMINFO, including padding
+	let available_space = audio_start - stream_info.end;
+	let metadata_len = metadata.len() as u64;
+
+	// If the new blocks fit, the audio can stay where it is. Any remaining space needs to be
+	// filled with a PADDING block, which requires room for at least its header.
+	//
+	// This is `Some(padding_size)` if the blocks can be written in place.
+	let in_place_padding = match available_space.checked_sub(metadata_len) {
+		Some(0) => Some(None),
+		Some(remaining) if remaining >= BLOCK_HEADER_SIZE as u64 => {
+			u32::try_from(remaining - BLOCK_HEADER_SIZE as u64)
+				.ok()
+				.filter(|padding| *padding <= MAX_BLOCK_SIZE)
+				.map(Some)
+		},
+		_ => None,
+	};
 
-			padding_block[0] = padding_byte;
-			padding_block[1..4].copy_from_slice(&block_size.to_be_bytes()[1..]);
+	let padding = match in_place_padding {
+		Some(padding) => {
+			log::debug!("Writing FLAC metadata in place, padding: {padding:?}");
+			padding
+		},
+		None => match write_options.preferred_padding {
+			None => Some(DEFAULT_REWRITE_PADDING),
+			Some(0) => None,
+			Some(padding) => Some(padding.min(MAX_BLOCK_SIZE)),
+		},
+	};
 
-			file_bytes.splice(last_block_info.2..last_block_info.2, padding_block);
-		}
+	if let Some(padding) = padding {
+		metadata.write_u8(BLOCK_ID_PADDING)?;
+		metadata.write_u24::<BigEndian>(padding)?;
+		metadata.resize(metadata.len() + padding as usize, 0);
 	}
 
-	let mut comment_blocks = Cursor::new(Vec::new());
+	set_last_block_flag(&mut metadata);
 
-	create_comment_block(&mut comment_blocks, &tag.vendor, &mut tag.items)?;
+	// STREAMINFO is only the last block if nothing follows it
+	let stream_info_byte = if metadata.is_empty() {
+		stream_info.byte | 0x80
+	} else {
+		stream_info.byte & 0x7F
+	};
+	plan.replace(
+		stream_info.start..stream_info.start + 1,
+		vec![stream_info_byte],
+	);
 
-	let mut comment_blocks = comment_blocks.into_inner();
+	if in_place_padding.is_none() {
+		log::debug!("FLAC metadata does not fit in place, moving the audio");
+	}
 
-	create_picture_blocks(&mut comment_blocks, &mut tag.pictures)?;
+	plan.replace(stream_info.end..audio_start, metadata);
 
-	if blocks_to_remove.is_empty() {
-		file_bytes.splice(0..0, comment_blocks);
-	} else {
-		blocks_to_remove.sort_unstable();
-		blocks_to_remove.reverse();
+	Ok(())
+}
+
+/// Plan turning every VORBIS_COMMENT and PICTURE block of the stream at the current position of
+/// `data` into PADDING
+///
+/// The blocks keep their size, so nothing else in the file has to move.
+pub(crate) fn plan_tag_block_removal<R>(data: &mut R, plan: &mut WritePlan) -> Result<()>
+where
+	R: Read + Seek,
+{
+	let stream_info = verify_flac(data)?;
 
-		let first = blocks_to_remove.pop().unwrap(); // Infallible
+	let mut last_block = stream_info.last;
+	while !last_block {
+		let block = Block::read(data, ParsingMode::Strict, |_, _| Ok(false))?;
+		last_block = block.last;
 
-		for (s, e) in &blocks_to_remove {
-			file_bytes.drain(*s as usize..*e as usize);
+		if block.ty != BLOCK_ID_VORBIS_COMMENTS && block.ty != BLOCK_ID_PICTURE {
+			continue;
 		}
 
-		file_bytes.splice(first.0 as usize..first.1 as usize, comment_blocks);
-	}
+		log::debug!("Turning FLAC block at offset {} into padding", block.start);
+
+		let size = (block.end - block.start) as usize - BLOCK_HEADER_SIZE;
+		let mut padding = Vec::with_capacity(BLOCK_HEADER_SIZE + size);
+		padding.write_u8((block.byte & 0x80) | BLOCK_ID_PADDING)?;
+		padding.write_u24::<BigEndian>(size as u32)?;
+		padding.resize(BLOCK_HEADER_SIZE + size, 0);
 
-	file.seek(SeekFrom::Start(stream_info.end))?;
-	file.truncate(stream_info.end)?;
-	file.write_all(&file_bytes)?;
+		plan.replace(block.start..block.end, padding);
+	}
 
 	Ok(())
 }
 
+// Sets the "last block" flag on the final block in `metadata`
+fn set_last_block_flag(metadata: &mut [u8]) {
+	let mut pos = 0;
+	let mut last_header = None;
+	while pos + BLOCK_HEADER_SIZE <= metadata.len() {
+		last_header = Some(pos);
+
+		let size = u32::from_be_bytes([0, metadata[pos + 1], metadata[pos + 2], metadata[pos + 3]]);
+		pos += BLOCK_HEADER_SIZE + size as usize;
+	}
+
+	if let Some(last_header) = last_header {
+		metadata[last_header] |= 0x80;
+	}
+}
+
 fn create_comment_block(
 	writer: &mut Cursor<Vec<u8>>,
 	vendor: &str,
 	items: &mut dyn Iterator<Item = (&str, &str)>,
+	write_options: WriteOptions,
 ) -> Result<()> {
 	let mut peek = items.peekable();
 
@@ -190,7 +275,7 @@ fn create_comment_block(
 
 		writer.write_u32::<LittleEndian>(count)?;
 
-		create_comments(writer, &mut count, &mut peek)?;
+		create_comments(writer, &mut count, &mut peek, write_options)?;
 
 		let len = (writer.get_ref().len() - 1) as u32;
 
@@ -221,11 +306,12 @@ fn create_comment_block(
 fn create_picture_blocks(
 	writer: &mut Vec<u8>,
 	pictures: &mut dyn Iterator<Item = (&Picture, PictureInformation)>,
+	write_options: WriteOptions,
 ) -> Result<()> {
 	let mut byte = 0_u8;
 	byte |= 6 & 0x7F;
 
-	for (pic, info) in pictures {
+	for (pic, info) in verify_icons(pictures, write_options)? {
 		writer.write_u8(byte)?;
 
 		let pic_bytes = pic.as_flac_bytes(info, false);
@@ -244,3 +330,84 @@ fn create_picture_blocks(
 
 	Ok(())
 }
+
+/// Verify the rules for file icons in `METADATA_BLOCK_PICTURE`s
+///
+/// There may only be one [`PictureType::Icon`] and one [`PictureType::OtherIcon`], and a
+/// [`PictureType::Icon`] must be a 32x32 PNG. Since the dimensions aren't always known, they are only
+/// verified when available.
+///
+/// This also rejects any pictures whose data was skipped while reading, as well as URL pictures
+/// (with the `-->` MIME type) that contain binary data. The MIME types are corrected first, if
+/// requested.
+///
+/// See [`WriteOptions::drop_invalid_icons`] and [`WriteOptions::fix_picture_mime_types`].
+pub(crate) fn verify_icons<'a>(
+	pictures: &mut dyn Iterator<Item = (&'a Picture, PictureInformation)>,
+	write_options: WriteOptions,
+) -> Result<Vec<(Cow<'a, Picture>, PictureInformation)>> {
+	const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+	let mut verified = Vec::new();
+	let mut duplicated_types = Vec::new();
+	let (mut seen_icon, mut seen_other_icon) = (false, false);
+	for (pic, info) in pictures {
+		pic.verify_has_data()?;
+
+		let pic = if write_options.fix_picture_mime_types {
+			pic.with_fixed_mime_type()
+		} else {
+			Cow::Borrowed(pic)
+		};
+
+		if pic.mime_type().is_some_and(MimeType::is_url) && !pic.has_url_data() {
+			err!(BinaryPictureUrl);
+		}
+
+		let seen = match pic.pic_type() {
+			PictureType::Icon => {
+				let dimensions_known = info.width != 0 && info.height != 0;
+				if dimensions_known
+					&& (!pic.data().starts_with(PNG_SIGNATURE)
+						|| info.width != 32
+						|| info.height != 32)
+				{
+					if !write_options.drop_invalid_icons {
+						err!(InvalidFileIcon);
+					}
+
+					log::warn!("Discarding file icon that is not a 32x32 PNG");
+					continue;
+				}
+
+				&mut seen_icon
+			},
+			PictureType::OtherIcon => &mut seen_other_icon,
+			_ => {
+				verified.push((pic, info));
+				continue;
+			},
+		};
+
+		if *seen {
+			if write_options.drop_invalid_icons {
+				log::warn!("Discarding duplicate picture of type {:?}", pic.pic_type());
+			} else if !duplicated_types.contains(&pic.pic_type()) {
+				duplicated_types.push(pic.pic_type());
+			}
+
+			continue;
+		}
+
+		*seen = true;
+		verified.push((pic, info));
+	}
+
+	if !duplicated_types.is_empty() {
+		return Err(LoftyError::new(ErrorKind::DuplicatePictureTypes(
+			duplicated_types,
+		)));
+	}
+
+	Ok(verified)
+}
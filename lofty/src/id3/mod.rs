@@ -6,10 +6,14 @@
 pub mod v1;
 pub mod v2;
 
-use crate::error::{ErrorKind, LoftyError, Result};
+use crate::config::{DuplicateTagPolicy, ParseOptions};
+use crate::error::{ErrorKind, FileDecodingError, LoftyError, ParseDiagnostic, Result};
 use crate::macros::try_vec;
+use crate::tag::{TagLocation, TagPlacement, TagType};
 use crate::util::text::utf8_decode_str;
+use crate::util::{diagnostics, tag_locations};
 use v2::header::Id3v2Header;
+use v2::tag::Id3v2Tag;
 
 use std::io::{Read, Seek, SeekFrom};
 use std::ops::Neg;
@@ -50,11 +54,15 @@ where
 	Ok(ID3FindResults(header, size))
 }
 
+/// Search for an ID3v1 tag at the end of `data`
+///
+/// The header holds the size of the tag, which includes an extended "TAG+" block if one precedes
+/// it. Either way, `data` is left at the start of the tag.
 #[allow(unused_variables)]
 pub(crate) fn find_id3v1<R>(
 	data: &mut R,
 	read: bool,
-) -> Result<ID3FindResults<(), Option<v1::tag::Id3v1Tag>>>
+) -> Result<ID3FindResults<u64, Option<v1::tag::Id3v1Tag>>>
 where
 	R: Read + Seek,
 {
@@ -82,15 +90,43 @@ where
 
 	log::debug!("Found an ID3v1 tag, parsing");
 
-	header = Some(());
-
+	let mut id3v1_tag = [0; 128];
 	if read {
-		let mut id3v1_tag = [0; 128];
 		data.read_exact(&mut id3v1_tag)?;
+	}
+
+	// An extended "TAG+" block may directly precede the tag
+	let mut size = 128;
+	let mut extended = None;
+	if data.seek(SeekFrom::End(-(128 + 227))).is_ok() {
+		let mut extended_tag = [0; 227];
+		data.read_exact(&mut extended_tag)?;
 
-		data.seek(SeekFrom::End(-128))?;
+		if extended_tag.starts_with(b"TAG+") {
+			log::debug!("Found an extended ID3v1 block");
 
-		id3v1 = Some(v1::read::parse_id3v1(id3v1_tag))
+			size += 227;
+			extended = Some(extended_tag);
+		}
+	}
+
+	let offset = data.seek(SeekFrom::End(-(size as i64)))?;
+	header = Some(size);
+
+	if read {
+		tag_locations::record(TagLocation::new(
+			TagType::Id3v1,
+			offset,
+			size,
+			TagPlacement::Tail,
+		));
+
+		let mut tag = v1::read::parse_id3v1(id3v1_tag);
+		if let Some(extended_tag) = extended {
+			v1::read::parse_id3v1_extended(extended_tag, &mut tag);
+		}
+
+		id3v1 = Some(tag)
 	}
 
 	Ok(ID3FindResults(header, id3v1))
@@ -114,10 +150,115 @@ impl FindId3v2Config {
 	};
 }
 
-pub(crate) fn find_id3v2<R>(
-	data: &mut R,
-	config: FindId3v2Config,
-) -> Result<ID3FindResults<Id3v2Header, Option<Vec<u8>>>>
+/// The maximum number of consecutive ID3v2 tags to search for
+///
+/// Some software writes a new tag in front of the existing one, rather than replacing it.
+pub(crate) const MAX_STACKED_ID3V2_TAGS: usize = 5;
+
+/// An ID3v2 tag found by [`find_id3v2`]
+pub(crate) struct FoundId3v2Tag {
+	pub(crate) header: Id3v2Header,
+	/// The offset of the tag's header
+	pub(crate) offset: u64,
+	/// The tag's content, if [`FindId3v2Config::read`] is set
+	pub(crate) content: Option<Vec<u8>>,
+}
+
+/// All consecutive ID3v2 tags found by [`find_id3v2`], in the order they appear
+pub(crate) struct FoundId3v2Tags(pub(crate) Vec<FoundId3v2Tag>);
+
+impl FoundId3v2Tags {
+	pub(crate) fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// The combined size of all tags, including their headers and footers
+	pub(crate) fn size(&self) -> u64 {
+		self.0
+			.iter()
+			.map(|tag| u64::from(tag.header.full_tag_size()))
+			.sum()
+	}
+
+	/// Parse the tags, combining them according to [`ParseOptions::duplicate_tag_policy`]
+	///
+	/// This will return `None` if no tags were found, or if they weren't read.
+	pub(crate) fn parse(self, parse_options: ParseOptions) -> Result<Option<Id3v2Tag>> {
+		let mut ret: Option<Id3v2Tag> = None;
+		for found in self.0 {
+			let Some(content) = found.content else {
+				continue;
+			};
+
+			record_id3v2_location(&found.header, found.offset, TagPlacement::Head);
+
+			let tag = v2::read::parse_id3v2(&mut &*content, found.header, parse_options)?;
+			match &mut ret {
+				Some(existing_tag) => {
+					handle_stacked_id3v2(existing_tag, tag, found.offset, parse_options)?
+				},
+				None => ret = Some(tag),
+			}
+		}
+
+		Ok(ret)
+	}
+}
+
+/// Record the location of an ID3v2 tag, with its header at `offset`
+pub(crate) fn record_id3v2_location(header: &Id3v2Header, offset: u64, placement: TagPlacement) {
+	tag_locations::record(
+		TagLocation::new(
+			TagType::Id3v2,
+			offset,
+			u64::from(header.full_tag_size()),
+			placement,
+		)
+		.with_header(true)
+		.with_footer(header.flags.footer),
+	);
+}
+
+/// Handle an additional ID3v2 tag directly following another, according to
+/// [`ParseOptions::duplicate_tag_policy`]
+///
+/// `offset` is the offset of the additional tag's header.
+pub(crate) fn handle_stacked_id3v2(
+	existing_tag: &mut Id3v2Tag,
+	tag: Id3v2Tag,
+	offset: u64,
+	parse_options: ParseOptions,
+) -> Result<()> {
+	log::warn!("Stacked ID3v2 tag found at offset {offset}");
+
+	match parse_options.duplicate_tag_policy {
+		DuplicateTagPolicy::Merge => {
+			// https://github.com/Serial-ATA/lofty-rs/issues/87
+			// Duplicate tags should have their frames appended to the previous
+			for frame in tag.frames {
+				existing_tag.insert(frame);
+			}
+		},
+		DuplicateTagPolicy::FirstWins => {},
+		DuplicateTagPolicy::LastWins => *existing_tag = tag,
+		DuplicateTagPolicy::Error => {
+			return Err(FileDecodingError::from_tag(
+				TagType::Id3v2,
+				"File contains multiple ID3v2 tags",
+			)
+			.into());
+		},
+	}
+
+	diagnostics::report(ParseDiagnostic::StackedId3v2Tag { offset });
+	Ok(())
+}
+
+/// Search for ID3v2 tags at the current position
+///
+/// After a tag is found, this will continue to search for tags directly following it, up to
+/// [`MAX_STACKED_ID3V2_TAGS`]. The reader will be positioned after the last tag found.
+pub(crate) fn find_id3v2<R>(data: &mut R, config: FindId3v2Config) -> Result<FoundId3v2Tags>
 where
 	R: Read + Seek,
 {
@@ -126,14 +267,13 @@ where
 		data.stream_position()?
 	);
 
-	let mut header = None;
-	let mut id3v2 = None;
+	let mut tags = Vec::new();
 
 	if let Some(junk_window) = config.allowed_junk_window {
 		let mut id3v2_search_window = data.by_ref().take(junk_window);
 
 		let Some(id3v2_offset) = find_id3v2_in_junk(&mut id3v2_search_window)? else {
-			return Ok(ID3FindResults(None, None));
+			return Ok(FoundId3v2Tags(tags));
 		};
 
 		log::warn!(
@@ -144,28 +284,37 @@ where
 		data.seek(SeekFrom::Current(-3))?;
 	}
 
-	if let Ok(id3v2_header) = Id3v2Header::parse(data) {
+	while tags.len() < MAX_STACKED_ID3V2_TAGS {
+		let offset = data.stream_position()?;
+		let Ok(header) = Id3v2Header::parse(data) else {
+			data.seek(SeekFrom::Start(offset))?;
+			break;
+		};
+
 		log::debug!("Found an ID3v2 tag, parsing");
 
+		let mut content = None;
 		if config.read {
-			let mut tag = try_vec![0; id3v2_header.size as usize];
+			let mut tag = try_vec![0; header.size as usize];
 			data.read_exact(&mut tag)?;
 
-			id3v2 = Some(tag)
+			content = Some(tag)
 		} else {
-			data.seek(SeekFrom::Current(i64::from(id3v2_header.size)))?;
+			data.seek(SeekFrom::Current(i64::from(header.size)))?;
 		}
 
-		if id3v2_header.flags.footer {
+		if header.flags.footer {
 			data.seek(SeekFrom::Current(10))?;
 		}
 
-		header = Some(id3v2_header);
-	} else {
-		data.seek(SeekFrom::Current(-10))?;
+		tags.push(FoundId3v2Tag {
+			header,
+			offset,
+			content,
+		});
 	}
 
-	Ok(ID3FindResults(header, id3v2))
+	Ok(FoundId3v2Tags(tags))
 }
 
 /// Searches for an ID3v2 tag in (potential) junk data between the start
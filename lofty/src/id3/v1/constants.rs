@@ -194,6 +194,41 @@ pub const GENRES: [&str; 192] = [
 	"Psybient",
 ];
 
+/// Find the index of the genre in [`GENRES`] that best matches `genre`
+///
+/// This accepts:
+///
+/// * Numeric indexes, optionally in parentheses (e.g. "17" or "(17)")
+/// * Genre names, compared case-insensitively while ignoring punctuation and whitespace,
+///   so that "hip hop" matches "Hip-Hop"
+pub(crate) fn genre_index(genre: &str) -> Option<u8> {
+	fn normalize(genre: &str) -> impl Iterator<Item = char> + '_ {
+		genre
+			.chars()
+			.filter(|c| c.is_alphanumeric())
+			.flat_map(char::to_lowercase)
+	}
+
+	let genre = genre.trim();
+
+	let numeric = genre
+		.strip_prefix('(')
+		.and_then(|g| g.strip_suffix(')'))
+		.unwrap_or(genre);
+	if let Ok(index) = numeric.parse::<u8>() {
+		return (usize::from(index) < GENRES.len()).then_some(index);
+	}
+
+	if let Some(index) = GENRES.iter().position(|g| g.eq_ignore_ascii_case(genre)) {
+		return Some(index as u8);
+	}
+
+	GENRES
+		.iter()
+		.position(|g| normalize(g).eq(normalize(genre)))
+		.map(|index| index as u8)
+}
+
 use crate::tag::ItemKey;
 pub(crate) const VALID_ITEMKEYS: [ItemKey; 7] = [
 	ItemKey::TrackTitle,
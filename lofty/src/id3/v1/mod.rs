@@ -6,8 +6,9 @@
 //!
 //! ## Genres
 //!
-//! ID3v1 stores the genre in a single byte ranging from 0 to 192 (inclusive).
-//! All possible genres have been stored in the [`GENRES`] constant.
+//! ID3v1 stores the genre in a single byte ranging from 0 to 191 (inclusive), with 255 meaning
+//! no genre. The first 80 genres are from the original specification, the remainder are the
+//! Winamp extensions. All possible genres have been stored in the [`GENRES`] constant.
 //!
 //! ## Track Numbers
 //!
@@ -22,4 +23,4 @@ pub(crate) mod write;
 // Exports
 
 pub use constants::GENRES;
-pub use tag::Id3v1Tag;
+pub use tag::{Id3v1Extended, Id3v1Tag};
@@ -1,5 +1,5 @@
 use super::constants::GENRES;
-use super::tag::Id3v1Tag;
+use super::tag::{Id3v1Extended, Id3v1Tag};
 
 pub fn parse_id3v1(reader: [u8; 128]) -> Id3v1Tag {
 	let mut tag = Id3v1Tag {
@@ -10,6 +10,7 @@ pub fn parse_id3v1(reader: [u8; 128]) -> Id3v1Tag {
 		comment: None,
 		track_number: None,
 		genre: None,
+		extended: None,
 	};
 
 	let reader = &reader[3..];
@@ -39,6 +40,40 @@ pub fn parse_id3v1(reader: [u8; 128]) -> Id3v1Tag {
 	tag
 }
 
+// The extended block is laid out as follows:
+//
+// "TAG+" (4)
+// Title (60)
+// Artist (60)
+// Album (60)
+// Speed (1)
+// Genre (30)
+// Start time (6)
+// End time (6)
+//
+// The title, artist, and album continue the fields of the standard tag.
+pub fn parse_id3v1_extended(reader: [u8; 227], tag: &mut Id3v1Tag) {
+	let reader = &reader[4..];
+
+	let continue_field = |base: &Option<String>, data: &[u8]| {
+		let continuation = decode_text(data)?;
+		Some(format!(
+			"{}{continuation}",
+			base.as_deref().unwrap_or_default()
+		))
+	};
+
+	tag.extended = Some(Id3v1Extended {
+		title: continue_field(&tag.title, &reader[..60]),
+		artist: continue_field(&tag.artist, &reader[60..120]),
+		album: continue_field(&tag.album, &reader[120..180]),
+		speed: Some(reader[180]).filter(|speed| *speed != 0),
+		genre: decode_text(&reader[181..211]),
+		start_time: decode_text(&reader[211..217]),
+		end_time: decode_text(&reader[217..223]),
+	});
+}
+
 fn decode_text(data: &[u8]) -> Option<String> {
 	let read = data
 		.iter()
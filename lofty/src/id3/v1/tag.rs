@@ -1,8 +1,10 @@
 use crate::config::WriteOptions;
 use crate::error::{LoftyError, Result};
-use crate::id3::v1::constants::GENRES;
+use crate::id3::v1::constants::{genre_index, GENRES};
+use crate::id3::v1::write::encode_field;
 use crate::tag::{Accessor, ItemKey, ItemValue, MergeTag, SplitTag, Tag, TagExt, TagItem, TagType};
 use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::text::latin1_decode;
 
 use std::borrow::Cow;
 use std::io::Write;
@@ -15,6 +17,11 @@ macro_rules! impl_accessor {
 		paste::paste! {
 			$(
 				fn $name(&self) -> Option<Cow<'_, str>> {
+					// The extended block holds the full text, if present
+					if let Some(item) = self.extended.as_ref().and_then(|e| e.$name.as_deref()) {
+						return Some(Cow::Borrowed(item));
+					}
+
 					if let Some(item) = self.$name.as_deref() {
 						return Some(Cow::Borrowed(item));
 					}
@@ -23,10 +30,18 @@ macro_rules! impl_accessor {
 				}
 
 				fn [<set_ $name>](&mut self, value: String) {
+					if let Some(extended) = &mut self.extended {
+						extended.$name = None;
+					}
+
 					self.$name = Some(value)
 				}
 
 				fn [<remove_ $name>](&mut self) {
+					if let Some(extended) = &mut self.extended {
+						extended.$name = None;
+					}
+
 					self.$name = None
 				}
 			)+
@@ -64,17 +79,29 @@ macro_rules! impl_accessor {
 /// The values will be used as-is, with two exceptions:
 ///
 /// * [`ItemKey::TrackNumber`] - Will only be used if the value can be parsed as a `u8`
-/// * [`ItemKey::Genre`] - Will only be used if:
+/// * [`ItemKey::Genre`] - Will be mapped to the closest matching index in [`GENRES`]. The value may
+///   also be an index, optionally in parentheses (e.g. "(17)"). If there is no match, the genre
+///   will be written as 255 (no genre).
 ///
-/// 	[`GENRES`] contains the string **OR** The [`ItemValue`](crate::ItemValue) can be parsed into
-/// 	a `u8` ***and*** it is a valid index into [`GENRES`]
+/// Genre names are matched case-insensitively, ignoring punctuation and whitespace, so
+/// "hip hop" will map to "Hip-Hop".
+///
+/// ## Extended tags
+///
+/// Some files have an extended "TAG+" block directly before the tag, see [`Id3v1Extended`].
+/// It is read into [`extended`](Self::extended), and its title, artist, and album are
+/// preferred over the standard fields by the [`Accessor`] methods and the conversion to [`Tag`].
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
 #[tag(
 	description = "An ID3v1 tag",
-	supported_formats(Aac, Ape, Mpeg, WavPack, read_only(Mpc))
+	supported_formats(Aac, Ape, Mpeg, Shn, Tta, WavPack, read_only(Mpc))
 )]
 pub struct Id3v1Tag {
 	/// Track title, 30 bytes max
+	///
+	/// All text fields are stored as Latin-1, see [`WriteOptions::strict_id3v1`].
+	///
+	/// [`WriteOptions::strict_id3v1`]: crate::config::WriteOptions::strict_id3v1
 	pub title: Option<String>,
 	/// Track artist, 30 bytes max
 	pub artist: Option<String>,
@@ -85,10 +112,11 @@ pub struct Id3v1Tag {
 	/// A short comment
 	///
 	/// The number of bytes differs between versions, but not much.
-	/// A V1 tag may have been read, which limits this field to 30 bytes.
+	/// A V1 tag has 30 bytes available for this field.
 	/// A V1.1 tag, however, only has 28 bytes available.
 	///
-	/// **Lofty** will *always* write a V1.1 tag.
+	/// **Lofty** will write a V1.1 tag whenever a [`track_number`](Self::track_number) is present,
+	/// and a V1 tag otherwise.
 	pub comment: Option<String>,
 	/// The track number, 1 byte max
 	///
@@ -104,6 +132,36 @@ pub struct Id3v1Tag {
 	/// ID3v1 has a predefined set of genres, see [`GENRES`](crate::id3::v1::GENRES).
 	/// This byte should be an index to a genre.
 	pub genre: Option<u8>,
+	/// The extended "TAG+" block, if one was read
+	///
+	/// NOTE: The extended block is never written. Saving the tag replaces both blocks with a
+	///       standard ID3v1 tag, and removing the tag removes both.
+	pub extended: Option<Id3v1Extended>,
+}
+
+/// An extended ID3v1 "TAG+" block
+///
+/// This 227 byte block sits directly before an ID3v1 tag, holding longer versions of some fields,
+/// along with a few fields of its own. It was never part of any specification, but some older
+/// software wrote it.
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+pub struct Id3v1Extended {
+	/// The full track title, 90 bytes max
+	///
+	/// This is the title of the standard tag, continued by the extended block.
+	pub title: Option<String>,
+	/// The full track artist, 90 bytes max
+	pub artist: Option<String>,
+	/// The full album title, 90 bytes max
+	pub album: Option<String>,
+	/// The speed of the track, from 1 (slow) to 4 (hardcore)
+	pub speed: Option<u8>,
+	/// A free-form genre, 30 bytes max
+	pub genre: Option<String>,
+	/// The start of the music, as "mmm:ss"
+	pub start_time: Option<String>,
+	/// The end of the music, as "mmm:ss"
+	pub end_time: Option<String>,
 }
 
 impl Id3v1Tag {
@@ -121,6 +179,43 @@ impl Id3v1Tag {
 	pub fn new() -> Self {
 		Self::default()
 	}
+
+	/// The genre index, see [`GENRES`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v1::Id3v1Tag;
+	/// use lofty::tag::Accessor;
+	///
+	/// let mut id3v1_tag = Id3v1Tag::new();
+	/// id3v1_tag.set_genre(String::from("Terror"));
+	///
+	/// assert_eq!(id3v1_tag.genre_index(), Some(130));
+	/// ```
+	pub fn genre_index(&self) -> Option<u8> {
+		self.genre
+	}
+
+	/// Set the genre index, see [`GENRES`]
+	///
+	/// Indexes outside of [`GENRES`] are retained, but will not be accessible through
+	/// [`Accessor::genre`], or be converted to a [`Tag`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v1::Id3v1Tag;
+	/// use lofty::tag::Accessor;
+	///
+	/// let mut id3v1_tag = Id3v1Tag::new();
+	/// id3v1_tag.set_genre_index(140);
+	///
+	/// assert_eq!(id3v1_tag.genre().as_deref(), Some("Contemporary Christian"));
+	/// ```
+	pub fn set_genre_index(&mut self, index: u8) {
+		self.genre = Some(index);
+	}
 }
 
 impl Accessor for Id3v1Tag {
@@ -139,14 +234,7 @@ impl Accessor for Id3v1Tag {
 	}
 
 	fn set_genre(&mut self, genre: String) {
-		let g_str = genre.as_str();
-
-		for (i, g) in GENRES.iter().enumerate() {
-			if g.eq_ignore_ascii_case(g_str) {
-				self.genre = Some(i as u8);
-				break;
-			}
-		}
+		self.genre = genre_index(&genre);
 	}
 
 	fn remove_genre(&mut self) {
@@ -273,6 +361,22 @@ impl TagExt for Id3v1Tag {
 		Into::<Id3v1TagRef<'_>>::into(self).dump_to(writer, write_options)
 	}
 
+	/// Gets the size of the tag, as it would be written by [`Id3v1Tag::dump_to`]
+	///
+	/// ID3v1 tags have a fixed size, so this is always 128.
+	///
+	/// # Errors
+	///
+	/// * [`ErrorKind::LossyId3v1Fields`](crate::error::ErrorKind::LossyId3v1Fields) when using [`WriteOptions::strict_id3v1`]
+	fn serialized_len(&self, write_options: WriteOptions) -> std::result::Result<u64, Self::Err> {
+		// The fields only need to be encoded to check if they're lossy
+		if write_options.strict_id3v1 {
+			self.dump_to(&mut std::io::sink(), write_options)?;
+		}
+
+		Ok(128)
+	}
+
 	fn remove_from_path<P: AsRef<Path>>(&self, path: P) -> std::result::Result<(), Self::Err> {
 		TagType::Id3v1.remove_from_path(path)
 	}
@@ -300,6 +404,15 @@ impl SplitTag for Id3v1Tag {
 	fn split_tag(mut self) -> (Self::Remainder, Tag) {
 		let mut tag = Tag::new(TagType::Id3v1);
 
+		// Prefer the full text from the extended block
+		let mut extended_genre = None;
+		if let Some(extended) = self.extended.take() {
+			self.title = extended.title.or(self.title);
+			self.artist = extended.artist.or(self.artist);
+			self.album = extended.album.or(self.album);
+			extended_genre = extended.genre;
+		}
+
 		self.title
 			.take()
 			.map(|t| tag.insert_text(ItemKey::TrackTitle, t));
@@ -321,10 +434,17 @@ impl SplitTag for Id3v1Tag {
 			))
 		}
 
-		if let Some(genre_index) = self.genre.take() {
-			if let Some(genre) = GENRES.get(genre_index as usize) {
+		match self
+			.genre
+			.take()
+			.and_then(|index| GENRES.get(index as usize))
+		{
+			Some(genre) => {
 				tag.insert_text(ItemKey::Genre, (*genre).to_string());
-			}
+			},
+			None => {
+				extended_genre.map(|g| tag.insert_text(ItemKey::Genre, g));
+			},
 		}
 
 		(SplitTagRemainder, tag)
@@ -347,30 +467,38 @@ impl From<Id3v1Tag> for Tag {
 
 impl From<Tag> for Id3v1Tag {
 	fn from(mut input: Tag) -> Self {
-		let title = input.take_strings(&ItemKey::TrackTitle).next();
-		let artist = input.take_strings(&ItemKey::TrackArtist).next();
-		let album = input.take_strings(&ItemKey::AlbumTitle).next();
+		// Fit the text the same way it would be written, so that the tag matches the file
+		fn fit(value: Option<String>, size: usize) -> Option<String> {
+			let (encoded, _) = encode_field(&value?, size);
+			if encoded.is_empty() {
+				return None;
+			}
+
+			Some(latin1_decode(&encoded))
+		}
+
+		let track_number = input
+			.get_string(&ItemKey::TrackNumber)
+			.map(|g| g.parse::<u8>().ok())
+			.and_then(|g| g);
+
+		let title = fit(input.take_strings(&ItemKey::TrackTitle).next(), 30);
+		let artist = fit(input.take_strings(&ItemKey::TrackArtist).next(), 30);
+		let album = fit(input.take_strings(&ItemKey::AlbumTitle).next(), 30);
 		let year = input.year().map(|y| y.to_string());
-		let comment = input.take_strings(&ItemKey::Comment).next();
+		let comment = fit(
+			input.take_strings(&ItemKey::Comment).next(),
+			if track_number.is_some() { 28 } else { 30 },
+		);
 		Self {
 			title,
 			artist,
 			album,
 			year,
 			comment,
-			track_number: input
-				.get_string(&ItemKey::TrackNumber)
-				.map(|g| g.parse::<u8>().ok())
-				.and_then(|g| g),
-			genre: input
-				.get_string(&ItemKey::Genre)
-				.map(|g| {
-					GENRES
-						.iter()
-						.position(|v| v == &g)
-						.map_or_else(|| g.parse::<u8>().ok(), |p| Some(p as u8))
-				})
-				.and_then(|g| g),
+			track_number,
+			genre: input.get_string(&ItemKey::Genre).and_then(genre_index),
+			extended: None,
 		}
 	}
 }
@@ -411,15 +539,7 @@ impl<'a> Into<Id3v1TagRef<'a>> for &'a Tag {
 				.get_string(&ItemKey::TrackNumber)
 				.map(|g| g.parse::<u8>().ok())
 				.and_then(|g| g),
-			genre: self
-				.get_string(&ItemKey::Genre)
-				.map(|g| {
-					GENRES
-						.iter()
-						.position(|v| v == &g)
-						.map_or_else(|| g.parse::<u8>().ok(), |p| Some(p as u8))
-				})
-				.and_then(|g| g),
+			genre: self.get_string(&ItemKey::Genre).and_then(genre_index),
 		}
 	}
 }
@@ -447,9 +567,9 @@ impl Id3v1TagRef<'_> {
 	pub(crate) fn dump_to<W: Write>(
 		&mut self,
 		writer: &mut W,
-		_write_options: WriteOptions,
+		write_options: WriteOptions,
 	) -> Result<()> {
-		let temp = super::write::encode(self)?;
+		let temp = super::write::encode(self, write_options)?;
 		writer.write_all(&temp)?;
 
 		Ok(())
@@ -459,6 +579,7 @@ impl Id3v1TagRef<'_> {
 #[cfg(test)]
 mod tests {
 	use crate::config::WriteOptions;
+	use crate::error::ErrorKind;
 	use crate::id3::v1::Id3v1Tag;
 	use crate::prelude::*;
 	use crate::tag::{Tag, TagType};
@@ -473,6 +594,7 @@ mod tests {
 			comment: Some(String::from("Qux comment")),
 			track_number: Some(1),
 			genre: Some(32),
+			extended: None,
 		};
 
 		let tag = crate::tag::utils::test_utils::read_path("tests/tags/assets/test.id3v1");
@@ -481,6 +603,104 @@ mod tests {
 		assert_eq!(expected_tag, parsed_tag);
 	}
 
+	#[test_log::test]
+	fn write_multi_byte_text_at_field_boundary() {
+		for (last, expected_last) in [
+			// "é" is 2 bytes in UTF-8, but only 1 in Latin-1, so it still fits
+			("é", Some(0xE9)),
+			// Not in Latin-1, so it's dropped rather than split
+			("日", None),
+			// Transliterated to "...", which doesn't fit
+			("…", None),
+		] {
+			let tag = Id3v1Tag {
+				title: Some(format!("{}{last}", "a".repeat(29))),
+				..Id3v1Tag::default()
+			};
+
+			let mut writer = Vec::new();
+			tag.dump_to(&mut writer, WriteOptions::default()).unwrap();
+
+			let mut expected_title = vec![b'a'; 29];
+			expected_title.push(expected_last.unwrap_or(0));
+
+			assert_eq!(writer.len(), 128);
+			assert_eq!(&writer[3..33], expected_title, "{last}");
+		}
+	}
+
+	#[test_log::test]
+	fn write_non_latin1_text() {
+		let mut tag = Tag::new(TagType::Id3v1);
+		tag.set_title(String::from("Café “Noir” — 日本"));
+		tag.set_artist(String::from("日本語"));
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::default()).unwrap();
+
+		// Every byte maps to the Latin-1 character it was encoded from
+		let parsed = crate::id3::v1::read::parse_id3v1(writer.try_into().unwrap());
+		assert_eq!(parsed.title.as_deref(), Some("Café \"Noir\" - "));
+		assert_eq!(parsed.artist, None);
+
+		// Converting the tag should give the same result as writing it
+		let converted: Id3v1Tag = tag.into();
+		assert_eq!(converted, parsed);
+	}
+
+	#[test_log::test]
+	fn write_comment_layout() {
+		let comment = "c".repeat(30);
+
+		// Without a track number, an ID3v1.0 tag is written, which has room for the full comment
+		let tag = Id3v1Tag {
+			comment: Some(comment.clone()),
+			..Id3v1Tag::default()
+		};
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::default()).unwrap();
+
+		let parsed = crate::id3::v1::read::parse_id3v1(writer.try_into().unwrap());
+		assert_eq!(parsed.comment.as_deref(), Some(comment.as_str()));
+		assert_eq!(parsed.track_number, None);
+
+		// With a track number, the comment is cut short to fit the ID3v1.1 layout
+		let tag = Id3v1Tag {
+			comment: Some(comment.clone()),
+			track_number: Some(5),
+			..Id3v1Tag::default()
+		};
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::default()).unwrap();
+
+		let parsed = crate::id3::v1::read::parse_id3v1(writer.try_into().unwrap());
+		assert_eq!(parsed.comment.as_deref(), Some(&comment[..28]));
+		assert_eq!(parsed.track_number, Some(5));
+	}
+
+	#[test_log::test]
+	fn write_strict() {
+		let tag = Id3v1Tag {
+			title: Some("t".repeat(31)),
+			artist: Some(String::from("日本")),
+			album: Some(String::from("Baz album")),
+			comment: Some("c".repeat(30)),
+			..Id3v1Tag::default()
+		};
+
+		let err = tag
+			.dump_to(&mut Vec::new(), WriteOptions::new().strict_id3v1(true))
+			.unwrap_err();
+		assert!(
+			matches!(err.kind(), ErrorKind::LossyId3v1Fields(fields) if fields == &["title", "artist"])
+		);
+
+		// Without `strict_id3v1`, the fields are simply cut short
+		tag.dump_to(&mut Vec::new(), WriteOptions::new()).unwrap();
+	}
+
 	#[test_log::test]
 	fn id3v2_re_read() {
 		let tag = crate::tag::utils::test_utils::read_path("tests/tags/assets/test.id3v1");
@@ -519,4 +739,45 @@ mod tests {
 		assert_eq!(id3v1_tag.track_number, Some(1));
 		assert_eq!(id3v1_tag.genre, Some(32));
 	}
+
+	#[test_log::test]
+	fn winamp_extension_genres() {
+		let mut id3v1 = Id3v1Tag::new();
+		id3v1.set_genre_index(130);
+		assert_eq!(id3v1.genre().as_deref(), Some("Terror"));
+
+		id3v1.set_genre_index(140);
+		assert_eq!(id3v1.genre().as_deref(), Some("Contemporary Christian"));
+
+		let tag: Tag = id3v1.into();
+		assert_eq!(tag.genre().as_deref(), Some("Contemporary Christian"));
+
+		let id3v1: Id3v1Tag = tag.into();
+		assert_eq!(id3v1.genre_index(), Some(140));
+	}
+
+	#[test_log::test]
+	fn genre_from_tag() {
+		for (genre, expected) in [
+			("Terror", Some(130)),
+			("terror", Some(130)),
+			("(130)", Some(130)),
+			("130", Some(130)),
+			("hip hop", Some(7)),
+			("Jazz+Funk", Some(29)),
+			("192", None),
+			("My custom genre", None),
+		] {
+			let mut tag = Tag::new(TagType::Id3v1);
+			tag.set_genre(String::from(genre));
+
+			let id3v1: Id3v1Tag = tag.clone().into();
+			assert_eq!(id3v1.genre_index(), expected, "{genre}");
+
+			// Unmatched genres are written as 255
+			let mut writer = Vec::new();
+			tag.dump_to(&mut writer, WriteOptions::default()).unwrap();
+			assert_eq!(writer[127], expected.unwrap_or(255), "{genre}");
+		}
+	}
 }
@@ -1,12 +1,12 @@
 use super::tag::Id3v1TagRef;
 use crate::config::WriteOptions;
-use crate::error::{LoftyError, Result};
+use crate::error::{ErrorKind, LoftyError, Result};
 use crate::id3::{find_id3v1, ID3FindResults};
 use crate::macros::err;
 use crate::probe::Probe;
 use crate::util::io::{FileLike, Length, Truncate};
 
-use std::io::{Cursor, Seek, Write};
+use std::io::Write;
 
 use byteorder::WriteBytesExt;
 
@@ -14,7 +14,7 @@ use byteorder::WriteBytesExt;
 pub(crate) fn write_id3v1<F>(
 	file: &mut F,
 	tag: &Id3v1TagRef<'_>,
-	_write_options: WriteOptions,
+	write_options: WriteOptions,
 ) -> Result<()>
 where
 	F: FileLike,
@@ -23,9 +23,15 @@ where
 {
 	let probe = Probe::new(file).guess_file_type()?;
 
-	match probe.file_type() {
-		Some(ft) if super::Id3v1Tag::SUPPORTED_FORMATS.contains(&ft) => {},
+	let file_type = match probe.file_type() {
+		Some(ft) if super::Id3v1Tag::SUPPORTED_FORMATS.contains(&ft) => ft,
 		_ => err!(UnsupportedTag),
+	};
+
+	// Attempting to write a non-empty tag to a read only format
+	// An empty tag implies the tag should be stripped.
+	if super::Id3v1Tag::READ_ONLY_FORMATS.contains(&file_type) && !tag.is_empty() {
+		err!(UnsupportedTag);
 	}
 
 	let file = probe.into_inner();
@@ -33,61 +39,115 @@ where
 	// This will seek us to the writing position
 	let ID3FindResults(header, _) = find_id3v1(file, false)?;
 
-	if tag.is_empty() && header.is_some() {
-		// An ID3v1 tag occupies the last 128 bytes of the file, so we can just
-		// shrink it down.
-		let new_length = file.len()?.saturating_sub(128);
+	if let (true, Some(size)) = (tag.is_empty(), header) {
+		// An ID3v1 tag (along with any extended block) occupies the end of the file, so we can
+		// just shrink it down.
+		let new_length = file.len()?.saturating_sub(size);
 		file.truncate(new_length)?;
 
 		return Ok(());
 	}
 
-	let tag = encode(tag)?;
+	let tag = encode(tag, write_options)?;
 
 	file.write_all(&tag)?;
 
+	// The extended block isn't written, so the file may need to shrink
+	if header.is_some_and(|size| size > 128) {
+		let new_length = file.stream_position()?;
+		file.truncate(new_length)?;
+	}
+
 	Ok(())
 }
 
-pub(super) fn encode(tag: &Id3v1TagRef<'_>) -> std::io::Result<Vec<u8>> {
-	fn resize_string(value: Option<&str>, size: usize) -> std::io::Result<Vec<u8>> {
-		let mut cursor = Cursor::new(vec![0; size]);
-		cursor.rewind()?;
+pub(crate) fn encode(tag: &Id3v1TagRef<'_>, write_options: WriteOptions) -> Result<Vec<u8>> {
+	let mut lossy_fields = Vec::new();
+	let mut field = |name: &'static str, value: Option<&str>, size: usize| {
+		let mut bytes = vec![0; size];
 
-		if let Some(val) = value {
-			if val.len() > size {
-				cursor.write_all(val.split_at(size).0.as_bytes())?;
-			} else {
-				cursor.write_all(val.as_bytes())?;
+		if let Some(value) = value {
+			let (encoded, lossy) = encode_field(value, size);
+			if lossy {
+				lossy_fields.push(name);
 			}
+
+			bytes[..encoded.len()].copy_from_slice(&encoded);
 		}
 
-		Ok(cursor.into_inner())
-	}
+		bytes
+	};
 
 	let mut writer = Vec::with_capacity(128);
 
 	writer.write_all(b"TAG")?;
+	writer.write_all(&field("title", tag.title, 30))?;
+	writer.write_all(&field("artist", tag.artist, 30))?;
+	writer.write_all(&field("album", tag.album, 30))?;
+	writer.write_all(&field("year", tag.year, 4))?;
+
+	// The comment can only use all 30 bytes in an ID3v1.0 tag. In ID3v1.1, the last 2 bytes
+	// hold a null terminator and the track number.
+	match tag.track_number {
+		Some(track_number) => {
+			writer.write_all(&field("comment", tag.comment, 28))?;
+			writer.write_u8(0)?;
+			writer.write_u8(track_number)?;
+		},
+		None => writer.write_all(&field("comment", tag.comment, 30))?,
+	}
 
-	let title = resize_string(tag.title, 30)?;
-	writer.write_all(&title)?;
-
-	let artist = resize_string(tag.artist, 30)?;
-	writer.write_all(&artist)?;
+	writer.write_u8(tag.genre.unwrap_or(255))?;
 
-	let album = resize_string(tag.album, 30)?;
-	writer.write_all(&album)?;
+	if write_options.strict_id3v1 && !lossy_fields.is_empty() {
+		return Err(LoftyError::new(ErrorKind::LossyId3v1Fields(lossy_fields)));
+	}
 
-	let year = resize_string(tag.year, 4)?;
-	writer.write_all(&year)?;
+	Ok(writer)
+}
 
-	let comment = resize_string(tag.comment, 28)?;
-	writer.write_all(&comment)?;
+/// Encode `value` as Latin-1, fitting it into `size` bytes
+///
+/// Characters outside of Latin-1 are transliterated where possible, and dropped otherwise.
+/// The text is only ever truncated at a character boundary.
+///
+/// The returned flag is set when the encoded text doesn't exactly represent `value`.
+pub(super) fn encode_field(value: &str, size: usize) -> (Vec<u8>, bool) {
+	let mut encoded = Vec::with_capacity(size);
+	let mut lossy = false;
+
+	for c in value.chars() {
+		let mut latin1 = [0; 1];
+		let replacement = match u8::try_from(u32::from(c)) {
+			Ok(byte) => {
+				latin1[0] = byte;
+				&latin1[..]
+			},
+			Err(_) => {
+				lossy = true;
+				transliterate(c).as_bytes()
+			},
+		};
+
+		if encoded.len() + replacement.len() > size {
+			lossy = true;
+			break;
+		}
 
-	writer.write_u8(0)?;
+		encoded.extend_from_slice(replacement);
+	}
 
-	writer.write_u8(tag.track_number.unwrap_or(0))?;
-	writer.write_u8(tag.genre.unwrap_or(255))?;
+	(encoded, lossy)
+}
 
-	Ok(writer)
+// Common punctuation outside of Latin-1, anything else is dropped
+fn transliterate(c: char) -> &'static str {
+	match c {
+		'\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{2032}' => "'",
+		'\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{2033}' => "\"",
+		'\u{2010}'..='\u{2015}' | '\u{2212}' => "-",
+		'\u{2026}' => "...",
+		'\u{2022}' => "*",
+		_ => "",
+	}
 }
@@ -1,9 +1,11 @@
-use crate::config::ParsingMode;
+use crate::config::ParseOptions;
 use crate::error::{Id3v2Error, Id3v2ErrorKind, Result};
 use crate::id3::v2::header::Id3v2Version;
 use crate::id3::v2::items::{
-	AttachedPictureFrame, CommentFrame, EventTimingCodesFrame, ExtendedTextFrame, ExtendedUrlFrame,
-	KeyValueFrame, OwnershipFrame, PopularimeterFrame, PrivateFrame, RelativeVolumeAdjustmentFrame,
+	AttachedPictureFrame, ChapterFrame, CommentFrame, CommercialFrame, EventTimingCodesFrame,
+	ExtendedTextFrame, ExtendedUrlFrame, GeneralObjectFrame, KeyValueFrame, MusicCdIdentifierFrame,
+	OwnershipFrame, PlayCounterFrame, PopularimeterFrame, PrivateFrame,
+	RelativeVolumeAdjustmentFrame, SynchronizedTextFrame, TableOfContentsFrame,
 	TextInformationFrame, TimestampFrame, UniqueFileIdentifierFrame, UnsynchronizedTextFrame,
 	UrlLinkFrame,
 };
@@ -19,33 +21,56 @@ pub(super) fn parse_content<R: Read>(
     id: FrameId<'static>,
 	flags: FrameFlags,
     version: Id3v2Version,
-	parse_mode: ParsingMode,
+	parse_options: ParseOptions,
+	content_len: Option<u32>,
 ) -> Result<Option<Frame<'static>>> {
 	log::trace!("Parsing frame content for ID: {}", id);
+
+	let parse_mode = parse_options.parsing_mode;
+	let fallback = parse_options.text_decode_fallback;
 	
 	Ok(match id.as_str() {
 		// The ID was previously upgraded, but the content remains unchanged, so version is necessary
 		"APIC" => {
-			Some(Frame::Picture(AttachedPictureFrame::parse(reader, flags, version)?))
+			let data_policy = parse_options.picture_data_policy;
+			Some(Frame::Picture(AttachedPictureFrame::parse_with_data_policy(reader, flags, version, data_policy, content_len, fallback)?))
 		},
-		"TXXX" => ExtendedTextFrame::parse(reader, flags, version)?.map(Frame::UserText),
-		"WXXX" => ExtendedUrlFrame::parse(reader, flags, version)?.map(Frame::UserUrl),
-		"COMM" => CommentFrame::parse(reader, flags, version)?.map(Frame::Comment),
-		"USLT" => UnsynchronizedTextFrame::parse(reader, flags, version)?.map(Frame::UnsynchronizedText),
-		"TIPL" | "TMCL" => KeyValueFrame::parse(reader, id, flags, version)?.map(Frame::KeyValue),
+		"TXXX" => ExtendedTextFrame::parse_with_fallback(reader, flags, version, fallback)?.map(Frame::UserText),
+		"WXXX" => ExtendedUrlFrame::parse_with_fallback(reader, flags, version, fallback)?.map(Frame::UserUrl),
+		"COMM" => CommentFrame::parse_with_fallback(reader, flags, version, fallback)?.map(Frame::Comment),
+		"USLT" => UnsynchronizedTextFrame::parse_with_fallback(reader, flags, version, fallback)?.map(Frame::UnsynchronizedText),
+		"TIPL" | "TMCL" => KeyValueFrame::parse_with_fallback(reader, id, flags, version, fallback)?.map(Frame::KeyValue),
 		"UFID" => UniqueFileIdentifierFrame::parse(reader, flags, parse_mode)?.map(Frame::UniqueFileIdentifier),
 		"RVA2" => RelativeVolumeAdjustmentFrame::parse(reader, flags, parse_mode)?.map(Frame::RelativeVolumeAdjustment),
-		"OWNE" => OwnershipFrame::parse(reader, flags)?.map(Frame::Ownership),
-		"ETCO" => EventTimingCodesFrame::parse(reader, flags)?.map(Frame::EventTimingCodes),
+		// ID3v2.3 RVAD frames are converted to RVA2
+		"RVAD" => RelativeVolumeAdjustmentFrame::parse_rvad(reader, flags)?.map(Frame::RelativeVolumeAdjustment),
+		"OWNE" => OwnershipFrame::parse_with_fallback(reader, flags, parse_mode, fallback)?.map(Frame::Ownership),
+		"ETCO" => EventTimingCodesFrame::parse(reader, flags, parse_mode)?.map(Frame::EventTimingCodes),
 		"PRIV" => PrivateFrame::parse(reader, flags)?.map(Frame::Private),
+		"MCDI" => MusicCdIdentifierFrame::parse(reader, flags)?.map(Frame::MusicCdIdentifier),
+		"PCNT" => PlayCounterFrame::parse(reader, flags)?.map(Frame::PlayCounter),
+		"COMR" => CommercialFrame::parse_with_fallback(reader, flags, parse_mode, fallback)?.map(Frame::from),
+		"GEOB" => GeneralObjectFrame::parse_with_fallback(reader, flags, version, fallback)?.map(Frame::GeneralObject),
+		"SYLT" => {
+			let mut content = Vec::new();
+			reader.read_to_end(&mut content)?;
+			if content.is_empty() {
+				None
+			} else {
+				Some(Frame::SynchronizedText(SynchronizedTextFrame::parse_with_fallback(&content, flags, fallback)?))
+			}
+		},
+		// Chapters embed their own frames, which are read the same as those of the tag
+		"CHAP" => ChapterFrame::parse(reader, flags, version, parse_options)?.map(Frame::Chapter),
+		"CTOC" => TableOfContentsFrame::parse(reader, flags, version, parse_options)?.map(Frame::TableOfContents),
 		"TDEN" | "TDOR" | "TDRC" | "TDRL" | "TDTG" => TimestampFrame::parse(reader, id, flags, parse_mode)?.map(Frame::Timestamp),
-		i if i.starts_with('T') => TextInformationFrame::parse(reader, id, flags, version)?.map(Frame::Text),
+		i if i.starts_with('T') => TextInformationFrame::parse_with_fallback(reader, id, flags, version, fallback)?.map(Frame::Text),
 		// Apple proprietary frames
 		// WFED (Podcast URL), GRP1 (Grouping), MVNM (Movement Name), MVIN (Movement Number)
-		"WFED" | "GRP1" | "MVNM" | "MVIN" => TextInformationFrame::parse(reader, id, flags, version)?.map(Frame::Text),
+		"WFED" | "GRP1" | "MVNM" | "MVIN" => TextInformationFrame::parse_with_fallback(reader, id, flags, version, fallback)?.map(Frame::Text),
 		i if i.starts_with('W') => UrlLinkFrame::parse(reader, id, flags)?.map(Frame::Url),
 		"POPM" => Some(Frame::Popularimeter(PopularimeterFrame::parse(reader, flags)?)),
-		// SYLT, GEOB, and any unknown frames
+		// Any unknown frames
 		_ => {
 			Some(Frame::Binary(BinaryFrame::parse(reader, id, flags)?))
 		},
@@ -9,13 +9,48 @@ use crate::id3::v2::{
 	UniqueFileIdentifierFrame,
 };
 use crate::macros::err;
+use crate::tag::items::Popularimeter;
 use crate::tag::{ItemKey, ItemValue, TagItem, TagType};
+use crate::util::flag_item;
 use crate::TextEncoding;
 
 use std::borrow::Cow;
+use std::fmt::Write;
+
+// `WFED` is a text frame, despite its ID
+fn is_url_frame_id(id: &FrameId<'_>) -> bool {
+	let id = id.as_str();
+	id.starts_with('W') && id != "WFED"
+}
+
+// URL frames are Latin-1 only, so any other characters are percent-encoded (as UTF-8)
+fn latin1_url(url: String) -> String {
+	if TextEncoding::verify_latin1(&url) {
+		return url;
+	}
+
+	let mut encoded = String::with_capacity(url.len());
+	for c in url.chars() {
+		if u32::from(c) <= 0xFF {
+			encoded.push(c);
+			continue;
+		}
+
+		for b in c.encode_utf8(&mut [0; 4]).bytes() {
+			// Writing to a `String` can't fail
+			let _ = write!(encoded, "%{b:02X}");
+		}
+	}
+
+	encoded
+}
 
 fn frame_from_unknown_item(id: FrameId<'_>, item_value: ItemValue) -> Result<Frame<'_>> {
 	match item_value {
+		// Other formats commonly store URLs as text
+		ItemValue::Text(url) | ItemValue::Locator(url) if is_url_frame_id(&id) => {
+			Ok(new_url_frame(id, latin1_url(url)))
+		},
 		ItemValue::Text(text) => Ok(new_text_frame(id, text)),
 		ItemValue::Locator(locator) => {
 			if TextEncoding::verify_latin1(&locator) {
@@ -28,9 +63,44 @@ fn frame_from_unknown_item(id: FrameId<'_>, item_value: ItemValue) -> Result<Fra
 	}
 }
 
+fn popularimeter_frame_from_item(item: &TagItem) -> Result<PopularimeterFrame<'static>> {
+	// POPM content is used as-is, to avoid the lossy rating conversion
+	if let ItemValue::Binary(content) = item.value() {
+		return PopularimeterFrame::parse(&mut &content[..], FrameFlags::default());
+	}
+
+	match Popularimeter::from_tag_item(item) {
+		Some(popularimeter) => Ok(popularimeter.into()),
+		None => Err(Id3v2Error::new(Id3v2ErrorKind::BadFrame(
+			String::from("POPM"),
+			"Popularimeter",
+		))
+		.into()),
+	}
+}
+
+// The recording ID is stored in a `UFID` frame, rather than a `TXXX` frame like the other MusicBrainz identifiers
+fn musicbrainz_recording_id_frame(recording_id: String) -> Option<Frame<'static>> {
+	// The identifier is stored as raw bytes, which MusicBrainz Picard expects to be ASCII
+	if !recording_id.is_ascii() {
+		return None;
+	}
+
+	Some(Frame::UniqueFileIdentifier(UniqueFileIdentifierFrame::new(
+		MUSICBRAINZ_UFID_OWNER.to_owned(),
+		recording_id.into_bytes(),
+	)))
+}
+
 impl From<TagItem> for Option<Frame<'static>> {
 	fn from(input: TagItem) -> Self {
 		let value;
+		if input.item_key == ItemKey::Popularimeter {
+			return popularimeter_frame_from_item(&input)
+				.ok()
+				.map(Frame::Popularimeter);
+		}
+
 		if let Ok(id) = input.key().try_into().map(FrameId::into_owned) {
 			return frame_from_unknown_item(id, input.item_value).ok();
 		}
@@ -55,14 +125,7 @@ impl From<TagItem> for Option<Frame<'static>> {
 			},
 			None => match (input.item_key, input.item_value) {
 				(ItemKey::MusicBrainzRecordingId, ItemValue::Text(recording_id)) => {
-					if !recording_id.is_ascii() {
-						return None;
-					}
-					let frame = UniqueFileIdentifierFrame::new(
-						MUSICBRAINZ_UFID_OWNER.to_owned(),
-						recording_id.into_bytes(),
-					);
-					value = Frame::UniqueFileIdentifier(frame);
+					value = musicbrainz_recording_id_frame(recording_id)?;
 				},
 				_ => {
 					return None;
@@ -103,17 +166,40 @@ impl<'a> TryFrom<&'a TagItem> for FrameRef<'a> {
 					(text_id, ItemValue::Text(text)) if text_id.len() > 4 => {
 						value = new_user_text_frame(String::from(text_id), text.clone());
 					},
-					("POPM", ItemValue::Binary(contents)) => {
-						value = Frame::Popularimeter(PopularimeterFrame::parse(
-							&mut &contents[..],
-							FrameFlags::default(),
-						)?);
+					// `PCST` is a flag by its presence alone, its content is always 4 zero bytes
+					("PCST", ItemValue::Text(text)) => {
+						if flag_item(text) != Some(true) {
+							return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrame(
+								String::from("PCST"),
+								"Binary",
+							))
+							.into());
+						}
+
+						value = new_binary_frame(id, vec![0; 4]);
+					},
+					("POPM", ItemValue::Binary(_) | ItemValue::Text(_)) => {
+						value = Frame::Popularimeter(popularimeter_frame_from_item(tag_item)?);
 					},
 					(_, item_value) => value = frame_from_unknown_item(id, item_value.clone())?,
 				};
 			},
 			Err(_) => {
 				let item_key = tag_item.key();
+				if let (ItemKey::MusicBrainzRecordingId, ItemValue::Text(recording_id)) =
+					(item_key, tag_item.value())
+				{
+					let Some(frame) = musicbrainz_recording_id_frame(recording_id.clone()) else {
+						return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrame(
+							String::from("UFID"),
+							"UniqueFileIdentifier",
+						))
+						.into());
+					};
+
+					return Ok(FrameRef(Cow::Owned(frame)));
+				}
+
 				let Some(desc) = item_key.map_key(TagType::Id3v2, true) else {
 					return Err(Id3v2Error::new(Id3v2ErrorKind::UnsupportedFrameId(
 						item_key.clone(),
@@ -5,10 +5,12 @@ pub(super) mod read;
 
 use super::header::Id3v2Version;
 use super::items::{
-	AttachedPictureFrame, BinaryFrame, CommentFrame, EventTimingCodesFrame, ExtendedTextFrame,
-	ExtendedUrlFrame, KeyValueFrame, OwnershipFrame, PopularimeterFrame, PrivateFrame,
-	RelativeVolumeAdjustmentFrame, TextInformationFrame, TimestampFrame, UniqueFileIdentifierFrame,
-	UnsynchronizedTextFrame, UrlLinkFrame,
+	AttachedPictureFrame, BinaryFrame, ChapterFrame, CommentFrame, CommercialFrame,
+	EventTimingCodesFrame, ExtendedTextFrame, ExtendedUrlFrame, GeneralObjectFrame, KeyValueFrame,
+	MusicCdIdentifierFrame, OwnershipFrame, PlayCounterFrame, PopularimeterFrame, PrivateFrame,
+	RelativeVolumeAdjustmentFrame, SynchronizedTextFrame, TableOfContentsFrame,
+	TextInformationFrame, TimestampFrame, UniqueFileIdentifierFrame, UnsynchronizedTextFrame,
+	UrlLinkFrame,
 };
 use crate::error::Result;
 use crate::id3::v2::FrameHeader;
@@ -88,6 +90,12 @@ macro_rules! define_frames {
 	}
 }
 
+impl<'a> From<CommercialFrame<'a>> for Frame<'a> {
+	fn from(value: CommercialFrame<'a>) -> Self {
+		Frame::Commercial(Box::new(value))
+	}
+}
+
 define_frames! {
 	/// Represents an `ID3v2` frame
 	///
@@ -134,14 +142,30 @@ define_frames! {
 		EventTimingCodes(EventTimingCodesFrame<'a>),
 		/// Represents a "PRIV" frame
 		Private(PrivateFrame<'a>),
+		/// Represents a "GEOB" frame
+		GeneralObject(GeneralObjectFrame<'a>),
+		/// Represents a "SYLT" frame
+		SynchronizedText(SynchronizedTextFrame<'a>),
 		/// Represents a timestamp for the "TDEN", "TDOR", "TDRC", "TDRL", and "TDTG" frames
 		Timestamp(TimestampFrame<'a>),
+		/// Represents a "CHAP" frame
+		Chapter(ChapterFrame<'a>),
+		/// Represents a "CTOC" frame
+		TableOfContents(TableOfContentsFrame<'a>),
+		/// Represents an "MCDI" frame
+		MusicCdIdentifier(MusicCdIdentifierFrame<'a>),
+		/// Represents a "PCNT" frame
+		PlayCounter(PlayCounterFrame<'a>),
+		/// Represents a "COMR" frame
+		///
+		/// This is boxed, as it's much larger than any other frame.
+		Commercial(Box<CommercialFrame<'a>>),
 		/// Binary data
 		///
 		/// NOTES:
 		///
-		/// * This is used for rare frames, such as GEOB, SYLT, and ATXT to skip additional unnecessary work.
-		///   See [`GeneralEncapsulatedObject::parse`](crate::id3::v2::GeneralEncapsulatedObject::parse), [`SynchronizedText::parse`](crate::id3::v2::SynchronizedTextFrame::parse), and [`AudioTextFrame::parse`](crate::id3::v2::AudioTextFrame::parse) respectively
+		/// * This is used for rare frames, such as ATXT to skip additional unnecessary work.
+		///   See [`AudioTextFrame::parse`](crate::id3::v2::AudioTextFrame::parse)
 		/// * This is used for **all** frames with an ID of [`FrameId::Outdated`]
 		/// * This is used for unknown frames
 		Binary(BinaryFrame<'a>),
@@ -181,8 +205,15 @@ impl Frame<'_> {
 			Frame::UniqueFileIdentifier(ufid) => ufid.identifier.is_empty(),
 			Frame::EventTimingCodes(event_timing) => event_timing.events.is_empty(),
 			Frame::Private(private) => private.private_data.is_empty(),
+			Frame::GeneralObject(object) => object.data.is_empty(),
+			Frame::SynchronizedText(sync_text) => sync_text.content.is_empty(),
 			Frame::Binary(binary) => binary.data.is_empty(),
+			Frame::TableOfContents(toc) => toc.child_element_ids.is_empty(),
+			Frame::MusicCdIdentifier(mcdi) => mcdi.data.is_empty(),
 			Frame::Popularimeter(_)
+			| Frame::PlayCounter(_)
+			| Frame::Commercial(_)
+			| Frame::Chapter(_)
 			| Frame::RelativeVolumeAdjustment(_)
 			| Frame::Ownership(_)
 			| Frame::Timestamp(_) => {
@@ -214,12 +245,25 @@ impl Frame<'_> {
 			},
 			Frame::Popularimeter(popularimeter) => popularimeter.as_bytes()?,
 			Frame::KeyValue(content) => content.as_bytes(is_id3v23),
-			Frame::RelativeVolumeAdjustment(frame) => frame.as_bytes(),
+			Frame::RelativeVolumeAdjustment(frame) => {
+				if is_id3v23 {
+					frame.as_rvad_bytes()
+				} else {
+					frame.as_bytes()
+				}
+			},
 			Frame::UniqueFileIdentifier(frame) => frame.as_bytes(),
 			Frame::Ownership(frame) => frame.as_bytes(is_id3v23)?,
 			Frame::EventTimingCodes(frame) => frame.as_bytes(),
 			Frame::Private(frame) => frame.as_bytes()?,
+			Frame::GeneralObject(frame) => frame.as_bytes(is_id3v23),
+			Frame::SynchronizedText(frame) => frame.as_bytes(is_id3v23)?,
 			Frame::Timestamp(frame) => frame.as_bytes(is_id3v23)?,
+			Frame::Chapter(frame) => frame.as_bytes(is_id3v23)?,
+			Frame::TableOfContents(frame) => frame.as_bytes(is_id3v23)?,
+			Frame::MusicCdIdentifier(frame) => frame.as_bytes(),
+			Frame::PlayCounter(frame) => frame.as_bytes(),
+			Frame::Commercial(frame) => frame.as_bytes(is_id3v23)?,
 			Frame::Binary(frame) => frame.as_bytes(),
 		})
 	}
@@ -241,7 +285,14 @@ impl Frame<'_> {
 			Frame::Ownership(_) => "Ownership",
 			Frame::EventTimingCodes(_) => "EventTimingCodes",
 			Frame::Private(_) => "Private",
+			Frame::GeneralObject(_) => "GeneralObject",
+			Frame::SynchronizedText(_) => "SynchronizedText",
 			Frame::Timestamp(_) => "Timestamp",
+			Frame::Chapter(_) => "Chapter",
+			Frame::TableOfContents(_) => "TableOfContents",
+			Frame::MusicCdIdentifier(_) => "MusicCdIdentifier",
+			Frame::PlayCounter(_) => "PlayCounter",
+			Frame::Commercial(_) => "Commercial",
 			Frame::Binary(_) => "Binary",
 		}
 	}
@@ -264,10 +315,18 @@ pub struct FrameFlags {
 	/// Frame is zlib compressed
 	///
 	/// It is **required** `data_length_indicator` be set if this is set.
+	///
+	/// NOTE: Compressed frames are decompressed when read (with the `id3v2_compression_support` feature),
+	///       after which this flag is unset. Frames that can't be decompressed (or any compressed
+	///       frame, without the feature) are skipped, unless using [`ParsingMode::Strict`].
+	///       Frames are never compressed when writing, unless they are also encrypted.
+	///
+	/// [`ParsingMode::Strict`]: crate::config::ParsingMode::Strict
 	pub compression: bool,
 	/// Frame encryption method symbol
 	///
-	/// NOTE: Since the encryption method is unknown, lofty cannot do anything with these frames
+	/// NOTE: Since the encryption method is unknown, lofty cannot do anything with these frames. They
+	///       will be read as [`Frame::Binary`], and written back as-is with their flags.
 	///
 	/// The encryption method symbol **must** be > 0x80.
 	pub encryption: Option<u8>,
@@ -277,9 +336,10 @@ pub struct FrameFlags {
 	/// with the MPEG frame header, which is often identified by its "frame sync" (11 set bits).
 	/// It is preferred an ID3v2 tag is either *completely* unsynchronised or not unsynchronised at all.
 	///
-	/// NOTE: While unsynchronized data is read, for the sake of simplicity, this flag has no effect when
-	/// writing. There isn't much reason to write unsynchronized data.
-	pub unsynchronisation: bool, /* TODO: Maybe? This doesn't seem very useful, and it is wasted effort if one forgets to make this false when writing. */
+	/// NOTE: Unsynchronised data is decoded when reading, so this flag has no effect when writing. Whether
+	/// frames are unsynchronised is decided by [`WriteOptions::unsynchronisation`](crate::config::WriteOptions::unsynchronisation)
+	/// and [`Id3v2TagFlags::unsynchronisation`](crate::id3::v2::Id3v2TagFlags::unsynchronisation).
+	pub unsynchronisation: bool,
 	/// Frame has a data length indicator
 	///
 	/// The data length indicator is the size of the frame if the flags were all zeroed out.
@@ -1,13 +1,15 @@
 use super::header::parse::{parse_header, parse_v2_header};
 use super::Frame;
 use crate::config::{ParseOptions, ParsingMode};
-use crate::error::{Id3v2Error, Id3v2ErrorKind, Result};
+use crate::error::{Id3v2Error, Id3v2ErrorKind, ParseDiagnostic, Result};
 use crate::id3::v2::frame::content::parse_content;
 use crate::id3::v2::header::Id3v2Version;
+use crate::id3::v2::read::TagReader;
 use crate::id3::v2::tag::ATTACHED_PICTURE_ID;
 use crate::id3::v2::util::synchsafe::{SynchsafeInteger, UnsynchronizedStream};
 use crate::id3::v2::{BinaryFrame, FrameFlags, FrameHeader, FrameId};
-use crate::macros::try_vec;
+use crate::util::alloc::VecFallibleCapacity;
+use crate::util::diagnostics;
 
 use std::io::Read;
 
@@ -19,11 +21,56 @@ pub(crate) enum ParsedFrame<'a> {
 	Eof,
 }
 
+// Chapters can't be nested, see `ParsedFrame::read_embedded`
+const CHAPTER_FRAME_IDS: [&str; 2] = ["CHAP", "CTOC"];
+
 impl ParsedFrame<'_> {
+	/// Read the next frame of a tag
+	///
+	/// `offset` is the offset of the frame from the start of the tag, if known. It's only used for
+	/// diagnostics.
 	pub(crate) fn read<R>(
-		reader: &mut R,
+		reader: &mut TagReader<R>,
+		version: Id3v2Version,
+		unsynchronised: bool,
+		parse_options: ParseOptions,
+		offset: Option<u64>,
+	) -> Result<Self>
+	where
+		R: Read,
+	{
+		Self::read_frame(
+			reader,
+			version,
+			unsynchronised,
+			parse_options,
+			offset,
+			false,
+		)
+	}
+
+	/// Read a frame embedded in a `CHAP` or `CTOC` frame
+	///
+	/// Embedded `CHAP` and `CTOC` frames are skipped (or an error with [`ParsingMode::Strict`]),
+	/// so the frames are never nested more than one level deep.
+	pub(crate) fn read_embedded<R>(
+		reader: &mut TagReader<R>,
+		version: Id3v2Version,
+		parse_options: ParseOptions,
+	) -> Result<Self>
+	where
+		R: Read,
+	{
+		Self::read_frame(reader, version, false, parse_options, None, true)
+	}
+
+	fn read_frame<R>(
+		reader: &mut TagReader<R>,
 		version: Id3v2Version,
+		unsynchronised: bool,
 		parse_options: ParseOptions,
+		offset: Option<u64>,
+		embedded: bool,
 	) -> Result<Self>
 	where
 		R: Read,
@@ -47,6 +94,10 @@ impl ParsedFrame<'_> {
 					ParsingMode::Strict => return Err(err),
 					ParsingMode::BestAttempt | ParsingMode::Relaxed => {
 						log::warn!("Failed to read frame header, skipping: {}", err);
+						diagnostics::report(ParseDiagnostic::InvalidId3v2FrameHeader {
+							offset,
+							message: err.to_string(),
+						});
 
 						// Skip this frame and continue reading
 						skip_frame(reader, size)?;
@@ -56,7 +107,39 @@ impl ParsedFrame<'_> {
 			},
 		};
 
-		if !parse_options.read_cover_art && id == ATTACHED_PICTURE_ID {
+		if embedded && CHAPTER_FRAME_IDS.contains(&id.as_str()) {
+			if parse_options.parsing_mode == ParsingMode::Strict {
+				return Err(Id3v2Error::new(Id3v2ErrorKind::NestedChapterFrame(id)).into());
+			}
+
+			log::warn!("Encountered a nested `{id}` frame, skipping");
+
+			skip_frame(reader, size)?;
+			return Ok(Self::Skip);
+		}
+
+		// The tag may declare all frames unsynchronised, even if they aren't flagged individually
+		if unsynchronised {
+			flags.unsynchronisation = true;
+		}
+
+		if let Some(remaining) = reader.remaining() {
+			if u64::from(size) > remaining && parse_options.parsing_mode != ParsingMode::Strict {
+				log::warn!(
+					"Frame `{id}` has a size of {size}, but only {remaining} bytes remain in the tag"
+				);
+				diagnostics::report(ParseDiagnostic::OversizedFrame {
+					id: id.to_string(),
+					offset,
+					size,
+					remaining,
+				});
+			}
+		}
+
+		if (!parse_options.read_cover_art && id == ATTACHED_PICTURE_ID)
+			|| !parse_options.verify_item_size(u64::from(size))?
+		{
 			skip_frame(reader, size)?;
 			return Ok(Self::Skip);
 		}
@@ -72,43 +155,40 @@ impl ParsedFrame<'_> {
 			return Ok(Self::Skip);
 		}
 
-		// Get the encryption method symbol
-		if let Some(enc) = flags.encryption.as_mut() {
-			log::trace!("Reading encryption method symbol");
-
-			if size < 1 {
-				return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
-			}
+		// The additional data is stored in the order of the flags. ID3v2.3 and ID3v2.4 order them
+		// differently:
+		//
+		// * ID3v2.3: decompressed size -> encryption method -> group identifier
+		// * ID3v2.4: group identifier -> encryption method -> data length indicator
+		let is_id3v24 = version == Id3v2Version::V4;
 
-			*enc = reader.read_u8()?;
-			size -= 1;
+		if !is_id3v24 && flags.compression {
+			log::trace!("Reading decompressed size");
+			flags.data_length_indicator = Some(read_u32_field(reader, &mut size)?);
 		}
 
-		// Get the group identifier
-		if let Some(group) = flags.grouping_identity.as_mut() {
-			log::trace!("Reading group identifier");
-
-			if size < 1 {
-				return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
+		if is_id3v24 {
+			if let Some(group) = flags.grouping_identity.as_mut() {
+				log::trace!("Reading group identifier");
+				*group = read_u8_field(reader, &mut size)?;
 			}
-
-			*group = reader.read_u8()?;
-			size -= 1;
 		}
 
-		// Get the real data length
-		if flags.data_length_indicator.is_some() || flags.compression {
-			log::trace!("Reading data length indicator");
-
-			if size < 4 {
-				return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
-			}
+		if let Some(enc) = flags.encryption.as_mut() {
+			log::trace!("Reading encryption method symbol");
+			*enc = read_u8_field(reader, &mut size)?;
+		}
 
+		if is_id3v24 {
 			// For some reason, no one can follow the spec, so while a data length indicator is *written*
 			// the flag **isn't always set**
-			let len = reader.read_u32::<BigEndian>()?.unsynch();
-			flags.data_length_indicator = Some(len);
-			size -= 4;
+			if flags.data_length_indicator.is_some() || flags.compression {
+				log::trace!("Reading data length indicator");
+				flags.data_length_indicator = Some(read_u32_field(reader, &mut size)?.unsynch());
+			}
+		} else if let Some(group) = flags.grouping_identity.as_mut() {
+			log::trace!("Reading group identifier");
+			*group = read_u8_field(reader, &mut size)?;
 		}
 
 		// Frames must have at least 1 byte, *after* all of the additional data flags can provide
@@ -117,111 +197,141 @@ impl ParsedFrame<'_> {
 		}
 
 		// Restrict the reader to the frame content
-		let mut reader = reader.take(u64::from(size));
+		let reader = reader.take(u64::from(size));
 
-		// It seems like the flags are applied in the order:
+		// The flags are applied in the order:
 		//
-		// unsynchronization -> compression -> encryption
+		// compression -> encryption -> unsynchronization
 		//
-		// Which all have their own needs, so this gets a little messy...
-		match flags {
-			// Possible combinations:
-			//
-			// * unsynchronized + compressed + encrypted
-			// * unsynchronized + compressed
-			// * unsynchronized + encrypted
-			// * unsynchronized
-			FrameFlags {
-				unsynchronisation: true,
-				..
-			} => {
-				let mut unsynchronized_reader = UnsynchronizedStream::new(reader);
-
-				if flags.compression {
-					let mut compression_reader = handle_compression(unsynchronized_reader)?;
-
-					if flags.encryption.is_some() {
-						return handle_encryption(&mut compression_reader, size, id, flags);
-					}
-
-					return parse_frame(
-						&mut compression_reader,
-						size,
-						id,
-						flags,
-						version,
-						parse_options.parsing_mode,
-					);
-				}
+		// So they need to be undone in reverse.
+		if flags.unsynchronisation {
+			return read_frame_content(
+				&mut UnsynchronizedStream::new(reader),
+				size,
+				id,
+				flags,
+				version,
+				parse_options,
+			);
+		}
 
-				if flags.encryption.is_some() {
-					return handle_encryption(&mut unsynchronized_reader, size, id, flags);
-				}
+		read_frame_content(&mut { reader }, size, id, flags, version, parse_options)
+	}
+}
 
-				return parse_frame(
-					&mut unsynchronized_reader,
-					size,
-					id,
-					flags,
-					version,
-					parse_options.parsing_mode,
-				);
-			},
-			// Possible combinations:
-			//
-			// * compressed + encrypted
-			// * compressed
-			FrameFlags {
-				compression: true, ..
-			} => {
-				let mut compression_reader = handle_compression(reader)?;
-
-				if flags.encryption.is_some() {
-					return handle_encryption(&mut compression_reader, size, id, flags);
-				}
+fn read_u8_field<R: Read>(reader: &mut R, size: &mut u32) -> Result<u8> {
+	if *size < 1 {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
+	}
 
-				return parse_frame(
-					&mut compression_reader,
-					size,
-					id,
-					flags,
-					version,
-					parse_options.parsing_mode,
-				);
-			},
-			// Possible combinations:
-			//
-			// * encrypted
-			FrameFlags {
-				encryption: Some(_),
-				..
-			} => {
-				return handle_encryption(&mut reader, size, id, flags);
-			},
-			// Everything else that doesn't have special flags
-			_ => {
-				return parse_frame(
-					&mut reader,
-					size,
-					id,
-					flags,
-					version,
-					parse_options.parsing_mode,
-				);
+	*size -= 1;
+	Ok(reader.read_u8()?)
+}
+
+fn read_u32_field<R: Read>(reader: &mut R, size: &mut u32) -> Result<u32> {
+	if *size < 4 {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
+	}
+
+	*size -= 4;
+	Ok(reader.read_u32::<BigEndian>()?)
+}
+
+fn read_frame_content<R: Read>(
+	reader: &mut R,
+	size: u32,
+	id: FrameId<'static>,
+	mut flags: FrameFlags,
+	version: Id3v2Version,
+	parse_options: ParseOptions,
+) -> Result<ParsedFrame<'static>> {
+	// Nothing further we can do with encrypted frames, their content will be preserved as-is
+	if flags.encryption.is_some() {
+		return handle_encryption(reader, size, id, flags);
+	}
+
+	if flags.compression {
+		let decompressed = match decompress(
+			reader,
+			flags.data_length_indicator,
+			parse_options.parsing_mode,
+		) {
+			Ok(decompressed) => decompressed,
+			// Either the content is corrupt, or support is disabled. Either way, the rest of the
+			// tag can still be read.
+			Err(err) if parse_options.parsing_mode != ParsingMode::Strict => {
+				log::warn!("Failed to decompress frame `{id}`, skipping: {err}");
+
+				skip_frame(reader, size)?;
+				return Ok(ParsedFrame::Skip);
 			},
-		}
+			Err(err) => return Err(err),
+		};
+
+		// The content is no longer compressed, so it can be treated like any other frame
+		flags.compression = false;
+		flags.data_length_indicator = None;
+
+		return parse_frame(
+			&mut &decompressed[..],
+			decompressed.len() as u32,
+			Some(decompressed.len() as u32),
+			id,
+			flags,
+			version,
+			parse_options,
+		);
 	}
+
+	// The size of unsynchronized content is that of the encoded content
+	let content_len = (!flags.unsynchronisation).then_some(size);
+	parse_frame(reader, size, content_len, id, flags, version, parse_options)
 }
 
 #[cfg(feature = "id3v2_compression_support")]
-#[allow(clippy::unnecessary_wraps)]
-fn handle_compression<R: Read>(reader: R) -> Result<flate2::read::ZlibDecoder<R>> {
-	Ok(flate2::read::ZlibDecoder::new(reader))
+fn decompress<R: Read>(
+	reader: &mut R,
+	expected_size: Option<u32>,
+	parse_mode: ParsingMode,
+) -> Result<Vec<u8>> {
+	use crate::config::global_options;
+	use crate::macros::err;
+
+	let allocation_limit = unsafe { global_options().allocation_limit };
+
+	let mut decompressed = Vec::new();
+	flate2::read::ZlibDecoder::new(reader)
+		.take(allocation_limit as u64 + 1)
+		.read_to_end(&mut decompressed)?;
+
+	if decompressed.len() > allocation_limit {
+		err!(TooMuchData);
+	}
+
+	if let Some(expected_size) = expected_size {
+		if decompressed.len() != expected_size as usize {
+			if parse_mode == ParsingMode::Strict {
+				return Err(Id3v2Error::new(Id3v2ErrorKind::DecompressedSizeMismatch {
+					expected: expected_size,
+					actual: decompressed.len(),
+				})
+				.into());
+			}
+
+			log::warn!(
+				"Compressed frame has a stated decompressed size of {expected_size}, but \
+				 decompressed to {} bytes",
+				decompressed.len()
+			);
+		}
+	}
+
+	Ok(decompressed)
 }
 
 #[cfg(not(feature = "id3v2_compression_support"))]
 #[allow(clippy::unnecessary_wraps)]
-fn handle_compression<R>(_: R) -> Result<std::io::Empty> {
+fn decompress<R: Read>(_: &mut R, _: Option<u32>, _: ParsingMode) -> Result<Vec<u8>> {
 	Err(Id3v2Error::new(Id3v2ErrorKind::CompressedFrameEncountered).into())
 }
 
@@ -231,31 +341,27 @@ fn handle_encryption<R: Read>(
 	id: FrameId<'static>,
 	flags: FrameFlags,
 ) -> Result<ParsedFrame<'static>> {
-	if flags.data_length_indicator.is_none() {
-		return Err(Id3v2Error::new(Id3v2ErrorKind::MissingDataLengthIndicator).into());
-	}
-
-	let mut content = try_vec![0; size as usize];
-	reader.read_exact(&mut content)?;
+	let mut content = Vec::try_with_capacity_stable(size as usize)?;
+	reader.read_to_end(&mut content)?;
 
 	let encrypted_frame = Frame::Binary(BinaryFrame {
 		header: FrameHeader::new(id, flags),
 		data: content,
 	});
 
-	// Nothing further we can do with encrypted frames
 	Ok(ParsedFrame::Next(encrypted_frame))
 }
 
 fn parse_frame<R: Read>(
 	reader: &mut R,
 	size: u32,
+	content_len: Option<u32>,
 	id: FrameId<'static>,
 	flags: FrameFlags,
 	version: Id3v2Version,
-	parse_mode: ParsingMode,
+	parse_options: ParseOptions,
 ) -> Result<ParsedFrame<'static>> {
-	match parse_content(reader, id, flags, version, parse_mode)? {
+	match parse_content(reader, id, flags, version, parse_options, content_len)? {
 		Some(frame) => Ok(ParsedFrame::Next(frame)),
 		None => {
 			skip_frame(reader, size)?;
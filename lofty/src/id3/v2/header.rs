@@ -23,6 +23,8 @@ pub enum Id3v2Version {
 #[allow(clippy::struct_excessive_bools)]
 pub struct Id3v2TagFlags {
 	/// Whether or not all frames are unsynchronised. See [`FrameFlags::unsynchronisation`](crate::id3::v2::FrameFlags::unsynchronisation)
+	///
+	/// If set, the tag will be unsynchronised when written. See [`WriteOptions::unsynchronisation`](crate::config::WriteOptions::unsynchronisation).
 	pub unsynchronisation: bool,
 	/// Indicates if the tag is in an experimental stage
 	pub experimental: bool,
@@ -34,7 +36,7 @@ pub struct Id3v2TagFlags {
 	///
 	/// This is calculated if the tag is written
 	pub crc: bool,
-	/// Restrictions on the tag, written in the extended header
+	/// Restrictions on the tag, written in the extended header (**ID3v2.4 only**)
 	///
 	/// In addition to being setting this flag, all restrictions must be provided. See [`TagRestrictions`]
 	pub restrictions: Option<TagRestrictions>,
@@ -68,6 +70,10 @@ impl Id3v2TagFlags {
 	pub fn as_id3v23_byte(&self) -> u8 {
 		let mut byte = 0;
 
+		if self.unsynchronisation {
+			byte |= 0x80;
+		}
+
 		if self.experimental {
 			byte |= 0x40;
 		}
@@ -80,17 +86,50 @@ impl Id3v2TagFlags {
 	}
 }
 
+/// An `ID3v2` tag header
+///
+/// This is needed to walk the frames of a tag with a [`FrameIter`](crate::id3::v2::FrameIter).
 #[derive(Copy, Clone, Debug)]
-pub(crate) struct Id3v2Header {
-	pub version: Id3v2Version,
-	pub flags: Id3v2TagFlags,
+pub struct Id3v2Header {
+	pub(crate) version: Id3v2Version,
+	pub(crate) flags: Id3v2TagFlags,
 	/// The size of the tag contents (**DOES NOT INCLUDE THE HEADER/FOOTER**)
-	pub size: u32,
-	pub extended_size: u32,
+	pub(crate) size: u32,
+	pub(crate) extended_size: u32,
+	pub(crate) extended_header: Option<ExtendedHeader>,
+	/// The padding size stated in an `ID3v2.3` extended header, which is excluded from the CRC
+	pub(crate) padding_size: u32,
 }
 
 impl Id3v2Header {
-	pub(crate) fn parse<R>(bytes: &mut R) -> Result<Self>
+	/// Parse an `ID3v2` header, including the extended header if present
+	///
+	/// This expects the reader to be positioned at the start of the tag (the `"ID3"` identifier).
+	/// Upon return, the reader will be positioned at the first frame.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `bytes`
+	/// * The header is not an `ID3v2` header
+	/// * The version is unsupported
+	/// * The extended header is invalid
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Id3v2Header, Id3v2Version};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// // An empty ID3v2.4 tag
+	/// let bytes = [b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 0];
+	///
+	/// let header = Id3v2Header::parse(&mut Cursor::new(bytes))?;
+	/// assert_eq!(header.version(), Id3v2Version::V4);
+	/// assert_eq!(header.size(), 0);
+	/// # Ok(()) }
+	/// ```
+	pub fn parse<R>(bytes: &mut R) -> Result<Self>
 	where
 		R: Read,
 	{
@@ -136,38 +175,22 @@ impl Id3v2Header {
 
 		let size = BigEndian::read_u32(&header[6..]).unsynch();
 		let mut extended_size = 0;
+		let mut extended_header = None;
+		let mut padding_size = 0;
 
-		let extended_header =
+		let has_extended_header =
 			(version == Id3v2Version::V4 || version == Id3v2Version::V3) && flags & 0x40 == 0x40;
 
-		if extended_header {
-			extended_size = bytes.read_u32::<BigEndian>()?.unsynch();
-
-			if extended_size < 6 {
-				return Err(Id3v2Error::new(Id3v2ErrorKind::BadExtendedHeaderSize).into());
-			}
-
-			// Useless byte since there's only 1 byte for flags
-			let _num_flag_bytes = bytes.read_u8()?;
-
-			let extended_flags = bytes.read_u8()?;
-
-			// The only flags we care about here are the CRC and restrictions
-
-			if extended_flags & 0x20 == 0x20 {
-				flags_parsed.crc = true;
+		if has_extended_header {
+			let parsed = if version == Id3v2Version::V4 {
+				ExtendedHeader::parse_v4(bytes, &mut flags_parsed)?
+			} else {
+				ExtendedHeader::parse_v3(bytes, &mut flags_parsed)?
+			};
 
-				// We don't care about the existing CRC (5) or its length byte (1)
-				let mut crc = [0; 6];
-				bytes.read_exact(&mut crc)?;
-			}
-
-			if extended_flags & 0x10 == 0x10 {
-				// We don't care about the length byte, it is always 1
-				let _data_length = bytes.read_u8()?;
-
-				flags_parsed.restrictions = Some(TagRestrictions::from_byte(bytes.read_u8()?));
-			}
+			extended_size = parsed.size;
+			padding_size = parsed.padding_size;
+			extended_header = Some(parsed.header);
 		}
 
 		if extended_size > 0 && extended_size >= size {
@@ -179,11 +202,180 @@ impl Id3v2Header {
 			flags: flags_parsed,
 			size,
 			extended_size,
+			extended_header,
+			padding_size,
 		})
 	}
 
+	/// The version of the tag
+	pub fn version(&self) -> Id3v2Version {
+		self.version
+	}
+
+	/// The flags of the tag
+	pub fn flags(&self) -> &Id3v2TagFlags {
+		&self.flags
+	}
+
+	/// The size of the tag contents
+	///
+	/// NOTE: This **does not** include the header or footer
+	pub fn size(&self) -> u32 {
+		self.size
+	}
+
+	/// The extended header of the tag, if present
+	pub fn extended_header(&self) -> Option<&ExtendedHeader> {
+		self.extended_header.as_ref()
+	}
+
 	/// The total size of the tag, including the header, footer, and extended header
 	pub(crate) fn full_tag_size(&self) -> u32 {
 		self.size + 10 + self.extended_size + if self.flags.footer { 10 } else { 0 }
 	}
 }
+
+/// An `ID3v2` extended header
+///
+/// This describes the extended header as it was read. To write an extended header,
+/// see [`Id3v2TagFlags::crc`] and [`Id3v2TagFlags::restrictions`].
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExtendedHeader {
+	/// The CRC-32 of the tag's frame data
+	///
+	/// When parsing, this is verified against the frame data. A mismatch is an error in
+	/// [`ParsingMode::Strict`](crate::config::ParsingMode::Strict).
+	pub crc: Option<u32>,
+	/// Restrictions on the tag (**ID3v2.4 only**)
+	pub restrictions: Option<TagRestrictions>,
+	/// Whether the tag is an update of a tag found earlier in the file (**ID3v2.4 only**)
+	pub is_update: bool,
+}
+
+struct ParsedExtendedHeader {
+	header: ExtendedHeader,
+	// The total size, including the size field itself
+	size: u32,
+	padding_size: u32,
+}
+
+impl ExtendedHeader {
+	fn parse_v4<R>(bytes: &mut R, flags: &mut Id3v2TagFlags) -> Result<ParsedExtendedHeader>
+	where
+		R: Read,
+	{
+		let size = bytes.read_u32::<BigEndian>()?.unsynch();
+
+		if size < 6 {
+			return Err(Id3v2Error::new(Id3v2ErrorKind::BadExtendedHeaderSize).into());
+		}
+
+		// Useless byte since there's only 1 byte for flags
+		let _num_flag_bytes = bytes.read_u8()?;
+
+		let extended_flags = bytes.read_u8()?;
+		let mut consumed = 6;
+
+		let mut header = ExtendedHeader::default();
+
+		// Each flag's data is preceded by its length, in the order of the flags
+		if extended_flags & 0x40 == 0x40 {
+			header.is_update = true;
+
+			// The length byte, which is always 0
+			let _data_length = bytes.read_u8()?;
+			consumed += 1;
+		}
+
+		if extended_flags & 0x20 == 0x20 {
+			flags.crc = true;
+
+			// The length byte, which is always 5
+			let _data_length = bytes.read_u8()?;
+
+			let mut crc = [0; 5];
+			bytes.read_exact(&mut crc)?;
+			header.crc = Some(decode_crc(crc));
+			consumed += 6;
+		}
+
+		if extended_flags & 0x10 == 0x10 {
+			// The length byte, which is always 1
+			let _data_length = bytes.read_u8()?;
+
+			let restrictions = TagRestrictions::from_byte(bytes.read_u8()?);
+			flags.restrictions = Some(restrictions);
+			header.restrictions = Some(restrictions);
+			consumed += 2;
+		}
+
+		skip_remaining(bytes, size, consumed)?;
+
+		Ok(ParsedExtendedHeader {
+			header,
+			size,
+			padding_size: 0,
+		})
+	}
+
+	fn parse_v3<R>(bytes: &mut R, flags: &mut Id3v2TagFlags) -> Result<ParsedExtendedHeader>
+	where
+		R: Read,
+	{
+		// Unlike ID3v2.4, the size is not synchsafe, and does not include itself
+		let size = bytes.read_u32::<BigEndian>()?;
+
+		if size < 6 {
+			return Err(Id3v2Error::new(Id3v2ErrorKind::BadExtendedHeaderSize).into());
+		}
+
+		let size = size
+			.checked_add(4)
+			.ok_or_else(|| Id3v2Error::new(Id3v2ErrorKind::BadExtendedHeaderSize))?;
+
+		let extended_flags = bytes.read_u16::<BigEndian>()?;
+		let padding_size = bytes.read_u32::<BigEndian>()?;
+		let mut consumed = 10;
+
+		let mut header = ExtendedHeader::default();
+
+		if extended_flags & 0x8000 == 0x8000 {
+			flags.crc = true;
+
+			header.crc = Some(bytes.read_u32::<BigEndian>()?);
+			consumed += 4;
+		}
+
+		skip_remaining(bytes, size, consumed)?;
+
+		Ok(ParsedExtendedHeader {
+			header,
+			size,
+			padding_size,
+		})
+	}
+}
+
+fn skip_remaining<R>(bytes: &mut R, size: u32, consumed: u32) -> Result<()>
+where
+	R: Read,
+{
+	if consumed > size {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::BadExtendedHeaderSize).into());
+	}
+
+	let remaining = u64::from(size - consumed);
+	if std::io::copy(&mut bytes.by_ref().take(remaining), &mut std::io::sink())? != remaining {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::BadExtendedHeaderSize).into());
+	}
+
+	Ok(())
+}
+
+// The CRC-32 is stored as a 35 bit synchsafe integer
+fn decode_crc(encoded: [u8; 5]) -> u32 {
+	encoded
+		.iter()
+		.fold(0_u64, |crc, b| (crc << 7) | u64::from(b & 0x7F)) as u32
+}
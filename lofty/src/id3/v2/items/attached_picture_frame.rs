@@ -1,8 +1,10 @@
+use crate::config::{PictureDataPolicy, TextDecodeFallback};
 use crate::error::{Id3v2Error, Id3v2ErrorKind, Result};
 use crate::id3::v2::header::Id3v2Version;
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
 use crate::macros::err;
 use crate::picture::{MimeType, Picture, PictureType};
+use crate::util::picture_sink;
 use crate::util::text::{encode_text, TextDecodeOptions, TextEncoding};
 
 use std::borrow::Cow;
@@ -63,6 +65,33 @@ impl AttachedPictureFrame<'_> {
 	///
 	/// * The format is not "PNG" or "JPG"
 	pub fn parse<R>(reader: &mut R, frame_flags: FrameFlags, version: Id3v2Version) -> Result<Self>
+	where
+		R: Read,
+	{
+		Self::parse_with_data_policy(
+			reader,
+			frame_flags,
+			version,
+			PictureDataPolicy::Load,
+			None,
+			TextDecodeFallback::None,
+		)
+	}
+
+	/// Same as [`AttachedPictureFrame::parse`], handling the picture data according to `data_policy`
+	///
+	/// `content_len` is the exact length of the frame content, if known. Without it, skipped data
+	/// has to be read into memory first to determine its length.
+	///
+	/// `fallback` is used for a description that doesn't match its declared encoding.
+	pub(crate) fn parse_with_data_policy<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		data_policy: PictureDataPolicy,
+		content_len: Option<u32>,
+		fallback: TextDecodeFallback,
+	) -> Result<Self>
 	where
 		R: Read,
 	{
@@ -70,10 +99,14 @@ impl AttachedPictureFrame<'_> {
 			err!(NotAPicture);
 		};
 
+		// Encoding + picture type
+		let mut header_len = 2;
+
 		let mime_type;
 		if version == Id3v2Version::V2 {
 			let mut format = [0; 3];
 			reader.read_exact(&mut format)?;
+			header_len += format.len();
 
 			match format {
 				[b'P', b'N', b'G'] => mime_type = Some(MimeType::Png),
@@ -91,8 +124,10 @@ impl AttachedPictureFrame<'_> {
 				TextDecodeOptions::new()
 					.encoding(TextEncoding::Latin1)
 					.terminated(true),
-			)?
-			.text_or_none();
+			)?;
+			header_len += mime_type_str.bytes_read;
+
+			let mime_type_str = mime_type_str.text_or_none();
 			mime_type = mime_type_str.map(|mime_type_str| MimeType::from_str(&mime_type_str));
 		};
 
@@ -100,21 +135,44 @@ impl AttachedPictureFrame<'_> {
 
 		let description = crate::util::text::decode_text(
 			reader,
-			TextDecodeOptions::new().encoding(encoding).terminated(true),
-		)?
-		.text_or_none()
-		.map(Cow::from);
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.terminated(true)
+				.fallback(fallback),
+		)?;
+		header_len += description.bytes_read;
 
-		let mut data = Vec::new();
-		reader.read_to_end(&mut data)?;
+		let description = description.text_or_none().map(Cow::from);
 
-		let picture = Picture {
+		let mut picture = Picture {
 			pic_type,
 			mime_type,
 			description,
-			data: Cow::from(data),
+			data: Cow::Owned(Vec::new()),
+			skipped_data_len: None,
 		};
 
+		match (data_policy, content_len) {
+			(PictureDataPolicy::Skip, Some(content_len)) => {
+				let data_len = (content_len as usize).saturating_sub(header_len);
+				picture.skipped_data_len = Some(data_len as u64);
+				picture_sink::consume(&picture, reader)?;
+			},
+			(PictureDataPolicy::Skip, None) => {
+				let mut data = Vec::new();
+				reader.read_to_end(&mut data)?;
+
+				picture.skipped_data_len = Some(data.len() as u64);
+				picture_sink::consume(&picture, &mut &data[..])?;
+			},
+			_ => {
+				let mut data = Vec::new();
+				reader.read_to_end(&mut data)?;
+
+				picture.data = Cow::Owned(data);
+			},
+		}
+
 		let header = FrameHeader::new(FRAME_ID, frame_flags);
 		Ok(Self {
 			header,
@@ -130,11 +188,14 @@ impl AttachedPictureFrame<'_> {
 	/// # Errors
 	///
 	/// * Too much data was provided
+	/// * The picture data was skipped while reading ([`ErrorKind::SkippedPictureData`](crate::error::ErrorKind::SkippedPictureData))
 	///
 	/// ID3v2.2:
 	///
 	/// * The mimetype is not [`MimeType::Png`] or [`MimeType::Jpeg`]
 	pub fn as_bytes(&self, version: Id3v2Version) -> Result<Vec<u8>> {
+		self.picture.verify_has_data()?;
+
 		let mut encoding = self.encoding;
 		if version != Id3v2Version::V4 {
 			encoding = encoding.to_id3v23();
@@ -0,0 +1,359 @@
+use crate::config::{ParseOptions, WriteOptions};
+use crate::error::Result;
+use crate::id3::v2::frame::read::ParsedFrame;
+use crate::id3::v2::read::TagReader;
+use crate::id3::v2::write::frame::{create_items, create_items_v3};
+use crate::id3::v2::{Frame, FrameFlags, FrameHeader, FrameId, Id3v2Version};
+use crate::util::text::{decode_text, encode_text, TextDecodeOptions, TextEncoding};
+
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+const FRAME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("CHAP"));
+
+// An offset of 0xFFFFFFFF means the offset isn't set, and the time should be used instead
+const UNSET_OFFSET: u32 = u32::MAX;
+
+/// An `ID3v2` chapter frame
+///
+/// This describes a single chapter of the audio, as defined in the
+/// [ID3v2 Chapter Frame Addendum](https://mutagen-specs.readthedocs.io/en/latest/id3/id3v2-chapters-1.0.html).
+/// A chapter can carry its own frames, such as a `TIT2` for its title, or an `APIC` for its image.
+///
+/// Chapters are ordered by [`TableOfContentsFrame`](crate::id3::v2::TableOfContentsFrame)s, which
+/// refer to them by their element ID.
+///
+/// NOTE: The `PartialEq` and `Hash` implementations only look at the element ID, as it is unique
+///       within a tag.
+#[derive(Clone, Debug, Eq)]
+pub struct ChapterFrame<'a> {
+	pub(crate) header: FrameHeader<'a>,
+	/// The identifier of the chapter, unique within the tag
+	///
+	/// This is not meant to be displayed, and is encoded as a null terminated Latin-1 string.
+	pub element_id: String,
+	/// The start of the chapter, in milliseconds
+	pub start_time: u32,
+	/// The end of the chapter, in milliseconds
+	pub end_time: u32,
+	/// The byte offset of the first frame of the chapter, from the start of the file
+	///
+	/// If `None`, [`ChapterFrame::start_time`] should be used instead.
+	pub start_offset: Option<u32>,
+	/// The byte offset of the first frame following the chapter, from the start of the file
+	///
+	/// If `None`, [`ChapterFrame::end_time`] should be used instead.
+	pub end_offset: Option<u32>,
+	/// The frames describing the chapter, such as its title (`TIT2`)
+	pub frames: Vec<Frame<'a>>,
+}
+
+impl PartialEq for ChapterFrame<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		self.element_id == other.element_id
+	}
+}
+
+impl Hash for ChapterFrame<'_> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.element_id.hash(state);
+	}
+}
+
+impl ChapterFrame<'_> {
+	/// Create a new [`ChapterFrame`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{ChapterFrame, Frame, FrameId, TextInformationFrame};
+	/// use lofty::TextEncoding;
+	/// use std::borrow::Cow;
+	///
+	/// let mut chapter = ChapterFrame::new(String::from("chp0"), 0, 5000);
+	/// chapter.frames.push(Frame::Text(TextInformationFrame::new(
+	/// 	FrameId::Valid(Cow::Borrowed("TIT2")),
+	/// 	TextEncoding::UTF8,
+	/// 	String::from("Introduction"),
+	/// )));
+	///
+	/// assert_eq!(chapter.title(), Some("Introduction"));
+	/// ```
+	pub fn new(element_id: String, start_time: u32, end_time: u32) -> Self {
+		let header = FrameHeader::new(FRAME_ID, FrameFlags::default());
+		Self {
+			header,
+			element_id,
+			start_time,
+			end_time,
+			start_offset: None,
+			end_offset: None,
+			frames: Vec::new(),
+		}
+	}
+
+	/// Get the ID for the frame
+	pub fn id(&self) -> FrameId<'_> {
+		FRAME_ID
+	}
+
+	/// Get the flags for the frame
+	pub fn flags(&self) -> FrameFlags {
+		self.header.flags
+	}
+
+	/// Set the flags for the frame
+	pub fn set_flags(&mut self, flags: FrameFlags) {
+		self.header.flags = flags;
+	}
+
+	/// The title of the chapter, from its `TIT2` frame
+	pub fn title(&self) -> Option<&str> {
+		self.frames.iter().find_map(|frame| match frame {
+			Frame::Text(text) if text.header.id.as_str() == "TIT2" => Some(text.value.as_str()),
+			_ => None,
+		})
+	}
+
+	/// Read a [`ChapterFrame`]
+	///
+	/// NOTE: This expects the frame header to have already been skipped
+	///
+	/// The embedded frames are read according to `version` and `parse_options`, the same as the
+	/// frames of the tag. Chapters can't be nested, so any embedded `CHAP` or `CTOC` frames are skipped.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * Any of the embedded frames are invalid, depending on the [`ParsingMode`](crate::config::ParsingMode)
+	/// * A `CHAP` or `CTOC` frame is embedded, with [`ParsingMode::Strict`](crate::config::ParsingMode::Strict)
+	pub fn parse<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		parse_options: ParseOptions,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let element_id = decode_text(
+			reader,
+			TextDecodeOptions::new()
+				.encoding(TextEncoding::Latin1)
+				.terminated(true),
+		)?;
+		if element_id.bytes_read == 0 {
+			return Ok(None);
+		}
+
+		let start_time = reader.read_u32::<BigEndian>()?;
+		let end_time = reader.read_u32::<BigEndian>()?;
+		let start_offset = reader.read_u32::<BigEndian>()?;
+		let end_offset = reader.read_u32::<BigEndian>()?;
+
+		let frames = read_embedded_frames(reader, version, parse_options)?;
+
+		let header = FrameHeader::new(FRAME_ID, frame_flags);
+		Ok(Some(ChapterFrame {
+			header,
+			element_id: element_id.content,
+			start_time,
+			end_time,
+			start_offset: (start_offset != UNSET_OFFSET).then_some(start_offset),
+			end_offset: (end_offset != UNSET_OFFSET).then_some(end_offset),
+			frames,
+		}))
+	}
+
+	/// Convert a [`ChapterFrame`] to a byte vec
+	///
+	/// # Errors
+	///
+	/// * Any of the embedded frames fail to convert, see [`Id3v2Tag`](crate::id3::v2::Id3v2Tag)'s
+	///   implementation of [`TagExt::save_to`](crate::tag::TagExt::save_to)
+	pub fn as_bytes(&self, is_id3v23: bool) -> Result<Vec<u8>> {
+		let mut bytes = encode_text(&self.element_id, TextEncoding::Latin1, true);
+
+		bytes.extend(self.start_time.to_be_bytes());
+		bytes.extend(self.end_time.to_be_bytes());
+		bytes.extend(self.start_offset.unwrap_or(UNSET_OFFSET).to_be_bytes());
+		bytes.extend(self.end_offset.unwrap_or(UNSET_OFFSET).to_be_bytes());
+
+		write_embedded_frames(&mut bytes, &self.frames, is_id3v23)?;
+
+		Ok(bytes)
+	}
+}
+
+// Reads the frames embedded in a `CHAP` or `CTOC` frame, which make up the rest of its content
+pub(super) fn read_embedded_frames<R>(
+	reader: &mut R,
+	version: Id3v2Version,
+	parse_options: ParseOptions,
+) -> Result<Vec<Frame<'static>>>
+where
+	R: Read,
+{
+	// The reader is already restricted to the content of the outer frame. It's erased to keep the
+	// (never nested) chapter frames from instantiating `ParsedFrame::read` with an ever-growing type.
+	let reader: &mut dyn Read = reader;
+	let mut reader = TagReader::Plain(reader.take(u64::MAX));
+
+	// The embedded frames are never unsynchronised on their own, any unsynchronisation was already
+	// undone when reading the outer frame
+	let mut frames = Vec::new();
+	loop {
+		match ParsedFrame::read_embedded(&mut reader, version, parse_options)? {
+			ParsedFrame::Next(frame) => frames.push(frame),
+			ParsedFrame::Skip => {},
+			ParsedFrame::Eof => break,
+		}
+	}
+
+	Ok(frames)
+}
+
+// NOTE: ID3v2.3 multi-value text frames are joined with the default separator, see
+//       `WriteOptions::id3v23_separator`
+pub(super) fn write_embedded_frames(
+	bytes: &mut Vec<u8>,
+	frames: &[Frame<'_>],
+	is_id3v23: bool,
+) -> Result<()> {
+	let mut frames = frames.iter().filter_map(Frame::as_opt_ref);
+
+	if is_id3v23 {
+		create_items_v3(bytes, &mut frames, WriteOptions::DEFAULT_ID3V23_SEPARATOR)
+	} else {
+		create_items(bytes, &mut frames, false)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::config::{ParseOptions, ParsingMode};
+	use crate::error::{ErrorKind, Id3v2ErrorKind};
+	use crate::id3::v2::{
+		AttachedPictureFrame, ChapterFrame, Frame, FrameFlags, FrameId, Id3v2Version,
+		TextInformationFrame,
+	};
+	use crate::picture::{MimeType, Picture, PictureType};
+	use crate::TextEncoding;
+
+	use std::borrow::Cow;
+
+	fn expected() -> ChapterFrame<'static> {
+		let mut chapter = ChapterFrame::new(String::from("chp1"), 5000, 10000);
+		chapter.start_offset = Some(0x1234);
+		chapter.frames.push(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TIT2")),
+			TextEncoding::Latin1,
+			String::from("Foo chapter"),
+		)));
+		chapter
+			.frames
+			.push(Frame::Picture(AttachedPictureFrame::new(
+				TextEncoding::Latin1,
+				Picture::new_unchecked(
+					PictureType::CoverFront,
+					Some(MimeType::Png),
+					None,
+					vec![1, 2, 3],
+				),
+			)));
+
+		chapter
+	}
+
+	#[test_log::test]
+	fn chap_round_trip() {
+		for (version, is_id3v23) in [(Id3v2Version::V4, false), (Id3v2Version::V3, true)] {
+			let bytes = expected().as_bytes(is_id3v23).unwrap();
+
+			let parsed = ChapterFrame::parse(
+				&mut &bytes[..],
+				FrameFlags::default(),
+				version,
+				ParseOptions::new(),
+			)
+			.unwrap()
+			.unwrap();
+
+			assert_eq!(parsed.element_id, "chp1");
+			assert_eq!(parsed.start_time, 5000);
+			assert_eq!(parsed.end_time, 10000);
+			assert_eq!(parsed.start_offset, Some(0x1234));
+			assert_eq!(parsed.end_offset, None);
+			assert_eq!(parsed.title(), Some("Foo chapter"));
+			assert_eq!(parsed.frames, expected().frames);
+		}
+	}
+
+	#[test_log::test]
+	fn chap_nested() {
+		// Build a chapter nested 1000 levels deep, each level also having its own title
+		fn chapter_body(level: u32) -> Vec<u8> {
+			let mut body = format!("chp{level}\0").into_bytes();
+			body.extend([0; 16]);
+
+			let title = format!("\0Chapter {level}").into_bytes();
+			body.extend(b"TIT2");
+			body.extend((title.len() as u32).to_be_bytes());
+			body.extend([0, 0]);
+			body.extend(title);
+
+			body
+		}
+
+		let mut content = chapter_body(1000);
+		for level in (0..1000).rev() {
+			let mut body = chapter_body(level);
+			body.extend(b"CHAP");
+			body.extend((content.len() as u32).to_be_bytes());
+			body.extend([0, 0]);
+			body.extend(content);
+
+			content = body;
+		}
+
+		let parsed = ChapterFrame::parse(
+			&mut &content[..],
+			FrameFlags::default(),
+			Id3v2Version::V3,
+			ParseOptions::new().parsing_mode(ParsingMode::Relaxed),
+		)
+		.unwrap()
+		.unwrap();
+
+		// Only the title is kept, the nested chapter is skipped
+		assert_eq!(parsed.element_id, "chp0");
+		assert_eq!(parsed.title(), Some("Chapter 0"));
+		assert_eq!(parsed.frames.len(), 1);
+
+		let err = ChapterFrame::parse(
+			&mut &content[..],
+			FrameFlags::default(),
+			Id3v2Version::V3,
+			ParseOptions::new().parsing_mode(ParsingMode::Strict),
+		)
+		.unwrap_err();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::Id3v2(e) if matches!(e.kind(), Id3v2ErrorKind::NestedChapterFrame(_))
+		));
+	}
+
+	#[test_log::test]
+	fn chap_unset_offsets() {
+		let bytes = ChapterFrame::new(String::from("chp0"), 0, 1000)
+			.as_bytes(false)
+			.unwrap();
+
+		// Element ID, start and end times, then the unset offsets
+		assert_eq!(&bytes[..5], b"chp0\0");
+		assert_eq!(&bytes[13..], &[0xFF; 8]);
+	}
+}
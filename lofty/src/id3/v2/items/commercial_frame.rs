@@ -0,0 +1,414 @@
+use crate::config::{ParsingMode, TextDecodeFallback};
+use crate::error::{ErrorKind, Id3v2Error, Id3v2ErrorKind, LoftyError, Result};
+use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
+use crate::picture::{MimeType, Picture, PictureType};
+use crate::util::text::{decode_text, encode_text, latin1_decode, TextDecodeOptions, TextEncoding};
+
+use std::borrow::Cow;
+use std::hash::Hash;
+use std::io::Read;
+
+use byteorder::ReadBytesExt;
+
+const FRAME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("COMR"));
+
+/// How the audio in a [`CommercialFrame`] is delivered when bought
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Hash)]
+#[allow(missing_docs)]
+pub enum ReceivedAs {
+	Other,
+	StandardCdAlbum,
+	CompressedAudioOnCd,
+	FileOverInternet,
+	StreamOverInternet,
+	NoteSheets,
+	NoteSheetsInBook,
+	MusicOnOtherMedia,
+	NonMusicalMerchandise,
+	/// A value not defined by the spec, which is kept as-is
+	Unknown(u8),
+}
+
+impl ReceivedAs {
+	/// Get a `u8` from a `ReceivedAs`
+	pub fn as_u8(self) -> u8 {
+		match self {
+			Self::Other => 0,
+			Self::StandardCdAlbum => 1,
+			Self::CompressedAudioOnCd => 2,
+			Self::FileOverInternet => 3,
+			Self::StreamOverInternet => 4,
+			Self::NoteSheets => 5,
+			Self::NoteSheetsInBook => 6,
+			Self::MusicOnOtherMedia => 7,
+			Self::NonMusicalMerchandise => 8,
+			Self::Unknown(byte) => byte,
+		}
+	}
+
+	/// Get a `ReceivedAs` from a `u8`
+	///
+	/// Any value above 8 is [`ReceivedAs::Unknown`].
+	pub fn from_u8(byte: u8) -> Self {
+		match byte {
+			0 => Self::Other,
+			1 => Self::StandardCdAlbum,
+			2 => Self::CompressedAudioOnCd,
+			3 => Self::FileOverInternet,
+			4 => Self::StreamOverInternet,
+			5 => Self::NoteSheets,
+			6 => Self::NoteSheetsInBook,
+			7 => Self::MusicOnOtherMedia,
+			8 => Self::NonMusicalMerchandise,
+			_ => Self::Unknown(byte),
+		}
+	}
+}
+
+/// An `ID3v2` commercial frame
+///
+/// This describes an offer to buy the audio, such as its price and the seller.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CommercialFrame<'a> {
+	pub(crate) header: FrameHeader<'a>,
+	/// The encoding of the seller name and description
+	pub encoding: TextEncoding,
+	/// The price
+	///
+	/// This is a three character ISO 4217 currency code, followed by the price as a numerical string
+	/// using "." as the decimal separator (e.g. "USD9.99"). Multiple prices are separated by "/".
+	pub price: String,
+	/// The date the price is valid until, as an 8 character date string (YYYYMMDD)
+	pub valid_until: String,
+	/// A URL to contact the seller
+	pub contact_url: String,
+	/// How the audio is delivered when bought
+	pub received_as: ReceivedAs,
+	/// The name of the seller
+	pub seller_name: String,
+	/// A short description of the product
+	pub description: String,
+	/// The MIME type of [`CommercialFrame::logo`]
+	///
+	/// This is only written if there is a logo.
+	pub logo_mime_type: String,
+	/// The image data of the seller's logo, if any
+	///
+	/// See [`CommercialFrame::seller_logo`] to get it as a [`Picture`].
+	pub logo: Vec<u8>,
+}
+
+impl CommercialFrame<'_> {
+	/// Create a new [`CommercialFrame`]
+	///
+	/// This will have no logo.
+	pub fn new(
+		encoding: TextEncoding,
+		price: String,
+		valid_until: String,
+		contact_url: String,
+		received_as: ReceivedAs,
+		seller_name: String,
+		description: String,
+	) -> Self {
+		let header = FrameHeader::new(FRAME_ID, FrameFlags::default());
+		Self {
+			header,
+			encoding,
+			price,
+			valid_until,
+			contact_url,
+			received_as,
+			seller_name,
+			description,
+			logo_mime_type: String::new(),
+			logo: Vec::new(),
+		}
+	}
+
+	/// Get the ID for the frame
+	pub fn id(&self) -> FrameId<'_> {
+		FRAME_ID
+	}
+
+	/// Get the flags for the frame
+	pub fn flags(&self) -> FrameFlags {
+		self.header.flags
+	}
+
+	/// Set the flags for the frame
+	pub fn set_flags(&mut self, flags: FrameFlags) {
+		self.header.flags = flags;
+	}
+
+	/// Get the seller's logo as a [`Picture`]
+	///
+	/// This will only return a picture for PNG and JPEG logos, as those are the only formats
+	/// allowed. Any other logo can still be accessed through [`CommercialFrame::logo`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{CommercialFrame, ReceivedAs};
+	/// use lofty::picture::{MimeType, PictureType};
+	/// use lofty::TextEncoding;
+	///
+	/// let mut commercial = CommercialFrame::new(
+	/// 	TextEncoding::UTF8,
+	/// 	String::from("USD9.99"),
+	/// 	String::from("20301231"),
+	/// 	String::from("https://example.com"),
+	/// 	ReceivedAs::FileOverInternet,
+	/// 	String::from("Foo Store"),
+	/// 	String::from("Bar album"),
+	/// );
+	/// assert!(commercial.seller_logo().is_none());
+	///
+	/// commercial.logo_mime_type = String::from("image/png");
+	/// commercial.logo = vec![1, 2, 3];
+	///
+	/// let logo = commercial.seller_logo().unwrap();
+	/// assert_eq!(logo.pic_type(), PictureType::PublisherLogo);
+	/// assert_eq!(logo.mime_type(), Some(&MimeType::Png));
+	/// ```
+	pub fn seller_logo(&self) -> Option<Picture> {
+		if self.logo.is_empty() {
+			return None;
+		}
+
+		let mime_type = MimeType::from_str(&self.logo_mime_type);
+		if !matches!(mime_type, MimeType::Png | MimeType::Jpeg) {
+			return None;
+		}
+
+		Some(Picture::new_unchecked(
+			PictureType::PublisherLogo,
+			Some(mime_type),
+			None,
+			self.logo.clone(),
+		))
+	}
+
+	/// Read a [`CommercialFrame`]
+	///
+	/// NOTE: This expects the frame header to have already been skipped
+	///
+	/// An unknown [`ReceivedAs`] is an error with [`ParsingMode::Strict`]. Otherwise, it is kept as
+	/// [`ReceivedAs::Unknown`].
+	///
+	/// # Errors
+	///
+	/// * Invalid text encoding
+	/// * Not enough data
+	/// * Unknown [`ReceivedAs`], with [`ParsingMode::Strict`]
+	pub fn parse<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		parse_mode: ParsingMode,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, frame_flags, parse_mode, TextDecodeFallback::None)
+	}
+
+	/// Same as [`CommercialFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		parse_mode: ParsingMode,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let Ok(encoding_byte) = reader.read_u8() else {
+			return Ok(None);
+		};
+
+		let encoding = TextEncoding::from_u8(encoding_byte)
+			.ok_or_else(|| LoftyError::new(ErrorKind::TextDecode("Found invalid encoding")))?;
+
+		let latin1_terminated = TextDecodeOptions::new()
+			.encoding(TextEncoding::Latin1)
+			.terminated(true);
+
+		let price = decode_text(reader, latin1_terminated)?.content;
+
+		let mut valid_until = [0; 8];
+		reader.read_exact(&mut valid_until)?;
+		let valid_until = latin1_decode(&valid_until);
+
+		let contact_url = decode_text(reader, latin1_terminated)?.content;
+
+		let received_as = ReceivedAs::from_u8(reader.read_u8()?);
+		if let ReceivedAs::Unknown(received_as_byte) = received_as {
+			if parse_mode == ParsingMode::Strict {
+				return Err(Id3v2Error::new(Id3v2ErrorKind::BadCommercialReceivedAs(
+					received_as_byte,
+				))
+				.into());
+			}
+
+			log::warn!("COMR: Unknown received as type {received_as_byte}, keeping it as-is");
+		}
+
+		let encoding_terminated = TextDecodeOptions::new()
+			.encoding(encoding)
+			.terminated(true)
+			.fallback(fallback);
+		let seller_name = decode_text(reader, encoding_terminated)?.content;
+		let description = decode_text(reader, encoding_terminated)?.content;
+
+		// The logo is optional, and takes up the rest of the frame
+		let logo_mime_type = decode_text(reader, latin1_terminated)?.content;
+
+		let mut logo = Vec::new();
+		reader.read_to_end(&mut logo)?;
+
+		let header = FrameHeader::new(FRAME_ID, frame_flags);
+		Ok(Some(CommercialFrame {
+			header,
+			encoding,
+			price,
+			valid_until,
+			contact_url,
+			received_as,
+			seller_name,
+			description,
+			logo_mime_type,
+			logo,
+		}))
+	}
+
+	/// Convert a [`CommercialFrame`] to a byte vec
+	///
+	/// NOTE: The caller must verify that the `price`, `contact_url`, and `logo_mime_type` fields are
+	///       valid Latin-1 encoded strings
+	///
+	/// # Errors
+	///
+	/// * `valid_until` is not at least 8 characters (it will be truncated if greater)
+	pub fn as_bytes(&self, is_id3v23: bool) -> Result<Vec<u8>> {
+		let mut encoding = self.encoding;
+		if is_id3v23 {
+			encoding = encoding.to_id3v23();
+		}
+
+		let mut bytes = vec![encoding as u8];
+
+		bytes.extend(encode_text(&self.price, TextEncoding::Latin1, true));
+
+		if self.valid_until.chars().count() < 8 {
+			return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
+		}
+		bytes.extend(
+			encode_text(&self.valid_until, TextEncoding::Latin1, false)
+				.into_iter()
+				.take(8),
+		);
+
+		bytes.extend(encode_text(&self.contact_url, TextEncoding::Latin1, true));
+		bytes.push(self.received_as.as_u8());
+		bytes.extend(encode_text(&self.seller_name, encoding, true));
+		bytes.extend(encode_text(&self.description, encoding, true));
+
+		if !self.logo.is_empty() {
+			bytes.extend(encode_text(
+				&self.logo_mime_type,
+				TextEncoding::Latin1,
+				true,
+			));
+			bytes.extend(&self.logo);
+		}
+
+		Ok(bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::config::ParsingMode;
+	use crate::id3::v2::{CommercialFrame, FrameFlags, ReceivedAs};
+	use crate::picture::MimeType;
+	use crate::TextEncoding;
+
+	fn expected() -> CommercialFrame<'static> {
+		let mut commercial = CommercialFrame::new(
+			TextEncoding::UTF16,
+			String::from("USD9.99/EUR8.99"),
+			String::from("20301231"),
+			String::from("https://example.com"),
+			ReceivedAs::FileOverInternet,
+			String::from("Foo Store"),
+			String::from("Bar album"),
+		);
+		commercial.logo_mime_type = String::from("image/jpeg");
+		commercial.logo = vec![0xFF, 0xD8, 0xFF, 0xE0];
+
+		commercial
+	}
+
+	#[test_log::test]
+	fn comr_round_trip() {
+		let bytes = expected().as_bytes(false).unwrap();
+
+		let parsed =
+			CommercialFrame::parse(&mut &bytes[..], FrameFlags::default(), ParsingMode::Strict)
+				.unwrap()
+				.unwrap();
+
+		assert_eq!(parsed, expected());
+		assert_eq!(parsed.as_bytes(false).unwrap(), bytes);
+
+		let logo = parsed.seller_logo().unwrap();
+		assert_eq!(logo.mime_type(), Some(&MimeType::Jpeg));
+		assert_eq!(logo.data(), &[0xFF, 0xD8, 0xFF, 0xE0]);
+	}
+
+	#[test_log::test]
+	fn comr_no_logo() {
+		let mut commercial = expected();
+		commercial.logo.clear();
+
+		let bytes = commercial.as_bytes(false).unwrap();
+		let parsed =
+			CommercialFrame::parse(&mut &bytes[..], FrameFlags::default(), ParsingMode::Strict)
+				.unwrap()
+				.unwrap();
+
+		assert!(parsed.logo_mime_type.is_empty());
+		assert!(parsed.logo.is_empty());
+		assert!(parsed.seller_logo().is_none());
+	}
+
+	#[test_log::test]
+	fn comr_unknown_received_as() {
+		let mut bytes = expected().as_bytes(false).unwrap();
+
+		// Encoding, price, valid until, contact URL, then the received as byte
+		let received_as_pos = 1 + "USD9.99/EUR8.99\0".len() + 8 + "https://example.com\0".len();
+		assert_eq!(bytes[received_as_pos], ReceivedAs::FileOverInternet.as_u8());
+		bytes[received_as_pos] = 0xFF;
+
+		assert!(CommercialFrame::parse(
+			&mut &bytes[..],
+			FrameFlags::default(),
+			ParsingMode::Strict
+		)
+		.is_err());
+
+		let parsed = CommercialFrame::parse(
+			&mut &bytes[..],
+			FrameFlags::default(),
+			ParsingMode::BestAttempt,
+		)
+		.unwrap()
+		.unwrap();
+		assert_eq!(parsed.received_as, ReceivedAs::Unknown(0xFF));
+		assert_eq!(parsed.seller_name, "Foo Store");
+
+		// The unknown value is written back unchanged
+		assert_eq!(parsed.as_bytes(false).unwrap(), bytes);
+	}
+}
@@ -1,14 +1,25 @@
-use crate::error::{ErrorKind, Id3v2Error, Id3v2ErrorKind, LoftyError, Result};
+use crate::config::TextDecodeFallback;
+use crate::error::Result;
+use crate::id3::v2::frame::content::verify_encoding;
+use crate::id3::v2::header::Id3v2Version;
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
 use crate::util::text::{decode_text, encode_text, TextDecodeOptions, TextEncoding};
 
-use std::io::{Cursor, Read};
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
 
-const FRAME_ID: FrameId<'static> = FrameId::Valid(std::borrow::Cow::Borrowed("GEOB"));
+use byteorder::ReadBytesExt;
 
-/// Allows for encapsulation of any file type inside an ID3v2 tag
-#[derive(PartialEq, Clone, Debug, Eq, Hash)]
-pub struct GeneralEncapsulatedObject<'a> {
+const FRAME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("GEOB"));
+
+/// An `ID3v2` general encapsulated object frame
+///
+/// This allows for encapsulation of any file type inside an ID3v2 tag, in the `GEOB` frame.
+/// The frames are told apart by descriptions, rather than their [`FrameId`]s.
+/// This means for each `GeneralObjectFrame` in the tag, the description must be unique.
+#[derive(Clone, Debug, Eq)]
+pub struct GeneralObjectFrame<'a> {
 	pub(crate) header: FrameHeader<'a>,
 	/// The text encoding of `file_name` and `description`
 	pub encoding: TextEncoding,
@@ -16,19 +27,31 @@ pub struct GeneralEncapsulatedObject<'a> {
 	pub mime_type: Option<String>,
 	/// The file's name
 	pub file_name: Option<String>,
-	/// A unique content descriptor
-	pub descriptor: Option<String>,
+	/// Unique content description
+	pub description: String,
 	/// The file's content
 	pub data: Vec<u8>,
 }
 
-impl GeneralEncapsulatedObject<'_> {
-	/// Create a new [`GeneralEncapsulatedObject`]
+impl PartialEq for GeneralObjectFrame<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		self.description == other.description
+	}
+}
+
+impl Hash for GeneralObjectFrame<'_> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.description.hash(state);
+	}
+}
+
+impl GeneralObjectFrame<'_> {
+	/// Create a new [`GeneralObjectFrame`]
 	pub fn new(
 		encoding: TextEncoding,
 		mime_type: Option<String>,
 		file_name: Option<String>,
-		descriptor: Option<String>,
+		description: String,
 		data: Vec<u8>,
 	) -> Self {
 		let header = FrameHeader::new(FRAME_ID, FrameFlags::default());
@@ -37,7 +60,7 @@ impl GeneralEncapsulatedObject<'_> {
 			encoding,
 			mime_type,
 			file_name,
-			descriptor,
+			description,
 			data,
 		}
 	}
@@ -57,54 +80,80 @@ impl GeneralEncapsulatedObject<'_> {
 		self.header.flags = flags;
 	}
 
-	/// Read a [`GeneralEncapsulatedObject`] from a slice
+	/// Read a [`GeneralObjectFrame`] from a slice
 	///
 	/// NOTE: This expects the frame header to have already been skipped
 	///
 	/// # Errors
 	///
-	/// This function will return an error if at any point it's unable to parse the data
-	pub fn parse(data: &[u8], frame_flags: FrameFlags) -> Result<Self> {
-		if data.len() < 4 {
-			return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
-		}
-
-		let encoding = TextEncoding::from_u8(data[0])
-			.ok_or_else(|| LoftyError::new(ErrorKind::TextDecode("Found invalid encoding")))?;
-
-		let mut cursor = Cursor::new(&data[1..]);
+	/// * Unable to decode the text
+	///
+	/// ID3v2.2:
+	///
+	/// * The encoding is not [`TextEncoding::Latin1`] or [`TextEncoding::UTF16`]
+	pub fn parse<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, frame_flags, version, TextDecodeFallback::None)
+	}
 
+	/// Same as [`GeneralObjectFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let Ok(encoding_byte) = reader.read_u8() else {
+			return Ok(None);
+		};
+
+		let encoding = verify_encoding(encoding_byte, version)?;
 		let mime_type = decode_text(
-			&mut cursor,
+			reader,
 			TextDecodeOptions::new()
 				.encoding(TextEncoding::Latin1)
 				.terminated(true),
 		)?;
 
-		let text_decode_options = TextDecodeOptions::new().encoding(encoding).terminated(true);
+		let text_decode_options = TextDecodeOptions::new()
+			.encoding(encoding)
+			.terminated(true)
+			.fallback(fallback);
 
-		let file_name = decode_text(&mut cursor, text_decode_options)?;
-		let descriptor = decode_text(&mut cursor, text_decode_options)?;
+		let file_name = decode_text(reader, text_decode_options)?;
+		let description = decode_text(reader, text_decode_options)?.content;
 
 		let mut data = Vec::new();
-		cursor.read_to_end(&mut data)?;
+		reader.read_to_end(&mut data)?;
 
 		let header = FrameHeader::new(FRAME_ID, frame_flags);
-		Ok(Self {
+		Ok(Some(GeneralObjectFrame {
 			header,
 			encoding,
 			mime_type: mime_type.text_or_none(),
 			file_name: file_name.text_or_none(),
-			descriptor: descriptor.text_or_none(),
+			description,
 			data,
-		})
+		}))
 	}
 
-	/// Convert a [`GeneralEncapsulatedObject`] into an ID3v2 GEOB frame byte Vec
+	/// Convert a [`GeneralObjectFrame`] to a byte vec
 	///
 	/// NOTE: This does not include a frame header
-	pub fn as_bytes(&self) -> Vec<u8> {
-		let encoding = self.encoding;
+	pub fn as_bytes(&self, is_id3v23: bool) -> Vec<u8> {
+		let mut encoding = self.encoding;
+		if is_id3v23 {
+			encoding = encoding.to_id3v23();
+		}
 
 		let mut bytes = vec![encoding as u8];
 
@@ -116,9 +165,7 @@ impl GeneralEncapsulatedObject<'_> {
 
 		let file_name = self.file_name.as_deref();
 		bytes.extend(&*encode_text(file_name.unwrap_or(""), encoding, true));
-
-		let descriptor = self.descriptor.as_deref();
-		bytes.extend(&*encode_text(descriptor.unwrap_or(""), encoding, true));
+		bytes.extend(&*encode_text(&self.description, encoding, true));
 
 		bytes.extend(&self.data);
 
@@ -128,16 +175,16 @@ impl GeneralEncapsulatedObject<'_> {
 
 #[cfg(test)]
 mod tests {
-	use crate::id3::v2::{FrameFlags, FrameHeader, GeneralEncapsulatedObject};
+	use crate::id3::v2::{FrameFlags, FrameHeader, GeneralObjectFrame, Id3v2Version};
 	use crate::util::text::TextEncoding;
 
-	fn expected() -> GeneralEncapsulatedObject<'static> {
-		GeneralEncapsulatedObject {
+	fn expected() -> GeneralObjectFrame<'static> {
+		GeneralObjectFrame {
 			header: FrameHeader::new(super::FRAME_ID, FrameFlags::default()),
 			encoding: TextEncoding::Latin1,
 			mime_type: Some(String::from("audio/mpeg")),
 			file_name: Some(String::from("a.mp3")),
-			descriptor: Some(String::from("Test Asset")),
+			description: String::from("Test Asset"),
 			data: crate::tag::utils::test_utils::read_path(
 				"tests/files/assets/minimal/full_test.mp3",
 			),
@@ -150,16 +197,23 @@ mod tests {
 
 		let cont = crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.geob");
 
-		let parsed_geob = GeneralEncapsulatedObject::parse(&cont, FrameFlags::default()).unwrap();
+		let parsed_geob =
+			GeneralObjectFrame::parse(&mut &cont[..], FrameFlags::default(), Id3v2Version::V4)
+				.unwrap()
+				.unwrap();
 
-		assert_eq!(parsed_geob, expected);
+		assert_eq!(parsed_geob.encoding, expected.encoding);
+		assert_eq!(parsed_geob.mime_type, expected.mime_type);
+		assert_eq!(parsed_geob.file_name, expected.file_name);
+		assert_eq!(parsed_geob.description, expected.description);
+		assert_eq!(parsed_geob.data, expected.data);
 	}
 
 	#[test_log::test]
 	fn geob_encode() {
 		let to_encode = expected();
 
-		let encoded = to_encode.as_bytes();
+		let encoded = to_encode.as_bytes(false);
 
 		let expected_bytes =
 			crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.geob");
@@ -1,3 +1,4 @@
+use crate::config::ParsingMode;
 use crate::error::{Id3v2Error, Id3v2ErrorKind, Result};
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId, TimestampFormat};
 
@@ -69,14 +70,16 @@ pub enum EventType {
 	AudioEnd = 0xFD,
 	AudioFileEnds = 0xFE,
 
-	/// 0x17..=0xDF and 0xF0..=0xFC
-	Reserved,
+	/// Any other event type (0x17..=0xDF and 0xF0..=0xFC)
+	///
+	/// These are reserved for future use, and are kept as-is.
+	Other(u8),
 }
 
 impl EventType {
 	/// Get a [`EventType`] from a `u8`
 	///
-	/// NOTE: 0x17..=0xDF and 0xF0..=0xFC map to [`EventType::Reserved`]
+	/// NOTE: 0x17..=0xDF and 0xF0..=0xFC map to [`EventType::Other`]
 	///
 	/// # Examples
 	///
@@ -91,7 +94,8 @@ impl EventType {
 	///
 	/// // This is in the undefined range
 	/// let invalid_byte = 0x17;
-	/// assert_eq!(EventType::from_u8(invalid_byte), EventType::Reserved);
+	/// assert_eq!(EventType::from_u8(invalid_byte), EventType::Other(0x17));
+	/// assert_eq!(EventType::from_u8(invalid_byte).as_u8(), invalid_byte);
 	/// ```
 	pub fn from_u8(byte: u8) -> Self {
 		match byte {
@@ -141,7 +145,69 @@ impl EventType {
 			0xFE => Self::AudioFileEnds,
 
 			// 0x17..=0xDF and 0xF0..=0xFC
-			_ => Self::Reserved,
+			_ => Self::Other(byte),
+		}
+	}
+
+	/// Get the `u8` representation of an [`EventType`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::EventType;
+	///
+	/// assert_eq!(EventType::KeyChange.as_u8(), 0x0B);
+	/// assert_eq!(EventType::Other(0xF0).as_u8(), 0xF0);
+	/// ```
+	pub fn as_u8(self) -> u8 {
+		match self {
+			Self::Padding => 0x00,
+			Self::EndOfInitialSilence => 0x01,
+			Self::IntroStart => 0x02,
+			Self::MainPartStart => 0x03,
+			Self::OutroStart => 0x04,
+			Self::OutroEnd => 0x05,
+			Self::VerseStart => 0x06,
+			Self::RefrainStart => 0x07,
+			Self::InterludeStart => 0x08,
+			Self::ThemeStart => 0x09,
+			Self::VariationStart => 0x0A,
+			Self::KeyChange => 0x0B,
+			Self::TimeChange => 0x0C,
+			Self::MomentaryUnwantedNoise => 0x0D,
+			Self::SustainedNoise => 0x0E,
+			Self::SustainedNoiseEnd => 0x0F,
+			Self::IntroEnd => 0x10,
+			Self::MainPartEnd => 0x11,
+			Self::VerseEnd => 0x12,
+			Self::RefrainEnd => 0x13,
+			Self::ThemeEnd => 0x14,
+			Self::Profanity => 0x15,
+			Self::ProfanityEnd => 0x16,
+
+			// User-defined events
+			Self::NotPredefinedSynch0 => 0xE0,
+			Self::NotPredefinedSynch1 => 0xE1,
+			Self::NotPredefinedSynch2 => 0xE2,
+			Self::NotPredefinedSynch3 => 0xE3,
+			Self::NotPredefinedSynch4 => 0xE4,
+			Self::NotPredefinedSynch5 => 0xE5,
+			Self::NotPredefinedSynch6 => 0xE6,
+			Self::NotPredefinedSynch7 => 0xE7,
+			Self::NotPredefinedSynch8 => 0xE8,
+			Self::NotPredefinedSynch9 => 0xE9,
+			Self::NotPredefinedSynchA => 0xEA,
+			Self::NotPredefinedSynchB => 0xEB,
+			Self::NotPredefinedSynchC => 0xEC,
+			Self::NotPredefinedSynchD => 0xED,
+			Self::NotPredefinedSynchE => 0xEE,
+			Self::NotPredefinedSynchF => 0xEF,
+
+			Self::AudioEnd => 0xFD,
+			Self::AudioFileEnds => 0xFE,
+
+			// 0x17..=0xDF and 0xF0..=0xFC
+			Self::Other(byte) => byte,
 		}
 	}
 }
@@ -182,6 +248,8 @@ pub struct EventTimingCodesFrame<'a> {
 	///
 	/// Events are guaranteed to be sorted by their timestamps when read. They can be inserted in
 	/// arbitrary order after the fact, and will be sorted again prior to writing.
+	///
+	/// NOTE: Events with the same timestamp keep their relative order.
 	pub events: Vec<Event>,
 }
 
@@ -215,10 +283,18 @@ impl EventTimingCodesFrame<'_> {
 	///
 	/// NOTE: This expects the frame header to have already been skipped
 	///
+	/// With [`ParsingMode::Strict`], events must already be in chronological order. Otherwise, they
+	/// will be sorted.
+	///
 	/// # Errors
 	///
 	/// * Invalid timestamp format
-	pub fn parse<R>(reader: &mut R, frame_flags: FrameFlags) -> Result<Option<Self>>
+	/// * Events are out of order, with [`ParsingMode::Strict`]
+	pub fn parse<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		parse_mode: ParsingMode,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -240,8 +316,16 @@ impl EventTimingCodesFrame<'_> {
 			})
 		}
 
-		// Order is important, can't use sort_unstable
-		events.sort();
+		if !events.is_sorted() {
+			if parse_mode == ParsingMode::Strict {
+				return Err(Id3v2Error::new(Id3v2ErrorKind::UnsortedEtcoEvents).into());
+			}
+
+			log::warn!("ETCO frame events are out of order, sorting");
+
+			// Order is important, can't use sort_unstable
+			events.sort();
+		}
 
 		let header = FrameHeader::new(FRAME_ID, frame_flags);
 		Ok(Some(EventTimingCodesFrame {
@@ -261,7 +345,7 @@ impl EventTimingCodesFrame<'_> {
 		sorted_events.sort();
 
 		for event in sorted_events {
-			content.push(event.event_type as u8);
+			content.push(event.event_type.as_u8());
 			content.extend(event.timestamp.to_be_bytes())
 		}
 
@@ -271,6 +355,7 @@ impl EventTimingCodesFrame<'_> {
 
 #[cfg(test)]
 mod tests {
+	use crate::config::ParsingMode;
 	use crate::id3::v2::{
 		Event, EventTimingCodesFrame, EventType, FrameFlags, FrameHeader, FrameId, TimestampFormat,
 	};
@@ -311,9 +396,13 @@ mod tests {
 	fn etco_decode() {
 		let cont = crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.etco");
 
-		let parsed_etco = EventTimingCodesFrame::parse(&mut &cont[..], FrameFlags::default())
-			.unwrap()
-			.unwrap();
+		let parsed_etco = EventTimingCodesFrame::parse(
+			&mut &cont[..],
+			FrameFlags::default(),
+			ParsingMode::Strict,
+		)
+		.unwrap()
+		.unwrap();
 
 		assert_eq!(parsed_etco, expected());
 	}
@@ -327,4 +416,58 @@ mod tests {
 
 		assert_eq!(encoded, expected_bytes);
 	}
+
+	#[test_log::test]
+	fn etco_reserved_event_type() {
+		// Timestamp format, then a reserved event type (0x17) at 1000ms, and another (0xF0) at 2000ms
+		let cont = [2, 0x17, 0, 0, 0x03, 0xE8, 0xF0, 0, 0, 0x07, 0xD0];
+
+		let parsed_etco = EventTimingCodesFrame::parse(
+			&mut &cont[..],
+			FrameFlags::default(),
+			ParsingMode::Strict,
+		)
+		.unwrap()
+		.unwrap();
+
+		assert_eq!(parsed_etco.events[0].event_type, EventType::Other(0x17));
+		assert_eq!(parsed_etco.events[1].event_type, EventType::Other(0xF0));
+		assert_eq!(parsed_etco.as_bytes(), cont);
+	}
+
+	#[test_log::test]
+	fn etco_out_of_order() {
+		// Timestamp format, then an intro end at 2000ms followed by an intro start at 1000ms
+		let cont = [2, 0x10, 0, 0, 0x07, 0xD0, 0x02, 0, 0, 0x03, 0xE8];
+
+		let err = EventTimingCodesFrame::parse(
+			&mut &cont[..],
+			FrameFlags::default(),
+			ParsingMode::Strict,
+		)
+		.unwrap_err();
+		assert!(err.to_string().contains("out of order"));
+
+		let parsed_etco = EventTimingCodesFrame::parse(
+			&mut &cont[..],
+			FrameFlags::default(),
+			ParsingMode::BestAttempt,
+		)
+		.unwrap()
+		.unwrap();
+
+		assert_eq!(
+			parsed_etco.events,
+			vec![
+				Event {
+					event_type: EventType::IntroStart,
+					timestamp: 1000,
+				},
+				Event {
+					event_type: EventType::IntroEnd,
+					timestamp: 2000,
+				},
+			]
+		);
+	}
 }
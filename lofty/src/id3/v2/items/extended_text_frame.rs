@@ -1,8 +1,9 @@
-use crate::error::{Id3v2Error, Id3v2ErrorKind, LoftyError, Result};
+use crate::config::TextDecodeFallback;
+use crate::error::{Id3v2Error, Id3v2ErrorKind, LoftyError, ParseDiagnostic, Result};
 use crate::id3::v2::frame::content::verify_encoding;
 use crate::id3::v2::header::Id3v2Version;
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
-use crate::macros::err;
+use crate::util::diagnostics;
 use crate::util::text::{
 	decode_text, encode_text, utf16_decode_bytes, TextDecodeOptions, TextEncoding,
 };
@@ -87,6 +88,19 @@ impl ExtendedTextFrame<'_> {
 		frame_flags: FrameFlags,
 		version: Id3v2Version,
 	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, frame_flags, version, TextDecodeFallback::None)
+	}
+
+	/// Same as [`ExtendedTextFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -97,13 +111,21 @@ impl ExtendedTextFrame<'_> {
 		let encoding = verify_encoding(encoding_byte, version)?;
 		let description = decode_text(
 			reader,
-			TextDecodeOptions::new().encoding(encoding).terminated(true),
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.terminated(true)
+				.fallback(fallback),
 		)?;
 
 		let frame_content;
 		if encoding != TextEncoding::UTF16 {
-			frame_content =
-				decode_text(reader, TextDecodeOptions::new().encoding(encoding))?.content;
+			frame_content = decode_text(
+				reader,
+				TextDecodeOptions::new()
+					.encoding(encoding)
+					.fallback(fallback),
+			)?
+			.content;
 
 			let header = FrameHeader::new(FRAME_ID, frame_flags);
 			return Ok(Some(ExtendedTextFrame {
@@ -140,9 +162,12 @@ impl ExtendedTextFrame<'_> {
 					frame_content = String::new();
 					break 'utf16;
 				},
+				// Neither the description nor the text specify a BOM, assume little-endian like
+				// `decode_text` does
 				[0x00, 0x00] => {
-					debug_assert!(description.content.is_empty());
-					err!(TextDecode("UTF-16 string has no BOM"));
+					log::warn!("UTF-16 string has no byte order mark, assuming little-endian");
+					diagnostics::report(ParseDiagnostic::MissingUtf16Bom);
+					u16::from_le_bytes
 				},
 				[0xFF, 0xFE] => u16::from_le_bytes,
 				[0xFE, 0xFF] => u16::from_be_bytes,
@@ -150,8 +175,10 @@ impl ExtendedTextFrame<'_> {
 				_ => unreachable!(),
 			};
 
-			frame_content = utf16_decode_bytes(&raw_text, endianness).map_err(|_| {
-				Into::<LoftyError>::into(Id3v2Error::new(Id3v2ErrorKind::BadSyncText))
+			frame_content = utf16_decode_bytes(&raw_text, endianness).map_err(|e| {
+				Into::<LoftyError>::into(
+					Id3v2Error::new(Id3v2ErrorKind::BadSyncText).with_source(e),
+				)
 			})?;
 		}
 
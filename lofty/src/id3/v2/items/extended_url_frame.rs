@@ -1,3 +1,4 @@
+use crate::config::TextDecodeFallback;
 use crate::error::Result;
 use crate::id3::v2::frame::content::verify_encoding;
 use crate::id3::v2::header::Id3v2Version;
@@ -84,6 +85,19 @@ impl ExtendedUrlFrame<'_> {
 		frame_flags: FrameFlags,
 		version: Id3v2Version,
 	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, frame_flags, version, TextDecodeFallback::None)
+	}
+
+	/// Same as [`ExtendedUrlFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -94,7 +108,10 @@ impl ExtendedUrlFrame<'_> {
 		let encoding = verify_encoding(encoding_byte, version)?;
 		let description = decode_text(
 			reader,
-			TextDecodeOptions::new().encoding(encoding).terminated(true),
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.terminated(true)
+				.fallback(fallback),
 		)?
 		.content;
 		let content = decode_text(
@@ -1,3 +1,4 @@
+use crate::config::TextDecodeFallback;
 use crate::error::Result;
 use crate::id3::v2::frame::content::verify_encoding;
 use crate::id3::v2::header::Id3v2Version;
@@ -65,6 +66,20 @@ impl<'a> KeyValueFrame<'a> {
 		frame_flags: FrameFlags,
 		version: Id3v2Version,
 	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, id, frame_flags, version, TextDecodeFallback::None)
+	}
+
+	/// Same as [`KeyValueFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		id: FrameId<'a>,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -76,7 +91,10 @@ impl<'a> KeyValueFrame<'a> {
 
 		let mut values = Vec::new();
 
-		let mut text_decode_options = TextDecodeOptions::new().encoding(encoding).terminated(true);
+		let mut text_decode_options = TextDecodeOptions::new()
+			.encoding(encoding)
+			.terminated(true)
+			.fallback(fallback);
 
 		// We have to read the first key/value pair separately because it may be the only string with a BOM
 
@@ -98,7 +116,9 @@ impl<'a> KeyValueFrame<'a> {
 		loop {
 			let key = decode_text(reader, text_decode_options)?;
 			let value = decode_text(reader, text_decode_options)?;
-			if key.bytes_read == 0 || value.bytes_read == 0 {
+
+			// A key without a value is kept, as long as it isn't just trailing padding
+			if key.bytes_read == 0 || (value.bytes_read == 0 && key.content.is_empty()) {
 				break;
 			}
 
@@ -1,3 +1,4 @@
+use crate::config::TextDecodeFallback;
 use crate::error::{Id3v2Error, Id3v2ErrorKind, Result};
 use crate::id3::v2::frame::content::verify_encoding;
 use crate::id3::v2::header::Id3v2Version;
@@ -22,7 +23,11 @@ struct LanguageFrame {
 }
 
 impl LanguageFrame {
-	fn parse<R>(reader: &mut R, version: Id3v2Version) -> Result<Option<Self>>
+	fn parse<R>(
+		reader: &mut R,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -37,10 +42,19 @@ impl LanguageFrame {
 
 		let description = decode_text(
 			reader,
-			TextDecodeOptions::new().encoding(encoding).terminated(true),
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.terminated(true)
+				.fallback(fallback),
+		)?
+		.content;
+		let content = decode_text(
+			reader,
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.fallback(fallback),
 		)?
 		.content;
-		let content = decode_text(reader, TextDecodeOptions::new().encoding(encoding))?.content;
 
 		Ok(Some(Self {
 			encoding,
@@ -158,7 +172,20 @@ impl CommentFrame<'_> {
 	where
 		R: Read,
 	{
-		let Some(language_frame) = LanguageFrame::parse(reader, version)? else {
+		Self::parse_with_fallback(reader, frame_flags, version, TextDecodeFallback::None)
+	}
+
+	/// Same as [`CommentFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let Some(language_frame) = LanguageFrame::parse(reader, version, fallback)? else {
 			return Ok(None);
 		};
 
@@ -274,7 +301,20 @@ impl UnsynchronizedTextFrame<'_> {
 	where
 		R: Read,
 	{
-		let Some(language_frame) = LanguageFrame::parse(reader, version)? else {
+		Self::parse_with_fallback(reader, frame_flags, version, TextDecodeFallback::None)
+	}
+
+	/// Same as [`UnsynchronizedTextFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let Some(language_frame) = LanguageFrame::parse(reader, version, fallback)? else {
 			return Ok(None);
 		};
 
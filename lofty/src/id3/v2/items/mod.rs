@@ -1,17 +1,22 @@
 mod attached_picture_frame;
 mod audio_text_frame;
 mod binary_frame;
+mod chapter_frame;
+mod commercial_frame;
 mod encapsulated_object;
 mod event_timing_codes_frame;
 mod extended_text_frame;
 mod extended_url_frame;
 mod key_value_frame;
 pub(in crate::id3::v2) mod language_frame;
+mod music_cd_identifier_frame;
 mod ownership_frame;
+mod play_counter_frame;
 mod popularimeter;
 mod private_frame;
 mod relative_volume_adjustment_frame;
 mod sync_text;
+mod table_of_contents_frame;
 mod text_information_frame;
 mod timestamp_frame;
 mod unique_file_identifier;
@@ -20,19 +25,24 @@ mod url_link_frame;
 pub use attached_picture_frame::AttachedPictureFrame;
 pub use audio_text_frame::{scramble, AudioTextFrame, AudioTextFrameFlags};
 pub use binary_frame::BinaryFrame;
-pub use encapsulated_object::GeneralEncapsulatedObject;
+pub use chapter_frame::ChapterFrame;
+pub use commercial_frame::{CommercialFrame, ReceivedAs};
+pub use encapsulated_object::GeneralObjectFrame;
 pub use event_timing_codes_frame::{Event, EventTimingCodesFrame, EventType};
 pub use extended_text_frame::ExtendedTextFrame;
 pub use extended_url_frame::ExtendedUrlFrame;
 pub use key_value_frame::KeyValueFrame;
 pub use language_frame::{CommentFrame, UnsynchronizedTextFrame};
+pub use music_cd_identifier_frame::{CdTableOfContents, MusicCdIdentifierFrame};
 pub use ownership_frame::OwnershipFrame;
+pub use play_counter_frame::PlayCounterFrame;
 pub use popularimeter::PopularimeterFrame;
 pub use private_frame::PrivateFrame;
 pub use relative_volume_adjustment_frame::{
 	ChannelInformation, ChannelType, RelativeVolumeAdjustmentFrame,
 };
 pub use sync_text::{SyncTextContentType, SynchronizedTextFrame, TimestampFormat};
+pub use table_of_contents_frame::TableOfContentsFrame;
 pub use text_information_frame::TextInformationFrame;
 pub use timestamp_frame::TimestampFrame;
 pub use unique_file_identifier::UniqueFileIdentifierFrame;
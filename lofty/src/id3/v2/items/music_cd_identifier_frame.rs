@@ -0,0 +1,229 @@
+use crate::error::Result;
+use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
+
+use std::borrow::Cow;
+use std::io::Read;
+
+const FRAME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("MCDI"));
+
+// The track number of the lead-out descriptor
+const LEAD_OUT_TRACK: u8 = 0xAA;
+// The size of a single track descriptor
+const TRACK_DESCRIPTOR_SIZE: usize = 8;
+
+/// An `ID3v2` music CD identifier frame
+///
+/// This holds the table of contents of the CD the audio was taken from, which can be used to
+/// identify the disc (e.g. to compute a [MusicBrainz disc ID](https://musicbrainz.org/doc/Disc_ID_Calculation)).
+///
+/// The data is kept as-is, and is written back untouched. See
+/// [`MusicCdIdentifierFrame::table_of_contents`] to parse it.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MusicCdIdentifierFrame<'a> {
+	pub(crate) header: FrameHeader<'a>,
+	/// The raw table of contents
+	pub data: Vec<u8>,
+}
+
+/// A CD table of contents, read from a [`MusicCdIdentifierFrame`]
+///
+/// NOTE: All offsets are logical block addresses, and do not include the 2 second (150 sector)
+///       lead-in. This needs to be added when computing a MusicBrainz disc ID.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CdTableOfContents {
+	/// The number of the first track
+	pub first_track: u8,
+	/// The number of the last track
+	pub last_track: u8,
+	/// The start offsets of each track, from `first_track` to `last_track`
+	pub track_offsets: Vec<u32>,
+	/// The offset of the lead-out, the end of the last track
+	pub lead_out_offset: u32,
+}
+
+impl MusicCdIdentifierFrame<'_> {
+	/// Create a new [`MusicCdIdentifierFrame`]
+	pub fn new(data: Vec<u8>) -> Self {
+		let header = FrameHeader::new(FRAME_ID, FrameFlags::default());
+		Self { header, data }
+	}
+
+	/// Get the ID for the frame
+	pub fn id(&self) -> FrameId<'_> {
+		FRAME_ID
+	}
+
+	/// Get the flags for the frame
+	pub fn flags(&self) -> FrameFlags {
+		self.header.flags
+	}
+
+	/// Set the flags for the frame
+	pub fn set_flags(&mut self, flags: FrameFlags) {
+		self.header.flags = flags;
+	}
+
+	/// Parse the table of contents
+	///
+	/// This expects the data to be a CD-ROM `READ TOC` response, as described in the
+	/// MMC specification, with the track addresses in LBA format.
+	///
+	/// This will return `None` if the data doesn't match that structure. Some software will write
+	/// the table of contents in other formats, which can still be accessed through
+	/// [`MusicCdIdentifierFrame::data`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::MusicCdIdentifierFrame;
+	///
+	/// // A single track, starting at 0, with the lead-out at 15000
+	/// let data = vec![
+	/// 	0x00, 0x12, 0x01, 0x01, // Header
+	/// 	0x00, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // Track 1
+	/// 	0x00, 0x10, 0xAA, 0x00, 0x00, 0x00, 0x3A, 0x98, // Lead-out
+	/// ];
+	///
+	/// let mcdi = MusicCdIdentifierFrame::new(data);
+	/// let toc = mcdi.table_of_contents().expect("should be a valid TOC");
+	///
+	/// assert_eq!(toc.first_track, 1);
+	/// assert_eq!(toc.last_track, 1);
+	/// assert_eq!(toc.track_offsets, vec![0]);
+	/// assert_eq!(toc.lead_out_offset, 15000);
+	/// ```
+	pub fn table_of_contents(&self) -> Option<CdTableOfContents> {
+		let [len_hi, len_lo, first_track, last_track, descriptors @ ..] = &self.data[..] else {
+			return None;
+		};
+
+		// The stated length doesn't include the length field itself
+		let stated_len = usize::from(u16::from_be_bytes([*len_hi, *len_lo]));
+		if stated_len != self.data.len() - 2 {
+			log::debug!("MCDI: Stated TOC length doesn't match the frame size");
+			return None;
+		}
+
+		if *first_track == 0 || first_track > last_track {
+			return None;
+		}
+
+		// One descriptor per track, and one for the lead-out
+		let track_count = usize::from(last_track - first_track) + 1;
+		if descriptors.len() != (track_count + 1) * TRACK_DESCRIPTOR_SIZE {
+			return None;
+		}
+
+		let mut track_offsets = Vec::with_capacity(track_count);
+		let mut lead_out_offset = None;
+		for (expected_number, descriptor) in (*first_track..=*last_track)
+			.chain(std::iter::once(LEAD_OUT_TRACK))
+			.zip(descriptors.chunks_exact(TRACK_DESCRIPTOR_SIZE))
+		{
+			// Reserved, ADR/control, track number, reserved, then the address
+			let [_, _, number, _, address @ ..] = descriptor else {
+				unreachable!("chunks are exactly 8 bytes")
+			};
+
+			if *number != expected_number {
+				log::debug!("MCDI: Expected track {expected_number}, found {number}");
+				return None;
+			}
+
+			let offset = u32::from_be_bytes([address[0], address[1], address[2], address[3]]);
+			if expected_number == LEAD_OUT_TRACK {
+				lead_out_offset = Some(offset);
+			} else {
+				track_offsets.push(offset);
+			}
+		}
+
+		Some(CdTableOfContents {
+			first_track: *first_track,
+			last_track: *last_track,
+			track_offsets,
+			lead_out_offset: lead_out_offset?,
+		})
+	}
+
+	/// Read a [`MusicCdIdentifierFrame`]
+	///
+	/// NOTE: This expects the frame header to have already been skipped
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	pub fn parse<R>(reader: &mut R, frame_flags: FrameFlags) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let mut data = Vec::new();
+		reader.read_to_end(&mut data)?;
+
+		if data.is_empty() {
+			return Ok(None);
+		}
+
+		let header = FrameHeader::new(FRAME_ID, frame_flags);
+		Ok(Some(MusicCdIdentifierFrame { header, data }))
+	}
+
+	/// Convert a [`MusicCdIdentifierFrame`] to a byte vec
+	pub fn as_bytes(&self) -> Vec<u8> {
+		self.data.clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::id3::v2::{CdTableOfContents, FrameFlags, MusicCdIdentifierFrame};
+
+	fn toc_bytes() -> Vec<u8> {
+		vec![
+			0x00, 0x1A, 0x01, 0x02, // Header
+			0x00, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // Track 1
+			0x00, 0x10, 0x02, 0x00, 0x00, 0x00, 0x55, 0xF0, // Track 2
+			0x00, 0x10, 0xAA, 0x00, 0x00, 0x00, 0xAB, 0xE0, // Lead-out
+		]
+	}
+
+	#[test_log::test]
+	fn mcdi_toc() {
+		let parsed = MusicCdIdentifierFrame::parse(&mut &toc_bytes()[..], FrameFlags::default())
+			.unwrap()
+			.unwrap();
+
+		assert_eq!(
+			parsed.table_of_contents(),
+			Some(CdTableOfContents {
+				first_track: 1,
+				last_track: 2,
+				track_offsets: vec![0, 22000],
+				lead_out_offset: 44000,
+			})
+		);
+		assert_eq!(parsed.as_bytes(), toc_bytes());
+	}
+
+	#[test_log::test]
+	fn mcdi_malformed_toc() {
+		// Wrong stated length
+		let mut data = toc_bytes();
+		data[1] = 0x20;
+		assert_eq!(MusicCdIdentifierFrame::new(data).table_of_contents(), None);
+
+		// Missing lead-out
+		let mut data = toc_bytes();
+		data.truncate(20);
+		data[1] = 0x12;
+		assert_eq!(MusicCdIdentifierFrame::new(data).table_of_contents(), None);
+
+		// Not a TOC at all (e.g. written as text), but still kept as-is
+		let data = b"1+2+AB82+96+55F0".to_vec();
+		let parsed = MusicCdIdentifierFrame::parse(&mut &data[..], FrameFlags::default())
+			.unwrap()
+			.unwrap();
+		assert_eq!(parsed.table_of_contents(), None);
+		assert_eq!(parsed.as_bytes(), data);
+	}
+}
@@ -1,8 +1,7 @@
+use crate::config::{ParsingMode, TextDecodeFallback};
 use crate::error::{ErrorKind, Id3v2Error, Id3v2ErrorKind, LoftyError, Result};
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
-use crate::util::text::{
-	decode_text, encode_text, utf8_decode_str, TextDecodeOptions, TextEncoding,
-};
+use crate::util::text::{decode_text, encode_text, latin1_decode, TextDecodeOptions, TextEncoding};
 
 use std::borrow::Cow;
 use std::hash::Hash;
@@ -28,6 +27,8 @@ pub struct OwnershipFrame<'a> {
 	/// as a numerical string using ”.” as the decimal separator.
 	pub price_paid: String,
 	/// The date of purchase as an 8 character date string (YYYYMMDD)
+	///
+	/// NOTE: With [`ParsingMode::Relaxed`], malformed dates are kept as-is, and may not follow this format.
 	pub date_of_purchase: String,
 	/// The seller name
 	pub seller: String,
@@ -70,11 +71,33 @@ impl OwnershipFrame<'_> {
 	///
 	/// NOTE: This expects the frame header to have already been skipped
 	///
+	/// The date of purchase is expected to be 8 digits (YYYYMMDD). Any other date is an error with
+	/// [`ParsingMode::Strict`]. Otherwise, it is kept as-is, unless it is cut short with
+	/// [`ParsingMode::BestAttempt`].
+	///
 	/// # Errors
 	///
 	/// * Invalid text encoding
 	/// * Not enough data
-	pub fn parse<R>(reader: &mut R, frame_flags: FrameFlags) -> Result<Option<Self>>
+	/// * Malformed date of purchase, depending on the [`ParsingMode`]
+	pub fn parse<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		parse_mode: ParsingMode,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, frame_flags, parse_mode, TextDecodeFallback::None)
+	}
+
+	/// Same as [`OwnershipFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		parse_mode: ParsingMode,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -92,12 +115,29 @@ impl OwnershipFrame<'_> {
 		)?
 		.content;
 
-		let mut date_bytes = [0u8; 8];
-		reader.read_exact(&mut date_bytes)?;
+		let mut date_bytes = Vec::with_capacity(8);
+		reader.by_ref().take(8).read_to_end(&mut date_bytes)?;
 
-		let date_of_purchase = utf8_decode_str(&date_bytes)?.to_owned();
+		let is_truncated = date_bytes.len() < 8;
+		if is_truncated || !date_bytes.iter().all(u8::is_ascii_digit) {
+			if parse_mode == ParsingMode::Strict
+				|| (is_truncated && parse_mode == ParsingMode::BestAttempt)
+			{
+				return Err(Id3v2Error::new(Id3v2ErrorKind::BadOwnershipDate).into());
+			}
 
-		let seller = decode_text(reader, TextDecodeOptions::new().encoding(encoding))?.content;
+			log::warn!("OWNE: Malformed date of purchase, keeping it as-is");
+		}
+
+		let date_of_purchase = latin1_decode(&date_bytes);
+
+		let seller = decode_text(
+			reader,
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.fallback(fallback),
+		)?
+		.content;
 
 		let header = FrameHeader::new(FRAME_ID, frame_flags);
 		Ok(Some(OwnershipFrame {
@@ -125,11 +165,15 @@ impl OwnershipFrame<'_> {
 		let mut bytes = vec![encoding as u8];
 
 		bytes.extend(encode_text(&self.price_paid, TextEncoding::Latin1, true));
-		if self.date_of_purchase.len() < 8 {
+		if self.date_of_purchase.chars().count() < 8 {
 			return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
 		}
 
-		bytes.extend(self.date_of_purchase.as_bytes().iter().take(8));
+		bytes.extend(
+			encode_text(&self.date_of_purchase, TextEncoding::Latin1, false)
+				.into_iter()
+				.take(8),
+		);
 		bytes.extend(encode_text(&self.seller, encoding, false));
 
 		Ok(bytes)
@@ -138,6 +182,7 @@ impl OwnershipFrame<'_> {
 
 #[cfg(test)]
 mod tests {
+	use crate::config::ParsingMode;
 	use crate::id3::v2::{FrameFlags, FrameHeader, FrameId, OwnershipFrame};
 	use crate::TextEncoding;
 
@@ -157,9 +202,10 @@ mod tests {
 	fn owne_decode() {
 		let cont = crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.owne");
 
-		let parsed_owne = OwnershipFrame::parse(&mut &cont[..], FrameFlags::default())
-			.unwrap()
-			.unwrap();
+		let parsed_owne =
+			OwnershipFrame::parse(&mut &cont[..], FrameFlags::default(), ParsingMode::Strict)
+				.unwrap()
+				.unwrap();
 
 		assert_eq!(parsed_owne, expected());
 	}
@@ -173,4 +219,44 @@ mod tests {
 
 		assert_eq!(encoded, expected_bytes);
 	}
+
+	#[test_log::test]
+	fn owne_malformed_date() {
+		// A date with separators, which is still 8 bytes long
+		let mut cont =
+			crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.owne");
+		cont[9..17].copy_from_slice(b"1984-4-7");
+
+		assert!(
+			OwnershipFrame::parse(&mut &cont[..], FrameFlags::default(), ParsingMode::Strict)
+				.is_err()
+		);
+
+		for parse_mode in [ParsingMode::BestAttempt, ParsingMode::Relaxed] {
+			let parsed_owne =
+				OwnershipFrame::parse(&mut &cont[..], FrameFlags::default(), parse_mode)
+					.unwrap()
+					.unwrap();
+
+			assert_eq!(parsed_owne.date_of_purchase, "1984-4-7");
+			assert_eq!(parsed_owne.seller, "FooBar");
+			assert_eq!(parsed_owne.as_bytes(false).unwrap(), cont);
+		}
+
+		// A date that is cut short, with no seller
+		let cont = &cont[..13];
+		for parse_mode in [ParsingMode::Strict, ParsingMode::BestAttempt] {
+			assert!(
+				OwnershipFrame::parse(&mut &cont[..], FrameFlags::default(), parse_mode).is_err()
+			);
+		}
+
+		let parsed_owne =
+			OwnershipFrame::parse(&mut &cont[..], FrameFlags::default(), ParsingMode::Relaxed)
+				.unwrap()
+				.unwrap();
+		assert_eq!(parsed_owne.price_paid, "USD1000");
+		assert_eq!(parsed_owne.date_of_purchase, "1984");
+		assert!(parsed_owne.seller.is_empty());
+	}
 }
@@ -0,0 +1,153 @@
+use crate::error::Result;
+use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
+
+use std::borrow::Cow;
+use std::io::Read;
+
+const FRAME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("PCNT"));
+
+/// An `ID3v2` play counter frame
+///
+/// This is the number of times the file has been played. For per-user counters, see
+/// [`PopularimeterFrame`](crate::id3::v2::PopularimeterFrame).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PlayCounterFrame<'a> {
+	pub(crate) header: FrameHeader<'a>,
+	/// The number of times the file has been played
+	///
+	/// The counter is stored with as many bytes as needed (at least 4). This is a `u64` for simplicity,
+	/// so larger counters will saturate at [`u64::MAX`] when read.
+	pub counter: u64,
+}
+
+impl PlayCounterFrame<'_> {
+	/// Create a new [`PlayCounterFrame`]
+	pub fn new(counter: u64) -> Self {
+		let header = FrameHeader::new(FRAME_ID, FrameFlags::default());
+		Self { header, counter }
+	}
+
+	/// Get the ID for the frame
+	pub fn id(&self) -> FrameId<'_> {
+		FRAME_ID
+	}
+
+	/// Get the flags for the frame
+	pub fn flags(&self) -> FrameFlags {
+		self.header.flags
+	}
+
+	/// Set the flags for the frame
+	pub fn set_flags(&mut self, flags: FrameFlags) {
+		self.header.flags = flags;
+	}
+
+	/// Read a [`PlayCounterFrame`]
+	///
+	/// NOTE: This expects the frame header to have already been skipped
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	pub fn parse<R>(reader: &mut R, frame_flags: FrameFlags) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let mut counter_content = Vec::new();
+		reader.read_to_end(&mut counter_content)?;
+
+		if counter_content.is_empty() {
+			return Ok(None);
+		}
+
+		let header = FrameHeader::new(FRAME_ID, frame_flags);
+		Ok(Some(Self {
+			header,
+			counter: read_counter(&counter_content, "PCNT"),
+		}))
+	}
+
+	/// Convert a [`PlayCounterFrame`] to a byte vec
+	///
+	/// NOTE: The counter will be written with at least 4 bytes, growing as needed.
+	pub fn as_bytes(&self) -> Vec<u8> {
+		let mut content = Vec::with_capacity(4);
+		write_counter(&mut content, self.counter);
+		content
+	}
+}
+
+// Reads a counter of any length, shared with `POPM`
+pub(super) fn read_counter(counter_content: &[u8], frame_id: &str) -> u64 {
+	// The counter can be any length, but it's only significant past any leading zeros
+	let counter_start = counter_content
+		.iter()
+		.position(|b| *b != 0)
+		.unwrap_or(counter_content.len());
+	let counter_content = &counter_content[counter_start..];
+
+	let remaining_size = counter_content.len();
+	if remaining_size > 8 {
+		log::warn!("{frame_id} counter is larger than 64 bits, saturating");
+		return u64::MAX;
+	}
+
+	let mut counter_bytes = [0; 8];
+	let counter_start_pos = 8 - remaining_size;
+
+	counter_bytes[counter_start_pos..].copy_from_slice(counter_content);
+	u64::from_be_bytes(counter_bytes)
+}
+
+// Writes a counter, shared with `POPM`
+pub(super) fn write_counter(content: &mut Vec<u8>, counter: u64) {
+	// When the counter reaches all one's, one byte is inserted in front of the counter
+	// thus making the counter eight bits bigger
+	//
+	// $xx xx xx xx (xx ...)
+	if let Ok(counter) = u32::try_from(counter) {
+		content.extend(counter.to_be_bytes())
+	} else {
+		let counter_bytes = counter.to_be_bytes();
+		let i = counter_bytes.iter().position(|b| *b != 0).unwrap_or(4);
+
+		content.extend(&counter_bytes[i..]);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::id3::v2::{FrameFlags, PlayCounterFrame};
+
+	#[test_log::test]
+	fn pcnt_decode() {
+		// A 5 byte counter, one past `u32::MAX`
+		let cont = crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.pcnt");
+
+		let parsed_pcnt = PlayCounterFrame::parse(&mut &cont[..], FrameFlags::default())
+			.unwrap()
+			.unwrap();
+
+		assert_eq!(parsed_pcnt, PlayCounterFrame::new(u64::from(u32::MAX) + 1));
+	}
+
+	#[test_log::test]
+	fn pcnt_encode() {
+		let encoded = PlayCounterFrame::new(u64::from(u32::MAX) + 1).as_bytes();
+
+		let expected_bytes =
+			crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.pcnt");
+
+		assert_eq!(encoded, expected_bytes);
+	}
+
+	#[test_log::test]
+	fn pcnt_minimum_size() {
+		assert_eq!(PlayCounterFrame::new(0).as_bytes(), [0; 4]);
+		assert_eq!(
+			PlayCounterFrame::new(u64::from(u32::MAX)).as_bytes(),
+			[0xFF; 4]
+		);
+		assert_eq!(PlayCounterFrame::new(u64::MAX).as_bytes(), [0xFF; 8]);
+	}
+}
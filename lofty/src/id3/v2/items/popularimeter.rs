@@ -1,3 +1,4 @@
+use super::play_counter_frame::{read_counter, write_counter};
 use crate::error::Result;
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
 use crate::util::alloc::VecFallibleCapacity;
@@ -27,7 +28,8 @@ pub struct PopularimeterFrame<'a> {
 	pub rating: u8,
 	/// A play counter for the user. It is to be incremented each time the file is played.
 	///
-	/// This is a `u64` for simplicity. It may change if it becomes an issue.
+	/// The counter is stored with as many bytes as needed (at least 4). This is a `u64` for simplicity,
+	/// so larger counters will saturate at [`u64::MAX`] when read.
 	pub counter: u64,
 }
 
@@ -91,17 +93,7 @@ impl PopularimeterFrame<'_> {
 		let mut counter_content = Vec::new();
 		reader.read_to_end(&mut counter_content)?;
 
-		let counter;
-		let remaining_size = counter_content.len();
-		if remaining_size > 8 {
-			counter = u64::MAX;
-		} else {
-			let mut counter_bytes = [0; 8];
-			let counter_start_pos = 8 - remaining_size;
-
-			counter_bytes[counter_start_pos..].copy_from_slice(&counter_content);
-			counter = u64::from_be_bytes(counter_bytes);
-		}
+		let counter = read_counter(&counter_content, "POPM");
 
 		let header = FrameHeader::new(FRAME_ID, frame_flags);
 		Ok(Self {
@@ -124,18 +116,8 @@ impl PopularimeterFrame<'_> {
 		content.extend(encode_text(self.email.as_str(), TextEncoding::Latin1, true));
 		content.push(self.rating);
 
-		// When the counter reaches all one's, one byte is inserted in front of the counter
-		// thus making the counter eight bits bigger in the same away as the play counter ("PCNT")
-		//
-		// $xx xx xx xx (xx ...)
-		if let Ok(counter) = u32::try_from(self.counter) {
-			content.extend(counter.to_be_bytes())
-		} else {
-			let counter_bytes = self.counter.to_be_bytes();
-			let i = counter_bytes.iter().position(|b| *b != 0).unwrap_or(4);
-
-			content.extend(&counter_bytes[i..]);
-		}
+		// The counter grows in the same way as the play counter ("PCNT")
+		write_counter(&mut content, self.counter);
 
 		Ok(content)
 	}
@@ -186,4 +168,36 @@ mod tests {
 		test_popm(&popm_u32_boundary);
 		test_popm(&popm_u40);
 	}
+
+	#[test_log::test]
+	fn read_popm_large_counter() {
+		for (counter_bytes, counter) in [
+			(&[][..], 0),
+			(&[0, 0, 0, 1][..], 1),
+			(&[1, 0, 0, 0, 0, 0][..], 1 << 40),
+			// Leading zeros don't count towards the size
+			(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0][..], 1 << 56),
+			(&[1, 0, 0, 0, 0, 0, 0, 0, 0][..], u64::MAX),
+		] {
+			let mut content = b"foo@bar.com\0\x80".to_vec();
+			content.extend(counter_bytes);
+
+			let popm = PopularimeterFrame::parse(&mut &content[..], FrameFlags::default()).unwrap();
+			assert_eq!(popm.email, "foo@bar.com");
+			assert_eq!(popm.rating, 128);
+			assert_eq!(popm.counter, counter);
+		}
+	}
+
+	#[test_log::test]
+	fn popm_round_trip() {
+		for counter in [0, 3, u64::from(u32::MAX) + 1, u64::MAX] {
+			let popm = PopularimeterFrame::new(String::from("foo@bar.com"), 196, counter);
+			let bytes = popm.as_bytes().unwrap();
+
+			let parsed = PopularimeterFrame::parse(&mut &bytes[..], FrameFlags::default()).unwrap();
+			assert_eq!(parsed.rating, 196);
+			assert_eq!(parsed.counter, counter);
+		}
+	}
 }
@@ -1,13 +1,12 @@
+use crate::config::TextDecodeFallback;
 use crate::error::{ErrorKind, Id3v2Error, Id3v2ErrorKind, LoftyError, Result};
 use crate::id3::v2::{FrameFlags, FrameHeader, FrameId};
 use crate::macros::err;
-use crate::util::text::{
-	decode_text, encode_text, read_to_terminator, utf16_decode_bytes, TextDecodeOptions,
-	TextEncoding,
-};
+use crate::util::text::{decode_text, encode_text, TextDecodeOptions, TextEncoding};
 
 use std::borrow::Cow;
-use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::hash::{Hash, Hasher};
+use std::io::{Cursor, Write};
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
@@ -69,7 +68,12 @@ impl SyncTextContentType {
 }
 
 /// Represents an ID3v2 synchronized text frame
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+///
+/// This is used in the `SYLT` frame, most commonly for timestamped lyrics.
+///
+/// NOTE: The `PartialEq` and `Hash` implementations only look at the language and description,
+///       as they must be unique within a tag.
+#[derive(Clone, Debug, Eq)]
 pub struct SynchronizedTextFrame<'a> {
 	pub(crate) header: FrameHeader<'a>,
 	/// The text encoding (description/text)
@@ -86,6 +90,19 @@ pub struct SynchronizedTextFrame<'a> {
 	pub content: Vec<(u32, String)>,
 }
 
+impl PartialEq for SynchronizedTextFrame<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		self.language == other.language && self.description == other.description
+	}
+}
+
+impl Hash for SynchronizedTextFrame<'_> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.language.hash(state);
+		self.description.hash(state);
+	}
+}
+
 impl SynchronizedTextFrame<'_> {
 	/// Create a new [`SynchronizedTextFrame`]
 	pub fn new(
@@ -123,15 +140,57 @@ impl SynchronizedTextFrame<'_> {
 		self.header.flags = flags;
 	}
 
+	/// The text of all entries, without their timestamps
+	///
+	/// The entries are joined as-is, as they are expected to include any whitespace or line
+	/// breaks between them.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{SyncTextContentType, SynchronizedTextFrame, TimestampFormat};
+	/// use lofty::TextEncoding;
+	///
+	/// let frame = SynchronizedTextFrame::new(
+	/// 	TextEncoding::UTF8,
+	/// 	*b"eng",
+	/// 	TimestampFormat::MS,
+	/// 	SyncTextContentType::Lyrics,
+	/// 	None,
+	/// 	vec![
+	/// 		(0, String::from("Strang")),
+	/// 		(500, String::from("ers")),
+	/// 		(1000, String::from(" in the night")),
+	/// 	],
+	/// );
+	///
+	/// assert_eq!(frame.text(), "Strangers in the night");
+	/// ```
+	pub fn text(&self) -> String {
+		self.content.iter().map(|(_, text)| text.as_str()).collect()
+	}
+
 	/// Read a [`SynchronizedTextFrame`] from a slice
 	///
 	/// NOTE: This expects the frame header to have already been skipped
 	///
+	/// With [`TextEncoding::UTF16`], the entries may either have their own byte order marks, or
+	/// rely on the byte order mark of the description.
+	///
 	/// # Errors
 	///
 	/// This function will return [`BadSyncText`][Id3v2ErrorKind::BadSyncText] if at any point it's unable to parse the data
-	#[allow(clippy::missing_panics_doc)] // Infallible
 	pub fn parse(data: &[u8], frame_flags: FrameFlags) -> Result<Self> {
+		Self::parse_with_fallback(data, frame_flags, TextDecodeFallback::None)
+	}
+
+	/// Same as [`SynchronizedTextFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	#[allow(clippy::missing_panics_doc)] // Infallible
+	pub(crate) fn parse_with_fallback(
+		data: &[u8],
+		frame_flags: FrameFlags,
+		fallback: TextDecodeFallback,
+	) -> Result<Self> {
 		if data.len() < 7 {
 			return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
 		}
@@ -147,65 +206,33 @@ impl SynchronizedTextFrame<'_> {
 		let content_type = SyncTextContentType::from_u8(data[5])
 			.ok_or_else(|| Id3v2Error::new(Id3v2ErrorKind::BadSyncText))?;
 
-		let mut cursor = Cursor::new(&data[6..]);
-		let description = crate::util::text::decode_text(
-			&mut cursor,
-			TextDecodeOptions::new().encoding(encoding).terminated(true),
-		)
-		.map_err(|_| Id3v2Error::new(Id3v2ErrorKind::BadSyncText))?
-		.text_or_none();
-
-		let mut endianness: fn([u8; 2]) -> u16 = u16::from_le_bytes;
-
-		// It's possible for the description to be the only string with a BOM
-		// To be safe, we change the encoding to the concrete variant determined from the description
-		if encoding == TextEncoding::UTF16 {
-			endianness = match cursor.get_ref()[..=1] {
-				[0xFF, 0xFE] => u16::from_le_bytes,
-				[0xFE, 0xFF] => u16::from_be_bytes,
-				// Since the description was already read, we can assume the BOM was valid
-				_ => unreachable!(),
-			};
-		}
+		// It's possible for the description to be the only string with a BOM, in which case the
+		// entries rely on it
+		let bom = match data[6..] {
+			[0xFF, 0xFE, ..] => [0xFF, 0xFE],
+			[0xFE, 0xFF, ..] => [0xFE, 0xFF],
+			_ => [0, 0],
+		};
+		let text_decode_options = TextDecodeOptions::new()
+			.encoding(encoding)
+			.terminated(true)
+			.bom(bom)
+			.fallback(fallback);
 
-		let mut pos = 0;
-		let total = (data.len() - 6) as u64 - cursor.stream_position()?;
+		let mut cursor = Cursor::new(&data[6..]);
+		let description = decode_text(&mut cursor, text_decode_options)
+			.map_err(|e| Id3v2Error::new(Id3v2ErrorKind::BadSyncText).with_source(e))?
+			.text_or_none();
 
 		let mut content = Vec::new();
-
-		while pos < total {
-			let text = (|| -> Result<String> {
-				if encoding == TextEncoding::UTF16 {
-					// Check for a BOM
-					let mut bom = [0; 2];
-					cursor
-						.read_exact(&mut bom)
-						.map_err(|_| Id3v2Error::new(Id3v2ErrorKind::BadSyncText))?;
-
-					cursor.seek(SeekFrom::Current(-2))?;
-
-					// Encountered text that doesn't include a BOM
-					if bom != [0xFF, 0xFE] && bom != [0xFE, 0xFF] {
-						let (raw_text, _) = read_to_terminator(&mut cursor, TextEncoding::UTF16);
-						return utf16_decode_bytes(&raw_text, endianness)
-							.map_err(|_| Id3v2Error::new(Id3v2ErrorKind::BadSyncText).into());
-					}
-				}
-
-				let decoded_text = decode_text(
-					&mut cursor,
-					TextDecodeOptions::new().encoding(encoding).terminated(true),
-				)
-				.map_err(|_| Id3v2Error::new(Id3v2ErrorKind::BadSyncText))?;
-				pos += decoded_text.bytes_read as u64;
-
-				Ok(decoded_text.content)
-			})()?;
+		while (cursor.position() as usize) < cursor.get_ref().len() {
+			let text = decode_text(&mut cursor, text_decode_options)
+				.map_err(|e| Id3v2Error::new(Id3v2ErrorKind::BadSyncText).with_source(e))?
+				.content;
 
 			let time = cursor
 				.read_u32::<BigEndian>()
-				.map_err(|_| Id3v2Error::new(Id3v2ErrorKind::BadSyncText))?;
-			pos += 4;
+				.map_err(|e| Id3v2Error::new(Id3v2ErrorKind::BadSyncText).with_source(e))?;
 
 			content.push((time, text));
 		}
@@ -229,35 +256,35 @@ impl SynchronizedTextFrame<'_> {
 	/// # Errors
 	///
 	/// * `content`'s length > [`u32::MAX`]
-	/// * `language` is not exactly 3 bytes
 	/// * `language` contains invalid characters (Only `'a'..='z'` and `'A'..='Z'` allowed)
-	pub fn as_bytes(&self) -> Result<Vec<u8>> {
-		let mut data = vec![self.encoding as u8];
-
-		if self.language.len() == 3 && self.language.iter().all(u8::is_ascii_alphabetic) {
-			data.write_all(&self.language)?;
-			data.write_u8(self.timestamp_format as u8)?;
-			data.write_u8(self.content_type as u8)?;
-
-			if let Some(description) = &self.description {
-				data.write_all(&encode_text(description, self.encoding, true))?;
-			} else {
-				data.write_u8(0)?;
-			}
-
-			for (time, ref text) in &self.content {
-				data.write_all(&encode_text(text, self.encoding, true))?;
-				data.write_u32::<BigEndian>(*time)?;
-			}
-
-			if data.len() as u64 > u64::from(u32::MAX) {
-				err!(TooMuchData);
-			}
-
-			return Ok(data);
+	pub fn as_bytes(&self, is_id3v23: bool) -> Result<Vec<u8>> {
+		if !self.language.iter().all(u8::is_ascii_alphabetic) {
+			return Err(Id3v2Error::new(Id3v2ErrorKind::BadSyncText).into());
+		}
+
+		let mut encoding = self.encoding;
+		if is_id3v23 {
+			encoding = encoding.to_id3v23();
+		}
+
+		let mut data = vec![encoding as u8];
+		data.write_all(&self.language)?;
+		data.write_u8(self.timestamp_format as u8)?;
+		data.write_u8(self.content_type as u8)?;
+
+		let description = self.description.as_deref().unwrap_or_default();
+		data.write_all(&encode_text(description, encoding, true))?;
+
+		for (time, text) in &self.content {
+			data.write_all(&encode_text(text, encoding, true))?;
+			data.write_u32::<BigEndian>(*time)?;
+		}
+
+		if data.len() as u64 > u64::from(u32::MAX) {
+			err!(TooMuchData);
 		}
 
-		Err(Id3v2Error::new(Id3v2ErrorKind::BadSyncText).into())
+		Ok(data)
 	}
 }
 
@@ -297,7 +324,7 @@ mod tests {
 
 	#[test_log::test]
 	fn sylt_encode() {
-		let encoded = expected(TextEncoding::Latin1).as_bytes().unwrap();
+		let encoded = expected(TextEncoding::Latin1).as_bytes(false).unwrap();
 
 		let expected_bytes =
 			crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test.sylt");
@@ -317,11 +344,50 @@ mod tests {
 
 	#[test_log::test]
 	fn sylt_encode_utf_16() {
-		let encoded = expected(TextEncoding::UTF16).as_bytes().unwrap();
+		let encoded = expected(TextEncoding::UTF16).as_bytes(false).unwrap();
 
 		let expected_bytes =
 			crate::tag::utils::test_utils::read_path("tests/tags/assets/id3v2/test_utf16.sylt");
 
 		assert_eq!(encoded, expected_bytes);
 	}
+
+	#[test_log::test]
+	fn sylt_decode_utf16_single_bom() {
+		#[rustfmt::skip]
+		let content = [
+			1,                      // UTF-16
+			b'e', b'n', b'g',       // Language
+			2,                      // Milliseconds
+			1,                      // Lyrics
+			0xFE, 0xFF, 0, b'a', 0, 0, // Description, the only BOM (big-endian)
+			0, b'b', 0, 0,          // Entry
+			0, 0, 0x03, 0xE8,       // 1000ms
+			0, b'c', 0, 0,          // Entry
+			0, 0, 0x07, 0xD0,       // 2000ms
+		];
+
+		let parsed_sylt = SynchronizedTextFrame::parse(&content, FrameFlags::default()).unwrap();
+
+		assert_eq!(parsed_sylt.description.as_deref(), Some("a"));
+		assert_eq!(
+			parsed_sylt.content,
+			[(1000, String::from("b")), (2000, String::from("c"))]
+		);
+	}
+
+	#[test_log::test]
+	fn sylt_encode_id3v23() {
+		let mut frame = expected(TextEncoding::UTF8);
+		frame.description = None;
+
+		let encoded = frame.as_bytes(true).unwrap();
+
+		// UTF-8 isn't available in ID3v2.3, and every string has its own BOM
+		assert_eq!(encoded[0], TextEncoding::UTF16 as u8);
+
+		let parsed_sylt = SynchronizedTextFrame::parse(&encoded, FrameFlags::default()).unwrap();
+		assert_eq!(parsed_sylt.description, None);
+		assert_eq!(parsed_sylt.content, frame.content);
+	}
 }
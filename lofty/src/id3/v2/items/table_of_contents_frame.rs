@@ -0,0 +1,231 @@
+use super::chapter_frame::{read_embedded_frames, write_embedded_frames};
+use crate::config::ParseOptions;
+use crate::error::Result;
+use crate::id3::v2::{Frame, FrameFlags, FrameHeader, FrameId, Id3v2Version};
+use crate::util::text::{decode_text, encode_text, TextDecodeOptions, TextEncoding};
+
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+
+use byteorder::ReadBytesExt;
+
+const FRAME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("CTOC"));
+
+const ORDERED_FLAG: u8 = 0x01;
+const TOP_LEVEL_FLAG: u8 = 0x02;
+
+/// An `ID3v2` table of contents frame
+///
+/// This lists the [`ChapterFrame`](crate::id3::v2::ChapterFrame)s (or other tables of contents)
+/// that make up the audio, by their element IDs. See the
+/// [ID3v2 Chapter Frame Addendum](https://mutagen-specs.readthedocs.io/en/latest/id3/id3v2-chapters-1.0.html).
+///
+/// NOTE: The `PartialEq` and `Hash` implementations only look at the element ID, as it is unique
+///       within a tag.
+#[derive(Clone, Debug, Eq)]
+pub struct TableOfContentsFrame<'a> {
+	pub(crate) header: FrameHeader<'a>,
+	/// The identifier of the table of contents, unique within the tag
+	///
+	/// This is not meant to be displayed, and is encoded as a null terminated Latin-1 string.
+	pub element_id: String,
+	/// Whether this is the root of the table of contents tree
+	///
+	/// There should only be one top-level table of contents in a tag.
+	pub top_level: bool,
+	/// Whether the entries are in order
+	pub ordered: bool,
+	/// The element IDs of the entries
+	///
+	/// NOTE: Only 255 entries can be written, any more will be discarded.
+	pub child_element_ids: Vec<String>,
+	/// The frames describing the table of contents, such as its title (`TIT2`)
+	pub frames: Vec<Frame<'a>>,
+}
+
+impl PartialEq for TableOfContentsFrame<'_> {
+	fn eq(&self, other: &Self) -> bool {
+		self.element_id == other.element_id
+	}
+}
+
+impl Hash for TableOfContentsFrame<'_> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.element_id.hash(state);
+	}
+}
+
+impl TableOfContentsFrame<'_> {
+	/// Create a new [`TableOfContentsFrame`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::TableOfContentsFrame;
+	///
+	/// let toc = TableOfContentsFrame::new(
+	/// 	String::from("toc"),
+	/// 	vec![String::from("chp0"), String::from("chp1")],
+	/// );
+	///
+	/// assert!(toc.top_level);
+	/// assert!(toc.ordered);
+	/// ```
+	pub fn new(element_id: String, child_element_ids: Vec<String>) -> Self {
+		let header = FrameHeader::new(FRAME_ID, FrameFlags::default());
+		Self {
+			header,
+			element_id,
+			top_level: true,
+			ordered: true,
+			child_element_ids,
+			frames: Vec::new(),
+		}
+	}
+
+	/// Get the ID for the frame
+	pub fn id(&self) -> FrameId<'_> {
+		FRAME_ID
+	}
+
+	/// Get the flags for the frame
+	pub fn flags(&self) -> FrameFlags {
+		self.header.flags
+	}
+
+	/// Set the flags for the frame
+	pub fn set_flags(&mut self, flags: FrameFlags) {
+		self.header.flags = flags;
+	}
+
+	/// Read a [`TableOfContentsFrame`]
+	///
+	/// NOTE: This expects the frame header to have already been skipped
+	///
+	/// The embedded frames are read according to `version` and `parse_options`, the same as the
+	/// frames of the tag. Chapters can't be nested, so any embedded `CHAP` or `CTOC` frames are skipped.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * Any of the embedded frames are invalid, depending on the [`ParsingMode`](crate::config::ParsingMode)
+	/// * A `CHAP` or `CTOC` frame is embedded, with [`ParsingMode::Strict`](crate::config::ParsingMode::Strict)
+	pub fn parse<R>(
+		reader: &mut R,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		parse_options: ParseOptions,
+	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let latin1_terminated = TextDecodeOptions::new()
+			.encoding(TextEncoding::Latin1)
+			.terminated(true);
+
+		let element_id = decode_text(reader, latin1_terminated)?;
+		if element_id.bytes_read == 0 {
+			return Ok(None);
+		}
+
+		let flags = reader.read_u8()?;
+		let entry_count = reader.read_u8()?;
+
+		let mut child_element_ids = Vec::with_capacity(usize::from(entry_count));
+		for _ in 0..entry_count {
+			child_element_ids.push(decode_text(reader, latin1_terminated)?.content);
+		}
+
+		let frames = read_embedded_frames(reader, version, parse_options)?;
+
+		let header = FrameHeader::new(FRAME_ID, frame_flags);
+		Ok(Some(TableOfContentsFrame {
+			header,
+			element_id: element_id.content,
+			top_level: flags & TOP_LEVEL_FLAG == TOP_LEVEL_FLAG,
+			ordered: flags & ORDERED_FLAG == ORDERED_FLAG,
+			child_element_ids,
+			frames,
+		}))
+	}
+
+	/// Convert a [`TableOfContentsFrame`] to a byte vec
+	///
+	/// # Errors
+	///
+	/// * Any of the embedded frames fail to convert, see [`Id3v2Tag`](crate::id3::v2::Id3v2Tag)'s
+	///   implementation of [`TagExt::save_to`](crate::tag::TagExt::save_to)
+	pub fn as_bytes(&self, is_id3v23: bool) -> Result<Vec<u8>> {
+		let mut bytes = encode_text(&self.element_id, TextEncoding::Latin1, true);
+
+		let mut flags = 0;
+		if self.top_level {
+			flags |= TOP_LEVEL_FLAG;
+		}
+		if self.ordered {
+			flags |= ORDERED_FLAG;
+		}
+		bytes.push(flags);
+
+		if self.child_element_ids.len() > usize::from(u8::MAX) {
+			log::warn!(
+				"CTOC frame has {} entries, discarding all past {}",
+				self.child_element_ids.len(),
+				u8::MAX
+			);
+		}
+
+		let entries = self.child_element_ids.iter().take(usize::from(u8::MAX));
+		bytes.push(entries.len() as u8);
+		for entry in entries {
+			bytes.extend(encode_text(entry, TextEncoding::Latin1, true));
+		}
+
+		write_embedded_frames(&mut bytes, &self.frames, is_id3v23)?;
+
+		Ok(bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::config::ParseOptions;
+	use crate::id3::v2::{
+		Frame, FrameFlags, FrameId, Id3v2Version, TableOfContentsFrame, TextInformationFrame,
+	};
+	use crate::TextEncoding;
+
+	use std::borrow::Cow;
+
+	#[test_log::test]
+	fn ctoc_round_trip() {
+		let mut toc = TableOfContentsFrame::new(
+			String::from("toc"),
+			vec![String::from("chp0"), String::from("chp1")],
+		);
+		toc.ordered = false;
+		toc.frames.push(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TIT2")),
+			TextEncoding::UTF8,
+			String::from("Foo contents"),
+		)));
+
+		let bytes = toc.as_bytes(false).unwrap();
+		assert_eq!(&bytes[..6], b"toc\0\x02\x02");
+
+		let parsed = TableOfContentsFrame::parse(
+			&mut &bytes[..],
+			FrameFlags::default(),
+			Id3v2Version::V4,
+			ParseOptions::new(),
+		)
+		.unwrap()
+		.unwrap();
+
+		assert!(parsed.top_level);
+		assert!(!parsed.ordered);
+		assert_eq!(parsed.child_element_ids, toc.child_element_ids);
+		assert_eq!(parsed.frames, toc.frames);
+	}
+}
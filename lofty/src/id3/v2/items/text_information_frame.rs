@@ -1,3 +1,4 @@
+use crate::config::TextDecodeFallback;
 use crate::error::Result;
 use crate::id3::v2::frame::content::verify_encoding;
 use crate::id3::v2::header::Id3v2Version;
@@ -74,6 +75,20 @@ impl<'a> TextInformationFrame<'a> {
 		frame_flags: FrameFlags,
 		version: Id3v2Version,
 	) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		Self::parse_with_fallback(reader, id, frame_flags, version, TextDecodeFallback::None)
+	}
+
+	/// Same as [`TextInformationFrame::parse`], using `fallback` for text that doesn't match its declared encoding
+	pub(crate) fn parse_with_fallback<R>(
+		reader: &mut R,
+		id: FrameId<'a>,
+		frame_flags: FrameFlags,
+		version: Id3v2Version,
+		fallback: TextDecodeFallback,
+	) -> Result<Option<Self>>
 	where
 		R: Read,
 	{
@@ -82,7 +97,13 @@ impl<'a> TextInformationFrame<'a> {
 		};
 
 		let encoding = verify_encoding(encoding_byte, version)?;
-		let value = decode_text(reader, TextDecodeOptions::new().encoding(encoding))?.content;
+		let value = decode_text(
+			reader,
+			TextDecodeOptions::new()
+				.encoding(encoding)
+				.fallback(fallback),
+		)?
+		.content;
 
 		let header = FrameHeader::new(id, frame_flags);
 		Ok(Some(TextInformationFrame {
@@ -18,7 +18,8 @@ pub(crate) mod write;
 
 // Exports
 
-pub use header::{Id3v2TagFlags, Id3v2Version};
+pub use header::{ExtendedHeader, Id3v2Header, Id3v2TagFlags, Id3v2Version};
+pub use read::FrameIter;
 pub use util::upgrade::{upgrade_v2, upgrade_v3};
 
 pub use tag::Id3v2Tag;
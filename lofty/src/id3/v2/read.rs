@@ -1,14 +1,187 @@
 use super::frame::read::ParsedFrame;
 use super::header::Id3v2Header;
 use super::tag::Id3v2Tag;
-use crate::config::ParseOptions;
-use crate::error::Result;
+use crate::config::{ParseOptions, ParsingMode};
+use crate::error::{Id3v2Error, Id3v2ErrorKind, ParseDiagnostic, Result};
+use crate::id3::v2::util::crc::crc32;
 use crate::id3::v2::util::synchsafe::UnsynchronizedStream;
 use crate::id3::v2::{Frame, FrameId, Id3v2Version, TimestampFrame};
 use crate::tag::items::Timestamp;
+use crate::util::diagnostics;
 
 use std::borrow::Cow;
-use std::io::Read;
+use std::io::{Read, Take};
+
+/// An iterator over the frames of an `ID3v2` tag
+///
+/// This reads a single [`Frame`] at a time, without building an [`Id3v2Tag`]. This is useful
+/// for when only a few frames are of interest, as iteration can stop at any point without reading
+/// the remainder of the tag.
+///
+/// Frames that are skipped during parsing (e.g. unreadable frames in [`ParsingMode::Relaxed`](crate::config::ParsingMode::Relaxed),
+/// or cover art when [`ParseOptions::read_cover_art`] is disabled) are not yielded.
+///
+/// NOTE: Unlike [`Id3v2Tag`]s read through the usual means, no post-processing is done on the frames.
+///       For example, `ID3v2.3` `TYER`, `TDAT`, and `TIME` frames will not be merged into a `TDRC` frame.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::config::ParseOptions;
+/// use lofty::id3::v2::{FrameIter, Id3v2Header, Id3v2Tag};
+/// use lofty::prelude::*;
+/// use std::io::Cursor;
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let mut tag = Id3v2Tag::new();
+/// # tag.set_title(String::from("Foo title"));
+/// # tag.set_artist(String::from("Bar artist"));
+/// # let mut tag_bytes = Vec::new();
+/// # tag.dump_to(&mut tag_bytes, lofty::config::WriteOptions::default())?;
+/// let mut reader = Cursor::new(tag_bytes);
+///
+/// let header = Id3v2Header::parse(&mut reader)?;
+/// let mut frames = FrameIter::new(&mut reader, header, ParseOptions::new());
+///
+/// // Stop as soon as we find the artist
+/// let artist = frames.find_map(|frame| match frame {
+/// 	Ok(frame) if frame.id_str() == "TPE1" => Some(frame),
+/// 	_ => None,
+/// });
+///
+/// assert!(artist.is_some());
+/// # Ok(()) }
+/// ```
+pub struct FrameIter<R> {
+	reader: TagReader<R>,
+	// The offset and length of the frames within the tag, used for diagnostics
+	frames_offset: u64,
+	frames_len: u64,
+	version: Id3v2Version,
+	unsynchronised_frames: bool,
+	parse_options: ParseOptions,
+	finished: bool,
+}
+
+pub(super) enum TagReader<R> {
+	Plain(Take<R>),
+	Unsynchronized(Box<UnsynchronizedStream<Take<R>>>),
+}
+
+impl<R> TagReader<R> {
+	/// The number of bytes remaining in the tag, if known
+	///
+	/// This is unknown for unsynchronized tags, as the size is that of the encoded content.
+	pub(super) fn remaining(&self) -> Option<u64> {
+		match self {
+			TagReader::Plain(reader) => Some(reader.limit()),
+			TagReader::Unsynchronized(_) => None,
+		}
+	}
+}
+
+impl<R: Read> Read for TagReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		match self {
+			TagReader::Plain(reader) => reader.read(buf),
+			TagReader::Unsynchronized(reader) => reader.read(buf),
+		}
+	}
+}
+
+impl<R: Read> FrameIter<R> {
+	/// Create a new [`FrameIter`]
+	///
+	/// This expects the reader to be positioned at the first frame, as it will be after [`Id3v2Header::parse`].
+	///
+	/// The reader will never be read past the end of the tag, as described by `header`.
+	pub fn new(reader: R, header: Id3v2Header, parse_options: ParseOptions) -> Self {
+		log::debug!(
+			"Parsing ID3v2 tag, size: {}, version: {:?}",
+			header.size,
+			header.version
+		);
+
+		let frames_len = u64::from(header.size - header.extended_size);
+		let tag_bytes = reader.take(frames_len);
+
+		// ID3v2.4 unsynchronises each frame separately, with the tag flag indicating that all of them are
+		let unsynchronised_frames =
+			header.flags.unsynchronisation && header.version == Id3v2Version::V4;
+
+		let reader = if header.flags.unsynchronisation && !unsynchronised_frames {
+			// Unsynchronize the entire tag
+			TagReader::Unsynchronized(Box::new(UnsynchronizedStream::new(tag_bytes)))
+		} else {
+			TagReader::Plain(tag_bytes)
+		};
+
+		Self {
+			reader,
+			// The frames follow the 10 byte header and the extended header
+			frames_offset: 10 + u64::from(header.extended_size),
+			frames_len,
+			version: header.version,
+			unsynchronised_frames,
+			parse_options,
+			finished: false,
+		}
+	}
+
+	/// Extract the reader, discarding the [`FrameIter`]
+	///
+	/// NOTE: The reader will be positioned wherever iteration stopped, which is not necessarily the end of the tag.
+	pub fn into_inner(self) -> R {
+		match self.reader {
+			TagReader::Plain(reader) => reader.into_inner(),
+			TagReader::Unsynchronized(reader) => reader.into_inner().into_inner(),
+		}
+	}
+
+	// Throw away the rest of the tag (padding, bad frames)
+	fn skip_remaining(&mut self) -> Result<()> {
+		let reader = match &mut self.reader {
+			TagReader::Plain(reader) => reader,
+			TagReader::Unsynchronized(reader) => reader.get_mut(),
+		};
+
+		std::io::copy(reader, &mut std::io::sink())?;
+		Ok(())
+	}
+}
+
+impl<R: Read> Iterator for FrameIter<R> {
+	type Item = Result<Frame<'static>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while !self.finished {
+			let offset = self
+				.reader
+				.remaining()
+				.map(|remaining| self.frames_offset + (self.frames_len - remaining));
+
+			match ParsedFrame::read(
+				&mut self.reader,
+				self.version,
+				self.unsynchronised_frames,
+				self.parse_options,
+				offset,
+			) {
+				Ok(ParsedFrame::Next(frame)) => return Some(Ok(frame)),
+				// No frame content found or ignored due to errors, but we can expect more frames
+				Ok(ParsedFrame::Skip) => {},
+				// No frame content found, and we can expect there are no more frames
+				Ok(ParsedFrame::Eof) => self.finished = true,
+				Err(e) => {
+					self.finished = true;
+					return Some(Err(e));
+				},
+			}
+		}
+
+		None
+	}
+}
 
 pub(crate) fn parse_id3v2<R>(
 	bytes: &mut R,
@@ -18,37 +191,91 @@ pub(crate) fn parse_id3v2<R>(
 where
 	R: Read,
 {
-	log::debug!(
-		"Parsing ID3v2 tag, size: {}, version: {:?}",
-		header.size,
-		header.version
-	);
-
-	let mut tag_bytes = bytes.take(u64::from(header.size - header.extended_size));
-
-	let mut ret;
-	if header.flags.unsynchronisation {
-		// Unsynchronize the entire tag
-		let mut unsynchronized_reader = UnsynchronizedStream::new(tag_bytes);
-		ret = read_all_frames_into_tag(&mut unsynchronized_reader, header, parse_options)?;
-
-		// Get the `Take` back from the `UnsynchronizedStream`
-		tag_bytes = unsynchronized_reader.into_inner();
-	} else {
-		ret = read_all_frames_into_tag(&mut tag_bytes, header, parse_options)?;
-	};
+	let read_frames = parse_options.verify_tag_size(u64::from(header.size))?;
+
+	if let Some(expected_crc) = header.extended_header.and_then(|extended| extended.crc) {
+		if read_frames {
+			// The CRC needs the entire tag content, so it has to be buffered up front
+			let mut content = Vec::new();
+			bytes
+				.take(u64::from(header.size - header.extended_size))
+				.read_to_end(&mut content)?;
+
+			verify_crc(&content, expected_crc, header, parse_options)?;
+			return parse_frames(&mut &*content, header, parse_options, read_frames);
+		}
+	}
 
-	// Throw away the rest of the tag (padding, bad frames)
-	std::io::copy(&mut tag_bytes, &mut std::io::sink())?;
+	parse_frames(bytes, header, parse_options, read_frames)
+}
+
+fn parse_frames<R>(
+	bytes: &mut R,
+	header: Id3v2Header,
+	parse_options: ParseOptions,
+	read_frames: bool,
+) -> Result<Id3v2Tag>
+where
+	R: Read,
+{
+	let mut frames = FrameIter::new(bytes, header, parse_options);
+
+	if !read_frames {
+		// Skip all frames, leaving an empty tag in its place
+		frames.finished = true;
+	}
+
+	let mut ret = read_all_frames_into_tag(&mut frames, header)?;
 
-	// Construct TDRC frame from TYER, TDAT, and TIME frames
-	if parse_options.implicit_conversions && header.version == Id3v2Version::V3 {
+	frames.skip_remaining()?;
+
+	// Construct TDRC frame from TYER, TDAT, and TIME frames (upgraded from TYE, TDA, and TIM in ID3v2.2)
+	if parse_options.implicit_conversions
+		&& matches!(header.version, Id3v2Version::V2 | Id3v2Version::V3)
+	{
 		construct_tdrc_from_v3(&mut ret);
 	}
 
 	Ok(ret)
 }
 
+fn verify_crc(
+	content: &[u8],
+	expected: u32,
+	header: Id3v2Header,
+	parse_options: ParseOptions,
+) -> Result<()> {
+	let actual = if header.version == Id3v2Version::V3 {
+		// ID3v2.3 calculates the CRC before unsynchronisation, and excludes the padding
+		let mut synchronized;
+		let mut content = content;
+		if header.flags.unsynchronisation {
+			synchronized = Vec::new();
+			UnsynchronizedStream::new(content).read_to_end(&mut synchronized)?;
+			content = &synchronized;
+		}
+
+		let frames_len = content.len().saturating_sub(header.padding_size as usize);
+		crc32(&content[..frames_len])
+	} else {
+		// ID3v2.4 calculates the CRC on all the data between the header and footer
+		crc32(content)
+	};
+
+	if actual == expected {
+		return Ok(());
+	}
+
+	if parse_options.parsing_mode == ParsingMode::Strict {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::CrcMismatch { expected, actual }).into());
+	}
+
+	log::warn!("ID3v2 CRC mismatch, expected {expected:#010X}, got {actual:#010X}");
+	diagnostics::report(ParseDiagnostic::Id3v2CrcMismatch { expected, actual });
+
+	Ok(())
+}
+
 fn construct_tdrc_from_v3(tag: &mut Id3v2Tag) {
 	const TDRC: FrameId<'_> = FrameId::Valid(Cow::Borrowed("TDRC"));
 	const TDAT: FrameId<'_> = FrameId::Valid(Cow::Borrowed("TDAT"));
@@ -67,6 +294,7 @@ fn construct_tdrc_from_v3(tag: &mut Id3v2Tag) {
 
 	// This is not a TYER frame
 	if year_frame.timestamp.month.is_some() {
+		tag.insert(Frame::Timestamp(year_frame));
 		return;
 	}
 
@@ -75,45 +303,8 @@ fn construct_tdrc_from_v3(tag: &mut Id3v2Tag) {
 		..Timestamp::default()
 	};
 
-	let mut date_used = false;
-	let mut time_used = false;
-	'build: {
-		let Some(date) = tag.get_text(&TDAT) else {
-			break 'build;
-		};
-
-		if date.len() != 4 || !date.is_ascii() {
-			log::warn!("Invalid TDAT frame, retaining.");
-			break 'build;
-		}
-
-		let (Ok(day), Ok(month)) = (date[..2].parse::<u8>(), date[2..].parse::<u8>()) else {
-			log::warn!("Invalid TDAT frame, retaining.");
-			break 'build;
-		};
-
-		tdrc.month = Some(month);
-		tdrc.day = Some(day);
-		date_used = true;
-
-		let Some(time) = tag.get_text(&TIME) else {
-			break 'build;
-		};
-
-		if time.len() != 4 || !time.is_ascii() {
-			log::warn!("Invalid TIME frame, retaining.");
-			break 'build;
-		}
-
-		let (Ok(hour), Ok(minute)) = (time[..2].parse::<u8>(), time[2..].parse::<u8>()) else {
-			log::warn!("Invalid TIME frame, retaining.");
-			break 'build;
-		};
-
-		tdrc.hour = Some(hour);
-		tdrc.minute = Some(minute);
-		time_used = true;
-	}
+	let (date_used, time_used) =
+		merge_v3_date_and_time(&mut tdrc, tag.get_text(&TDAT), tag.get_text(&TIME));
 
 	tag.insert(Frame::Timestamp(TimestampFrame::new(
 		FrameId::Valid(Cow::Borrowed("TDRC")),
@@ -130,47 +321,90 @@ fn construct_tdrc_from_v3(tag: &mut Id3v2Tag) {
 	}
 }
 
-fn read_all_frames_into_tag<R>(
-	reader: &mut R,
-	header: Id3v2Header,
-	parse_options: ParseOptions,
-) -> Result<Id3v2Tag>
+/// Fill in the month, day, hour, and minute of `timestamp` from the contents of ID3v2.3 `TDAT` (DDMM)
+/// and `TIME` (HHMM) frames
+///
+/// The time is only used if the date is valid. This returns whether the date and time were used, respectively.
+pub(in crate::id3::v2) fn merge_v3_date_and_time(
+	timestamp: &mut Timestamp,
+	date: Option<&str>,
+	time: Option<&str>,
+) -> (bool, bool) {
+	fn split_pair(value: &str) -> Option<(u8, u8)> {
+		if value.len() != 4 || !value.is_ascii() {
+			return None;
+		}
+
+		match (value[..2].parse::<u8>(), value[2..].parse::<u8>()) {
+			(Ok(first), Ok(second)) => Some((first, second)),
+			_ => None,
+		}
+	}
+
+	let Some(date) = date else {
+		return (false, false);
+	};
+
+	let Some((day, month)) = split_pair(date) else {
+		log::warn!("Invalid TDAT frame, retaining.");
+		return (false, false);
+	};
+
+	timestamp.month = Some(month);
+	timestamp.day = Some(day);
+
+	let Some(time) = time else {
+		return (true, false);
+	};
+
+	let Some((hour, minute)) = split_pair(time) else {
+		log::warn!("Invalid TIME frame, retaining.");
+		return (true, false);
+	};
+
+	timestamp.hour = Some(hour);
+	timestamp.minute = Some(minute);
+
+	(true, true)
+}
+
+fn read_all_frames_into_tag<R>(frames: &mut FrameIter<R>, header: Id3v2Header) -> Result<Id3v2Tag>
 where
 	R: Read,
 {
 	let mut tag = Id3v2Tag::default();
 	tag.original_version = header.version;
+	tag.original_flags = Some(header.flags);
+	tag.original_size = Some(header.size + if header.flags.footer { 20 } else { 10 });
+	tag.extended_header = header.extended_header;
 	tag.set_flags(header.flags);
 
-	loop {
-		match ParsedFrame::read(reader, header.version, parse_options)? {
-			ParsedFrame::Next(frame) => {
-				let frame_value_is_empty = frame.is_empty();
-				if let Some(replaced_frame) = tag.insert(frame) {
-					// Duplicate frames are not allowed. But if this occurs we try
-					// to keep the frame with the non-empty content. Superfluous,
-					// duplicate frames that follow the first frame are often empty.
-					if frame_value_is_empty == Some(true)
-						&& replaced_frame.is_empty() == Some(false)
-					{
-						log::warn!(
-							"Restoring non-empty frame with ID \"{id}\" that has been replaced by \
-							 an empty frame with the same ID",
-							id = replaced_frame.id()
-						);
-						drop(tag.insert(replaced_frame));
-					} else {
-						log::warn!(
-							"Replaced frame with ID \"{id}\" by a frame with the same ID",
-							id = replaced_frame.id()
-						);
-					}
-				}
-			},
-			// No frame content found or ignored due to errors, but we can expect more frames
-			ParsedFrame::Skip => {},
-			// No frame content found, and we can expect there are no more frames
-			ParsedFrame::Eof => break,
+	for frame in frames {
+		let frame = frame?;
+		let frame_value_is_empty = frame.is_empty();
+		if let Some(replaced_frame) = tag.insert(frame) {
+			// Duplicate frames are not allowed. But if this occurs we try
+			// to keep the frame with the non-empty content. Superfluous,
+			// duplicate frames that follow the first frame are often empty.
+			if frame_value_is_empty == Some(true) && replaced_frame.is_empty() == Some(false) {
+				log::warn!(
+					"Restoring non-empty frame with ID \"{id}\" that has been replaced by an \
+					 empty frame with the same ID",
+					id = replaced_frame.id()
+				);
+				diagnostics::report(ParseDiagnostic::DuplicateId3v2Frame {
+					id: replaced_frame.id().to_string(),
+				});
+				drop(tag.insert(replaced_frame));
+			} else {
+				log::warn!(
+					"Replaced frame with ID \"{id}\" by a frame with the same ID",
+					id = replaced_frame.id()
+				);
+				diagnostics::report(ParseDiagnostic::DuplicateId3v2Frame {
+					id: replaced_frame.id().to_string(),
+				});
+			}
 		}
 	}
 
@@ -232,4 +466,359 @@ mod tests {
 		assert_eq!(id3v2.track(), Some(1));
 		assert_eq!(id3v2.genre().as_deref(), Some("Classical"));
 	}
+
+	#[test_log::test]
+	fn frame_iter_early_exit() {
+		use crate::config::ParsingMode;
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::id3::v2::FrameIter;
+		use crate::tag::utils::test_utils::{read_path, CountingReader};
+		use crate::tag::TagExt;
+
+		let tag_bytes = read_path("tests/tags/assets/id3v2/test_full.id3v24");
+		let mut reader = CountingReader::new(&tag_bytes[..]);
+
+		let header = Id3v2Header::parse(&mut reader).unwrap();
+		let header_len = reader.bytes_read;
+
+		let parse_options = ParseOptions::new().parsing_mode(ParsingMode::Strict);
+		let mut frames = FrameIter::new(&mut reader, header, parse_options);
+		let first = frames.next().unwrap().unwrap();
+
+		// Only the header and first frame should have been read
+		let frame_len = reader.bytes_read - header_len;
+		assert!(frame_len > 0);
+		assert!(reader.bytes_read < u64::from(header.full_tag_size()));
+
+		// The iterator should yield the same frames as a full parse
+		let full_tag = parse_id3v2(
+			&mut &tag_bytes[header_len as usize..],
+			header,
+			parse_options,
+		)
+		.unwrap();
+		assert_eq!(full_tag.frames().next(), Some(&first));
+
+		let all_frames = FrameIter::new(&tag_bytes[header_len as usize..], header, parse_options)
+			.collect::<crate::error::Result<Vec<_>>>()
+			.unwrap();
+		assert_eq!(all_frames.len(), full_tag.len());
+	}
+
+	#[test_log::test]
+	fn relaxed_diagnostics() {
+		use crate::config::ParsingMode;
+		use crate::error::ParseDiagnostic;
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::prelude::*;
+		use crate::util::diagnostics;
+
+		use std::io::Cursor;
+
+		let parse_options = ParseOptions::new().parsing_mode(ParsingMode::Relaxed);
+
+		// Contains a frame with a "+" in the ID
+		let mut f = Cursor::new(
+			std::fs::read("tests/tags/assets/id3v2/bad_frame_otherwise_valid.id3v24").unwrap(),
+		);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let (id3v2, diagnostics) =
+			diagnostics::collect(|| parse_id3v2(&mut f, header, parse_options));
+		assert_eq!(id3v2.unwrap().len(), 6);
+		assert_eq!(diagnostics.len(), 1);
+		assert!(matches!(
+			diagnostics[0],
+			ParseDiagnostic::InvalidId3v2FrameHeader {
+				offset: Some(51),
+				..
+			}
+		));
+
+		// A TIT2 frame claiming to be 100 bytes, with only 4 bytes remaining in the tag
+		#[rustfmt::skip]
+		let tag = [
+			b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 14,
+			b'T', b'I', b'T', b'2', 0, 0, 0, 100, 0, 0,
+			0, b'F', b'o', b'o',
+		];
+		let mut f = Cursor::new(tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let (id3v2, diagnostics) =
+			diagnostics::collect(|| parse_id3v2(&mut f, header, parse_options));
+		assert_eq!(id3v2.unwrap().title().as_deref(), Some("Foo"));
+		assert_eq!(
+			diagnostics,
+			[ParseDiagnostic::OversizedFrame {
+				id: String::from("TIT2"),
+				offset: Some(10),
+				size: 100,
+				remaining: 4,
+			}]
+		);
+	}
+
+	#[test_log::test]
+	fn utf16_without_bom() {
+		use crate::config::ParsingMode;
+		use crate::error::ParseDiagnostic;
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::prelude::*;
+		use crate::util::diagnostics;
+
+		use std::io::Cursor;
+
+		// A UTF-16 TIT2 frame with the content "Foo", but no BOM
+		#[rustfmt::skip]
+		let tag = [
+			b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 17,
+			b'T', b'I', b'T', b'2', 0, 0, 0, 7, 0, 0,
+			1, b'F', 0, b'o', 0, b'o', 0,
+		];
+
+		let mut f = Cursor::new(tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let (id3v2, diagnostics) = diagnostics::collect(|| {
+			parse_id3v2(
+				&mut f,
+				header,
+				ParseOptions::new().parsing_mode(ParsingMode::Strict),
+			)
+		});
+		assert_eq!(id3v2.unwrap().title().as_deref(), Some("Foo"));
+		assert_eq!(diagnostics, [ParseDiagnostic::MissingUtf16Bom]);
+	}
+
+	#[test_log::test]
+	fn text_decode_fallback() {
+		use crate::config::{ParsingMode, TextDecodeFallback};
+		use crate::error::ParseDiagnostic;
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::prelude::*;
+		use crate::util::diagnostics;
+		use crate::TextEncoding;
+
+		use std::borrow::Cow;
+		use std::io::Cursor;
+
+		fn parse(tag: &[u8], fallback: TextDecodeFallback) -> Option<String> {
+			let mut f = Cursor::new(tag);
+			let header = Id3v2Header::parse(&mut f).unwrap();
+			let parse_options = ParseOptions::new()
+				.parsing_mode(ParsingMode::Strict)
+				.text_decode_fallback(fallback);
+
+			parse_id3v2(&mut f, header, parse_options)
+				.ok()
+				.and_then(|tag| tag.title().map(Cow::into_owned))
+		}
+
+		fn decode_cp1251(bytes: &[u8]) -> String {
+			bytes
+				.iter()
+				.map(|&b| match b {
+					0xC0..=0xFF => char::from_u32(0x0410 + u32::from(b - 0xC0)).unwrap(),
+					_ => char::from(b),
+				})
+				.collect()
+		}
+
+		// A UTF-8 TIT2 frame with an invalid continuation byte
+		#[rustfmt::skip]
+		let utf8_tag = [
+			b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 14,
+			b'T', b'I', b'T', b'2', 0, 0, 0, 4, 0, 0,
+			3, b'F', 0xC3, b'(',
+		];
+
+		assert_eq!(parse(&utf8_tag, TextDecodeFallback::None), None);
+		assert_eq!(
+			parse(&utf8_tag, TextDecodeFallback::Latin1).as_deref(),
+			Some("F\u{C3}(")
+		);
+		assert_eq!(
+			parse(&utf8_tag, TextDecodeFallback::Lossy).as_deref(),
+			Some("F\u{FFFD}(")
+		);
+
+		let (_, diagnostics) = diagnostics::collect(|| parse(&utf8_tag, TextDecodeFallback::Lossy));
+		assert_eq!(
+			diagnostics,
+			[ParseDiagnostic::TextDecodeFallback {
+				encoding: TextEncoding::UTF8
+			}]
+		);
+
+		// A Latin-1 TIT2 frame, actually containing "Привет" in Windows-1251
+		#[rustfmt::skip]
+		let latin1_tag = [
+			b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 17,
+			b'T', b'I', b'T', b'2', 0, 0, 0, 7, 0, 0,
+			0, 0xCF, 0xF0, 0xE8, 0xE2, 0xE5, 0xF2,
+		];
+
+		// Latin-1 can't fail, so only a custom fallback is used
+		assert_eq!(
+			parse(&latin1_tag, TextDecodeFallback::Lossy).as_deref(),
+			Some("\u{CF}\u{F0}\u{E8}\u{E2}\u{E5}\u{F2}")
+		);
+		assert_eq!(
+			parse(&latin1_tag, TextDecodeFallback::Custom(decode_cp1251)).as_deref(),
+			Some("Привет")
+		);
+
+		// Plain ASCII is left alone
+		#[rustfmt::skip]
+		let ascii_tag = [
+			b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 14,
+			b'T', b'I', b'T', b'2', 0, 0, 0, 4, 0, 0,
+			0, b'F', b'o', b'o',
+		];
+
+		let (title, diagnostics) =
+			diagnostics::collect(|| parse(&ascii_tag, TextDecodeFallback::Custom(decode_cp1251)));
+		assert_eq!(title.as_deref(), Some("Foo"));
+		assert!(diagnostics.is_empty());
+
+		// The fallback reaches frames other than text information frames
+		#[rustfmt::skip]
+		let comment_tag = [
+			b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 18,
+			b'C', b'O', b'M', b'M', 0, 0, 0, 8, 0, 0,
+			3, b'e', b'n', b'g', 0, b'F', 0xC3, b'(',
+		];
+
+		let mut f = Cursor::new(comment_tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let parse_options = ParseOptions::new()
+			.parsing_mode(ParsingMode::Strict)
+			.text_decode_fallback(TextDecodeFallback::Lossy);
+		let id3v2 = parse_id3v2(&mut f, header, parse_options).unwrap();
+		assert_eq!(id3v2.comment().as_deref(), Some("F\u{FFFD}("));
+	}
+
+	#[test_log::test]
+	#[cfg(feature = "id3v2_compression_support")]
+	fn compressed_frames() {
+		use crate::config::ParsingMode;
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::id3::v2::{Frame, FrameFlags};
+		use crate::prelude::*;
+
+		use std::io::Cursor;
+
+		let parse_options = ParseOptions::new().parsing_mode(ParsingMode::Strict);
+
+		let mut f =
+			Cursor::new(std::fs::read("tests/tags/assets/id3v2/compressed_tit2.id3v24").unwrap());
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let id3v2 = parse_id3v2(&mut f, header, parse_options).unwrap();
+		assert_eq!(id3v2.title().as_deref(), Some("Foo title"));
+
+		// The content is decompressed, so the frame is no longer compressed
+		let Some(Frame::Text(frame)) = id3v2.frames().next() else {
+			unreachable!()
+		};
+		assert_eq!(frame.header.flags, FrameFlags::default());
+
+		// ID3v2.3 stores the decompressed size as a regular integer
+		let mut f =
+			Cursor::new(std::fs::read("tests/tags/assets/id3v2/compressed_tit2.id3v23").unwrap());
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let id3v2 = parse_id3v2(&mut f, header, parse_options).unwrap();
+		assert_eq!(
+			id3v2.title().as_deref(),
+			Some("Foo title ".repeat(20).as_str())
+		);
+	}
+
+	#[test_log::test]
+	#[cfg(feature = "id3v2_compression_support")]
+	fn compressed_frame_size_mismatch() {
+		use crate::config::ParsingMode;
+		use crate::error::{ErrorKind, Id3v2ErrorKind};
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::prelude::*;
+
+		use std::io::Cursor;
+
+		let mut tag = std::fs::read("tests/tags/assets/id3v2/compressed_tit2.id3v24").unwrap();
+
+		// Bump the data length indicator
+		assert_eq!(tag[23], 10);
+		tag[23] = 11;
+
+		let mut f = Cursor::new(&tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let err = parse_id3v2(
+			&mut f,
+			header,
+			ParseOptions::new().parsing_mode(ParsingMode::Strict),
+		)
+		.unwrap_err();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::Id3v2(e) if matches!(
+				e.kind(),
+				Id3v2ErrorKind::DecompressedSizeMismatch { expected: 11, actual: 10 }
+			)
+		));
+
+		let mut f = Cursor::new(&tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let id3v2 = parse_id3v2(
+			&mut f,
+			header,
+			ParseOptions::new().parsing_mode(ParsingMode::Relaxed),
+		)
+		.unwrap();
+		assert_eq!(id3v2.title().as_deref(), Some("Foo title"));
+	}
+
+	#[test_log::test]
+	fn undecompressable_frame() {
+		use crate::config::ParsingMode;
+		use crate::id3::v2::header::Id3v2Header;
+		use crate::prelude::*;
+
+		use std::io::Cursor;
+
+		// A TIT2 frame with the compression and data length indicator flags set, whose content
+		// isn't valid zlib. Without support, the content is never touched.
+		let mut tit2_content = 10_u32.to_be_bytes().to_vec();
+		tit2_content.extend(b"\0\0\0\0\0\0\0\0\0\0");
+
+		let mut frames = b"TIT2".to_vec();
+		frames.extend((tit2_content.len() as u32).to_be_bytes());
+		frames.extend([0x00, 0x09]);
+		frames.extend(tit2_content);
+
+		// Followed by a plain TPE1 frame
+		frames.extend(b"TPE1\0\0\0\x04\0\0\0Bar");
+
+		// All sizes are < 128, so they're the same when synchsafe
+		let mut tag = b"ID3\x04\0\0".to_vec();
+		tag.extend((frames.len() as u32).to_be_bytes());
+		tag.extend(frames);
+
+		let mut f = Cursor::new(&tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		assert!(parse_id3v2(
+			&mut f,
+			header,
+			ParseOptions::new().parsing_mode(ParsingMode::Strict),
+		)
+		.is_err());
+
+		// The frame is skipped, but the rest of the tag is still read
+		let mut f = Cursor::new(&tag);
+		let header = Id3v2Header::parse(&mut f).unwrap();
+		let id3v2 = parse_id3v2(
+			&mut f,
+			header,
+			ParseOptions::new().parsing_mode(ParsingMode::Relaxed),
+		)
+		.unwrap();
+		assert_eq!(id3v2.title(), None);
+		assert_eq!(id3v2.artist().as_deref(), Some("Bar"));
+	}
 }
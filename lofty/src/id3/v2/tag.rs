@@ -2,31 +2,39 @@
 mod tests;
 
 use super::frame::{Frame, EMPTY_CONTENT_DESCRIPTOR};
-use super::header::{Id3v2TagFlags, Id3v2Version};
-use crate::config::{global_options, WriteOptions};
-use crate::error::{LoftyError, Result};
+use super::header::{ExtendedHeader, Id3v2TagFlags, Id3v2Version};
+use crate::config::{global_options, ParseOptions, PrettyPrintOptions, WriteOptions};
+use crate::error::{LoftyError, ParseDiagnostic, Result};
 use crate::id3::v1::GENRES;
 use crate::id3::v2::frame::{FrameRef, MUSICBRAINZ_UFID_OWNER};
 use crate::id3::v2::items::{
-	AttachedPictureFrame, CommentFrame, ExtendedTextFrame, ExtendedUrlFrame, TextInformationFrame,
+	AttachedPictureFrame, ChapterFrame, CommentFrame, ExtendedTextFrame, ExtendedUrlFrame,
+	PlayCounterFrame, PopularimeterFrame, TableOfContentsFrame, TextInformationFrame,
 	UniqueFileIdentifierFrame, UnsynchronizedTextFrame, UrlLinkFrame,
 };
+use crate::id3::v2::read::parse_id3v2;
 use crate::id3::v2::util::mappings::TIPL_MAPPINGS;
 use crate::id3::v2::util::pairs::{
 	format_number_pair, set_number, NUMBER_PAIR_KEYS, NUMBER_PAIR_SEPARATOR,
 };
-use crate::id3::v2::{BinaryFrame, FrameHeader, FrameId, KeyValueFrame, TimestampFrame};
+use crate::id3::v2::{
+	BinaryFrame, ChannelType, FrameFlags, FrameHeader, FrameId, Id3v2Header, KeyValueFrame,
+	SyncTextContentType, TimestampFrame,
+};
+use crate::macros::err;
 use crate::mp4::AdvisoryRating;
 use crate::picture::{Picture, PictureType, TOMBSTONE_PICTURE};
 use crate::tag::companion_tag::CompanionTag;
-use crate::tag::items::{Lang, Timestamp, UNKNOWN_LANGUAGE};
+use crate::tag::items::{Lang, Popularimeter, Timestamp, UNKNOWN_LANGUAGE};
 use crate::tag::{Accessor, ItemKey, ItemValue, MergeTag, SplitTag, Tag, TagExt, TagItem, TagType};
-use crate::util::flag_item;
 use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::pretty_print::{PrettyPrinter, Value};
 use crate::util::text::{decode_text, TextDecodeOptions, TextEncoding};
+use crate::util::{diagnostics, flag_item};
 
 use std::borrow::Cow;
-use std::io::{Cursor, Write};
+use std::fmt;
+use std::io::{Cursor, Read, Write};
 use std::iter::Peekable;
 use std::ops::Deref;
 use std::str::FromStr;
@@ -34,9 +42,22 @@ use std::str::FromStr;
 use lofty_attr::tag;
 
 const INVOLVED_PEOPLE_LIST_ID: &str = "TIPL";
+const MUSICIAN_CREDITS_LIST_ID: &str = "TMCL";
 
 const V4_MULTI_VALUE_SEPARATOR: char = '\0';
 
+// Splits the values of a (possibly) multi-value text frame
+//
+// Trailing separators will not create any empty values, though a lone empty string is still
+// considered a single (empty) value.
+pub(in crate::id3::v2) fn split_multi_value_text(value: &str) -> impl Iterator<Item = &str> {
+	let value = value.trim_end_matches(V4_MULTI_VALUE_SEPARATOR);
+	let is_empty = value.is_empty();
+	value
+		.split(V4_MULTI_VALUE_SEPARATOR)
+		.filter(move |v| is_empty || !v.is_empty())
+}
+
 // Used exclusively for `Accessor` convenience methods
 fn remove_separators_from_frame_text(value: &str, version: Id3v2Version) -> Cow<'_, str> {
 	if !value.contains(V4_MULTI_VALUE_SEPARATOR) || version != Id3v2Version::V4 {
@@ -94,32 +115,51 @@ macro_rules! impl_accessor {
 ///    * `description` - Left empty, which is invalid if there are more than one of these frames. These frames can only be identified
 ///    by their descriptions, and as such they are expected to be unique for each.
 /// * [`ItemKey::Unknown("WXXX" | "TXXX")`](ItemKey::Unknown) - These frames are also identified by their descriptions.
+/// * URL items (e.g. [`ItemKey::TrackArtistUrl`]) - URL frames can only hold Latin-1 text, so any other characters are
+/// percent-encoded as UTF-8 (e.g. `"€"` becomes `"%E2%82%AC"`).
 ///
 /// ### To `Tag`
 ///
 /// * TXXX/WXXX - These frames will be stored as an [`ItemKey`] by their description. Some variants exist for these descriptions, such as the one for `ReplayGain`,
 /// otherwise [`ItemKey::Unknown`] will be used.
-/// * Frames that require a language (COMM/USLT) - With ID3v2 being the only format that allows for language-specific items, this information is not retained.
+/// * Frames that require a language (COMM/USLT) - The language and description are stored in the [`TagItem`], see [`TagItem::lang`]
+/// and [`TagItem::description`]. Frames in multiple languages are stored as separate items, and will be written back as
+/// separate frames.
 /// * POPM - These frames will be stored as a raw [`ItemValue::Binary`] value under the [`ItemKey::Popularimeter`] key.
-///
-/// ## Special Frames
-///
-/// ID3v2 has `GEOB` and `SYLT` frames, which are not parsed by default, instead storing them as [`FrameType::Binary`].
-/// They can easily be parsed with [`GeneralEncapsulatedObject::parse`](crate::id3::v2::GeneralEncapsulatedObject::parse)
-/// and [`SynchronizedText::parse`](crate::id3::v2::SynchronizedTextFrame::parse) respectively, and converted back to binary with
-/// [`GeneralEncapsulatedObject::as_bytes`](crate::id3::v2::GeneralEncapsulatedObject::as_bytes) and
-/// [`SynchronizedText::as_bytes`](crate::id3::v2::SynchronizedTextFrame::as_bytes) for writing.
-#[derive(PartialEq, Eq, Debug, Clone)]
+///   They can be parsed with [`Popularimeter::from_tag_item`](crate::tag::items::Popularimeter::from_tag_item).
+/// * RVA2 - The master channel of frames identified as "track" or "album" will be stored as ReplayGain items
+///   (e.g. [`ItemKey::ReplayGainTrackGain`]), unless those items already exist. The frames themselves are retained,
+///   and changes to the items are written back to them.
+/// * SYLT - The text of frames with [`SyncTextContentType::Lyrics`](crate::id3::v2::SyncTextContentType::Lyrics) will be
+///   stored as [`ItemKey::Lyrics`] items (see [`SynchronizedTextFrame::text`](crate::id3::v2::SynchronizedTextFrame::text)), unless there are already lyrics from
+///   USLT frames. The frames themselves are retained. If the item is removed (or replaced by one with another language
+///   or description), the frame is removed as well. If its text is changed, it is written as a separate USLT frame, as
+///   the timestamps can't be recreated.
+#[derive(Eq, Debug, Clone)]
 #[tag(
 	description = "An `ID3v2` tag",
-	supported_formats(Aac, Aiff, Mpeg, Wav, read_only(Ape, Flac, Mpc))
+	supported_formats(Aac, Aiff, Dsf, Mpeg, Wav, read_only(Ape, Dff, Flac, Mpc, Shn, Tta))
 )]
 pub struct Id3v2Tag {
 	flags: Id3v2TagFlags,
 	pub(super) original_version: Id3v2Version,
+	pub(super) original_flags: Option<Id3v2TagFlags>,
+	pub(super) original_size: Option<u32>,
+	pub(super) extended_header: Option<ExtendedHeader>,
 	pub(crate) frames: Vec<Frame<'static>>,
 }
 
+// The original size and flags only describe the tag as it was read, two tags read from differently
+// padded files are still the same tag
+impl PartialEq for Id3v2Tag {
+	fn eq(&self, other: &Self) -> bool {
+		self.flags == other.flags
+			&& self.original_version == other.original_version
+			&& self.extended_header == other.extended_header
+			&& self.frames == other.frames
+	}
+}
+
 impl IntoIterator for Id3v2Tag {
 	type Item = Frame<'static>;
 	type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -143,6 +183,9 @@ impl Default for Id3v2Tag {
 		Self {
 			flags: Id3v2TagFlags::default(),
 			original_version: Id3v2Version::V4,
+			original_flags: None,
+			original_size: None,
+			extended_header: None,
 			frames: Vec::new(),
 		}
 	}
@@ -164,6 +207,76 @@ impl Id3v2Tag {
 		Self::default()
 	}
 
+	/// Parse an `Id3v2Tag` from a reader
+	///
+	/// This accepts exactly what [`TagExt::dump_to`] produces: a header, followed by the frames,
+	/// padding, and a footer (if [`Id3v2TagFlags::footer`] is set). The reader is expected to be
+	/// positioned at the start of the header (the `"ID3"` identifier), and will be positioned at
+	/// the end of the tag upon return.
+	///
+	/// NOTE: An empty tag is written as nothing at all, so there is nothing to parse in that case.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * The header is invalid, see [`Id3v2Header::parse`]
+	/// * Any of the frames are invalid, depending on the [`ParsingMode`](crate::config::ParsingMode)
+	/// * [`Id3v2TagFlags::footer`] is set, but the footer is missing
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, WriteOptions};
+	/// use lofty::id3::v2::Id3v2Tag;
+	/// use lofty::tag::{Accessor, TagExt};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Id3v2Tag::new();
+	/// id3v2_tag.set_title(String::from("Foo title"));
+	///
+	/// let mut bytes = Vec::new();
+	/// id3v2_tag.dump_to(&mut bytes, WriteOptions::default())?;
+	///
+	/// let parsed_tag = Id3v2Tag::parse(&mut Cursor::new(bytes), ParseOptions::new())?;
+	/// assert_eq!(parsed_tag.title().as_deref(), Some("Foo title"));
+	/// # Ok(()) }
+	/// ```
+	pub fn parse<R>(reader: &mut R, parse_options: ParseOptions) -> Result<Self>
+	where
+		R: Read,
+	{
+		let header = Id3v2Header::parse(reader)?;
+		let tag = parse_id3v2(reader, header, parse_options)?;
+
+		if header.flags.footer {
+			let mut footer = [0; 10];
+			reader.read_exact(&mut footer)?;
+
+			if &footer[..3] != b"3DI" {
+				err!(FakeTag);
+			}
+		}
+
+		Ok(tag)
+	}
+
+	/// Same as [`Id3v2Tag::parse`], also returning any [`ParseDiagnostic`]s
+	///
+	/// # Errors
+	///
+	/// See [`Id3v2Tag::parse`]
+	pub fn parse_with_diagnostics<R>(
+		reader: &mut R,
+		parse_options: ParseOptions,
+	) -> Result<(Self, Vec<ParseDiagnostic>)>
+	where
+		R: Read,
+	{
+		let (tag, diagnostics) = diagnostics::collect(|| Self::parse(reader, parse_options));
+		Ok((tag?, diagnostics))
+	}
+
 	/// Returns the [`Id3v2TagFlags`]
 	pub fn flags(&self) -> &Id3v2TagFlags {
 		&self.flags
@@ -181,9 +294,128 @@ impl Id3v2Tag {
 	pub fn original_version(&self) -> Id3v2Version {
 		self.original_version
 	}
+
+	/// The flags of the tag as it was read
+	///
+	/// Unlike [`Id3v2Tag::flags`], this isn't changed by [`Id3v2Tag::set_flags`]. This will be `None`
+	/// if the tag wasn't read from a file.
+	pub fn original_flags(&self) -> Option<&Id3v2TagFlags> {
+		self.original_flags.as_ref()
+	}
+
+	/// The total size of the tag as it was read, in bytes
+	///
+	/// This includes the header, extended header, padding, and footer. This will be `None` if the
+	/// tag wasn't read from a file.
+	///
+	/// See [`WriteOptions::preserve_id3v2_size`](crate::config::WriteOptions::preserve_id3v2_size)
+	/// for writing a tag over the existing one.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, WriteOptions};
+	/// use lofty::id3::v2::Id3v2Tag;
+	/// use lofty::tag::{Accessor, TagExt};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Id3v2Tag::new();
+	/// id3v2_tag.set_title(String::from("Foo title"));
+	/// assert_eq!(id3v2_tag.original_size(), None);
+	///
+	/// let mut bytes = Vec::new();
+	/// id3v2_tag.dump_to(&mut bytes, WriteOptions::default())?;
+	///
+	/// let parsed_tag = Id3v2Tag::parse(&mut Cursor::new(&bytes), ParseOptions::new())?;
+	/// assert_eq!(parsed_tag.original_size(), Some(bytes.len() as u32));
+	/// # Ok(()) }
+	/// ```
+	pub fn original_size(&self) -> Option<u32> {
+		self.original_size
+	}
+
+	/// The extended header of the tag, if one was read
+	///
+	/// This is only informational, and is not used when writing. To write an extended header,
+	/// see [`Id3v2TagFlags::crc`] and [`Id3v2TagFlags::restrictions`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, WriteOptions};
+	/// use lofty::id3::v2::{Id3v2Tag, Id3v2TagFlags};
+	/// use lofty::tag::{Accessor, TagExt};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut id3v2_tag = Id3v2Tag::new();
+	/// id3v2_tag.set_title(String::from("Foo title"));
+	///
+	/// let mut flags = Id3v2TagFlags::default();
+	/// flags.crc = true;
+	/// id3v2_tag.set_flags(flags);
+	///
+	/// let mut bytes = Vec::new();
+	/// id3v2_tag.dump_to(&mut bytes, WriteOptions::default())?;
+	///
+	/// let parsed_tag = Id3v2Tag::parse(&mut Cursor::new(bytes), ParseOptions::new())?;
+	/// let extended_header = parsed_tag.extended_header().unwrap();
+	/// assert!(extended_header.crc.is_some());
+	/// # Ok(()) }
+	/// ```
+	pub fn extended_header(&self) -> Option<&ExtendedHeader> {
+		self.extended_header.as_ref()
+	}
 }
 
 impl Id3v2Tag {
+	/// Returns an iterator over all [`Frame`]s in the tag
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::Id3v2Tag;
+	/// use lofty::tag::Accessor;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.set_title(String::from("Foo title"));
+	/// tag.set_artist(String::from("Bar artist"));
+	///
+	/// let ids = tag.frames().map(|f| f.id_str()).collect::<Vec<_>>();
+	/// assert_eq!(ids, ["TIT2", "TPE1"]);
+	/// ```
+	pub fn frames(&self) -> impl Iterator<Item = &Frame<'static>> + Clone {
+		self.frames.iter()
+	}
+
+	/// Returns an iterator that allows modifying each [`Frame`] in the tag
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, FrameFlags, Id3v2Tag};
+	/// use lofty::tag::Accessor;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.set_title(String::from("Foo title"));
+	/// tag.set_artist(String::from("Bar artist"));
+	///
+	/// let read_only = FrameFlags {
+	/// 	read_only: true,
+	/// 	..FrameFlags::default()
+	/// };
+	///
+	/// for frame in tag.frames_mut() {
+	/// 	frame.set_flags(read_only);
+	/// }
+	///
+	/// assert!(tag.frames().all(|f| f.flags().read_only));
+	/// ```
+	pub fn frames_mut(&mut self) -> impl Iterator<Item = &mut Frame<'static>> {
+		self.frames.iter_mut()
+	}
+
 	/// Gets a [`Frame`] from an id
 	pub fn get(&self, id: &FrameId<'_>) -> Option<&Frame<'static>> {
 		self.frames.iter().find(|f| f.id() == id)
@@ -253,12 +485,95 @@ impl Id3v2Tag {
 	/// ```
 	pub fn get_texts(&self, id: &FrameId<'_>) -> Option<impl Iterator<Item = &str>> {
 		if let Some(Frame::Text(TextInformationFrame { value, .. })) = self.get(id) {
-			return Some(value.split(V4_MULTI_VALUE_SEPARATOR));
+			return Some(split_multi_value_text(value));
 		}
 
 		None
 	}
 
+	/// Gets the role/person pairs of the involved people list (`TIPL`)
+	///
+	/// An ID3v2.3 `IPLS` frame is read as a `TIPL` frame, unless [`ParseOptions::implicit_conversions`]
+	/// is disabled.
+	///
+	/// Some roles are also mapped to [`ItemKey`]s when converting to a [`Tag`] (e.g. `"producer"`
+	/// to [`ItemKey::Producer`]), any others are kept in the tag.
+	///
+	/// [`ParseOptions::implicit_conversions`]: crate::config::ParseOptions::implicit_conversions
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, FrameId, Id3v2Tag, KeyValueFrame};
+	/// use lofty::TextEncoding;
+	/// use std::borrow::Cow;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.insert(Frame::KeyValue(KeyValueFrame::new(
+	/// 	FrameId::Valid(Cow::Borrowed("TIPL")),
+	/// 	TextEncoding::UTF8,
+	/// 	vec![
+	/// 		(String::from("producer"), String::from("Foo")),
+	/// 		(String::from("engineer"), String::from("Bar")),
+	/// 	],
+	/// )));
+	///
+	/// let mut involved_people = tag.involved_people();
+	/// assert_eq!(involved_people.next(), Some(("producer", "Foo")));
+	/// assert_eq!(involved_people.next(), Some(("engineer", "Bar")));
+	/// assert_eq!(involved_people.next(), None);
+	/// ```
+	pub fn involved_people(&self) -> impl Iterator<Item = (&str, &str)> + Clone {
+		self.key_value_pairs(INVOLVED_PEOPLE_LIST_ID)
+	}
+
+	/// Gets the instrument/person pairs of the musician credits list (`TMCL`)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, FrameId, Id3v2Tag, KeyValueFrame};
+	/// use lofty::TextEncoding;
+	/// use std::borrow::Cow;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.insert(Frame::KeyValue(KeyValueFrame::new(
+	/// 	FrameId::Valid(Cow::Borrowed("TMCL")),
+	/// 	TextEncoding::UTF8,
+	/// 	vec![
+	/// 		(String::from("bass"), String::from("Foo")),
+	/// 		(String::from("drums"), String::from("Bar")),
+	/// 	],
+	/// )));
+	///
+	/// // Who played bass?
+	/// let bassist = tag
+	/// 	.musician_credits()
+	/// 	.find_map(|(instrument, person)| (instrument == "bass").then_some(person));
+	/// assert_eq!(bassist, Some("Foo"));
+	/// ```
+	pub fn musician_credits(&self) -> impl Iterator<Item = (&str, &str)> + Clone {
+		self.key_value_pairs(MUSICIAN_CREDITS_LIST_ID)
+	}
+
+	fn key_value_pairs<'a>(
+		&'a self,
+		id: &'a str,
+	) -> impl Iterator<Item = (&'a str, &'a str)> + Clone {
+		self.frames
+			.iter()
+			.filter_map(move |frame| match frame {
+				Frame::KeyValue(KeyValueFrame {
+					header: FrameHeader { id: frame_id, .. },
+					key_value_pairs,
+					..
+				}) if frame_id.as_str() == id => Some(key_value_pairs),
+				_ => None,
+			})
+			.flatten()
+			.map(|(key, value)| (key.as_str(), value.as_str()))
+	}
+
 	/// Gets the text for a user-defined frame
 	///
 	/// NOTE: If the tag is [`Id3v2Version::V4`], there could be multiple values separated by null characters (`'\0'`).
@@ -330,12 +645,10 @@ impl Id3v2Tag {
 	/// Inserts a [`Frame`]
 	///
 	/// This will replace any frame of the same id (**or description!** See [`ExtendedTextFrame`])
+	///
+	/// NOTE: Frames marked as [read-only](FrameFlags::read_only) will be replaced as well. To avoid
+	///       this, see [`Id3v2Tag::insert_respecting_read_only`].
 	pub fn insert(&mut self, frame: Frame<'static>) -> Option<Frame<'static>> {
-		// Some frames can only appear once in a tag, handle them separately
-		const ONE_PER_TAG: [&str; 11] = [
-			"MCDI", "ETCO", "MLLT", "SYTC", "RVRB", "PCNT", "RBUF", "POSS", "OWNE", "SEEK", "ASPI",
-		];
-
 		if ONE_PER_TAG.contains(&frame.id_str()) {
 			let ret = self.remove(frame.id()).next();
 			self.frames.push(frame);
@@ -352,6 +665,66 @@ impl Id3v2Tag {
 		replaced
 	}
 
+	/// Inserts a [`Frame`], unless it would replace a read-only frame
+	///
+	/// This is the same as [`Id3v2Tag::insert`], except that frames with the
+	/// [`FrameFlags::read_only`] flag set will be left untouched.
+	///
+	/// # Errors
+	///
+	/// If `frame` would replace a read-only frame, it is given back.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, FrameFlags, FrameId, Id3v2Tag, TextInformationFrame};
+	/// use lofty::prelude::*;
+	/// use lofty::TextEncoding;
+	/// use std::borrow::Cow;
+	///
+	/// let title = |value: &str| {
+	/// 	Frame::Text(TextInformationFrame::new(
+	/// 		FrameId::Valid(Cow::Borrowed("TIT2")),
+	/// 		TextEncoding::UTF8,
+	/// 		String::from(value),
+	/// 	))
+	/// };
+	///
+	/// let mut tag = Id3v2Tag::new();
+	///
+	/// let mut read_only_title = title("Foo title");
+	/// read_only_title.set_flags(FrameFlags {
+	/// 	read_only: true,
+	/// 	..FrameFlags::default()
+	/// });
+	/// tag.insert(read_only_title);
+	///
+	/// assert!(tag.insert_respecting_read_only(title("Bar title")).is_err());
+	/// assert_eq!(tag.title().as_deref(), Some("Foo title"));
+	/// ```
+	#[allow(clippy::result_large_err)]
+	pub fn insert_respecting_read_only(
+		&mut self,
+		frame: Frame<'static>,
+	) -> std::result::Result<Option<Frame<'static>>, Frame<'static>> {
+		let one_per_tag = ONE_PER_TAG.contains(&frame.id_str());
+		let replaces_read_only = self.frames.iter().any(|existing| {
+			let replaced = if one_per_tag {
+				existing.id() == frame.id()
+			} else {
+				*existing == frame
+			};
+
+			replaced && existing.flags().read_only
+		});
+
+		if replaces_read_only {
+			return Err(frame);
+		}
+
+		Ok(self.insert(frame))
+	}
+
 	/// Removes a user-defined text frame (`TXXX`) by its description
 	///
 	/// This will return the matching frame.
@@ -426,17 +799,41 @@ impl Id3v2Tag {
 	/// # Ok(()) }
 	/// ```
 	pub fn remove(&mut self, id: &FrameId<'_>) -> impl Iterator<Item = Frame<'static>> + '_ {
-		// TODO: drain_filter
-		let mut split_idx = 0_usize;
+		self.remove_all(id)
+	}
 
-		for read_idx in 0..self.frames.len() {
-			if self.frames[read_idx].id() == id {
-				self.frames.swap(split_idx, read_idx);
-				split_idx += 1;
-			}
-		}
+	/// Removes all [`Frame`]s with the given id, returning them
+	///
+	/// This is the same as [`Id3v2Tag::remove`]. The order of the remaining frames is preserved.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{FrameId, Id3v2Tag};
+	/// use lofty::tag::Accessor;
+	/// use std::borrow::Cow;
+	///
+	/// const TITLE_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TIT2"));
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.set_artist(String::from("Foo artist"));
+	/// tag.set_title(String::from("Bar title"));
+	/// tag.set_album(String::from("Baz album"));
+	///
+	/// let removed = tag.remove_all(&TITLE_ID).collect::<Vec<_>>();
+	/// assert_eq!(removed.len(), 1);
+	///
+	/// let remaining = tag.frames().map(|f| f.id_str()).collect::<Vec<_>>();
+	/// assert_eq!(remaining, ["TPE1", "TALB"]);
+	/// ```
+	pub fn remove_all(&mut self, id: &FrameId<'_>) -> impl Iterator<Item = Frame<'static>> + '_ {
+		// TODO: Vec::extract_if, once stabilized
+		let (removed, retained) = std::mem::take(&mut self.frames)
+			.into_iter()
+			.partition::<Vec<_>, _>(|frame| frame.id() == id);
 
-		self.frames.drain(..split_idx)
+		self.frames = retained;
+		removed.into_iter()
 	}
 
 	fn take_first(&mut self, id: &FrameId<'_>) -> Option<Frame<'static>> {
@@ -447,6 +844,33 @@ impl Id3v2Tag {
 	}
 
 	/// Retains [`Frame`]s by evaluating the predicate
+	///
+	/// This has the same semantics as [`Vec::retain`], the order of the retained frames is preserved.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, Id3v2Tag, PrivateFrame};
+	/// use lofty::tag::TagExt;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.insert(Frame::Private(PrivateFrame::new(
+	/// 	String::from("com.apple.iTunes"),
+	/// 	vec![1, 2, 3],
+	/// )));
+	/// tag.insert(Frame::Private(PrivateFrame::new(
+	/// 	String::from("WM/MediaClassPrimaryID"),
+	/// 	vec![4, 5, 6],
+	/// )));
+	///
+	/// // Drop all `PRIV` frames belonging to iTunes
+	/// tag.retain(|frame| match frame {
+	/// 	Frame::Private(private) => !private.owner.starts_with("com.apple"),
+	/// 	_ => true,
+	/// });
+	///
+	/// assert_eq!(tag.len(), 1);
+	/// ```
 	pub fn retain<P>(&mut self, predicate: P)
 	where
 		P: FnMut(&Frame<'_>) -> bool,
@@ -514,6 +938,202 @@ impl Id3v2Tag {
 		})
 	}
 
+	/// Returns all `CHAP` frames, sorted by their start time
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{ChapterFrame, Id3v2Tag};
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.insert_chapter(ChapterFrame::new(String::from("chp1"), 5000, 10000));
+	/// tag.insert_chapter(ChapterFrame::new(String::from("chp0"), 0, 5000));
+	///
+	/// let element_ids = tag
+	/// 	.chapters()
+	/// 	.map(|chapter| chapter.element_id.as_str())
+	/// 	.collect::<Vec<_>>();
+	/// assert_eq!(element_ids, ["chp0", "chp1"]);
+	/// ```
+	pub fn chapters(&self) -> impl Iterator<Item = &ChapterFrame<'static>> + Clone {
+		let mut chapters = self
+			.frames
+			.iter()
+			.filter_map(|frame| match frame {
+				Frame::Chapter(chapter) => Some(chapter),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+
+		// Chapters can be stored in any order
+		chapters.sort_by_key(|chapter| chapter.start_time);
+		chapters.into_iter()
+	}
+
+	/// Inserts a `CHAP` frame
+	///
+	/// This will replace any chapter with the same element ID, see [`Id3v2Tag::insert`].
+	///
+	/// NOTE: This doesn't add the chapter to a [`TableOfContentsFrame`].
+	pub fn insert_chapter(&mut self, chapter: ChapterFrame<'static>) -> Option<Frame<'static>> {
+		self.insert(Frame::Chapter(chapter))
+	}
+
+	/// Returns all `CTOC` frames
+	pub fn tables_of_contents(
+		&self,
+	) -> impl Iterator<Item = &TableOfContentsFrame<'static>> + Clone {
+		self.frames.iter().filter_map(|frame| match frame {
+			Frame::TableOfContents(toc) => Some(toc),
+			_ => None,
+		})
+	}
+
+	/// Returns all `POPM` frames
+	pub fn popularimeters(&self) -> impl Iterator<Item = &PopularimeterFrame<'static>> + Clone {
+		self.frames.iter().filter_map(|frame| match frame {
+			Frame::Popularimeter(popularimeter) => Some(popularimeter),
+			_ => None,
+		})
+	}
+
+	/// Gets the rating from a `POPM` frame
+	///
+	/// If `email` is `None`, the first `POPM` frame is used. Otherwise, it's the frame with the
+	/// same email.
+	///
+	/// See [`PopularimeterFrame::rating`] for the meaning of the value.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::Id3v2Tag;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.set_rating(Some("Windows Media Player 9 Series"), 196);
+	/// tag.set_rating(Some("MusicBee"), 255);
+	///
+	/// assert_eq!(tag.rating(None), Some(196));
+	/// assert_eq!(tag.rating(Some("MusicBee")), Some(255));
+	/// assert_eq!(tag.rating(Some("foo@bar.com")), None);
+	/// ```
+	pub fn rating(&self, email: Option<&str>) -> Option<u8> {
+		self.popularimeters()
+			.find(|popularimeter| match email {
+				Some(email) => popularimeter.email == email,
+				None => true,
+			})
+			.map(|popularimeter| popularimeter.rating)
+	}
+
+	/// Sets the rating of a `POPM` frame
+	///
+	/// If `email` is `None`, the first `POPM` frame is updated. Otherwise, it's the frame with the
+	/// same email. If there is no such frame, a new one is created, with an empty email if none is
+	/// provided.
+	///
+	/// The play counter of an existing frame is kept.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::{Frame, Id3v2Tag, PopularimeterFrame};
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.insert(Frame::Popularimeter(PopularimeterFrame::new(
+	/// 	String::from("foo@bar.com"),
+	/// 	64,
+	/// 	10,
+	/// )));
+	///
+	/// tag.set_rating(None, 128);
+	///
+	/// let popularimeter = tag.popularimeters().next().unwrap();
+	/// assert_eq!(popularimeter.rating, 128);
+	/// assert_eq!(popularimeter.counter, 10);
+	/// ```
+	pub fn set_rating(&mut self, email: Option<&str>, rating: u8) {
+		let existing = self.frames.iter_mut().find_map(|frame| match frame {
+			Frame::Popularimeter(popularimeter)
+				if email.is_none_or(|email| popularimeter.email == email) =>
+			{
+				Some(popularimeter)
+			},
+			_ => None,
+		});
+
+		match existing {
+			Some(popularimeter) => popularimeter.rating = rating,
+			None => self
+				.frames
+				.push(Frame::Popularimeter(PopularimeterFrame::new(
+					email.unwrap_or_default().to_owned(),
+					rating,
+					0,
+				))),
+		}
+	}
+
+	/// Gets the play count from the `PCNT` frame
+	///
+	/// NOTE: This is separate from the per-user counters of `POPM` frames, see [`Id3v2Tag::popularimeters`].
+	pub fn play_count(&self) -> Option<u64> {
+		self.frames.iter().find_map(|frame| match frame {
+			Frame::PlayCounter(play_counter) => Some(play_counter.counter),
+			_ => None,
+		})
+	}
+
+	/// Sets the play count of the `PCNT` frame, creating it if necessary
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::Id3v2Tag;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	/// assert_eq!(tag.play_count(), None);
+	///
+	/// tag.set_play_count(5);
+	/// assert_eq!(tag.play_count(), Some(5));
+	/// ```
+	pub fn set_play_count(&mut self, count: u64) {
+		match self.play_counter_mut() {
+			Some(play_counter) => play_counter.counter = count,
+			None => self
+				.frames
+				.push(Frame::PlayCounter(PlayCounterFrame::new(count))),
+		}
+	}
+
+	/// Increments the play count of the `PCNT` frame, creating it if necessary
+	///
+	/// The counter saturates at [`u64::MAX`]. This returns the new play count.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::Id3v2Tag;
+	///
+	/// let mut tag = Id3v2Tag::new();
+	///
+	/// assert_eq!(tag.increment_play_count(), 1);
+	/// assert_eq!(tag.increment_play_count(), 2);
+	/// assert_eq!(tag.play_count(), Some(2));
+	/// ```
+	pub fn increment_play_count(&mut self) -> u64 {
+		let count = self.play_count().unwrap_or(0).saturating_add(1);
+		self.set_play_count(count);
+		count
+	}
+
+	fn play_counter_mut(&mut self) -> Option<&mut PlayCounterFrame<'static>> {
+		self.frames.iter_mut().find_map(|frame| match frame {
+			Frame::PlayCounter(play_counter) => Some(play_counter),
+			_ => None,
+		})
+	}
+
 	fn split_num_pair(&self, id: &FrameId<'_>) -> (Option<u32>, Option<u32>) {
 		if let Some(Frame::Text(TextInformationFrame { ref value, .. })) = self.get(id) {
 			let mut split = value
@@ -553,6 +1173,220 @@ impl Id3v2Tag {
 		None
 	}
 
+	/// Writes a line-oriented listing of the frames to `writer`
+	///
+	/// Frames are keyed by their ID. Frames that can appear multiple times with different descriptors
+	/// add them to the key:
+	///
+	/// * `COMM`, `USLT`, and `SYLT` - The language and description (ex. `COMM:eng:Description`)
+	/// * `TXXX`, `WXXX`, and `GEOB` - The description (ex. `TXXX:BARCODE`)
+	/// * `POPM` - The email
+	/// * `RVA2` - The identification
+	/// * `UFID` and `PRIV` - The owner
+	/// * `CHAP` and `CTOC` - The element ID
+	///
+	/// See [`PrettyPrintOptions`] for the format.
+	///
+	/// # Errors
+	///
+	/// * Writing to `writer` fails
+	pub fn pretty_print(
+		&self,
+		writer: &mut impl fmt::Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		fn text(text: &str) -> Value<'_> {
+			Value::Text(Cow::Borrowed(text))
+		}
+
+		let mut printer = PrettyPrinter::default();
+		for frame in &self.frames {
+			let id = frame.id_str();
+			let (key, ty, value) = match frame {
+				Frame::Comment(CommentFrame {
+					language,
+					description,
+					content,
+					..
+				})
+				| Frame::UnsynchronizedText(UnsynchronizedTextFrame {
+					language,
+					description,
+					content,
+					..
+				}) => {
+					let language = String::from_utf8_lossy(language);
+					let key = format!("{id}:{language}:{description}");
+					let ty = if matches!(frame, Frame::Comment(_)) {
+						"Comment"
+					} else {
+						"UnsynchronizedText"
+					};
+
+					(Cow::Owned(key), ty, text(content))
+				},
+				Frame::Text(TextInformationFrame { value, .. }) => {
+					(Cow::Borrowed(id), "Text", text(value))
+				},
+				Frame::UserText(ExtendedTextFrame {
+					description,
+					content,
+					..
+				}) => (
+					Cow::Owned(format!("{id}:{description}")),
+					"UserText",
+					text(content),
+				),
+				Frame::Url(UrlLinkFrame { content, .. }) => {
+					(Cow::Borrowed(id), "Url", text(content))
+				},
+				Frame::UserUrl(ExtendedUrlFrame {
+					description,
+					content,
+					..
+				}) => (
+					Cow::Owned(format!("{id}:{description}")),
+					"UserUrl",
+					text(content),
+				),
+				Frame::Picture(AttachedPictureFrame { picture, .. }) => {
+					(Cow::Borrowed(id), "Picture", Value::Picture(picture))
+				},
+				Frame::Popularimeter(popularimeter) => (
+					Cow::Owned(format!("{id}:{}", popularimeter.email)),
+					"Popularimeter",
+					Value::Text(Cow::Owned(format!(
+						"rating {}, counter {}",
+						popularimeter.rating, popularimeter.counter
+					))),
+				),
+				Frame::KeyValue(KeyValueFrame {
+					key_value_pairs, ..
+				}) => {
+					let pairs = key_value_pairs
+						.iter()
+						.map(|(key, value)| format!("{key}={value}"))
+						.collect::<Vec<_>>()
+						.join("; ");
+
+					(
+						Cow::Borrowed(id),
+						"KeyValue",
+						Value::Text(Cow::Owned(pairs)),
+					)
+				},
+				Frame::RelativeVolumeAdjustment(rva2) => (
+					Cow::Owned(format!("{id}:{}", rva2.identification)),
+					"RelativeVolumeAdjustment",
+					Value::Text(Cow::Owned(format!("{} channels", rva2.channels.len()))),
+				),
+				Frame::UniqueFileIdentifier(UniqueFileIdentifierFrame {
+					owner,
+					identifier,
+					..
+				}) => {
+					// Identifiers are usually text, such as the MusicBrainz recording ID
+					let value = match std::str::from_utf8(identifier) {
+						Ok(identifier) => text(identifier),
+						Err(_) => Value::Bytes(identifier.len()),
+					};
+
+					(
+						Cow::Owned(format!("{id}:{owner}")),
+						"UniqueFileIdentifier",
+						value,
+					)
+				},
+				Frame::Ownership(ownership) => (
+					Cow::Borrowed(id),
+					"Ownership",
+					Value::Text(Cow::Owned(format!(
+						"{}, {}, {}",
+						ownership.price_paid, ownership.date_of_purchase, ownership.seller
+					))),
+				),
+				Frame::EventTimingCodes(event_timing_codes) => (
+					Cow::Borrowed(id),
+					"EventTimingCodes",
+					Value::Text(Cow::Owned(format!(
+						"{} events",
+						event_timing_codes.events.len()
+					))),
+				),
+				Frame::Private(private) => (
+					Cow::Owned(format!("{id}:{}", private.owner)),
+					"Private",
+					Value::Bytes(private.private_data.len()),
+				),
+				Frame::SynchronizedText(sync_text) => {
+					let language = String::from_utf8_lossy(&sync_text.language);
+					let description = sync_text.description.as_deref().unwrap_or_default();
+
+					(
+						Cow::Owned(format!("{id}:{language}:{description}")),
+						"SynchronizedText",
+						Value::Text(Cow::Owned(format!("{} entries", sync_text.content.len()))),
+					)
+				},
+				Frame::GeneralObject(object) => (
+					Cow::Owned(format!("{id}:{}", object.description)),
+					"GeneralObject",
+					Value::Bytes(object.data.len()),
+				),
+				Frame::Timestamp(TimestampFrame { timestamp, .. }) => (
+					Cow::Borrowed(id),
+					"Timestamp",
+					Value::Text(Cow::Owned(timestamp.to_string())),
+				),
+				Frame::Chapter(chapter) => (
+					Cow::Owned(format!("{id}:{}", chapter.element_id)),
+					"Chapter",
+					Value::Text(Cow::Owned(format!(
+						"{}ms - {}ms, {}",
+						chapter.start_time,
+						chapter.end_time,
+						chapter.title().unwrap_or_default()
+					))),
+				),
+				Frame::TableOfContents(toc) => (
+					Cow::Owned(format!("{id}:{}", toc.element_id)),
+					"TableOfContents",
+					Value::Text(Cow::Owned(toc.child_element_ids.join(", "))),
+				),
+				Frame::MusicCdIdentifier(mcdi) => {
+					let value = match mcdi.table_of_contents() {
+						Some(toc) => {
+							Value::Text(Cow::Owned(format!("{} tracks", toc.track_offsets.len())))
+						},
+						None => Value::Bytes(mcdi.data.len()),
+					};
+
+					(Cow::Borrowed(id), "MusicCdIdentifier", value)
+				},
+				Frame::PlayCounter(play_counter) => (
+					Cow::Borrowed(id),
+					"PlayCounter",
+					Value::Text(Cow::Owned(play_counter.counter.to_string())),
+				),
+				Frame::Commercial(commercial) => (
+					Cow::Borrowed(id),
+					"Commercial",
+					Value::Text(Cow::Owned(format!(
+						"{}, {}",
+						commercial.price, commercial.seller_name
+					))),
+				),
+				Frame::Binary(BinaryFrame { data, .. }) => {
+					(Cow::Borrowed(id), "Binary", Value::Bytes(data.len()))
+				},
+			};
+
+			printer.push(key, ty, value);
+		}
+
+		printer.finish(writer, options)
+	}
+
 	fn insert_number_pair(
 		&mut self,
 		id: FrameId<'static>,
@@ -617,18 +1451,23 @@ impl<'a> Iterator for GenresIter<'a> {
 }
 
 fn parse_genre(genre: &str, preserve_indexes: bool) -> &str {
-	if genre.len() > 3 {
+	// Legacy references may also appear within a null-separated list, e.g. "(17)\0Foo"
+	let reference = genre
+		.strip_prefix('(')
+		.and_then(|g| g.strip_suffix(')'))
+		.unwrap_or(genre);
+	if reference.len() > 3 {
 		return genre;
 	}
-	if let Ok(id) = genre.parse::<usize>() {
+	if let Ok(id) = reference.parse::<usize>() {
 		if id < GENRES.len() && !preserve_indexes {
 			GENRES[id]
 		} else {
 			genre
 		}
-	} else if genre == "RX" {
+	} else if reference == "RX" {
 		"Remix"
-	} else if genre == "CR" {
+	} else if reference == "CR" {
 		"Cover"
 	} else {
 		genre
@@ -721,12 +1560,18 @@ pub(super) fn new_binary_frame(id: FrameId<'_>, data: Vec<u8>) -> Frame<'_> {
 }
 
 const TITLE_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TIT2"));
+const PODCAST_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("PCST"));
 const ARTIST_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TPE1"));
 const ALBUM_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TALB"));
 const GENRE_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TCON"));
 const TRACK_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TRCK"));
 const DISC_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TPOS"));
 const RECORDING_TIME_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TDRC"));
+
+// Some frames can only appear once in a tag, and are replaced regardless of their content
+const ONE_PER_TAG: [&str; 11] = [
+	"MCDI", "ETCO", "MLLT", "SYTC", "RVRB", "PCNT", "RBUF", "POSS", "OWNE", "SEEK", "ASPI",
+];
 pub(super) const ATTACHED_PICTURE_ID: FrameId<'static> = FrameId::Valid(Cow::Borrowed("APIC"));
 
 impl Accessor for Id3v2Tag {
@@ -871,6 +1716,36 @@ impl Accessor for Id3v2Tag {
 			filter_comment_frame_by_description(frame, &EMPTY_CONTENT_DESCRIPTOR).is_none()
 		})
 	}
+
+	fn popularimeter(&self) -> Option<Popularimeter> {
+		self.popularimeters()
+			.next()
+			.map(|popularimeter| Popularimeter::from(popularimeter.clone()))
+	}
+
+	/// Sets the `POPM` frame with the same email
+	///
+	/// The `POPM` frames of other emails are kept, but the new frame is moved in front of them,
+	/// so that it's the one returned by [`Accessor::popularimeter`].
+	fn set_popularimeter(&mut self, value: Popularimeter) {
+		self.frames.retain(|frame| match frame {
+			Frame::Popularimeter(popularimeter) => popularimeter.email != value.email,
+			_ => true,
+		});
+
+		let position = self
+			.frames
+			.iter()
+			.position(|frame| matches!(frame, Frame::Popularimeter(_)))
+			.unwrap_or(self.frames.len());
+		self.frames
+			.insert(position, Frame::Popularimeter(value.into()));
+	}
+
+	fn remove_popularimeter(&mut self) {
+		self.frames
+			.retain(|frame| !matches!(frame, Frame::Popularimeter(_)));
+	}
 }
 
 impl TagExt for Id3v2Tag {
@@ -936,13 +1811,37 @@ impl TagExt for Id3v2Tag {
 		.dump_to(writer, write_options)
 	}
 
+	/// Gets the size of the tag, as it would be written by [`Id3v2Tag::dump_to`]
+	///
+	/// This accounts for the version chosen with [`WriteOptions::use_id3v23`] or
+	/// [`WriteOptions::use_id3v22`], any padding, and the extended header and footer.
+	///
+	/// # Errors
+	///
+	/// See [`Id3v2Tag::dump_to`]
+	fn serialized_len(&self, write_options: WriteOptions) -> std::result::Result<u64, Self::Err> {
+		Id3v2TagRef {
+			flags: self.flags,
+			frames: self.frames.iter().filter_map(Frame::as_opt_ref).peekable(),
+		}
+		.serialized_len(write_options)
+	}
+
 	fn clear(&mut self) {
 		self.frames.clear();
 	}
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct SplitTagRemainder(Id3v2Tag);
+pub struct SplitTagRemainder(
+	Id3v2Tag,
+	// Frames with preserved flags that were split into the `Tag`, used to restore the flags of unchanged frames
+	Vec<Frame<'static>>,
+	// Identifications of the RVA2 frames whose master channel was exposed as ReplayGain items
+	Vec<String>,
+	// Languages and descriptions of the SYLT frames whose text was exposed as lyrics items
+	Vec<(Lang, Option<String>)>,
+);
 
 impl From<SplitTagRemainder> for Id3v2Tag {
 	fn from(from: SplitTagRemainder) -> Self {
@@ -1097,7 +1996,7 @@ fn handle_tag_split(tag: &mut Tag, frame: &mut Frame<'_>) -> bool {
 			..
 		}) if !description.is_empty() && description.len() != 4 => {
 			let item_key = ItemKey::from_key(TagType::Id3v2, description);
-			for c in content.split(V4_MULTI_VALUE_SEPARATOR) {
+			for c in split_multi_value_text(content) {
 				tag.items.push(TagItem::new(
 					item_key.clone(),
 					ItemValue::Text(c.to_string()),
@@ -1112,7 +2011,7 @@ fn handle_tag_split(tag: &mut Tag, frame: &mut Frame<'_>) -> bool {
 			..
 		}) if !description.is_empty() && description.len() != 4 => {
 			let item_key = ItemKey::from_key(TagType::Id3v2, description);
-			for c in content.split(V4_MULTI_VALUE_SEPARATOR) {
+			for c in split_multi_value_text(content) {
 				tag.items.push(TagItem::new(
 					item_key.clone(),
 					ItemValue::Locator(c.to_string()),
@@ -1164,7 +2063,7 @@ fn handle_tag_split(tag: &mut Tag, frame: &mut Frame<'_>) -> bool {
 		}) => {
 			let item_key = ItemKey::from_key(TagType::Id3v2, id.as_str());
 
-			for c in content.split(V4_MULTI_VALUE_SEPARATOR) {
+			for c in split_multi_value_text(content) {
 				let mut item = TagItem::new(item_key.clone(), ItemValue::Text(c.to_string()));
 
 				item.set_lang(*language);
@@ -1206,7 +2105,7 @@ fn handle_tag_split(tag: &mut Tag, frame: &mut Frame<'_>) -> bool {
 		Frame::Text(TextInformationFrame { header: FrameHeader {id, .. }, value: content, .. }) => {
 			let item_key = ItemKey::from_key(TagType::Id3v2, id.as_str());
 
-			for c in content.split(V4_MULTI_VALUE_SEPARATOR) {
+			for c in split_multi_value_text(content) {
 				tag.items.push(TagItem::new(
 					item_key.clone(),
 					ItemValue::Text(c.to_string()),
@@ -1227,6 +2126,30 @@ fn handle_tag_split(tag: &mut Tag, frame: &mut Frame<'_>) -> bool {
 			return FRAME_CONSUMED;
 		},
 
+		Frame::Popularimeter(popularimeter) => {
+			// The full frame is kept, to retain the email and play counter
+			let Ok(content) = popularimeter.as_bytes() else {
+				return FRAME_RETAINED;
+			};
+
+			tag.items.push(TagItem::new(
+				ItemKey::Popularimeter,
+				ItemValue::Binary(content),
+			));
+
+			return FRAME_CONSUMED;
+		},
+
+		// iTunes' podcast flag, its content is always 4 zero bytes
+		Frame::Binary(BinaryFrame { header: FrameHeader { id, .. }, .. }) if id == &PODCAST_ID => {
+			tag.items.push(TagItem::new(
+				ItemKey::FlagPodcast,
+				ItemValue::Text(String::from("1")),
+			));
+
+			return FRAME_CONSUMED;
+		},
+
 		Frame::Binary(_)
 		| Frame::UserText(_)
 		| Frame::UserUrl(_) // Bare extended text/URL frames make no sense to support.
@@ -1234,23 +2157,158 @@ fn handle_tag_split(tag: &mut Tag, frame: &mut Frame<'_>) -> bool {
 		| Frame::RelativeVolumeAdjustment(_)
 		| Frame::Ownership(_)
 		| Frame::EventTimingCodes(_)
-		| Frame::Popularimeter(_)
-		| Frame::Private(_) => {
+		| Frame::Private(_)
+		| Frame::GeneralObject(_)
+		| Frame::SynchronizedText(_)
+		| Frame::Chapter(_)
+		| Frame::TableOfContents(_)
+		| Frame::MusicCdIdentifier(_)
+		| Frame::PlayCounter(_)
+		| Frame::Commercial(_) => {
 			return FRAME_RETAINED; // Keep unsupported frame
 		},
 	}
 }
 
+// The flags that describe how a frame should be handled by editors, rather than how its content is stored
+fn preserved_flags(flags: FrameFlags) -> FrameFlags {
+	FrameFlags {
+		tag_alter_preservation: flags.tag_alter_preservation,
+		file_alter_preservation: flags.file_alter_preservation,
+		read_only: flags.read_only,
+		grouping_identity: flags.grouping_identity,
+		..FrameFlags::default()
+	}
+}
+
+// The ReplayGain gain and peak keys for an RVA2 identification
+fn replay_gain_keys(identification: &str) -> Option<(ItemKey, ItemKey)> {
+	if identification.eq_ignore_ascii_case("track") {
+		return Some((ItemKey::ReplayGainTrackGain, ItemKey::ReplayGainTrackPeak));
+	}
+
+	if identification.eq_ignore_ascii_case("album") {
+		return Some((ItemKey::ReplayGainAlbumGain, ItemKey::ReplayGainAlbumPeak));
+	}
+
+	None
+}
+
+fn format_replay_gain(volume_adjustment_db: f32) -> String {
+	format!("{volume_adjustment_db:.2} dB")
+}
+
+fn parse_replay_gain(text: &str) -> Option<f32> {
+	let text = text.trim();
+	let text = match text.len().checked_sub(2) {
+		Some(unit_start)
+			if text.is_char_boundary(unit_start)
+				&& text[unit_start..].eq_ignore_ascii_case("dB") =>
+		{
+			&text[..unit_start]
+		},
+		_ => text,
+	};
+
+	text.trim_end().parse().ok()
+}
+
+fn format_replay_gain_peak(peak_amplitude: f32) -> String {
+	format!("{peak_amplitude:.6}")
+}
+
+// The items and pictures a frame would be split into, used for comparing frame contents
+fn split_frame_content(frame: &Frame<'static>) -> (Vec<TagItem>, Vec<Picture>) {
+	let mut tag = Tag::new(TagType::Id3v2);
+	if handle_tag_split(&mut tag, &mut frame.clone()) {
+		return (Vec::new(), Vec::new());
+	}
+
+	(tag.items, tag.pictures)
+}
+
 impl SplitTag for Id3v2Tag {
 	type Remainder = SplitTagRemainder;
 
 	fn split_tag(mut self) -> (Self::Remainder, Tag) {
 		let mut tag = Tag::new(TagType::Id3v2);
+		let mut flagged_frames = Vec::new();
 
-		self.frames
-			.retain_mut(|frame| handle_tag_split(&mut tag, frame));
+		self.frames.retain_mut(|frame| {
+			let original =
+				(preserved_flags(frame.flags()) != FrameFlags::default()).then(|| frame.clone());
+
+			let retained = handle_tag_split(&mut tag, frame);
+			if !retained {
+				flagged_frames.extend(original);
+			}
+
+			retained
+		});
+
+		// RVA2 frames are retained, but the master channel of ReplayGain adjustments is also exposed.
+		// Existing ReplayGain items (e.g. from TXXX frames) take precedence.
+		let mut replay_gain_frames = Vec::new();
+		for frame in &self.frames {
+			let Frame::RelativeVolumeAdjustment(rva2) = frame else {
+				continue;
+			};
+
+			let Some((gain_key, peak_key)) = replay_gain_keys(&rva2.identification) else {
+				continue;
+			};
+
+			let Some(master) = rva2.channels.get(&ChannelType::MasterVolume) else {
+				continue;
+			};
+
+			if tag.get(&gain_key).is_some() || tag.get(&peak_key).is_some() {
+				continue;
+			}
+
+			tag.items.push(TagItem::new(
+				gain_key,
+				ItemValue::Text(format_replay_gain(master.volume_adjustment_db())),
+			));
+
+			if let Some(peak_amplitude) = master.peak_amplitude() {
+				tag.items.push(TagItem::new(
+					peak_key,
+					ItemValue::Text(format_replay_gain_peak(peak_amplitude)),
+				));
+			}
+
+			replay_gain_frames.push(rva2.identification.clone());
+		}
+
+		// SYLT lyrics are retained, but their text is also exposed.
+		// Existing lyrics (e.g. from USLT frames) take precedence.
+		let mut sync_text_frames = Vec::new();
+		if tag.get(&ItemKey::Lyrics).is_none() {
+			for frame in &self.frames {
+				let Frame::SynchronizedText(sync_text) = frame else {
+					continue;
+				};
 
-		(SplitTagRemainder(self), tag)
+				if sync_text.content_type != SyncTextContentType::Lyrics {
+					continue;
+				}
+
+				let mut item = TagItem::new(ItemKey::Lyrics, ItemValue::Text(sync_text.text()));
+				item.set_lang(sync_text.language);
+				if let Some(description) = &sync_text.description {
+					item.set_description(description.clone());
+				}
+
+				tag.items.push(item);
+				sync_text_frames.push((sync_text.language, sync_text.description.clone()));
+			}
+		}
+
+		(
+			SplitTagRemainder(self, flagged_frames, replay_gain_frames, sync_text_frames),
+			tag,
+		)
 	}
 }
 
@@ -1357,9 +2415,47 @@ impl MergeTag for SplitTagRemainder {
 			Some(joined_items.into_iter())
 		}
 
-		let Self(mut merged) = self;
+		let Self(mut merged, flagged_frames, replay_gain_frames, sync_text_frames) = self;
 		merged.frames.reserve(tag.item_count() as usize);
 
+		// Lyrics items that were exposed from SYLT frames are only written back if they changed.
+		// The frames are removed along with their items.
+		for (language, description) in sync_text_frames {
+			let Some(frame_pos) = merged.frames.iter().position(|frame| {
+				matches!(
+					frame,
+					Frame::SynchronizedText(sync_text)
+						if sync_text.language == language && sync_text.description == description
+				)
+			}) else {
+				continue;
+			};
+
+			let Frame::SynchronizedText(sync_text) = &merged.frames[frame_pos] else {
+				unreachable!("SYLT frame was just found");
+			};
+
+			let description = description.unwrap_or_default();
+			let text = sync_text.text();
+
+			let mut found = false;
+			tag.items.retain(|item| {
+				if item.key() != &ItemKey::Lyrics
+					|| item.lang != language
+					|| item.description != description
+				{
+					return true;
+				}
+
+				found = true;
+				item.value().text() != Some(text.as_str())
+			});
+
+			if !found {
+				merged.frames.remove(frame_pos);
+			}
+		}
+
 		// Multi-valued text key-to-frame mappings
 		// TODO: Extend this list of item keys as needed or desired
 		for item_key in [
@@ -1507,25 +2603,26 @@ impl MergeTag for SplitTagRemainder {
 		}
 
 		// Flag items
-		for item_key in [&ItemKey::FlagCompilation, &ItemKey::FlagPodcast] {
-			let Some(text) = tag.take_strings(item_key).next() else {
-				continue;
-			};
-
-			let Some(flag_value) = flag_item(&text) else {
-				continue;
-			};
-
-			let frame_id = item_key
-				.map_key(TagType::Id3v2, false)
-				.expect("valid frame id");
-
+		if let Some(flag_value) = tag
+			.take_strings(&ItemKey::FlagCompilation)
+			.next()
+			.and_then(|text| flag_item(&text))
+		{
 			merged.frames.push(new_text_frame(
-				FrameId::Valid(Cow::Borrowed(frame_id)),
+				FrameId::Valid(Cow::Borrowed("TCMP")),
 				u8::from(flag_value).to_string(),
 			));
 		}
 
+		// Unlike `TCMP`, `PCST` is a flag by its presence alone
+		if let Some(true) = tag
+			.take_strings(&ItemKey::FlagPodcast)
+			.next()
+			.and_then(|text| flag_item(&text))
+		{
+			merged.frames.push(new_binary_frame(PODCAST_ID, vec![0; 4]));
+		}
+
 		// iTunes advisory rating
 		'rate: {
 			if let Some(advisory_rating) = tag.take_strings(&ItemKey::ParentalAdvisory).next() {
@@ -1572,6 +2669,71 @@ impl MergeTag for SplitTagRemainder {
 			merged.insert(frame);
 		}
 
+		// ReplayGain items that were exposed from RVA2 frames are written back to them
+		for identification in replay_gain_frames {
+			let Some((gain_key, peak_key)) = replay_gain_keys(&identification) else {
+				continue;
+			};
+
+			let Some(frame_pos) = merged.frames.iter().position(|frame| {
+				matches!(frame, Frame::RelativeVolumeAdjustment(rva2) if rva2.identification == identification)
+			}) else {
+				continue;
+			};
+
+			let Frame::RelativeVolumeAdjustment(rva2) = &mut merged.frames[frame_pos] else {
+				unreachable!("RVA2 frame was just found");
+			};
+
+			let Some(master) = rva2.channels.get_mut(&ChannelType::MasterVolume) else {
+				continue;
+			};
+
+			// Only update values that were changed, to avoid losing precision
+			let Some(gain) = tag.get_string(&gain_key) else {
+				// The gain was removed, so the adjustment no longer applies
+				rva2.channels.remove(&ChannelType::MasterVolume);
+				if rva2.channels.is_empty() {
+					merged.frames.remove(frame_pos);
+				}
+
+				continue;
+			};
+
+			match parse_replay_gain(gain) {
+				Some(volume_adjustment_db) => {
+					if gain != format_replay_gain(master.volume_adjustment_db()) {
+						master.set_volume_adjustment_db(volume_adjustment_db);
+					}
+
+					tag.remove_key(&gain_key);
+				},
+				None => {
+					log::warn!("ReplayGain gain is not a number: {gain}, writing it separately");
+					continue;
+				},
+			}
+
+			let peak = tag.get_string(&peak_key);
+			match peak.map(|peak| (peak, peak.trim().parse::<f32>())) {
+				None => master.set_peak_amplitude(None),
+				Some((peak, Ok(peak_amplitude))) => {
+					if master
+						.peak_amplitude()
+						.map(format_replay_gain_peak)
+						.as_deref() != Some(peak)
+					{
+						master.set_peak_amplitude(Some(peak_amplitude));
+					}
+
+					tag.remove_key(&peak_key);
+				},
+				Some((peak, Err(_))) => {
+					log::warn!("ReplayGain peak is not a number: {peak}, writing it separately");
+				},
+			}
+		}
+
 		// Insert all remaining items as single frames and deduplicate as needed
 		for item in tag.items {
 			merged.insert_item(item);
@@ -1585,6 +2747,21 @@ impl MergeTag for SplitTagRemainder {
 			}
 		}
 
+		// Restore the flags of any frames that passed through unchanged
+		for original in flagged_frames {
+			let Some(frame) = merged.frames.iter_mut().find(|frame| **frame == original) else {
+				continue;
+			};
+
+			if split_frame_content(frame) == split_frame_content(&original) {
+				let flags = FrameFlags {
+					data_length_indicator: frame.flags().data_length_indicator,
+					..preserved_flags(original.flags())
+				};
+				frame.set_flags(flags);
+			}
+		}
+
 		merged
 	}
 }
@@ -1593,8 +2770,10 @@ impl From<Id3v2Tag> for Tag {
 	fn from(input: Id3v2Tag) -> Self {
 		let (remainder, mut tag) = input.split_tag();
 
-		if unsafe { global_options().preserve_format_specific_items } && remainder.0.len() > 0 {
-			tag.companion_tag = Some(CompanionTag::Id3v2(remainder.0));
+		if unsafe { global_options().preserve_format_specific_items }
+			&& (remainder.0.len() > 0 || !remainder.1.is_empty())
+		{
+			tag.companion_tag = Some(CompanionTag::Id3v2(remainder));
 		}
 
 		tag
@@ -1605,7 +2784,7 @@ impl From<Tag> for Id3v2Tag {
 	fn from(mut input: Tag) -> Self {
 		if unsafe { global_options().preserve_format_specific_items } {
 			if let Some(companion) = input.companion_tag.take().and_then(CompanionTag::id3v2) {
-				return SplitTagRemainder(companion).merge_tag(input);
+				return companion.merge_tag(input);
 			}
 		}
 
@@ -1713,9 +2892,13 @@ impl<'a, I: Iterator<Item = FrameRef<'a>> + 'a> Id3v2TagRef<'a, I> {
 		writer: &mut W,
 		write_options: WriteOptions,
 	) -> Result<()> {
-		let temp = super::write::create_tag(self, write_options)?;
+		let temp = super::write::create_tag(self, write_options, None)?;
 		writer.write_all(&temp)?;
 
 		Ok(())
 	}
+
+	pub(crate) fn serialized_len(&mut self, write_options: WriteOptions) -> Result<u64> {
+		super::write::tag_len(self, write_options)
+	}
 }
@@ -1,9 +1,13 @@
 use crate::config::{ParseOptions, ParsingMode};
+use crate::error::{ErrorKind, Id3v2ErrorKind};
 use crate::id3::v2::header::Id3v2Header;
-use crate::id3::v2::items::PopularimeterFrame;
+use crate::id3::v2::items::{PopularimeterFrame, PrivateFrame};
 use crate::id3::v2::util::pairs::DEFAULT_NUMBER_IN_PAIR;
 use crate::id3::v2::{
-	ChannelInformation, ChannelType, RelativeVolumeAdjustmentFrame, TimestampFrame,
+	ChannelInformation, ChannelType, ChapterFrame, Event, EventTimingCodesFrame, EventType,
+	FrameFlags, GeneralObjectFrame, ImageSizeRestrictions, MusicCdIdentifierFrame, OwnershipFrame,
+	PlayCounterFrame, RelativeVolumeAdjustmentFrame, SynchronizedTextFrame, TableOfContentsFrame,
+	TagRestrictions, TagSizeRestrictions, TextSizeRestrictions, TimestampFormat, TimestampFrame,
 };
 use crate::picture::MimeType;
 use crate::tag::items::{Timestamp, ENGLISH};
@@ -250,6 +254,7 @@ fn create_full_test_tag(version: Id3v2Version) -> Id3v2Tag {
 			mime_type: Some(MimeType::Png),
 			description: None,
 			data: read_path("tests/tags/assets/id3v2/test_full_cover.png").into(),
+			skipped_data_len: None,
 		},
 	)));
 
@@ -279,9 +284,14 @@ fn id3v23_full() {
 
 #[test_log::test]
 fn id3v22_full() {
-	let tag = create_full_test_tag(Id3v2Version::V2);
-	let parsed_tag = read_tag("tests/tags/assets/id3v2/test_full.id3v22");
+	let mut tag = create_full_test_tag(Id3v2Version::V2);
+	let mut parsed_tag = read_tag("tests/tags/assets/id3v2/test_full.id3v22");
 
+	// Tags may change order after being read, due to the TDRC conversion
+	tag.frames.sort_by_key(|frame| frame.id_str().to_string());
+	parsed_tag
+		.frames
+		.sort_by_key(|frame| frame.id_str().to_string());
 	assert_eq!(tag, parsed_tag);
 }
 
@@ -361,6 +371,127 @@ fn popm_frame() {
 	)
 }
 
+#[test_log::test]
+fn popm_split_merge_round_trip() {
+	let mut id3v2 = Id3v2Tag::default();
+	for (email, rating, counter) in [
+		("Windows Media Player 9 Series", 196, 3),
+		("foo@bar.com", 3, u64::from(u32::MAX) + 1),
+	] {
+		id3v2.insert(Frame::Popularimeter(PopularimeterFrame::new(
+			String::from(email),
+			rating,
+			counter,
+		)));
+	}
+
+	let (split_remainder, split_tag) = id3v2.clone().split_tag();
+	assert_eq!(split_remainder.0.len(), 0);
+	assert_eq!(split_tag.get_items(&ItemKey::Popularimeter).count(), 2);
+
+	let popularimeter = split_tag.popularimeter().unwrap();
+	assert_eq!(popularimeter.email, "Windows Media Player 9 Series");
+	assert_eq!(popularimeter.rating(), 77);
+	assert_eq!(popularimeter.counter, 3);
+
+	// No information should be lost
+	let merged = split_remainder.merge_tag(split_tag);
+	assert_eq!(merged.frames.len(), 2);
+	for (original, merged) in id3v2.frames().zip(merged.frames()) {
+		let (Frame::Popularimeter(original), Frame::Popularimeter(merged)) = (original, merged)
+		else {
+			panic!("Expected PopularimeterFrames");
+		};
+
+		assert_eq!(original.email, merged.email);
+		assert_eq!(original.rating, merged.rating);
+		assert_eq!(original.counter, merged.counter);
+	}
+}
+
+#[test_log::test]
+fn popm_rating() {
+	let mut tag = Id3v2Tag::default();
+	assert_eq!(tag.rating(None), None);
+
+	tag.set_rating(None, 1);
+	tag.set_rating(Some("MusicBee"), 255);
+	tag.set_rating(Some("Windows Media Player 9 Series"), 196);
+	assert_eq!(tag.popularimeters().count(), 3);
+
+	// The first frame is updated, rather than a new one created
+	tag.set_rating(None, 64);
+	assert_eq!(tag.popularimeters().count(), 3);
+	assert_eq!(tag.rating(None), Some(64));
+	assert_eq!(tag.rating(Some("")), Some(64));
+
+	let re_read = dump_and_re_read(&tag, WriteOptions::default());
+	assert_eq!(re_read.rating(Some("MusicBee")), Some(255));
+	assert_eq!(
+		re_read.rating(Some("Windows Media Player 9 Series")),
+		Some(196)
+	);
+	assert_eq!(re_read.rating(Some("foo@bar.com")), None);
+}
+
+#[test_log::test]
+fn popm_accessor() {
+	let mut tag = Id3v2Tag::default();
+	tag.set_rating(Some("MusicBee"), 255);
+	tag.set_rating(Some("Windows Media Player 9 Series"), 64);
+
+	// The first frame is used
+	let popularimeter = tag.popularimeter().unwrap();
+	assert_eq!(popularimeter.email, "MusicBee");
+	assert_eq!(popularimeter.rating(), 100);
+
+	// Only the frame with the same email is replaced, and it's moved to the front
+	tag.set_popularimeter(Popularimeter::new(
+		String::from("Windows Media Player 9 Series"),
+		77,
+		3,
+	));
+	assert_eq!(tag.popularimeters().count(), 2);
+	assert_eq!(
+		tag.popularimeter(),
+		Some(Popularimeter::new(
+			String::from("Windows Media Player 9 Series"),
+			77,
+			3,
+		))
+	);
+	assert_eq!(tag.rating(Some("Windows Media Player 9 Series")), Some(196));
+	assert_eq!(tag.rating(Some("MusicBee")), Some(255));
+
+	tag.remove_popularimeter();
+	assert_eq!(tag.popularimeters().count(), 0);
+	assert_eq!(tag.popularimeter(), None);
+}
+
+#[test_log::test]
+fn popm_to_other_formats() {
+	let mut id3v2 = Id3v2Tag::default();
+	id3v2.insert(Frame::Popularimeter(PopularimeterFrame::new(
+		String::from("Windows Media Player 9 Series"),
+		128,
+		3,
+	)));
+
+	let mut tag: Tag = id3v2.into();
+	tag.re_map(TagType::VorbisComments);
+	assert_eq!(tag.get_string(&ItemKey::Popularimeter), Some("50"));
+
+	// Only the rating survives
+	let id3v2: Id3v2Tag = tag.into();
+	let Some(Frame::Popularimeter(popm)) = id3v2.frames().next() else {
+		panic!("Expected a PopularimeterFrame");
+	};
+
+	assert_eq!(popm.email, "");
+	assert_eq!(popm.rating, 128);
+	assert_eq!(popm.counter, 0);
+}
+
 #[test_log::test]
 fn multi_value_frame_to_tag() {
 	let mut tag = Id3v2Tag::default();
@@ -486,6 +617,107 @@ fn multi_value_roundtrip() {
 	assert_eq!(tag.items, split_tag.items);
 }
 
+#[test_log::test]
+fn multi_value_id3v24_save() {
+	let mut tag = Tag::new(TagType::Id3v2);
+	for artist in ["foo", "bar", "baz"] {
+		tag.push(TagItem::new(
+			ItemKey::TrackArtist,
+			ItemValue::Text(String::from(artist)),
+		));
+	}
+
+	let id3v2 = Id3v2Tag::from(tag.clone());
+	let tag_re_read = dump_and_re_read(&id3v2, WriteOptions::default());
+
+	// All values are kept in a single, null-separated frame
+	assert_eq!(tag_re_read.len(), 1);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TPE1"))),
+		Some("foo\0bar\0baz")
+	);
+
+	let (_, split_tag) = tag_re_read.split_tag();
+	assert_eq!(split_tag.items, tag.items);
+}
+
+#[test_log::test]
+fn multi_value_id3v23_save() {
+	let mut tag = Id3v2Tag::new();
+	tag.set_artist(String::from("foo\0bar\0baz"));
+	tag.insert_user_text(String::from("FOO"), String::from("one\0two"));
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default().use_id3v23(true));
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TPE1"))),
+		Some("foo/bar/baz")
+	);
+	assert_eq!(tag_re_read.get_user_text("FOO"), Some("one/two"));
+
+	let tag_re_read = dump_and_re_read(
+		&tag,
+		WriteOptions::default()
+			.use_id3v23(true)
+			.id3v23_separator("; "),
+	);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TPE1"))),
+		Some("foo; bar; baz")
+	);
+	assert_eq!(tag_re_read.get_user_text("FOO"), Some("one; two"));
+}
+
+#[test_log::test]
+fn multi_value_trailing_nulls() {
+	fn split_artists(value: &str) -> Vec<String> {
+		let mut tag = Id3v2Tag::new();
+		tag.insert(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TPE1")),
+			TextEncoding::UTF8,
+			String::from(value),
+		)));
+
+		let tag: Tag = tag.into();
+		tag.get_strings(&ItemKey::TrackArtist)
+			.map(String::from)
+			.collect()
+	}
+
+	assert_eq!(split_artists("foo\0bar\0"), ["foo", "bar"]);
+	assert_eq!(split_artists("foo\0\0"), ["foo"]);
+	assert_eq!(split_artists("foo\0\0bar"), ["foo", "bar"]);
+
+	// A lone empty string is still a valid value
+	assert_eq!(split_artists(""), [""]);
+	assert_eq!(split_artists("\0"), [""]);
+}
+
+#[test_log::test]
+fn multi_value_trailing_nulls_id3v23_save() {
+	fn joined_artists(value: &str) -> String {
+		let mut tag = Id3v2Tag::new();
+		tag.insert(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TPE1")),
+			TextEncoding::UTF8,
+			String::from(value),
+		)));
+
+		let tag_re_read = dump_and_re_read(&tag, WriteOptions::default().use_id3v23(true));
+		tag_re_read
+			.get_text(&FrameId::Valid(Cow::Borrowed("TPE1")))
+			.unwrap()
+			.to_owned()
+	}
+
+	assert_eq!(joined_artists("foo\0bar\0"), "foo/bar");
+	assert_eq!(joined_artists("foo\0\0"), "foo");
+	assert_eq!(joined_artists("foo\0\0bar"), "foo/bar");
+
+	// Empty values are kept as-is
+	assert_eq!(joined_artists(""), "");
+	assert_eq!(joined_artists("\0"), "");
+}
+
 #[test_log::test]
 fn comments() {
 	let mut tag = Id3v2Tag::default();
@@ -1079,6 +1311,17 @@ fn genres_id_multiple_into_tag() {
 	assert_eq!(genres.next(), None);
 }
 
+#[test_log::test]
+fn genres_winamp_extension_into_tag() {
+	let id3v2 = id3v2_tag_with_genre("(130)\0(140)\0Foo");
+	let tag: Tag = id3v2.into();
+	let mut genres = tag.get_strings(&ItemKey::Genre);
+	assert_eq!(genres.next(), Some("Terror"));
+	assert_eq!(genres.next(), Some("Contemporary Christian"));
+	assert_eq!(genres.next(), Some("Foo"));
+	assert_eq!(genres.next(), None);
+}
+
 #[test_log::test]
 fn genres_null_separated() {
 	let tag = id3v2_tag_with_genre("Samba-rock\0MPB\0Funk");
@@ -1166,21 +1409,137 @@ fn tipl_round_trip() {
 	}
 }
 
+#[test_log::test]
+fn involved_people_and_musician_credits() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::KeyValue(KeyValueFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIPL")),
+		TextEncoding::UTF16,
+		vec![
+			(String::from("producer"), String::from("Foo")),
+			// No person
+			(String::from("mix"), String::new()),
+			(String::from("vocal coach"), String::from("Bär")),
+		],
+	)));
+	tag.insert(Frame::KeyValue(KeyValueFrame::new(
+		FrameId::Valid(Cow::Borrowed("TMCL")),
+		TextEncoding::UTF8,
+		vec![
+			(String::from("bass"), String::from("Baz")),
+			(String::from("drums"), String::from("Qux")),
+		],
+	)));
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+
+	assert_eq!(
+		tag_re_read.involved_people().collect::<Vec<_>>(),
+		[("producer", "Foo"), ("mix", ""), ("vocal coach", "Bär")]
+	);
+	assert_eq!(
+		tag_re_read.musician_credits().collect::<Vec<_>>(),
+		[("bass", "Baz"), ("drums", "Qux")]
+	);
+
+	let Some(Frame::KeyValue(tipl)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TIPL")))
+	else {
+		panic!("Expected a KeyValueFrame");
+	};
+	assert_eq!(tipl.encoding, TextEncoding::UTF16);
+}
+
+#[test_log::test]
+fn key_value_frame_odd_string_count() {
+	// A trailing role without a person is kept, but trailing padding is not
+	let content = b"\x00producer\x00Foo\x00engineer\x00\x00\x00";
+
+	let frame = KeyValueFrame::parse(
+		&mut &content[..],
+		FrameId::Valid(Cow::Borrowed("TIPL")),
+		FrameFlags::default(),
+		Id3v2Version::V4,
+	)
+	.unwrap()
+	.unwrap();
+
+	assert_eq!(
+		frame.key_value_pairs,
+		vec![
+			(String::from("producer"), String::from("Foo")),
+			(String::from("engineer"), String::new()),
+		]
+	);
+}
+
+#[test_log::test]
+fn involved_people_id3v23_round_trip() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::KeyValue(KeyValueFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIPL")),
+		TextEncoding::UTF8,
+		vec![
+			(String::from("producer"), String::from("Foo")),
+			(String::from("mix"), String::from("Bar")),
+		],
+	)));
+	tag.insert(Frame::KeyValue(KeyValueFrame::new(
+		FrameId::Valid(Cow::Borrowed("TMCL")),
+		TextEncoding::UTF8,
+		vec![(String::from("bass"), String::from("Baz"))],
+	)));
+
+	// ID3v2.3 only has `IPLS`, which both frames are merged into
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default().use_id3v23(true));
+
+	assert_eq!(
+		tag_re_read.involved_people().collect::<Vec<_>>(),
+		[("producer", "Foo"), ("mix", "Bar"), ("bass", "Baz")]
+	);
+	assert_eq!(tag_re_read.musician_credits().count(), 0);
+
+	// The well-known roles are still mapped
+	let (_, split_tag) = tag_re_read.split_tag();
+	assert_eq!(split_tag.get_string(&ItemKey::Producer), Some("Foo"));
+	assert_eq!(split_tag.get_string(&ItemKey::MixEngineer), Some("Bar"));
+}
+
 #[test_log::test]
 fn flag_item_conversion() {
 	let mut tag = Tag::new(TagType::Id3v2);
 	tag.insert_text(ItemKey::FlagCompilation, "1".to_owned());
-	tag.insert_text(ItemKey::FlagPodcast, "0".to_owned());
+	tag.insert_text(ItemKey::FlagPodcast, "1".to_owned());
 
-	let tag: Id3v2Tag = tag.into();
+	let id3v2: Id3v2Tag = tag.clone().into();
 	assert_eq!(
-		tag.get_text(&FrameId::Valid(Cow::Borrowed("TCMP"))),
+		id3v2.get_text(&FrameId::Valid(Cow::Borrowed("TCMP"))),
 		Some("1")
 	);
+
+	// `PCST` is a flag by its presence alone
+	let pcst_id = FrameId::Valid(Cow::Borrowed("PCST"));
+	assert_eq!(
+		id3v2.get(&pcst_id),
+		Some(&Frame::Binary(BinaryFrame::new(
+			pcst_id.clone(),
+			vec![0; 4]
+		)))
+	);
+
+	let tag_back: Tag = id3v2.into();
+	assert_eq!(tag_back.get_string(&ItemKey::FlagPodcast), Some("1"));
+
+	// Same when writing a `Tag` directly
+	let item = TagItem::new(ItemKey::FlagPodcast, ItemValue::Text(String::from("1")));
+	let frame: crate::id3::v2::frame::FrameRef<'_> = (&item).try_into().unwrap();
 	assert_eq!(
-		tag.get_text(&FrameId::Valid(Cow::Borrowed("PCST"))),
-		Some("0")
+		&*frame,
+		&Frame::Binary(BinaryFrame::new(pcst_id.clone(), vec![0; 4]))
 	);
+
+	tag.insert_text(ItemKey::FlagPodcast, "0".to_owned());
+	let id3v2: Id3v2Tag = tag.into();
+	assert!(id3v2.get(&pcst_id).is_none());
 }
 
 #[test_log::test]
@@ -1341,6 +1700,47 @@ fn preserve_comment_lang_description_on_conversion() {
 	}
 }
 
+#[test_log::test]
+fn preserve_multiple_comments_on_conversion() {
+	let mut tag = Id3v2Tag::new();
+
+	tag.insert(Frame::Comment(CommentFrame::new(
+		TextEncoding::UTF8,
+		ENGLISH,
+		String::from("iTunNORM"),
+		String::from(" 0000044E 00000061 00009B67 000044C3"),
+	)));
+	tag.insert(Frame::Comment(CommentFrame::new(
+		TextEncoding::UTF8,
+		ENGLISH,
+		String::new(),
+		String::from("Foo comment"),
+	)));
+
+	let tag: Tag = tag.into();
+	assert_eq!(tag.len(), 2);
+
+	let tag: Id3v2Tag = tag.into();
+	assert_eq!(tag.len(), 2);
+
+	// The user comment is still the one exposed through the accessor
+	assert_eq!(tag.comment().as_deref(), Some("Foo comment"));
+
+	let mut comments = tag
+		.into_iter()
+		.filter_map(|frame| match frame {
+			Frame::Comment(comm) => Some(comm),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	comments.sort_by(|a, b| a.description.cmp(&b.description));
+
+	assert_eq!(comments[0].description, "");
+	assert_eq!(comments[0].content, "Foo comment");
+	assert_eq!(comments[1].description, "iTunNORM");
+	assert_eq!(comments[1].content, " 0000044E 00000061 00009B67 000044C3");
+}
+
 // TODO: Remove this once we have a better solution
 #[test_log::test]
 fn hold_back_4_character_txxx_description() {
@@ -1381,23 +1781,188 @@ fn skip_reading_cover_art() {
 fn remove_id3v24_frames_on_id3v23_save() {
 	let mut tag = Id3v2Tag::new();
 
-	tag.insert(Frame::RelativeVolumeAdjustment(
-		RelativeVolumeAdjustmentFrame::new(
-			String::from("Foo RVA"),
-			HashMap::from([(
-				ChannelType::MasterVolume,
-				ChannelInformation {
-					channel_type: ChannelType::MasterVolume,
-					volume_adjustment: 30,
-					bits_representing_peak: 0,
-					peak_volume: None,
-				},
-			)]),
-		),
-	));
+	tag.insert(Frame::Text(TextInformationFrame::new(
+		FrameId::Valid(Cow::Borrowed("TMOO")),
+		TextEncoding::UTF8,
+		String::from("Foo mood"),
+	)));
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default().use_id3v23(true));
+
+	assert_eq!(tag_re_read.frames.len(), 0);
+}
+
+#[test_log::test]
+fn convert_rva2_to_rvad_on_id3v23_save() {
+	let mut tag = Id3v2Tag::new();
+
+	for (identification, volume_adjustment) in [("Foo RVA", -3083), ("Bar RVA", 512)] {
+		tag.insert(Frame::RelativeVolumeAdjustment(
+			RelativeVolumeAdjustmentFrame::new(
+				String::from(identification),
+				HashMap::from([(
+					ChannelType::MasterVolume,
+					ChannelInformation {
+						channel_type: ChannelType::MasterVolume,
+						volume_adjustment,
+						bits_representing_peak: 0,
+						peak_volume: None,
+					},
+				)]),
+			),
+		));
+	}
 
 	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default().use_id3v23(true));
 
+	// Only a single RVAD frame can be written, so the second is discarded
+	assert_eq!(tag_re_read.frames.len(), 1);
+
+	let Frame::RelativeVolumeAdjustment(rva2) = &tag_re_read.frames[0] else {
+		panic!("Expected a RelativeVolumeAdjustmentFrame");
+	};
+
+	// The identification and master volume can't be represented in RVAD
+	assert!(rva2.identification.is_empty());
+	assert_eq!(rva2.channels.len(), 2);
+	for channel_type in [ChannelType::FrontRight, ChannelType::FrontLeft] {
+		let channel = &rva2.channels[&channel_type];
+		assert!((channel.volume_adjustment + 3083).abs() <= 1);
+		assert_eq!(channel.peak_volume, None);
+	}
+
+	// And back to ID3v2.4
+	let tag_re_read = dump_and_re_read(&tag_re_read, WriteOptions::default());
+	assert_eq!(tag_re_read.frames.len(), 1);
+	assert_eq!(tag_re_read.frames[0].id_str(), "RVA2");
+}
+
+fn replay_gain_rva2(
+	identification: &str,
+	volume_adjustment: i16,
+	peak: Option<f32>,
+) -> Frame<'static> {
+	let mut master = ChannelInformation {
+		channel_type: ChannelType::MasterVolume,
+		volume_adjustment,
+		bits_representing_peak: 0,
+		peak_volume: None,
+	};
+	master.set_peak_amplitude(peak);
+
+	Frame::RelativeVolumeAdjustment(RelativeVolumeAdjustmentFrame::new(
+		String::from(identification),
+		HashMap::from([(ChannelType::MasterVolume, master)]),
+	))
+}
+
+#[test_log::test]
+fn rva2_replay_gain_split_merge() {
+	let mut tag = Id3v2Tag::new();
+	tag.insert(replay_gain_rva2("track", -3318, Some(0.96)));
+	tag.insert(replay_gain_rva2("album", 1024, None));
+	tag.insert(replay_gain_rva2("Foo RVA", 512, None));
+
+	let (remainder, mut split) = tag.clone().split_tag();
+
+	// The RVA2 frames are retained
+	assert_eq!(remainder.frames.len(), 3);
+
+	assert_eq!(split.len(), 3);
+	assert_eq!(
+		split.get_string(&ItemKey::ReplayGainTrackGain),
+		Some("-6.48 dB")
+	);
+	assert_eq!(
+		split.get_string(&ItemKey::ReplayGainTrackPeak),
+		Some("0.959991")
+	);
+	assert_eq!(
+		split.get_string(&ItemKey::ReplayGainAlbumGain),
+		Some("2.00 dB")
+	);
+
+	// Unchanged items don't alter the frames, and aren't duplicated
+	let merged = remainder.clone().merge_tag(split.clone());
+	assert_eq!(merged.frames.len(), 3);
+	for (frame, original) in merged.frames.iter().zip(&tag.frames) {
+		let (Frame::RelativeVolumeAdjustment(frame), Frame::RelativeVolumeAdjustment(original)) =
+			(frame, original)
+		else {
+			panic!("Expected a RelativeVolumeAdjustmentFrame");
+		};
+
+		assert_eq!(frame.as_bytes(), original.as_bytes());
+	}
+
+	// Changed items update the master channel, removed items remove it
+	split.insert_text(ItemKey::ReplayGainTrackGain, String::from("-3 dB"));
+	split.remove_key(&ItemKey::ReplayGainTrackPeak);
+	split.remove_key(&ItemKey::ReplayGainAlbumGain);
+
+	let merged = remainder.merge_tag(split);
+	assert_eq!(merged.frames.len(), 2);
+
+	let Frame::RelativeVolumeAdjustment(track) = &merged.frames[0] else {
+		panic!("Expected a RelativeVolumeAdjustmentFrame");
+	};
+
+	assert_eq!(track.identification, "track");
+	let master = &track.channels[&ChannelType::MasterVolume];
+	assert_eq!(master.volume_adjustment, -1536);
+	assert_eq!(master.peak_volume, None);
+
+	let Frame::RelativeVolumeAdjustment(other) = &merged.frames[1] else {
+		panic!("Expected a RelativeVolumeAdjustmentFrame");
+	};
+	assert_eq!(other.identification, "Foo RVA");
+}
+
+#[test_log::test]
+fn rva2_replay_gain_user_text_precedence() {
+	let mut tag = Id3v2Tag::new();
+	tag.insert(replay_gain_rva2("track", -3318, Some(0.96)));
+	tag.insert(new_user_text_frame(
+		String::from("REPLAYGAIN_TRACK_GAIN"),
+		String::from("-1.00 dB"),
+	));
+
+	let (remainder, split) = tag.clone().split_tag();
+
+	assert_eq!(split.len(), 1);
+	assert_eq!(
+		split.get_string(&ItemKey::ReplayGainTrackGain),
+		Some("-1.00 dB")
+	);
+
+	// Both frames are kept as they are
+	let merged = remainder.merge_tag(split);
+	assert_eq!(merged.frames.len(), 2);
+	assert!(merged.frames.contains(&tag.frames[0]));
+	assert!(merged.frames.contains(&tag.frames[1]));
+
+	let Some(Frame::RelativeVolumeAdjustment(rva2)) =
+		merged.get(&FrameId::Valid(Cow::Borrowed("RVA2")))
+	else {
+		panic!("Expected a RelativeVolumeAdjustmentFrame");
+	};
+	assert_eq!(
+		rva2.channels[&ChannelType::MasterVolume].volume_adjustment,
+		-3318
+	);
+}
+
+#[test_log::test]
+fn remove_id3v23_frames_on_id3v24_save() {
+	let mut tag = Id3v2Tag::new();
+
+	tag.insert(Frame::Binary(BinaryFrame::new(
+		FrameId::Valid(Cow::Borrowed("EQUA")),
+		vec![16, 0x80, 0x00, 0x00, 0x10],
+	)));
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+
 	assert_eq!(tag_re_read.frames.len(), 0);
 }
 
@@ -1536,50 +2101,1408 @@ fn split_tdrc_on_id3v23_save() {
 }
 
 #[test_log::test]
-fn artists_tag_conversion() {
-	const ARTISTS: &[&str] = &["Foo", "Bar", "Baz"];
-
-	let mut tag = Tag::new(TagType::Id3v2);
-
-	for artist in ARTISTS {
-		tag.push(TagItem::new(
-			ItemKey::TrackArtists,
-			ItemValue::Text((*artist).to_string()),
-		));
-	}
+fn partial_tdrc_on_id3v23_save() {
+	let mut tag = Id3v2Tag::new();
 
-	let tag: Id3v2Tag = tag.into();
-	let txxx_artists = tag.get_user_text("ARTISTS").unwrap();
-	let id3v2_artists = txxx_artists.split('\0').collect::<Vec<_>>();
+	// ID3v2.3 can't represent a month without a day, so only the year is kept
+	tag.insert(Frame::Timestamp(TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDRC")),
+		TextEncoding::UTF8,
+		Timestamp {
+			year: 1994,
+			month: Some(6),
+			..Timestamp::default()
+		},
+	)));
 
-	assert_eq!(id3v2_artists, ARTISTS);
-}
+	let mut bytes = Cursor::new(Vec::new());
+	tag.dump_to(&mut bytes, WriteOptions::default().use_id3v23(true))
+		.unwrap();
 
-#[test_log::test]
+	let tag_re_read = read_tag_with_options(
+		bytes.get_ref(),
+		ParseOptions::new()
+			.parsing_mode(ParsingMode::Strict)
+			.implicit_conversions(false),
+	);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TYER"))),
+		Some("1994")
+	);
+	assert!(tag_re_read
+		.get(&FrameId::Valid(Cow::Borrowed("TDAT")))
+		.is_none());
+
+	let tag_re_read = read_tag_with_options(
+		bytes.get_ref(),
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	let Some(Frame::Timestamp(frame)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TDRC")))
+	else {
+		panic!("Expected a TimestampFrame");
+	};
+	assert_eq!(
+		frame.timestamp,
+		Timestamp {
+			year: 1994,
+			..Timestamp::default()
+		}
+	);
+}
+
+#[test_log::test]
+fn replace_split_tdrc_on_id3v23_save() {
+	let mut tag = Id3v2Tag::new();
+
+	// Leftovers from a tag read without implicit conversions
+	for (id, value) in [("TYER", "1990"), ("TDAT", "0101"), ("TIME", "1200")] {
+		tag.insert(new_text_frame(
+			FrameId::Valid(Cow::Borrowed(id)),
+			String::from(value),
+		));
+	}
+
+	tag.insert(Frame::Timestamp(TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDRC")),
+		TextEncoding::UTF8,
+		Timestamp {
+			year: 2024,
+			month: Some(6),
+			day: Some(3),
+			..Timestamp::default()
+		},
+	)));
+
+	let mut bytes = Cursor::new(Vec::new());
+	tag.dump_to(&mut bytes, WriteOptions::default().use_id3v23(true))
+		.unwrap();
+
+	let tag_re_read = read_tag_with_options(
+		bytes.get_ref(),
+		ParseOptions::new()
+			.parsing_mode(ParsingMode::Strict)
+			.implicit_conversions(false),
+	);
+
+	// The time wasn't part of the new `TDRC`, so the old one is kept
+	assert_eq!(tag_re_read.len(), 3);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TYER"))),
+		Some("2024")
+	);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TDAT"))),
+		Some("0306")
+	);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TIME"))),
+		Some("1200")
+	);
+}
+
+#[test_log::test]
+fn merge_split_tdrc_on_id3v24_save() {
+	let mut tag = Id3v2Tag::new();
+
+	// As read from an ID3v2.3 tag without implicit conversions
+	for (id, value) in [("TYER", "2024"), ("TDAT", "0306"), ("TIME", "1408")] {
+		tag.insert(new_text_frame(
+			FrameId::Valid(Cow::Borrowed(id)),
+			String::from(value),
+		));
+	}
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+
+	assert_eq!(tag_re_read.len(), 1);
+	let Some(Frame::Timestamp(frame)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TDRC")))
+	else {
+		panic!("Expected a TimestampFrame");
+	};
+	assert_eq!(
+		frame.timestamp,
+		Timestamp {
+			year: 2024,
+			month: Some(6),
+			day: Some(3),
+			hour: Some(14),
+			minute: Some(8),
+			second: None,
+		}
+	);
+}
+
+#[test_log::test]
+fn partial_split_tdrc_on_id3v24_save() {
+	let mut tag = Id3v2Tag::new();
+
+	// Without a TDAT frame, the time can't be placed, so only the year is kept
+	for (id, value) in [("TYER", "2024"), ("TIME", "1408")] {
+		tag.insert(new_text_frame(
+			FrameId::Valid(Cow::Borrowed(id)),
+			String::from(value),
+		));
+	}
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+
+	assert_eq!(tag_re_read.len(), 1);
+	let Some(Frame::Timestamp(frame)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TDRC")))
+	else {
+		panic!("Expected a TimestampFrame");
+	};
+	assert_eq!(
+		frame.timestamp,
+		Timestamp {
+			year: 2024,
+			..Timestamp::default()
+		}
+	);
+}
+
+#[test_log::test]
+fn convert_v3_frames_on_id3v24_save() {
+	let mut tag = Id3v2Tag::new();
+
+	// As read from an ID3v2.3 tag without implicit conversions
+	tag.insert(new_text_frame(
+		FrameId::Valid(Cow::Borrowed("TORY")),
+		String::from("1994"),
+	));
+	tag.insert(Frame::Binary(BinaryFrame::new(
+		FrameId::Valid(Cow::Borrowed("IPLS")),
+		b"\x00producer\x00Foo\x00mixer\x00Bar\x00".to_vec(),
+	)));
+
+	// These have no ID3v2.4 equivalent
+	tag.insert(new_text_frame(
+		FrameId::Valid(Cow::Borrowed("TRDA")),
+		String::from("June 3rd"),
+	));
+	tag.insert(new_text_frame(
+		FrameId::Valid(Cow::Borrowed("TSIZ")),
+		String::from("1024"),
+	));
+
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+
+	assert_eq!(tag_re_read.len(), 2);
+
+	let Some(Frame::Timestamp(tdor)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TDOR")))
+	else {
+		panic!("Expected a TimestampFrame");
+	};
+	assert_eq!(
+		tdor.timestamp,
+		Timestamp {
+			year: 1994,
+			..Timestamp::default()
+		}
+	);
+
+	let Some(Frame::KeyValue(tipl)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TIPL")))
+	else {
+		panic!("Expected a KeyValueFrame");
+	};
+	assert_eq!(
+		tipl.key_value_pairs,
+		vec![
+			(String::from("producer"), String::from("Foo")),
+			(String::from("mixer"), String::from("Bar")),
+		]
+	);
+}
+
+#[test_log::test]
+fn replace_v3_frames_on_save() {
+	let mut tag = Id3v2Tag::new();
+
+	// Leftovers from a tag read without implicit conversions, alongside their ID3v2.4 equivalents
+	tag.insert(new_text_frame(
+		FrameId::Valid(Cow::Borrowed("TORY")),
+		String::from("1990"),
+	));
+	tag.insert(Frame::Binary(BinaryFrame::new(
+		FrameId::Valid(Cow::Borrowed("IPLS")),
+		b"\x00engineer\x00Baz\x00".to_vec(),
+	)));
+	tag.insert(Frame::Timestamp(TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDOR")),
+		TextEncoding::UTF8,
+		Timestamp {
+			year: 2024,
+			..Timestamp::default()
+		},
+	)));
+	tag.insert(Frame::KeyValue(KeyValueFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIPL")),
+		TextEncoding::Latin1,
+		vec![(String::from("producer"), String::from("Foo"))],
+	)));
+
+	// The ID3v2.4 frames take precedence when writing either version
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+	assert_eq!(tag_re_read.len(), 2);
+	assert!(tag_re_read
+		.get(&FrameId::Valid(Cow::Borrowed("TDOR")))
+		.is_some());
+	assert!(tag_re_read
+		.get(&FrameId::Valid(Cow::Borrowed("TIPL")))
+		.is_some());
+
+	let mut bytes = Cursor::new(Vec::new());
+	tag.dump_to(&mut bytes, WriteOptions::default().use_id3v23(true))
+		.unwrap();
+
+	let tag_re_read = read_tag_with_options(
+		bytes.get_ref(),
+		ParseOptions::new()
+			.parsing_mode(ParsingMode::Strict)
+			.implicit_conversions(false),
+	);
+
+	assert_eq!(tag_re_read.len(), 2);
+	assert_eq!(
+		tag_re_read.get_text(&FrameId::Valid(Cow::Borrowed("TORY"))),
+		Some("2024")
+	);
+	let Some(Frame::Binary(ipls)) = tag_re_read.get(&FrameId::Valid(Cow::Borrowed("IPLS"))) else {
+		panic!("Expected a BinaryFrame");
+	};
+	assert_eq!(ipls.data, b"\x00producer\x00Foo");
+}
+
+#[test_log::test]
+fn id3v23_id3v24_round_trip() {
+	let mut tag = Id3v2Tag::new();
+	tag.insert(Frame::Timestamp(TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDRC")),
+		TextEncoding::Latin1,
+		Timestamp {
+			year: 2024,
+			month: Some(6),
+			day: Some(3),
+			hour: Some(14),
+			minute: Some(8),
+			second: None,
+		},
+	)));
+	tag.insert(Frame::Timestamp(TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDOR")),
+		TextEncoding::Latin1,
+		Timestamp {
+			year: 1994,
+			..Timestamp::default()
+		},
+	)));
+	tag.insert(Frame::KeyValue(KeyValueFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIPL")),
+		TextEncoding::Latin1,
+		vec![(String::from("producer"), String::from("Foo"))],
+	)));
+
+	let mut v4_bytes = Cursor::new(Vec::new());
+	tag.dump_to(&mut v4_bytes, WriteOptions::default()).unwrap();
+
+	// ID3v2.4 -> ID3v2.3, read without implicit conversions to keep the ID3v2.3 frames
+	let mut v3_bytes = Cursor::new(Vec::new());
+	tag.dump_to(&mut v3_bytes, WriteOptions::default().use_id3v23(true))
+		.unwrap();
+
+	let v3_tag = read_tag_with_options(
+		v3_bytes.get_ref(),
+		ParseOptions::new()
+			.parsing_mode(ParsingMode::Strict)
+			.implicit_conversions(false),
+	);
+	assert_eq!(v3_tag.len(), 5);
+
+	// ID3v2.3 -> ID3v2.4 should produce the original tag
+	let mut round_trip_bytes = Cursor::new(Vec::new());
+	v3_tag
+		.dump_to(&mut round_trip_bytes, WriteOptions::default())
+		.unwrap();
+
+	let original = read_tag_with_options(
+		v4_bytes.get_ref(),
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	let round_trip = read_tag_with_options(
+		round_trip_bytes.get_ref(),
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	assert_eq!(round_trip.len(), 3);
+	for frame in &original {
+		assert_eq!(round_trip.get(frame.id()), Some(frame));
+	}
+}
+
+#[test_log::test]
+fn full_tdrc_in_id3v23() {
+	use crate::id3::v2::util::synchsafe::SynchsafeInteger;
+
+	// Some software writes `TDRC` to ID3v2.3 tags, it shouldn't be lost when reading
+	let content = b"\x002024-06-03";
+	let mut bytes = Vec::new();
+	bytes.extend(b"ID3\x03\x00\x00");
+	bytes.extend((10 + content.len() as u32).synch().unwrap().to_be_bytes());
+	bytes.extend(b"TDRC");
+	bytes.extend((content.len() as u32).to_be_bytes());
+	bytes.extend([0, 0]);
+	bytes.extend(content);
+
+	let tag = read_tag_with_options(&bytes, ParseOptions::new());
+	let Some(Frame::Timestamp(frame)) = tag.get(&FrameId::Valid(Cow::Borrowed("TDRC"))) else {
+		panic!("Expected a TimestampFrame");
+	};
+	assert_eq!(
+		frame.timestamp,
+		Timestamp {
+			year: 2024,
+			month: Some(6),
+			day: Some(3),
+			..Timestamp::default()
+		}
+	);
+}
+
+#[test_log::test]
+fn recording_date_round_trip() {
+	let date = Timestamp {
+		year: 1994,
+		month: Some(6),
+		..Timestamp::default()
+	};
+
+	let mut tag = Tag::new(TagType::Id3v2);
+	assert!(tag.set_recording_date(date));
+
+	let tag_re_read = dump_and_re_read(&Id3v2Tag::from(tag), WriteOptions::default());
+	assert_eq!(Tag::from(tag_re_read).recording_date(), Some(date));
+}
+
+fn flagged_text_frame(id: &'static str, value: &str, flags: FrameFlags) -> Frame<'static> {
+	let mut frame = new_text_frame(FrameId::Valid(Cow::Borrowed(id)), String::from(value));
+	frame.set_flags(flags);
+	frame
+}
+
+#[test_log::test]
+fn frame_flags_round_trip() {
+	let flags = FrameFlags {
+		tag_alter_preservation: true,
+		file_alter_preservation: true,
+		read_only: true,
+		grouping_identity: Some(0x42),
+		..FrameFlags::default()
+	};
+
+	let mut tag = Id3v2Tag::new();
+	tag.insert(flagged_text_frame("TIT2", "Foo title", flags));
+
+	// The flag bytes differ between versions, and the group byte is in a different position
+	// relative to other additional data
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let tag_re_read = dump_and_re_read(&tag, write_options);
+
+		let frame = tag_re_read
+			.get(&FrameId::Valid(Cow::Borrowed("TIT2")))
+			.unwrap();
+		assert_eq!(frame.flags(), flags);
+		assert_eq!(tag_re_read.title().as_deref(), Some("Foo title"));
+	}
+}
+
+#[test_log::test]
+fn unsynchronisation_flag_not_written() {
+	let flags = FrameFlags {
+		unsynchronisation: true,
+		..FrameFlags::default()
+	};
+
+	let mut tag = Id3v2Tag::new();
+	tag.insert(flagged_text_frame("TIT2", "Foo title", flags));
+
+	// The content isn't unsynchronised when writing, so neither is the flag
+	let tag_re_read = dump_and_re_read(&tag, WriteOptions::default());
+	let frame = tag_re_read
+		.get(&FrameId::Valid(Cow::Borrowed("TIT2")))
+		.unwrap();
+	assert_eq!(frame.flags(), FrameFlags::default());
+	assert_eq!(tag_re_read.title().as_deref(), Some("Foo title"));
+}
+
+#[test_log::test]
+fn frame_flags_survive_tag_conversion() {
+	let read_only = FrameFlags {
+		read_only: true,
+		grouping_identity: Some(1),
+		..FrameFlags::default()
+	};
+	let preserved = FrameFlags {
+		file_alter_preservation: true,
+		..FrameFlags::default()
+	};
+
+	let mut id3v2 = Id3v2Tag::new();
+	id3v2.insert(flagged_text_frame("TIT2", "Foo title", read_only));
+	id3v2.insert(flagged_text_frame("TPE1", "Bar artist", preserved));
+	id3v2.insert(flagged_text_frame("TRCK", "1/2", read_only));
+	id3v2.insert_user_text(String::from("FOO"), String::from("Baz"));
+	let user_text = id3v2.frames.last_mut().unwrap();
+	user_text.set_flags(preserved);
+
+	let (remainder, mut tag) = id3v2.split_tag();
+	assert!(remainder.is_empty());
+
+	// Changed frames lose their flags
+	tag.set_artist(String::from("Qux artist"));
+
+	let merged = remainder.merge_tag(tag);
+
+	let flags = |id: &str| {
+		merged
+			.frames
+			.iter()
+			.find(|frame| frame.id_str() == id)
+			.map(Frame::flags)
+			.unwrap()
+	};
+	assert_eq!(flags("TIT2"), read_only);
+	assert_eq!(flags("TRCK"), read_only);
+	assert_eq!(flags("TXXX"), preserved);
+	assert_eq!(flags("TPE1"), FrameFlags::default());
+
+	// And through a full `Tag` conversion
+	let tag: Tag = merged.into();
+	let id3v2: Id3v2Tag = tag.into();
+	let frame = id3v2.get(&FrameId::Valid(Cow::Borrowed("TIT2"))).unwrap();
+	assert_eq!(frame.flags(), read_only);
+
+	let tag_re_read = dump_and_re_read(&id3v2, WriteOptions::default());
+	let frame = tag_re_read
+		.get(&FrameId::Valid(Cow::Borrowed("TIT2")))
+		.unwrap();
+	assert_eq!(frame.flags(), read_only);
+}
+
+#[test_log::test]
+fn insert_respecting_read_only() {
+	let read_only = FrameFlags {
+		read_only: true,
+		..FrameFlags::default()
+	};
+
+	let mut tag = Id3v2Tag::new();
+	tag.insert(flagged_text_frame("TIT2", "Foo title", read_only));
+
+	let rejected = tag
+		.insert_respecting_read_only(flagged_text_frame(
+			"TIT2",
+			"Bar title",
+			FrameFlags::default(),
+		))
+		.unwrap_err();
+	assert_eq!(rejected.id_str(), "TIT2");
+	assert_eq!(tag.title().as_deref(), Some("Foo title"));
+
+	// Other frames are unaffected
+	assert!(tag
+		.insert_respecting_read_only(flagged_text_frame(
+			"TPE1",
+			"Bar artist",
+			FrameFlags::default()
+		))
+		.unwrap()
+		.is_none());
+
+	// `insert` always replaces
+	assert!(tag
+		.insert(flagged_text_frame(
+			"TIT2",
+			"Bar title",
+			FrameFlags::default()
+		))
+		.is_some());
+	assert_eq!(tag.title().as_deref(), Some("Bar title"));
+}
+
+#[test_log::test]
+fn artists_tag_conversion() {
+	const ARTISTS: &[&str] = &["Foo", "Bar", "Baz"];
+
+	let mut tag = Tag::new(TagType::Id3v2);
+
+	for artist in ARTISTS {
+		tag.push(TagItem::new(
+			ItemKey::TrackArtists,
+			ItemValue::Text((*artist).to_string()),
+		));
+	}
+
+	let tag: Id3v2Tag = tag.into();
+	let txxx_artists = tag.get_user_text("ARTISTS").unwrap();
+	let id3v2_artists = txxx_artists.split('\0').collect::<Vec<_>>();
+
+	assert_eq!(id3v2_artists, ARTISTS);
+}
+
+#[test_log::test]
 fn ensure_frame_skipping_within_bounds() {
 	// This tag has an invalid `TDEN` frame, but it is skippable in BestAttempt/Relaxed parsing mode.
 	// We should be able to continue reading the tag as normal, reaching the other `TDTG` frame.
 
-	let path = "tests/tags/assets/id3v2/skippable_frame_otherwise_valid.id3v24";
+	let path = "tests/tags/assets/id3v2/skippable_frame_otherwise_valid.id3v24";
+	let tag = read_tag_with_options(
+		&read_path(path),
+		ParseOptions::new().parsing_mode(ParsingMode::BestAttempt),
+	);
+
+	assert_eq!(tag.len(), 1);
+	assert_eq!(
+		tag.get(&FrameId::Valid(Cow::Borrowed("TDTG"))),
+		Some(&Frame::Timestamp(TimestampFrame::new(
+			FrameId::Valid(Cow::Borrowed("TDTG")),
+			TextEncoding::Latin1,
+			Timestamp {
+				year: 2014,
+				month: Some(6),
+				day: Some(10),
+				hour: Some(2),
+				minute: Some(16),
+				second: Some(10),
+			},
+		)))
+	);
+}
+
+#[test_log::test]
+fn retain_private_frames_by_owner() {
+	let mut tag = Id3v2Tag::new();
+	tag.set_title(String::from("Foo title"));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("com.apple.iTunes"),
+		vec![1, 2, 3],
+	)));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("WM/MediaClassPrimaryID"),
+		vec![4, 5, 6],
+	)));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("com.apple.Music"),
+		vec![7, 8, 9],
+	)));
+	tag.set_artist(String::from("Bar artist"));
+
+	tag.retain(|frame| match frame {
+		Frame::Private(private) => !private.owner.starts_with("com.apple"),
+		_ => true,
+	});
+
+	let ids = tag.frames().map(Frame::id_str).collect::<Vec<_>>();
+	assert_eq!(ids, ["TIT2", "PRIV", "TPE1"]);
+
+	let Some(Frame::Private(remaining)) = tag.frames().nth(1) else {
+		unreachable!()
+	};
+	assert_eq!(remaining.owner, "WM/MediaClassPrimaryID");
+}
+
+#[test_log::test]
+fn remove_all_private_frames() {
+	let mut tag = Id3v2Tag::new();
+	tag.set_title(String::from("Foo title"));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("com.apple.iTunes"),
+		vec![1, 2, 3],
+	)));
+	tag.set_artist(String::from("Bar artist"));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("WM/MediaClassPrimaryID"),
+		vec![4, 5, 6],
+	)));
+	tag.set_album(String::from("Baz album"));
+
+	let removed = tag
+		.remove_all(&FrameId::Valid(Cow::Borrowed("PRIV")))
+		.map(|frame| match frame {
+			Frame::Private(private) => private.owner,
+			_ => unreachable!(),
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(removed, ["com.apple.iTunes", "WM/MediaClassPrimaryID"]);
+
+	// The order of the remaining frames is preserved
+	let ids = tag.frames().map(Frame::id_str).collect::<Vec<_>>();
+	assert_eq!(ids, ["TIT2", "TPE1", "TALB"]);
+}
+
+#[test_log::test]
+fn frames_mut_edits_in_place() {
+	let mut tag = Id3v2Tag::new();
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("com.apple.iTunes"),
+		vec![1, 2, 3],
+	)));
+
+	for frame in tag.frames_mut() {
+		if let Frame::Private(private) = frame {
+			private.private_data.clear();
+		}
+	}
+
+	let Some(Frame::Private(private)) = tag.frames().next() else {
+		unreachable!()
+	};
+	assert!(private.private_data.is_empty());
+}
+
+#[test_log::test]
+fn encrypted_frame_round_trip() {
+	let flags = FrameFlags {
+		grouping_identity: Some(5),
+		compression: true,
+		encryption: Some(0x80),
+		data_length_indicator: Some(200),
+		..FrameFlags::default()
+	};
+
+	let mut frame = BinaryFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIT2")),
+		vec![1, 2, 3, 4, 5, 6, 7, 8],
+	);
+	frame.header.flags = flags;
+
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::Binary(frame.clone()));
+
+	// The frame can't be decrypted, it should be retained as-is
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let re_read = dump_and_re_read(&tag, write_options);
+		assert_eq!(re_read.len(), 1);
+
+		let Some(Frame::Binary(re_read_frame)) = re_read.frames().next() else {
+			panic!("expected an encrypted binary frame");
+		};
+		assert_eq!(re_read_frame, &frame);
+	}
+}
+
+#[test_log::test]
+fn oversized_frame_exceeds_item_limit() {
+	// A single TIT2 frame claiming to be ~256 MiB, with only 4 bytes of content
+	#[rustfmt::skip]
+	let tag_bytes = [
+		b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 14,
+		b'T', b'I', b'T', b'2', 0x7F, 0x7F, 0x7F, 0x7F, 0, 0,
+		3, b'a', b'b', b'c',
+	];
+
+	let parse_options = || ParseOptions::new().max_item_size(1024);
+
+	let mut reader = Cursor::new(&tag_bytes[..]);
+	let header = Id3v2Header::parse(&mut reader).unwrap();
+	let err = crate::id3::v2::read::parse_id3v2(
+		&mut reader,
+		header,
+		parse_options().parsing_mode(ParsingMode::Strict),
+	)
+	.unwrap_err();
+	assert!(matches!(
+		err.kind(),
+		ErrorKind::TooLarge {
+			limit: 1024,
+			requested: 0x0FFF_FFFF
+		}
+	));
+
+	let tag = read_tag_with_options(
+		&tag_bytes,
+		parse_options().parsing_mode(ParsingMode::Relaxed),
+	);
+	assert!(tag.is_empty());
+}
+
+#[test_log::test]
+fn tag_exceeds_tag_limit() {
+	let mut tag = Id3v2Tag::default();
+	tag.set_artist(String::from("Foo artist"));
+	tag.set_title(String::from("Foo title"));
+
+	let mut tag_bytes = Vec::new();
+	tag.dump_to(&mut tag_bytes, WriteOptions::default().preferred_padding(0))
+		.unwrap();
+
+	let parse_options = || ParseOptions::new().max_tag_size(16);
+
+	let mut reader = Cursor::new(&tag_bytes[..]);
+	let header = Id3v2Header::parse(&mut reader).unwrap();
+	let err = crate::id3::v2::read::parse_id3v2(
+		&mut reader,
+		header,
+		parse_options().parsing_mode(ParsingMode::Strict),
+	)
+	.unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::TooLarge { limit: 16, .. }));
+
+	let re_read = read_tag_with_options(&tag_bytes, parse_options());
+	assert!(re_read.is_empty());
+
+	// Within the limit, the tag is read as usual
+	let re_read = read_tag_with_options(&tag_bytes, ParseOptions::new().max_tag_size(1024));
+	assert_eq!(re_read.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(re_read.title().as_deref(), Some("Foo title"));
+}
+
+#[test_log::test]
+fn extended_header_crc_round_trip() {
+	let restrictions = TagRestrictions {
+		size: TagSizeRestrictions::S_32F_4K,
+		text_encoding: true,
+		text_fields_size: Some(TextSizeRestrictions::C_128),
+		image_encoding: false,
+		image_size: Some(ImageSizeRestrictions::P_64_64),
+	};
+
+	let mut tag = create_full_test_tag(Id3v2Version::V4);
+	tag.flags.crc = true;
+	tag.flags.restrictions = Some(restrictions);
+
+	for write_options in [
+		WriteOptions::default(),
+		WriteOptions::default().preferred_padding(0),
+		WriteOptions::default().use_id3v23(true),
+	] {
+		let parsed_tag = dump_and_re_read(&tag, write_options);
+		let extended_header = parsed_tag.extended_header().unwrap();
+
+		assert!(extended_header.crc.is_some());
+		assert!(!extended_header.is_update);
+		assert!(parsed_tag.flags().crc);
+		assert_eq!(
+			parsed_tag.title().as_deref(),
+			Some("TempleOS Hymn Risen (Remix)")
+		);
+
+		// Restrictions only exist in ID3v2.4
+		if write_options.use_id3v23 {
+			assert_eq!(extended_header.restrictions, None);
+		} else {
+			assert_eq!(extended_header.restrictions, Some(restrictions));
+			assert_eq!(parsed_tag.flags().restrictions, Some(restrictions));
+		}
+	}
+}
+
+#[test_log::test]
+fn extended_header_crc_mismatch() {
+	let mut tag = Id3v2Tag::new();
+	tag.insert(Frame::Text(TextInformationFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIT2")),
+		TextEncoding::Latin1,
+		String::from("Foo title"),
+	)));
+	tag.flags.crc = true;
+
+	for write_options in [
+		WriteOptions::default(),
+		WriteOptions::default().use_id3v23(true),
+	] {
+		let mut tag_bytes = Vec::new();
+		tag.dump_to(&mut tag_bytes, write_options).unwrap();
+
+		// Corrupt the title
+		let title_pos = tag_bytes
+			.windows(9)
+			.position(|window| window == b"Foo title")
+			.unwrap();
+		tag_bytes[title_pos] = b'B';
+
+		let mut reader = Cursor::new(&tag_bytes);
+		let header = Id3v2Header::parse(&mut reader).unwrap();
+		let err = crate::id3::v2::read::parse_id3v2(
+			&mut reader,
+			header,
+			ParseOptions::new().parsing_mode(ParsingMode::Strict),
+		)
+		.unwrap_err();
+
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::Id3v2(e) if matches!(e.kind(), Id3v2ErrorKind::CrcMismatch { .. })
+		));
+
+		// The tag is still usable in relaxed mode
+		let parsed_tag = read_tag_with_options(
+			&tag_bytes,
+			ParseOptions::new().parsing_mode(ParsingMode::Relaxed),
+		);
+		assert_eq!(parsed_tag.title().as_deref(), Some("Boo title"));
+	}
+}
+
+#[test_log::test]
+fn tag_restrictions_byte() {
+	let restrictions = TagRestrictions {
+		size: TagSizeRestrictions::S_64F_128K,
+		text_encoding: false,
+		text_fields_size: Some(TextSizeRestrictions::C_30),
+		image_encoding: true,
+		image_size: Some(ImageSizeRestrictions::P_256),
+	};
+
+	// %01011101
+	assert_eq!(restrictions.as_bytes(), 0x5D);
+	assert_eq!(TagRestrictions::from_byte(0x5D), restrictions);
+
+	for size in [
+		TagSizeRestrictions::S_128F_1M,
+		TagSizeRestrictions::S_64F_128K,
+		TagSizeRestrictions::S_32F_40K,
+		TagSizeRestrictions::S_32F_4K,
+	] {
+		let restrictions = TagRestrictions {
+			size,
+			..TagRestrictions::default()
+		};
+		assert_eq!(
+			TagRestrictions::from_byte(restrictions.as_bytes()),
+			restrictions
+		);
+	}
+}
+
+fn unsynchronisation_test_tag() -> Id3v2Tag {
+	let mut tag = Id3v2Tag::default();
+	tag.set_artist(String::from("Foo artist"));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("lofty"),
+		vec![0xFF, 0xFB, 0x90, 0x44, 0xFF, 0x00, 0x12, 0xFF, 0xE0, 0xFF],
+	)));
+
+	tag
+}
+
+fn assert_no_false_syncs(tag_bytes: &[u8]) {
+	assert!(!tag_bytes[10..]
+		.windows(2)
+		.any(|window| window[0] == 0xFF && window[1] >= 0xE0));
+}
+
+fn assert_unsynchronisation_round_trip(tag: &Id3v2Tag, re_read: &Id3v2Tag) {
+	assert_eq!(re_read.artist().as_deref(), Some("Foo artist"));
+
+	let original_private = tag.frames.iter().find_map(|frame| match frame {
+		Frame::Private(private) => Some(private),
+		_ => None,
+	});
+	let re_read_private = re_read.frames.iter().find_map(|frame| match frame {
+		Frame::Private(private) => Some(private),
+		_ => None,
+	});
+	assert_eq!(
+		re_read_private.unwrap().private_data,
+		original_private.unwrap().private_data
+	);
+}
+
+#[test_log::test]
+fn unsynchronisation_id3v24() {
+	let tag = unsynchronisation_test_tag();
+	let write_options = WriteOptions::default().unsynchronisation(true);
+
+	let mut tag_bytes = Vec::new();
+	tag.dump_to(&mut tag_bytes, write_options).unwrap();
+
+	assert_eq!(tag_bytes[5] & 0x80, 0x80);
+	assert_no_false_syncs(&tag_bytes);
+	assert_eq!(
+		tag.serialized_len(write_options).unwrap(),
+		tag_bytes.len() as u64
+	);
+
+	let re_read = read_tag_with_options(
+		&tag_bytes,
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	assert!(re_read.flags().unsynchronisation);
+	assert!(re_read
+		.frames
+		.iter()
+		.all(|frame| frame.flags().unsynchronisation));
+	assert_unsynchronisation_round_trip(&tag, &re_read);
+
+	// The flag is kept when writing the tag back out
+	let mut re_written_bytes = Vec::new();
+	re_read
+		.dump_to(&mut re_written_bytes, WriteOptions::default())
+		.unwrap();
+	assert_eq!(re_written_bytes, tag_bytes);
+}
+
+#[test_log::test]
+fn unsynchronisation_id3v23() {
+	let mut tag = unsynchronisation_test_tag();
+	tag.set_flags(Id3v2TagFlags {
+		crc: true,
+		..Id3v2TagFlags::default()
+	});
+
+	let write_options = WriteOptions::default()
+		.use_id3v23(true)
+		.unsynchronisation(true);
+
+	let mut tag_bytes = Vec::new();
+	tag.dump_to(&mut tag_bytes, write_options).unwrap();
+
+	assert_eq!(tag_bytes[3], 3);
+	assert_eq!(tag_bytes[5] & 0x80, 0x80);
+	assert_no_false_syncs(&tag_bytes);
+	assert_eq!(
+		tag.serialized_len(write_options).unwrap(),
+		tag_bytes.len() as u64
+	);
+
+	let re_read = read_tag_with_options(
+		&tag_bytes,
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	assert!(re_read.flags().unsynchronisation);
+	assert_unsynchronisation_round_trip(&tag, &re_read);
+}
+
+#[test_log::test]
+fn unsynchronisation_disabled_by_default() {
+	let tag = unsynchronisation_test_tag();
+
+	let mut tag_bytes = Vec::new();
+	tag.dump_to(&mut tag_bytes, WriteOptions::default())
+		.unwrap();
+
+	assert_eq!(tag_bytes[5] & 0x80, 0);
+
+	let re_read = dump_and_re_read(&tag, WriteOptions::default());
+	assert!(!re_read.flags().unsynchronisation);
+	assert_unsynchronisation_round_trip(&tag, &re_read);
+}
+
+#[test_log::test]
+fn id3v24_unsynchronisation_flag_not_applied_twice() {
+	// An ID3v2.4 tag with the unsynchronisation header flag, whose only frame is unsynchronised
+	// on its own. The frame must only be decoded once.
+	let mut tag_bytes = vec![b'I', b'D', b'3', 4, 0, 0x80, 0, 0, 0, 16];
+	tag_bytes.extend(b"TPE1");
+	tag_bytes.extend([0, 0, 0, 6, 0x00, 0x02]);
+	tag_bytes.extend([0x00, b'F', b'o', b'o', 0xFF, 0x00]);
+
 	let tag = read_tag_with_options(
-		&read_path(path),
-		ParseOptions::new().parsing_mode(ParsingMode::BestAttempt),
+		&tag_bytes,
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	assert_eq!(tag.artist().as_deref(), Some("Foo\u{FF}"));
+}
+
+#[test_log::test]
+fn chapter_frames_round_trip() {
+	let mut toc = TableOfContentsFrame::new(
+		String::from("toc"),
+		vec![String::from("chp0"), String::from("chp1")],
+	);
+	toc.frames.push(Frame::Text(TextInformationFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIT2")),
+		TextEncoding::UTF8,
+		String::from("Contents"),
+	)));
+
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::TableOfContents(toc));
+
+	// Insert out of order, `chapters()` sorts by start time
+	for (element_id, start_time, title) in [("chp1", 5000, "Second"), ("chp0", 0, "First")] {
+		let mut chapter =
+			ChapterFrame::new(String::from(element_id), start_time, start_time + 5000);
+		chapter.frames.push(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TIT2")),
+			TextEncoding::UTF8,
+			String::from(title),
+		)));
+		assert!(tag.insert_chapter(chapter).is_none());
+	}
+
+	// Replaces the existing chapter with the same element ID
+	let mut replacement = ChapterFrame::new(String::from("chp1"), 5000, 12000);
+	replacement
+		.frames
+		.push(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TIT2")),
+			TextEncoding::UTF8,
+			String::from("Second"),
+		)));
+	assert!(tag.insert_chapter(replacement).is_some());
+
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let re_read = dump_and_re_read(&tag, write_options);
+		assert_eq!(re_read.len(), 3);
+
+		let chapters = re_read.chapters().collect::<Vec<_>>();
+		assert_eq!(chapters.len(), 2);
+		assert_eq!(chapters[0].element_id, "chp0");
+		assert_eq!(chapters[0].title(), Some("First"));
+		assert_eq!(chapters[1].element_id, "chp1");
+		assert_eq!(chapters[1].end_time, 12000);
+		assert_eq!(chapters[1].title(), Some("Second"));
+
+		let toc = re_read.tables_of_contents().next().unwrap();
+		assert_eq!(toc.child_element_ids, ["chp0", "chp1"]);
+		assert_eq!(toc.frames.len(), 1);
+	}
+}
+
+#[test_log::test]
+fn chapter_with_encrypted_sub_frame() {
+	let mut sub_frame = BinaryFrame::new(
+		FrameId::Valid(Cow::Borrowed("TIT2")),
+		vec![1, 2, 3, 4, 5, 6, 7, 8],
+	);
+	sub_frame.header.flags = FrameFlags {
+		encryption: Some(0x80),
+		data_length_indicator: Some(200),
+		..FrameFlags::default()
+	};
+
+	let mut chapter = ChapterFrame::new(String::from("chp0"), 0, 1000);
+	chapter.frames.push(Frame::Binary(sub_frame.clone()));
+
+	let mut tag = Id3v2Tag::default();
+	tag.insert_chapter(chapter);
+
+	// The sub-frame can't be decrypted, it should be retained as-is
+	let re_read = dump_and_re_read(&tag, WriteOptions::new());
+	let chapter = re_read.chapters().next().unwrap();
+	assert_eq!(chapter.frames, [Frame::Binary(sub_frame)]);
+	assert_eq!(chapter.title(), None);
+}
+
+#[test_log::test]
+fn general_object_frame_round_trip() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::GeneralObject(GeneralObjectFrame::new(
+		TextEncoding::UTF8,
+		Some(String::from("application/octet-stream")),
+		Some(String::from("cues.bin")),
+		String::from("Serato Markers2"),
+		vec![1, 2, 3],
+	)));
+
+	// Replaces the existing frame with the same description
+	let expected = GeneralObjectFrame::new(
+		TextEncoding::UTF8,
+		Some(String::from("application/octet-stream")),
+		None,
+		String::from("Serato Markers2"),
+		vec![4, 5, 6],
+	);
+	assert!(tag.insert(Frame::GeneralObject(expected.clone())).is_some());
+	assert_eq!(tag.len(), 1);
+
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let re_read = dump_and_re_read(&tag, write_options);
+		assert_eq!(re_read.len(), 1);
+
+		let Some(Frame::GeneralObject(object)) = re_read.frames().next() else {
+			panic!("expected a GEOB frame");
+		};
+		assert_eq!(object.mime_type, expected.mime_type);
+		assert_eq!(object.file_name, None);
+		assert_eq!(object.description, expected.description);
+		assert_eq!(object.data, expected.data);
+	}
+}
+
+#[test_log::test]
+fn etco_round_trip() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::EventTimingCodes(EventTimingCodesFrame::new(
+		TimestampFormat::MS,
+		vec![
+			Event {
+				event_type: EventType::IntroStart,
+				timestamp: 0,
+			},
+			Event {
+				event_type: EventType::Other(0x17),
+				timestamp: 1500,
+			},
+			Event {
+				event_type: EventType::AudioEnd,
+				timestamp: 1500,
+			},
+		],
+	)));
+
+	let mut tag_bytes = Vec::new();
+	tag.dump_to(&mut tag_bytes, WriteOptions::new()).unwrap();
+
+	let re_read = read_tag_with_options(
+		&tag_bytes[..],
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
 	);
+	assert_eq!(re_read, tag);
+
+	// An untouched frame is written back as-is
+	let mut re_written_bytes = Vec::new();
+	re_read
+		.dump_to(&mut re_written_bytes, WriteOptions::new())
+		.unwrap();
+	assert_eq!(re_written_bytes, tag_bytes);
+}
+
+#[test_log::test]
+fn mcdi_round_trip() {
+	// A valid TOC, and one that can't be parsed, should both be written back as-is
+	let valid_toc = vec![
+		0x00, 0x12, 0x01, 0x01, // Header
+		0x00, 0x10, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, // Track 1
+		0x00, 0x10, 0xAA, 0x00, 0x00, 0x00, 0x3A, 0x98, // Lead-out
+	];
+	let invalid_toc = vec![0x00, 0xFF, 0x01];
+
+	for data in [valid_toc, invalid_toc] {
+		let mut tag = Id3v2Tag::default();
+		tag.insert(Frame::MusicCdIdentifier(MusicCdIdentifierFrame::new(
+			data.clone(),
+		)));
+
+		for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+			let re_read = dump_and_re_read(&tag, write_options);
+
+			let Some(Frame::MusicCdIdentifier(mcdi)) = re_read.frames().next() else {
+				panic!("expected an MCDI frame");
+			};
+			assert_eq!(mcdi.data, data);
+		}
+	}
+}
+
+#[test_log::test]
+fn pcnt_round_trip() {
+	// A 5 byte counter, one past `u32::MAX`
+	let counter_content = read_path("tests/tags/assets/id3v2/test.pcnt");
+
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::PlayCounter(
+		PlayCounterFrame::parse(&mut &counter_content[..], FrameFlags::default())
+			.unwrap()
+			.unwrap(),
+	));
+	assert_eq!(tag.play_count(), Some(u64::from(u32::MAX) + 1));
+
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let re_read = dump_and_re_read(&tag, write_options);
+		assert_eq!(re_read.play_count(), Some(u64::from(u32::MAX) + 1));
+
+		let Some(Frame::PlayCounter(play_counter)) = re_read.frames().next() else {
+			panic!("expected a PCNT frame");
+		};
+		assert_eq!(play_counter.as_bytes(), counter_content);
+	}
 
+	// Only a single frame is kept
+	assert_eq!(tag.increment_play_count(), u64::from(u32::MAX) + 2);
+	tag.insert(Frame::PlayCounter(PlayCounterFrame::new(1)));
 	assert_eq!(tag.len(), 1);
+	assert_eq!(tag.play_count(), Some(1));
+}
+
+#[test_log::test]
+fn owne_round_trip() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::Ownership(OwnershipFrame::new(
+		TextEncoding::UTF16,
+		String::from("USD9.99"),
+		String::from("20240101"),
+		String::from("Foo Store"),
+	)));
+
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let re_read = dump_and_re_read(&tag, write_options);
+
+		let Some(Frame::Ownership(ownership)) = re_read.frames().next() else {
+			panic!("expected an OWNE frame");
+		};
+		assert_eq!(ownership.price_paid, "USD9.99");
+		assert_eq!(ownership.date_of_purchase, "20240101");
+		assert_eq!(ownership.seller, "Foo Store");
+	}
+}
+
+fn sync_lyrics() -> SynchronizedTextFrame<'static> {
+	SynchronizedTextFrame::new(
+		TextEncoding::UTF8,
+		*b"eng",
+		TimestampFormat::MS,
+		SyncTextContentType::Lyrics,
+		None,
+		vec![
+			(0, String::from("Foo ")),
+			(1000, String::from("bar\n")),
+			(2000, String::from("Baz")),
+		],
+	)
+}
+
+#[test_log::test]
+fn sylt_round_trip() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::SynchronizedText(sync_lyrics()));
+
+	for write_options in [WriteOptions::new(), WriteOptions::new().use_id3v23(true)] {
+		let re_read = dump_and_re_read(&tag, write_options);
+		assert_eq!(re_read.len(), 1);
+
+		let Some(Frame::SynchronizedText(sync_text)) = re_read.frames().next() else {
+			panic!("expected a SYLT frame");
+		};
+		assert_eq!(sync_text.language, *b"eng");
+		assert_eq!(sync_text.timestamp_format, TimestampFormat::MS);
+		assert_eq!(sync_text.content, sync_lyrics().content);
+	}
+}
+
+#[test_log::test]
+fn sylt_to_tag_lyrics() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::SynchronizedText(sync_lyrics()));
+
+	let (remainder, split) = tag.clone().split_tag();
+	assert_eq!(split.get_string(&ItemKey::Lyrics), Some("Foo bar\nBaz"));
+	assert_eq!(split.get(&ItemKey::Lyrics).unwrap().lang(), b"eng");
+
+	// Unchanged, the lyrics aren't duplicated into a USLT frame
+	let merged = remainder.clone().merge_tag(split.clone());
+	assert_eq!(merged.len(), 1);
+	assert!(matches!(
+		merged.frames().next(),
+		Some(Frame::SynchronizedText(_))
+	));
+
+	// Changed, the lyrics are written separately
+	let mut changed = split.clone();
+	let original = changed.take(&ItemKey::Lyrics).next().unwrap();
+	let mut lyrics = TagItem::new(ItemKey::Lyrics, ItemValue::Text(String::from("Qux")));
+	lyrics.set_lang(*original.lang());
+	changed.push(lyrics);
+
+	let merged = remainder.clone().merge_tag(changed);
+	assert_eq!(merged.len(), 2);
+	assert_eq!(merged.unsync_text().next().unwrap().content, "Qux");
+
+	// Replaced, the frame is removed
+	let mut replaced = split.clone();
+	replaced.insert_text(ItemKey::Lyrics, String::from("Qux"));
+	let merged = remainder.clone().merge_tag(replaced);
+	assert_eq!(merged.len(), 1);
+	assert_eq!(merged.unsync_text().next().unwrap().content, "Qux");
+
+	// Removed, the frame is removed as well
+	let mut removed = split;
+	removed.remove_key(&ItemKey::Lyrics);
+	let merged = remainder.merge_tag(removed);
+	assert!(merged.is_empty());
+}
+
+#[test_log::test]
+fn sylt_uslt_lyrics_precedence() {
+	let mut tag = Id3v2Tag::default();
+	tag.insert(Frame::SynchronizedText(sync_lyrics()));
+	tag.insert(Frame::UnsynchronizedText(UnsynchronizedTextFrame::new(
+		TextEncoding::UTF8,
+		*b"eng",
+		String::new(),
+		String::from("Unsynchronized lyrics"),
+	)));
+
+	let (remainder, split) = tag.split_tag();
+	assert_eq!(split.len(), 1);
 	assert_eq!(
-		tag.get(&FrameId::Valid(Cow::Borrowed("TDTG"))),
-		Some(&Frame::Timestamp(TimestampFrame::new(
-			FrameId::Valid(Cow::Borrowed("TDTG")),
-			TextEncoding::Latin1,
-			Timestamp {
-				year: 2014,
-				month: Some(6),
-				day: Some(10),
-				hour: Some(2),
-				minute: Some(16),
-				second: Some(10),
-			},
-		)))
+		split.get_string(&ItemKey::Lyrics),
+		Some("Unsynchronized lyrics")
+	);
+
+	let merged = remainder.merge_tag(split);
+	assert_eq!(merged.len(), 2);
+}
+
+#[test_log::test]
+fn id3v22_save() {
+	let mut tag = Id3v2Tag::new();
+	tag.set_title(String::from("Foo title"));
+	tag.set_artist(String::from("Bar artist"));
+	tag.insert(Frame::Timestamp(TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDRC")),
+		TextEncoding::UTF8,
+		Timestamp {
+			year: 2024,
+			month: Some(3),
+			day: Some(14),
+			..Timestamp::default()
+		},
+	)));
+	tag.insert_picture(Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		None,
+		vec![1, 2, 3],
+	));
+
+	// No ID3v2.2 equivalent, discarded
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("foo@bar.com"),
+		vec![1, 2, 3],
+	)));
+
+	let mut tag_bytes = Vec::new();
+	tag.dump_to(&mut tag_bytes, WriteOptions::default().use_id3v22(true))
+		.unwrap();
+
+	assert_eq!(&tag_bytes[..6], b"ID3\x02\x00\x00");
+	assert_eq!(
+		tag.serialized_len(WriteOptions::default().use_id3v22(true))
+			.unwrap(),
+		tag_bytes.len() as u64
+	);
+
+	let contains = |id: &[u8]| tag_bytes.windows(id.len()).any(|window| window == id);
+	assert!(contains(b"TT2"));
+	assert!(contains(b"TP1"));
+	assert!(contains(b"TYE"));
+	assert!(contains(b"TDA"));
+	assert!(contains(b"PIC"));
+	assert!(!contains(b"PRIV"));
+	assert!(!contains(b"TIT2"));
+
+	let tag_re_read = read_tag_with_options(
+		&tag_bytes[..],
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	);
+	assert_eq!(tag_re_read.original_version(), Id3v2Version::V2);
+	assert_eq!(tag_re_read.title().as_deref(), Some("Foo title"));
+	assert_eq!(tag_re_read.artist().as_deref(), Some("Bar artist"));
+	assert_eq!(tag_re_read.year(), Some(2024));
+	let Some(Frame::Timestamp(TimestampFrame { timestamp, .. })) =
+		tag_re_read.get(&FrameId::Valid(Cow::Borrowed("TDRC")))
+	else {
+		panic!("Expected a TDRC frame");
+	};
+	assert_eq!(timestamp.day, Some(14));
+	assert!(tag_re_read
+		.get(&FrameId::Valid(Cow::Borrowed("PRIV")))
+		.is_none());
+	assert_eq!(
+		tag_re_read
+			.into_iter()
+			.filter(|frame| matches!(frame, Frame::Picture(_)))
+			.count(),
+		1
 	);
 }
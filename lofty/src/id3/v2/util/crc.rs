@@ -0,0 +1,29 @@
+use std::ops::Not;
+use std::sync::OnceLock;
+
+// In the very rare chance someone wants a CRC in their extended header
+fn crc_32_table() -> &'static [u32; 256] {
+	static INSTANCE: OnceLock<[u32; 256]> = OnceLock::new();
+	INSTANCE.get_or_init(|| {
+		let mut crc32_table = [0; 256];
+
+		for n in 0..256 {
+			crc32_table[n as usize] = (0..8).fold(n as u32, |acc, _| match acc & 1 {
+				1 => 0xEDB8_8320 ^ (acc >> 1),
+				_ => acc >> 1,
+			});
+		}
+
+		crc32_table
+	})
+}
+
+// https://github.com/rstemmer/id3edit/blob/0246f3dc1a7a80a64461eeeb7b9ee88379003eb1/encoding/crc.c#L6:6
+pub(crate) fn crc32(content: &[u8]) -> u32 {
+	content
+		.iter()
+		.fold(!0, |crc, octet| {
+			(crc >> 8) ^ crc_32_table()[(((crc & 0xFF) ^ u32::from(*octet)) & 0xFF) as usize]
+		})
+		.not()
+}
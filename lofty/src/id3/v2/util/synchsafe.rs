@@ -97,6 +97,30 @@ impl<R> UnsynchronizedStream<R> {
 	pub fn get_ref(&self) -> &R {
 		&self.reader
 	}
+
+	/// Get a mutable reference to the inner reader
+	///
+	/// NOTE: Reading directly from the inner reader will bypass any content already buffered
+	///       by the [`UnsynchronizedStream`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::id3::v2::util::synchsafe::UnsynchronizedStream;
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let reader = Cursor::new([0xFF, 0x00, 0x1A]);
+	/// let mut unsynchronized_reader = UnsynchronizedStream::new(reader);
+	///
+	/// let reader = unsynchronized_reader.get_mut();
+	/// reader.set_position(1);
+	/// assert_eq!(reader.position(), 1);
+	/// # Ok(()) }
+	/// ```
+	pub fn get_mut(&mut self) -> &mut R {
+		&mut self.reader
+	}
 }
 
 impl<R: Read> Read for UnsynchronizedStream<R> {
@@ -147,6 +171,23 @@ impl<R: Read> Read for UnsynchronizedStream<R> {
 	}
 }
 
+/// Unsynchronise content, the reverse of [`UnsynchronizedStream`]
+///
+/// A `0x00` is inserted after every `0xFF` that is followed by `0x00` or a byte >= `0xE0`,
+/// or that ends the content.
+pub(crate) fn unsynchronise(content: &[u8]) -> Vec<u8> {
+	let mut unsynchronised = Vec::with_capacity(content.len());
+	for (i, byte) in content.iter().copied().enumerate() {
+		unsynchronised.push(byte);
+
+		if byte == 0xFF && !matches!(content.get(i + 1), Some(0x01..=0xDF)) {
+			unsynchronised.push(0);
+		}
+	}
+
+	unsynchronised
+}
+
 /// An integer that can be converted to and from synchsafe variants
 pub trait SynchsafeInteger: Sized {
 	/// The integer type that this can be widened to for use in [`SynchsafeInteger::widening_synch`]
@@ -349,6 +390,27 @@ mod tests {
 		assert_eq!(final_content, EXPECTED.repeat(1000));
 	}
 
+	#[test_log::test]
+	fn unsynchronise() {
+		// Only false syncs are broken up
+		const ORIGINAL_CONTENT: &[u8] = &[0xFF, 0x1A, 0xFF, 0xE0, 0xFF, 0xDF, 0xFF];
+
+		assert_eq!(super::unsynchronise(EXPECTED), UNSYNCHRONIZED_CONTENT);
+
+		let unsynchronised = super::unsynchronise(ORIGINAL_CONTENT);
+		assert_eq!(
+			unsynchronised,
+			[0xFF, 0x1A, 0xFF, 0x00, 0xE0, 0xFF, 0xDF, 0xFF, 0x00]
+		);
+
+		let mut final_content = Vec::new();
+		UnsynchronizedStream::new(Cursor::new(unsynchronised))
+			.read_to_end(&mut final_content)
+			.unwrap();
+
+		assert_eq!(final_content, ORIGINAL_CONTENT);
+	}
+
 	#[test_log::test]
 	fn unsynchronized_stream_should_not_replace_unrelated() {
 		const ORIGINAL_CONTENT: &[u8] = &[0xFF, 0x1A, 0xFF, 0xC0, 0x10, 0x01];
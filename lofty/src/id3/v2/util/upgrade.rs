@@ -1,4 +1,6 @@
 //! Utilities for upgrading old ID3v2 frame IDs
+//!
+//! The reverse, downgrading to ID3v2.2 frame IDs, is only used when writing ID3v2.2 tags.
 
 use std::collections::HashMap;
 
@@ -34,6 +36,100 @@ pub fn upgrade_v3(key: &str) -> Option<&'static str> {
 	v3keys().get(key).copied()
 }
 
+// Downgrade an ID3v2.3 key to an ID3v2.2 key
+//
+// ID3v2.4 frames need to be converted to ID3v2.3 first (e.g. `TDRC` -> `TYER`, `TDAT`, `TIME`).
+pub(in crate::id3::v2) fn downgrade_v3_to_v2(key: &str) -> Option<&'static str> {
+	let key = match key {
+		// Standard frames
+		"AENC" => "CRA",
+		"APIC" => "PIC",
+		"COMM" => "COM",
+		"EQUA" => "EQU",
+		"ETCO" => "ETC",
+		"GEOB" => "GEO",
+		"IPLS" => "IPL",
+		"LINK" => "LNK",
+		"MCDI" => "MCI",
+		"MLLT" => "MLL",
+		"PCNT" => "CNT",
+		"POPM" => "POP",
+		"RBUF" => "BUF",
+		"RVAD" => "RVA",
+		"RVRB" => "REV",
+		"SYLT" => "SLT",
+		"SYTC" => "STC",
+		"TALB" => "TAL",
+		"TBPM" => "TBP",
+		"TCOM" => "TCM",
+		"TCON" => "TCO",
+		"TCOP" => "TCR",
+		"TDAT" => "TDA",
+		"TDLY" => "TDY",
+		"TENC" => "TEN",
+		"TEXT" => "TXT",
+		"TFLT" => "TFT",
+		"TIME" => "TIM",
+		"TIT1" => "TT1",
+		"TIT2" => "TT2",
+		"TIT3" => "TT3",
+		"TKEY" => "TKE",
+		"TLAN" => "TLA",
+		"TLEN" => "TLE",
+		"TMED" => "TMT",
+		"TOAL" => "TOT",
+		"TOFN" => "TOF",
+		"TOLY" => "TOL",
+		"TOPE" => "TOA",
+		"TORY" => "TOR",
+		"TPE1" => "TP1",
+		"TPE2" => "TP2",
+		"TPE3" => "TP3",
+		"TPE4" => "TP4",
+		"TPOS" => "TPA",
+		"TPUB" => "TPB",
+		"TRCK" => "TRK",
+		"TRDA" => "TRD",
+		"TSIZ" => "TSI",
+		"TSRC" => "TRC",
+		"TSSE" => "TSS",
+		"TXXX" => "TXX",
+		"TYER" => "TYE",
+		"UFID" => "UFI",
+		"USLT" => "ULT",
+		"WCOM" => "WCM",
+		"WCOP" => "WCP",
+		"WOAF" => "WAF",
+		"WOAR" => "WAR",
+		"WOAS" => "WAS",
+		"WPUB" => "WPB",
+		"WXXX" => "WXX",
+
+		// iTunes non-standard frames
+		"TCMP" => "TCP",
+		"TSO2" => "TS2",
+		"TSOA" => "TSA",
+		"TSOC" => "TSC",
+		"TSOP" => "TSP",
+		"TSOT" => "TST",
+
+		// Podcast
+		"PCST" => "PCS",
+		"TCAT" => "TCT",
+		"TDES" => "TDS",
+		"TGID" => "TID",
+		"WFED" => "WFD",
+
+		// Identifiers
+		"MVIN" => "MVI",
+		"MVNM" => "MVN",
+		"GRP1" => "GP1",
+		_ => return None,
+	};
+
+	Some(key)
+}
+
 macro_rules! gen_upgrades {
     (V2 => [$($($v2_key:literal)|* => $id3v24_from_v2:literal),+]; V3 => [$($($v3_key:literal)|* => $id3v24_from_v3:literal),+]) => {
 		use std::sync::OnceLock;
@@ -90,9 +186,11 @@ gen_upgrades!(
 		"TCO" => "TCON",
 		"TCP" => "TCMP",
 		"TCR" => "TCOP",
+		"TDA" => "TDAT",
 		"TDY" => "TDLY",
 		"TEN" => "TENC",
 		"TFT" => "TFLT",
+		"TIM" => "TIME",
 		"TKE" => "TKEY",
 		"TLA" => "TLAN",
 		"TLE" => "TLEN",
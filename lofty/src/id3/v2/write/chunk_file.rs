@@ -1,18 +1,21 @@
+use super::create_tag;
 use crate::config::WriteOptions;
 use crate::error::{LoftyError, Result};
+use crate::id3::v2::frame::FrameRef;
+use crate::id3::v2::tag::Id3v2TagRef;
 use crate::iff::chunk::Chunks;
 use crate::util::io::{FileLike, Length, Truncate};
 
 use std::io::SeekFrom;
 
-use byteorder::{ByteOrder, WriteBytesExt};
+use byteorder::{ByteOrder, ReadBytesExt, WriteBytesExt};
 
 const CHUNK_NAME_UPPER: [u8; 4] = [b'I', b'D', b'3', b' '];
 const CHUNK_NAME_LOWER: [u8; 4] = [b'i', b'd', b'3', b' '];
 
-pub(in crate::id3::v2) fn write_to_chunk_file<F, B>(
+pub(in crate::id3::v2) fn write_to_chunk_file<'a, F, B, I>(
 	file: &mut F,
-	tag: &[u8],
+	tag: &mut Id3v2TagRef<'a, I>,
 	write_options: WriteOptions,
 ) -> Result<()>
 where
@@ -20,20 +23,29 @@ where
 	LoftyError: From<<F as Truncate>::Error>,
 	LoftyError: From<<F as Length>::Error>,
 	B: ByteOrder,
+	I: Iterator<Item = FrameRef<'a>> + 'a,
 {
 	// RIFF....WAVE
 	file.seek(SeekFrom::Current(12))?;
 
 	let file_len = file.len()?.saturating_sub(12);
 
-	let mut id3v2_chunk = (None, None);
+	// Some software adds a new chunk rather than updating the existing one, so there may be several
+	let mut id3v2_chunks = Vec::new();
 
 	let mut chunks = Chunks::<B>::new(file_len);
 
 	while chunks.next(file).is_ok() {
 		if chunks.fourcc == CHUNK_NAME_UPPER || chunks.fourcc == CHUNK_NAME_LOWER {
-			id3v2_chunk = (Some(file.stream_position()? - 8), Some(chunks.size));
-			break;
+			let chunk_start = file.stream_position()? - 8;
+
+			// We need to remove the padding byte if it exists
+			let mut chunk_size = u64::from(chunks.size);
+			if chunk_size % 2 != 0 {
+				chunk_size += 1;
+			}
+
+			id3v2_chunks.push(chunk_start as usize..(chunk_start + chunk_size + 8) as usize);
 		}
 
 		file.seek(SeekFrom::Current(i64::from(chunks.size)))?;
@@ -41,21 +53,41 @@ where
 		chunks.correct_position(file)?;
 	}
 
-	if let (Some(chunk_start), Some(mut chunk_size)) = id3v2_chunk {
-		file.rewind()?;
+	// The chunk can only be overwritten if there's just one
+	let mut preserved_len = None;
+	if write_options.preserve_id3v2_size {
+		if let [chunk] = &*id3v2_chunks {
+			file.seek(SeekFrom::Start(chunk.start as u64 + 4))?;
+			preserved_len = Some(u64::from(file.read_u32::<B>()?));
+		}
+	}
+
+	let tag = create_tag(tag, write_options, preserved_len)?;
+	if let (Some(preserved_len), [chunk]) = (preserved_len, &*id3v2_chunks) {
+		if preserved_len == tag.len() as u64 {
+			log::debug!("Writing ID3v2 chunk in place");
+
+			file.seek(SeekFrom::Start(chunk.start as u64 + 8))?;
+			file.write_all(&tag)?;
+			return Ok(());
+		}
+	}
 
-		// We need to remove the padding byte if it exists
-		if chunk_size % 2 != 0 {
-			chunk_size += 1;
+	if !id3v2_chunks.is_empty() {
+		if id3v2_chunks.len() > 1 {
+			log::warn!("Removing {} duplicate ID3v2 chunks", id3v2_chunks.len() - 1);
 		}
 
+		file.rewind()?;
+
 		let mut file_bytes = Vec::new();
 		file.read_to_end(&mut file_bytes)?;
 
-		file_bytes.splice(
-			chunk_start as usize..(chunk_start + u64::from(chunk_size) + 8) as usize,
-			[],
-		);
+		// Remove them back to front, so the earlier ranges stay valid
+		for range in id3v2_chunks.into_iter().rev() {
+			let end = range.end.min(file_bytes.len());
+			file_bytes.drain(range.start..end);
+		}
 
 		file.rewind()?;
 		file.truncate(0)?;
@@ -72,20 +104,21 @@ where
 		}
 
 		file.write_u32::<B>(tag.len() as u32)?;
-		file.write_all(tag)?;
+		file.write_all(&tag)?;
 
 		// It is required an odd length chunk be padded with a 0
 		// The 0 isn't included in the chunk size, however
 		if tag.len() % 2 != 0 {
 			file.write_u8(0)?;
 		}
+	}
 
-		let total_size = file.stream_position()? - 8;
+	// The file size changes whenever a chunk was removed or added
+	let total_size = file.seek(SeekFrom::End(0))? - 8;
 
-		file.seek(SeekFrom::Start(4))?;
+	file.seek(SeekFrom::Start(4))?;
 
-		file.write_u32::<B>(total_size as u32)?;
-	}
+	file.write_u32::<B>(total_size as u32)?;
 
 	Ok(())
 }
@@ -0,0 +1,61 @@
+use crate::dsf::read::{DsdChunk, DSD_CHUNK_SIZE};
+use crate::error::{LoftyError, Result};
+use crate::macros::decode_err;
+use crate::util::io::{FileLike, Length, Truncate};
+
+use std::io::SeekFrom;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+// DSF files store their ID3v2 tag in a metadata chunk at the end of the file, with its
+// offset recorded in the "DSD " chunk
+pub(in crate::id3::v2) fn write_to_dsf<F>(file: &mut F, tag: &[u8]) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+{
+	file.rewind()?;
+	DsdChunk::read(file)?;
+
+	// The metadata chunk comes directly after the "data" chunk
+	let mut end_of_audio = DSD_CHUNK_SIZE;
+	for expected_id in [b"fmt ", b"data"] {
+		let mut id = [0; 4];
+		file.read_exact(&mut id)?;
+		if &id != expected_id {
+			decode_err!(@BAIL Dsf, "File has an invalid chunk order");
+		}
+
+		let chunk_size = file.read_u64::<LittleEndian>()?;
+		if chunk_size < 12 {
+			decode_err!(@BAIL Dsf, "File has an invalid chunk size");
+		}
+
+		end_of_audio += chunk_size;
+		file.seek(SeekFrom::Start(end_of_audio))?;
+	}
+
+	if end_of_audio > file.len()? {
+		decode_err!(@BAIL Dsf, "File has an invalid \"data\" chunk size");
+	}
+
+	// Replace any existing metadata chunk
+	file.truncate(end_of_audio)?;
+
+	let mut metadata_offset = 0;
+	if !tag.is_empty() {
+		file.seek(SeekFrom::Start(end_of_audio))?;
+		file.write_all(tag)?;
+
+		metadata_offset = end_of_audio;
+	}
+
+	let file_size = end_of_audio + tag.len() as u64;
+
+	file.seek(SeekFrom::Start(DsdChunk::FILE_SIZE_POS))?;
+	file.write_u64::<LittleEndian>(file_size)?;
+	file.write_u64::<LittleEndian>(metadata_offset)?;
+
+	Ok(())
+}
@@ -1,9 +1,16 @@
 use crate::error::{Id3v2Error, Id3v2ErrorKind, Result};
 use crate::id3::v2::frame::{FrameFlags, FrameRef};
-use crate::id3::v2::util::synchsafe::SynchsafeInteger;
-use crate::id3::v2::{Frame, FrameId, KeyValueFrame, TextInformationFrame};
+use crate::id3::v2::read::merge_v3_date_and_time;
+use crate::id3::v2::tag::split_multi_value_text;
+use crate::id3::v2::util::synchsafe::{self, SynchsafeInteger};
+use crate::id3::v2::util::upgrade::downgrade_v3_to_v2;
+use crate::id3::v2::{
+	ExtendedTextFrame, Frame, FrameId, Id3v2Version, KeyValueFrame, TextInformationFrame,
+	TimestampFrame,
+};
 use crate::tag::items::Timestamp;
 
+use std::borrow::Cow;
 use std::io::Write;
 
 use crate::id3::v2::tag::GenresIter;
@@ -12,13 +19,62 @@ use byteorder::{BigEndian, WriteBytesExt};
 pub(in crate::id3::v2) fn create_items<W>(
 	writer: &mut W,
 	frames: &mut dyn Iterator<Item = FrameRef<'_>>,
+	unsynchronise: bool,
 ) -> Result<()>
 where
 	W: Write,
 {
+	// These are all frames from ID3v2.3 that don't exist in ID3v2.4. Those with an equivalent
+	// are only discarded if they couldn't be converted, or the equivalent is already present.
+	const FRAMES_TO_DISCARD: &[&str] = &[
+		"EQUA", "IPLS", "RVAD", "TDAT", "TIME", "TORY", "TRDA", "TSIZ", "TYER",
+	];
+
 	let is_id3v23 = false;
 
-	for frame in frames {
+	// ID3v2.3 `TYER`, `TDAT`, and `TIME` frames (e.g. from a tag read without implicit conversions)
+	// need to be merged into `TDRC`, as they don't exist in ID3v2.4
+	let frames = frames.collect::<Vec<_>>();
+	let recording_time = merge_v3_recording_time(&frames);
+	let mut wrote_recording_time = false;
+
+	let has_tdor = frames.iter().any(|frame| frame.id_str() == "TDOR");
+	let has_tipl = frames.iter().any(|frame| frame.id_str() == "TIPL");
+
+	for mut frame in frames {
+		if let Some(recording_time) = &recording_time {
+			if matches!(frame.id_str(), "TDRC" | "TYER" | "TDAT" | "TIME") {
+				// Written in place of the first component
+				if wrote_recording_time {
+					continue;
+				}
+
+				frame = FrameRef(Cow::Owned(recording_time.clone()));
+				wrote_recording_time = true;
+			}
+		}
+
+		let converted = match frame.id_str() {
+			"TORY" if !has_tdor => tory_to_tdor(&frame),
+			"IPLS" if !has_tipl => ipls_to_tipl(&frame),
+			_ => None,
+		};
+
+		if let Some(converted) = converted {
+			log::debug!(
+				"Converting frame: {}, to {}",
+				frame.id_str(),
+				converted.id_str()
+			);
+			frame = FrameRef(Cow::Owned(converted));
+		}
+
+		let id = frame.id_str();
+		if FRAMES_TO_DISCARD.contains(&id) {
+			log::warn!("Discarding frame: {}, not supported in ID3v2.4", id);
+			continue;
+		}
+
 		verify_frame(&frame)?;
 		let value = frame.as_bytes(is_id3v23)?;
 
@@ -27,39 +83,209 @@ where
 			frame.id().as_str(),
 			frame.flags(),
 			&value,
-			is_id3v23,
+			Id3v2Version::V4,
+			unsynchronise,
 		)?;
 	}
 
 	Ok(())
 }
 
+// Creates a `TDRC` frame from the ID3v2.3 `TYER` (or a year-only `TDRC`), `TDAT`, and `TIME` frames
+fn merge_v3_recording_time<'a>(frames: &[FrameRef<'a>]) -> Option<Frame<'a>> {
+	let find_text = |id| {
+		frames.iter().find_map(|frame| match &**frame {
+			Frame::Text(f) if f.header.id.as_str() == id => Some(f),
+			_ => None,
+		})
+	};
+
+	let year_frame = frames.iter().find_map(|frame| match &**frame {
+		Frame::Timestamp(f) if f.header.id.as_str() == "TDRC" => Some(f.clone()),
+		_ => None,
+	});
+
+	let mut tdrc = match year_frame {
+		// Already a complete `TDRC`, any leftover components are discarded
+		Some(frame) if frame.timestamp.month.is_some() => return None,
+		Some(frame) => frame,
+		None => {
+			let tyer = find_text("TYER")?;
+			let Ok(year) = tyer.value.trim().parse::<u16>() else {
+				log::warn!("Invalid TYER frame, discarding");
+				return None;
+			};
+
+			let mut frame = TimestampFrame::new(
+				FrameId::Valid(Cow::Borrowed("TDRC")),
+				tyer.encoding,
+				Timestamp {
+					year,
+					..Timestamp::default()
+				},
+			);
+			frame.header.flags = tyer.header.flags;
+			frame
+		},
+	};
+
+	merge_v3_date_and_time(
+		&mut tdrc.timestamp,
+		find_text("TDAT").map(|f| f.value.as_str()),
+		find_text("TIME").map(|f| f.value.as_str()),
+	);
+
+	Some(Frame::Timestamp(tdrc))
+}
+
+// Creates a `TDOR` frame from an ID3v2.3 `TORY` (Original release year) frame
+fn tory_to_tdor(frame: &Frame<'_>) -> Option<Frame<'static>> {
+	let Frame::Text(tory) = frame else {
+		return None;
+	};
+
+	let Ok(year) = tory.value.trim().parse::<u16>() else {
+		log::warn!("Invalid TORY frame, unable to convert");
+		return None;
+	};
+
+	let mut tdor = TimestampFrame::new(
+		FrameId::Valid(Cow::Borrowed("TDOR")),
+		tory.encoding,
+		Timestamp {
+			year,
+			..Timestamp::default()
+		},
+	);
+	tdor.header.flags = tory.header.flags;
+
+	Some(Frame::Timestamp(tdor))
+}
+
+// Creates a `TIPL` frame from an ID3v2.3 `IPLS` (Involved people list) frame
+//
+// Without implicit conversions, `IPLS` is read as a binary frame, but its content is identical to `TIPL`.
+fn ipls_to_tipl(frame: &Frame<'_>) -> Option<Frame<'static>> {
+	const TIPL: FrameId<'static> = FrameId::Valid(Cow::Borrowed("TIPL"));
+
+	let mut tipl = match frame {
+		Frame::Binary(ipls) => {
+			let Ok(Some(tipl)) =
+				KeyValueFrame::parse(&mut &ipls.data[..], TIPL, ipls.flags(), Id3v2Version::V3)
+			else {
+				log::warn!("Invalid IPLS frame, unable to convert");
+				return None;
+			};
+
+			tipl
+		},
+		Frame::Text(ipls) => {
+			let mut values = ipls.value.split('\0');
+
+			let mut key_value_pairs = Vec::new();
+			while let (Some(key), Some(value)) = (values.next(), values.next()) {
+				key_value_pairs.push((key.to_owned(), value.to_owned()));
+			}
+
+			KeyValueFrame::new(TIPL, ipls.encoding, key_value_pairs)
+		},
+		_ => return None,
+	};
+	tipl.header.flags = frame.flags();
+
+	Some(Frame::KeyValue(tipl))
+}
+
 pub(in crate::id3::v2) fn create_items_v3<W>(
 	writer: &mut W,
 	frames: &mut dyn Iterator<Item = FrameRef<'_>>,
+	separator: &str,
+) -> Result<()>
+where
+	W: Write,
+{
+	create_items_pre_v4(writer, frames, separator, Id3v2Version::V3)
+}
+
+// ID3v2.2 frames are converted the same as ID3v2.3 frames, and are then written with their ID3v2.2 IDs.
+// Any frame without an ID3v2.2 ID is discarded, see `write_frame`.
+pub(in crate::id3::v2) fn create_items_v2<W>(
+	writer: &mut W,
+	frames: &mut dyn Iterator<Item = FrameRef<'_>>,
+	separator: &str,
+) -> Result<()>
+where
+	W: Write,
+{
+	create_items_pre_v4(writer, frames, separator, Id3v2Version::V2)
+}
+
+fn create_items_pre_v4<W>(
+	writer: &mut W,
+	frames: &mut dyn Iterator<Item = FrameRef<'_>>,
+	separator: &str,
+	version: Id3v2Version,
 ) -> Result<()>
 where
 	W: Write,
 {
 	// These are all frames from ID3v2.4
 	const FRAMES_TO_DISCARD: &[&str] = &[
-		"ASPI", "EQU2", "RVA2", "SEEK", "SIGN", "TDEN", "TDRL", "TDTG", "TMOO", "TPRO", "TSOA",
-		"TSOP", "TSOT", "TSST",
+		"ASPI", "EQU2", "SEEK", "SIGN", "TDEN", "TDRL", "TDTG", "TMOO", "TPRO", "TSOA", "TSOP",
+		"TSOT", "TSST",
 	];
 
+	// iTunes has its own ID3v2.2 frames for these
+	const ITUNES_V2_FRAMES: &[&str] = &["TSOA", "TSOP", "TSOT"];
+
 	const IPLS_ID: &str = "IPLS";
+	const RVAD_ID: &str = "RVAD";
 
+	// ID3v2.2 and ID3v2.3 frames have the same content, other than pictures
 	let is_id3v23 = true;
 
+	// Any existing `TYER`, `TDAT`, and `TIME` frames are replaced by those created from `TDRC`
+	let frames = frames.collect::<Vec<_>>();
+	let recording_time = frames.iter().find_map(|frame| match &**frame {
+		Frame::Timestamp(f) if f.header.id.as_str() == "TDRC" && f.timestamp.verify().is_ok() => {
+			Some(f.timestamp)
+		},
+		_ => None,
+	});
+	let replaces_date = recording_time.is_some_and(|t| t.day.is_some());
+	let replaces_time = recording_time.is_some_and(|t| t.minute.is_some());
+
+	// The same goes for `TORY` and `IPLS`, which are created from `TDOR`, `TIPL`, and `TMCL`
+	let replaces_tory = frames.iter().any(|frame| match &**frame {
+		Frame::Timestamp(f) => f.header.id.as_str() == "TDOR" && f.timestamp.verify().is_ok(),
+		_ => false,
+	});
+	let replaces_ipls = frames
+		.iter()
+		.any(|frame| matches!(frame.id_str(), "TIPL" | "TMCL"));
+
 	let mut ipls = None;
+	let mut wrote_rvad = false;
 	for mut frame in frames {
 		let id = frame.id_str();
 
-		if FRAMES_TO_DISCARD.contains(&id) {
+		if FRAMES_TO_DISCARD.contains(&id)
+			&& !(version == Id3v2Version::V2 && ITUNES_V2_FRAMES.contains(&id))
+		{
 			log::warn!("Discarding frame: {}, not supported in ID3v2.3", id);
 			continue;
 		}
 
+		if (id == "TYER" && recording_time.is_some())
+			|| (id == "TDAT" && replaces_date)
+			|| (id == "TIME" && replaces_time)
+			|| (id == "TORY" && replaces_tory)
+			|| (id == "IPLS" && replaces_ipls)
+		{
+			log::debug!("Replacing frame: {}, with one converted from ID3v2.4", id);
+			continue;
+		}
+
 		verify_frame(&frame)?;
 
 		match id {
@@ -128,7 +354,8 @@ where
 							frame.id().as_str(),
 							frame.flags(),
 							&value,
-							is_id3v23,
+							version,
+							false,
 						)?;
 					}
 
@@ -202,29 +429,78 @@ where
 
 				continue;
 			},
-			_ => {},
+			// RVA2 (Relative volume adjustment (2)) gets converted to RVAD, which can
+			// only appear once and has no identification
+			"RVA2" => {
+				if wrote_rvad {
+					log::warn!("Discarding frame: {}, only one RVAD frame is allowed", id);
+					continue;
+				}
+
+				let value = frame.as_bytes(is_id3v23)?;
+				write_frame(writer, RVAD_ID, frame.flags(), &value, version, false)?;
+
+				wrote_rvad = true;
+				continue;
+			},
+			// ID3v2.3 has no concept of multi-value text frames, so the values have to be joined
+			_ => join_multi_value_text(&mut frame, separator),
 		}
 
-		let value = frame.as_bytes(is_id3v23)?;
+		let value = match &*frame {
+			// ID3v2.2 `PIC` frames have a different layout, and only support PNG and JPEG
+			Frame::Picture(attached_picture) if version == Id3v2Version::V2 => {
+				match attached_picture.as_bytes(version) {
+					Ok(value) => value,
+					Err(e) => {
+						log::warn!("Discarding frame: APIC, {}", e);
+						continue;
+					},
+				}
+			},
+			_ => frame.as_bytes(is_id3v23)?,
+		};
 
 		write_frame(
 			writer,
 			frame.id().as_str(),
 			frame.flags(),
 			&value,
-			is_id3v23,
+			version,
+			false,
 		)?;
 	}
 
 	if let Some(ipls) = ipls {
 		let frame = Frame::Text(ipls);
 		let value = frame.as_bytes(is_id3v23)?;
-		write_frame(writer, IPLS_ID, frame.flags(), &value, is_id3v23)?;
+		write_frame(writer, IPLS_ID, frame.flags(), &value, version, false)?;
 	}
 
 	Ok(())
 }
 
+fn join_multi_value_text(frame: &mut FrameRef<'_>, separator: &str) {
+	let (Frame::Text(TextInformationFrame { value: text, .. })
+	| Frame::UserText(ExtendedTextFrame { content: text, .. })) = &*frame.0
+	else {
+		return;
+	};
+
+	if !text.contains('\0') {
+		return;
+	}
+
+	if let Frame::Text(TextInformationFrame { value: text, .. })
+	| Frame::UserText(ExtendedTextFrame { content: text, .. }) = frame.0.to_mut()
+	{
+		// Trailing and repeated separators don't make for extra values, see `split_multi_value_text`
+		*text = split_multi_value_text(text)
+			.collect::<Vec<_>>()
+			.join(separator);
+	}
+}
+
 fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 	match (frame.id().as_str(), &**frame) {
 		("APIC", Frame::Picture { .. })
@@ -239,7 +515,16 @@ fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 		| ("WFED" | "GRP1" | "MVNM" | "MVIN", Frame::Text { .. })
 		| ("TDEN" | "TDOR" | "TDRC" | "TDRL" | "TDTG", Frame::Timestamp(_))
 		| ("RVA2", Frame::RelativeVolumeAdjustment(_))
-		| ("PRIV", Frame::Private(_)) => Ok(()),
+		| ("CHAP", Frame::Chapter(_))
+		| ("CTOC", Frame::TableOfContents(_))
+		| ("PRIV", Frame::Private(_))
+		| ("GEOB", Frame::GeneralObject(_))
+		| ("SYLT", Frame::SynchronizedText(_))
+		| ("ETCO", Frame::EventTimingCodes(_))
+		| ("MCDI", Frame::MusicCdIdentifier(_))
+		| ("PCNT", Frame::PlayCounter(_))
+		| ("OWNE", Frame::Ownership(_))
+		| ("COMR", Frame::Commercial(_)) => Ok(()),
 		(id, Frame::Text { .. }) if id.starts_with('T') => Ok(()),
 		(id, Frame::Url(_)) if id.starts_with('W') => Ok(()),
 		(id, frame_value) => Err(Id3v2Error::new(Id3v2ErrorKind::BadFrame(
@@ -253,49 +538,85 @@ fn verify_frame(frame: &FrameRef<'_>) -> Result<()> {
 fn write_frame<W>(
 	writer: &mut W,
 	name: &str,
-	flags: FrameFlags,
+	mut flags: FrameFlags,
 	value: &[u8],
-	is_id3v23: bool,
+	version: Id3v2Version,
+	unsynchronise: bool,
 ) -> Result<()>
 where
 	W: Write,
 {
+	if version == Id3v2Version::V2 {
+		return write_frame_v2(writer, name, flags, value);
+	}
+
+	let is_id3v23 = version == Id3v2Version::V3;
+
+	let mut content = Vec::with_capacity(value.len() + 1);
 	if flags.encryption.is_some() {
-		write_encrypted(writer, name, value, flags, is_id3v23)?;
+		write_encrypted(&mut content, value, &mut flags, is_id3v23)?;
+	} else {
+		// Frames are never compressed when writing
+		flags.compression = false;
+		flags.data_length_indicator = None;
+
+		if let Some(group) = flags.grouping_identity {
+			content.push(group);
+		}
+
+		content.extend_from_slice(value);
+	}
+
+	// Only ID3v2.4 unsynchronises individual frames, ID3v2.3 tags are unsynchronised as a whole
+	flags.unsynchronisation = unsynchronise && !is_id3v23;
+	if flags.unsynchronisation {
+		content = synchsafe::unsynchronise(&content);
+	}
+
+	write_frame_header(writer, name, content.len() as u32, flags, is_id3v23)?;
+	writer.write_all(&content)?;
+
+	Ok(())
+}
+
+// ID3v2.2 frames have a 3 character ID, a 24-bit size, and no flags
+fn write_frame_v2<W>(writer: &mut W, name: &str, flags: FrameFlags, value: &[u8]) -> Result<()>
+where
+	W: Write,
+{
+	let Some(v2_name) = downgrade_v3_to_v2(name) else {
+		log::warn!("Discarding frame: {}, not supported in ID3v2.2", name);
+		return Ok(());
+	};
+
+	// Without flags, there's no way to mark a frame as encrypted
+	if flags.encryption.is_some() {
+		log::warn!(
+			"Discarding frame: {}, encryption is not supported in ID3v2.2",
+			name
+		);
 		return Ok(());
 	}
 
 	let len = value.len() as u32;
-	let is_grouping_identity = flags.grouping_identity.is_some();
-
-	write_frame_header(
-		writer,
-		name,
-		if is_grouping_identity { len + 1 } else { len },
-		flags,
-		is_id3v23,
-	)?;
-
-	if is_grouping_identity {
-		// Guaranteed to be `Some` at this point.
-		writer.write_u8(flags.grouping_identity.unwrap())?;
+	if len > 0xFF_FFFF {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::BadFrameLength).into());
 	}
 
+	writer.write_all(v2_name.as_bytes())?;
+	writer.write_u24::<BigEndian>(len)?;
 	writer.write_all(value)?;
 
 	Ok(())
 }
 
-fn write_encrypted<W>(
-	writer: &mut W,
-	name: &str,
+// Encrypted frames are written as-is, with all of their additional data
+fn write_encrypted(
+	content: &mut Vec<u8>,
 	value: &[u8],
-	flags: FrameFlags,
+	flags: &mut FrameFlags,
 	is_id3v23: bool,
-) -> Result<()>
-where
-	W: Write,
-{
+) -> Result<()> {
 	// Guaranteed to be `Some` at this point.
 	let method_symbol = flags.encryption.unwrap();
 
@@ -305,22 +626,41 @@ where
 		);
 	}
 
-	if let Some(mut len) = flags.data_length_indicator {
-		if len > 0 {
-			write_frame_header(writer, name, (value.len() + 1) as u32, flags, is_id3v23)?;
-			if !is_id3v23 {
-				len = len.synch()?;
-			}
+	// ID3v2.3 only has a decompressed size for compressed frames
+	if is_id3v23 && !flags.compression {
+		flags.data_length_indicator = None;
+	}
+
+	if flags.compression && flags.data_length_indicator.is_none() {
+		return Err(Id3v2Error::new(Id3v2ErrorKind::MissingDataLengthIndicator).into());
+	}
+
+	// See `ParsedFrame::read` for the order of the additional data
+	if is_id3v23 {
+		if let Some(len) = flags.data_length_indicator {
+			content.write_u32::<BigEndian>(len)?;
+		}
 
-			writer.write_u32::<BigEndian>(len)?;
-			writer.write_u8(method_symbol)?;
-			writer.write_all(value)?;
+		content.write_u8(method_symbol)?;
 
-			return Ok(());
+		if let Some(group) = flags.grouping_identity {
+			content.write_u8(group)?;
+		}
+	} else {
+		if let Some(group) = flags.grouping_identity {
+			content.write_u8(group)?;
+		}
+
+		content.write_u8(method_symbol)?;
+
+		if let Some(len) = flags.data_length_indicator {
+			content.write_u32::<BigEndian>(len.synch()?)?;
 		}
 	}
 
-	Err(Id3v2Error::new(Id3v2ErrorKind::MissingDataLengthIndicator).into())
+	content.extend_from_slice(value);
+
+	Ok(())
 }
 
 fn write_frame_header<W>(
@@ -1,42 +1,27 @@
 mod chunk_file;
-mod frame;
+mod dsf;
+pub(super) mod frame;
 
-use super::Id3v2TagFlags;
+use super::{Id3v2TagFlags, Id3v2Version};
 use crate::config::WriteOptions;
 use crate::error::{LoftyError, Result};
 use crate::file::FileType;
 use crate::id3::v2::frame::FrameRef;
 use crate::id3::v2::tag::Id3v2TagRef;
-use crate::id3::v2::util::synchsafe::SynchsafeInteger;
+use crate::id3::v2::util::crc::crc32;
+use crate::id3::v2::util::synchsafe::{self, SynchsafeInteger};
 use crate::id3::v2::Id3v2Tag;
+use crate::id3::v2::{AttachedPictureFrame, Frame};
 use crate::id3::{find_id3v2, FindId3v2Config};
 use crate::macros::{err, try_vec};
 use crate::probe::Probe;
-use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::io::{ByteCounter, FileLike, Length, Truncate};
 
+use std::borrow::Cow;
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
-use std::ops::Not;
-use std::sync::OnceLock;
 
 use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
 
-// In the very rare chance someone wants to write a CRC in their extended header
-fn crc_32_table() -> &'static [u32; 256] {
-	static INSTANCE: OnceLock<[u32; 256]> = OnceLock::new();
-	INSTANCE.get_or_init(|| {
-		let mut crc32_table = [0; 256];
-
-		for n in 0..256 {
-			crc32_table[n as usize] = (0..8).fold(n as u32, |acc, _| match acc & 1 {
-				1 => 0xEDB8_8320 ^ (acc >> 1),
-				_ => acc >> 1,
-			});
-		}
-
-		crc32_table
-	})
-}
-
 #[allow(clippy::shadow_unrelated)]
 pub(crate) fn write_id3v2<'a, F, I>(
 	file: &mut F,
@@ -71,28 +56,53 @@ where
 		err!(UnsupportedTag);
 	}
 
-	let id3v2 = create_tag(tag, write_options)?;
-
 	match file_type {
 		// Formats such as WAV and AIFF store the ID3v2 tag in an 'ID3 ' chunk rather than at the beginning of the file
 		FileType::Wav => {
 			tag.flags.footer = false;
-			return chunk_file::write_to_chunk_file::<F, LittleEndian>(file, &id3v2, write_options);
+			return chunk_file::write_to_chunk_file::<F, LittleEndian, _>(file, tag, write_options);
 		},
 		FileType::Aiff => {
 			tag.flags.footer = false;
-			return chunk_file::write_to_chunk_file::<F, BigEndian>(file, &id3v2, write_options);
+			return chunk_file::write_to_chunk_file::<F, BigEndian, _>(file, tag, write_options);
+		},
+		FileType::Dsf => {
+			tag.flags.footer = false;
+			let id3v2 = create_tag(tag, write_options, None)?;
+			return dsf::write_to_dsf(file, &id3v2);
 		},
+		// TODO: Support writing the 'ID3 ' chunk of DFF files
+		FileType::Dff => err!(UnsupportedTag),
 		_ => {},
 	}
 
 	// find_id3v2 will seek us to the end of the tag
 	// TODO: Search through junk
-	find_id3v2(file, FindId3v2Config::NO_READ_TAG)?;
+	let existing_tags = find_id3v2(file, FindId3v2Config::NO_READ_TAG)?;
+
+	// Only a single tag can be overwritten, stacked tags are merged into one
+	let mut preserved_len = None;
+	if write_options.preserve_id3v2_size && existing_tags.0.len() == 1 {
+		preserved_len = Some(file.stream_position()?);
+	}
+
+	let id3v2 = create_tag(tag, write_options, preserved_len)?;
+	if preserved_len == Some(id3v2.len() as u64) {
+		log::debug!("Writing ID3v2 tag in place");
+
+		file.rewind()?;
+		file.write_all(&id3v2)?;
+		return Ok(());
+	}
 
 	let mut file_bytes = Vec::new();
 	file.read_to_end(&mut file_bytes)?;
 
+	// Some encoders include the ID3v2 tag in the VBR header's byte count
+	if file_type == FileType::Mpeg {
+		crate::mpeg::vbr::update_vbr_header(&mut file_bytes)?;
+	}
+
 	file_bytes.splice(0..0, id3v2);
 
 	file.rewind()?;
@@ -102,77 +112,141 @@ where
 	Ok(())
 }
 
+/// Create `tag` for a [`WritePlan`](crate::file::WritePlan), see [`create_tag`]
+///
+/// Tags stored in a chunk (WAV and AIFF) can't have a footer.
+pub(crate) fn create_planned_tag(
+	tag: &Id3v2Tag,
+	in_chunk: bool,
+	write_options: WriteOptions,
+	preserved_len: Option<u64>,
+) -> Result<Vec<u8>> {
+	let mut tag_ref = Id3v2TagRef {
+		flags: *tag.flags(),
+		frames: tag.frames.iter().filter_map(Frame::as_opt_ref).peekable(),
+	};
+
+	if in_chunk {
+		tag_ref.flags.footer = false;
+	}
+
+	create_tag(&mut tag_ref, write_options, preserved_len)
+}
+
+/// Create the tag, padding it up to `preserved_len` if it fits, see `WriteOptions::preserve_id3v2_size`
 pub(super) fn create_tag<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 	tag: &mut Id3v2TagRef<'a, I>,
 	write_options: WriteOptions,
+	preserved_len: Option<u64>,
 ) -> Result<Vec<u8>> {
 	let frames = &mut tag.frames;
-	let mut peek = frames.peekable();
+	let mut peek = frames
+		.map(|frame| fix_picture_mime_type(frame, write_options))
+		.peekable();
 
 	// We are stripping the tag
 	if peek.peek().is_none() {
 		return Ok(Vec::new());
 	}
 
-	let is_id3v23 = write_options.use_id3v23;
-	if is_id3v23 {
-		log::debug!("Using ID3v2.3");
+	let version = target_version(write_options);
+	match version {
+		Id3v2Version::V2 => log::debug!("Using ID3v2.2"),
+		Id3v2Version::V3 => log::debug!("Using ID3v2.3"),
+		Id3v2Version::V4 => {},
 	}
 
-	let has_footer = tag.flags.footer;
-	let needs_crc = tag.flags.crc;
-	let has_restrictions = tag.flags.restrictions.is_some();
+	let is_id3v23 = version == Id3v2Version::V3;
+	let unsynchronise = write_options.unsynchronisation || tag.flags.unsynchronisation;
+
+	let flags = if version == Id3v2Version::V2 {
+		// ID3v2.2 has no extended header or footer, so unsynchronisation is the only usable flag
+		Id3v2TagFlags {
+			unsynchronisation: unsynchronise,
+			..Id3v2TagFlags::default()
+		}
+	} else {
+		Id3v2TagFlags {
+			unsynchronisation: unsynchronise,
+			..tag.flags
+		}
+	};
+
+	let has_footer = flags.footer;
+	let needs_crc = flags.crc;
 
-	let (mut id3v2, extended_header_len) = create_tag_header(tag.flags, is_id3v23)?;
+	let (mut id3v2, extended_header_len) = create_tag_header(flags, version)?;
 	let header_len = id3v2.get_ref().len();
 
 	// Write the items
-	if is_id3v23 {
-		frame::create_items_v3(&mut id3v2, &mut peek)?;
+	let mut id3v23_crc = 0;
+	if version == Id3v2Version::V4 {
+		frame::create_items(&mut id3v2, &mut peek, unsynchronise)?;
 	} else {
-		frame::create_items(&mut id3v2, &mut peek)?;
+		let mut frames = Vec::new();
+		create_items_pre_v4(&mut frames, &mut peek, write_options, version)?;
+
+		// ID3v2.3 calculates the CRC on the frames before unsynchronisation, excluding the padding
+		if needs_crc {
+			id3v23_crc = crc32(&frames);
+		}
+
+		// The entire tag is unsynchronised in ID3v2.2 and ID3v2.3, rather than individual frames
+		if unsynchronise {
+			frames = synchsafe::unsynchronise(&frames);
+		}
+
+		id3v2.write_all(&frames)?;
 	}
 
-	let mut len = id3v2.get_ref().len() - header_len;
+	let frames_len = id3v2.get_ref().len() - header_len;
+	let unpadded_len = (id3v2.get_ref().len() + if has_footer { 10 } else { 0 }) as u64;
 
 	// https://mutagen-specs.readthedocs.io/en/latest/id3/id3v2.4.0-structure.html#padding:
 	//
 	// "[A tag] MUST NOT have any padding when a tag footer is added to the tag"
-	let padding_len = write_options.preferred_padding.unwrap_or(0) as usize;
-	if !has_footer {
-		len += padding_len;
+	let padding_len = if has_footer {
+		log::trace!("Footer requested, not padding tag");
+		0
+	} else if let Some(preserved_len) = preserved_len.filter(|len| *len >= unpadded_len) {
+		log::trace!("Padding tag to the existing size of {preserved_len} bytes");
+		(preserved_len - unpadded_len) as usize
+	} else {
+		write_options
+			.preferred_padding
+			.unwrap_or(WriteOptions::DEFAULT_PREFERRED_PADDING) as usize
+	};
+
+	if padding_len > 0 {
+		log::trace!("Padding tag with {} bytes", padding_len);
+
+		id3v2.seek(SeekFrom::End(0))?;
+		id3v2.write_all(&try_vec![0; padding_len])?;
 	}
 
 	// Go back to the start and write the final size
 	id3v2.seek(SeekFrom::Start(6))?;
-	id3v2.write_u32::<BigEndian>((extended_header_len + len as u32).synch()?)?;
+	id3v2.write_u32::<BigEndian>(
+		(extended_header_len + (frames_len + padding_len) as u32).synch()?,
+	)?;
 
 	if needs_crc {
-		// The CRC is calculated on all the data between the header and footer
-		#[allow(unused_mut)]
-		// Past the CRC
-		let mut content_start_idx = 22;
-
-		if has_restrictions {
-			content_start_idx += 3;
-		}
-
-		// Skip 16 bytes
-		//
-		// Normal ID3v2 header (10)
-		// Extended header (6)
+		// Skip the header (10), extended header size (4), and flags (2)
 		id3v2.seek(SeekFrom::Start(16))?;
 
-		let tag_contents = &id3v2.get_ref()[content_start_idx..];
-		let encoded_crc = calculate_crc(tag_contents);
+		if is_id3v23 {
+			id3v2.write_u32::<BigEndian>(padding_len as u32)?;
+			id3v2.write_u32::<BigEndian>(id3v23_crc)?;
+		} else {
+			// ID3v2.4 calculates the CRC on all the data between the header and footer
+			let crc = crc32(&id3v2.get_ref()[header_len..]);
 
-		id3v2.write_u8(5)?;
-		id3v2.write_all(&encoded_crc)?;
+			id3v2.write_u8(5)?;
+			id3v2.write_all(&encode_crc(crc))?;
+		}
 	}
 
 	if has_footer {
-		log::trace!("Footer requested, not padding tag");
-
 		id3v2.seek(SeekFrom::Start(3))?;
 
 		let mut header_without_identifier = [0; 7];
@@ -182,49 +256,181 @@ pub(super) fn create_tag<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
 		// The footer is the same as the header, but with the identifier reversed
 		id3v2.write_all(b"3DI")?;
 		id3v2.write_all(&header_without_identifier)?;
+	}
+
+	Ok(id3v2.into_inner())
+}
 
-		return Ok(id3v2.into_inner());
+// Corrects the MIME type of `APIC` frames, see `WriteOptions::fix_picture_mime_types`
+fn fix_picture_mime_type(frame: FrameRef<'_>, write_options: WriteOptions) -> FrameRef<'_> {
+	if !write_options.fix_picture_mime_types {
+		return frame;
 	}
 
-	if padding_len == 0 {
-		log::trace!("No padding requested, writing tag as-is");
-		return Ok(id3v2.into_inner());
+	let Frame::Picture(attached_picture) = &*frame else {
+		return frame;
+	};
+
+	match attached_picture.picture.with_fixed_mime_type() {
+		Cow::Borrowed(_) => frame,
+		Cow::Owned(picture) => FrameRef(Cow::Owned(Frame::Picture(AttachedPictureFrame {
+			header: attached_picture.header.clone(),
+			encoding: attached_picture.encoding,
+			picture,
+		}))),
 	}
+}
 
-	log::trace!("Padding tag with {} bytes", padding_len);
+/// Get the size of the tag that [`create_tag`] would produce, without keeping it in memory
+pub(super) fn tag_len<'a, I: Iterator<Item = FrameRef<'a>> + 'a>(
+	tag: &mut Id3v2TagRef<'a, I>,
+	write_options: WriteOptions,
+) -> Result<u64> {
+	let frames = &mut tag.frames;
+	let mut peek = frames
+		.map(|frame| fix_picture_mime_type(frame, write_options))
+		.peekable();
 
-	id3v2.seek(SeekFrom::End(0))?;
-	id3v2.write_all(&try_vec![0; padding_len])?;
+	// We are stripping the tag
+	if peek.peek().is_none() {
+		return Ok(0);
+	}
 
-	Ok(id3v2.into_inner())
+	let unsynchronise = write_options.unsynchronisation || tag.flags.unsynchronisation;
+	let version = target_version(write_options);
+
+	// Only the frames need to be encoded, everything else has a known size
+	let mut frames_len = ByteCounter::default();
+	if version != Id3v2Version::V4 && unsynchronise {
+		// The size of an unsynchronised ID3v2.2/ID3v2.3 tag depends on the content of all frames
+		let mut frames = Vec::new();
+		create_items_pre_v4(&mut frames, &mut peek, write_options, version)?;
+		frames_len.0 = synchsafe::unsynchronise(&frames).len() as u64;
+	} else if version != Id3v2Version::V4 {
+		create_items_pre_v4(&mut frames_len, &mut peek, write_options, version)?;
+	} else {
+		frame::create_items(&mut frames_len, &mut peek, unsynchronise)?;
+	}
+
+	// ID3v2.2 has no extended header or footer, see `create_tag`
+	let flags = if version == Id3v2Version::V2 {
+		Id3v2TagFlags::default()
+	} else {
+		tag.flags
+	};
+
+	let mut extended_header_len = 0;
+	if version == Id3v2Version::V3 {
+		// Restrictions don't exist in ID3v2.3
+		if flags.crc {
+			extended_header_len += 14;
+		}
+	} else if flags.crc || flags.restrictions.is_some() {
+		extended_header_len += 6;
+
+		if flags.crc {
+			extended_header_len += 6;
+		}
+
+		if flags.restrictions.is_some() {
+			extended_header_len += 2;
+		}
+	}
+
+	// The header is followed by either a footer or padding, never both
+	let trailer_len = if flags.footer {
+		10
+	} else {
+		u64::from(
+			write_options
+				.preferred_padding
+				.unwrap_or(WriteOptions::DEFAULT_PREFERRED_PADDING),
+		)
+	};
+
+	Ok(10 + extended_header_len + frames_len.0 + trailer_len)
 }
 
-fn create_tag_header(flags: Id3v2TagFlags, is_id3v23: bool) -> Result<(Cursor<Vec<u8>>, u32)> {
+// `WriteOptions::use_id3v22` takes precedence over `WriteOptions::use_id3v23`
+fn target_version(write_options: WriteOptions) -> Id3v2Version {
+	if write_options.use_id3v22 {
+		Id3v2Version::V2
+	} else if write_options.use_id3v23 {
+		Id3v2Version::V3
+	} else {
+		Id3v2Version::V4
+	}
+}
+
+fn create_items_pre_v4<W: Write>(
+	writer: &mut W,
+	frames: &mut dyn Iterator<Item = FrameRef<'_>>,
+	write_options: WriteOptions,
+	version: Id3v2Version,
+) -> Result<()> {
+	if version == Id3v2Version::V2 {
+		frame::create_items_v2(writer, frames, write_options.id3v23_separator)
+	} else {
+		frame::create_items_v3(writer, frames, write_options.id3v23_separator)
+	}
+}
+
+fn create_tag_header(
+	flags: Id3v2TagFlags,
+	version: Id3v2Version,
+) -> Result<(Cursor<Vec<u8>>, u32)> {
 	let mut header = Cursor::new(Vec::new());
 
 	header.write_all(b"ID3")?;
 
-	if is_id3v23 {
+	let is_id3v23 = version == Id3v2Version::V3;
+	match version {
+		// Version 2, rev 0
+		Id3v2Version::V2 => header.write_all(&[2, 0])?,
 		// Version 3, rev 0
-		header.write_all(&[3, 0])?;
-	} else {
+		Id3v2Version::V3 => header.write_all(&[3, 0])?,
 		// Version 4, rev 0
-		header.write_all(&[4, 0])?;
+		Id3v2Version::V4 => header.write_all(&[4, 0])?,
 	}
 
-	let extended_header = flags.crc || flags.restrictions.is_some();
+	// There's no extended header in ID3v2.2, and restrictions don't exist in ID3v2.3
+	let extended_header = match version {
+		Id3v2Version::V2 => false,
+		Id3v2Version::V3 => flags.crc,
+		Id3v2Version::V4 => flags.crc || flags.restrictions.is_some(),
+	};
 
-	let tag_flags = if is_id3v23 {
-		flags.as_id3v23_byte()
-	} else {
+	// The ID3v2.2 flags are only ever unsynchronisation here, which has the same bit as ID3v2.3
+	let mut tag_flags = if version == Id3v2Version::V4 {
 		flags.as_id3v24_byte()
+	} else {
+		flags.as_id3v23_byte()
 	};
 
+	if extended_header {
+		tag_flags |= 0x40;
+	}
+
 	header.write_u8(tag_flags)?;
 	header.write_u32::<BigEndian>(0)?;
 
 	let mut extended_header_size = 0;
-	if extended_header {
+	if extended_header && is_id3v23 {
+		// Structure of extended header:
+		//
+		// Size (4) (Excluding itself, and *not* synchsafe)
+		// Flags (2)
+		// Padding size (4)
+		// CRC (4)
+		//
+		// The padding size and CRC are written once the frames are known
+
+		extended_header_size = 14_u32;
+
+		header.write_u32::<BigEndian>(10)?;
+		header.write_u16::<BigEndian>(0x8000)?;
+		header.write_all(&[0; 8])?;
+	} else if extended_header {
 		// Structure of extended header:
 		//
 		// Size (4)
@@ -266,25 +472,12 @@ fn create_tag_header(flags: Id3v2TagFlags, is_id3v23: bool) -> Result<(Cursor<Ve
 	Ok((header, extended_header_size))
 }
 
-// https://github.com/rstemmer/id3edit/blob/0246f3dc1a7a80a64461eeeb7b9ee88379003eb1/encoding/crc.c#L6:6
-fn calculate_crc(content: &[u8]) -> [u8; 5] {
-	let crc: u32 = content
-		.iter()
-		.fold(!0, |crc, octet| {
-			(crc >> 8) ^ crc_32_table()[(((crc & 0xFF) ^ u32::from(*octet)) & 0xFF) as usize]
-		})
-		.not();
-
-	// The CRC-32 is stored as an 35 bit synchsafe integer, leaving the upper
-	// four bits always zeroed.
+// The CRC-32 is stored as a 35 bit synchsafe integer, leaving the upper four bits always zeroed
+fn encode_crc(crc: u32) -> [u8; 5] {
 	let mut encoded_crc = [0; 5];
-	let mut b;
 
-	#[allow(clippy::needless_range_loop)]
-	for i in 0..5 {
-		b = (crc >> ((4 - i) * 7)) as u8;
-		b &= 0x7F;
-		encoded_crc[i] = b;
+	for (i, b) in encoded_crc.iter_mut().enumerate() {
+		*b = (crc >> ((4 - i) * 7)) as u8 & 0x7F;
 	}
 
 	encoded_crc
@@ -308,7 +501,8 @@ mod tests {
 		tag.set_flags(flags);
 
 		let mut writer = Vec::new();
-		tag.dump_to(&mut writer, WriteOptions::default()).unwrap();
+		tag.dump_to(&mut writer, WriteOptions::default().preferred_padding(0))
+			.unwrap();
 
 		let crc_content = &writer[16..22];
 		assert_eq!(crc_content, &[5, 0x06, 0x35, 0x69, 0x7D, 0x14]);
@@ -16,6 +16,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 /// This contains a non-exhaustive list of compression types
 #[allow(non_camel_case_types)]
 #[derive(Clone, Eq, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AiffCompressionType {
 	#[default]
 	/// PCM
@@ -44,6 +45,8 @@ pub enum AiffCompressionType {
 	ALAW,
 	/// IEEE 32-bit float (From SoundHack & Csound)
 	FL32,
+	/// 4-to-1 IMA ADPCM
+	ima4,
 	/// Catch-all for unknown compression algorithms
 	Other {
 		/// Identifier from the compression algorithm
@@ -81,15 +84,64 @@ impl AiffCompressionType {
 			AiffCompressionType::ULAW => Cow::Borrowed("CCITT G.711 u-law"),
 			AiffCompressionType::ALAW => Cow::Borrowed("CCITT G.711 A-law"),
 			AiffCompressionType::FL32 => Cow::Borrowed("Float 32"),
+			AiffCompressionType::ima4 => Cow::Borrowed("IMA 4:1"),
 			AiffCompressionType::Other {
 				compression_name, ..
 			} => Cow::from(compression_name),
 		}
 	}
+
+	/// Whether the audio is actually compressed
+	///
+	/// Some compression types are just different encodings of uncompressed audio, such as
+	/// [`AiffCompressionType::sowt`] (little-endian PCM) and [`AiffCompressionType::fl32`] (floating point).
+	///
+	/// Unknown compression types ([`AiffCompressionType::Other`]) are assumed to be compressed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::iff::aiff::AiffCompressionType;
+	///
+	/// assert!(!AiffCompressionType::sowt.is_compressed());
+	/// assert!(AiffCompressionType::ulaw.is_compressed());
+	/// ```
+	pub fn is_compressed(&self) -> bool {
+		!matches!(
+			self,
+			AiffCompressionType::None
+				| AiffCompressionType::sowt
+				| AiffCompressionType::fl32
+				| AiffCompressionType::fl64
+				| AiffCompressionType::FL32
+		)
+	}
+
+	// The number of sample frames in each of the "sample frames" declared in the COMM chunk
+	//
+	// For some compression types, the COMM chunk holds the number of packets, rather than the number
+	// of sample frames.
+	fn frames_per_packet(&self) -> u32 {
+		match self {
+			AiffCompressionType::ima4 => 64,
+			AiffCompressionType::MAC3 | AiffCompressionType::MAC6 => 6,
+			_ => 1,
+		}
+	}
+
+	// The sample size of the stored audio, for types where it's fixed
+	fn sample_size(&self) -> Option<u16> {
+		match self {
+			AiffCompressionType::fl32 | AiffCompressionType::FL32 => Some(32),
+			AiffCompressionType::fl64 => Some(64),
+			_ => None,
+		}
+	}
 }
 
 /// A AIFF file's audio properties
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct AiffProperties {
 	pub(crate) duration: Duration,
@@ -108,7 +160,8 @@ impl From<AiffProperties> for FileProperties {
 			overall_bitrate: Some(value.overall_bitrate),
 			audio_bitrate: Some(value.audio_bitrate),
 			sample_rate: Some(value.sample_rate),
-			bit_depth: Some(value.sample_size as u8),
+			// The sample size of compressed audio only describes the decoded samples
+			bit_depth: (!value.is_compressed()).then_some(value.sample_size as u8),
 			channels: Some(value.channels as u8),
 			channel_mask: None,
 		}
@@ -137,6 +190,8 @@ impl AiffProperties {
 	}
 
 	/// Bits per sample
+	///
+	/// For compressed audio (see [`AiffProperties::is_compressed`]), this is the size of the decoded samples.
 	pub fn sample_size(&self) -> u16 {
 		self.sample_size
 	}
@@ -150,6 +205,15 @@ impl AiffProperties {
 	pub fn compression_type(&self) -> Option<&AiffCompressionType> {
 		self.compression_type.as_ref()
 	}
+
+	/// Whether the audio is actually compressed
+	///
+	/// This is always `false` for AIFF files. See [`AiffCompressionType::is_compressed`].
+	pub fn is_compressed(&self) -> bool {
+		self.compression_type
+			.as_ref()
+			.is_some_and(AiffCompressionType::is_compressed)
+	}
 }
 
 pub(super) fn read_properties(
@@ -165,7 +229,7 @@ pub(super) fn read_properties(
 	}
 
 	let sample_frames = comm.read_u32::<BigEndian>()?;
-	let sample_size = comm.read_u16::<BigEndian>()?;
+	let mut sample_size = comm.read_u16::<BigEndian>()?;
 
 	let sample_rate_extended = comm.read_f80()?;
 	let sample_rate_64 = sample_rate_extended.as_f64();
@@ -175,8 +239,29 @@ pub(super) fn read_properties(
 
 	let sample_rate = sample_rate_64.round() as u32;
 
-	let (duration, overall_bitrate, audio_bitrate) = if sample_rate > 0 && sample_frames > 0 {
-		let length = (f64::from(sample_frames) * 1000.0) / f64::from(sample_rate);
+	let compression_type = if comm.len() >= 4 && compression_present == CompressionPresent::Yes {
+		Some(read_compression_type(comm)?)
+	} else {
+		None
+	};
+
+	// Floating point types don't always specify a sample size
+	if sample_size == 0 {
+		if let Some(size) = compression_type
+			.as_ref()
+			.and_then(AiffCompressionType::sample_size)
+		{
+			sample_size = size;
+		}
+	}
+
+	let frames_per_packet = compression_type
+		.as_ref()
+		.map_or(1, AiffCompressionType::frames_per_packet);
+	let total_frames = u64::from(sample_frames) * u64::from(frames_per_packet);
+
+	let (duration, overall_bitrate, audio_bitrate) = if sample_rate > 0 && total_frames > 0 {
+		let length = (total_frames as f64 * 1000.0) / f64::from(sample_rate);
 
 		(
 			Duration::from_millis(length as u64),
@@ -187,23 +272,22 @@ pub(super) fn read_properties(
 		(Duration::ZERO, 0, 0)
 	};
 
-	let is_compressed = comm.len() >= 5 && compression_present == CompressionPresent::Yes;
-	if !is_compressed {
-		return Ok(AiffProperties {
-			duration,
-			overall_bitrate,
-			audio_bitrate,
-			sample_rate,
-			sample_size,
-			channels,
-			compression_type: None,
-		});
-	}
+	Ok(AiffProperties {
+		duration,
+		overall_bitrate,
+		audio_bitrate,
+		sample_rate,
+		sample_size,
+		channels,
+		compression_type,
+	})
+}
 
+fn read_compression_type(comm: &mut &[u8]) -> Result<AiffCompressionType> {
 	let mut compression_type = [0u8; 4];
 	comm.read_exact(&mut compression_type)?;
 
-	let compression = Some(match &compression_type {
+	let compression = match &compression_type {
 		b"NONE" => AiffCompressionType::None,
 		b"ACE2" => AiffCompressionType::ACE2,
 		b"ACE8" => AiffCompressionType::ACE8,
@@ -217,16 +301,17 @@ pub(super) fn read_properties(
 		b"ULAW" => AiffCompressionType::ULAW,
 		b"ALAW" => AiffCompressionType::ALAW,
 		b"FL32" => AiffCompressionType::FL32,
+		b"ima4" => AiffCompressionType::ima4,
 		_ => {
 			log::debug!(
 				"Encountered unknown compression type: {:?}",
 				compression_type
 			);
 
-			// We have to read the compression name string
+			// We have to read the compression name string, which some writers omit
 			let mut compression_name = String::new();
 
-			let compression_name_size = comm.read_u8()?;
+			let compression_name_size = comm.read_u8().unwrap_or(0);
 			if compression_name_size > 0 {
 				let mut compression_name_bytes = try_vec![0u8; compression_name_size as usize];
 				comm.read_exact(&mut compression_name_bytes)?;
@@ -239,15 +324,7 @@ pub(super) fn read_properties(
 				compression_name,
 			}
 		},
-	});
+	};
 
-	Ok(AiffProperties {
-		duration,
-		overall_bitrate,
-		audio_bitrate,
-		sample_rate,
-		sample_size,
-		channels,
-		compression_type: compression,
-	})
+	Ok(compression)
 }
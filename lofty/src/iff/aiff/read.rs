@@ -3,11 +3,13 @@ use super::tag::{AiffTextChunks, Comment};
 use super::AiffFile;
 use crate::config::ParseOptions;
 use crate::error::Result;
+use crate::file::FileType;
 use crate::id3::v2::tag::Id3v2Tag;
-use crate::iff::chunk::Chunks;
+use crate::iff::chunk::{handle_duplicate_id3_chunk, Chunks};
 use crate::macros::{decode_err, err};
 
 use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
 
 use byteorder::{BigEndian, ReadBytesExt};
 
@@ -70,15 +72,16 @@ where
 	while chunks.next(data).is_ok() {
 		match &chunks.fourcc {
 			b"ID3 " | b"id3 " if parse_options.read_tags => {
+				let offset = data.stream_position()? - 8;
 				let tag = chunks.id3_chunk(data, parse_options)?;
 				if let Some(existing_tag) = id3v2_tag.as_mut() {
-					log::warn!("Duplicate ID3v2 tag found, appending frames to previous tag");
-
-					// https://github.com/Serial-ATA/lofty-rs/issues/87
-					// Duplicate tags should have their frames appended to the previous
-					for frame in tag.frames {
-						existing_tag.insert(frame);
-					}
+					handle_duplicate_id3_chunk(
+						existing_tag,
+						tag,
+						offset,
+						FileType::Aiff,
+						parse_options,
+					)?;
 					continue;
 				}
 				id3v2_tag = Some(tag);
@@ -180,3 +183,58 @@ where
 		id3v2_tag,
 	})
 }
+
+/// Read the duration of an AIFF file from its `COMM` chunk
+///
+/// The chunks are only walked until both the `COMM` and `SSND` chunks have been found.
+pub(crate) fn read_duration<R>(data: &mut R) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	let compression_present = verify_aiff(data)?;
+
+	let current_pos = data.stream_position()?;
+	let file_len = data.seek(SeekFrom::End(0))?;
+
+	data.seek(SeekFrom::Start(current_pos))?;
+
+	let mut comm = None;
+	let mut stream_len = 0;
+
+	let mut chunks = Chunks::<BigEndian>::new(file_len);
+
+	while chunks.next(data).is_ok() {
+		match &chunks.fourcc {
+			b"COMM" if comm.is_none() => {
+				if chunks.size < 18 {
+					decode_err!(@BAIL Aiff, "File has an invalid \"COMM\" chunk size (< 18)");
+				}
+
+				comm = Some(chunks.content(data)?);
+				chunks.correct_position(data)?;
+			},
+			b"SSND" => {
+				stream_len = chunks.size;
+				chunks.skip(data)?;
+			},
+			_ => chunks.skip(data)?,
+		}
+
+		if comm.is_some() && stream_len > 0 {
+			break;
+		}
+	}
+
+	let Some(comm) = comm else {
+		decode_err!(@BAIL Aiff, "File does not contain a \"COMM\" chunk");
+	};
+
+	if stream_len == 0 {
+		decode_err!(@BAIL Aiff, "File does not contain a \"SSND\" chunk");
+	}
+
+	// The file length is only needed for the bitrates
+	let properties =
+		super::properties::read_properties(&mut &*comm, compression_present, stream_len, 0)?;
+	Ok(properties.duration)
+}
@@ -1,8 +1,11 @@
-use crate::config::ParseOptions;
-use crate::error::Result;
+use crate::config::{DuplicateTagPolicy, ParseOptions};
+use crate::error::{FileDecodingError, ParseDiagnostic, Result};
+use crate::file::FileType;
 use crate::id3::v2::tag::Id3v2Tag;
 use crate::macros::{err, try_vec};
+use crate::tag::{TagLocation, TagPlacement, TagType};
 use crate::util::text::utf8_decode;
+use crate::util::{diagnostics, tag_locations};
 
 use std::io::{Read, Seek, SeekFrom};
 use std::marker::PhantomData;
@@ -98,18 +101,26 @@ impl<B: ByteOrder> Chunks<B> {
 		use crate::id3::v2::header::Id3v2Header;
 		use crate::id3::v2::read::parse_id3v2;
 
+		let offset = data.stream_position()? - 8;
 		let content = self.content(data)?;
 
 		let reader = &mut &*content;
 
 		let header = Id3v2Header::parse(reader)?;
+		tag_locations::record(
+			TagLocation::new(
+				TagType::Id3v2,
+				offset,
+				8 + u64::from(self.size),
+				TagPlacement::Embedded,
+			)
+			.with_header(true)
+			.with_footer(header.flags.footer),
+		);
+
+		// The footer (if any) is part of the chunk content, so there's nothing to skip
 		let id3v2 = parse_id3v2(reader, header, parse_options)?;
 
-		// Skip over the footer
-		if id3v2.flags().footer {
-			data.seek(SeekFrom::Current(10))?;
-		}
-
 		self.correct_position(data)?;
 
 		Ok(id3v2)
@@ -142,3 +153,38 @@ impl<B: ByteOrder> Chunks<B> {
 		Ok(())
 	}
 }
+
+/// Handle an additional `ID3 ` chunk, according to [`ParseOptions::duplicate_tag_policy`]
+///
+/// `offset` is the offset of the additional chunk's header.
+pub(crate) fn handle_duplicate_id3_chunk(
+	existing_tag: &mut Id3v2Tag,
+	tag: Id3v2Tag,
+	offset: u64,
+	file_type: FileType,
+	parse_options: ParseOptions,
+) -> Result<()> {
+	log::warn!("Duplicate ID3v2 chunk found at offset {offset}");
+
+	match parse_options.duplicate_tag_policy {
+		DuplicateTagPolicy::Merge => {
+			// https://github.com/Serial-ATA/lofty-rs/issues/87
+			// Duplicate tags should have their frames appended to the previous
+			for frame in tag.frames {
+				existing_tag.insert(frame);
+			}
+		},
+		DuplicateTagPolicy::FirstWins => {},
+		DuplicateTagPolicy::LastWins => *existing_tag = tag,
+		DuplicateTagPolicy::Error => {
+			return Err(FileDecodingError::new(
+				file_type,
+				"File contains multiple \"ID3 \" chunks",
+			)
+			.into());
+		},
+	}
+
+	diagnostics::report(ParseDiagnostic::DuplicateId3Chunk { offset });
+	Ok(())
+}
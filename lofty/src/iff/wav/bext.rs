@@ -0,0 +1,290 @@
+use super::tag::write::find_chunks;
+use crate::config::ParsingMode;
+use crate::error::{FileEncodingError, LoftyError, Result};
+use crate::file::FileType;
+use crate::iff::wav::read::verify_wav;
+use crate::macros::{decode_err, parse_mode_choice};
+use crate::util::io::{splice, FileLike, Length, Truncate};
+use crate::util::text::latin1_decode;
+
+use std::io::{Read, SeekFrom};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+const DESCRIPTION_LEN: usize = 256;
+const ORIGINATOR_LEN: usize = 32;
+const ORIGINATOR_REFERENCE_LEN: usize = 32;
+const ORIGINATION_DATE_LEN: usize = 10;
+const ORIGINATION_TIME_LEN: usize = 8;
+const UMID_LEN: usize = 64;
+const RESERVED_LEN: usize = 180;
+
+/// The size of the fixed length fields, everything after this is the coding history
+const FIXED_LEN: usize = DESCRIPTION_LEN
+	+ ORIGINATOR_LEN
+	+ ORIGINATOR_REFERENCE_LEN
+	+ ORIGINATION_DATE_LEN
+	+ ORIGINATION_TIME_LEN
+	+ 8 // Time reference
+	+ 2 // Version
+	+ UMID_LEN
+	+ 10 // Loudness
+	+ RESERVED_LEN;
+
+/// A Broadcast Wave Format `bext` chunk
+///
+/// See [EBU Tech 3285](https://tech.ebu.ch/docs/tech/tech3285.pdf) for the meaning of each field.
+///
+/// The text fields are fixed length ASCII fields in the chunk. They are trimmed when read, and
+/// must fit within their field when written (see [`WavFile::set_bext`](super::WavFile::set_bext)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BextChunk {
+	/// A description of the sound sequence, at most 256 characters
+	pub description: String,
+	/// The name of the originator, at most 32 characters
+	pub originator: String,
+	/// An unambiguous reference allocated by the originator, at most 32 characters
+	pub originator_reference: String,
+	/// The date of creation, formatted as `yyyy:mm:dd`
+	pub origination_date: String,
+	/// The time of creation, formatted as `hh:mm:ss`
+	pub origination_time: String,
+	/// The sample count of the first sample since midnight
+	pub time_reference: u64,
+	/// The version of the BWF
+	pub version: u16,
+	/// A SMPTE 330M Unique Material Identifier
+	///
+	/// This is all zeros if the file doesn't have one.
+	pub umid: [u8; UMID_LEN],
+	/// The loudness values, only available in version 2 and above
+	pub loudness: Option<BextLoudness>,
+	/// The history of coding processes applied to the audio, each line ending in CR/LF
+	pub coding_history: String,
+}
+
+impl Default for BextChunk {
+	fn default() -> Self {
+		Self {
+			description: String::new(),
+			originator: String::new(),
+			originator_reference: String::new(),
+			origination_date: String::new(),
+			origination_time: String::new(),
+			time_reference: 0,
+			version: 0,
+			umid: [0; UMID_LEN],
+			loudness: None,
+			coding_history: String::new(),
+		}
+	}
+}
+
+/// The loudness values of a [`BextChunk`], as defined in EBU R 128
+///
+/// All values are in hundredths of their unit (e.g. `-2300` is -23.00 LUFS).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BextLoudness {
+	/// The integrated loudness, in LUFS
+	pub loudness_value: i16,
+	/// The loudness range, in LU
+	pub loudness_range: i16,
+	/// The maximum true peak level, in dBTP
+	pub max_true_peak_level: i16,
+	/// The highest value of the momentary loudness, in LUFS
+	pub max_momentary_loudness: i16,
+	/// The highest value of the short-term loudness, in LUFS
+	pub max_short_term_loudness: i16,
+}
+
+impl BextChunk {
+	pub(super) fn parse(content: &[u8], parse_mode: ParsingMode) -> Result<Option<Self>> {
+		if content.len() < FIXED_LEN {
+			parse_mode_choice!(
+				parse_mode,
+				STRICT: decode_err!(@BAIL Wav, "bext chunk is too short"),
+			);
+
+			log::warn!("bext chunk is too short, discarding");
+			return Ok(None);
+		}
+
+		let reader = &mut &content[..];
+
+		let description = read_text(reader, DESCRIPTION_LEN)?;
+		let originator = read_text(reader, ORIGINATOR_LEN)?;
+		let originator_reference = read_text(reader, ORIGINATOR_REFERENCE_LEN)?;
+		let origination_date = read_text(reader, ORIGINATION_DATE_LEN)?;
+		let origination_time = read_text(reader, ORIGINATION_TIME_LEN)?;
+
+		// Stored as the low 32 bits followed by the high 32 bits
+		let time_reference = reader.read_u64::<LittleEndian>()?;
+		let version = reader.read_u16::<LittleEndian>()?;
+
+		let mut umid = [0; UMID_LEN];
+		reader.read_exact(&mut umid)?;
+
+		let loudness = BextLoudness {
+			loudness_value: reader.read_i16::<LittleEndian>()?,
+			loudness_range: reader.read_i16::<LittleEndian>()?,
+			max_true_peak_level: reader.read_i16::<LittleEndian>()?,
+			max_momentary_loudness: reader.read_i16::<LittleEndian>()?,
+			max_short_term_loudness: reader.read_i16::<LittleEndian>()?,
+		};
+
+		let coding_history = &reader[RESERVED_LEN..];
+		let coding_history = latin1_decode(coding_history)
+			.trim_end_matches(['\0', ' '])
+			.to_owned();
+
+		Ok(Some(Self {
+			description,
+			originator,
+			originator_reference,
+			origination_date,
+			origination_time,
+			time_reference,
+			version,
+			umid,
+			// Earlier versions reserve this space
+			loudness: (version >= 2).then_some(loudness),
+			coding_history,
+		}))
+	}
+
+	/// Create the chunk, including the header and padding
+	pub(super) fn as_bytes(&self) -> Result<Vec<u8>> {
+		let mut content = Vec::with_capacity(FIXED_LEN + self.coding_history.len());
+
+		write_text(
+			&mut content,
+			"description",
+			&self.description,
+			DESCRIPTION_LEN,
+		)?;
+		write_text(&mut content, "originator", &self.originator, ORIGINATOR_LEN)?;
+		write_text(
+			&mut content,
+			"originator reference",
+			&self.originator_reference,
+			ORIGINATOR_REFERENCE_LEN,
+		)?;
+		write_text(
+			&mut content,
+			"origination date",
+			&self.origination_date,
+			ORIGINATION_DATE_LEN,
+		)?;
+		write_text(
+			&mut content,
+			"origination time",
+			&self.origination_time,
+			ORIGINATION_TIME_LEN,
+		)?;
+
+		content.write_u64::<LittleEndian>(self.time_reference)?;
+
+		// The loudness values are only read from version 2 and above
+		let version = match self.loudness {
+			Some(_) => self.version.max(2),
+			None => self.version,
+		};
+		content.write_u16::<LittleEndian>(version)?;
+		content.extend(self.umid);
+
+		let loudness = self.loudness.unwrap_or_default();
+		for value in [
+			loudness.loudness_value,
+			loudness.loudness_range,
+			loudness.max_true_peak_level,
+			loudness.max_momentary_loudness,
+			loudness.max_short_term_loudness,
+		] {
+			content.write_i16::<LittleEndian>(value)?;
+		}
+
+		content.resize(FIXED_LEN, 0);
+
+		if !self.coding_history.is_ascii() {
+			return Err(bext_encode_err("coding history"));
+		}
+		content.extend(self.coding_history.as_bytes());
+
+		let Ok(size) = u32::try_from(content.len()) else {
+			return Err(FileEncodingError::new(FileType::Wav, "bext chunk is too large").into());
+		};
+
+		let mut bytes = Vec::with_capacity(content.len() + 9);
+		bytes.extend(b"bext");
+		bytes.write_u32::<LittleEndian>(size)?;
+		bytes.extend(content);
+
+		// Chunks are padded to an even size, the padding isn't included in the chunk size
+		if size % 2 != 0 {
+			bytes.push(0);
+		}
+
+		Ok(bytes)
+	}
+}
+
+fn read_text(reader: &mut &[u8], len: usize) -> Result<String> {
+	let mut text = vec![0; len];
+	reader.read_exact(&mut text)?;
+
+	let text = latin1_decode(&text);
+	Ok(text.trim_end_matches(['\0', ' ']).to_owned())
+}
+
+fn write_text(content: &mut Vec<u8>, field: &str, text: &str, len: usize) -> Result<()> {
+	if !text.is_ascii() || text.len() > len {
+		return Err(bext_encode_err(field));
+	}
+
+	content.extend(text.as_bytes());
+	content.extend(std::iter::repeat_n(0, len - text.len()));
+
+	Ok(())
+}
+
+fn bext_encode_err(field: &str) -> LoftyError {
+	log::error!("bext {field} must be ASCII, and fit within its field");
+	FileEncodingError::new(
+		FileType::Wav,
+		"bext text fields must fit within their fixed size",
+	)
+	.into()
+}
+
+/// Replace the file's `bext` chunk, or remove it if `bext` is `None`
+///
+/// A new chunk is placed before the `data` chunk.
+pub(super) fn write_bext<F>(file: &mut F, bext: Option<&BextChunk>) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+{
+	// Encode first, so the file is untouched if any field is invalid
+	let bext_bytes = match bext {
+		Some(bext) => bext.as_bytes()?,
+		None => Vec::new(),
+	};
+
+	verify_wav(file)?;
+	let file_len = file.len()?;
+
+	let chunks = find_chunks(file, file_len)?;
+	match (chunks.bext, chunks.data) {
+		(Some(existing), _) => splice(file, existing, &bext_bytes)?,
+		(None, Some(data)) => splice(file, data.start..data.start, &bext_bytes)?,
+		(None, None) => splice(file, file_len..file_len, &bext_bytes)?,
+	}
+
+	let len = (file.len()? - 8) as u32;
+
+	file.seek(SeekFrom::Start(4))?;
+	file.write_u32::<LittleEndian>(len)?;
+
+	Ok(())
+}
@@ -1,21 +1,37 @@
 //! WAV specific items
 
+mod bext;
 mod properties;
-mod read;
+pub(crate) mod read;
 pub(crate) mod tag;
 
+use crate::error::{LoftyError, Result};
+use crate::file::{FileType, TaggedFile};
 use crate::id3::v2::tag::Id3v2Tag;
+use crate::tag::{ItemKey, Tag, TagExt, TagType};
+use crate::util::io::{FileLike, Length, Truncate};
 
 use lofty_attr::LoftyFile;
 
 // Exports
+pub use crate::iff::wav::bext::{BextChunk, BextLoudness};
 pub use crate::iff::wav::properties::{WavFormat, WavProperties};
 pub use tag::RiffInfoList;
 
 /// A WAV file
+///
+/// ## Broadcast Wave
+///
+/// The `bext` chunk of a Broadcast Wave file is available through [`WavFile::bext`].
+///
+/// When converted to a [`TaggedFile`], its description and originator are mapped to
+/// [`ItemKey::Comment`] and [`ItemKey::EncodedBy`] of the RIFF INFO tag, if the tag doesn't already
+/// have them. This is one way only, the `bext` chunk can only be written with [`WavFile::set_bext`].
+/// Saving the [`TaggedFile`] will write the mapped items to the RIFF INFO list.
 #[derive(LoftyFile)]
 #[lofty(read_fn = "read::read_from")]
 #[lofty(internal_write_module_do_not_use_anywhere_else)]
+#[lofty(no_into_taggedfile_impl)]
 pub struct WavFile {
 	/// A RIFF INFO LIST
 	#[lofty(tag_type = "RiffInfo")]
@@ -25,4 +41,123 @@ pub struct WavFile {
 	pub(crate) id3v2_tag: Option<Id3v2Tag>,
 	/// The file's audio properties
 	pub(crate) properties: WavProperties,
+	/// The Broadcast Wave `bext` chunk
+	pub(crate) bext: Option<BextChunk>,
+}
+
+impl WavFile {
+	/// Returns the file's Broadcast Wave `bext` chunk, if it exists
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::iff::wav::WavFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut wav_reader = std::io::Cursor::new(&[]);
+	/// let wav_file = WavFile::read_from(&mut wav_reader, ParseOptions::new())?;
+	///
+	/// if let Some(bext) = wav_file.bext() {
+	/// 	println!("{} ({})", bext.description, bext.originator);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn bext(&self) -> Option<&BextChunk> {
+		self.bext.as_ref()
+	}
+
+	/// Write `bext` to the file's `bext` chunk
+	///
+	/// This replaces the existing `bext` chunk, resizing it as needed, or creates one before the
+	/// `data` chunk. The rest of the file is left untouched.
+	///
+	/// If `bext` is `None`, the chunk is removed.
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid WAV file
+	/// * A text field of `bext` isn't ASCII, or doesn't fit within its fixed size
+	/// * [`std::io::Error`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::iff::wav::WavFile;
+	/// use std::fs::OpenOptions;
+	/// use std::io::{Seek, SeekFrom};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut file = OpenOptions::new().read(true).write(true).open("foo.wav")?;
+	/// let mut wav_file = WavFile::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// let mut bext = wav_file.bext().cloned().unwrap_or_default();
+	/// bext.coding_history
+	/// 	.push_str("A=PCM,F=48000,W=24,M=stereo,T=Foo encoder\r\n");
+	///
+	/// file.seek(SeekFrom::Start(0))?;
+	/// wav_file.set_bext(&mut file, Some(bext))?;
+	/// # Ok(()) }
+	/// ```
+	pub fn set_bext<F>(&mut self, file: &mut F, bext: Option<BextChunk>) -> Result<()>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		bext::write_bext(file, bext.as_ref())?;
+
+		self.bext = bext;
+		Ok(())
+	}
+}
+
+impl From<WavFile> for TaggedFile {
+	fn from(value: WavFile) -> Self {
+		TaggedFile {
+			ty: FileType::Wav,
+			properties: value.properties.into(),
+			tags: {
+				let mut tags = Vec::with_capacity(2);
+
+				let mut riff_info = value.riff_info_tag.map(Tag::from);
+				if let Some(bext) = value.bext {
+					let tag = riff_info.get_or_insert_with(|| Tag::new(TagType::RiffInfo));
+					map_bext(tag, bext);
+
+					if tag.is_empty() {
+						riff_info = None;
+					}
+				}
+
+				if let Some(riff_info) = riff_info {
+					tags.push(riff_info);
+				}
+
+				if let Some(id3v2) = value.id3v2_tag {
+					tags.push(id3v2.into());
+				}
+
+				tags
+			},
+			removed_tags: Vec::new(),
+			tag_precedence: Vec::new(),
+			diagnostics: Vec::new(),
+			tag_locations: Vec::new(),
+		}
+	}
+}
+
+fn map_bext(tag: &mut Tag, bext: BextChunk) {
+	for (key, value) in [
+		(ItemKey::Comment, bext.description),
+		(ItemKey::EncodedBy, bext.originator),
+	] {
+		if !value.is_empty() && tag.get(&key).is_none() {
+			tag.insert_text(key, value);
+		}
+	}
 }
@@ -14,6 +14,7 @@ const EXTENSIBLE: u16 = 0xFFFE;
 /// A WAV file's format
 #[allow(missing_docs, non_camel_case_types)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WavFormat {
 	PCM,
 	IEEE_FLOAT,
@@ -28,6 +29,7 @@ impl Default for WavFormat {
 
 /// A WAV file's audio properties
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct WavProperties {
 	pub(crate) format: WavFormat,
@@ -1,13 +1,18 @@
+use super::bext::BextChunk;
 use super::properties::WavProperties;
 use super::tag::RiffInfoList;
 use super::WavFile;
 use crate::config::ParseOptions;
 use crate::error::Result;
+use crate::file::FileType;
 use crate::id3::v2::tag::Id3v2Tag;
-use crate::iff::chunk::Chunks;
+use crate::iff::chunk::{handle_duplicate_id3_chunk, Chunks};
 use crate::macros::{decode_err, err};
+use crate::tag::{TagLocation, TagPlacement, TagType};
+use crate::util::tag_locations;
 
 use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
@@ -47,6 +52,7 @@ where
 
 	let mut riff_info = RiffInfoList::default();
 	let mut id3v2_tag: Option<Id3v2Tag> = None;
+	let mut bext = None;
 
 	let mut chunks = Chunks::<LittleEndian>::new(file_len);
 
@@ -93,6 +99,14 @@ where
 							err!(SizeMismatch);
 						}
 
+						// LIST chunk header (8) + list type (4)
+						tag_locations::record(TagLocation::new(
+							TagType::RiffInfo,
+							end - u64::from(chunks.size) - 8,
+							u64::from(chunks.size) + 8,
+							TagPlacement::Embedded,
+						));
+
 						super::tag::read::parse_riff_info(
 							data,
 							&mut chunks,
@@ -107,16 +121,25 @@ where
 					},
 				}
 			},
+			b"bext" if parse_options.read_tags => {
+				let content = chunks.content(data)?;
+				chunks.correct_position(data)?;
+
+				if bext.is_none() {
+					bext = BextChunk::parse(&content, parse_options.parsing_mode)?;
+				}
+			},
 			b"ID3 " | b"id3 " if parse_options.read_tags => {
+				let offset = data.stream_position()? - 8;
 				let tag = chunks.id3_chunk(data, parse_options)?;
 				if let Some(existing_tag) = id3v2_tag.as_mut() {
-					log::warn!("Duplicate ID3v2 tag found, appending frames to previous tag");
-
-					// https://github.com/Serial-ATA/lofty-rs/issues/87
-					// Duplicate tags should have their frames appended to the previous
-					for frame in tag.frames {
-						existing_tag.insert(frame);
-					}
+					handle_duplicate_id3_chunk(
+						existing_tag,
+						tag,
+						offset,
+						FileType::Wav,
+						parse_options,
+					)?;
 					continue;
 				}
 				id3v2_tag = Some(tag);
@@ -137,5 +160,56 @@ where
 		properties,
 		riff_info_tag: (!riff_info.items.is_empty()).then_some(riff_info),
 		id3v2_tag,
+		bext,
 	})
 }
+
+/// Read the duration of a WAV file from its `fmt `, `fact`, and `data` chunks
+///
+/// The `data` chunk comes after the other two, so nothing past it (usually the tags) is visited.
+pub(crate) fn read_duration<R>(data: &mut R) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	verify_wav(data)?;
+
+	let current_pos = data.stream_position()?;
+	let file_len = data.seek(SeekFrom::End(0))?;
+
+	data.seek(SeekFrom::Start(current_pos))?;
+
+	let mut stream_len = 0_u32;
+	let mut total_samples = 0_u32;
+	let mut fmt = Vec::new();
+
+	let mut chunks = Chunks::<LittleEndian>::new(file_len);
+
+	while chunks.next(data).is_ok() {
+		match &chunks.fourcc {
+			b"fmt " if fmt.is_empty() => fmt = chunks.content(data)?,
+			b"fact" => {
+				if total_samples == 0 {
+					total_samples = data.read_u32::<LittleEndian>()?;
+				} else {
+					data.seek(SeekFrom::Current(4))?;
+				}
+			},
+			b"data" => {
+				if stream_len == 0 {
+					stream_len = chunks.size;
+				}
+
+				if !fmt.is_empty() && stream_len > 0 {
+					break;
+				}
+
+				chunks.skip(data)?;
+			},
+			_ => chunks.skip(data)?,
+		}
+	}
+
+	// The file length is only needed for the bitrates
+	let properties = super::properties::read_properties(&mut &*fmt, total_samples, stream_len, 0)?;
+	Ok(properties.duration)
+}
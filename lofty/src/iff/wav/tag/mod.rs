@@ -1,5 +1,5 @@
 pub(super) mod read;
-mod write;
+pub(crate) mod write;
 
 use crate::config::WriteOptions;
 use crate::error::{LoftyError, Result};
@@ -33,18 +33,29 @@ macro_rules! impl_accessor {
 	}
 }
 
+/// ## Item keys
+///
+/// Keys are four-character codes, made up of uppercase ASCII letters and digits. Along with the
+/// registered INFO IDs (such as `IART`, `ICMT`, or `ISFT`), any custom four-character code (such as `XMPT`)
+/// can be used.
+///
+/// NOTE: `ISRC` is the *source* of the material (a person or organization), not an International
+///       Standard Recording Code, so it isn't mapped to [`ItemKey::Isrc`]. The same goes for `IKEY`,
+///       which holds keywords rather than [`ItemKey::InitialKey`].
+///
 /// ## Conversions
 ///
 /// ### To `Tag`
 ///
-/// All items will be converted to a [`TagItem`], with all unknown keys being stored with [`ItemKey::Unknown`].
+/// All items will be converted to a [`TagItem`], with all unknown keys being stored with [`ItemKey::Unknown`],
+/// where the key is exactly the four-character code.
 ///
 /// ### From `Tag`
 ///
 /// When converting a [`TagItem`], two conditions must be met:
 ///
 /// * The [`TagItem`] has a value other than [`ItemValue::Binary`](crate::ItemValue::Binary)
-/// * It has a key that is 4 bytes in length and within the ASCII range
+/// * It has a key that maps to a valid four-character code
 #[derive(Default, Debug, PartialEq, Eq, Clone)]
 #[tag(description = "A RIFF INFO LIST", supported_formats(Wav))]
 pub struct RiffInfoList {
@@ -457,4 +468,15 @@ mod tests {
 		assert_eq!(riff_info.get("ICMT"), Some("Qux comment"));
 		assert_eq!(riff_info.get("IPRT"), Some("1"));
 	}
+
+	#[test_log::test]
+	fn write_invalid_key() {
+		for key in ["ABC", "ABCDE", "abcd", "AB\u{e9}"] {
+			let mut writer = Vec::new();
+			let result = super::RIFFInfoListRef::new([(key, "Foo")].into_iter())
+				.dump_to(&mut writer, WriteOptions::default());
+
+			assert!(result.is_err(), "{key:?} was written");
+		}
+	}
 }
@@ -20,11 +20,12 @@ where
 	R: Read + Seek,
 {
 	while data.stream_position()? != end && chunks.next(data).is_ok() {
-		let key_str = utf8_decode_str(&chunks.fourcc)
-			.map_err(|_| decode_err!(Wav, "Invalid item key found in RIFF INFO"))?;
+		let key_str = utf8_decode_str(&chunks.fourcc).map_err(
+			|e| decode_err!(@TAG RiffInfo, "Invalid item key found in RIFF INFO", source: e),
+		)?;
 
 		if !verify_key(key_str) {
-			decode_err!(@BAIL Wav, "RIFF INFO item key contains invalid characters");
+			decode_err!(@BAIL @TAG RiffInfo, "RIFF INFO item key contains invalid characters");
 		}
 
 		let key = key_str.to_owned();
@@ -33,7 +34,11 @@ where
 			Ok(cstr) => value = cstr,
 			Err(e) => {
 				if parse_mode == ParsingMode::Strict {
-					decode_err!(@BAIL Wav, "Failed to read RIFF INFO item value")
+					return Err(decode_err!(
+						@TAG RiffInfo,
+						"Failed to read RIFF INFO item value",
+						source: e
+					));
 				}
 
 				// RIFF INFO tags have no standard text encoding, so they will occasionally default
@@ -1,19 +1,22 @@
+use super::read::verify_key;
 use super::RIFFInfoListRef;
-use crate::config::WriteOptions;
-use crate::error::{LoftyError, Result};
+use crate::config::{RiffInfoPlacement, WriteOptions};
+use crate::error::{FileEncodingError, LoftyError, Result};
+use crate::file::FileType;
 use crate::iff::chunk::Chunks;
 use crate::iff::wav::read::verify_wav;
 use crate::macros::err;
-use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::io::{splice, FileLike, Length, Truncate};
 
 use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 
 pub(in crate::iff::wav) fn write_riff_info<'a, F, I>(
 	file: &mut F,
 	tag: &mut RIFFInfoListRef<'a, I>,
-	_write_options: WriteOptions,
+	write_options: WriteOptions,
 ) -> Result<()>
 where
 	F: FileLike,
@@ -22,74 +25,93 @@ where
 	I: Iterator<Item = (&'a str, &'a str)>,
 {
 	verify_wav(file)?;
-	let file_len = file.len()?.saturating_sub(12);
+	let file_len = file.len()?;
 
 	let mut riff_info_bytes = Vec::new();
 	create_riff_info(&mut tag.items, &mut riff_info_bytes)?;
 
-	let Some(info_list_size) = find_info_list(file, file_len)? else {
-		// Simply append the info list to the end of the file and update the file size
-		file.seek(SeekFrom::End(0))?;
+	let chunks = find_chunks(file, file_len)?;
 
-		file.write_all(&riff_info_bytes)?;
-
-		let len = (file.stream_position()? - 8) as u32;
-
-		file.seek(SeekFrom::Start(4))?;
-		file.write_u32::<LittleEndian>(len)?;
-
-		return Ok(());
+	let target = match write_options.riff_info_placement {
+		RiffInfoPlacement::BeforeData => chunks.data.map(|data| data.start),
+		RiffInfoPlacement::AfterData => chunks.data.map(|data| data.end),
+		_ => None,
 	};
 
-	// Replace the existing tag
-
-	let info_list_start = file.seek(SeekFrom::Current(-12))? as usize;
-	let info_list_end = info_list_start + 8 + info_list_size as usize;
-
-	file.rewind()?;
-
-	let mut file_bytes = Vec::new();
-	file.read_to_end(&mut file_bytes)?;
+	match (chunks.info, target) {
+		// The existing list is replaced in place when it's already where it should be, or when
+		// there's nowhere else to put it
+		(Some(info), Some(target)) if info.end == target || info.start == target => {
+			splice(file, info, &riff_info_bytes)?;
+		},
+		(Some(info), None) => splice(file, info, &riff_info_bytes)?,
+		(Some(info), Some(mut target)) => {
+			let info_len = info.end - info.start;
+			if info.end <= target {
+				target -= info_len;
+			}
 
-	let _ = file_bytes.splice(info_list_start..info_list_end, riff_info_bytes);
+			splice(file, info, &[])?;
+			splice(file, target..target, &riff_info_bytes)?;
+		},
+		(None, target) => {
+			let target = target.unwrap_or(file_len);
+			splice(file, target..target, &riff_info_bytes)?;
+		},
+	}
 
-	let total_size = (file_bytes.len() - 8) as u32;
-	let _ = file_bytes.splice(4..8, total_size.to_le_bytes());
+	let len = (file.len()? - 8) as u32;
 
-	file.rewind()?;
-	file.truncate(0)?;
-	file.write_all(&file_bytes)?;
+	file.seek(SeekFrom::Start(4))?;
+	file.write_u32::<LittleEndian>(len)?;
 
 	Ok(())
 }
 
-fn find_info_list<R>(data: &mut R, file_size: u64) -> Result<Option<u32>>
+/// The positions of the chunks relevant to writing, including their headers and padding
+#[derive(Default)]
+pub(crate) struct ChunkPositions {
+	pub(crate) info: Option<Range<u64>>,
+	pub(crate) bext: Option<Range<u64>>,
+	pub(crate) data: Option<Range<u64>>,
+}
+
+pub(crate) fn find_chunks<R>(data: &mut R, file_len: u64) -> Result<ChunkPositions>
 where
 	R: Read + Seek,
 {
-	let mut info = None;
-
-	let mut chunks = Chunks::<LittleEndian>::new(file_size);
-
-	while chunks.next(data).is_ok() {
-		if &chunks.fourcc == b"LIST" {
-			let mut list_type = [0; 4];
-			data.read_exact(&mut list_type)?;
-
-			if &list_type == b"INFO" {
-				log::debug!("Found existing RIFF INFO list, size: {} bytes", chunks.size);
+	let mut positions = ChunkPositions::default();
 
-				info = Some(chunks.size);
-				break;
-			}
+	let mut chunks = Chunks::<LittleEndian>::new(file_len.saturating_sub(12));
+	loop {
+		let start = data.stream_position()?;
+		if chunks.next(data).is_err() {
+			break;
+		}
 
-			data.seek(SeekFrom::Current(-8))?;
+		// Chunks are padded to an even size, see `Chunks::correct_position`
+		let size = u64::from(chunks.size) + u64::from(chunks.size % 2);
+		let end = (start + 8 + size).min(file_len);
+
+		match &chunks.fourcc {
+			b"LIST" if positions.info.is_none() => {
+				let mut list_type = [0; 4];
+				data.read_exact(&mut list_type)?;
+
+				if &list_type == b"INFO" {
+					log::debug!("Found existing RIFF INFO list, size: {} bytes", chunks.size);
+					positions.info = Some(start..end);
+				}
+			},
+			b"bext" if positions.bext.is_none() => positions.bext = Some(start..end),
+			b"data" if positions.data.is_none() => positions.data = Some(start..end),
+			_ => {},
 		}
 
-		chunks.skip(data)?;
+		data.seek(SeekFrom::Start(end))?;
 	}
 
-	Ok(info)
+	Ok(positions)
 }
 
 pub(super) fn create_riff_info(
@@ -107,10 +129,20 @@ pub(super) fn create_riff_info(
 	bytes.extend(b"INFO");
 
 	for (k, v) in items {
+		// The terminator is added below
+		let v = v.trim_end_matches('\0');
 		if v.is_empty() {
 			continue;
 		}
 
+		if !verify_key(k) {
+			return Err(FileEncodingError::new(
+				FileType::Wav,
+				"RIFF INFO item keys must be 4 uppercase ASCII letters or digits",
+			)
+			.into());
+		}
+
 		let val_b = v.as_bytes();
 		// Account for null terminator
 		let len = val_b.len() + 1;
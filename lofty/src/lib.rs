@@ -119,6 +119,9 @@ mod util;
 
 pub mod aac;
 pub mod ape;
+pub mod asf;
+pub mod dff;
+pub mod dsf;
 pub mod flac;
 pub mod id3;
 pub mod iff;
@@ -126,9 +129,12 @@ pub mod mp4;
 pub mod mpeg;
 pub mod musepack;
 pub mod ogg;
+pub mod shn;
+pub mod tta;
 pub mod wavpack;
 
-pub use crate::probe::{read_from, read_from_path};
+pub use crate::probe::{duration, read_from, read_from_path, verify_stream};
+pub use crate::tag::apply_patch;
 
 pub use util::text::TextEncoding;
 
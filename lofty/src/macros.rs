@@ -27,24 +27,37 @@ macro_rules! err {
 // Usage:
 //
 // - decode_err!(Variant, Message)
+// - decode_err!(Variant, Message, source: Error)
+// - decode_err!(@TAG TagVariant, Message)
+// - decode_err!(@TAG TagVariant, Message, source: Error)
 // - decode_err!(Message)
 //
 // or bail:
 //
 // - decode_err!(@BAIL Variant, Message)
+// - decode_err!(@BAIL @TAG TagVariant, Message)
 // - decode_err!(@BAIL Message)
 macro_rules! decode_err {
-	($file_ty:ident, $reason:literal) => {
-		Into::<crate::error::LoftyError>::into(crate::error::FileDecodingError::new(
-			crate::file::FileType::$file_ty,
-			$reason,
-		))
+	($file_ty:ident, $reason:literal $(, source: $source:expr)?) => {
+		Into::<crate::error::LoftyError>::into(
+			crate::error::FileDecodingError::new(crate::file::FileType::$file_ty, $reason)
+				$(.with_source($source))?,
+		)
+	};
+	(@TAG $tag_ty:ident, $reason:literal $(, source: $source:expr)?) => {
+		Into::<crate::error::LoftyError>::into(
+			crate::error::FileDecodingError::from_tag(crate::tag::TagType::$tag_ty, $reason)
+				$(.with_source($source))?,
+		)
 	};
 	($reason:literal) => {
 		Into::<crate::error::LoftyError>::into(crate::error::FileDecodingError::from_description(
 			$reason,
 		))
 	};
+	(@BAIL @TAG $tag_ty:ident, $reason:literal) => {
+		return Err(decode_err!(@TAG $tag_ty, $reason))
+	};
 	(@BAIL $($file_ty:ident,)? $reason:literal) => {
 		return Err(decode_err!($($file_ty,)? $reason))
 	};
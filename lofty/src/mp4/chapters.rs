@@ -0,0 +1,623 @@
+use super::atom_info::{AtomIdent, AtomInfo, ATOM_HEADER_LEN};
+use super::read::AtomReader;
+use super::write::{update_offsets, AtomWriter};
+use crate::config::{ParseOptions, ParsingMode};
+use crate::error::{FileEncodingError, LoftyError, Result};
+use crate::file::FileType;
+use crate::macros::{decode_err, err, try_vec};
+use crate::util::alloc::VecFallibleCapacity;
+use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::text::{utf16_decode_bytes, utf8_decode};
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::time::Duration;
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+/// `chpl` timestamps are in units of 100 nanoseconds
+const CHPL_TIMESCALE: u64 = 10_000_000;
+
+/// A chapter in an MP4 file
+///
+/// Chapters are read from either a Nero `chpl` atom, or a QuickTime chapter track. See [`Mp4File::chapters`](super::Mp4File::chapters).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mp4Chapter {
+	/// The offset from the start of the file's audio
+	pub start: Duration,
+	/// The chapter's title
+	pub title: String,
+}
+
+impl Mp4Chapter {
+	/// Create a new `Mp4Chapter`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::mp4::Mp4Chapter;
+	/// use std::time::Duration;
+	///
+	/// let chapter = Mp4Chapter::new(Duration::from_secs(90), String::from("Chapter 2"));
+	/// assert_eq!(chapter.title, "Chapter 2");
+	/// ```
+	pub fn new(start: Duration, title: String) -> Self {
+		Self { start, title }
+	}
+}
+
+/// Parse the content of a `chpl` atom
+///
+/// This expects the reader to be at the start of the atom's content.
+pub(super) fn parse_chpl<R>(reader: &mut R, len: u64) -> Result<Vec<Mp4Chapter>>
+where
+	R: Read,
+{
+	let mut content = try_vec![0; len as usize];
+	reader.read_exact(&mut content)?;
+
+	let reader = &mut &content[..];
+
+	let version = reader.read_u8()?;
+	let _flags = reader.read_u24::<BigEndian>()?;
+
+	// Version 1 adds a reserved field before the chapter count
+	if version > 0 {
+		let _reserved = reader.read_u32::<BigEndian>()?;
+	}
+
+	let chapter_count = reader.read_u8()?;
+
+	let mut chapters = Vec::try_with_capacity_stable(usize::from(chapter_count))?;
+	for _ in 0..chapter_count {
+		let start = reader.read_u64::<BigEndian>()?;
+
+		let title_len = reader.read_u8()?;
+		let mut title = try_vec![0; usize::from(title_len)];
+		reader.read_exact(&mut title)?;
+
+		chapters.push(Mp4Chapter {
+			start: chpl_timestamp_to_duration(start),
+			title: utf8_decode(title)?,
+		});
+	}
+
+	Ok(chapters)
+}
+
+fn chpl_timestamp_to_duration(timestamp: u64) -> Duration {
+	Duration::new(
+		timestamp / CHPL_TIMESCALE,
+		((timestamp % CHPL_TIMESCALE) * 100) as u32,
+	)
+}
+
+/// Create a version 1 `chpl` atom
+fn build_chpl(chapters: &[Mp4Chapter]) -> Result<Vec<u8>> {
+	let Ok(chapter_count) = u8::try_from(chapters.len()) else {
+		err!(TooMuchData);
+	};
+
+	let mut chpl = Vec::new();
+
+	// Size, identifier, version, flags, and reserved field
+	chpl.write_u32::<BigEndian>(0)?;
+	chpl.write_all(b"chpl")?;
+	chpl.write_u32::<BigEndian>(1 << 24)?;
+	chpl.write_u32::<BigEndian>(0)?;
+
+	chpl.write_u8(chapter_count)?;
+	for chapter in chapters {
+		let timestamp = chapter.start.as_nanos() / 100;
+		chpl.write_u64::<BigEndian>(u64::try_from(timestamp).unwrap_or(u64::MAX))?;
+
+		// The title length is stored in a single byte, so longer titles have to be cut
+		let mut title_len = chapter.title.len().min(usize::from(u8::MAX));
+		while !chapter.title.is_char_boundary(title_len) {
+			title_len -= 1;
+		}
+
+		chpl.write_u8(title_len as u8)?;
+		chpl.write_all(&chapter.title.as_bytes()[..title_len])?;
+	}
+
+	let size = chpl.len() as u32;
+	chpl[..4].copy_from_slice(&size.to_be_bytes());
+
+	Ok(chpl)
+}
+
+/// Write `chapters` to the `moov.udta.chpl` atom of `file`
+///
+/// An empty `chapters` will remove the `chpl` atom.
+pub(super) fn write_chpl<F>(file: &mut F, chapters: &[Mp4Chapter]) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+{
+	log::debug!("Attempting to write `chpl` atom to file");
+
+	file.rewind()?;
+	let atom_writer = AtomWriter::new_for_moov(file, ParseOptions::DEFAULT_PARSING_MODE)?;
+
+	let Some(moov) = atom_writer.find_contextual_atom(*b"moov") else {
+		return Err(FileEncodingError::new(
+			FileType::Mp4,
+			"Could not find \"moov\" atom in target file",
+		)
+		.into());
+	};
+
+	let chpl = if chapters.is_empty() {
+		Vec::new()
+	} else {
+		build_chpl(chapters)?
+	};
+
+	let udta = moov
+		.children
+		.iter()
+		.find(|atom| atom.info.ident == AtomIdent::Fourcc(*b"udta"));
+
+	let mut write_handle = atom_writer.start_write();
+
+	let (range, replacement) = match udta {
+		Some(udta) => {
+			let existing_chpl = udta
+				.children
+				.iter()
+				.find(|atom| atom.info.ident == AtomIdent::Fourcc(*b"chpl"));
+
+			let range = match existing_chpl {
+				Some(existing_chpl) => {
+					let start = existing_chpl.info.start as usize;
+					start..start + existing_chpl.info.len as usize
+				},
+				// Nothing to do
+				None if chpl.is_empty() => return Ok(()),
+				None => {
+					let udta_end = (udta.info.start + udta.info.len) as usize;
+					udta_end..udta_end
+				},
+			};
+
+			let new_udta_size = (udta.info.len - range.len() as u64) + chpl.len() as u64;
+
+			log::trace!(
+				"Updating `udta` atom size, old size: {}, new size: {}",
+				udta.info.len,
+				new_udta_size
+			);
+
+			write_handle.seek(SeekFrom::Start(udta.info.start))?;
+			write_handle.write_atom_size(udta.info.start, new_udta_size, udta.info.extended)?;
+
+			(range, chpl)
+		},
+		// Nothing to do
+		None if chpl.is_empty() => return Ok(()),
+		None => {
+			log::trace!("No `udta` atom found, creating one");
+
+			let mut udta = Vec::try_with_capacity_stable(ATOM_HEADER_LEN as usize + chpl.len())?;
+			udta.write_u32::<BigEndian>((ATOM_HEADER_LEN as usize + chpl.len()) as u32)?;
+			udta.write_all(b"udta")?;
+			udta.extend(chpl);
+
+			// We'll put the new `udta` atom right at the start of `moov`
+			let udta_pos = (moov.info.start + moov.info.header_size()) as usize;
+			(udta_pos..udta_pos, udta)
+		},
+	};
+
+	let difference = replacement.len() as i64 - range.len() as i64;
+
+	let new_moov_size = (moov.info.len as i64 + difference) as u64;
+
+	log::trace!(
+		"Updating `moov` atom size, old size: {}, new size: {}",
+		moov.info.len,
+		new_moov_size
+	);
+
+	write_handle.seek(SeekFrom::Start(moov.info.start))?;
+	write_handle.write_atom_size(moov.info.start, new_moov_size, moov.info.extended)?;
+
+	drop(write_handle);
+
+	// The offsets need to be updated before any data is moved, as the offset atom positions
+	// are those of the original file
+	if difference != 0 {
+		update_offsets(&atom_writer, moov, difference, range.start as u64)?;
+	}
+
+	let mut write_handle = atom_writer.start_write();
+	write_handle.splice(range, replacement);
+	drop(write_handle);
+
+	let mut atom_writer = atom_writer;
+	atom_writer.save_to(file)?;
+
+	Ok(())
+}
+
+/// The parts of a `trak` atom needed to read a QuickTime chapter track
+#[derive(Default)]
+struct ChapterTrak {
+	id: u32,
+	// The IDs of the tracks referenced by `tref.chap`
+	chapter_track_ids: Vec<u32>,
+	timescale: u32,
+	// (sample count, sample duration)
+	time_to_sample: Vec<(u32, u32)>,
+	// (first chunk, samples per chunk)
+	sample_to_chunk: Vec<(u32, u32)>,
+	sample_sizes: Vec<u32>,
+	chunk_offsets: Vec<u64>,
+}
+
+/// Read the chapters from a QuickTime chapter track
+///
+/// The chapter track is the first track referenced by a `tref.chap` atom. Its samples are
+/// the chapter titles, and their timing determines the chapter start times.
+///
+/// NOTE: The reader is expected to have no bounds, as the samples may be located anywhere in the file.
+pub(super) fn read_quicktime_chapters<R>(
+	reader: &mut AtomReader<R>,
+	moov: &AtomInfo,
+	parse_mode: ParsingMode,
+) -> Result<Vec<Mp4Chapter>>
+where
+	R: Read + Seek,
+{
+	let mut traks = Vec::new();
+	for_each_child(reader, moov, parse_mode, |reader, atom| {
+		if atom.ident == AtomIdent::Fourcc(*b"trak") {
+			traks.push(parse_trak(reader, atom, parse_mode)?);
+		}
+
+		Ok(())
+	})?;
+
+	let Some(chapter_track_id) = traks.iter().find_map(|trak| trak.chapter_track_ids.first())
+	else {
+		return Ok(Vec::new());
+	};
+
+	let Some(chapter_trak) = traks.iter().find(|trak| trak.id == *chapter_track_id) else {
+		log::warn!("Chapter track {chapter_track_id} does not exist");
+		return Ok(Vec::new());
+	};
+
+	if chapter_trak.timescale == 0 {
+		decode_err!(@BAIL Mp4, "Chapter track has a timescale of 0");
+	}
+
+	let sample_offsets = sample_offsets(chapter_trak)?;
+
+	let mut chapters = Vec::try_with_capacity_stable(sample_offsets.len())?;
+	let mut sample_durations = chapter_trak
+		.time_to_sample
+		.iter()
+		.flat_map(|&(count, duration)| std::iter::repeat_n(duration, count as usize));
+
+	let mut elapsed = 0u64;
+	for offset in sample_offsets {
+		reader.seek(SeekFrom::Start(offset))?;
+
+		let title_len = reader.read_u16()?;
+		let mut title = try_vec![0; usize::from(title_len)];
+		reader.read_exact(&mut title)?;
+
+		let title = match title.strip_prefix(&[0xFE, 0xFF]) {
+			Some(utf16_title) => utf16_decode_bytes(utf16_title, u16::from_be_bytes)?,
+			None => utf8_decode(title)?,
+		};
+
+		let start_nanos = u128::from(elapsed) * 1_000_000_000 / u128::from(chapter_trak.timescale);
+		chapters.push(Mp4Chapter {
+			start: Duration::from_nanos(u64::try_from(start_nanos).unwrap_or(u64::MAX)),
+			title,
+		});
+
+		elapsed += u64::from(sample_durations.next().unwrap_or(0));
+	}
+
+	Ok(chapters)
+}
+
+/// Get the position of every sample in the track
+fn sample_offsets(trak: &ChapterTrak) -> Result<Vec<u64>> {
+	let mut offsets = Vec::try_with_capacity_stable(trak.sample_sizes.len())?;
+	let mut sample_sizes = trak.sample_sizes.iter();
+
+	'chunks: for (chunk_idx, chunk_offset) in trak.chunk_offsets.iter().enumerate() {
+		// Chunk numbers start at 1
+		let chunk_number = chunk_idx as u32 + 1;
+
+		let samples_per_chunk = trak
+			.sample_to_chunk
+			.iter()
+			.rev()
+			.find(|(first_chunk, _)| *first_chunk <= chunk_number)
+			.map_or(0, |(_, samples_per_chunk)| *samples_per_chunk);
+
+		let mut offset = *chunk_offset;
+		for _ in 0..samples_per_chunk {
+			let Some(sample_size) = sample_sizes.next() else {
+				break 'chunks;
+			};
+
+			offsets.push(offset);
+			offset += u64::from(*sample_size);
+		}
+	}
+
+	Ok(offsets)
+}
+
+fn parse_trak<R>(
+	reader: &mut AtomReader<R>,
+	trak: &AtomInfo,
+	parse_mode: ParsingMode,
+) -> Result<ChapterTrak>
+where
+	R: Read + Seek,
+{
+	let mut chapter_trak = ChapterTrak::default();
+
+	for_each_child(reader, trak, parse_mode, |reader, atom| {
+		let AtomIdent::Fourcc(fourcc) = atom.ident else {
+			return Ok(());
+		};
+
+		match &fourcc {
+			b"tkhd" => {
+				let version = reader.read_u8()?;
+				let _flags = reader.read_u24()?;
+
+				// Skip the creation and modification times
+				if version == 1 {
+					reader.seek(SeekFrom::Current(16))?;
+				} else {
+					reader.seek(SeekFrom::Current(8))?;
+				}
+
+				chapter_trak.id = reader.read_u32()?;
+			},
+			b"tref" => for_each_child(reader, atom, parse_mode, |reader, atom| {
+				if atom.ident == AtomIdent::Fourcc(*b"chap") {
+					let content_len = atom.len - atom.header_size();
+					for _ in 0..content_len / 4 {
+						chapter_trak.chapter_track_ids.push(reader.read_u32()?);
+					}
+				}
+
+				Ok(())
+			})?,
+			b"mdia" => parse_mdia(reader, atom, parse_mode, &mut chapter_trak)?,
+			_ => {},
+		}
+
+		Ok(())
+	})?;
+
+	Ok(chapter_trak)
+}
+
+fn parse_mdia<R>(
+	reader: &mut AtomReader<R>,
+	mdia: &AtomInfo,
+	parse_mode: ParsingMode,
+	chapter_trak: &mut ChapterTrak,
+) -> Result<()>
+where
+	R: Read + Seek,
+{
+	for_each_child(reader, mdia, parse_mode, |reader, atom| {
+		let AtomIdent::Fourcc(fourcc) = atom.ident else {
+			return Ok(());
+		};
+
+		match &fourcc {
+			b"mdhd" => {
+				let version = reader.read_u8()?;
+				let _flags = reader.read_u24()?;
+
+				// Skip the creation and modification times
+				if version == 1 {
+					reader.seek(SeekFrom::Current(16))?;
+				} else {
+					reader.seek(SeekFrom::Current(8))?;
+				}
+
+				chapter_trak.timescale = reader.read_u32()?;
+			},
+			b"minf" => for_each_child(reader, atom, parse_mode, |reader, atom| {
+				if atom.ident == AtomIdent::Fourcc(*b"stbl") {
+					parse_stbl(reader, atom, parse_mode, chapter_trak)?;
+				}
+
+				Ok(())
+			})?,
+			_ => {},
+		}
+
+		Ok(())
+	})
+}
+
+fn parse_stbl<R>(
+	reader: &mut AtomReader<R>,
+	stbl: &AtomInfo,
+	parse_mode: ParsingMode,
+	chapter_trak: &mut ChapterTrak,
+) -> Result<()>
+where
+	R: Read + Seek,
+{
+	for_each_child(reader, stbl, parse_mode, |reader, atom| {
+		let AtomIdent::Fourcc(fourcc) = atom.ident else {
+			return Ok(());
+		};
+
+		match &fourcc {
+			b"stts" => {
+				let _version_and_flags = reader.read_u32()?;
+				let entry_count = reader.read_u32()?;
+
+				chapter_trak.time_to_sample = Vec::try_with_capacity_stable(entry_count as usize)?;
+				for _ in 0..entry_count {
+					let sample_count = reader.read_u32()?;
+					let sample_duration = reader.read_u32()?;
+					chapter_trak
+						.time_to_sample
+						.push((sample_count, sample_duration));
+				}
+			},
+			b"stsc" => {
+				let _version_and_flags = reader.read_u32()?;
+				let entry_count = reader.read_u32()?;
+
+				chapter_trak.sample_to_chunk = Vec::try_with_capacity_stable(entry_count as usize)?;
+				for _ in 0..entry_count {
+					let first_chunk = reader.read_u32()?;
+					let samples_per_chunk = reader.read_u32()?;
+					let _sample_description_index = reader.read_u32()?;
+					chapter_trak
+						.sample_to_chunk
+						.push((first_chunk, samples_per_chunk));
+				}
+			},
+			b"stsz" => {
+				let _version_and_flags = reader.read_u32()?;
+				let sample_size = reader.read_u32()?;
+				let sample_count = reader.read_u32()?;
+
+				chapter_trak.sample_sizes = Vec::try_with_capacity_stable(sample_count as usize)?;
+				for _ in 0..sample_count {
+					// A non-zero sample size applies to every sample, and there is no table
+					if sample_size == 0 {
+						chapter_trak.sample_sizes.push(reader.read_u32()?);
+					} else {
+						chapter_trak.sample_sizes.push(sample_size);
+					}
+				}
+			},
+			// 32-bit and 64-bit chunk offsets
+			b"stco" | b"co64" => {
+				let _version_and_flags = reader.read_u32()?;
+				let entry_count = reader.read_u32()?;
+
+				chapter_trak.chunk_offsets = Vec::try_with_capacity_stable(entry_count as usize)?;
+				for _ in 0..entry_count {
+					let offset = if &fourcc == b"co64" {
+						reader.read_u64()?
+					} else {
+						u64::from(reader.read_u32()?)
+					};
+
+					chapter_trak.chunk_offsets.push(offset);
+				}
+			},
+			_ => {},
+		}
+
+		Ok(())
+	})
+}
+
+/// Call `f` for each child of `parent`
+///
+/// `f` does not need to read the entire child, the reader will be moved to the end of the
+/// child afterwards.
+fn for_each_child<R, F>(
+	reader: &mut AtomReader<R>,
+	parent: &AtomInfo,
+	parse_mode: ParsingMode,
+	mut f: F,
+) -> Result<()>
+where
+	R: Read + Seek,
+	F: FnMut(&mut AtomReader<R>, &AtomInfo) -> Result<()>,
+{
+	let parent_end = parent.start + parent.len;
+
+	let mut pos = reader.seek(SeekFrom::Start(parent.start + parent.header_size()))?;
+	while parent_end.saturating_sub(pos) >= ATOM_HEADER_LEN {
+		let Some(atom) = AtomInfo::read(reader, parent_end - pos, parse_mode)? else {
+			break;
+		};
+
+		f(reader, &atom)?;
+
+		pos = reader.seek(SeekFrom::Start(atom.start + atom.len))?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{build_chpl, parse_chpl, Mp4Chapter};
+	use crate::mp4::atom_info::ATOM_HEADER_LEN;
+
+	use std::time::Duration;
+
+	#[test_log::test]
+	fn chpl_round_trip() {
+		let chapters = vec![
+			Mp4Chapter::new(Duration::ZERO, String::from("Intro")),
+			Mp4Chapter::new(Duration::from_millis(1500), String::from("Kapitel Zwei")),
+			Mp4Chapter::new(Duration::from_secs(3725), String::from("終わり")),
+		];
+
+		let chpl = build_chpl(&chapters).unwrap();
+		assert_eq!(&chpl[4..8], b"chpl");
+		assert_eq!(
+			u32::from_be_bytes(chpl[..4].try_into().unwrap()) as usize,
+			chpl.len()
+		);
+
+		let content = &chpl[ATOM_HEADER_LEN as usize..];
+		let parsed = parse_chpl(&mut &content[..], content.len() as u64).unwrap();
+		assert_eq!(parsed, chapters);
+	}
+
+	#[test_log::test]
+	fn chpl_version_0() {
+		#[rustfmt::skip]
+		let content = [
+			// Version and flags
+			0, 0, 0, 0,
+			// Chapter count
+			2,
+			// Start + title
+			0, 0, 0, 0, 0, 0, 0, 0,
+			3, b'O', b'n', b'e',
+			0, 0, 0, 0, 0x05, 0xF5, 0xE1, 0x00,
+			3, b'T', b'w', b'o',
+		];
+
+		let parsed = parse_chpl(&mut &content[..], content.len() as u64).unwrap();
+		assert_eq!(
+			parsed,
+			vec![
+				Mp4Chapter::new(Duration::ZERO, String::from("One")),
+				Mp4Chapter::new(Duration::from_secs(10), String::from("Two")),
+			]
+		);
+	}
+
+	#[test_log::test]
+	fn chpl_long_title_is_truncated() {
+		let chapters = vec![Mp4Chapter::new(Duration::ZERO, "é".repeat(200))];
+
+		let chpl = build_chpl(&chapters).unwrap();
+		let content = &chpl[ATOM_HEADER_LEN as usize..];
+		let parsed = parse_chpl(&mut &content[..], content.len() as u64).unwrap();
+
+		// 127 two byte characters is the most that fits in 255 bytes
+		assert_eq!(parsed[0].title, "é".repeat(127));
+	}
+}
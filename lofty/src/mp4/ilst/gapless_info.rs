@@ -0,0 +1,103 @@
+/// Gapless playback information, from the `----:com.apple.iTunes:iTunSMPB` atom
+///
+/// iTunes stores the encoder delay and padding as a string of space-separated hexadecimal fields:
+///
+/// ```text
+///  00000000 00000840 000001CA 00000000003F31F6 00000000 00000000 00000000 ...
+/// ```
+///
+/// Only the second through fourth fields are meaningful, the rest are always zero.
+///
+/// See [`Ilst::gapless_info`](crate::mp4::Ilst::gapless_info) and [`Ilst::set_gapless_info`](crate::mp4::Ilst::set_gapless_info).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GaplessInfo {
+	/// The number of priming samples the encoder added to the start of the stream
+	pub delay: u32,
+	/// The number of samples added to the end of the stream to fill the last frame
+	pub padding: u32,
+	/// The number of samples in the original audio, excluding the delay and padding
+	pub valid_samples: u64,
+}
+
+impl GaplessInfo {
+	/// The number of fields iTunes writes, including the unused ones
+	const FIELD_COUNT: usize = 12;
+
+	pub(crate) fn parse(value: &str) -> Option<Self> {
+		let mut fields = value.split_whitespace();
+
+		// The first field is unused
+		fields.next()?;
+
+		let delay = u32::from_str_radix(fields.next()?, 16).ok()?;
+		let padding = u32::from_str_radix(fields.next()?, 16).ok()?;
+		let valid_samples = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+		// Any remaining fields are ignored, their number varies between encoders
+		Some(Self {
+			delay,
+			padding,
+			valid_samples,
+		})
+	}
+
+	pub(crate) fn to_itunsmpb(self) -> String {
+		let mut value = format!(
+			" 00000000 {:08X} {:08X} {:016X}",
+			self.delay, self.padding, self.valid_samples
+		);
+
+		for _ in 4..Self::FIELD_COUNT {
+			value.push_str(" 00000000");
+		}
+
+		value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::GaplessInfo;
+
+	#[test_log::test]
+	fn parse_itunsmpb() {
+		let expected = GaplessInfo {
+			delay: 0x840,
+			padding: 0x1CA,
+			valid_samples: 0x3F_31F6,
+		};
+
+		for value in [
+			// As written by iTunes
+			" 00000000 00000840 000001CA 00000000003F31F6 00000000 00000000 00000000 00000000 \
+			 00000000 00000000 00000000 00000000",
+			// Lowercase, without the trailing fields
+			" 00000000 00000840 000001ca 00000000003f31f6",
+			// Extra whitespace
+			"00000000  00000840\t000001CA 00000000003F31F6 00000000\0",
+		] {
+			assert_eq!(GaplessInfo::parse(value), Some(expected), "{value:?}");
+		}
+
+		assert_eq!(GaplessInfo::parse(""), None);
+		assert_eq!(GaplessInfo::parse(" 00000000 00000840 000001CA"), None);
+		assert_eq!(GaplessInfo::parse(" 00000000 0000084G 000001CA 0"), None);
+	}
+
+	#[test_log::test]
+	fn write_itunsmpb() {
+		let info = GaplessInfo {
+			delay: 0x840,
+			padding: 0x1CA,
+			valid_samples: 0x3F_31F6,
+		};
+
+		let value = info.to_itunsmpb();
+		assert_eq!(
+			value,
+			" 00000000 00000840 000001CA 00000000003F31F6 00000000 00000000 00000000 00000000 \
+			 00000000 00000000 00000000 00000000"
+		);
+		assert_eq!(GaplessInfo::parse(&value), Some(info));
+	}
+}
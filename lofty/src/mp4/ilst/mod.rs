@@ -2,29 +2,39 @@ pub(super) mod advisory_rating;
 pub(super) mod atom;
 pub(super) mod constants;
 pub(super) mod data_type;
+pub(super) mod gapless_info;
 pub(super) mod read;
 mod r#ref;
 pub(crate) mod write;
 
 use super::AtomIdent;
-use crate::config::{global_options, WriteOptions};
+use crate::config::{global_options, ParseOptions, PrettyPrintOptions, WriteOptions};
 use crate::error::LoftyError;
+use crate::macros::{err, try_vec};
+use crate::mp4::atom_info::ATOM_HEADER_LEN;
 use crate::mp4::ilst::atom::AtomDataStorage;
-use crate::picture::{Picture, PictureType, TOMBSTONE_PICTURE};
+use crate::mp4::read::AtomReader;
+use crate::picture::{Picture, PictureType};
 use crate::tag::companion_tag::CompanionTag;
+use crate::tag::items::Popularimeter;
 use crate::tag::{
 	try_parse_year, Accessor, ItemKey, ItemValue, MergeTag, SplitTag, Tag, TagExt, TagItem, TagType,
 };
 use crate::util::flag_item;
 use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::pretty_print::{PrettyPrinter, Value};
 use advisory_rating::AdvisoryRating;
 use atom::{Atom, AtomData};
 use data_type::DataType;
+use gapless_info::GaplessInfo;
 
 use std::borrow::Cow;
-use std::io::Write;
+use std::fmt;
+use std::io::{Cursor, Read, Write};
 use std::ops::Deref;
 
+use byteorder::{BigEndian, ReadBytesExt};
+
 use lofty_attr::tag;
 
 const ARTIST: AtomIdent<'_> = AtomIdent::Fourcc(*b"\xa9ART");
@@ -34,6 +44,10 @@ const GENRE: AtomIdent<'_> = AtomIdent::Fourcc(*b"\xa9gen");
 const COMMENT: AtomIdent<'_> = AtomIdent::Fourcc(*b"\xa9cmt");
 const ADVISORY_RATING: AtomIdent<'_> = AtomIdent::Fourcc(*b"rtng");
 const COVR: AtomIdent<'_> = AtomIdent::Fourcc(*b"covr");
+const ITUNSMPB: AtomIdent<'_> = AtomIdent::Freeform {
+	mean: Cow::Borrowed("com.apple.iTunes"),
+	name: Cow::Borrowed("iTunSMPB"),
+};
 
 macro_rules! impl_accessor {
 	($($name:ident => $const:ident;)+) => {
@@ -106,8 +120,8 @@ macro_rules! impl_flag_accessors {
 ///
 /// ### From `Tag`
 ///
-/// When converting from [`Tag`], only items with a value of [`ItemValue::Text`](crate::ItemValue::Text), as
-/// well as pictures, will be preserved.
+/// When converting from [`Tag`], only items with a value of [`ItemValue::Text`](crate::ItemValue::Text) or
+/// [`ItemValue::Locator`](crate::ItemValue::Locator), as well as pictures, will be preserved.
 ///
 /// An attempt will be made to create the `TrackNumber/TrackTotal` (trkn) and `DiscNumber/DiscTotal` (disk) pairs.
 #[derive(Default, PartialEq, Debug, Clone)]
@@ -132,6 +146,69 @@ impl Ilst {
 		Self::default()
 	}
 
+	/// Parse an `Ilst` from a reader
+	///
+	/// This accepts exactly what [`TagExt::dump_to`] produces: a single `ilst` atom, including its
+	/// header. The reader is expected to be positioned at the start of the atom, and will be
+	/// positioned at its end upon return.
+	///
+	/// NOTE: An empty tag is written as nothing at all, so there is nothing to parse in that case.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * The atom is not an `ilst` atom, or has an invalid length
+	/// * Any of the items are invalid, depending on the [`ParsingMode`](crate::config::ParsingMode)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, WriteOptions};
+	/// use lofty::mp4::Ilst;
+	/// use lofty::tag::{Accessor, TagExt};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut ilst = Ilst::new();
+	/// ilst.set_title(String::from("Foo title"));
+	///
+	/// let mut bytes = Vec::new();
+	/// ilst.dump_to(&mut bytes, WriteOptions::default())?;
+	///
+	/// let parsed_tag = Ilst::parse(&mut Cursor::new(bytes), ParseOptions::new())?;
+	/// assert_eq!(parsed_tag.title().as_deref(), Some("Foo title"));
+	/// # Ok(()) }
+	/// ```
+	pub fn parse<R>(reader: &mut R, parse_options: ParseOptions) -> crate::error::Result<Self>
+	where
+		R: Read,
+	{
+		let mut header_len = ATOM_HEADER_LEN;
+		let mut len = u64::from(reader.read_u32::<BigEndian>()?);
+
+		let mut ident = [0; 4];
+		reader.read_exact(&mut ident)?;
+
+		if &ident != b"ilst" {
+			err!(BadAtom("Expected an \"ilst\" atom"));
+		}
+
+		if len == 1 {
+			header_len += 8;
+			len = reader.read_u64::<BigEndian>()?;
+		}
+
+		let Some(content_len) = len.checked_sub(header_len) else {
+			err!(BadAtom("Found an invalid length (< 8)"));
+		};
+
+		let mut content = try_vec![0; content_len as usize];
+		reader.read_exact(&mut content)?;
+
+		let mut atom_reader = AtomReader::new(Cursor::new(content), parse_options.parsing_mode)?;
+		read::parse_ilst(&mut atom_reader, parse_options, content_len)
+	}
+
 	/// Get an item by its [`AtomIdent`]
 	///
 	/// # Examples
@@ -445,6 +522,94 @@ impl Ilst {
 		})
 	}
 
+	/// Returns the gapless playback information according to the `iTunSMPB` atom
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::mp4::{GaplessInfo, Ilst};
+	///
+	/// let mut ilst = Ilst::new();
+	/// assert_eq!(ilst.gapless_info(), None);
+	///
+	/// let gapless_info = GaplessInfo {
+	/// 	delay: 2112,
+	/// 	padding: 458,
+	/// 	valid_samples: 4_141_558,
+	/// };
+	/// ilst.set_gapless_info(gapless_info);
+	///
+	/// assert_eq!(ilst.gapless_info(), Some(gapless_info));
+	/// ```
+	pub fn gapless_info(&self) -> Option<GaplessInfo> {
+		self.get(&ITUNSMPB)
+			.into_iter()
+			.flat_map(Atom::data)
+			.find_map(|data| match data {
+				AtomData::UTF8(value) | AtomData::UTF16(value) => GaplessInfo::parse(value),
+				AtomData::Unknown { data, .. } => {
+					std::str::from_utf8(data).ok().and_then(GaplessInfo::parse)
+				},
+				_ => None,
+			})
+	}
+
+	/// Sets the gapless playback information
+	///
+	/// This is written to the `iTunSMPB` atom in the same format iTunes uses.
+	pub fn set_gapless_info(&mut self, gapless_info: GaplessInfo) {
+		self.replace_atom(Atom {
+			ident: ITUNSMPB,
+			data: AtomDataStorage::Single(AtomData::UTF8(gapless_info.to_itunsmpb())),
+		})
+	}
+
+	/// Writes a line-oriented listing of the atoms to `writer`
+	///
+	/// Atoms with multiple data entries print one line per entry. FOURCCs are shown with each byte
+	/// as a Latin-1 character (ex. `©nam`), and freeform identifiers as `----:<mean>:<name>`.
+	///
+	/// See [`PrettyPrintOptions`] for the format.
+	///
+	/// # Errors
+	///
+	/// * Writing to `writer` fails
+	pub fn pretty_print(
+		&self,
+		writer: &mut impl fmt::Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		let mut printer = PrettyPrinter::default();
+		for atom in &self.atoms {
+			let key = match atom.ident() {
+				AtomIdent::Fourcc(fourcc) => {
+					fourcc.iter().map(|&b| char::from(b)).collect::<String>()
+				},
+				AtomIdent::Freeform { mean, name } => format!("----:{mean}:{name}"),
+			};
+
+			for data in atom.data() {
+				let (ty, value) = match data {
+					AtomData::UTF8(text) => ("UTF8", Value::Text(Cow::Borrowed(text))),
+					AtomData::UTF16(text) => ("UTF16", Value::Text(Cow::Borrowed(text))),
+					AtomData::Picture(picture) => ("Picture", Value::Picture(picture)),
+					AtomData::SignedInteger(int) => {
+						("SignedInteger", Value::Text(Cow::Owned(int.to_string())))
+					},
+					AtomData::UnsignedInteger(int) => {
+						("UnsignedInteger", Value::Text(Cow::Owned(int.to_string())))
+					},
+					AtomData::Bool(b) => ("Bool", Value::Text(Cow::Owned(b.to_string()))),
+					AtomData::Unknown { data, .. } => ("Unknown", Value::Bytes(data.len())),
+				};
+
+				printer.push(key.clone(), ty, value);
+			}
+		}
+
+		printer.finish(writer, options)
+	}
+
 	// Extracts a u16 from an integer pair
 	fn extract_number(&self, fourcc: [u8; 4], expected_size: usize) -> Option<u16> {
 		if let Some(atom) = self.get(&AtomIdent::Fourcc(fourcc)) {
@@ -593,6 +758,27 @@ impl Accessor for Ilst {
 	fn remove_year(&mut self) {
 		let _ = self.remove(&AtomIdent::Fourcc(*b"Year"));
 	}
+
+	fn popularimeter(&self) -> Option<Popularimeter> {
+		if let Some(atom) = self.get(&AtomIdent::Fourcc(*b"rate")) {
+			if let Some(AtomData::UTF8(text)) = atom.data().next() {
+				return Popularimeter::from_text(text);
+			}
+		}
+
+		None
+	}
+
+	fn set_popularimeter(&mut self, value: Popularimeter) {
+		self.replace_atom(Atom::text(
+			AtomIdent::Fourcc(*b"rate"),
+			value.rating().to_string(),
+		));
+	}
+
+	fn remove_popularimeter(&mut self) {
+		let _ = self.remove(&AtomIdent::Fourcc(*b"rate"));
+	}
 }
 
 impl TagExt for Ilst {
@@ -637,6 +823,10 @@ impl TagExt for Ilst {
 		self.as_ref().dump_to(writer, write_options)
 	}
 
+	fn serialized_len(&self, _write_options: WriteOptions) -> std::result::Result<u64, Self::Err> {
+		self.as_ref().serialized_len()
+	}
+
 	fn clear(&mut self) {
 		self.atoms.clear();
 	}
@@ -667,15 +857,23 @@ impl SplitTag for Ilst {
 
 		self.atoms.retain_mut(|atom| {
 			let Atom { ident, data } = atom;
+
+			// A `covr` atom can hold multiple pictures
+			if data.is_pictures() {
+				let pictures = std::mem::replace(data, AtomDataStorage::Multiple(Vec::new()));
+				for picture in pictures {
+					if let AtomData::Picture(picture) = picture {
+						tag.pictures.push(picture);
+					}
+				}
+
+				return false; // Atom consumed
+			}
+
 			let value = match data.first_mut() {
 				AtomData::UTF8(text) | AtomData::UTF16(text) => {
 					ItemValue::Text(std::mem::take(text))
 				},
-				AtomData::Picture(picture) => {
-					tag.pictures
-						.push(std::mem::replace(picture, TOMBSTONE_PICTURE));
-					return false; // Atom consumed
-				},
 				AtomData::Bool(b) => {
 					let text = if *b { "1".to_owned() } else { "0".to_owned() };
 					ItemValue::Text(text)
@@ -785,7 +983,7 @@ impl MergeTag for SplitTagRemainder {
 			let key = item.item_key;
 
 			if let Ok(ident) = TryInto::<AtomIdent<'_>>::try_into(&key) {
-				let ItemValue::Text(text) = item.item_value else {
+				let (ItemValue::Text(text) | ItemValue::Locator(text)) = item.item_value else {
 					continue;
 				};
 
@@ -835,15 +1033,9 @@ impl MergeTag for SplitTagRemainder {
 			}
 		}
 
-		for mut picture in tag.pictures {
-			// Just for correctness, since we can't actually
-			// assign a picture type in this format
-			picture.pic_type = PictureType::Other;
-
-			merged.atoms.push(Atom {
-				ident: COVR,
-				data: AtomDataStorage::Single(AtomData::Picture(picture)),
-			})
+		// All pictures are stored in a single `covr` atom, in their original order
+		for picture in tag.pictures {
+			merged.insert_picture(picture);
 		}
 
 		create_int_pair(&mut merged, *b"trkn", tracks);
@@ -883,9 +1075,12 @@ mod tests {
 	use crate::mp4::ilst::atom::AtomDataStorage;
 	use crate::mp4::ilst::TITLE;
 	use crate::mp4::read::AtomReader;
-	use crate::mp4::{AdvisoryRating, Atom, AtomData, AtomIdent, DataType, Ilst, Mp4File};
+	use crate::mp4::{
+		AdvisoryRating, Atom, AtomData, AtomIdent, DataType, GaplessInfo, Ilst, Mp4File,
+	};
 	use crate::picture::{MimeType, Picture, PictureType};
 	use crate::prelude::*;
+	use crate::tag::items::Popularimeter;
 	use crate::tag::utils::test_utils;
 	use crate::tag::utils::test_utils::read_path;
 	use crate::tag::{ItemValue, Tag, TagItem, TagType};
@@ -1126,6 +1321,49 @@ mod tests {
 		assert_eq!(ilst.advisory_rating(), Some(AdvisoryRating::Explicit));
 	}
 
+	#[test_log::test]
+	fn gapless_info() {
+		fn atom(ident: [u8; 4], content: &[u8]) -> Vec<u8> {
+			let mut atom = ((content.len() + 8) as u32).to_be_bytes().to_vec();
+			atom.extend_from_slice(&ident);
+			atom.extend_from_slice(content);
+			atom
+		}
+
+		// A hand-built `----:com.apple.iTunes:iTunSMPB` atom, with lowercase hex and no trailing fields
+		let mut freeform = atom(*b"mean", b"\0\0\0\0com.apple.iTunes");
+		freeform.extend(atom(*b"name", b"\0\0\0\0iTunSMPB"));
+		freeform.extend(atom(
+			*b"data",
+			b"\0\0\0\x01\0\0\0\0 00000000 00000840 000001ca 00000000003f31f6",
+		));
+
+		let ilst = read_ilst_raw(
+			&atom(*b"----", &freeform),
+			ParseOptions::new().parsing_mode(ParsingMode::Strict),
+		);
+
+		let expected = GaplessInfo {
+			delay: 2112,
+			padding: 458,
+			valid_samples: 4_141_558,
+		};
+		assert_eq!(ilst.gapless_info(), Some(expected));
+
+		// Written back in the format iTunes uses
+		let mut ilst = Ilst::new();
+		ilst.set_gapless_info(expected);
+
+		let atom = ilst.get(&super::ITUNSMPB).unwrap();
+		assert_eq!(
+			atom.data().next(),
+			Some(&AtomData::UTF8(String::from(
+				" 00000000 00000840 000001CA 00000000003F31F6 00000000 00000000 00000000 00000000 \
+				 00000000 00000000 00000000 00000000"
+			)))
+		);
+	}
+
 	#[test_log::test]
 	fn trailing_padding() {
 		const ILST_START: usize = 97;
@@ -1474,6 +1712,109 @@ mod tests {
 		assert!(ilst.artist().is_some());
 	}
 
+	fn cover(mime_type: Option<MimeType>, data: &[u8]) -> Picture {
+		let mut content = data.to_vec();
+		content.resize(50, 0);
+		Picture::new_unchecked(PictureType::Other, mime_type, None, content)
+	}
+
+	// Collects the type indicators of all `data` atoms in the `covr` atom
+	fn covr_data_types(ilst_bytes: &[u8]) -> Vec<u32> {
+		let covr_start = ilst_bytes
+			.windows(4)
+			.position(|w| w == b"covr")
+			.expect("should have a covr atom");
+		let covr_len =
+			u32::from_be_bytes(ilst_bytes[covr_start - 4..covr_start].try_into().unwrap()) as usize;
+
+		let mut data_atoms = &ilst_bytes[covr_start + 4..covr_start - 4 + covr_len];
+		let mut data_types = Vec::new();
+		while !data_atoms.is_empty() {
+			let len = u32::from_be_bytes(data_atoms[..4].try_into().unwrap()) as usize;
+			assert_eq!(&data_atoms[4..8], b"data");
+			data_types.push(u32::from_be_bytes(data_atoms[8..12].try_into().unwrap()));
+			data_atoms = &data_atoms[len..];
+		}
+
+		data_types
+	}
+
+	#[test_log::test]
+	fn multiple_covers_roundtrip() {
+		const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0];
+		const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+		const BMP: &[u8] = b"BM";
+		const TIFF: &[u8] = &[b'I', b'I', b'*', 0x00];
+
+		let mut ilst = Ilst::new();
+		ilst.insert_picture(cover(Some(MimeType::Jpeg), JPEG));
+		// The MIME type is wrong, the type should be based on the actual data
+		ilst.insert_picture(cover(Some(MimeType::Jpeg), PNG));
+		ilst.insert_picture(cover(None, BMP));
+		// Not supported in MP4, should fall back to an implicit type
+		ilst.insert_picture(cover(Some(MimeType::Tiff), TIFF));
+		assert_eq!(ilst.len(), 1);
+
+		let mut writer = Vec::new();
+		ilst.dump_to(&mut writer, WriteOptions::new()).unwrap();
+
+		assert_eq!(covr_data_types(&writer), [13, 14, 27, 0]);
+
+		// Skip `ilst` header
+		let re_read = read_ilst_raw(&writer[8..], ParseOptions::new());
+		assert_eq!(re_read.len(), 1);
+
+		let pictures = re_read.pictures().unwrap().collect::<Vec<_>>();
+		assert_eq!(pictures.len(), 4);
+
+		let mime_types = pictures
+			.iter()
+			.map(|p| p.mime_type().cloned())
+			.collect::<Vec<_>>();
+		assert_eq!(
+			mime_types,
+			[
+				Some(MimeType::Jpeg),
+				Some(MimeType::Png),
+				Some(MimeType::Bmp),
+				// Detected from the data
+				Some(MimeType::Tiff),
+			]
+		);
+
+		for (picture, expected) in pictures.iter().zip([JPEG, PNG, BMP, TIFF]) {
+			assert!(picture.data().starts_with(expected));
+		}
+	}
+
+	#[test_log::test]
+	fn multiple_covers_tag_conversion() {
+		const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0];
+		const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+		let mut ilst = Ilst::new();
+		ilst.insert_picture(cover(Some(MimeType::Png), PNG));
+		ilst.insert_picture(cover(Some(MimeType::Jpeg), JPEG));
+
+		// All pictures should be exposed, in order
+		let tag: Tag = ilst.clone().into();
+		assert_eq!(tag.picture_count(), 2);
+		assert_eq!(tag.pictures()[0].mime_type(), Some(&MimeType::Png));
+		assert_eq!(tag.pictures()[1].mime_type(), Some(&MimeType::Jpeg));
+		assert!(tag
+			.pictures()
+			.iter()
+			.all(|p| p.pic_type() == PictureType::Other));
+
+		// And end up in a single `covr` atom
+		let mut tag_bytes = Vec::new();
+		tag.dump_to(&mut tag_bytes, WriteOptions::new()).unwrap();
+		assert_eq!(covr_data_types(&tag_bytes), [14, 13]);
+
+		let re_read = read_ilst_raw(&tag_bytes[8..], ParseOptions::new());
+		assert_eq!(re_read, ilst);
+	}
+
 	#[test_log::test]
 	fn gnre_conversion_case_1() {
 		// Case 1: 1 `gnre` atom present, no `©gen` present. `gnre` gets converted without issue.
@@ -1518,4 +1859,21 @@ mod tests {
 			}
 		);
 	}
+
+	#[test_log::test]
+	fn popularimeter_accessor() {
+		let mut ilst = Ilst::default();
+		assert_eq!(ilst.popularimeter(), None);
+
+		ilst.set_popularimeter(Popularimeter::new(String::from("foo@bar.com"), 60, 5));
+		assert_eq!(
+			ilst.get(&AtomIdent::Fourcc(*b"rate"))
+				.and_then(|atom| atom.data().next()),
+			Some(&AtomData::UTF8(String::from("60")))
+		);
+		assert_eq!(ilst.popularimeter().map(|p| p.rating()), Some(60));
+
+		ilst.remove_popularimeter();
+		assert_eq!(ilst.popularimeter(), None);
+	}
 }
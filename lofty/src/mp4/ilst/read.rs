@@ -194,6 +194,7 @@ where
 		// Most atoms we encounter are only going to have 1 value, so store them as such
 		if atom_data.len() == 1 {
 			let (flags, content) = atom_data.remove(0);
+			let flags = implicit_data_type(&atom_info.ident, flags);
 			let data = match interpret_atom_content(flags, content) {
 				Ok(data) => data,
 				Err(err) => return handle_error(err, parsing_mode),
@@ -209,6 +210,7 @@ where
 
 		let mut data = Vec::new();
 		for (flags, content) in atom_data {
+			let flags = implicit_data_type(&atom_info.ident, flags);
 			let value = match interpret_atom_content(flags, content) {
 				Ok(data) => data,
 				Err(err) => return handle_error(err, parsing_mode),
@@ -366,13 +368,11 @@ where
 	R: Read + Seek,
 {
 	if let Some(atom_data) = parse_data_inner(reader, parsing_mode, atom_info)? {
-		let mut data = Vec::new();
-
-		let len = atom_data.len();
+		let mut data = Vec::with_capacity(atom_data.len());
 		for (data_type, value) in atom_data {
 			let mime_type = match data_type {
-				// Type 0 is implicit
-				DataType::Reserved => None,
+				// Type 0 is implicit, try to detect the format
+				DataType::Reserved => Picture::mimetype_from_bin(&value).ok(),
 				// GIF is deprecated
 				DataType::Gif => Some(MimeType::Gif),
 				DataType::Jpeg => Some(MimeType::Jpeg),
@@ -384,41 +384,43 @@ where
 					}
 
 					log::warn!(
-						"Encountered \"covr\" atom with an unknown type of `{}`, discarding",
+						"Encountered \"covr\" data atom with an unknown type of `{}`, discarding",
 						Into::<u32>::into(data_type)
 					);
-					return Ok(());
+					continue;
 				},
 			};
 
-			let picture_data = AtomData::Picture(Picture {
+			data.push(AtomData::Picture(Picture {
 				pic_type: PictureType::Other,
 				mime_type,
 				description: None,
 				data: Cow::from(value),
-			});
-
-			if len == 1 {
-				tag.atoms.push(Atom {
-					ident: AtomIdent::Fourcc(*b"covr"),
-					data: AtomDataStorage::Single(picture_data),
-				});
-
-				return Ok(());
-			}
-
-			data.push(picture_data);
+				skipped_data_len: None,
+			}));
 		}
 
-		tag.atoms.push(Atom {
-			ident: AtomIdent::Fourcc(*b"covr"),
-			data: AtomDataStorage::Multiple(data),
-		});
+		if let Some(storage) = AtomDataStorage::from_vec(data) {
+			tag.atoms.push(Atom {
+				ident: AtomIdent::Fourcc(*b"covr"),
+				data: storage,
+			});
+		}
 	}
 
 	Ok(())
 }
 
+// iTunes stores the podcast URL and episode GUID without a type, despite them being UTF-8 text
+fn implicit_data_type(ident: &AtomIdent<'_>, flags: DataType) -> DataType {
+	match (ident, flags) {
+		(AtomIdent::Fourcc(fourcc), DataType::Reserved) if matches!(fourcc, b"purl" | b"egid") => {
+			DataType::Utf8
+		},
+		_ => flags,
+	}
+}
+
 fn interpret_atom_content(flags: DataType, content: Vec<u8>) -> Result<AtomData> {
 	// https://developer.apple.com/library/archive/documentation/QuickTime/QTFF/Metadata/Metadata.html#//apple_ref/doc/uid/TP40000939-CH1-SW35
 	Ok(match flags {
@@ -44,6 +44,10 @@ where
 
 		Ok(())
 	}
+
+	pub(crate) fn serialized_len(&mut self) -> Result<u64> {
+		super::write::ilst_len(&mut self.atoms)
+	}
 }
 
 impl Atom<'_> {
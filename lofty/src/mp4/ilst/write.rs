@@ -3,11 +3,11 @@ use super::r#ref::IlstRef;
 use crate::config::{ParseOptions, WriteOptions};
 use crate::error::{FileEncodingError, LoftyError, Result};
 use crate::file::FileType;
-use crate::macros::{decode_err, err, try_vec};
+use crate::macros::{err, try_vec};
 use crate::mp4::atom_info::{AtomIdent, AtomInfo, ATOM_HEADER_LEN, FOURCC_LEN};
 use crate::mp4::ilst::r#ref::AtomRef;
-use crate::mp4::read::{atom_tree, find_child_atom, meta_is_full, verify_mp4, AtomReader};
-use crate::mp4::write::{AtomWriter, AtomWriterCompanion, ContextualAtom};
+use crate::mp4::read::{atom_tree, find_child_atom, meta_is_full};
+use crate::mp4::write::{update_offsets, AtomWriter, AtomWriterCompanion, ContextualAtom};
 use crate::mp4::AtomData;
 use crate::picture::{MimeType, Picture};
 use crate::util::alloc::VecFallibleCapacity;
@@ -15,7 +15,7 @@ use crate::util::io::{FileLike, Length, Truncate};
 
 use std::io::{Cursor, Seek, SeekFrom, Write};
 
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{BigEndian, WriteBytesExt};
 
 // A "full" atom is a traditional length + identifier, followed by a version (1) and flags (3)
 const FULL_ATOM_SIZE: u64 = ATOM_HEADER_LEN + 4;
@@ -35,15 +35,8 @@ where
 {
 	log::debug!("Attempting to write `ilst` tag to file");
 
-	// Create a temporary `AtomReader`, just to verify that this is a valid MP4 file
-	let mut reader = AtomReader::new(file, ParseOptions::DEFAULT_PARSING_MODE)?;
-	verify_mp4(&mut reader)?;
-
-	// Now we can just read the entire file into memory
-	let file = reader.into_inner();
 	file.rewind()?;
-
-	let mut atom_writer = AtomWriter::new_from_file(file, ParseOptions::DEFAULT_PARSING_MODE)?;
+	let mut atom_writer = AtomWriter::new_for_moov(file, ParseOptions::DEFAULT_PARSING_MODE)?;
 
 	let Some(moov) = atom_writer.find_contextual_atom(*b"moov") else {
 		return Err(FileEncodingError::new(
@@ -398,10 +391,13 @@ where
 		return Ok((atom_size_difference, padding_size));
 	}
 
-	let Some(preferred_padding) = write_options.preferred_padding else {
+	let preferred_padding = write_options
+		.preferred_padding
+		.unwrap_or(WriteOptions::DEFAULT_PREFERRED_PADDING);
+	if preferred_padding == 0 {
 		log::trace!("Cannot avoid offset update, not padding atom");
 		return Ok((atom_size_difference, 0));
-	};
+	}
 
 	log::trace!(
 		"Cannot avoid offset update, padding atom with {} bytes",
@@ -427,116 +423,6 @@ where
 	Ok(())
 }
 
-fn update_offsets(
-	writer: &AtomWriter,
-	moov: &ContextualAtom,
-	difference: i64,
-	ilst_offset: u64,
-) -> Result<()> {
-	log::debug!("Checking for offset atoms to update");
-
-	let mut write_handle = writer.start_write();
-
-	// 32-bit offsets
-	for stco in moov.find_all_children(*b"stco", true) {
-		log::trace!("Found `stco` atom");
-
-		let stco_start = stco.start;
-		if stco.extended {
-			decode_err!(@BAIL Mp4, "Found an extended `stco` atom");
-		}
-
-		write_handle.seek(SeekFrom::Start(stco_start + ATOM_HEADER_LEN + 4))?;
-
-		let count = write_handle.read_u32::<BigEndian>()?;
-		for _ in 0..count {
-			let read_offset = write_handle.read_u32::<BigEndian>()?;
-			if u64::from(read_offset) < ilst_offset {
-				continue;
-			}
-			write_handle.seek(SeekFrom::Current(-4))?;
-			write_handle.write_u32::<BigEndian>((i64::from(read_offset) + difference) as u32)?;
-
-			log::trace!(
-				"Updated offset from {} to {}",
-				read_offset,
-				(i64::from(read_offset) + difference) as u32
-			);
-		}
-	}
-
-	// 64-bit offsets
-	for co64 in moov.find_all_children(*b"co64", true) {
-		log::trace!("Found `co64` atom");
-
-		let co64_start = co64.start;
-		if !co64.extended {
-			decode_err!(@BAIL Mp4, "Expected `co64` atom to be extended");
-		}
-
-		write_handle.seek(SeekFrom::Start(co64_start + ATOM_HEADER_LEN + 8 + 4))?;
-
-		let count = write_handle.read_u32::<BigEndian>()?;
-		for _ in 0..count {
-			let read_offset = write_handle.read_u64::<BigEndian>()?;
-			if read_offset < ilst_offset {
-				continue;
-			}
-
-			write_handle.seek(SeekFrom::Current(-8))?;
-			write_handle.write_u64::<BigEndian>((read_offset as i64 + difference) as u64)?;
-
-			log::trace!(
-				"Updated offset from {} to {}",
-				read_offset,
-				((read_offset as i64) + difference) as u64
-			);
-		}
-	}
-
-	let Some(moof) = writer.find_contextual_atom(*b"moof") else {
-		return Ok(());
-	};
-
-	log::trace!("Found `moof` atom, checking for `tfhd` atoms to update");
-
-	// 64-bit offsets
-	for tfhd in moof.find_all_children(*b"tfhd", true) {
-		log::trace!("Found `tfhd` atom");
-
-		let tfhd_start = tfhd.start;
-		if tfhd.extended {
-			decode_err!(@BAIL Mp4, "Found an extended `tfhd` atom");
-		}
-
-		// Skip atom header + version (1)
-		write_handle.seek(SeekFrom::Start(tfhd_start + ATOM_HEADER_LEN + 1))?;
-
-		let flags = write_handle.read_u24::<BigEndian>()?;
-		let base_data_offset = (flags & 0b1) != 0;
-
-		if base_data_offset {
-			let read_offset = write_handle.read_u64::<BigEndian>()?;
-			if read_offset < ilst_offset {
-				continue;
-			}
-
-			write_handle.seek(SeekFrom::Current(-8))?;
-			write_handle.write_u64::<BigEndian>((read_offset as i64 + difference) as u64)?;
-
-			log::trace!(
-				"Updated offset from {} to {}",
-				read_offset,
-				((read_offset as i64) + difference) as u64
-			);
-		}
-	}
-
-	drop(write_handle);
-
-	Ok(())
-}
-
 fn create_udta(ilst: &[u8]) -> Result<Vec<u8>> {
 	const UDTA_HEADER: [u8; 8] = [0, 0, 0, 0, b'u', b'd', b't', b'a'];
 
@@ -648,6 +534,59 @@ where
 	Ok(ilst_writer.into_contents())
 }
 
+/// Get the size of the atom that [`build_ilst`] would produce, without keeping it in memory
+pub(super) fn ilst_len<'a, I>(atoms: &mut dyn Iterator<Item = AtomRef<'a, I>>) -> Result<u64>
+where
+	I: IntoIterator<Item = &'a AtomData> + 'a,
+{
+	// Atoms that don't fit in 32 bits are written with an additional 64-bit extended size
+	fn atom_len(content_len: u64) -> u64 {
+		let size = ATOM_HEADER_LEN + content_len;
+		match u32::try_from(size) {
+			Ok(_) => size,
+			Err(_) => size + 8,
+		}
+	}
+
+	let mut peek = atoms.peekable();
+
+	if peek.peek().is_none() {
+		return Ok(0);
+	}
+
+	let mut ilst_content_len = 0;
+	for atom in peek {
+		let mut content_len = match atom.ident {
+			AtomIdent::Fourcc(_) => 0,
+			// The "----" identifier is followed by the `mean` and `name` atoms
+			AtomIdent::Freeform { mean, name } => {
+				FULL_ATOM_SIZE * 2 + mean.len() as u64 + name.len() as u64
+			},
+		};
+
+		for value in atom.data {
+			let data_len = match value {
+				AtomData::UTF8(text) | AtomData::UTF16(text) => text.len(),
+				AtomData::Picture(pic) => pic.data.len(),
+				AtomData::SignedInteger(_) => 4,
+				AtomData::UnsignedInteger(uint) => bytes_to_occupy_uint(*uint),
+				AtomData::Bool(_) => 1,
+				AtomData::Unknown { code, data } => {
+					verify_data_type(*code)?;
+					data.len()
+				},
+			};
+
+			// .... DATA (version = 0) (flags) (locale = 0000) (data)
+			content_len += atom_len(8 + data_len as u64);
+		}
+
+		ilst_content_len += atom_len(content_len);
+	}
+
+	Ok(atom_len(ilst_content_len))
+}
+
 fn write_freeform<W>(mean: &str, name: &str, writer: &mut W) -> Result<()>
 where
 	W: Write,
@@ -735,23 +674,35 @@ fn write_bool(b: bool, writer: &mut AtomWriterCompanion<'_>) -> Result<()> {
 }
 
 fn write_picture(picture: &Picture, writer: &mut AtomWriterCompanion<'_>) -> Result<()> {
-	match picture.mime_type {
+	picture.verify_has_data()?;
+
+	// Players may reject pictures with the wrong type, so prefer the format of the actual data
+	// over the MIME type
+	let mime_type = Picture::mimetype_from_bin(&picture.data)
+		.ok()
+		.or_else(|| picture.mime_type.clone());
+
+	let data_type = match mime_type {
 		// GIF is deprecated
-		Some(MimeType::Gif) => write_data(DataType::Gif, &picture.data, writer),
-		Some(MimeType::Jpeg) => write_data(DataType::Jpeg, &picture.data, writer),
-		Some(MimeType::Png) => write_data(DataType::Png, &picture.data, writer),
-		Some(MimeType::Bmp) => write_data(DataType::Bmp, &picture.data, writer),
+		Some(MimeType::Gif) => DataType::Gif,
+		Some(MimeType::Jpeg) => DataType::Jpeg,
+		Some(MimeType::Png) => DataType::Png,
+		Some(MimeType::Bmp) => DataType::Bmp,
 		// We'll assume implicit (0) was the intended type
-		None => write_data(DataType::Reserved, &picture.data, writer),
-		_ => Err(FileEncodingError::new(
-			FileType::Mp4,
-			"Attempted to write an unsupported picture format",
-		)
-		.into()),
-	}
+		None => DataType::Reserved,
+		Some(mime_type) => {
+			log::warn!(
+				"Attempted to write an unsupported picture format ({mime_type}), writing with an \
+				 implicit type"
+			);
+			DataType::Reserved
+		},
+	};
+
+	write_data(data_type, &picture.data, writer)
 }
 
-fn write_data(flags: DataType, data: &[u8], writer: &mut AtomWriterCompanion<'_>) -> Result<()> {
+fn verify_data_type(flags: DataType) -> Result<()> {
 	if u32::from(flags) > DataType::MAX {
 		return Err(FileEncodingError::new(
 			FileType::Mp4,
@@ -760,6 +711,12 @@ fn write_data(flags: DataType, data: &[u8], writer: &mut AtomWriterCompanion<'_>
 		.into());
 	}
 
+	Ok(())
+}
+
+fn write_data(flags: DataType, data: &[u8], writer: &mut AtomWriterCompanion<'_>) -> Result<()> {
+	verify_data_type(flags)?;
+
 	// .... DATA (version = 0) (flags) (locale = 0000) (data)
 	let size = FULL_ATOM_SIZE + 4 + data.len() as u64;
 
@@ -4,12 +4,16 @@
 //!
 //! The only supported tag format is [`Ilst`].
 mod atom_info;
+mod chapters;
 pub(crate) mod ilst;
 mod moov;
 mod properties;
-mod read;
+pub(crate) mod read;
 mod write;
 
+use crate::error::{LoftyError, Result};
+use crate::util::io::{FileLike, Length, Truncate};
+
 use lofty_attr::LoftyFile;
 
 // Exports
@@ -23,9 +27,11 @@ pub mod constants {
 
 pub use crate::mp4::properties::{AudioObjectType, Mp4Codec, Mp4Properties};
 pub use atom_info::AtomIdent;
+pub use chapters::Mp4Chapter;
 pub use ilst::advisory_rating::AdvisoryRating;
 pub use ilst::atom::{Atom, AtomData};
 pub use ilst::data_type::DataType;
+pub use ilst::gapless_info::GaplessInfo;
 pub use ilst::Ilst;
 
 pub(crate) use properties::SAMPLE_RATES;
@@ -41,6 +47,8 @@ pub struct Mp4File {
 	pub(crate) ilst_tag: Option<Ilst>,
 	/// The file's audio properties
 	pub(crate) properties: Mp4Properties,
+	/// The chapters from either `moov.udta.chpl` or a QuickTime chapter track
+	pub(crate) chapters: Vec<Mp4Chapter>,
 }
 
 impl Mp4File {
@@ -63,4 +71,109 @@ impl Mp4File {
 	pub fn ftyp(&self) -> &str {
 		self.ftyp.as_ref()
 	}
+
+	/// Returns the gapless playback information, if the file has an `iTunSMPB` atom
+	///
+	/// See [`Ilst::gapless_info`].
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::mp4::Mp4File;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut m4a_reader = std::io::Cursor::new(&[]);
+	/// let m4a_file = Mp4File::read_from(&mut m4a_reader, ParseOptions::new())?;
+	///
+	/// if let Some(gapless_info) = m4a_file.gapless_info() {
+	/// 	println!(
+	/// 		"Skip {} samples, play {}",
+	/// 		gapless_info.delay, gapless_info.valid_samples
+	/// 	);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn gapless_info(&self) -> Option<GaplessInfo> {
+		self.ilst_tag.as_ref().and_then(Ilst::gapless_info)
+	}
+
+	/// Returns the file's chapters
+	///
+	/// These are read from the Nero `chpl` atom if it exists, otherwise from the QuickTime chapter
+	/// track referenced by the audio track.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::mp4::Mp4File;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut m4b_reader = std::io::Cursor::new(&[]);
+	/// let m4b_file = Mp4File::read_from(&mut m4b_reader, ParseOptions::new())?;
+	///
+	/// for chapter in m4b_file.chapters() {
+	/// 	println!("{:?}: {}", chapter.start, chapter.title);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn chapters(&self) -> &[Mp4Chapter] {
+		&self.chapters
+	}
+
+	/// Write `chapters` to the file's `chpl` atom
+	///
+	/// This creates or replaces the `moov.udta.chpl` atom, the rest of the file is left untouched.
+	/// QuickTime chapter tracks are not modified, but are hidden by the `chpl` atom when reading.
+	///
+	/// An empty `chapters` removes the `chpl` atom. If the file has a QuickTime chapter track, it
+	/// will be used when the file is read again.
+	///
+	/// NOTE: A `chpl` atom can hold at most 255 chapters, and titles are cut to 255 bytes.
+	///
+	/// # Errors
+	///
+	/// * `file` is not a valid MP4 file
+	/// * `chapters` has more than 255 entries
+	/// * [`std::io::Error`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::mp4::{Mp4Chapter, Mp4File};
+	/// use std::fs::OpenOptions;
+	/// use std::io::{Seek, SeekFrom};
+	/// use std::time::Duration;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut file = OpenOptions::new().read(true).write(true).open("foo.m4b")?;
+	/// let mut m4b_file = Mp4File::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// let chapters = vec![
+	/// 	Mp4Chapter::new(Duration::ZERO, String::from("Prologue")),
+	/// 	Mp4Chapter::new(Duration::from_secs(300), String::from("Chapter 1")),
+	/// ];
+	///
+	/// file.seek(SeekFrom::Start(0))?;
+	/// m4b_file.set_chapters(&mut file, chapters)?;
+	///
+	/// assert_eq!(m4b_file.chapters().len(), 2);
+	/// # Ok(()) }
+	/// ```
+	pub fn set_chapters<F>(&mut self, file: &mut F, chapters: Vec<Mp4Chapter>) -> Result<()>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		chapters::write_chpl(file, &chapters)?;
+
+		self.chapters = chapters;
+		Ok(())
+	}
 }
@@ -1,18 +1,29 @@
 use super::atom_info::{AtomIdent, AtomInfo};
+use super::chapters::{parse_chpl, Mp4Chapter};
 use super::ilst::read::parse_ilst;
 use super::ilst::Ilst;
 use super::read::{find_child_atom, meta_is_full, skip_atom, AtomReader};
 use crate::config::ParseOptions;
 use crate::error::Result;
 use crate::macros::decode_err;
+use crate::tag::{TagLocation, TagPlacement, TagType};
+use crate::util::tag_locations;
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
 
 pub(crate) struct Moov {
 	// Represents the trak.mdia atom
 	pub(crate) traks: Vec<AtomInfo>,
 	// Represents a parsed moov.udta.meta.ilst
 	pub(crate) ilst: Option<Ilst>,
+	// Represents a parsed moov.udta.chpl
+	pub(crate) chapters: Option<Vec<Mp4Chapter>>,
+}
+
+#[derive(Default)]
+struct Udta {
+	ilst: Option<Ilst>,
+	chapters: Option<Vec<Mp4Chapter>>,
 }
 
 impl Moov {
@@ -40,6 +51,7 @@ impl Moov {
 	{
 		let mut traks = Vec::new();
 		let mut ilst = None;
+		let mut chapters = None;
 
 		while let Ok(Some(atom)) = reader.next() {
 			if let AtomIdent::Fourcc(fourcc) = atom.ident {
@@ -50,12 +62,23 @@ impl Moov {
 							find_child_atom(reader, atom.len, *b"mdia", parse_options.parsing_mode)?
 						{
 							skip_atom(reader, mdia.extended, mdia.len)?;
+
+							// Skip anything after the `mdia` atom
+							let trak_end = atom.start + atom.len;
+							reader.seek(SeekFrom::Current(
+								(trak_end - (mdia.start + mdia.len)) as i64,
+							))?;
+
 							traks.push(mdia);
 						}
 					},
 					b"udta" if parse_options.read_tags => {
-						let ilst_parsed = ilst_from_udta(reader, parse_options, atom.len - 8)?;
-						if let Some(ilst_parsed) = ilst_parsed {
+						let udta = parse_udta(reader, parse_options, atom.len)?;
+						if udta.chapters.is_some() {
+							chapters = udta.chapters;
+						}
+
+						if let Some(ilst_parsed) = udta.ilst {
 							let Some(mut existing_ilst) = ilst else {
 								ilst = Some(ilst_parsed);
 								continue;
@@ -78,50 +101,65 @@ impl Moov {
 			skip_atom(reader, atom.extended, atom.len)?
 		}
 
-		Ok(Self { traks, ilst })
+		Ok(Self {
+			traks,
+			ilst,
+			chapters,
+		})
 	}
 }
 
-fn ilst_from_udta<R>(
-	reader: &mut AtomReader<R>,
-	parse_options: ParseOptions,
-	len: u64,
-) -> Result<Option<Ilst>>
+/// Parse the children of a `udta` atom
+///
+/// This expects the reader to be at the start of the atom's content.
+fn parse_udta<R>(reader: &mut AtomReader<R>, parse_options: ParseOptions, len: u64) -> Result<Udta>
 where
 	R: Read + Seek,
 {
-	let mut read = 8;
-	let mut found_meta = false;
-	let mut meta_atom_size = 0;
+	let mut udta = Udta::default();
 
+	let mut read = 8;
 	while read < len {
 		let Some(atom) = reader.next()? else {
 			break;
 		};
 
-		if atom.ident == AtomIdent::Fourcc(*b"meta") {
-			found_meta = true;
-			meta_atom_size = atom.len;
-			break;
-		}
-
 		read += atom.len;
-		skip_atom(reader, atom.extended, atom.len)?;
-	}
 
-	if !found_meta {
-		return Ok(None);
+		match atom.ident {
+			AtomIdent::Fourcc(ref fourcc) if fourcc == b"meta" => {
+				let content_start = reader.stream_position()?;
+
+				udta.ilst = ilst_from_meta(reader, parse_options, atom.len)?;
+
+				// Skip anything after the `ilst` atom
+				let read = reader.stream_position()? - content_start;
+				let remaining = (atom.len - atom.header_size()).saturating_sub(read);
+				reader.seek(SeekFrom::Current(remaining as i64))?;
+			},
+			AtomIdent::Fourcc(ref fourcc) if fourcc == b"chpl" => {
+				udta.chapters = Some(parse_chpl(reader, atom.len - atom.header_size())?);
+			},
+			_ => skip_atom(reader, atom.extended, atom.len)?,
+		}
 	}
 
+	Ok(udta)
+}
+
+fn ilst_from_meta<R>(
+	reader: &mut AtomReader<R>,
+	parse_options: ParseOptions,
+	meta_atom_size: u64,
+) -> Result<Option<Ilst>>
+where
+	R: Read + Seek,
+{
 	// It's possible for the `meta` atom to be non-full,
 	// so we have to check for that case
 	let full_meta_atom = meta_is_full(reader)?;
 
-	if full_meta_atom {
-		read = 12;
-	} else {
-		read = 8;
-	}
+	let mut read = if full_meta_atom { 12 } else { 8 };
 
 	let mut found_ilst = false;
 	let mut ilst_atom_size = 0;
@@ -134,6 +172,13 @@ where
 		if atom.ident == AtomIdent::Fourcc(*b"ilst") {
 			found_ilst = true;
 			ilst_atom_size = atom.len;
+
+			tag_locations::record(TagLocation::new(
+				TagType::Mp4Ilst,
+				atom.start,
+				atom.len,
+				TagPlacement::Embedded,
+			));
 			break;
 		}
 
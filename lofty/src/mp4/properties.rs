@@ -3,7 +3,7 @@ use super::read::{find_child_atom, skip_atom, AtomReader};
 use crate::config::ParsingMode;
 use crate::error::{LoftyError, Result};
 use crate::macros::{decode_err, err, try_vec};
-use crate::properties::FileProperties;
+use crate::properties::{ChannelMask, FileProperties};
 use crate::util::alloc::VecFallibleCapacity;
 use crate::util::math::RoundedDivision;
 
@@ -15,6 +15,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 /// An MP4 file's audio codec
 #[allow(missing_docs)]
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Mp4Codec {
 	#[default]
@@ -27,6 +28,7 @@ pub enum Mp4Codec {
 
 #[allow(missing_docs)]
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[rustfmt::skip]
 #[non_exhaustive]
 pub enum AudioObjectType {
@@ -133,6 +135,7 @@ impl TryFrom<u8> for AudioObjectType {
 
 /// An MP4 file's audio properties
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct Mp4Properties {
 	pub(crate) codec: Mp4Codec,
@@ -143,6 +146,7 @@ pub struct Mp4Properties {
 	pub(crate) sample_rate: u32,
 	pub(crate) bit_depth: Option<u8>,
 	pub(crate) channels: u8,
+	pub(crate) channel_mask: Option<ChannelMask>,
 	pub(crate) drm_protected: bool,
 }
 
@@ -155,7 +159,7 @@ impl From<Mp4Properties> for FileProperties {
 			sample_rate: Some(input.sample_rate),
 			bit_depth: input.bit_depth,
 			channels: Some(input.channels),
-			channel_mask: None,
+			channel_mask: input.channel_mask,
 		}
 	}
 }
@@ -191,6 +195,14 @@ impl Mp4Properties {
 		self.channels
 	}
 
+	/// Channel mask
+	///
+	/// This is taken from the `chan` atom if it exists, otherwise it is derived from the codec's
+	/// channel configuration.
+	pub fn channel_mask(&self) -> Option<ChannelMask> {
+		self.channel_mask
+	}
+
 	/// Audio codec
 	pub fn codec(&self) -> &Mp4Codec {
 		&self.codec
@@ -328,6 +340,15 @@ impl Mdhd {
 			duration,
 		})
 	}
+
+	fn duration(&self) -> Duration {
+		if self.timescale == 0 {
+			return Duration::ZERO;
+		}
+
+		let duration_millis = (self.duration * 1000).div_round(u64::from(self.timescale));
+		Duration::from_millis(duration_millis)
+	}
 }
 
 // TODO: Estimate duration from stts?
@@ -421,7 +442,11 @@ impl Minf {
 	}
 }
 
-fn read_stsd<R>(reader: &mut AtomReader<R>, properties: &mut Mp4Properties) -> Result<()>
+fn read_stsd<R>(
+	reader: &mut AtomReader<R>,
+	properties: &mut Mp4Properties,
+	parse_mode: ParsingMode,
+) -> Result<()>
 where
 	R: Read + Seek,
 {
@@ -440,6 +465,8 @@ where
 			err!(BadAtom("Expected fourcc atom in `stsd` atom"))
 		};
 
+		let entry_start = reader.stream_position()?;
+
 		match fourcc {
 			b"mp4a" => mp4a_properties(reader, properties)?,
 			b"alac" => alac_properties(reader, properties)?,
@@ -464,6 +491,16 @@ where
 			},
 		}
 
+		// A `chan` atom can follow the codec specific atoms, and takes precedence over
+		// their channel configuration
+		reader.seek(SeekFrom::Start(entry_start))?;
+		let entry_len = (atom.start + atom.len).saturating_sub(entry_start);
+		match read_chan(reader, entry_len, parse_mode) {
+			Ok(Some(channel_mask)) => properties.channel_mask = Some(channel_mask),
+			Ok(None) => {},
+			Err(e) => log::warn!("Failed to read `chan` atom: {e}"),
+		}
+
 		// We only want to read the properties of the first stream
 		// that we can actually recognize
 		break;
@@ -472,6 +509,17 @@ where
 	Ok(())
 }
 
+/// Read the duration of the first audio track from its `mdhd` atom
+pub(super) fn read_duration<R>(reader: &mut AtomReader<R>, traks: &[AtomInfo]) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	let AudioTrak { mdhd, .. } = find_audio_trak(reader, traks)?;
+
+	reader.seek(SeekFrom::Start(mdhd.start + 8))?;
+	Ok(Mdhd::parse(reader)?.duration())
+}
+
 pub(super) fn read_properties<R>(
 	reader: &mut AtomReader<R>,
 	traks: &[AtomInfo],
@@ -485,18 +533,18 @@ where
 	let AudioTrak { mdhd, minf } = find_audio_trak(reader, traks)?;
 
 	reader.seek(SeekFrom::Start(mdhd.start + 8))?;
-	let Mdhd {
-		timescale,
-		duration,
-	} = Mdhd::parse(reader)?;
+	let mdhd = Mdhd::parse(reader)?;
 
 	// We create the properties here, since it is possible the other information isn't available
-	let mut properties = Mp4Properties::default();
+	let mut properties = Mp4Properties {
+		duration: mdhd.duration(),
+		..Mp4Properties::default()
+	};
 
-	if timescale > 0 {
-		let duration_millis = (duration * 1000).div_round(u64::from(timescale));
-		properties.duration = Duration::from_millis(duration_millis);
-	}
+	let Mdhd {
+		timescale,
+		duration,
+	} = mdhd;
 
 	// We need an `mdhd` atom at the bare minimum, everything else can be optional.
 	let Some(minf_info) = minf else {
@@ -511,7 +559,7 @@ where
 	// `stsd` contains the majority of the audio properties
 	let mut cursor = Cursor::new(&*stsd_data);
 	let mut stsd_reader = AtomReader::new(&mut cursor, parse_mode)?;
-	read_stsd(&mut stsd_reader, &mut properties)?;
+	read_stsd(&mut stsd_reader, &mut properties, parse_mode)?;
 
 	// We do the mdat check up here, so we have access to the entire file
 	if duration > 0 {
@@ -703,6 +751,7 @@ where
 				// the Audio Lossless Coding reference software
 				if channel_conf > 0 {
 					properties.channels = channel_conf;
+					properties.channel_mask = ChannelMask::from_mp4_channels(channel_conf);
 				}
 
 				// We just check for ALS here, might extend it for more codes eventually
@@ -834,19 +883,150 @@ where
 		return Ok(());
 	}
 
-	let stream_info_block = crate::flac::block::Block::read(stsd, |_| true)?;
+	let stream_info_block =
+		crate::flac::block::Block::read(stsd, ParsingMode::Strict, |_, _| Ok(true))?;
 	let flac_properties =
 		crate::flac::properties::read_properties(&mut &stream_info_block.content[..], 0, 0)?;
 
 	properties.sample_rate = flac_properties.sample_rate;
 	properties.bit_depth = Some(flac_properties.bit_depth);
 	properties.channels = flac_properties.channels;
+	properties.channel_mask = flac_properties.channel_mask;
 
 	// Bitrate values are calculated later...
 
 	Ok(())
 }
 
+/// Read the channel layout from a `chan` atom within an audio sample entry
+///
+/// This assumes the reader is at the start of the sample entry's content.
+///
+/// See <https://developer.apple.com/documentation/coreaudiotypes/audiochannellayout>
+fn read_chan<R>(
+	reader: &mut AtomReader<R>,
+	entry_len: u64,
+	parse_mode: ParsingMode,
+) -> Result<Option<ChannelMask>>
+where
+	R: Read + Seek,
+{
+	const USE_CHANNEL_DESCRIPTIONS: u32 = 0;
+	const USE_CHANNEL_BITMAP: u32 = 1 << 16;
+
+	// Skipping 8 bytes
+	// Reserved (6)
+	// Data reference index (2)
+	reader.seek(SeekFrom::Current(8))?;
+
+	// The size of the sound sample description depends on its version
+	let description_len = match reader.read_u16()? {
+		0 => 28,
+		1 => 44,
+		2 => 64,
+		_ => return Ok(None),
+	};
+
+	reader.seek(SeekFrom::Current(description_len - 10))?;
+
+	let Some(chan) = find_child_atom(
+		reader,
+		entry_len.saturating_sub(description_len as u64),
+		*b"chan",
+		parse_mode,
+	)?
+	else {
+		return Ok(None);
+	};
+
+	if chan.len < 20 {
+		return Ok(None);
+	}
+
+	// Skipping 4 bytes
+	// Version (1)
+	// Flags (3)
+	reader.seek(SeekFrom::Current(4))?;
+
+	let layout_tag = reader.read_u32()?;
+	let bitmap = reader.read_u32()?;
+
+	match layout_tag {
+		USE_CHANNEL_BITMAP => Ok(Some(ChannelMask(bitmap))),
+		USE_CHANNEL_DESCRIPTIONS => {
+			let description_count = reader.read_u32()?;
+
+			let mut channel_mask = ChannelMask::default();
+			for _ in 0..description_count {
+				let label = reader.read_u32()?;
+
+				// Skipping 16 bytes
+				// Flags (4)
+				// Coordinates (12)
+				reader.seek(SeekFrom::Current(16))?;
+
+				channel_mask = channel_mask
+					| match label {
+						// The first 18 labels line up with the channel bits
+						1..=18 => ChannelMask(1 << (label - 1)),
+						// Mono
+						42 => ChannelMask::mono(),
+						_ => return Ok(None),
+					};
+			}
+
+			Ok(Some(channel_mask))
+		},
+		_ => Ok(channel_mask_from_layout_tag(layout_tag)),
+	}
+}
+
+/// Map the common `AudioChannelLayoutTag`s to a channel mask
+fn channel_mask_from_layout_tag(layout_tag: u32) -> Option<ChannelMask> {
+	// The lower 16 bits are the channel count
+	let channels = layout_tag & 0xFFFF;
+
+	let channel_mask = match (layout_tag >> 16, channels) {
+		// Mono
+		(100, 1) => ChannelMask::mono(),
+		// Stereo, StereoHeadphones, MatrixStereo
+		(101..=103, 2) => ChannelMask::stereo(),
+		// Quadraphonic
+		(108, 4) => {
+			ChannelMask::FRONT_LEFT
+				| ChannelMask::FRONT_RIGHT
+				| ChannelMask::BACK_LEFT
+				| ChannelMask::BACK_RIGHT
+		},
+		// MPEG_3_0_A, MPEG_3_0_B
+		(113 | 114, 3) => ChannelMask::linear_surround(),
+		// MPEG_4_0_A, MPEG_4_0_B
+		(115 | 116, 4) => ChannelMask::linear_surround() | ChannelMask::BACK_CENTER,
+		// MPEG_5_0_A - MPEG_5_0_D
+		(117..=120, 5) => {
+			ChannelMask::linear_surround() | ChannelMask::BACK_LEFT | ChannelMask::BACK_RIGHT
+		},
+		// MPEG_5_1_A - MPEG_5_1_D
+		(121..=124, 6) => ChannelMask::five_point_one(),
+		// MPEG_6_1_A
+		(125, 7) => ChannelMask::five_point_one() | ChannelMask::BACK_CENTER,
+		// MPEG_7_1_A
+		(126, 8) => {
+			ChannelMask::five_point_one()
+				| ChannelMask::FRONT_LEFT_OF_CENTER
+				| ChannelMask::FRONT_RIGHT_OF_CENTER
+		},
+		// MPEG_7_1_C
+		(128, 8) => ChannelMask::seven_point_one(),
+		_ => {
+			log::debug!("Unsupported channel layout tag: {layout_tag:#010X}");
+			return None;
+		},
+	};
+
+	Some(channel_mask)
+}
+
 // Used to calculate the bitrate, when it isn't readily available to us
 fn mdat_length<R>(reader: &mut AtomReader<R>) -> Result<u64>
 where
@@ -887,3 +1067,105 @@ impl Descriptor {
 		Ok(Descriptor { tag, _size: size })
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{read_stsd, Mp4Properties};
+	use crate::config::ParsingMode;
+	use crate::mp4::read::AtomReader;
+	use crate::properties::ChannelMask;
+
+	use std::io::Cursor;
+
+	// An `stsd` atom's content, with a single `mp4a` sample entry containing the given `chan` content
+	fn stsd_with_chan(chan: &[u8]) -> Vec<u8> {
+		let mut sample_entry = Vec::new();
+		// Reserved (6), data reference index (2), version (2), revision level (2), vendor (4)
+		sample_entry.extend([0; 16]);
+		// Channels
+		sample_entry.extend(6_u16.to_be_bytes());
+		// Sample size (2), compression ID (2)
+		sample_entry.extend([0, 16, 0, 0]);
+		// Sample rate (16.16), reserved (2)
+		sample_entry.extend(48000_u32.to_be_bytes());
+		sample_entry.extend([0; 2]);
+
+		sample_entry.extend((chan.len() as u32 + 8).to_be_bytes());
+		sample_entry.extend(b"chan");
+		sample_entry.extend(chan);
+
+		let mut stsd = Vec::new();
+		// Version and flags
+		stsd.extend([0; 4]);
+		// Number of sample entries
+		stsd.extend(1_u32.to_be_bytes());
+		stsd.extend((sample_entry.len() as u32 + 8).to_be_bytes());
+		stsd.extend(b"mp4a");
+		stsd.extend(sample_entry);
+
+		stsd
+	}
+
+	fn read_channel_mask(stsd: &[u8]) -> Option<ChannelMask> {
+		let mut cursor = Cursor::new(stsd);
+		let mut reader = AtomReader::new(&mut cursor, ParsingMode::Strict).unwrap();
+
+		let mut properties = Mp4Properties::default();
+		read_stsd(&mut reader, &mut properties, ParsingMode::Strict).unwrap();
+
+		assert_eq!(properties.channels, 6);
+		properties.channel_mask
+	}
+
+	#[test_log::test]
+	fn chan_layout_tag() {
+		// kAudioChannelLayoutTag_MPEG_5_1_A
+		let mut chan = vec![0; 4];
+		chan.extend(((121_u32 << 16) | 6).to_be_bytes());
+		chan.extend([0; 8]);
+
+		assert_eq!(
+			read_channel_mask(&stsd_with_chan(&chan)),
+			Some(ChannelMask::five_point_one())
+		);
+	}
+
+	#[test_log::test]
+	fn chan_bitmap() {
+		// kAudioChannelLayoutTag_UseChannelBitmap, 6.0 (no LFE)
+		let bitmap = ChannelMask::linear_surround()
+			| ChannelMask::BACK_LEFT
+			| ChannelMask::BACK_RIGHT
+			| ChannelMask::BACK_CENTER;
+
+		let mut chan = vec![0; 4];
+		chan.extend((1_u32 << 16).to_be_bytes());
+		chan.extend(bitmap.bits().to_be_bytes());
+		chan.extend([0; 4]);
+
+		assert_eq!(read_channel_mask(&stsd_with_chan(&chan)), Some(bitmap));
+	}
+
+	#[test_log::test]
+	fn chan_descriptions() {
+		// kAudioChannelLayoutTag_UseChannelDescriptions
+		let mut chan = vec![0; 4];
+		chan.extend(0_u32.to_be_bytes());
+		chan.extend([0; 4]);
+		chan.extend(2_u32.to_be_bytes());
+		// Left, Right
+		for label in [1_u32, 2] {
+			chan.extend(label.to_be_bytes());
+			chan.extend([0; 16]);
+		}
+
+		assert_eq!(
+			read_channel_mask(&stsd_with_chan(&chan)),
+			Some(ChannelMask::stereo())
+		);
+
+		// An unknown label means we can't build a mask
+		chan[16..20].copy_from_slice(&200_u32.to_be_bytes());
+		assert_eq!(read_channel_mask(&stsd_with_chan(&chan)), None);
+	}
+}
@@ -1,6 +1,7 @@
 mod atom_reader;
 
 use super::atom_info::{AtomIdent, AtomInfo};
+use super::chapters::read_quicktime_chapters;
 use super::moov::Moov;
 use super::properties::Mp4Properties;
 use super::Mp4File;
@@ -11,6 +12,7 @@ use crate::util::io::SeekStreamLen;
 use crate::util::text::utf8_decode_str;
 
 use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
 
 use byteorder::{BigEndian, ReadBytesExt};
 
@@ -65,16 +67,38 @@ where
 
 	let moov = Moov::parse(&mut reader, parse_options)?;
 
+	// `chpl` takes precedence over a QuickTime chapter track
+	let chapters = match moov.chapters {
+		Some(chapters) => chapters,
+		None if parse_options.read_tags => {
+			// The chapter track samples can be anywhere in the file
+			reader.reset_bounds(0, file_length);
+			match read_quicktime_chapters(&mut reader, &moov_info, parse_options.parsing_mode) {
+				Ok(chapters) => chapters,
+				Err(e) if parse_options.parsing_mode == ParsingMode::Strict => return Err(e),
+				Err(e) => {
+					log::warn!("Failed to read QuickTime chapters: {e}");
+					Vec::new()
+				},
+			}
+		},
+		None => Vec::new(),
+	};
+
 	Ok(Mp4File {
 		ftyp,
 		ilst_tag: moov.ilst,
+		chapters,
 		properties: if parse_options.read_properties {
 			// Remove the length restriction
 			reader.reset_bounds(0, file_length);
-			super::properties::read_properties(
-				&mut reader,
-				&moov.traks,
-				file_length,
+			crate::properties::or_incomplete(
+				super::properties::read_properties(
+					&mut reader,
+					&moov.traks,
+					file_length,
+					parse_options.parsing_mode,
+				),
 				parse_options.parsing_mode,
 			)?
 		} else {
@@ -83,6 +107,31 @@ where
 	})
 }
 
+/// Read the duration of an MP4 file from the `mdhd` atom of its audio track
+///
+/// Only the `trak` atoms are visited, `udta` (and with it `ilst`) is skipped entirely.
+pub(crate) fn read_duration<R>(data: &mut R, parse_options: ParseOptions) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	let mut reader = AtomReader::new(data, parse_options.parsing_mode)?;
+	let file_length = reader.stream_len_hack()?;
+
+	verify_mp4(&mut reader)?;
+
+	let moov_info = Moov::find(&mut reader)?;
+	reader.reset_bounds(moov_info.start + 8, moov_info.len - 8);
+
+	let moov = Moov::parse(&mut reader, parse_options)?;
+
+	// Remove the length restriction
+	reader.reset_bounds(0, file_length);
+	crate::properties::or_incomplete(
+		super::properties::read_duration(&mut reader, &moov.traks),
+		parse_options.parsing_mode,
+	)
+}
+
 /// Seeks the reader to the end of the atom
 ///
 /// This should be used immediately after [`AtomInfo::read`] to skip an unwanted atom.
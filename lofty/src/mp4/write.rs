@@ -1,15 +1,15 @@
 use crate::config::ParsingMode;
 use crate::error::{LoftyError, Result};
 use crate::io::{FileLike, Length, Truncate};
-use crate::macros::err;
-use crate::mp4::atom_info::{AtomIdent, AtomInfo, IDENTIFIER_LEN};
-use crate::mp4::read::{meta_is_full, skip_atom};
+use crate::macros::{decode_err, err};
+use crate::mp4::atom_info::{AtomIdent, AtomInfo, ATOM_HEADER_LEN, IDENTIFIER_LEN};
+use crate::mp4::read::{meta_is_full, skip_atom, verify_mp4, AtomReader};
 
 use std::cell::{RefCell, RefMut};
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::ops::RangeBounds;
 
-use byteorder::{BigEndian, WriteBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
 /// A wrapper around [`AtomInfo`] that allows us to track all of the children of containers we deem important
 #[derive(Debug)]
@@ -106,9 +106,13 @@ impl ContextualAtom {
 /// being parsed recursively. We are then able to use this information to find atoms nested deeply within the file.
 ///
 /// Atoms that are not "important" containers are simply parsed at the top level, with all children being skipped.
+///
+/// The contents don't necessarily start at the beginning of the file, see [`AtomWriter::new_for_moov`].
+/// All atom positions are relative to `file_start`.
 pub(super) struct AtomWriter {
 	contents: RefCell<Cursor<Vec<u8>>>,
 	atoms: Vec<ContextualAtom>,
+	file_start: u64,
 }
 
 impl AtomWriter {
@@ -119,18 +123,65 @@ impl AtomWriter {
 		Self {
 			contents: RefCell::new(Cursor::new(content)),
 			atoms: Vec::new(),
+			file_start: 0,
+		}
+	}
+
+	/// Create a new [`AtomWriter`] for modifying the `moov` atom of an MP4 file
+	///
+	/// This verifies that `file` is an MP4 file. If no media data (`mdat` or `moof`) follows the `moov`
+	/// atom, nothing before it will ever need to move, so only the file from the start of `moov` is read.
+	/// Otherwise, this is the same as [`AtomWriter::new_from_file`].
+	pub(super) fn new_for_moov<F>(file: &mut F, parse_mode: ParsingMode) -> Result<Self>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		let mut reader = AtomReader::new(file, parse_mode)?;
+		verify_mp4(&mut reader)?;
+
+		let mut moov_start = None;
+		while let Some(atom) = reader.next()? {
+			match atom.ident {
+				AtomIdent::Fourcc(ident) if ident == *b"moov" && moov_start.is_none() => {
+					moov_start = Some(atom.start);
+				},
+				AtomIdent::Fourcc(ident)
+					if moov_start.is_some() && (ident == *b"mdat" || ident == *b"moof") =>
+				{
+					log::trace!("Found media data after `moov`, reading the entire file");
+					moov_start = None;
+					break;
+				},
+				_ => {},
+			}
+
+			skip_atom(&mut reader, atom.extended, atom.len)?;
+		}
+
+		let start = moov_start.unwrap_or(0);
+		if start != 0 {
+			log::debug!("No media data follows `moov`, only reading from offset {start}");
 		}
+
+		let file = reader.into_inner();
+		file.seek(SeekFrom::Start(start))?;
+
+		Self::new_from_file(file, parse_mode)
 	}
 
 	/// Create a new [`AtomWriter`]
 	///
-	/// This will read the entire file into memory, and parse its atoms.
+	/// This will read the file from its current position into memory, and parse its atoms.
 	pub(super) fn new_from_file<F>(file: &mut F, parse_mode: ParsingMode) -> Result<Self>
 	where
 		F: FileLike,
 		LoftyError: From<<F as Truncate>::Error>,
 		LoftyError: From<<F as Length>::Error>,
 	{
+		let file_start = file.stream_position()?;
+
 		let mut contents = Cursor::new(Vec::new());
 		file.read_to_end(contents.get_mut())?;
 
@@ -145,6 +196,7 @@ impl AtomWriter {
 		Ok(Self {
 			contents: RefCell::new(contents),
 			atoms,
+			file_start,
 		})
 	}
 
@@ -170,8 +222,9 @@ impl AtomWriter {
 		LoftyError: From<<F as Truncate>::Error>,
 		LoftyError: From<<F as Length>::Error>,
 	{
-		file.rewind()?;
-		file.truncate(0)?;
+		// Anything before `file_start` was never read, and is left untouched
+		file.seek(SeekFrom::Start(self.file_start))?;
+		file.truncate(self.file_start)?;
 		file.write_all(self.contents.borrow().get_ref())?;
 
 		Ok(())
@@ -310,3 +363,111 @@ impl<'a> Iterator for AtomFindAll<std::slice::Iter<'a, ContextualAtom>> {
 		}
 	}
 }
+
+/// Shift all chunk offsets at or after `offset` by `difference`
+///
+/// This needs to be called whenever data is inserted into or removed from the file before an `mdat` atom.
+///
+/// `offset` is relative to the start of the writer's contents, while the chunk offsets are relative to
+/// the start of the file.
+pub(super) fn update_offsets(
+	writer: &AtomWriter,
+	moov: &ContextualAtom,
+	difference: i64,
+	offset: u64,
+) -> Result<()> {
+	log::debug!("Checking for offset atoms to update");
+
+	let offset = writer.file_start + offset;
+
+	let mut write_handle = writer.start_write();
+
+	// 32-bit offsets
+	for stco in moov.find_all_children(*b"stco", true) {
+		log::trace!("Found `stco` atom");
+
+		write_handle.seek(SeekFrom::Start(stco.start + stco.header_size() + 4))?;
+
+		let count = write_handle.read_u32::<BigEndian>()?;
+		for _ in 0..count {
+			let read_offset = write_handle.read_u32::<BigEndian>()?;
+			if u64::from(read_offset) < offset {
+				continue;
+			}
+			write_handle.seek(SeekFrom::Current(-4))?;
+			write_handle.write_u32::<BigEndian>((i64::from(read_offset) + difference) as u32)?;
+
+			log::trace!(
+				"Updated offset from {} to {}",
+				read_offset,
+				(i64::from(read_offset) + difference) as u32
+			);
+		}
+	}
+
+	// 64-bit offsets
+	for co64 in moov.find_all_children(*b"co64", true) {
+		log::trace!("Found `co64` atom");
+
+		write_handle.seek(SeekFrom::Start(co64.start + co64.header_size() + 4))?;
+
+		let count = write_handle.read_u32::<BigEndian>()?;
+		for _ in 0..count {
+			let read_offset = write_handle.read_u64::<BigEndian>()?;
+			if read_offset < offset {
+				continue;
+			}
+
+			write_handle.seek(SeekFrom::Current(-8))?;
+			write_handle.write_u64::<BigEndian>((read_offset as i64 + difference) as u64)?;
+
+			log::trace!(
+				"Updated offset from {} to {}",
+				read_offset,
+				((read_offset as i64) + difference) as u64
+			);
+		}
+	}
+
+	let Some(moof) = writer.find_contextual_atom(*b"moof") else {
+		return Ok(());
+	};
+
+	log::trace!("Found `moof` atom, checking for `tfhd` atoms to update");
+
+	// 64-bit offsets
+	for tfhd in moof.find_all_children(*b"tfhd", true) {
+		log::trace!("Found `tfhd` atom");
+
+		let tfhd_start = tfhd.start;
+		if tfhd.extended {
+			decode_err!(@BAIL Mp4, "Found an extended `tfhd` atom");
+		}
+
+		// Skip atom header + version (1)
+		write_handle.seek(SeekFrom::Start(tfhd_start + ATOM_HEADER_LEN + 1))?;
+
+		let flags = write_handle.read_u24::<BigEndian>()?;
+		let base_data_offset = (flags & 0b1) != 0;
+
+		if base_data_offset {
+			let read_offset = write_handle.read_u64::<BigEndian>()?;
+			if read_offset < offset {
+				continue;
+			}
+
+			write_handle.seek(SeekFrom::Current(-8))?;
+			write_handle.write_u64::<BigEndian>((read_offset as i64 + difference) as u64)?;
+
+			log::trace!(
+				"Updated offset from {} to {}",
+				read_offset,
+				((read_offset as i64) + difference) as u64
+			);
+		}
+	}
+
+	drop(write_handle);
+
+	Ok(())
+}
@@ -1,6 +1,6 @@
 use super::constants::{BITRATES, PADDING_SIZES, SAMPLES, SAMPLE_RATES, SIDE_INFORMATION_SIZES};
 use crate::error::Result;
-use crate::macros::decode_err;
+use crate::macros::{decode_err, try_vec};
 
 use std::io::{Read, Seek, SeekFrom};
 
@@ -157,8 +157,70 @@ where
 	}
 }
 
+/// A run of consecutive frames with matching headers, see [`verify_frames()`]
+pub(crate) struct FrameRun {
+	pub(crate) first_header: Header,
+	/// The number of frames in the run, including the first
+	pub(crate) count: u8,
+	/// Whether the run was cut short by the end of the stream
+	pub(crate) reached_eof: bool,
+}
+
+impl FrameRun {
+	/// Whether the run is long enough to accept the first frame as a sync point
+	///
+	/// A shorter run is accepted if the stream ended, as long as there were at least two frames.
+	pub(crate) fn is_synced(&self, required_frames: u8) -> bool {
+		self.count >= required_frames || (self.reached_eof && self.count >= 2)
+	}
+}
+
+/// Counts up to `max_frames` consecutive frames with matching headers, starting at the current position
+///
+/// Returns `None` if there isn't a valid frame header at the current position.
+pub(crate) fn verify_frames<R>(reader: &mut R, max_frames: u8) -> std::io::Result<Option<FrameRun>>
+where
+	R: Read + Seek,
+{
+	let Ok(first_header_data) = reader.read_u32::<BigEndian>() else {
+		return Ok(None);
+	};
+	let Some(first_header) = Header::read(first_header_data) else {
+		return Ok(None);
+	};
+
+	let mut run = FrameRun {
+		first_header,
+		count: 1,
+		reached_eof: false,
+	};
+
+	let mut frame_len = first_header.len;
+	while run.count < max_frames {
+		match cmp_header(reader, 4, frame_len, first_header_data, HEADER_MASK) {
+			HeaderCmpResult::Equal => {},
+			HeaderCmpResult::Undetermined => {
+				run.reached_eof = true;
+				break;
+			},
+			HeaderCmpResult::NotEqual => break,
+		}
+
+		// `cmp_header` leaves us at the start of the next header
+		let Some(header) = Header::read(reader.read_u32::<BigEndian>()?) else {
+			break;
+		};
+
+		frame_len = header.len;
+		run.count += 1;
+	}
+
+	Ok(Some(run))
+}
+
 /// MPEG Audio version
 #[derive(Default, PartialEq, Eq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum MpegVersion {
 	#[default]
@@ -171,6 +233,7 @@ pub enum MpegVersion {
 
 /// MPEG layer
 #[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Layer {
 	Layer1 = 1,
@@ -181,6 +244,7 @@ pub enum Layer {
 
 /// Channel mode
 #[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum ChannelMode {
 	#[default]
@@ -193,6 +257,7 @@ pub enum ChannelMode {
 
 /// A rarely-used decoder hint that the file must be de-emphasized
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs, non_camel_case_types)]
 pub enum Emphasis {
 	/// 50/15 ms
@@ -366,26 +431,6 @@ impl VbrHeader {
 
 				Ok(Some(Self { ty, frames, size }))
 			},
-			b"VBRI" => {
-				if reader_len < 32 {
-					decode_err!(@BAIL Mpeg, "VBRI header has an invalid size (< 32)");
-				}
-
-				// Skip 6 bytes
-				// Version ID (2)
-				// Delay float (2)
-				// Quality indicator (2)
-				let _info = reader.read_uint::<BigEndian>(6)?;
-
-				let size = reader.read_u32::<BigEndian>()?;
-				let frames = reader.read_u32::<BigEndian>()?;
-
-				Ok(Some(Self {
-					ty: VbrHeaderType::Vbri,
-					frames,
-					size,
-				}))
-			},
 			_ => Ok(None),
 		}
 	}
@@ -395,6 +440,137 @@ impl VbrHeader {
 	}
 }
 
+impl From<&VbriHeader> for VbrHeader {
+	fn from(vbri_header: &VbriHeader) -> Self {
+		Self {
+			ty: VbrHeaderType::Vbri,
+			frames: vbri_header.frames,
+			size: vbri_header.size,
+		}
+	}
+}
+
+/// A VBRI header, written by the Fraunhofer encoder
+///
+/// Unlike a Xing header, this is always located 32 bytes after the header of the first frame.
+///
+/// See [`MpegFile::vbri_header`](crate::mpeg::MpegFile::vbri_header).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VbriHeader {
+	/// The version of the header
+	pub version: u16,
+	/// The encoder delay
+	pub delay: u16,
+	/// The quality indicator
+	pub quality: u16,
+	/// The size of the stream in bytes
+	pub size: u32,
+	/// The number of frames in the stream
+	pub frames: u32,
+	/// The number of frames covered by each entry of the seek table
+	pub frames_per_entry: u16,
+	/// The size in bytes of each group of [`VbriHeader::frames_per_entry`] frames
+	pub seek_table: Vec<u32>,
+}
+
+impl VbriHeader {
+	/// The offset of the header from the start of the frame
+	pub(super) const OFFSET: u64 = 36;
+
+	pub(super) fn read<R>(reader: &mut R) -> Result<Option<Self>>
+	where
+		R: Read,
+	{
+		let mut header = [0; 4];
+		reader.read_exact(&mut header)?;
+
+		if &header != b"VBRI" {
+			return Ok(None);
+		}
+
+		let version = reader.read_u16::<BigEndian>()?;
+		let delay = reader.read_u16::<BigEndian>()?;
+		let quality = reader.read_u16::<BigEndian>()?;
+		let size = reader.read_u32::<BigEndian>()?;
+		let frames = reader.read_u32::<BigEndian>()?;
+
+		let entry_count = reader.read_u16::<BigEndian>()?;
+		let entry_scale = reader.read_u16::<BigEndian>()?;
+		let entry_size = reader.read_u16::<BigEndian>()?;
+		let frames_per_entry = reader.read_u16::<BigEndian>()?;
+
+		if !(1..=4).contains(&entry_size) {
+			decode_err!(@BAIL Mpeg, "VBRI header has an invalid seek table entry size");
+		}
+
+		let mut entries = try_vec![0; usize::from(entry_count) * usize::from(entry_size)];
+		reader.read_exact(&mut entries)?;
+
+		let seek_table = entries
+			.chunks_exact(usize::from(entry_size))
+			.map(|entry| {
+				let value = entry
+					.iter()
+					.fold(0u32, |value, byte| (value << 8) | u32::from(*byte));
+				value.saturating_mul(u32::from(entry_scale))
+			})
+			.collect();
+
+		Ok(Some(Self {
+			version,
+			delay,
+			quality,
+			size,
+			frames,
+			frames_per_entry,
+			seek_table,
+		}))
+	}
+
+	/// Estimate the offset of a frame, using the seek table
+	///
+	/// The offset is relative to the start of the first frame (the one containing the VBRI header).
+	///
+	/// This will return `None` if `frame` is out of range, or the seek table doesn't cover it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::mpeg::VbriHeader;
+	///
+	/// let mut vbri_header = VbriHeader::default();
+	/// vbri_header.frames = 100;
+	/// vbri_header.frames_per_entry = 50;
+	/// vbri_header.seek_table = vec![5000, 10000];
+	///
+	/// assert_eq!(vbri_header.frame_offset(0), Some(0));
+	/// assert_eq!(vbri_header.frame_offset(25), Some(2500));
+	/// assert_eq!(vbri_header.frame_offset(75), Some(10000));
+	/// assert_eq!(vbri_header.frame_offset(100), None);
+	/// ```
+	pub fn frame_offset(&self, frame: u32) -> Option<u64> {
+		if frame >= self.frames || self.frames_per_entry == 0 {
+			return None;
+		}
+
+		let frames_per_entry = u32::from(self.frames_per_entry);
+		let entry = (frame / frames_per_entry) as usize;
+
+		let entry_size = u64::from(*self.seek_table.get(entry)?);
+		let entry_start = self.seek_table[..entry]
+			.iter()
+			.map(|size| u64::from(*size))
+			.sum::<u64>();
+
+		// Assume the frames are evenly sized within an entry
+		let offset_in_entry =
+			entry_size * u64::from(frame % frames_per_entry) / u64::from(frames_per_entry);
+
+		Some(entry_start + offset_in_entry)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::tag::utils::test_utils::read_path;
@@ -2,9 +2,11 @@
 mod constants;
 pub(crate) mod header;
 mod properties;
-mod read;
+pub(crate) mod read;
+pub(crate) mod vbr;
+pub(crate) mod verify;
 
-pub use header::{ChannelMode, Emphasis, Layer, MpegVersion};
+pub use header::{ChannelMode, Emphasis, Layer, MpegVersion, VbriHeader};
 pub use properties::MpegProperties;
 
 use crate::ape::tag::ApeTag;
@@ -29,4 +31,64 @@ pub struct MpegFile {
 	pub(crate) ape_tag: Option<ApeTag>,
 	/// The file's audio properties
 	pub(crate) properties: MpegProperties,
+	/// The number of junk bytes between any leading tags and the first frame
+	pub(crate) leading_junk: u64,
+	/// The VBRI header of the first frame
+	pub(crate) vbri_header: Option<VbriHeader>,
+}
+
+impl MpegFile {
+	/// The number of junk bytes skipped before the first MPEG frame
+	///
+	/// This only includes bytes following any tags at the start of the file. Junk bytes will be retained when saving.
+	///
+	/// See [`ParseOptions::mpeg_sync_frames`](crate::config::ParseOptions::mpeg_sync_frames).
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::mpeg::MpegFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut reader = std::io::Cursor::new(&[]);
+	/// let mpeg_file = MpegFile::read_from(&mut reader, ParseOptions::new())?;
+	///
+	/// if mpeg_file.leading_junk() > 0 {
+	/// 	println!("The file starts with {} junk bytes", mpeg_file.leading_junk());
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn leading_junk(&self) -> u64 {
+		self.leading_junk
+	}
+
+	/// The VBRI header of the first frame, if one exists
+	///
+	/// VBRI headers are written by the Fraunhofer encoder, rather than the more common Xing header. When present,
+	/// it is used to calculate the duration and bitrate of the stream. Its seek table can be used to
+	/// find the offset of a frame, see [`VbriHeader::frame_offset`].
+	///
+	/// This is only read with [`ParseOptions::read_properties`](crate::config::ParseOptions::read_properties).
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::mpeg::MpegFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let mut reader = std::io::Cursor::new(&[]);
+	/// let mpeg_file = MpegFile::read_from(&mut reader, ParseOptions::new())?;
+	///
+	/// if let Some(vbri_header) = mpeg_file.vbri_header() {
+	/// 	println!("The stream has {} frames", vbri_header.frames);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn vbri_header(&self) -> Option<&VbriHeader> {
+		self.vbri_header.as_ref()
+	}
 }
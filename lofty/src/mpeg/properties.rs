@@ -9,6 +9,7 @@ use std::time::Duration;
 
 /// An MPEG file's audio properties
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct MpegProperties {
 	pub(crate) version: MpegVersion,
@@ -120,6 +121,20 @@ impl MpegProperties {
 	}
 }
 
+// Calculates the duration (in milliseconds) from the frame count of a Xing or VBRI header
+pub(super) fn vbr_duration(first_frame_header: &Header, vbr_header: &VbrHeader) -> Option<u64> {
+	if first_frame_header.sample_rate == 0 || !vbr_header.is_valid() {
+		return None;
+	}
+
+	let sample_rate = u64::from(first_frame_header.sample_rate);
+	let samples_per_frame = u64::from(first_frame_header.samples);
+
+	let total_frames = u64::from(vbr_header.frames);
+
+	Some((samples_per_frame * 1000 * total_frames).div_round(sample_rate))
+}
+
 pub(super) fn read_properties<R>(
 	properties: &mut MpegProperties,
 	reader: &mut R,
@@ -149,16 +164,9 @@ where
 	};
 
 	if let Some(vbr_header) = vbr_header {
-		if first_frame_header.sample_rate > 0 && vbr_header.is_valid() {
+		if let Some(length) = vbr_duration(&first_frame_header, &vbr_header) {
 			log::debug!("MPEG: Valid VBR header; using it to calculate duration");
 
-			let sample_rate = u64::from(first_frame_header.sample_rate);
-			let samples_per_frame = u64::from(first_frame_header.samples);
-
-			let total_frames = u64::from(vbr_header.frames);
-
-			let length = (samples_per_frame * 1000 * total_frames).div_round(sample_rate);
-
 			properties.duration = Duration::from_millis(length);
 			properties.overall_bitrate = ((file_length * 8) / length) as u32;
 			properties.audio_bitrate = ((u64::from(vbr_header.size) * 8) / length) as u32;
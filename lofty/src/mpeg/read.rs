@@ -1,18 +1,24 @@
-use super::header::{cmp_header, search_for_frame_sync, Header, HeaderCmpResult, VbrHeader};
+use super::header::{search_for_frame_sync, verify_frames, Header, VbrHeader, VbriHeader};
 use super::{MpegFile, MpegProperties};
-use crate::ape::header::read_ape_header;
-use crate::config::{ParseOptions, ParsingMode};
+use crate::ape::header::ApeHeader;
+use crate::ape::tag::read::record_ape_location;
+use crate::config::{IoHint, ParseOptions, ParsingMode};
 use crate::error::Result;
 use crate::id3::v2::header::Id3v2Header;
 use crate::id3::v2::read::parse_id3v2;
-use crate::id3::{find_id3v1, find_lyrics3v2, FindId3v2Config, ID3FindResults};
+use crate::id3::{
+	find_id3v1, find_lyrics3v2, handle_stacked_id3v2, record_id3v2_location, FindId3v2Config,
+	ID3FindResults, MAX_STACKED_ID3V2_TAGS,
+};
 use crate::io::SeekStreamLen;
 use crate::macros::{decode_err, err};
-use crate::mpeg::header::HEADER_MASK;
+use crate::properties;
+use crate::tag::TagPlacement;
 
 use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
 
-use byteorder::{BigEndian, ReadBytesExt};
+use byteorder::ReadBytesExt;
 
 pub(super) fn read_from<R>(reader: &mut R, parse_options: ParseOptions) -> Result<MpegFile>
 where
@@ -23,6 +29,10 @@ where
 	let mut first_frame_offset = 0;
 	let mut first_frame_header = None;
 
+	// The end of any tags at the start of the file, anything between this and the first frame is junk
+	let mut tags_end = 0;
+	let mut id3v2_tag_count = 0;
+
 	// Skip any invalid padding
 	while reader.read_u8()? == 0 {}
 
@@ -36,24 +46,28 @@ where
 			//
 			// Best case scenario, we find an ID3v2 tag at the beginning of the file.
 			// We will check again after finding the frame sync, in case the tag is buried in junk.
-			[b'I', b'D', b'3', ..] => {
+			//
+			// Some software writes a new tag in front of the existing one, so there may be multiple.
+			[b'I', b'D', b'3', ..] if id3v2_tag_count < MAX_STACKED_ID3V2_TAGS => {
 				// Seek back to read the tag in full
 				reader.seek(SeekFrom::Current(-4))?;
 
+				let offset = reader.stream_position()?;
 				let header = Id3v2Header::parse(reader)?;
 				let skip_footer = header.flags.footer;
 
+				id3v2_tag_count += 1;
+
 				if parse_options.read_tags {
+					record_id3v2_location(&header, offset, TagPlacement::Head);
+
 					let id3v2 = parse_id3v2(reader, header, parse_options)?;
-					if let Some(existing_tag) = &mut file.id3v2_tag {
-						// https://github.com/Serial-ATA/lofty-rs/issues/87
-						// Duplicate tags should have their frames appended to the previous
-						for frame in id3v2.frames {
-							existing_tag.insert(frame);
-						}
-						continue;
+					match &mut file.id3v2_tag {
+						Some(existing_tag) => {
+							handle_stacked_id3v2(existing_tag, id3v2, offset, parse_options)?
+						},
+						None => file.id3v2_tag = Some(id3v2),
 					}
-					file.id3v2_tag = Some(id3v2);
 				} else {
 					reader.seek(SeekFrom::Current(i64::from(header.size)))?;
 				}
@@ -63,6 +77,7 @@ where
 					reader.seek(SeekFrom::Current(10))?;
 				}
 
+				tags_end = reader.stream_position()?;
 				continue;
 			},
 			// TODO: APE tags may suffer the same issue as ID3v2 tag described above.
@@ -76,9 +91,15 @@ where
 				reader.read_exact(&mut header_remaining)?;
 
 				if &header_remaining == b"AGEX" {
-					let ape_header = read_ape_header(reader, false)?;
+					let ape_header = ApeHeader::parse(reader, false)?;
 
 					if parse_options.read_tags {
+						record_ape_location(
+							ape_header,
+							reader.stream_position()?,
+							TagPlacement::Head,
+						);
+
 						file.ape_tag = Some(crate::ape::tag::read::read_ape_tag_with_header(
 							reader,
 							ape_header,
@@ -88,6 +109,7 @@ where
 						reader.seek(SeekFrom::Current(i64::from(ape_header.size)))?;
 					}
 
+					tags_end = reader.stream_position()?;
 					continue;
 				}
 
@@ -100,7 +122,9 @@ where
 				#[allow(clippy::neg_multiply)]
 				reader.seek(SeekFrom::Current(-1 * header.len() as i64))?;
 
-				let Some((_first_frame_header, _first_frame_offset)) = find_next_frame(reader)?
+				// The junk isn't limited here, as the file is already known to be MPEG
+				let Some((_first_frame_header, _first_frame_offset)) =
+					find_next_frame(reader, parse_options, None)?
 				else {
 					break;
 				};
@@ -119,24 +143,16 @@ where
 						allowed_junk_window: Some(search_window_size),
 					};
 
-					if let ID3FindResults(Some(header), Some(id3v2_bytes)) =
-						crate::id3::find_id3v2(reader, config)?
-					{
-						let reader = &mut &*id3v2_bytes;
-
-						let id3v2 = parse_id3v2(reader, header, parse_options)?;
-
-						if let Some(existing_tag) = &mut file.id3v2_tag {
-							// https://github.com/Serial-ATA/lofty-rs/issues/87
-							// Duplicate tags should have their frames appended to the previous
-							for frame in id3v2.frames {
-								existing_tag.insert(frame);
-							}
-							continue;
-						}
+					file.id3v2_tag =
+						crate::id3::find_id3v2(reader, config)?.parse(parse_options)?;
+				}
 
-						file.id3v2_tag = Some(id3v2);
-					}
+				file.leading_junk = _first_frame_offset.saturating_sub(tags_end);
+				if file.leading_junk > 0 {
+					log::warn!(
+						"Skipped {} junk bytes before the first MPEG frame",
+						file.leading_junk
+					);
 				}
 
 				first_frame_offset = _first_frame_offset;
@@ -146,40 +162,107 @@ where
 		}
 	}
 
-	#[allow(unused_variables)]
-	let ID3FindResults(header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
+	let last_frame_offset = if parse_options.io_hint == IoHint::StreamingRemote {
+		// Trailing tags are optional, so the end of the stream is the best guess
+		reader.seek(SeekFrom::End(0))?
+	} else {
+		find_trailing_tags(reader, &mut file, parse_options)?;
+		reader.stream_position()?
+	};
+	file.properties = MpegProperties::default();
 
-	if header.is_some() {
-		file.id3v1_tag = id3v1;
-	}
+	if parse_options.read_properties {
+		let result = (|| {
+			let Some(first_frame_header) = first_frame_header else {
+				// The search for sync bits was unsuccessful
+				decode_err!(@BAIL Mpeg, "File contains an invalid frame");
+			};
 
-	let _ = find_lyrics3v2(reader)?;
+			if first_frame_header.sample_rate == 0 {
+				decode_err!(@BAIL Mpeg, "Sample rate is 0");
+			}
 
-	reader.seek(SeekFrom::Current(-32))?;
+			let first_frame_offset = first_frame_offset;
 
-	match crate::ape::tag::read::read_ape_tag(reader, true, parse_options)? {
-		(tag, Some(header)) => {
-			file.ape_tag = tag;
+			let (vbr_header, vbri_header) =
+				read_vbr_header(reader, &first_frame_header, first_frame_offset)?;
+			file.vbri_header = vbri_header;
 
-			// Seek back to the start of the tag
-			let pos = reader.stream_position()?;
-			let Some(start_of_tag) = pos.checked_sub(u64::from(header.size)) else {
-				err!(SizeMismatch);
-			};
+			let file_length = reader.stream_len_hack()?;
 
-			reader.seek(SeekFrom::Start(start_of_tag))?;
-		},
-		_ => {
-			// Correct the position (APE header - Preamble)
-			reader.seek(SeekFrom::Current(24))?;
-		},
+			super::properties::read_properties(
+				&mut file.properties,
+				reader,
+				(first_frame_header, first_frame_offset),
+				last_frame_offset,
+				vbr_header,
+				file_length,
+			)
+		})();
+
+		properties::or_incomplete(result, parse_options.parsing_mode)?;
 	}
 
-	let last_frame_offset = reader.stream_position()?;
-	file.properties = MpegProperties::default();
+	Ok(file)
+}
 
-	if parse_options.read_properties {
-		let Some(first_frame_header) = first_frame_header else {
+/// Read the duration of an MPEG file, starting from its first frame
+///
+/// Any tags at the start of the file are skipped by their headers. When the first frame holds a
+/// valid Xing or VBRI header, its frame count gives the duration directly. Otherwise, the duration
+/// is estimated from the stream length, the same as a full read.
+pub(crate) fn read_duration<R>(reader: &mut R, parse_options: ParseOptions) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	let mut first_frame = None;
+	let mut id3v2_tag_count = 0;
+
+	// Skip any invalid padding
+	while reader.read_u8()? == 0 {}
+
+	reader.seek(SeekFrom::Current(-1))?;
+
+	let mut header = [0; 4];
+
+	while let Ok(()) = reader.read_exact(&mut header) {
+		match header {
+			[b'I', b'D', b'3', ..] if id3v2_tag_count < MAX_STACKED_ID3V2_TAGS => {
+				reader.seek(SeekFrom::Current(-4))?;
+
+				let header = Id3v2Header::parse(reader)?;
+				reader.seek(SeekFrom::Current(i64::from(header.size)))?;
+
+				// Skip over the footer
+				if header.flags.footer {
+					reader.seek(SeekFrom::Current(10))?;
+				}
+
+				id3v2_tag_count += 1;
+			},
+			[b'A', b'P', b'E', b'T'] => {
+				let mut header_remaining = [0; 4];
+				reader.read_exact(&mut header_remaining)?;
+
+				if &header_remaining != b"AGEX" {
+					err!(FakeTag);
+				}
+
+				let ape_header = ApeHeader::parse(reader, false)?;
+				reader.seek(SeekFrom::Current(i64::from(ape_header.size)))?;
+			},
+			_ => {
+				#[allow(clippy::neg_multiply)]
+				reader.seek(SeekFrom::Current(-1 * header.len() as i64))?;
+
+				first_frame = find_next_frame(reader, parse_options, None)?;
+				break;
+			},
+		}
+	}
+
+	let result = (|| {
+		let Some((first_frame_header, first_frame_offset)) = first_frame else {
 			// The search for sync bits was unsuccessful
 			decode_err!(@BAIL Mpeg, "File contains an invalid frame");
 		};
@@ -188,58 +271,156 @@ where
 			decode_err!(@BAIL Mpeg, "Sample rate is 0");
 		}
 
-		let first_frame_offset = first_frame_offset;
-
-		// Try to read a Xing header
-		let xing_header_location = first_frame_offset + u64::from(first_frame_header.data_start);
-		reader.seek(SeekFrom::Start(xing_header_location))?;
-
-		let mut xing_reader = [0; 32];
-		reader.read_exact(&mut xing_reader)?;
+		let (vbr_header, _) = read_vbr_header(reader, &first_frame_header, first_frame_offset)?;
+		if let Some(length) = vbr_header
+			.as_ref()
+			.and_then(|vbr_header| super::properties::vbr_duration(&first_frame_header, vbr_header))
+		{
+			return Ok(Duration::from_millis(length));
+		}
 
-		let xing_header = VbrHeader::read(&mut &xing_reader[..])?;
+		// No frame count, the end of the stream is needed to estimate the duration
+		let last_frame_offset = if parse_options.io_hint == IoHint::StreamingRemote {
+			reader.seek(SeekFrom::End(0))?
+		} else {
+			find_trailing_tags(reader, &mut MpegFile::default(), parse_options)?;
+			reader.stream_position()?
+		};
 
 		let file_length = reader.stream_len_hack()?;
 
+		let mut properties = MpegProperties::default();
 		super::properties::read_properties(
-			&mut file.properties,
+			&mut properties,
 			reader,
 			(first_frame_header, first_frame_offset),
 			last_frame_offset,
-			xing_header,
+			vbr_header,
 			file_length,
 		)?;
+
+		Ok(properties.duration)
+	})();
+
+	properties::or_incomplete(result, parse_options.parsing_mode)
+}
+
+// Reads the Xing or VBRI header from the first frame, if there is one
+//
+// The VBRI header is also returned separately, to be exposed through `MpegFile::vbri_header`.
+pub(super) fn read_vbr_header<R>(
+	reader: &mut R,
+	first_frame_header: &Header,
+	first_frame_offset: u64,
+) -> Result<(Option<VbrHeader>, Option<VbriHeader>)>
+where
+	R: Read + Seek,
+{
+	// Try to read a Xing header
+	let xing_header_location = first_frame_offset + u64::from(first_frame_header.data_start);
+	reader.seek(SeekFrom::Start(xing_header_location))?;
+
+	let mut xing_reader = [0; 32];
+	reader.read_exact(&mut xing_reader)?;
+
+	if let Some(vbr_header) = VbrHeader::read(&mut &xing_reader[..])? {
+		return Ok((Some(vbr_header), None));
 	}
 
-	Ok(file)
+	// The VBRI header isn't placed after the side information, like the Xing header
+	reader.seek(SeekFrom::Start(first_frame_offset + VbriHeader::OFFSET))?;
+	match VbriHeader::read(reader)? {
+		Some(vbri_header) => Ok((Some(VbrHeader::from(&vbri_header)), Some(vbri_header))),
+		None => Ok((None, None)),
+	}
 }
 
-// Searches for the next frame, comparing it to the following one
-fn find_next_frame<R>(reader: &mut R) -> Result<Option<(Header, u64)>>
+// Searches for ID3v1, Lyrics3v2, and APE tags at the end of the file
+//
+// Upon return, the reader will be positioned at the start of the trailing tags.
+pub(super) fn find_trailing_tags<R>(
+	reader: &mut R,
+	file: &mut MpegFile,
+	parse_options: ParseOptions,
+) -> Result<()>
 where
 	R: Read + Seek,
 {
-	let mut pos = reader.stream_position()?;
-
-	while let Ok(Some(first_mp3_frame_start_relative)) = search_for_frame_sync(reader) {
-		let first_mp3_frame_start_absolute = pos + first_mp3_frame_start_relative;
-
-		// Seek back to the start of the frame and read the header
-		reader.seek(SeekFrom::Start(first_mp3_frame_start_absolute))?;
-		let first_header_data = reader.read_u32::<BigEndian>()?;
-
-		if let Some(first_header) = Header::read(first_header_data) {
-			match cmp_header(reader, 4, first_header.len, first_header_data, HEADER_MASK) {
-				HeaderCmpResult::Equal => {
-					return Ok(Some((first_header, first_mp3_frame_start_absolute)))
-				},
-				HeaderCmpResult::Undetermined => return Ok(None),
-				HeaderCmpResult::NotEqual => {},
+	#[allow(unused_variables)]
+	let ID3FindResults(header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
+
+	if header.is_some() {
+		file.id3v1_tag = id3v1;
+	}
+
+	let _ = find_lyrics3v2(reader)?;
+
+	reader.seek(SeekFrom::Current(-32))?;
+
+	match crate::ape::tag::read::read_ape_tag(reader, true, parse_options)? {
+		(tag, Some(header)) => {
+			file.ape_tag = tag;
+
+			// Seek back to the start of the tag
+			let pos = reader.stream_position()?;
+			let Some(start_of_tag) = pos.checked_sub(u64::from(header.size)) else {
+				err!(SizeMismatch);
+			};
+
+			reader.seek(SeekFrom::Start(start_of_tag))?;
+		},
+		_ => {
+			// Correct the position (APE header - Preamble)
+			reader.seek(SeekFrom::Current(24))?;
+		},
+	}
+
+	Ok(())
+}
+
+// Searches for the next frame that is followed by enough matching frames
+//
+// If `max_junk` is set, this will only search up to that many bytes from the current position. If no
+// frame is followed by `ParseOptions::mpeg_sync_frames` matching frames, the first frame followed by
+// at least one matching frame is used.
+pub(super) fn find_next_frame<R>(
+	reader: &mut R,
+	parse_options: ParseOptions,
+	max_junk: Option<u64>,
+) -> Result<Option<(Header, u64)>>
+where
+	R: Read + Seek,
+{
+	let start = reader.stream_position()?;
+	let mut pos = start;
+
+	let mut fallback = None;
+	while let Ok(Some(frame_start_relative)) = search_for_frame_sync(reader) {
+		let frame_start_absolute = pos + frame_start_relative;
+		if max_junk.is_some_and(|max_junk| frame_start_absolute - start > max_junk) {
+			log::debug!("No MPEG frame sync found within the junk search window");
+			break;
+		}
+
+		// Seek back to the start of the frame and verify the frames following it
+		reader.seek(SeekFrom::Start(frame_start_absolute))?;
+		if let Some(run) = verify_frames(reader, parse_options.mpeg_sync_frames)? {
+			if run.is_synced(parse_options.mpeg_sync_frames) {
+				return Ok(Some((run.first_header, frame_start_absolute)));
+			}
+
+			if run.count >= 2 && fallback.is_none() {
+				fallback = Some((run.first_header, frame_start_absolute));
+			}
+
+			if run.reached_eof {
+				break;
 			}
 		}
 
-		pos = reader.stream_position()?;
+		// Continue searching from the byte after this false frame sync
+		pos = reader.seek(SeekFrom::Start(frame_start_absolute + 1))?;
 	}
 
-	Ok(None)
+	Ok(fallback)
 }
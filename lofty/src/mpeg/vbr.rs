@@ -0,0 +1,111 @@
+use super::header::VbriHeader;
+use super::read::{find_next_frame, find_trailing_tags};
+use super::MpegFile;
+use crate::config::ParseOptions;
+use crate::error::Result;
+
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use byteorder::{BigEndian, ByteOrder};
+
+/// Update the byte count of a Xing/Info or VBRI header to match the audio stream
+///
+/// `stream` is everything following the ID3v2 tag(s) at the start of the file. The byte count
+/// covers the first frame up to any trailing tags. Some encoders include the ID3v2 tag in the count,
+/// which is no longer accurate once the tag is resized or removed.
+///
+/// Nothing is changed if there is no VBR header.
+pub(crate) fn update_vbr_header(stream: &mut [u8]) -> Result<()> {
+	if let Some((offset, stream_len)) = vbr_byte_count_update(&mut Cursor::new(&*stream), 0)? {
+		let offset = offset as usize;
+		BigEndian::write_u32(&mut stream[offset..offset + 4], stream_len);
+	}
+
+	Ok(())
+}
+
+/// Find the byte count of a Xing/Info or VBRI header that needs updating, see [`update_vbr_header`]
+///
+/// `stream_start` is the offset of everything following the ID3v2 tag(s) at the start of the file.
+/// This returns the offset of the byte count along with its new value, if it needs to change.
+pub(crate) fn vbr_byte_count_update<R>(
+	reader: &mut R,
+	stream_start: u64,
+) -> Result<Option<(u64, u32)>>
+where
+	R: Read + Seek,
+{
+	// NOTE: The tags need to be read, since the start of an APE tag is only found when it is read
+	let parse_options = ParseOptions::new();
+
+	reader.seek(SeekFrom::Start(stream_start))?;
+	let Some((first_frame_header, first_frame_offset)) =
+		find_next_frame(reader, parse_options, None)?
+	else {
+		log::debug!("MPEG: Unable to find the first frame, not updating the VBR header");
+		return Ok(None);
+	};
+
+	// Trailing tags aren't included in the byte count
+	if let Err(e) = find_trailing_tags(reader, &mut MpegFile::default(), parse_options) {
+		log::warn!("MPEG: Unable to read the trailing tags ({e}), not updating the VBR header");
+		return Ok(None);
+	}
+	let audio_end = reader.stream_position()?;
+
+	let Some(Ok(stream_len)) = audio_end.checked_sub(first_frame_offset).map(u32::try_from) else {
+		return Ok(None);
+	};
+
+	// The layouts are the same as in `VbrHeader::read` and `VbriHeader::read`
+	let xing_start = first_frame_offset + u64::from(first_frame_header.data_start);
+	let vbri_start = first_frame_offset + VbriHeader::OFFSET;
+
+	let mut xing = [0; 8];
+	let mut vbri = [0; 4];
+	let size_start =
+		if read_at(reader, xing_start, &mut xing)? && matches!(&xing[..4], b"Xing" | b"Info") {
+			// The byte count is only present if its flag (0x0002) is set, and follows the
+			// frame count if its flag (0x0001) is set
+			let flags = xing[7];
+			if flags & 0x02 == 0 {
+				return Ok(None);
+			}
+
+			if flags & 0x01 == 0x01 {
+				xing_start + 12
+			} else {
+				xing_start + 8
+			}
+		} else if read_at(reader, vbri_start, &mut vbri)? && &vbri == b"VBRI" {
+			vbri_start + 10
+		} else {
+			return Ok(None);
+		};
+
+	let mut size = [0; 4];
+	if !read_at(reader, size_start, &mut size)? {
+		return Ok(None);
+	}
+
+	let old_stream_len = BigEndian::read_u32(&size);
+	if old_stream_len == stream_len {
+		return Ok(None);
+	}
+
+	log::debug!("MPEG: Updating the VBR header byte count from {old_stream_len} to {stream_len}");
+	Ok(Some((size_start, stream_len)))
+}
+
+// Fill `buf` from `offset`, returning `false` if the stream ends first
+fn read_at<R>(reader: &mut R, offset: u64, buf: &mut [u8]) -> Result<bool>
+where
+	R: Read + Seek,
+{
+	reader.seek(SeekFrom::Start(offset))?;
+	match reader.read_exact(buf) {
+		Ok(()) => Ok(true),
+		Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+		Err(e) => Err(e.into()),
+	}
+}
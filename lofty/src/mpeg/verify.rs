@@ -0,0 +1,146 @@
+use super::header::{search_for_frame_sync, Header, VbrHeader};
+use super::read::{find_next_frame, find_trailing_tags, read_vbr_header};
+use super::MpegFile;
+use crate::config::ParseOptions;
+use crate::error::Result;
+use crate::id3::{find_id3v2, FindId3v2Config};
+use crate::macros::decode_err;
+use crate::properties::StreamVerification;
+use crate::util::math::RoundedDivision;
+
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+/// Walk the frames of an MPEG stream, comparing the frame count to the Xing/VBRI header
+///
+/// Without a Xing or VBRI header, there's no declared frame count, so only a partial final frame is
+/// detected.
+pub(crate) fn verify_stream<R>(reader: &mut R) -> Result<StreamVerification>
+where
+	R: Read + Seek,
+{
+	// NOTE: The tags need to be read, since the start of an APE tag is only found when it is read
+	let parse_options = ParseOptions::new();
+
+	find_id3v2(reader, FindId3v2Config::NO_READ_TAG)?;
+
+	let Some((first_frame_header, first_frame_offset)) = find_next_frame(
+		reader,
+		parse_options,
+		Some(parse_options.max_junk_bytes as u64),
+	)?
+	else {
+		decode_err!(@BAIL Mpeg, "File contains an invalid frame");
+	};
+
+	if first_frame_header.sample_rate == 0 {
+		decode_err!(@BAIL Mpeg, "Sample rate is 0");
+	}
+
+	find_trailing_tags(reader, &mut MpegFile::default(), parse_options)?;
+	let audio_end = reader.stream_position()?;
+
+	let (vbr_header, _) = read_vbr_header(reader, &first_frame_header, first_frame_offset)?;
+	let vbr_header = vbr_header.filter(VbrHeader::is_valid);
+
+	let mut pos = first_frame_offset;
+	let mut frames = 0_u64;
+	let mut corrupt_at = None;
+	let mut partial_frame = false;
+
+	while pos < audio_end {
+		if audio_end - pos < 4 {
+			partial_frame = true;
+			break;
+		}
+
+		reader.seek(SeekFrom::Start(pos))?;
+		let header = Header::read(reader.read_u32::<BigEndian>()?)
+			.filter(|header| header.len > 0 && header.cmp(&first_frame_header));
+
+		let Some(header) = header else {
+			log::warn!("MPEG: Invalid frame header at offset {pos}");
+			corrupt_at.get_or_insert(pos);
+
+			let Some(next_frame) = resync(reader, pos + 1, audio_end, &first_frame_header)? else {
+				break;
+			};
+
+			// The damaged frames are still there, estimate how many there were from the average
+			// frame size so far
+			let average_frame_len = match frames {
+				0 => u64::from(first_frame_header.len),
+				_ => (pos - first_frame_offset) / frames,
+			};
+			frames += (next_frame - pos).div_round(average_frame_len.max(1));
+
+			pos = next_frame;
+			continue;
+		};
+
+		if pos + u64::from(header.len) > audio_end {
+			partial_frame = true;
+			break;
+		}
+
+		frames += 1;
+		pos += u64::from(header.len);
+	}
+
+	// The frame holding the Xing/VBRI header has no audio, and isn't included in its frame count
+	if vbr_header.is_some() {
+		frames = frames.saturating_sub(1);
+	}
+
+	let expected_frames = match vbr_header {
+		Some(vbr_header) => u64::from(vbr_header.frames),
+		None => frames + u64::from(partial_frame),
+	};
+
+	let frames_to_duration = |frames: u64| {
+		let samples = frames * u64::from(first_frame_header.samples);
+		Duration::from_millis((samples * 1000).div_round(u64::from(first_frame_header.sample_rate)))
+	};
+
+	Ok(StreamVerification {
+		expected: frames_to_duration(expected_frames),
+		found: frames_to_duration(frames),
+		truncated: partial_frame || frames < expected_frames,
+		corrupt_at,
+	})
+}
+
+// Searches for the next frame matching the first frame, starting at `pos`
+fn resync<R>(
+	reader: &mut R,
+	mut pos: u64,
+	end: u64,
+	first_frame_header: &Header,
+) -> Result<Option<u64>>
+where
+	R: Read + Seek,
+{
+	while pos < end {
+		reader.seek(SeekFrom::Start(pos))?;
+
+		let Some(frame_start) = search_for_frame_sync(&mut reader.by_ref().take(end - pos))? else {
+			return Ok(None);
+		};
+
+		pos += frame_start;
+
+		reader.seek(SeekFrom::Start(pos))?;
+		if end - pos >= 4 {
+			let header = Header::read(reader.read_u32::<BigEndian>()?);
+			if header.is_some_and(|header| header.len > 0 && header.cmp(first_frame_header)) {
+				return Ok(Some(pos));
+			}
+		}
+
+		pos += 1;
+	}
+
+	Ok(None)
+}
@@ -6,9 +6,13 @@ pub mod sv7;
 pub mod sv8;
 
 use crate::ape::tag::ApeTag;
+use crate::error::{LoftyError, Result};
 use crate::id3::v1::tag::Id3v1Tag;
 use crate::id3::v2::tag::Id3v2Tag;
+use crate::id3::{find_id3v2, FindId3v2Config};
+use crate::macros::decode_err;
 use crate::properties::FileProperties;
+use crate::util::io::{FileLike, Length, Truncate};
 
 use lofty_attr::LoftyFile;
 
@@ -16,6 +20,7 @@ use lofty_attr::LoftyFile;
 ///
 /// The information available differs between stream versions
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MpcProperties {
 	/// MPC stream version 8 properties
 	Sv8(sv8::MpcSv8Properties),
@@ -78,4 +83,80 @@ impl MpcFile {
 	pub fn stream_version(&self) -> MpcStreamVersion {
 		self.stream_version
 	}
+
+	/// Change the ReplayGain values stored in the stream header
+	///
+	/// This only rewrites the ReplayGain fields in place, the tags and audio are left untouched.
+	/// `file` is expected to be positioned at the start of the file, as it is when reading.
+	///
+	/// The values are in the format described in [`ReplayGain`](sv8::ReplayGain). For stream version 7,
+	/// they are converted to the format of its header, which may lose some precision. The
+	/// [`ReplayGain::version`](sv8::ReplayGain::version) is only stored in stream version 8.
+	///
+	/// # Errors
+	///
+	/// * The stream version is 4 to 6, which has no ReplayGain fields
+	/// * The stream header is invalid
+	/// * [`std::io::Error`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::musepack::sv8::ReplayGain;
+	/// use lofty::musepack::{MpcFile, MpcProperties};
+	/// use std::fs::OpenOptions;
+	/// use std::io::{Seek, SeekFrom};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut file = OpenOptions::new().read(true).write(true).open("foo.mpc")?;
+	/// let mut mpc_file = MpcFile::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// let replay_gain = ReplayGain {
+	/// 	version: 1,
+	/// 	title_gain: 0x4E8F,
+	/// 	title_peak: 0x59F5,
+	/// 	..ReplayGain::default()
+	/// };
+	///
+	/// file.seek(SeekFrom::Start(0))?;
+	/// mpc_file.set_replay_gain(&mut file, replay_gain)?;
+	///
+	/// if let MpcProperties::Sv8(properties) = mpc_file.properties() {
+	/// 	assert_eq!(properties.replay_gain, replay_gain);
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn set_replay_gain<F>(&mut self, file: &mut F, replay_gain: sv8::ReplayGain) -> Result<()>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		// Skip any ID3v2 tag, the stream header follows it
+		let _ = find_id3v2(file, FindId3v2Config::NO_READ_TAG)?;
+
+		match self.stream_version {
+			MpcStreamVersion::Sv8 => {
+				sv8::write_replay_gain(file, replay_gain)?;
+
+				if let MpcProperties::Sv8(properties) = &mut self.properties {
+					properties.replay_gain = replay_gain;
+				}
+			},
+			MpcStreamVersion::Sv7 => {
+				sv7::write_replay_gain(file, replay_gain)?;
+
+				if let MpcProperties::Sv7(properties) = &mut self.properties {
+					properties.set_replay_gain(replay_gain);
+				}
+			},
+			MpcStreamVersion::Sv4to6 => {
+				decode_err!(@BAIL Mpc, "Stream versions 4 to 6 have no ReplayGain fields")
+			},
+		}
+
+		Ok(())
+	}
 }
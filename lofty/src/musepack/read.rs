@@ -4,7 +4,6 @@ use super::sv8::MpcSv8Properties;
 use super::{MpcFile, MpcProperties, MpcStreamVersion};
 use crate::config::ParseOptions;
 use crate::error::Result;
-use crate::id3::v2::read::parse_id3v2;
 use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, FindId3v2Config, ID3FindResults};
 use crate::macros::err;
 use crate::util::io::SeekStreamLen;
@@ -30,19 +29,14 @@ where
 	};
 
 	// ID3v2 tags are unsupported in MPC files, but still possible
-	#[allow(unused_variables)]
-	if let ID3FindResults(Some(header), Some(content)) = find_id3v2(reader, find_id3v2_config)? {
-		let Some(new_stream_length) = stream_length.checked_sub(u64::from(header.full_tag_size()))
-		else {
+	let id3v2_tags = find_id3v2(reader, find_id3v2_config)?;
+	if !id3v2_tags.is_empty() {
+		let Some(new_stream_length) = stream_length.checked_sub(id3v2_tags.size()) else {
 			err!(SizeMismatch);
 		};
 
 		stream_length = new_stream_length;
-
-		let reader = &mut &*content;
-
-		let id3v2 = parse_id3v2(reader, header, parse_options)?;
-		file.id3v2_tag = Some(id3v2);
+		file.id3v2_tag = id3v2_tags.parse(parse_options)?;
 	}
 
 	// Save the current position, so we can go back and read the properties after the tags
@@ -51,9 +45,9 @@ where
 	#[allow(unused_variables)]
 	let ID3FindResults(header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
 
-	if header.is_some() {
+	if let Some(id3v1_size) = header {
 		file.id3v1_tag = id3v1;
-		let Some(new_stream_length) = stream_length.checked_sub(128) else {
+		let Some(new_stream_length) = stream_length.checked_sub(id3v1_size) else {
 			err!(SizeMismatch);
 		};
 
@@ -114,6 +108,8 @@ where
 		},
 	}
 
+	file.stream_version = version;
+
 	if parse_options.read_properties {
 		match version {
 			MpcStreamVersion::Sv8 => {
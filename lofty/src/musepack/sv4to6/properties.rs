@@ -12,6 +12,7 @@ use byteorder::{LittleEndian, ReadBytesExt};
 
 /// MPC stream versions 4-6 audio properties
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpcSv4to6Properties {
 	pub(crate) duration: Duration,
 	pub(crate) channels: u8,     // NOTE: always 2
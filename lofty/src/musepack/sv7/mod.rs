@@ -2,6 +2,8 @@
 
 mod properties;
 
+pub(crate) use properties::write_replay_gain;
+
 // Exports
 
 pub use properties::*;
@@ -3,15 +3,17 @@ use crate::macros::decode_err;
 use crate::musepack::constants::{
 	FREQUENCY_TABLE, MPC_DECODER_SYNTH_DELAY, MPC_FRAME_LENGTH, MPC_OLD_GAIN_REF,
 };
+use crate::musepack::sv8::ReplayGain;
 use crate::properties::FileProperties;
 
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::time::Duration;
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
 /// Used profile
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Profile {
 	/// No profile
 	#[default]
@@ -72,6 +74,7 @@ impl Profile {
 
 /// Volume description for the start and end of the title
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Link {
 	/// Title starts or ends with a very low level (no live or classical genre titles)
 	#[default]
@@ -103,6 +106,7 @@ impl Link {
 
 /// MPC stream version 7 audio properties
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MpcSv7Properties {
 	pub(crate) duration: Duration,
@@ -329,29 +333,10 @@ impl MpcSv7Properties {
 		// -- End of parsing --
 
 		// Convert ReplayGain values
-		let set_replay_gain = |gain: u16| -> i16 {
-			if gain == 0 {
-				return 0;
-			}
-
-			let gain = ((MPC_OLD_GAIN_REF - f32::from(gain) / 100.0) * 256.0 + 0.5) as i16;
-			if !(0..i16::MAX).contains(&gain) {
-				return 0;
-			}
-			gain
-		};
-		let set_replay_peak = |peak: u16| -> u16 {
-			if peak == 0 {
-				return 0;
-			}
-
-			((f64::from(peak).log10() * 20.0 * 256.0) + 0.5) as u16
-		};
-
-		properties.title_gain = set_replay_gain(title_gain);
-		properties.title_peak = set_replay_peak(title_peak);
-		properties.album_gain = set_replay_gain(album_gain);
-		properties.album_peak = set_replay_peak(album_peak);
+		properties.title_gain = gain_from_header(title_gain);
+		properties.title_peak = peak_from_header(title_peak);
+		properties.album_gain = gain_from_header(album_gain);
+		properties.album_peak = peak_from_header(album_peak);
 
 		if properties.last_frame_length > MPC_FRAME_LENGTH as u16 {
 			decode_err!(@BAIL Mpc, "Invalid last frame length");
@@ -386,3 +371,86 @@ impl MpcSv7Properties {
 		Ok(properties)
 	}
 }
+
+// The header stores the gain in 0.01 dB relative to the old reference, and the peak as a
+// linear sample value. These are converted to the stream version 8 format, see `ReplayGain`.
+
+fn gain_from_header(gain: u16) -> i16 {
+	if gain == 0 {
+		return 0;
+	}
+
+	let gain = ((MPC_OLD_GAIN_REF - f32::from(gain) / 100.0) * 256.0 + 0.5) as i16;
+	if !(0..i16::MAX).contains(&gain) {
+		return 0;
+	}
+	gain
+}
+
+fn gain_to_header(gain: u16) -> u16 {
+	if gain == 0 {
+		return 0;
+	}
+
+	((MPC_OLD_GAIN_REF - f32::from(gain) / 256.0) * 100.0).round() as u16
+}
+
+fn peak_from_header(peak: u16) -> u16 {
+	if peak == 0 {
+		return 0;
+	}
+
+	((f64::from(peak).log10() * 20.0 * 256.0) + 0.5) as u16
+}
+
+fn peak_to_header(peak: u16) -> u16 {
+	if peak == 0 {
+		return 0;
+	}
+
+	10_f64.powf(f64::from(peak) / (20.0 * 256.0)).round() as u16
+}
+
+/// Rewrite the ReplayGain fields of the stream header
+///
+/// This expects the writer to be positioned at the `"MP+"` signature.
+pub(crate) fn write_replay_gain<F>(file: &mut F, replay_gain: ReplayGain) -> Result<()>
+where
+	F: Read + Write + Seek,
+{
+	let start = file.stream_position()?;
+
+	// The signature (3), version (1), and sections 1 and 2 (8), followed by the ReplayGain
+	// sections 3 and 4 (8)
+	let mut header = [0; 20];
+	file.read_exact(&mut header)?;
+
+	if &header[..3] != b"MP+" || header[3] & 0x0F != 7 {
+		decode_err!(@BAIL Mpc, "Expected stream version 7");
+	}
+
+	for (pos, value) in [
+		(12, peak_to_header(replay_gain.title_peak)),
+		(14, gain_to_header(replay_gain.title_gain)),
+		(16, peak_to_header(replay_gain.album_peak)),
+		(18, gain_to_header(replay_gain.album_gain)),
+	] {
+		header[pos..pos + 2].copy_from_slice(&value.to_le_bytes());
+	}
+
+	file.seek(SeekFrom::Start(start))?;
+	file.write_all(&header)?;
+
+	Ok(())
+}
+
+impl MpcSv7Properties {
+	/// Update the ReplayGain values, as they would be read from the header after
+	/// [`write_replay_gain`]
+	pub(crate) fn set_replay_gain(&mut self, replay_gain: ReplayGain) {
+		self.title_gain = gain_from_header(gain_to_header(replay_gain.title_gain));
+		self.title_peak = peak_from_header(peak_to_header(replay_gain.title_peak));
+		self.album_gain = gain_from_header(gain_to_header(replay_gain.album_gain));
+		self.album_peak = peak_from_header(peak_to_header(replay_gain.album_peak));
+	}
+}
@@ -3,6 +3,8 @@
 mod properties;
 mod read;
 
+pub(crate) use read::write_replay_gain;
+
 // Exports
 
 pub use properties::*;
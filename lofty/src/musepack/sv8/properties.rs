@@ -13,6 +13,7 @@ use byteorder::{BigEndian, ReadBytesExt};
 
 /// MPC stream version 8 audio properties
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MpcSv8Properties {
 	pub(crate) duration: Duration,
 	pub(crate) average_bitrate: u32,
@@ -73,6 +74,7 @@ impl MpcSv8Properties {
 ///
 /// This contains the information needed to decode the stream.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StreamHeader {
 	/// CRC 32 of the stream header packet
 	///
@@ -162,6 +164,7 @@ impl StreamHeader {
 /// * For 16-bit output (range \[-32767 32768]), the max is 68813 (out of range). It will be encoded as $ 20 * log10(68813) * 256 ~ 24769 = 0x60C1 $
 /// * For float output (range \[-1 1]), the max is 0.96. It will be encoded as $ 20 * log10(0.96 * 215) * 256 ~ 23029 = 0x59F5 $ (for peak values it is suggested to round to nearest higher integer)
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub struct ReplayGain {
 	/// The replay gain version
@@ -203,6 +206,7 @@ impl ReplayGain {
 
 /// Information from an Encoder Info packet
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub struct EncoderInfo {
 	/// Quality in 4.3 format
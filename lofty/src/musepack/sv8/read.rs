@@ -3,15 +3,14 @@ use crate::config::ParsingMode;
 use crate::error::{ErrorKind, LoftyError, Result};
 use crate::macros::{decode_err, parse_mode_choice};
 
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom, Write};
 
-use byteorder::ReadBytesExt;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
 // TODO: Support chapter packets?
 const STREAM_HEADER_KEY: [u8; 2] = *b"SH";
 const REPLAYGAIN_KEY: [u8; 2] = *b"RG";
 const ENCODER_INFO_KEY: [u8; 2] = *b"EI";
-#[allow(dead_code)]
 const AUDIO_PACKET_KEY: [u8; 2] = *b"AP";
 const STREAM_END_KEY: [u8; 2] = *b"SE";
 
@@ -81,6 +80,58 @@ where
 	Ok(properties)
 }
 
+/// Rewrite the payload of the ReplayGain packet
+///
+/// This expects the writer to be positioned at the `"MPCK"` signature. Unlike the Stream Header,
+/// the ReplayGain packet has no CRC, so no other packet is affected.
+pub(crate) fn write_replay_gain<F>(file: &mut F, replay_gain: ReplayGain) -> Result<()>
+where
+	F: Read + Write + Seek,
+{
+	let mut signature = [0; 4];
+	file.read_exact(&mut signature)?;
+
+	if &signature != b"MPCK" {
+		decode_err!(@BAIL Mpc, "Expected stream version 8");
+	}
+
+	// The ReplayGain packet must come before any audio
+	loop {
+		let mut key = [0; 2];
+		file.read_exact(&mut key)?;
+
+		let (packet_size, packet_size_byte_count) = PacketReader::read_size(file)?;
+		let payload_size = packet_size.saturating_sub(u64::from(2 + packet_size_byte_count));
+
+		match key {
+			REPLAYGAIN_KEY => {
+				// Version (1), title gain (2), title peak (2), album gain (2), album peak (2)
+				if payload_size < 9 {
+					decode_err!(@BAIL Mpc, "ReplayGain packet is too short");
+				}
+
+				file.write_u8(replay_gain.version)?;
+				file.write_u16::<BigEndian>(replay_gain.title_gain)?;
+				file.write_u16::<BigEndian>(replay_gain.title_peak)?;
+				file.write_u16::<BigEndian>(replay_gain.album_gain)?;
+				file.write_u16::<BigEndian>(replay_gain.album_peak)?;
+
+				return Ok(());
+			},
+			AUDIO_PACKET_KEY | STREAM_END_KEY => {
+				decode_err!(@BAIL Mpc, "File is missing a ReplayGain packet")
+			},
+			_ => {
+				let Ok(payload_size) = i64::try_from(payload_size) else {
+					decode_err!(@BAIL Mpc, "Packet size is too large");
+				};
+
+				file.seek(SeekFrom::Current(payload_size))?;
+			},
+		}
+	}
+}
+
 pub struct PacketReader<R> {
 	reader: R,
 	capacity: u64,
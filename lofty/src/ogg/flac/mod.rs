@@ -0,0 +1,28 @@
+pub(super) mod read;
+pub(in crate::ogg) mod write;
+
+use super::tag::VorbisComments;
+use crate::flac::FlacProperties;
+
+use lofty_attr::LoftyFile;
+
+/// An Ogg FLAC file
+///
+/// This is a FLAC stream encapsulated in Ogg, following the [Ogg FLAC mapping](https://xiph.org/flac/ogg_mapping.html).
+///
+/// ## Notes
+///
+/// * Pictures are stored in their own metadata blocks, like in native FLAC. When reading, they will be
+///   made available in the [`VorbisComments`] tag, and any pictures in the tag will be written as picture blocks.
+/// * Any other metadata blocks (`SEEKTABLE`, `APPLICATION`, etc.) are retained when writing, with the exception of `PADDING`.
+#[derive(LoftyFile)]
+#[lofty(read_fn = "read::read_from")]
+pub struct OggFlacFile {
+	/// The vorbis comments contained in the file
+	///
+	/// NOTE: While a comment block is required, it isn't required to actually have any data.
+	#[lofty(tag_type = "VorbisComments")]
+	pub(crate) vorbis_comments_tag: VorbisComments,
+	/// The file's audio properties
+	pub(crate) properties: FlacProperties,
+}
@@ -0,0 +1,253 @@
+use super::OggFlacFile;
+use crate::config::{IoHint, ParseOptions, ParsingMode};
+use crate::error::Result;
+use crate::flac::block::{BLOCK_ID_PICTURE, BLOCK_ID_STREAMINFO, BLOCK_ID_VORBIS_COMMENTS};
+use crate::flac::FlacProperties;
+use crate::macros::decode_err;
+use crate::ogg::constants::OGG_FLAC_HEAD;
+use crate::ogg::find_last_page;
+use crate::ogg::read::read_comments;
+use crate::ogg::verify_signature;
+use crate::picture::Picture;
+use crate::properties;
+use crate::util::picture_sink;
+
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use ogg_pager::{Packets, PageHeader};
+
+// Mapping header signature (5), Version (2), Header packet count (2), "fLaC" (4)
+const STREAMINFO_BLOCK_OFFSET: usize = 13;
+// STREAMINFO block header (4), STREAMINFO (34)
+const MAPPING_PACKET_LEN: usize = STREAMINFO_BLOCK_OFFSET + 4 + 34;
+
+/// Read and verify the mapping header packet, which holds the STREAMINFO block
+fn read_mapping_packet<R>(data: &mut R) -> Result<Packets>
+where
+	R: Read + Seek,
+{
+	let packets = Packets::read_count(data, 1)?;
+	let mapping_packet = packets
+		.get(0)
+		.ok_or_else(|| decode_err!(OggFlac, "Expected mapping header packet"))?;
+
+	verify_signature(mapping_packet, OGG_FLAC_HEAD)?;
+
+	if mapping_packet.len() < MAPPING_PACKET_LEN
+		|| &mapping_packet[9..STREAMINFO_BLOCK_OFFSET] != b"fLaC"
+		|| mapping_packet[STREAMINFO_BLOCK_OFFSET] & 0x7F != BLOCK_ID_STREAMINFO
+	{
+		decode_err!(@BAIL OggFlac, "File has an invalid mapping header packet");
+	}
+
+	// Only version 1.x of the mapping exists
+	let major_version = mapping_packet[5];
+	if major_version != 1 {
+		decode_err!(@BAIL OggFlac, "Unsupported mapping version");
+	}
+
+	Ok(packets)
+}
+
+/// Read the mapping header packet, followed by every metadata block packet
+///
+/// Each packet after the first contains exactly one metadata block, including its header.
+pub(super) fn read_header_packets<R>(data: &mut R) -> Result<Packets>
+where
+	R: Read + Seek,
+{
+	let start = data.stream_position()?;
+
+	let packets = read_mapping_packet(data)?;
+
+	// `read_mapping_packet` verified the packet
+	let mapping_packet = packets.get(0).expect("Mapping header packet expected");
+
+	let header_packet_count = u16::from_be_bytes([mapping_packet[7], mapping_packet[8]]);
+	let streaminfo_is_last = mapping_packet[STREAMINFO_BLOCK_OFFSET] & 0x80 != 0;
+
+	if header_packet_count > 0 {
+		data.seek(SeekFrom::Start(start))?;
+		return Ok(Packets::read_count(data, 1 + header_packet_count as isize)?);
+	}
+
+	if streaminfo_is_last {
+		return Ok(packets);
+	}
+
+	// A count of 0 means the number of header packets is unknown, so we have to keep reading
+	// until we find the last metadata block
+	let mut count = 2;
+	loop {
+		data.seek(SeekFrom::Start(start))?;
+		let packets = Packets::read_count(data, count)?;
+
+		let is_last_block = packets
+			.get(count as usize - 1)
+			.and_then(|packet| packet.first())
+			.is_some_and(|block_header| block_header & 0x80 != 0);
+		if is_last_block {
+			return Ok(packets);
+		}
+
+		count += 1;
+	}
+}
+
+pub(super) fn read_from<R>(data: &mut R, parse_options: ParseOptions) -> Result<OggFlacFile>
+where
+	R: Read + Seek,
+{
+	let start = data.stream_position()?;
+	let first_page_header = PageHeader::read(data)?;
+	data.seek(SeekFrom::Start(start))?;
+
+	let packets = read_header_packets(data)?;
+
+	let mut vorbis_comments_tag = None;
+	let mut pictures = Vec::new();
+	for block in packets.iter().skip(1) {
+		if block.len() < 4 {
+			decode_err!(@BAIL OggFlac, "Encountered a metadata block packet that is too small");
+		}
+
+		let block_type = block[0] & 0x7F;
+		let content = &block[4..];
+
+		if block_type == BLOCK_ID_VORBIS_COMMENTS
+			&& parse_options.read_tags
+			&& parse_options.verify_tag_size(content.len() as u64)?
+		{
+			log::debug!("Encountered a Vorbis Comments block, parsing");
+
+			if vorbis_comments_tag.is_some() {
+				if parse_options.parsing_mode == ParsingMode::Strict {
+					decode_err!(@BAIL OggFlac, "Streams are only allowed one Vorbis Comments block per stream");
+				}
+
+				log::warn!("Encountered multiple Vorbis Comments blocks, using the latest");
+			}
+
+			vorbis_comments_tag = Some(read_comments(
+				&mut &*content,
+				content.len() as u64,
+				parse_options,
+			)?);
+			continue;
+		}
+
+		if block_type == BLOCK_ID_PICTURE
+			&& parse_options.read_cover_art
+			&& parse_options.verify_item_size(content.len() as u64)?
+		{
+			log::debug!("Encountered a FLAC picture block, parsing");
+
+			let reader = &mut &*content;
+			let picture = Picture::from_flac_reader(
+				reader,
+				content.len(),
+				parse_options.parsing_mode,
+				parse_options.picture_data_policy,
+			)
+			.and_then(|picture| {
+				if picture.0.skipped_data_len.is_some() {
+					picture_sink::consume(&picture.0, reader)?;
+				}
+
+				Ok(picture)
+			});
+
+			match picture {
+				Ok(picture) => pictures.push(picture),
+				Err(e) => {
+					if parse_options.parsing_mode == ParsingMode::Strict {
+						return Err(e);
+					}
+
+					log::warn!("Unable to read FLAC picture block, discarding");
+				},
+			}
+		}
+	}
+
+	// A comment block is mandatory in Ogg FLAC
+	let mut vorbis_comments_tag = vorbis_comments_tag.unwrap_or_default();
+	vorbis_comments_tag.pictures.append(&mut pictures);
+
+	let mut properties = FlacProperties::default();
+	if parse_options.read_properties {
+		// It's impossible to get this far without the mapping header packet, safe to unwrap
+		let mapping_packet = packets.get(0).expect("Mapping header packet expected");
+		let stream_info = &mapping_packet[STREAMINFO_BLOCK_OFFSET + 4..MAPPING_PACKET_LEN];
+
+		properties = properties::or_incomplete(
+			read_properties(data, stream_info, &first_page_header, parse_options),
+			parse_options.parsing_mode,
+		)?;
+	}
+
+	Ok(OggFlacFile {
+		vorbis_comments_tag,
+		properties,
+	})
+}
+
+/// Read the duration of an Ogg FLAC stream
+///
+/// Only the mapping header packet is read, the metadata block packets are skipped over along with the
+/// audio while searching for the last page.
+pub(in crate::ogg) fn read_duration<R>(
+	data: &mut R,
+	parse_options: ParseOptions,
+) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	let start = data.stream_position()?;
+	let first_page_header = PageHeader::read(data)?;
+	data.seek(SeekFrom::Start(start))?;
+
+	let packets = read_mapping_packet(data)?;
+	let mapping_packet = packets.get(0).expect("Mapping header packet expected");
+	let stream_info = &mapping_packet[STREAMINFO_BLOCK_OFFSET + 4..MAPPING_PACKET_LEN];
+
+	let properties = properties::or_incomplete(
+		read_properties(data, stream_info, &first_page_header, parse_options),
+		parse_options.parsing_mode,
+	)?;
+	Ok(properties.duration)
+}
+
+fn read_properties<R>(
+	data: &mut R,
+	stream_info: &[u8],
+	first_page_header: &PageHeader,
+	parse_options: ParseOptions,
+) -> Result<FlacProperties>
+where
+	R: Read + Seek,
+{
+	if parse_options.io_hint == IoHint::StreamingRemote {
+		return crate::flac::properties::read_properties(&mut &*stream_info, 0, 0);
+	}
+
+	let stream_start = data.stream_position()?;
+	let file_length = data.seek(SeekFrom::End(0))?;
+	let stream_length = file_length.saturating_sub(stream_start);
+
+	let mut properties =
+		crate::flac::properties::read_properties(&mut &*stream_info, stream_length, file_length)?;
+
+	// The total sample count is optional in STREAMINFO, which is common for streamed encodes. The
+	// granule position of the last page is the same sample count.
+	data.seek(SeekFrom::Start(stream_start))?;
+	if let Ok((last_page, _)) = find_last_page(data, first_page_header.stream_serial) {
+		let total_samples = last_page.header().abgp;
+		if total_samples != u64::MAX {
+			properties.set_length(total_samples, stream_length, file_length);
+		}
+	}
+
+	Ok(properties)
+}
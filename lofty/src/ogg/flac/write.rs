@@ -0,0 +1,120 @@
+use super::read::read_header_packets;
+use crate::config::WriteOptions;
+use crate::error::{LoftyError, Result};
+use crate::flac::block::{BLOCK_ID_PADDING, BLOCK_ID_PICTURE, BLOCK_ID_VORBIS_COMMENTS};
+use crate::flac::write::{verify_icons, MAX_BLOCK_SIZE};
+use crate::macros::{decode_err, err};
+use crate::ogg::tag::VorbisCommentsRef;
+use crate::ogg::write::{create_metadata_packet, read_vendor, write_header_packets};
+use crate::picture::{Picture, PictureInformation};
+use crate::util::io::{FileLike, Length, Truncate};
+
+use std::io::SeekFrom;
+
+use ogg_pager::PageHeader;
+
+pub(in crate::ogg) fn write_to<'a, F, II, IP>(
+	file: &mut F,
+	tag: &mut VorbisCommentsRef<'a, II, IP>,
+	write_options: WriteOptions,
+) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+	II: Iterator<Item = (&'a str, &'a str)>,
+	IP: Iterator<Item = (&'a Picture, PictureInformation)>,
+{
+	// Read the first page header to get the stream serial number
+	let start = file.stream_position()?;
+	let first_page_header = PageHeader::read(file)?;
+
+	let stream_serial = first_page_header.stream_serial;
+
+	file.seek(SeekFrom::Start(start))?;
+	let packets = read_header_packets(file)?;
+
+	let mut remaining_file_content = Vec::new();
+	file.read_to_end(&mut remaining_file_content)?;
+
+	// It's impossible to get this far without the mapping header packet, safe to unwrap
+	let mut mapping_packet = packets
+		.get(0)
+		.expect("Mapping header packet expected")
+		.to_vec();
+
+	// All existing blocks other than VORBIS_COMMENT, PICTURE, and PADDING are kept, and placed after
+	// the new blocks.
+	let mut other_blocks = Vec::new();
+	for block in packets.iter().skip(1) {
+		let Some(block_header) = block.first() else {
+			decode_err!(@BAIL OggFlac, "Encountered an empty metadata block packet");
+		};
+
+		match block_header & 0x7F {
+			BLOCK_ID_VORBIS_COMMENTS => {
+				// Retain the original vendor string
+				tag.vendor = read_vendor(block.get(4..).unwrap_or_default())?;
+			},
+			BLOCK_ID_PICTURE | BLOCK_ID_PADDING => {},
+			_ => other_blocks.push(block.to_vec()),
+		}
+	}
+
+	// The comment block must immediately follow the mapping header packet
+	let comments = create_metadata_packet(
+		&mut VorbisCommentsRef {
+			vendor: tag.vendor.clone(),
+			items: &mut tag.items,
+			pictures: std::iter::empty(),
+		},
+		&[],
+		false,
+		write_options,
+	)?;
+
+	let mut blocks = vec![create_block(BLOCK_ID_VORBIS_COMMENTS, &comments)?];
+	for (pic, info) in verify_icons(&mut tag.pictures, write_options)? {
+		let pic_bytes = pic.as_flac_bytes(info, false);
+		blocks.push(create_block(BLOCK_ID_PICTURE, &pic_bytes)?);
+	}
+
+	blocks.append(&mut other_blocks);
+
+	// Only the final block can have the "last block" flag set
+	let block_count = blocks.len();
+	for (idx, block) in blocks.iter_mut().enumerate() {
+		if idx == block_count - 1 {
+			block[0] |= 0x80;
+		} else {
+			block[0] &= 0x7F;
+		}
+	}
+
+	// The number of header packets following the mapping header packet, where 0 means unknown
+	let header_packet_count = u16::try_from(block_count).unwrap_or(0);
+	mapping_packet[7..9].copy_from_slice(&header_packet_count.to_be_bytes());
+
+	// STREAMINFO is never the last block, as there's always a comment block
+	mapping_packet[13] &= 0x7F;
+
+	write_header_packets(
+		file,
+		std::iter::once(mapping_packet.as_slice()).chain(blocks.iter().map(Vec::as_slice)),
+		stream_serial,
+		&remaining_file_content,
+	)
+}
+
+fn create_block(block_type: u8, content: &[u8]) -> Result<Vec<u8>> {
+	if content.len() > MAX_BLOCK_SIZE as usize {
+		err!(TooMuchData);
+	}
+
+	let mut block = Vec::with_capacity(4 + content.len());
+	block.push(block_type);
+	block.extend_from_slice(&(content.len() as u32).to_be_bytes()[1..]);
+	block.extend_from_slice(content);
+
+	Ok(block)
+}
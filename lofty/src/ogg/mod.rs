@@ -3,27 +3,38 @@
 //! ## File notes
 //!
 //! The only supported tag format is [`VorbisComments`]
+//!
+//! * See [`OggFlacFile`] for notes on FLAC in Ogg
 pub(crate) mod constants;
+pub(crate) mod flac;
 pub(crate) mod opus;
 mod picture_storage;
 pub(crate) mod read;
 pub(crate) mod speex;
 pub(crate) mod tag;
+pub(crate) mod verify;
 pub(crate) mod vorbis;
 pub(crate) mod write;
 
+use crate::config::ParseOptions;
 use crate::error::Result;
+use crate::file::FileType;
 use crate::macros::decode_err;
 
 use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
 
-use ogg_pager::{Page, PageHeader};
+use ogg_pager::{
+	Packets, Page, PageHeader, CONTAINS_FIRST_PAGE_OF_BITSTREAM, CONTAINS_LAST_PAGE_OF_BITSTREAM,
+};
 
 // Exports
 
+pub use flac::OggFlacFile;
 pub use opus::properties::OpusProperties;
 pub use opus::OpusFile;
 pub use picture_storage::OggPictureStorage;
+pub use read::VorbisCommentIter;
 pub use speex::properties::SpeexProperties;
 pub use speex::SpeexFile;
 pub use tag::VorbisComments;
@@ -40,18 +51,90 @@ fn verify_signature(content: &[u8], sig: &[u8]) -> Result<()> {
 	Ok(())
 }
 
-fn find_last_page<R>(data: &mut R) -> Result<Page>
+/// Read the duration of an Ogg stream
+///
+/// Only the identification packet is read, the duration comes from the granule position of the
+/// last page.
+pub(crate) fn read_duration<R>(
+	data: &mut R,
+	file_type: FileType,
+	parse_options: ParseOptions,
+) -> Result<Duration>
+where
+	R: Read + Seek,
+{
+	if file_type == FileType::OggFlac {
+		return flac::read::read_duration(data, parse_options);
+	}
+
+	let start = data.stream_position()?;
+	let first_page_header = PageHeader::read(data)?;
+	data.seek(SeekFrom::Start(start))?;
+
+	let packets = Packets::read_count(data, 1)?;
+	let identification_packet = packets
+		.get(0)
+		.ok_or_else(|| decode_err!("OGG: Expected identification packet"))?;
+
+	let duration = match file_type {
+		FileType::Opus => {
+			verify_signature(identification_packet, constants::OPUSHEAD)?;
+			opus::properties::read_properties(data, &first_page_header, &packets)
+				.map(|properties| properties.duration)
+		},
+		FileType::Speex => {
+			verify_signature(identification_packet, constants::SPEEXHEADER)?;
+			speex::properties::read_properties(data, &first_page_header, &packets)
+				.map(|properties| properties.duration)
+		},
+		FileType::Vorbis => {
+			verify_signature(identification_packet, constants::VORBIS_IDENT_HEAD)?;
+			vorbis::properties::read_properties(data, &first_page_header, &packets)
+				.map(|properties| properties.duration)
+		},
+		_ => unreachable!("not an Ogg file type"),
+	};
+
+	crate::properties::or_incomplete(duration, parse_options.parsing_mode)
+}
+
+/// Find the last page of the logical bitstream `stream_serial`
+///
+/// Pages of other multiplexed bitstreams are skipped. Scanning stops at the start of the next chain,
+/// which is indicated by a new beginning of stream page once `stream_serial` has ended.
+///
+/// Returns the page, and whether the file contains further chains.
+fn find_last_page<R>(data: &mut R, stream_serial: u32) -> Result<(Page, bool)>
 where
 	R: Read + Seek,
 {
-	let mut last_page_header = PageHeader::read(data)?;
-	data.seek(SeekFrom::Current(last_page_header.content_size() as i64))?;
+	let mut last_page_header = None;
+	let mut stream_ended = false;
+	let mut chained = false;
 
 	while let Ok(header) = PageHeader::read(data) {
-		last_page_header = header;
-		data.seek(SeekFrom::Current(last_page_header.content_size() as i64))?;
+		if stream_ended && header.header_type_flag() & CONTAINS_FIRST_PAGE_OF_BITSTREAM != 0 {
+			chained = true;
+			break;
+		}
+
+		data.seek(SeekFrom::Current(header.content_size() as i64))?;
+
+		if header.stream_serial != stream_serial {
+			continue;
+		}
+
+		if header.header_type_flag() & CONTAINS_LAST_PAGE_OF_BITSTREAM != 0 {
+			stream_ended = true;
+		}
+
+		last_page_header = Some(header);
 	}
 
+	let Some(last_page_header) = last_page_header else {
+		decode_err!(@BAIL "OGG: Unable to find the last page of the stream");
+	};
+
 	data.seek(SeekFrom::Start(last_page_header.start))?;
-	Ok(Page::read(data)?)
+	Ok((Page::read(data)?, chained))
 }
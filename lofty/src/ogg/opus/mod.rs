@@ -1,13 +1,16 @@
 pub(super) mod properties;
 
-use super::find_last_page;
 use super::tag::VorbisComments;
+use super::{find_last_page, verify_signature};
 use crate::config::ParseOptions;
 use crate::error::Result;
+use crate::macros::decode_err;
 use crate::ogg::constants::{OPUSHEAD, OPUSTAGS};
 use properties::OpusProperties;
 
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use ogg_pager::Page;
 
 use lofty_attr::LoftyFile;
 
@@ -34,7 +37,10 @@ impl OpusFile {
 
 		Ok(Self {
 			properties: if parse_options.read_properties {
-				properties::read_properties(reader, &file_information.1, &file_information.2)?
+				crate::properties::or_incomplete(
+					properties::read_properties(reader, &file_information.1, &file_information.2),
+					parse_options.parsing_mode,
+				)?
 			} else {
 				OpusProperties::default()
 			},
@@ -42,4 +48,72 @@ impl OpusFile {
 			vorbis_comments_tag: file_information.0.unwrap_or_default(),
 		})
 	}
+
+	/// Change the output gain of the file
+	///
+	/// This only rewrites the `OpusHead` packet in the first page of `file`, the comment and audio pages are left untouched.
+	/// `file` is expected to be positioned at the start of the stream, as it is when reading.
+	///
+	/// See [`OpusProperties::output_gain`] for the format of `output_gain`.
+	///
+	/// # Errors
+	///
+	/// * `file` does not start with an `OpusHead` packet
+	/// * [`std::io::Error`]
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::config::ParseOptions;
+	/// use lofty::file::AudioFile;
+	/// use lofty::ogg::OpusFile;
+	/// use std::fs::OpenOptions;
+	/// use std::io::{Seek, SeekFrom};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut file = OpenOptions::new().read(true).write(true).open("foo.opus")?;
+	/// let mut opus_file = OpusFile::read_from(&mut file, ParseOptions::new())?;
+	///
+	/// // Reduce the volume by 3 dB
+	/// file.seek(SeekFrom::Start(0))?;
+	/// opus_file.set_output_gain(&mut file, -3 * 256)?;
+	///
+	/// assert_eq!(opus_file.properties().output_gain(), -3 * 256);
+	/// # Ok(()) }
+	/// ```
+	pub fn set_output_gain<F>(&mut self, file: &mut F, output_gain: i16) -> Result<()>
+	where
+		F: Read + Write + Seek,
+	{
+		// The output gain follows the signature, version, channel count, pre-skip, and input sample rate
+		const OUTPUT_GAIN_OFFSET: usize = 16;
+		const CHECKSUM_OFFSET: usize = 22;
+
+		let start = file.stream_position()?;
+
+		// The `OpusHead` packet must be alone on the first page, but may span multiple segments
+		let first_page = Page::read(file)?;
+		let content = first_page.content();
+		verify_signature(content, OPUSHEAD)?;
+
+		if content.len() < OUTPUT_GAIN_OFFSET + 2 {
+			decode_err!(@BAIL Opus, "Identification packet is too short");
+		}
+
+		let mut page_bytes = first_page.as_bytes();
+		let output_gain_pos = page_bytes.len() - content.len() + OUTPUT_GAIN_OFFSET;
+		page_bytes[output_gain_pos..output_gain_pos + 2]
+			.copy_from_slice(&output_gain.to_le_bytes());
+
+		// The checksum is calculated with the checksum field zeroed
+		page_bytes[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4].fill(0);
+		let checksum = ogg_pager::crc32(&page_bytes);
+		page_bytes[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4].copy_from_slice(&checksum.to_le_bytes());
+
+		file.seek(SeekFrom::Start(start))?;
+		file.write_all(&page_bytes)?;
+
+		self.properties.output_gain = output_gain;
+		Ok(())
+	}
 }
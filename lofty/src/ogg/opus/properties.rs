@@ -12,6 +12,7 @@ use ogg_pager::{Packets, PageHeader};
 
 /// An Opus file's audio properties
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct OpusProperties {
 	pub(crate) duration: Duration,
@@ -21,6 +22,9 @@ pub struct OpusProperties {
 	pub(crate) channel_mask: ChannelMask,
 	pub(crate) version: u8,
 	pub(crate) input_sample_rate: u32,
+	pub(crate) output_gain: i16,
+	pub(crate) channel_mapping_family: u8,
+	pub(crate) is_chained: bool,
 }
 
 impl From<OpusProperties> for FileProperties {
@@ -76,6 +80,29 @@ impl OpusProperties {
 	pub fn input_sample_rate(&self) -> u32 {
 		self.input_sample_rate
 	}
+
+	/// Output gain
+	///
+	/// This is a Q7.8 fixed point number in dB (divide by 256 to get the gain in dB), to be
+	/// applied by the decoder. It can be changed with [`OpusFile::set_output_gain`](crate::ogg::OpusFile::set_output_gain).
+	pub fn output_gain(&self) -> i16 {
+		self.output_gain
+	}
+
+	/// Channel mapping family
+	///
+	/// See [RFC 7845, Section 5.1.1](https://datatracker.ietf.org/doc/html/rfc7845.html#section-5.1.1).
+	pub fn channel_mapping_family(&self) -> u8 {
+		self.channel_mapping_family
+	}
+
+	/// Whether the file contains chained streams
+	///
+	/// A chained file is several complete streams concatenated together. Only the first stream is
+	/// used for tags and properties, and the remaining streams are left untouched when writing.
+	pub fn is_chained(&self) -> bool {
+		self.is_chained
+	}
 }
 
 pub(in crate::ogg) fn read_properties<R>(
@@ -102,9 +129,10 @@ where
 
 	properties.input_sample_rate = identification_packet_reader.read_u32::<LittleEndian>()?;
 
-	let _output_gain = identification_packet_reader.read_u16::<LittleEndian>()?;
+	properties.output_gain = identification_packet_reader.read_i16::<LittleEndian>()?;
 
 	let channel_mapping_family = identification_packet_reader.read_u8()?;
+	properties.channel_mapping_family = channel_mapping_family;
 
 	// https://datatracker.ietf.org/doc/html/rfc7845.html#section-5.1.1
 	if (channel_mapping_family == 0 && properties.channels > 2)
@@ -113,13 +141,18 @@ where
 		decode_err!(@BAIL Opus, "Invalid channel count for mapping family");
 	}
 
-	properties.channel_mask =
-		ChannelMask::from_opus_channels(properties.channels).expect("Channel count is valid");
+	// Only mapping families 0 and 1 have a defined channel layout
+	if channel_mapping_family <= 1 {
+		properties.channel_mask =
+			ChannelMask::from_opus_channels(properties.channels).unwrap_or_default();
+	}
 
-	let last_page = find_last_page(data);
+	let last_page = find_last_page(data, first_page_header.stream_serial);
 	let file_length = data.seek(SeekFrom::End(0))?;
 
-	if let Ok(last_page) = last_page {
+	if let Ok((last_page, is_chained)) = last_page {
+		properties.is_chained = is_chained;
+
 		let first_page_abgp = first_page_header.abgp;
 		let last_page_abgp = last_page.header().abgp;
 
@@ -135,10 +168,7 @@ where
 			let length = (total_samples * 1000).div_round(48000);
 
 			// Get the stream length by subtracting the length of the header packets
-
-			// Safe to unwrap, metadata is checked prior
-			let metadata_packet = packets.get(1).unwrap();
-			let header_size = identification_packet.len() + metadata_packet.len();
+			let header_size = packets.iter().take(2).map(<[u8]>::len).sum::<usize>();
 
 			let stream_len = file_length - header_size as u64;
 
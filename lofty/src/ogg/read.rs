@@ -1,122 +1,388 @@
 use super::tag::VorbisComments;
 use super::verify_signature;
 use crate::config::{ParseOptions, ParsingMode};
-use crate::error::{ErrorKind, LoftyError, Result};
+use crate::error::{ErrorKind, LoftyError, ParseDiagnostic, Result};
 use crate::macros::{decode_err, err, parse_mode_choice};
-use crate::picture::{MimeType, Picture, PictureInformation, PictureType};
+use crate::picture::{decode_base64, MimeType, Picture, PictureInformation, PictureType};
 use crate::tag::Accessor;
+use crate::util::diagnostics;
 use crate::util::text::{utf16_decode, utf8_decode, utf8_decode_str};
 
 use std::borrow::Cow;
 use std::io::{Read, Seek, SeekFrom};
 
 use byteorder::{LittleEndian, ReadBytesExt};
-use data_encoding::BASE64;
 use ogg_pager::{Packets, PageHeader};
 
 pub type OGGTags = (Option<VorbisComments>, PageHeader, Packets);
 
-pub(crate) fn read_comments<R>(
-	data: &mut R,
-	mut len: u64,
+// Every field is prefixed with its length, so it takes up at least 4 bytes
+const FIELD_LEN_SIZE: u64 = 4;
+
+// Reading a field is cheap, but the declared number of fields can't be trusted for preallocation
+const MAX_PREALLOCATED_FIELDS: usize = 1024;
+
+/// An iterator over the fields of a Vorbis comments block
+///
+/// This reads a single `KEY=VALUE` field at a time, without building a [`VorbisComments`]. This is useful
+/// for when only a few fields are of interest, as iteration can stop at any point without reading
+/// the remainder of the block.
+///
+/// The fields are yielded exactly as they are stored, there is no special handling of pictures
+/// (`METADATA_BLOCK_PICTURE`/`COVERART`) or `TRACKNUMBER`s with the `current/total` format.
+///
+/// Fields that are skipped during parsing (e.g. fields with invalid keys in [`ParsingMode::Relaxed`],
+/// or pictures when [`ParseOptions::read_cover_art`] is disabled) are not yielded.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::config::{ParseOptions, WriteOptions};
+/// use lofty::ogg::{VorbisCommentIter, VorbisComments};
+/// use lofty::prelude::*;
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let mut tag = VorbisComments::new();
+/// # tag.set_title(String::from("Foo title"));
+/// # tag.set_artist(String::from("Bar artist"));
+/// # let mut tag_bytes = Vec::new();
+/// # tag.dump_to(&mut tag_bytes, WriteOptions::default())?;
+/// let len = tag_bytes.len() as u64;
+/// let mut fields = VorbisCommentIter::new(&tag_bytes[..], len, ParseOptions::new())?;
+///
+/// // Stop as soon as we find the artist
+/// let artist = fields.find_map(|field| match field {
+/// 	Ok((key, value)) if key.eq_ignore_ascii_case("ARTIST") => Some(value),
+/// 	_ => None,
+/// });
+///
+/// assert_eq!(artist.as_deref(), Some("Bar artist"));
+/// # Ok(()) }
+/// ```
+pub struct VorbisCommentIter<R> {
+	reader: R,
 	parse_options: ParseOptions,
-) -> Result<VorbisComments>
+	vendor: String,
+	remaining_len: u64,
+	remaining_fields: u32,
+	declared_fields: u32,
+	read_fields: u32,
+	reported_truncation: bool,
+}
+
+impl<R> VorbisCommentIter<R>
 where
 	R: Read,
 {
-	use crate::macros::try_vec;
+	/// Create a new [`VorbisCommentIter`]
+	///
+	/// This expects the reader to be positioned at the start of the block, past any signature or block header,
+	/// and `len` to be the length of the block. The vendor string is read immediately.
+	///
+	/// If the block declares more fields than could fit in `len`, the field count is truncated outside of
+	/// [`ParsingMode::Strict`].
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * The vendor string is invalid
+	/// * The field count is too large for the block, with [`ParsingMode::Strict`]
+	pub fn new(mut reader: R, len: u64, parse_options: ParseOptions) -> Result<Self> {
+		use crate::macros::try_vec;
+
+		let parse_mode = parse_options.parsing_mode;
+
+		let vendor_len = reader.read_u32::<LittleEndian>()?;
+
+		// The vendor length, vendor string, and field count
+		let Some(len) = len.checked_sub(u64::from(vendor_len) + 2 * FIELD_LEN_SIZE) else {
+			err!(SizeMismatch);
+		};
 
-	let parse_mode = parse_options.parsing_mode;
+		let mut vendor_bytes = Vec::new();
+		if parse_options.verify_item_size(u64::from(vendor_len))? {
+			vendor_bytes = try_vec![0; vendor_len as usize];
+			reader.read_exact(&mut vendor_bytes)?;
+		} else {
+			skip(&mut reader, u64::from(vendor_len))?;
+		}
 
-	let vendor_len = data.read_u32::<LittleEndian>()?;
-	if u64::from(vendor_len) > len {
-		err!(SizeMismatch);
+		let vendor;
+		match utf8_decode(vendor_bytes) {
+			Ok(v) => vendor = v,
+			Err(e) => {
+				// The actions following this are not spec-compliant in the slightest, so
+				// we need to short circuit if strict.
+				if parse_mode == ParsingMode::Strict {
+					return Err(e);
+				}
+
+				log::warn!("Possibly corrupt vendor string, attempting to recover");
+
+				// Some vendor strings have invalid mixed UTF-8 and UTF-16 encodings.
+				// This seems to work, while preserving the string opposed to using
+				// the replacement character
+				let LoftyError {
+					kind: ErrorKind::StringFromUtf8(e),
+				} = e
+				else {
+					return Err(e);
+				};
+				let s = e
+					.as_bytes()
+					.iter()
+					.map(|c| u16::from(*c))
+					.collect::<Vec<_>>();
+
+				match utf16_decode(&s) {
+					Ok(v) => {
+						log::warn!("Vendor string recovered as: '{v}'");
+						vendor = v;
+					},
+					Err(e) => {
+						return Err(decode_err!(
+							@TAG VorbisComments,
+							"Comments have an invalid vendor string",
+							source: e
+						))
+					},
+				}
+			},
+		};
+
+		let declared_fields = reader.read_u32::<LittleEndian>()?;
+		let mut number_of_items = declared_fields;
+
+		let max_items = len / FIELD_LEN_SIZE;
+		let reported_truncation = u64::from(number_of_items) > max_items;
+		if reported_truncation {
+			if parse_mode == ParsingMode::Strict {
+				err!(SizeMismatch);
+			}
+
+			log::warn!(
+				"Vorbis Comments declare {number_of_items} fields, but only {max_items} can fit, \
+				 truncating"
+			);
+			diagnostics::report(ParseDiagnostic::TooManyVorbisCommentFields {
+				declared: number_of_items,
+				max: max_items as u32,
+			});
+
+			// Smaller than the declared count, so this can't truncate
+			number_of_items = max_items as u32;
+		}
+
+		Ok(Self {
+			reader,
+			parse_options,
+			vendor,
+			remaining_len: len,
+			remaining_fields: number_of_items,
+			declared_fields,
+			read_fields: 0,
+			reported_truncation,
+		})
+	}
+
+	/// The vendor string
+	pub fn vendor(&self) -> &str {
+		&self.vendor
 	}
 
-	let mut vendor_bytes = try_vec![0; vendor_len as usize];
-	data.read_exact(&mut vendor_bytes)?;
+	/// The number of fields left to read
+	///
+	/// NOTE: Fields may be skipped during parsing, so this is an upper bound.
+	pub fn remaining_fields(&self) -> u32 {
+		self.remaining_fields
+	}
+
+	/// Extract the reader, discarding the [`VorbisCommentIter`]
+	///
+	/// NOTE: The reader will be positioned wherever iteration stopped, which is not necessarily the end of the block.
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
 
-	len -= u64::from(vendor_len);
+	fn read_field(&mut self) -> Result<Option<(String, String)>> {
+		use crate::macros::try_vec;
 
-	let vendor;
-	match utf8_decode(vendor_bytes) {
-		Ok(v) => vendor = v,
-		Err(e) => {
-			// The actions following this are not spec-compliant in the slightest, so
-			// we need to short circuit if strict.
+		let parse_mode = self.parse_options.parsing_mode;
+
+		if self.remaining_len < FIELD_LEN_SIZE {
 			if parse_mode == ParsingMode::Strict {
-				return Err(e);
+				err!(SizeMismatch);
 			}
 
-			log::warn!("Possibly corrupt vendor string, attempting to recover");
-
-			// Some vendor strings have invalid mixed UTF-8 and UTF-16 encodings.
-			// This seems to work, while preserving the string opposed to using
-			// the replacement character
-			let LoftyError {
-				kind: ErrorKind::StringFromUtf8(e),
-			} = e
-			else {
-				return Err(e);
-			};
-			let s = e
-				.as_bytes()
-				.iter()
-				.map(|c| u16::from(*c))
-				.collect::<Vec<_>>();
-
-			match utf16_decode(&s) {
-				Ok(v) => {
-					log::warn!("Vendor string recovered as: '{v}'");
-					vendor = v;
-				},
-				Err(_) => decode_err!(@BAIL "OGG: File has an invalid vendor string"),
+			log::warn!(
+				"Vorbis Comments block ended after {} of {} fields",
+				self.read_fields,
+				self.declared_fields
+			);
+			if !self.reported_truncation {
+				diagnostics::report(ParseDiagnostic::TooManyVorbisCommentFields {
+					declared: self.declared_fields,
+					max: self.read_fields,
+				});
 			}
-		},
-	};
 
-	let number_of_items = data.read_u32::<LittleEndian>()?;
-	if number_of_items > (len >> 2) as u32 {
-		err!(SizeMismatch);
-	}
+			self.remaining_fields = 0;
+			return Ok(None);
+		}
 
-	let mut tag = VorbisComments {
-		vendor,
-		items: Vec::with_capacity(number_of_items as usize),
-		pictures: Vec::new(),
-	};
+		let comment_len = self.reader.read_u32::<LittleEndian>()?;
+		self.remaining_len -= FIELD_LEN_SIZE;
+		self.read_fields += 1;
 
-	for _ in 0..number_of_items {
-		let comment_len = data.read_u32::<LittleEndian>()?;
-		if u64::from(comment_len) > len {
-			err!(SizeMismatch);
+		if u64::from(comment_len) > self.remaining_len {
+			if parse_mode == ParsingMode::Strict {
+				err!(SizeMismatch);
+			}
+
+			log::warn!(
+				"Vorbis Comments field has a size of {comment_len}, but only {} bytes remain, \
+				 discarding the remaining fields",
+				self.remaining_len
+			);
+			diagnostics::report(ParseDiagnostic::OversizedVorbisCommentField {
+				size: comment_len,
+				remaining: self.remaining_len,
+			});
+
+			// The position of any following fields is unknown
+			self.remaining_fields = 0;
+			return Ok(None);
 		}
 
-		let mut comment_bytes = try_vec![0; comment_len as usize];
-		data.read_exact(&mut comment_bytes)?;
+		self.remaining_len -= u64::from(comment_len);
 
-		len -= u64::from(comment_len);
+		if !self
+			.parse_options
+			.verify_item_size(u64::from(comment_len))?
+		{
+			skip(&mut self.reader, u64::from(comment_len))?;
+			return Ok(None);
+		}
+
+		let mut comment_bytes = try_vec![0; comment_len as usize];
+		self.reader.read_exact(&mut comment_bytes)?;
 
 		// KEY=VALUE
 		let mut comment_split = comment_bytes.splitn(2, |b| *b == b'=');
 
 		let Some(key) = comment_split.next() else {
-			continue;
+			return Ok(None);
 		};
 
 		// Make sure there was a separator present, otherwise just move on
 		let Some(value) = comment_split.next() else {
 			log::warn!("No separator found in field, discarding");
-			continue;
+			diagnostics::report(ParseDiagnostic::InvalidVorbisCommentField { key: None });
+			return Ok(None);
 		};
 
-		match key {
-			k if k.eq_ignore_ascii_case(b"METADATA_BLOCK_PICTURE") => {
-				if !parse_options.read_cover_art {
-					continue;
+		if !self.parse_options.read_cover_art
+			&& (key.eq_ignore_ascii_case(b"METADATA_BLOCK_PICTURE")
+				|| key.eq_ignore_ascii_case(b"COVERART"))
+		{
+			return Ok(None);
+		}
+
+		// The valid range is 0x20..=0x7D not including 0x3D
+		let key = if key.iter().all(|c| (b' '..=b'}').contains(c) && *c != b'=') {
+			// SAFETY: We just verified that all of the bytes fall within the subset of ASCII
+			unsafe { String::from_utf8_unchecked(key.to_vec()) }
+		} else {
+			let key = String::from_utf8_lossy(key).into_owned();
+
+			parse_mode_choice!(
+				parse_mode,
+				STRICT: decode_err!(@BAIL @TAG VorbisComments, "Comments contain an invalid key"),
+				RELAXED: {
+					// Keep the field, so the caller can decide what to do with it
+					log::warn!("Keeping field with an invalid name {key:?}");
+					diagnostics::report(ParseDiagnostic::InvalidVorbisFieldName { key: key.clone() });
+					key
+				},
+				DEFAULT: {
+					// Otherwise discard invalid keys
+					diagnostics::report(ParseDiagnostic::InvalidVorbisCommentField { key: Some(key) });
+					return Ok(None);
+				}
+			)
+		};
+
+		match utf8_decode_str(value) {
+			Ok(value) => Ok(Some((key, value.to_owned()))),
+			Err(e) => {
+				if parse_mode == ParsingMode::Strict {
+					return Err(e);
 				}
 
-				match Picture::from_flac_bytes(value, true, parse_mode) {
+				log::warn!("Non UTF-8 value found, discarding field {key:?}");
+				diagnostics::report(ParseDiagnostic::InvalidVorbisCommentField { key: Some(key) });
+				Ok(None)
+			},
+		}
+	}
+}
+
+impl<R> Iterator for VorbisCommentIter<R>
+where
+	R: Read,
+{
+	type Item = Result<(String, String)>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.remaining_fields > 0 {
+			self.remaining_fields -= 1;
+			match self.read_field() {
+				Ok(Some(field)) => return Some(Ok(field)),
+				Ok(None) => {},
+				Err(e) => {
+					self.remaining_fields = 0;
+					return Some(Err(e));
+				},
+			}
+		}
+
+		None
+	}
+}
+
+fn skip<R: Read>(reader: &mut R, len: u64) -> Result<()> {
+	let skipped = std::io::copy(&mut reader.take(len), &mut std::io::sink())?;
+	if skipped != len {
+		err!(SizeMismatch);
+	}
+
+	Ok(())
+}
+
+pub(crate) fn read_comments<R>(
+	data: &mut R,
+	len: u64,
+	parse_options: ParseOptions,
+) -> Result<VorbisComments>
+where
+	R: Read,
+{
+	let parse_mode = parse_options.parsing_mode;
+
+	let mut fields = VorbisCommentIter::new(data, len, parse_options)?;
+
+	let mut tag = VorbisComments {
+		vendor: std::mem::take(&mut fields.vendor),
+		items: Vec::with_capacity((fields.remaining_fields as usize).min(MAX_PREALLOCATED_FIELDS)),
+		pictures: Vec::new(),
+	};
+
+	for field in fields {
+		let (key, value) = field?;
+
+		match key {
+			k if k.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE") => {
+				match Picture::from_flac_bytes(value.as_bytes(), true, parse_mode) {
 					Ok(picture) => tag.pictures.push(picture),
 					Err(e) => {
 						if parse_mode == ParsingMode::Strict {
@@ -128,11 +394,7 @@ where
 					},
 				}
 			},
-			k if k.eq_ignore_ascii_case(b"COVERART") => {
-				if !parse_options.read_cover_art {
-					continue;
-				}
-
+			k if k.eq_ignore_ascii_case("COVERART") => {
 				// `COVERART` is an old deprecated image storage format. We have to convert it
 				// to a `METADATA_BLOCK_PICTURE` for it to be useful.
 				//
@@ -142,23 +404,27 @@ where
 					 `METADATA_BLOCK_PICTURE`"
 				);
 
-				let picture_data = BASE64.decode(value);
+				let picture_data = decode_base64(value.as_bytes());
 
 				match picture_data {
-					Ok(picture_data) => {
+					Some(picture_data) => {
 						let mime_type = Picture::mimetype_from_bin(&picture_data)
 							.unwrap_or_else(|_| MimeType::Unknown(String::from("image/")));
 
+						let information = PictureInformation::from_picture_data(&picture_data)
+							.unwrap_or_default();
+
 						let picture = Picture {
 							pic_type: PictureType::Other,
 							mime_type: Some(mime_type),
 							description: None,
 							data: Cow::from(picture_data),
+							skipped_data_len: None,
 						};
 
-						tag.pictures.push((picture, PictureInformation::default()))
+						tag.pictures.push((picture, information))
 					},
-					Err(_) => {
+					None => {
 						if parse_mode == ParsingMode::Strict {
 							return Err(LoftyError::new(ErrorKind::NotAPicture));
 						}
@@ -169,62 +435,24 @@ where
 				}
 			},
 			// Support the case of TRACKNUMBER being equal to current/total
-			k if k.eq_ignore_ascii_case(b"TRACKNUMBER") => {
-				match utf8_decode_str(value) {
-					Ok(value) => {
-						// try to parse as current/total
-						let mut value_split = value.splitn(2, '/');
-						let track_number: Option<u32> =
-							value_split.next().and_then(|b| b.parse().ok());
-						let track_total: Option<u32> =
-							value_split.next().and_then(|b| b.parse().ok());
-
-						if let Some(n) = track_number {
-							tag.set_track(n);
-						} else {
-							// Probably some other format, like a vinyl track number (A1, B1, etc.).
-							// Just leave it up to the caller to deal with.
-							tag.items
-								.push((String::from("TRACKNUMBER"), value.to_owned()));
-						}
-						if let Some(n) = track_total {
-							tag.set_track_total(n);
-						}
-					},
-					Err(e) => {
-						if parse_mode == ParsingMode::Strict {
-							return Err(e);
-						}
-
-						log::warn!("Non UTF-8 value found, discarding field {key:?}");
-						continue;
-					},
+			k if k.eq_ignore_ascii_case("TRACKNUMBER") => {
+				// try to parse as current/total
+				let mut value_split = value.splitn(2, '/');
+				let track_number: Option<u32> = value_split.next().and_then(|b| b.parse().ok());
+				let track_total: Option<u32> = value_split.next().and_then(|b| b.parse().ok());
+
+				if let Some(n) = track_number {
+					tag.set_track(n);
+				} else {
+					// Probably some other format, like a vinyl track number (A1, B1, etc.).
+					// Just leave it up to the caller to deal with.
+					tag.items.push((String::from("TRACKNUMBER"), value));
 				}
-			},
-			// The valid range is 0x20..=0x7D not including 0x3D
-			k if k.iter().all(|c| (b' '..=b'}').contains(c) && *c != b'=') => {
-				// SAFETY: We just verified that all of the bytes fall within the subset of ASCII
-				let key = unsafe { String::from_utf8_unchecked(k.to_vec()) };
-
-				match utf8_decode_str(value) {
-					Ok(value) => tag.items.push((key, value.to_owned())),
-					Err(e) => {
-						if parse_mode == ParsingMode::Strict {
-							return Err(e);
-						}
-
-						log::warn!("Non UTF-8 value found, discarding field {key:?}");
-						continue;
-					},
+				if let Some(n) = track_total {
+					tag.set_track_total(n);
 				}
 			},
-			_ => {
-				parse_mode_choice!(
-					parse_mode,
-					STRICT: decode_err!(@BAIL "OGG: Vorbis comments contain an invalid key"),
-					// Otherwise discard invalid keys
-				)
-			},
+			_ => tag.items.push((key, value)),
 		}
 	}
 
@@ -250,7 +478,20 @@ where
 	data.seek(SeekFrom::Start(start))?;
 
 	// Read the header packets
-	let packets = Packets::read_count(data, packets_to_read)?;
+	let packets = match Packets::read_count(data, packets_to_read) {
+		// Only the identification and comment packets are needed, any others (e.g. the Vorbis setup
+		// header) can be lost to a truncated stream.
+		Err(e) if packets_to_read > 2 && parse_options.parsing_mode == ParsingMode::Relaxed => {
+			log::warn!("OGG: Unable to read all header packets, the stream may be truncated: {e}");
+			diagnostics::report(ParseDiagnostic::IncompleteProperties {
+				message: e.to_string(),
+			});
+
+			data.seek(SeekFrom::Start(start))?;
+			Packets::read_count(data, 2)?
+		},
+		packets => packets?,
+	};
 
 	let identification_packet = packets
 		.get(0)
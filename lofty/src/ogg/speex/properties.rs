@@ -12,6 +12,7 @@ use ogg_pager::{Packets, PageHeader};
 
 /// A Speex file's audio properties
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct SpeexProperties {
 	pub(crate) duration: Duration,
@@ -23,6 +24,7 @@ pub struct SpeexProperties {
 	pub(crate) overall_bitrate: u32,
 	pub(crate) audio_bitrate: u32,
 	pub(crate) nominal_bitrate: i32,
+	pub(crate) is_chained: bool,
 }
 
 impl From<SpeexProperties> for FileProperties {
@@ -84,6 +86,14 @@ impl SpeexProperties {
 	pub fn nominal_bitrate(&self) -> i32 {
 		self.nominal_bitrate
 	}
+
+	/// Whether the file contains chained streams
+	///
+	/// A chained file is several complete streams concatenated together. Only the first stream is
+	/// used for tags and properties, and the remaining streams are left untouched when writing.
+	pub fn is_chained(&self) -> bool {
+		self.is_chained
+	}
 }
 
 pub(in crate::ogg) fn read_properties<R>(
@@ -141,7 +151,7 @@ where
 
 	properties.vbr = identification_packet_reader.read_u32::<LittleEndian>()? == 1;
 
-	let last_page = find_last_page(data);
+	let last_page = find_last_page(data, first_page_header.stream_serial);
 	let file_length = data.seek(SeekFrom::End(0))?;
 
 	// The stream length is the entire file minus the two mandatory metadata packets
@@ -151,7 +161,9 @@ where
 	// This is used for bitrate calculation, it should be the length in
 	// milliseconds, but if we can't determine it then we'll just use 1000.
 	let mut length = 1000;
-	if let Ok(last_page) = last_page {
+	if let Ok((last_page, is_chained)) = last_page {
+		properties.is_chained = is_chained;
+
 		let first_page_abgp = first_page_header.abgp;
 		let last_page_abgp = last_page.header().abgp;
 
@@ -1,19 +1,23 @@
-use crate::config::WriteOptions;
-use crate::error::{LoftyError, Result};
+use crate::config::{ParseOptions, PrettyPrintOptions, WriteOptions};
+use crate::error::{LoftyError, ParseDiagnostic, Result};
 use crate::file::FileType;
+use crate::flac::CueSheet;
 use crate::macros::err;
 use crate::ogg::picture_storage::OggPictureStorage;
 use crate::ogg::write::OGGFormat;
 use crate::picture::{Picture, PictureInformation};
 use crate::probe::Probe;
+use crate::tag::items::Popularimeter;
 use crate::tag::{
 	try_parse_year, Accessor, ItemKey, ItemValue, MergeTag, SplitTag, Tag, TagExt, TagItem, TagType,
 };
-use crate::util::flag_item;
 use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::pretty_print::{PrettyPrinter, Value};
+use crate::util::{diagnostics, flag_item};
 
 use std::borrow::Cow;
-use std::io::Write;
+use std::fmt;
+use std::io::{Read, Write};
 use std::ops::Deref;
 
 use lofty_attr::tag;
@@ -61,7 +65,7 @@ macro_rules! impl_accessor {
 #[derive(Default, PartialEq, Eq, Debug, Clone)]
 #[tag(
 	description = "Vorbis comments",
-	supported_formats(Flac, Opus, Speex, Vorbis)
+	supported_formats(Flac, OggFlac, Opus, Speex, Vorbis)
 )]
 pub struct VorbisComments {
 	/// An identifier for the encoding software
@@ -88,6 +92,62 @@ impl VorbisComments {
 		Self::default()
 	}
 
+	/// Parse `VorbisComments` from a reader
+	///
+	/// This accepts exactly what [`TagExt::dump_to`] produces: the vendor string, followed by the
+	/// comments. There is no packet signature or framing bit, and `len` is the size of the
+	/// entire block. The reader is expected to be positioned at the start of the vendor string length.
+	///
+	/// # Errors
+	///
+	/// * Failure to read from `reader`
+	/// * The vendor string or any of the comments extend past `len`
+	/// * Any of the comments are invalid, depending on the [`ParsingMode`](crate::config::ParsingMode)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, WriteOptions};
+	/// use lofty::ogg::VorbisComments;
+	/// use lofty::tag::{Accessor, TagExt};
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut vorbis_comments = VorbisComments::new();
+	/// vorbis_comments.set_title(String::from("Foo title"));
+	///
+	/// let mut bytes = Vec::new();
+	/// vorbis_comments.dump_to(&mut bytes, WriteOptions::default())?;
+	///
+	/// let len = bytes.len() as u64;
+	/// let parsed_tag = VorbisComments::parse(&mut Cursor::new(bytes), len, ParseOptions::new())?;
+	/// assert_eq!(parsed_tag.title().as_deref(), Some("Foo title"));
+	/// # Ok(()) }
+	/// ```
+	pub fn parse<R>(reader: &mut R, len: u64, parse_options: ParseOptions) -> Result<Self>
+	where
+		R: Read,
+	{
+		super::read::read_comments(reader, len, parse_options)
+	}
+
+	/// Same as [`VorbisComments::parse`], also returning any [`ParseDiagnostic`]s
+	///
+	/// # Errors
+	///
+	/// See [`VorbisComments::parse`]
+	pub fn parse_with_diagnostics<R>(
+		reader: &mut R,
+		len: u64,
+		parse_options: ParseOptions,
+	) -> Result<(Self, Vec<ParseDiagnostic>)>
+	where
+		R: Read,
+	{
+		let (tag, diagnostics) = diagnostics::collect(|| Self::parse(reader, len, parse_options));
+		Ok((tag?, diagnostics))
+	}
+
 	/// Returns the vendor string
 	///
 	/// ```rust
@@ -300,11 +360,89 @@ impl VorbisComments {
 
 		self.items.drain(..split_idx).map(|(_, v)| v)
 	}
+
+	/// Gets the embedded cue sheet (`CUESHEET`)
+	///
+	/// # Errors
+	///
+	/// See [`CueSheet::parse_text`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::ogg::VorbisComments;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut tag = VorbisComments::default();
+	/// assert!(tag.cuesheet()?.is_none());
+	///
+	/// tag.insert(
+	/// 	String::from("CUESHEET"),
+	/// 	String::from("FILE \"album.flac\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00"),
+	/// );
+	///
+	/// let cue_sheet = tag.cuesheet()?.unwrap();
+	/// assert_eq!(cue_sheet.tracks.len(), 1);
+	/// # Ok(()) }
+	/// ```
+	pub fn cuesheet(&self) -> Result<Option<CueSheet>> {
+		self.get("CUESHEET").map(CueSheet::parse_text).transpose()
+	}
+
+	/// Sets the embedded cue sheet (`CUESHEET`)
+	///
+	/// This will replace any existing cue sheet.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::flac::CueSheet;
+	/// use lofty::ogg::VorbisComments;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut cue_sheet = CueSheet::default();
+	/// cue_sheet.title = Some(String::from("Foo album"));
+	///
+	/// let mut tag = VorbisComments::default();
+	/// tag.set_cuesheet(&cue_sheet);
+	///
+	/// assert_eq!(tag.cuesheet()?, Some(cue_sheet));
+	/// # Ok(()) }
+	/// ```
+	pub fn set_cuesheet(&mut self, cue_sheet: &CueSheet) {
+		self.insert(String::from("CUESHEET"), cue_sheet.to_string());
+	}
+
+	/// Writes a line-oriented listing of the fields and pictures to `writer`
+	///
+	/// The vendor string is not included. Pictures are keyed by `METADATA_BLOCK_PICTURE`.
+	///
+	/// See [`PrettyPrintOptions`] for the format.
+	///
+	/// # Errors
+	///
+	/// * Writing to `writer` fails
+	pub fn pretty_print(
+		&self,
+		writer: &mut impl fmt::Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		let mut printer = PrettyPrinter::default();
+		for (key, value) in &self.items {
+			printer.push(key.as_str(), "Text", Value::Text(Cow::Borrowed(value)));
+		}
+
+		for (picture, _) in &self.pictures {
+			printer.push("METADATA_BLOCK_PICTURE", "Picture", Value::Picture(picture));
+		}
+
+		printer.finish(writer, options)
+	}
 }
 
 // A case-insensitive field name that may consist of ASCII 0x20 through 0x7D, 0x3D ('=') excluded.
 // ASCII 0x41 through 0x5A inclusive (A-Z) is to be considered equivalent to ASCII 0x61 through 0x7A inclusive (a-z).
-fn verify_key(key: &str) -> bool {
+pub(crate) fn verify_key(key: &str) -> bool {
 	if key.is_empty() {
 		return false;
 	}
@@ -427,6 +565,18 @@ impl Accessor for VorbisComments {
 		let _ = self.remove("DATE");
 		let _ = self.remove("YEAR");
 	}
+
+	fn popularimeter(&self) -> Option<Popularimeter> {
+		self.get("RATING").and_then(Popularimeter::from_text)
+	}
+
+	fn set_popularimeter(&mut self, value: Popularimeter) {
+		self.insert(String::from("RATING"), value.rating().to_string());
+	}
+
+	fn remove_popularimeter(&mut self) {
+		let _ = self.remove("RATING");
+	}
 }
 
 impl TagExt for VorbisComments {
@@ -500,6 +650,20 @@ impl TagExt for VorbisComments {
 		.dump_to(writer, write_options)
 	}
 
+	/// Gets the size of the tag, as it would be written by [`VorbisComments::dump_to`]
+	///
+	/// # Errors
+	///
+	/// See [`VorbisComments::dump_to`]
+	fn serialized_len(&self, write_options: WriteOptions) -> std::result::Result<u64, Self::Err> {
+		VorbisCommentsRef {
+			vendor: Cow::from(self.vendor.as_str()),
+			items: self.items.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+			pictures: self.pictures.iter().map(|(p, i)| (p, *i)),
+		}
+		.serialized_len(write_options)
+	}
+
 	fn clear(&mut self) {
 		self.items.clear();
 		self.pictures.clear();
@@ -530,10 +694,9 @@ impl SplitTag for VorbisComments {
 		let mut tag = Tag::new(TagType::VorbisComments);
 
 		for (k, v) in std::mem::take(&mut self.items) {
-			tag.items.push(TagItem::new(
-				ItemKey::from_key(TagType::VorbisComments, &k),
-				ItemValue::Text(v),
-			));
+			let item_key = ItemKey::from_key(TagType::VorbisComments, &k);
+			tag.record_original_key(&item_key, &k);
+			tag.items.push(TagItem::new(item_key, ItemValue::Text(v)));
 		}
 
 		// We need to preserve the vendor string
@@ -571,7 +734,7 @@ impl MergeTag for SplitTagRemainder {
 			merged.vendor = val;
 		}
 
-		for item in tag.items {
+		for item in std::mem::take(&mut tag.items) {
 			let item_key = item.item_key;
 			let item_value = item.item_value;
 
@@ -589,22 +752,17 @@ impl MergeTag for SplitTagRemainder {
 				val = u8::from(flag).to_string();
 			}
 
-			let key;
-			match item_key {
-				ItemKey::Unknown(unknown) => {
-					if !verify_key(&unknown) {
-						continue; // Bad key, discard the item
-					}
-
-					key = unknown
-				},
-				_ => match item_key.map_key(TagType::VorbisComments, false) {
-					Some(mapped_key) => key = mapped_key.to_string(),
-					None => continue, // No mapping exists, discard the item
-				},
+			if let ItemKey::Unknown(unknown) = &item_key {
+				if !verify_key(unknown) {
+					continue; // Bad key, discard the item
+				}
 			}
 
-			merged.items.push((key, val));
+			let Some(key) = tag.styled_key(&item_key, TagType::VorbisComments, true) else {
+				continue; // No mapping exists, discard the item
+			};
+
+			merged.items.push((key.into_owned(), val));
 		}
 
 		for picture in tag.pictures {
@@ -666,6 +824,10 @@ where
 			return crate::flac::write::write_to_inner(file, self, write_options);
 		}
 
+		if file_type == FileType::OggFlac {
+			return super::flac::write::write_to(file, self, write_options);
+		}
+
 		let (format, header_packet_count) = OGGFormat::from_filetype(file_type);
 
 		super::write::write(file, self, format, header_packet_count, write_options)
@@ -674,30 +836,40 @@ where
 	pub(crate) fn dump_to<W: Write>(
 		&mut self,
 		writer: &mut W,
-		_write_options: WriteOptions,
+		write_options: WriteOptions,
 	) -> Result<()> {
-		let metadata_packet = super::write::create_metadata_packet(self, &[], false)?;
+		let metadata_packet =
+			super::write::create_metadata_packet(self, &[], false, write_options)?;
 		writer.write_all(&metadata_packet)?;
 		Ok(())
 	}
+
+	pub(crate) fn serialized_len(&mut self, write_options: WriteOptions) -> Result<u64> {
+		super::write::metadata_packet_len(self, write_options)
+	}
 }
 
 pub(crate) fn create_vorbis_comments_ref(
 	tag: &Tag,
 ) -> (
 	&str,
-	impl Iterator<Item = (&str, &str)>,
+	Vec<(Cow<'_, str>, &str)>,
 	impl Iterator<Item = (&Picture, PictureInformation)>,
 ) {
 	let vendor = tag.get_string(&ItemKey::EncoderSoftware).unwrap_or("");
 
-	let items = tag.items.iter().filter_map(|i| match i.value() {
-		ItemValue::Text(val) | ItemValue::Locator(val) => i
-			.key()
-			.map_key(TagType::VorbisComments, true)
-			.map(|key| (key, val.as_str())),
-		_ => None,
-	});
+	// The keys may need to be respelled (see `GlobalOptions::key_style`), so they can't be
+	// borrowed for the whole write
+	let items = tag
+		.items
+		.iter()
+		.filter_map(|i| match i.value() {
+			ItemValue::Text(val) | ItemValue::Locator(val) => tag
+				.styled_key(i.key(), TagType::VorbisComments, true)
+				.map(|key| (key, val.as_str())),
+			_ => None,
+		})
+		.collect();
 
 	let pictures = tag
 		.pictures
@@ -709,9 +881,11 @@ pub(crate) fn create_vorbis_comments_ref(
 #[cfg(test)]
 mod tests {
 	use crate::config::{ParseOptions, ParsingMode, WriteOptions};
+	use crate::error::ErrorKind;
 	use crate::ogg::{OggPictureStorage, VorbisComments};
 	use crate::picture::{MimeType, Picture, PictureType};
 	use crate::prelude::*;
+	use crate::tag::items::Popularimeter;
 	use crate::tag::{ItemValue, Tag, TagItem, TagType};
 	use std::io::Cursor;
 
@@ -746,6 +920,33 @@ mod tests {
 		assert_eq!(expected_tag, parsed_tag);
 	}
 
+	#[test_log::test]
+	fn comment_iter_early_exit() {
+		use crate::ogg::VorbisCommentIter;
+		use crate::tag::utils::test_utils::{read_path, CountingReader};
+
+		let file_cont = read_path("tests/tags/assets/test.vorbis");
+		let mut reader = CountingReader::new(&file_cont[..]);
+
+		let parse_options = ParseOptions::new().parsing_mode(ParsingMode::Strict);
+		let mut fields =
+			VorbisCommentIter::new(&mut reader, file_cont.len() as u64, parse_options).unwrap();
+		assert_eq!(fields.vendor(), "Lavf58.76.100");
+
+		let first = fields.next().unwrap().unwrap();
+		assert_eq!(first, (String::from("ALBUM"), String::from("Baz album")));
+
+		// Only the vendor string and first field should have been read
+		assert!(reader.bytes_read < file_cont.len() as u64);
+
+		let all_fields =
+			VorbisCommentIter::new(&file_cont[..], file_cont.len() as u64, parse_options)
+				.unwrap()
+				.collect::<crate::error::Result<Vec<_>>>()
+				.unwrap();
+		assert_eq!(all_fields, read_tag(&file_cont).items);
+	}
+
 	#[test_log::test]
 	fn vorbis_comments_re_read() {
 		let file_cont = crate::tag::utils::test_utils::read_path("tests/tags/assets/test.vorbis");
@@ -931,4 +1132,190 @@ mod tests {
 		assert_eq!(tag.pictures().len(), 0); // Artist, no picture
 		assert!(tag.artist().is_some());
 	}
+
+	#[test_log::test]
+	fn field_exceeds_item_limit() {
+		let mut tag = VorbisComments::default();
+		tag.set_artist(String::from("Foo artist"));
+		tag.set_title("A".repeat(100));
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::new()).unwrap();
+
+		let read = |parse_options| {
+			crate::ogg::read::read_comments(
+				&mut Cursor::new(&writer),
+				writer.len() as u64,
+				parse_options,
+			)
+		};
+
+		let item_limit = || ParseOptions::new().max_item_size(64);
+		let err = read(item_limit().parsing_mode(ParsingMode::Strict)).unwrap_err();
+		assert!(matches!(
+			err.kind(),
+			ErrorKind::TooLarge {
+				limit: 64,
+				requested: 106
+			}
+		));
+
+		let tag = read(item_limit()).unwrap();
+		assert_eq!(tag.artist().as_deref(), Some("Foo artist"));
+		assert!(tag.title().is_none());
+	}
+
+	fn dump_with_two_fields() -> Vec<u8> {
+		let mut tag = VorbisComments::default();
+		tag.set_artist(String::from("Foo artist"));
+		tag.set_title(String::from("Bar title"));
+
+		let mut writer = Vec::new();
+		tag.dump_to(&mut writer, WriteOptions::new()).unwrap();
+		writer
+	}
+
+	// The offset of the field count, directly after the vendor string
+	fn field_count_offset(comments: &[u8]) -> usize {
+		4 + u32::from_le_bytes(comments[..4].try_into().unwrap()) as usize
+	}
+
+	#[test_log::test]
+	fn too_many_fields() {
+		use crate::error::ParseDiagnostic;
+
+		let mut comments = dump_with_two_fields();
+		let count_offset = field_count_offset(&comments);
+		comments[count_offset..count_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+		let read = |parsing_mode| {
+			crate::util::diagnostics::collect(|| {
+				crate::ogg::read::read_comments(
+					&mut Cursor::new(&comments),
+					comments.len() as u64,
+					ParseOptions::new().parsing_mode(parsing_mode),
+				)
+			})
+		};
+
+		let (err, _) = read(ParsingMode::Strict);
+		assert!(matches!(err.unwrap_err().kind(), ErrorKind::SizeMismatch));
+
+		let (tag, diagnostics) = read(ParsingMode::Relaxed);
+		let tag = tag.unwrap();
+		assert_eq!(tag.artist().as_deref(), Some("Foo artist"));
+		assert_eq!(tag.title().as_deref(), Some("Bar title"));
+		assert_eq!(diagnostics.len(), 1);
+		assert!(matches!(
+			diagnostics[0],
+			ParseDiagnostic::TooManyVorbisCommentFields {
+				declared: u32::MAX,
+				..
+			}
+		));
+	}
+
+	#[test_log::test]
+	fn oversized_field() {
+		use crate::error::ParseDiagnostic;
+
+		let mut comments = dump_with_two_fields();
+		let count_offset = field_count_offset(&comments);
+
+		// Make the second field claim a size larger than the block
+		let first_field_offset = count_offset + 4;
+		let first_field_len = u32::from_le_bytes(
+			comments[first_field_offset..first_field_offset + 4]
+				.try_into()
+				.unwrap(),
+		) as usize;
+		let second_field_offset = first_field_offset + 4 + first_field_len;
+		comments[second_field_offset..second_field_offset + 4]
+			.copy_from_slice(&0x00FF_FFFF_u32.to_le_bytes());
+
+		let read = |parsing_mode| {
+			crate::util::diagnostics::collect(|| {
+				crate::ogg::read::read_comments(
+					&mut Cursor::new(&comments),
+					comments.len() as u64,
+					ParseOptions::new().parsing_mode(parsing_mode),
+				)
+			})
+		};
+
+		let (err, _) = read(ParsingMode::Strict);
+		assert!(matches!(err.unwrap_err().kind(), ErrorKind::SizeMismatch));
+
+		let (tag, diagnostics) = read(ParsingMode::BestAttempt);
+		let tag = tag.unwrap();
+		assert_eq!(tag.len(), 1);
+		assert_eq!(diagnostics.len(), 1);
+		assert!(matches!(
+			diagnostics[0],
+			ParseDiagnostic::OversizedVorbisCommentField {
+				size: 0x00FF_FFFF,
+				..
+			}
+		));
+	}
+
+	#[test_log::test]
+	fn key_style() {
+		use crate::config::{apply_global_options, GlobalOptions, KeyStyle};
+
+		fn contains(bytes: &[u8], needle: &str) -> bool {
+			bytes.windows(needle.len()).any(|w| w == needle.as_bytes())
+		}
+
+		let vorbis_comments = VorbisComments {
+			items: vec![
+				(String::from("AlbumArtist"), String::from("Foo artist")),
+				(String::from("title"), String::from("Foo title")),
+			],
+			..Default::default()
+		};
+		let tag = Tag::from(vorbis_comments);
+
+		for (key_style, album_artist, title) in [
+			(KeyStyle::Format, "ALBUMARTIST=", "TITLE="),
+			(KeyStyle::Uppercase, "ALBUMARTIST=", "TITLE="),
+			(KeyStyle::TitleCase, "AlbumArtist=", "Title="),
+			(KeyStyle::Preserve, "AlbumArtist=", "title="),
+		] {
+			apply_global_options(GlobalOptions::new().key_style(key_style));
+
+			let mut written = Vec::new();
+			tag.dump_to(&mut written, WriteOptions::default()).unwrap();
+
+			let mut merged = Vec::new();
+			VorbisComments::from(tag.clone())
+				.dump_to(&mut merged, WriteOptions::default())
+				.unwrap();
+
+			for bytes in [written, merged] {
+				assert!(contains(&bytes, album_artist), "{key_style:?}");
+				assert!(contains(&bytes, title), "{key_style:?}");
+			}
+		}
+
+		// The original spelling is only known when converting back to the same format
+		let mut tag = tag;
+		tag.re_map(TagType::VorbisComments);
+		let merged = VorbisComments::from(tag);
+		assert!(merged.items().any(|(k, _)| k == "ALBUMARTIST"));
+	}
+
+	#[test_log::test]
+	fn popularimeter_accessor() {
+		let mut tag = VorbisComments::default();
+		tag.insert(String::from("RATING"), String::from("0.8"));
+		assert_eq!(tag.popularimeter().map(|p| p.rating()), Some(80));
+
+		tag.set_popularimeter(Popularimeter::new(String::from("foo@bar.com"), 60, 5));
+		assert_eq!(tag.get("RATING"), Some("60"));
+		assert_eq!(tag.items().count(), 1);
+
+		tag.remove_popularimeter();
+		assert_eq!(tag.popularimeter(), None);
+	}
 }
@@ -0,0 +1,182 @@
+use crate::error::Result;
+use crate::macros::decode_err;
+use crate::properties::StreamVerification;
+use crate::util::math::RoundedDivision;
+
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use ogg_pager::{Page, PageError, PageHeader, CONTAINS_LAST_PAGE_OF_BITSTREAM};
+
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Walk the pages of the first logical stream, checking their CRCs and sequence numbers
+///
+/// Ogg has no declared duration, so the expected duration is taken from the granule position of
+/// the last page, even if that page is cut short. A stream that ends without an end-of-stream page
+/// is considered truncated.
+pub(crate) fn verify_stream<R>(reader: &mut R) -> Result<StreamVerification>
+where
+	R: Read + Seek,
+{
+	let first_page = Page::read(reader)?;
+	let stream_serial = first_page.header().stream_serial;
+	let first_page_abgp = first_page.header().abgp;
+
+	let (sample_rate, pre_skip) = granule_rate(first_page.content())?;
+	if sample_rate == 0 {
+		decode_err!(@BAIL "Ogg stream has a sample rate of 0");
+	}
+
+	let mut corrupt_at = None;
+	if !has_valid_crc(&first_page) {
+		corrupt_at = Some(first_page.header().start);
+	}
+
+	let mut next_sequence_number = first_page.header().sequence_number + 1;
+	let mut found_granule = None;
+	let mut expected_granule = None;
+	let mut end_of_stream =
+		first_page.header().header_type_flag() & CONTAINS_LAST_PAGE_OF_BITSTREAM != 0;
+	let mut truncated_page = false;
+
+	let file_len = reader.seek(SeekFrom::End(0))?;
+	let mut pos = first_page.end;
+
+	while pos < file_len && !end_of_stream {
+		reader.seek(SeekFrom::Start(pos))?;
+
+		let page = match Page::read(reader) {
+			Ok(page) => page,
+			Err(PageError::Io(e)) if e.kind() == ErrorKind::UnexpectedEof => {
+				// The page was cut short, its header can still tell us how long the stream was
+				// meant to be
+				reader.seek(SeekFrom::Start(pos))?;
+				if let Ok(header) = PageHeader::read(reader) {
+					if header.stream_serial == stream_serial && header.abgp != u64::MAX {
+						expected_granule = Some(header.abgp);
+					}
+				}
+
+				truncated_page = true;
+				break;
+			},
+			Err(_) => {
+				log::warn!("Ogg: Invalid page at offset {pos}");
+				corrupt_at.get_or_insert(pos);
+
+				match find_next_page(reader, pos + 1)? {
+					Some(next_page) => {
+						pos = next_page;
+						continue;
+					},
+					None => break,
+				}
+			},
+		};
+
+		pos = page.end;
+
+		// Other logical streams, or chained streams, aren't checked
+		let header = page.header();
+		if header.stream_serial != stream_serial {
+			continue;
+		}
+
+		if header.sequence_number != next_sequence_number || !has_valid_crc(&page) {
+			log::warn!("Ogg: Page at offset {} is corrupt", header.start);
+			corrupt_at.get_or_insert(header.start);
+		}
+
+		next_sequence_number = header.sequence_number.wrapping_add(1);
+		end_of_stream = header.header_type_flag() & CONTAINS_LAST_PAGE_OF_BITSTREAM != 0;
+
+		// Pages that don't finish a packet have no granule position
+		if header.abgp != u64::MAX {
+			found_granule = Some(header.abgp);
+			expected_granule = Some(header.abgp);
+		}
+	}
+
+	let granule_to_duration = |granule: Option<u64>| {
+		let samples = granule
+			.unwrap_or(first_page_abgp)
+			.saturating_sub(first_page_abgp)
+			.saturating_sub(pre_skip);
+
+		Duration::from_millis((samples * 1000).div_round(u64::from(sample_rate)))
+	};
+
+	Ok(StreamVerification {
+		expected: granule_to_duration(expected_granule),
+		found: granule_to_duration(found_granule),
+		truncated: truncated_page || !end_of_stream,
+		corrupt_at,
+	})
+}
+
+// The rate of the granule positions, and the number of samples to skip, from the identification header
+fn granule_rate(identification_packet: &[u8]) -> Result<(u32, u64)> {
+	let u32_at = |pos: usize| {
+		identification_packet
+			.get(pos..pos + 4)
+			.map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+	};
+
+	let rate = match identification_packet {
+		[1, b'v', b'o', b'r', b'b', b'i', b's', ..] => u32_at(12).map(|rate| (rate, 0)),
+		[b'O', b'p', b'u', b's', b'H', b'e', b'a', b'd', _, _, pre_skip_lo, pre_skip_hi, ..] => {
+			// Opus granule positions are always at 48kHz
+			Some((
+				48000,
+				u64::from(u16::from_le_bytes([*pre_skip_lo, *pre_skip_hi])),
+			))
+		},
+		[b'S', b'p', b'e', b'e', b'x', b' ', b' ', b' ', ..] => u32_at(36).map(|rate| (rate, 0)),
+		// The 20 bit sample rate is in the STREAMINFO block, which follows the Ogg FLAC mapping
+		// header (9 bytes), the "fLaC" marker, and the block header
+		[0x7F, b'F', b'L', b'A', b'C', ..] => identification_packet
+			.get(27..30)
+			.map(|bytes| u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 4)
+			.map(|rate| (rate, 0)),
+		_ => None,
+	};
+
+	match rate {
+		Some(rate) => Ok(rate),
+		None => decode_err!(@BAIL "Ogg stream has an unknown or invalid identification header"),
+	}
+}
+
+fn has_valid_crc(page: &Page) -> bool {
+	let mut page = page.clone();
+
+	let stored = page.header().checksum();
+	page.gen_crc();
+
+	page.header().checksum() == stored
+}
+
+// Searches for the next "OggS" capture pattern, starting at `pos`
+fn find_next_page<R>(reader: &mut R, mut pos: u64) -> Result<Option<u64>>
+where
+	R: Read + Seek,
+{
+	reader.seek(SeekFrom::Start(pos))?;
+
+	let mut buf = vec![0; READ_CHUNK_SIZE];
+	loop {
+		let read = reader.read(&mut buf)?;
+		if read < 4 {
+			return Ok(None);
+		}
+
+		if let Some(offset) = buf[..read].windows(4).position(|window| window == b"OggS") {
+			return Ok(Some(pos + offset as u64));
+		}
+
+		// The capture pattern may be split across reads
+		pos += read as u64 - 3;
+		reader.seek(SeekFrom::Start(pos))?;
+	}
+}
@@ -39,7 +39,10 @@ impl VorbisFile {
 
 		Ok(Self {
 			properties: if parse_options.read_properties {
-				properties::read_properties(reader, &file_information.1, &file_information.2)?
+				crate::properties::or_incomplete(
+					properties::read_properties(reader, &file_information.1, &file_information.2),
+					parse_options.parsing_mode,
+				)?
 			} else {
 				VorbisProperties::default()
 			},
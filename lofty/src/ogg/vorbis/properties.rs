@@ -1,6 +1,6 @@
 use super::find_last_page;
 use crate::error::Result;
-use crate::properties::FileProperties;
+use crate::properties::{ChannelMask, FileProperties};
 use crate::util::math::RoundedDivision;
 
 use std::io::{Read, Seek, SeekFrom};
@@ -11,6 +11,7 @@ use ogg_pager::{Packets, PageHeader};
 
 /// An OGG Vorbis file's audio properties
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct VorbisProperties {
 	pub(crate) duration: Duration,
@@ -18,10 +19,12 @@ pub struct VorbisProperties {
 	pub(crate) audio_bitrate: u32,
 	pub(crate) sample_rate: u32,
 	pub(crate) channels: u8,
+	pub(crate) channel_mask: Option<ChannelMask>,
 	pub(crate) version: u32,
 	pub(crate) bitrate_maximum: i32,
 	pub(crate) bitrate_nominal: i32,
 	pub(crate) bitrate_minimum: i32,
+	pub(crate) is_chained: bool,
 }
 
 impl From<VorbisProperties> for FileProperties {
@@ -33,7 +36,7 @@ impl From<VorbisProperties> for FileProperties {
 			sample_rate: Some(input.sample_rate),
 			bit_depth: None,
 			channels: Some(input.channels),
-			channel_mask: None,
+			channel_mask: input.channel_mask,
 		}
 	}
 }
@@ -64,6 +67,13 @@ impl VorbisProperties {
 		self.channels
 	}
 
+	/// Channel mask
+	///
+	/// This is derived from the channel count, using the layouts defined by the Vorbis specification.
+	pub fn channel_mask(&self) -> Option<ChannelMask> {
+		self.channel_mask
+	}
+
 	/// Vorbis version
 	pub fn version(&self) -> u32 {
 		self.version
@@ -83,6 +93,14 @@ impl VorbisProperties {
 	pub fn bitrate_min(&self) -> i32 {
 		self.bitrate_minimum
 	}
+
+	/// Whether the file contains chained streams
+	///
+	/// A chained file is several complete streams concatenated together. Only the first stream is
+	/// used for tags and properties, and the remaining streams are left untouched when writing.
+	pub fn is_chained(&self) -> bool {
+		self.is_chained
+	}
 }
 
 pub(in crate::ogg) fn read_properties<R>(
@@ -104,19 +122,22 @@ where
 	properties.version = first_page_content.read_u32::<LittleEndian>()?;
 
 	properties.channels = first_page_content.read_u8()?;
+	properties.channel_mask = ChannelMask::from_vorbis_channels(properties.channels);
 	properties.sample_rate = first_page_content.read_u32::<LittleEndian>()?;
 
 	properties.bitrate_maximum = first_page_content.read_i32::<LittleEndian>()?;
 	properties.bitrate_nominal = first_page_content.read_i32::<LittleEndian>()?;
 	properties.bitrate_minimum = first_page_content.read_i32::<LittleEndian>()?;
 
-	let last_page = find_last_page(data);
+	let last_page = find_last_page(data, first_page_header.stream_serial);
 	let file_length = data.seek(SeekFrom::End(0))?;
 
 	// This is used for bitrate calculation, it should be the length in
 	// milliseconds, but if we can't determine it then we'll just use 1000.
 	let mut length = 1000;
-	if let Ok(last_page) = last_page {
+	if let Ok((last_page, is_chained)) = last_page {
+		properties.is_chained = is_chained;
+
 		let first_page_abgp = first_page_header.abgp;
 		let last_page_abgp = last_page.header().abgp;
 
@@ -1,19 +1,22 @@
 use super::verify_signature;
-use crate::config::WriteOptions;
-use crate::error::{LoftyError, Result};
+use crate::config::{InvalidFieldNamePolicy, WriteOptions};
+use crate::error::{ErrorKind, LoftyError, Result};
 use crate::file::FileType;
+use crate::flac::write::verify_icons;
 use crate::macros::{decode_err, err, try_vec};
 use crate::ogg::constants::{OPUSTAGS, VORBIS_COMMENT_HEAD};
-use crate::ogg::tag::{create_vorbis_comments_ref, VorbisCommentsRef};
+use crate::ogg::tag::{create_vorbis_comments_ref, verify_key, VorbisCommentsRef};
 use crate::picture::{Picture, PictureInformation};
 use crate::tag::{Tag, TagType};
-use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::io::{ByteCounter, FileLike, Length, Truncate};
 
 use std::borrow::Cow;
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use ogg_pager::{Packets, Page, PageHeader, CONTAINS_FIRST_PAGE_OF_BITSTREAM};
+use ogg_pager::{
+	Packets, Page, PageHeader, CONTAINS_FIRST_PAGE_OF_BITSTREAM, CONTAINS_LAST_PAGE_OF_BITSTREAM,
+};
 
 #[derive(PartialEq, Copy, Clone)]
 pub(crate) enum OGGFormat {
@@ -60,10 +63,15 @@ where
 
 	let mut comments_ref = VorbisCommentsRef {
 		vendor: Cow::from(vendor),
-		items,
+		items: items.iter().map(|(k, v)| (&**k, *v)),
 		pictures,
 	};
 
+	// FLAC metadata blocks are stored in separate packets, rather than a single comment packet
+	if file_type == FileType::OggFlac {
+		return super::flac::write::write_to(file, &mut comments_ref, write_options);
+	}
+
 	let (format, header_packet_count) = OGGFormat::from_filetype(file_type);
 
 	write(
@@ -80,7 +88,7 @@ pub(super) fn write<'a, F, II, IP>(
 	tag: &mut VorbisCommentsRef<'a, II, IP>,
 	format: OGGFormat,
 	header_packet_count: isize,
-	_write_options: WriteOptions,
+	write_options: WriteOptions,
 ) -> Result<()>
 where
 	F: FileLike,
@@ -115,40 +123,96 @@ where
 	let comment_signature = comment_signature.unwrap_or_default();
 
 	// Retain the file's vendor string
-	let md_reader = &mut &comment_packet[comment_signature.len()..];
+	tag.vendor = read_vendor(&comment_packet[comment_signature.len()..])?;
+
+	let add_framing_bit = format == OGGFormat::Vorbis;
+	let new_metadata_packet =
+		create_metadata_packet(tag, comment_signature, add_framing_bit, write_options)?;
+
+	// Replace the old comment packet
+	packets.set(1, new_metadata_packet);
+
+	write_header_packets(file, packets.iter(), stream_serial, &remaining_file_content)
+}
+
+/// Get the vendor string from a comment packet (with its signature removed)
+pub(super) fn read_vendor(comment_packet: &[u8]) -> Result<Cow<'static, str>> {
+	let md_reader = &mut &comment_packet[..];
 
 	let vendor_len = md_reader.read_u32::<LittleEndian>()?;
 	let mut vendor = try_vec![0; vendor_len as usize];
 	md_reader.read_exact(&mut vendor)?;
 
-	let vendor_str;
 	match String::from_utf8(vendor) {
-		Ok(s) => vendor_str = Cow::Owned(s),
+		Ok(s) => Ok(Cow::Owned(s)),
 		Err(_) => {
 			// TODO: Error on strict?
 			log::warn!("OGG vendor string is not valid UTF-8, not re-using");
-			vendor_str = Cow::Borrowed("");
+			Ok(Cow::Borrowed(""))
 		},
 	}
+}
 
-	tag.vendor = vendor_str;
-
-	let add_framing_bit = format == OGGFormat::Vorbis;
-	let new_metadata_packet = create_metadata_packet(tag, comment_signature, add_framing_bit)?;
-
-	// Replace the old comment packet
-	packets.set(1, new_metadata_packet);
-
+/// Replace the contents of `file` with the new header packets of `stream_serial`, followed by the
+/// rest of the file
+///
+/// `remaining_file_content` is everything in the file after the original header packets.
+pub(super) fn write_header_packets<'a, F, P>(
+	file: &mut F,
+	header_packets: P,
+	stream_serial: u32,
+	remaining_file_content: &[u8],
+) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	P: IntoIterator<Item = &'a [u8]> + 'a,
+{
 	file.rewind()?;
 	file.truncate(0)?;
 
-	let pages_written =
-		packets.write_to(file, stream_serial, 0, CONTAINS_FIRST_PAGE_OF_BITSTREAM)? as u32;
+	let pages = ogg_pager::paginate(
+		header_packets,
+		stream_serial,
+		0,
+		CONTAINS_FIRST_PAGE_OF_BITSTREAM,
+	)?;
+	let pages_written = pages.len() as u32;
+	for mut page in pages {
+		page.gen_crc();
+		file.write_all(&page.as_bytes())?;
+	}
 
 	// Correct all remaining page sequence numbers
-	let mut pages_reader = Cursor::new(&remaining_file_content[..]);
+	//
+	// Only pages belonging to our stream need to be renumbered. Pages from other multiplexed
+	// streams, and any chained streams following ours, are copied as-is.
+	let mut pages_reader = Cursor::new(remaining_file_content);
 	let mut idx = 0;
-	while let Ok(mut page) = Page::read(&mut pages_reader) {
+	let mut stream_ended = false;
+	loop {
+		let page_start = pages_reader.position() as usize;
+		let Ok(mut page) = Page::read(&mut pages_reader) else {
+			break;
+		};
+
+		let header_type_flag = page.header().header_type_flag();
+		if stream_ended && header_type_flag & CONTAINS_FIRST_PAGE_OF_BITSTREAM != 0 {
+			log::debug!("OGG: Found a chained stream, copying the remainder of the file");
+			file.write_all(&remaining_file_content[page_start..])?;
+			break;
+		}
+
+		let page_end = pages_reader.position() as usize;
+		if page.header().stream_serial != stream_serial {
+			file.write_all(&remaining_file_content[page_start..page_end])?;
+			continue;
+		}
+
+		if header_type_flag & CONTAINS_LAST_PAGE_OF_BITSTREAM != 0 {
+			stream_ended = true;
+		}
+
 		let header = page.header_mut();
 		header.sequence_number = pages_written + idx;
 		page.gen_crc();
@@ -164,6 +228,7 @@ pub(super) fn create_metadata_packet<'a, II, IP>(
 	tag: &mut VorbisCommentsRef<'a, II, IP>,
 	comment_signature: &[u8],
 	add_framing_bit: bool,
+	write_options: WriteOptions,
 ) -> Result<Vec<u8>>
 where
 	II: Iterator<Item = (&'a str, &'a str)>,
@@ -181,8 +246,18 @@ where
 	new_comment_packet.write_u32::<LittleEndian>(0)?;
 
 	let mut count = 0;
-	create_comments(&mut new_comment_packet, &mut count, &mut tag.items)?;
-	create_pictures(&mut new_comment_packet, &mut count, &mut tag.pictures)?;
+	create_comments(
+		&mut new_comment_packet,
+		&mut count,
+		&mut tag.items,
+		write_options,
+	)?;
+	create_pictures(
+		&mut new_comment_packet,
+		&mut count,
+		&mut tag.pictures,
+		write_options,
+	)?;
 
 	// Seek back and write the item count
 	new_comment_packet.seek(SeekFrom::Start(item_count_pos))?;
@@ -199,16 +274,46 @@ where
 	Ok(new_comment_packet.into_inner())
 }
 
+/// Get the size of the packet that [`create_metadata_packet`] would produce (without a signature
+/// or framing bit), without keeping it in memory
+pub(super) fn metadata_packet_len<'a, II, IP>(
+	tag: &mut VorbisCommentsRef<'a, II, IP>,
+	write_options: WriteOptions,
+) -> Result<u64>
+where
+	II: Iterator<Item = (&'a str, &'a str)>,
+	IP: Iterator<Item = (&'a Picture, PictureInformation)>,
+{
+	let mut comments_len = ByteCounter::default();
+
+	let mut count = 0;
+	create_comments(&mut comments_len, &mut count, &mut tag.items, write_options)?;
+	create_pictures(
+		&mut comments_len,
+		&mut count,
+		&mut tag.pictures,
+		write_options,
+	)?;
+
+	// Vendor length (4), Vendor, Item count (4), Items
+	Ok(4 + tag.vendor.len() as u64 + 4 + comments_len.0)
+}
+
 pub(crate) fn create_comments(
 	packet: &mut impl Write,
 	count: &mut u32,
 	items: &mut dyn Iterator<Item = (&str, &str)>,
+	write_options: WriteOptions,
 ) -> Result<()> {
 	for (k, v) in items {
 		if v.is_empty() {
 			continue;
 		}
 
+		let Some(k) = verify_field_name(k, write_options.invalid_field_name_policy)? else {
+			continue;
+		};
+
 		let comment = format!("{k}={v}");
 		let comment_bytes = comment.as_bytes();
 
@@ -225,14 +330,43 @@ pub(crate) fn create_comments(
 	Ok(())
 }
 
+fn verify_field_name(key: &str, policy: InvalidFieldNamePolicy) -> Result<Option<Cow<'_, str>>> {
+	if verify_key(key) {
+		return Ok(Some(Cow::Borrowed(key)));
+	}
+
+	match policy {
+		InvalidFieldNamePolicy::Sanitize if !key.is_empty() => {
+			let sanitized = key
+				.chars()
+				.map(|c| match c {
+					' '..='}' if c != '=' => c,
+					_ => '_',
+				})
+				.collect::<String>();
+
+			log::warn!("Replacing invalid field name {key:?} with {sanitized:?}");
+			Ok(Some(Cow::Owned(sanitized)))
+		},
+		InvalidFieldNamePolicy::Error => Err(LoftyError::new(ErrorKind::InvalidVorbisFieldName(
+			key.to_owned(),
+		))),
+		_ => {
+			log::warn!("Discarding field with an invalid name {key:?}");
+			Ok(None)
+		},
+	}
+}
+
 fn create_pictures(
 	packet: &mut impl Write,
 	count: &mut u32,
 	pictures: &mut dyn Iterator<Item = (&Picture, PictureInformation)>,
+	write_options: WriteOptions,
 ) -> Result<()> {
 	const PICTURE_KEY: &str = "METADATA_BLOCK_PICTURE=";
 
-	for (pic, info) in pictures {
+	for (pic, info) in verify_icons(pictures, write_options)? {
 		let picture = pic.as_flac_bytes(info, true);
 
 		let Ok(bytes_len) = u32::try_from(picture.len() + PICTURE_KEY.len()) else {
@@ -1,16 +1,16 @@
 //! Format-agnostic picture handling
 
-use crate::config::ParsingMode;
+use crate::config::{ParsingMode, PictureDataPolicy};
 use crate::error::{ErrorKind, LoftyError, Result};
 use crate::macros::err;
-use crate::util::text::utf8_decode_str;
+use crate::util::text::{latin1_decode, utf8_decode_str};
 
 use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter};
 use std::io::{Cursor, Read, Seek, SeekFrom};
 
-use byteorder::{BigEndian, ReadBytesExt as _};
-use data_encoding::BASE64;
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt as _};
+use data_encoding::{BASE64, BASE64_NOPAD};
 
 /// Common picture item keys for APE
 pub const APE_PICTURE_TYPES: [&str; 21] = [
@@ -39,6 +39,7 @@ pub const APE_PICTURE_TYPES: [&str; 21] = [
 
 /// MIME types for pictures.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum MimeType {
 	/// PNG image
@@ -51,6 +52,8 @@ pub enum MimeType {
 	Bmp,
 	/// GIF image
 	Gif,
+	/// WebP image
+	WebP,
 	/// Some unknown MIME type
 	Unknown(String),
 }
@@ -75,6 +78,7 @@ impl MimeType {
 			"image/tiff" => Self::Tiff,
 			"image/bmp" => Self::Bmp,
 			"image/gif" => Self::Gif,
+			"image/webp" => Self::WebP,
 			_ => Self::Unknown(mime_type.to_owned()),
 		}
 	}
@@ -97,6 +101,7 @@ impl MimeType {
 			MimeType::Tiff => "image/tiff",
 			MimeType::Bmp => "image/bmp",
 			MimeType::Gif => "image/gif",
+			MimeType::WebP => "image/webp",
 			MimeType::Unknown(unknown) => unknown,
 		}
 	}
@@ -117,9 +122,15 @@ impl MimeType {
 			MimeType::Tiff => Some("tif"),
 			MimeType::Bmp => Some("bmp"),
 			MimeType::Gif => Some("gif"),
+			MimeType::WebP => Some("webp"),
 			MimeType::Unknown(_) => None,
 		}
 	}
+
+	// FLAC pictures with this MIME type hold a URL rather than the picture data
+	pub(crate) fn is_url(&self) -> bool {
+		matches!(self, MimeType::Unknown(mime_type) if mime_type == "-->")
+	}
 }
 
 impl Display for MimeType {
@@ -131,6 +142,7 @@ impl Display for MimeType {
 /// The picture type, according to ID3v2 APIC
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum PictureType {
 	Other,
@@ -280,6 +292,7 @@ impl PictureType {
 /// This information is necessary for FLAC's `METADATA_BLOCK_PICTURE`.
 /// See [`Picture::as_flac_bytes`] for more information.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PictureInformation {
 	/// The picture's width in pixels
 	pub width: u32,
@@ -294,23 +307,50 @@ pub struct PictureInformation {
 impl PictureInformation {
 	/// Attempt to extract [`PictureInformation`] from a [`Picture`]
 	///
-	/// NOTE: This only supports PNG and JPEG images. If another image is provided,
-	/// the `PictureInformation` will be zeroed out.
+	/// See [`PictureInformation::from_picture_data`].
 	///
 	/// # Errors
 	///
-	/// * `picture.data` is less than 8 bytes in length
-	/// * See [`PictureInformation::from_png`] and [`PictureInformation::from_jpeg`]
+	/// See [`PictureInformation::from_picture_data`]
 	pub fn from_picture(picture: &Picture) -> Result<Self> {
-		let reader = &mut &*picture.data;
+		Self::from_picture_data(&picture.data)
+	}
 
-		if reader.len() < 8 {
+	/// Attempt to extract [`PictureInformation`] from raw image data
+	///
+	/// The format is detected from the data itself, and only the image header is read.
+	///
+	/// NOTE: This only supports PNG, JPEG, GIF, and BMP images. If another image is provided,
+	/// the `PictureInformation` will be zeroed out.
+	///
+	/// # Errors
+	///
+	/// * `data` is less than 8 bytes in length
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::PictureInformation;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let gif_data = [
+	/// # 	b'G', b'I', b'F', b'8', b'9', b'a', 0x80, 0x02, 0x74, 0x02, 0xF7, 0x00, 0x00,
+	/// # ];
+	/// let information = PictureInformation::from_picture_data(&gif_data)?;
+	/// assert_eq!(information.width, 640);
+	/// assert_eq!(information.height, 628);
+	/// # Ok(()) }
+	/// ```
+	pub fn from_picture_data(data: &[u8]) -> Result<Self> {
+		if data.len() < 8 {
 			err!(NotAPicture);
 		}
 
-		match reader[..4] {
-			[0x89, b'P', b'N', b'G'] => Ok(Self::from_png(reader).unwrap_or_default()),
-			[0xFF, 0xD8, 0xFF, ..] => Ok(Self::from_jpeg(reader).unwrap_or_default()),
+		match data[..4] {
+			[0x89, b'P', b'N', b'G'] => Ok(Self::from_png(data).unwrap_or_default()),
+			[0xFF, 0xD8, 0xFF, ..] => Ok(Self::from_jpeg(data).unwrap_or_default()),
+			[b'G', b'I', b'F', b'8'] => Ok(Self::from_gif(data).unwrap_or_default()),
+			[b'B', b'M', ..] => Ok(Self::from_bmp(data).unwrap_or_default()),
 			_ => Ok(Self::default()),
 		}
 	}
@@ -344,8 +384,12 @@ impl PictureInformation {
 		let color_type = reader.read_u8()?;
 
 		match color_type {
+			// Truecolor
 			2 => color_depth *= 3,
-			4 | 6 => color_depth *= 4,
+			// Grayscale with alpha
+			4 => color_depth *= 2,
+			// Truecolor with alpha
+			6 => color_depth *= 4,
 			_ => {},
 		}
 
@@ -398,6 +442,8 @@ impl PictureInformation {
 
 	/// Attempt to extract [`PictureInformation`] from a JPEG
 	///
+	/// This supports all `SOFn` frames, so progressive and lossless JPEGs are handled as well.
+	///
 	/// # Errors
 	///
 	/// * `reader` is not a JPEG image
@@ -405,39 +451,39 @@ impl PictureInformation {
 	pub fn from_jpeg(mut data: &[u8]) -> Result<Self> {
 		let reader = &mut data;
 
-		let mut frame_marker = [0; 4];
-		reader.read_exact(&mut frame_marker)?;
+		let mut soi = [0; 2];
+		reader.read_exact(&mut soi)?;
 
-		if !matches!(frame_marker, [0xFF, 0xD8, 0xFF, ..]) {
+		if soi != [0xFF, 0xD8] {
 			err!(NotAPicture);
 		}
 
-		let mut section_len = reader.read_u16::<BigEndian>()?;
-
 		let mut reader = Cursor::new(reader);
 
-		// The length contains itself, so anything < 2 is invalid
-		let (content_len, overflowed) = section_len.overflowing_sub(2);
-		if overflowed {
-			err!(NotAPicture);
-		}
-		reader.seek(SeekFrom::Current(i64::from(content_len)))?;
-
 		while let Ok(0xFF) = reader.read_u8() {
-			let marker = reader.read_u8()?;
-			section_len = reader.read_u16::<BigEndian>()?;
+			let mut marker = reader.read_u8()?;
 
-			// This marks the SOS (Start of Scan), which is
-			// the end of the header
-			if marker == 0xDA {
-				break;
+			// Any number of fill bytes can precede a marker
+			while marker == 0xFF {
+				marker = reader.read_u8()?;
 			}
 
-			// We are looking for a frame with a "SOFn" marker,
-			// with `n` either being 0 or 2. Since there isn't a
-			// header like PNG, we actually need to search for this
-			// frame
-			if marker == 0xC0 || marker == 0xC2 {
+			match marker {
+				// TEM and RSTn markers are standalone, they have no length
+				0x01 | 0xD0..=0xD7 => continue,
+				// This marks the SOS (Start of Scan), which is
+				// the end of the header
+				0xDA | 0xD9 => break,
+				_ => {},
+			}
+
+			let section_len = reader.read_u16::<BigEndian>()?;
+
+			// We are looking for a frame with a "SOFn" marker. Since there isn't a
+			// header like PNG, we actually need to search for this frame.
+			//
+			// 0xC4 (DHT), 0xC8 (JPG), and 0xCC (DAC) share the range, but aren't frames.
+			if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
 				let precision = reader.read_u8()?;
 				let height = u32::from(reader.read_u16::<BigEndian>()?);
 				let width = u32::from(reader.read_u16::<BigEndian>()?);
@@ -446,20 +492,143 @@ impl PictureInformation {
 				return Ok(Self {
 					width,
 					height,
-					color_depth: u32::from(precision * components),
+					color_depth: u32::from(precision) * u32::from(components),
 					num_colors: 0,
 				});
 			}
 
-			reader.seek(SeekFrom::Current(i64::from(section_len - 2)))?;
+			// The length contains itself, so anything < 2 is invalid
+			let Some(content_len) = section_len.checked_sub(2) else {
+				err!(NotAPicture);
+			};
+
+			reader.seek(SeekFrom::Current(i64::from(content_len)))?;
 		}
 
 		err!(NotAPicture)
 	}
+
+	/// Attempt to extract [`PictureInformation`] from a GIF
+	///
+	/// NOTE: The values are taken from the logical screen descriptor. Individual frames
+	/// can use a local color table, which is not taken into account.
+	///
+	/// # Errors
+	///
+	/// * `reader` is not a GIF image
+	pub fn from_gif(mut data: &[u8]) -> Result<Self> {
+		let reader = &mut data;
+
+		let mut signature = [0; 6];
+		reader.read_exact(&mut signature)?;
+
+		if &signature != b"GIF87a" && &signature != b"GIF89a" {
+			err!(NotAPicture);
+		}
+
+		let width = u32::from(reader.read_u16::<LittleEndian>()?);
+		let height = u32::from(reader.read_u16::<LittleEndian>()?);
+		let flags = reader.read_u8()?;
+
+		// Bit 7: Global color table flag
+		// Bits 4-6: The number of bits per primary color, minus 1
+		// Bits 0-2: The size of the global color table, as 2^(N+1)
+		let (color_depth, num_colors);
+		if flags & 0x80 == 0 {
+			color_depth = u32::from((flags >> 4) & 0b111) + 1;
+			num_colors = 0;
+		} else {
+			// Like indexed PNGs, the color depth is the size of a palette index
+			color_depth = u32::from(flags & 0b111) + 1;
+			num_colors = 1 << color_depth;
+		}
+
+		Ok(Self {
+			width,
+			height,
+			color_depth,
+			num_colors,
+		})
+	}
+
+	/// Attempt to extract [`PictureInformation`] from a BMP
+	///
+	/// # Errors
+	///
+	/// * `reader` is not a BMP image
+	/// * `reader` has an unknown DIB header
+	pub fn from_bmp(mut data: &[u8]) -> Result<Self> {
+		const BITMAPCOREHEADER_SIZE: u32 = 12;
+		const BITMAPINFOHEADER_SIZE: u32 = 40;
+
+		let reader = &mut data;
+
+		let mut signature = [0; 2];
+		reader.read_exact(&mut signature)?;
+
+		if &signature != b"BM" {
+			err!(NotAPicture);
+		}
+
+		// Skip the rest of the file header
+		// File size (4)
+		// Reserved (4)
+		// Pixel data offset (4)
+		let mut file_header = [0; 12];
+		reader.read_exact(&mut file_header)?;
+
+		let dib_header_size = reader.read_u32::<LittleEndian>()?;
+
+		let (width, height, bits_per_pixel, colors_used);
+		if dib_header_size == BITMAPCOREHEADER_SIZE {
+			width = u32::from(reader.read_u16::<LittleEndian>()?);
+			height = u32::from(reader.read_u16::<LittleEndian>()?);
+			let _planes = reader.read_u16::<LittleEndian>()?;
+			bits_per_pixel = u32::from(reader.read_u16::<LittleEndian>()?);
+			colors_used = 0;
+		} else if dib_header_size >= BITMAPINFOHEADER_SIZE {
+			width = reader.read_i32::<LittleEndian>()?.unsigned_abs();
+			// A negative height denotes a top-down image
+			height = reader.read_i32::<LittleEndian>()?.unsigned_abs();
+			let _planes = reader.read_u16::<LittleEndian>()?;
+			bits_per_pixel = u32::from(reader.read_u16::<LittleEndian>()?);
+
+			// Skip to the number of colors in the palette
+			// Compression (4)
+			// Image size (4)
+			// Horizontal resolution (4)
+			// Vertical resolution (4)
+			let mut skipped = [0; 16];
+			reader.read_exact(&mut skipped)?;
+
+			colors_used = reader.read_u32::<LittleEndian>()?;
+		} else {
+			err!(NotAPicture);
+		}
+
+		// Only images with <= 8 bits per pixel use a palette. A `colors_used` of 0
+		// means the palette has the maximum number of colors for the bit depth.
+		let mut num_colors = 0;
+		if (1..=8).contains(&bits_per_pixel) {
+			num_colors = if colors_used == 0 {
+				1 << bits_per_pixel
+			} else {
+				colors_used
+			};
+		}
+
+		Ok(Self {
+			width,
+			height,
+			color_depth: bits_per_pixel,
+			num_colors,
+		})
+	}
 }
 
 /// Represents a picture.
 #[derive(Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Picture {
 	/// The picture type according to ID3v2 APIC
 	pub(crate) pic_type: PictureType,
@@ -468,7 +637,10 @@ pub struct Picture {
 	/// The picture's description
 	pub(crate) description: Option<Cow<'static, str>>,
 	/// The binary data of the picture
+	#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_bytes"))]
 	pub(crate) data: Cow<'static, [u8]>,
+	/// The length of the data, if it was skipped while reading
+	pub(crate) skipped_data_len: Option<u64>,
 }
 
 impl Debug for Picture {
@@ -478,6 +650,7 @@ impl Debug for Picture {
 			.field("mime_type", &self.mime_type)
 			.field("description", &self.description)
 			.field("data", &format!("<{} bytes>", self.data.len()))
+			.field("skipped_data_len", &self.skipped_data_len)
 			.finish()
 	}
 }
@@ -506,13 +679,14 @@ impl Picture {
 			err!(NotAPicture);
 		}
 
-		let mime_type = Self::mimetype_from_bin(&data[..8])?;
+		let mime_type = Self::mimetype_from_bin(&data)?;
 
 		Ok(Self {
 			pic_type: PictureType::Other,
 			mime_type: Some(mime_type),
 			description: None,
 			data: data.into(),
+			skipped_data_len: None,
 		})
 	}
 
@@ -532,6 +706,7 @@ impl Picture {
 			mime_type,
 			description: description.map(Cow::Owned),
 			data: Cow::Owned(data),
+			skipped_data_len: None,
 		}
 	}
 
@@ -549,10 +724,130 @@ impl Picture {
 	///
 	/// The `mime_type` is determined from the `data`, and
 	/// is immutable.
+	///
+	/// NOTE: For pictures read from a file, this is the MIME type declared in the file, which may
+	///       not match the data. See [`Picture::verify`].
 	pub fn mime_type(&self) -> Option<&MimeType> {
 		self.mime_type.as_ref()
 	}
 
+	/// Detect the [`MimeType`] from the picture data
+	///
+	/// This only checks the signature of the data, supporting PNG, JPEG, GIF, BMP, TIFF, and WebP.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::{MimeType, Picture, PictureType};
+	///
+	/// let png_data = b"\x89PNG\r\n\x1a\n".to_vec();
+	///
+	/// // The declared MIME type is not used
+	/// let picture = Picture::new_unchecked(PictureType::Other, Some(MimeType::Jpeg), None, png_data);
+	/// assert_eq!(picture.detect_mime(), Some(MimeType::Png));
+	/// ```
+	pub fn detect_mime(&self) -> Option<MimeType> {
+		Self::mimetype_from_bin(&self.data).ok()
+	}
+
+	/// Verify that the declared [`MimeType`] matches the picture data
+	///
+	/// Nonstandard names of known MIME types (e.g. "image/jpg") are accepted, as they are
+	/// normalized when writing.
+	///
+	/// Pictures whose data was skipped while reading (see [`Picture::skipped_data_len`]) can't be
+	/// verified, and are always accepted. The same goes for data that isn't a known image format,
+	/// unless the declared MIME type claims otherwise.
+	///
+	/// # Errors
+	///
+	/// * The data is a known image format, but a different (or no) MIME type is declared
+	/// * A known image format is declared, but the data isn't one
+	///
+	/// Both are reported as [`ErrorKind::PictureMimeMismatch`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::{MimeType, Picture, PictureType};
+	///
+	/// let png_data = b"\x89PNG\r\n\x1a\n".to_vec();
+	///
+	/// let picture =
+	/// 	Picture::new_unchecked(PictureType::Other, Some(MimeType::Png), None, png_data.clone());
+	/// assert!(picture.verify().is_ok());
+	///
+	/// let picture = Picture::new_unchecked(PictureType::Other, Some(MimeType::Jpeg), None, png_data);
+	/// assert!(picture.verify().is_err());
+	/// ```
+	pub fn verify(&self) -> Result<()> {
+		if self.skipped_data_len.is_some() {
+			return Ok(());
+		}
+
+		let declared = self.normalized_mime_type();
+		let detected = self.detect_mime();
+
+		let matches = match (&declared, &detected) {
+			(_, Some(detected)) => declared.as_ref() == Some(detected),
+			(Some(MimeType::Unknown(_)) | None, None) => true,
+			(Some(_), None) => false,
+		};
+
+		if !matches {
+			return Err(LoftyError::new(ErrorKind::PictureMimeMismatch {
+				declared,
+				detected,
+			}));
+		}
+
+		Ok(())
+	}
+
+	// The declared MIME type, with nonstandard names of known types resolved
+	fn normalized_mime_type(&self) -> Option<MimeType> {
+		match &self.mime_type {
+			Some(MimeType::Unknown(mime_type)) => Some(MimeType::from_str(mime_type)),
+			mime_type => mime_type.clone(),
+		}
+	}
+
+	/// The picture with the MIME type detected from its data, falling back to the normalized
+	/// declared type
+	///
+	/// See [`WriteOptions::fix_picture_mime_types`](crate::config::WriteOptions::fix_picture_mime_types).
+	pub(crate) fn with_fixed_mime_type(&self) -> Cow<'_, Self> {
+		if self.skipped_data_len.is_some() {
+			return Cow::Borrowed(self);
+		}
+
+		let mut fixed = self.detect_mime().or_else(|| self.normalized_mime_type());
+
+		// A URL picture that doesn't hold a URL can only be fixed by dropping its MIME type
+		if fixed.as_ref().is_some_and(MimeType::is_url) && !self.has_url_data() {
+			fixed = None;
+		}
+
+		if fixed == self.mime_type {
+			return Cow::Borrowed(self);
+		}
+
+		log::warn!(
+			"Correcting picture MIME type: {:?} => {:?}",
+			self.mime_str(),
+			fixed.as_ref().map_or("", MimeType::as_str)
+		);
+
+		let mut picture = self.clone();
+		picture.mime_type = fixed;
+		Cow::Owned(picture)
+	}
+
+	/// Whether the data can be the URL of a FLAC picture with the `-->` MIME type
+	pub(crate) fn has_url_data(&self) -> bool {
+		std::str::from_utf8(&self.data).is_ok_and(|url| !url.chars().any(char::is_control))
+	}
+
 	// Used commonly internally
 	pub(crate) fn mime_str(&self) -> &str {
 		match self.mime_type.as_ref() {
@@ -572,10 +867,49 @@ impl Picture {
 	}
 
 	/// Returns the [`Picture`] data as borrowed bytes.
+	///
+	/// NOTE: This is empty if the data was skipped while reading, see [`Picture::skipped_data_len`].
 	pub fn data(&self) -> &[u8] {
 		&self.data
 	}
 
+	/// The length of the picture data, if it was skipped while reading
+	///
+	/// This is only set when reading with [`PictureDataPolicy::Skip`], in which case [`Picture::data`]
+	/// is empty. Pictures with skipped data cannot be written.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::{ParseOptions, PictureDataPolicy};
+	/// use lofty::file::TaggedFileExt;
+	/// use lofty::probe::Probe;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.flac";
+	/// let tagged_file = Probe::open(path)?
+	/// 	.options(ParseOptions::new().picture_data_policy(PictureDataPolicy::Skip))
+	/// 	.read()?;
+	///
+	/// for picture in tagged_file.tags().iter().flat_map(|tag| tag.pictures()) {
+	/// 	assert!(picture.data().is_empty());
+	/// 	println!("{:?}: {:?} bytes", picture.pic_type(), picture.skipped_data_len());
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn skipped_data_len(&self) -> Option<u64> {
+		self.skipped_data_len
+	}
+
+	/// Error if the data of this picture was skipped while reading
+	pub(crate) fn verify_has_data(&self) -> Result<()> {
+		if self.skipped_data_len.is_some() {
+			err!(SkippedPictureData);
+		}
+
+		Ok(())
+	}
+
 	/// Consumes a [`Picture`], returning the data as [`Vec`] without clones or allocation.
 	pub fn into_data(self) -> Vec<u8> {
 		self.data.into_owned()
@@ -632,7 +966,8 @@ impl Picture {
 	/// Get a [`Picture`] from FLAC `METADATA_BLOCK_PICTURE` bytes:
 	///
 	/// NOTE: This takes both the base64 encoded string from Vorbis comments, and
-	/// the raw data from a FLAC block, specified with `encoded`.
+	/// the raw data from a FLAC block, specified with `encoded`. When decoding base64,
+	/// whitespace (e.g. line breaks) and missing padding are tolerated.
 	///
 	/// # Errors
 	///
@@ -644,9 +979,8 @@ impl Picture {
 		parse_mode: ParsingMode,
 	) -> Result<(Self, PictureInformation)> {
 		if encoded {
-			let data = BASE64
-				.decode(bytes)
-				.map_err(|_| LoftyError::new(ErrorKind::NotAPicture))?;
+			let data =
+				decode_base64(bytes).ok_or_else(|| LoftyError::new(ErrorKind::NotAPicture))?;
 			Self::from_flac_bytes_inner(&data, parse_mode)
 		} else {
 			Self::from_flac_bytes_inner(bytes, parse_mode)
@@ -657,10 +991,28 @@ impl Picture {
 		content: &[u8],
 		parse_mode: ParsingMode,
 	) -> Result<(Self, PictureInformation)> {
-		use crate::macros::try_vec;
+		Self::from_flac_reader(
+			&mut &content[..],
+			content.len(),
+			parse_mode,
+			PictureDataPolicy::Load,
+		)
+	}
 
-		let mut size = content.len();
-		let mut reader = Cursor::new(content);
+	/// Read a FLAC `METADATA_BLOCK_PICTURE` of `size` bytes from `reader`
+	///
+	/// With [`PictureDataPolicy::Skip`], the reader is left at the start of the picture data, which
+	/// is up to the caller to consume.
+	pub(crate) fn from_flac_reader<R>(
+		reader: &mut R,
+		mut size: usize,
+		parse_mode: ParsingMode,
+		data_policy: PictureDataPolicy,
+	) -> Result<(Self, PictureInformation)>
+	where
+		R: Read,
+	{
+		use crate::macros::try_vec;
 
 		if size < 32 {
 			err!(NotAPicture);
@@ -683,24 +1035,25 @@ impl Picture {
 			err!(SizeMismatch);
 		}
 
-		let mime_type_str = utf8_decode_str(&content[8..8 + mime_len])?;
-		size -= mime_len;
+		let mut mime_type_bytes = try_vec![0; mime_len];
+		reader.read_exact(&mut mime_type_bytes)?;
 
-		reader.seek(SeekFrom::Current(mime_len as i64))?;
+		let mime_type_str = utf8_decode_str(&mime_type_bytes)?;
+		size -= mime_len;
 
 		let desc_len = reader.read_u32::<BigEndian>()? as usize;
 		size -= 4;
 
 		let mut description = None;
 		if desc_len > 0 && desc_len < size {
-			let pos = 12 + mime_len;
+			let mut desc_bytes = try_vec![0; desc_len];
+			reader.read_exact(&mut desc_bytes)?;
 
-			if let Ok(desc) = utf8_decode_str(&content[pos..pos + desc_len]) {
+			if let Ok(desc) = utf8_decode_str(&desc_bytes) {
 				description = Some(desc.to_owned().into());
 			}
 
 			size -= desc_len;
-			reader.seek(SeekFrom::Current(desc_len as i64))?;
 		}
 
 		let width = reader.read_u32::<BigEndian>()?;
@@ -710,49 +1063,113 @@ impl Picture {
 		let data_len = reader.read_u32::<BigEndian>()? as usize;
 		size -= 20;
 
-		if data_len <= size {
-			let mut data = try_vec![0; data_len];
+		if data_len > size {
+			err!(NotAPicture);
+		}
 
-			if let Ok(()) = reader.read_exact(&mut data) {
-				let mime_type;
-				if mime_type_str.is_empty() {
-					mime_type = None;
-				} else {
-					mime_type = Some(MimeType::from_str(mime_type_str));
+		let mut data = Vec::new();
+		let mut skipped_data_len = None;
+		match data_policy {
+			PictureDataPolicy::Load => {
+				data = try_vec![0; data_len];
+				if reader.read_exact(&mut data).is_err() {
+					err!(NotAPicture);
 				}
+			},
+			PictureDataPolicy::Skip => skipped_data_len = Some(data_len as u64),
+		}
 
-				return Ok((
-					Self {
-						pic_type: PictureType::from_u8(pic_ty as u8),
-						mime_type,
-						description,
-						data: Cow::from(data),
-					},
-					PictureInformation {
-						width,
-						height,
-						color_depth,
-						num_colors,
-					},
-				));
-			}
+		let mime_type;
+		if mime_type_str.is_empty() {
+			mime_type = None;
+		} else {
+			mime_type = Some(MimeType::from_str(mime_type_str));
 		}
 
-		err!(NotAPicture)
+		Ok((
+			Self {
+				pic_type: PictureType::from_u8(pic_ty as u8),
+				mime_type,
+				description,
+				data: Cow::from(data),
+				skipped_data_len,
+			},
+			PictureInformation {
+				width,
+				height,
+				color_depth,
+				num_colors,
+			},
+		))
 	}
 
 	/// Convert a [`Picture`] to an APE Cover Art byte vec:
 	///
-	/// NOTE: This is only the picture data and description, a
+	/// APE cover art is conventionally prefixed with the picture's filename. If the description
+	/// looks like a filename (such as `"cover.jpg"`), it will be used as-is. Otherwise, a filename
+	/// is made up from the MIME type (such as `"cover.png"`). To choose the filename, see
+	/// [`Picture::as_ape_bytes_with_filename`].
+	///
+	/// NOTE: This is only the picture data and filename, a
 	/// key and terminating null byte will not be prepended.
 	/// To map a [`PictureType`] to an APE key see [`PictureType::as_ape_key`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::{MimeType, Picture, PictureType};
+	///
+	/// let picture = Picture::new_unchecked(
+	/// 	PictureType::CoverFront,
+	/// 	Some(MimeType::Png),
+	/// 	Some(String::from("Front cover")),
+	/// 	vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A],
+	/// );
+	///
+	/// // "Front cover" isn't a filename, so one is made up
+	/// assert!(picture.as_ape_bytes().starts_with(b"cover.png\0"));
+	/// ```
 	pub fn as_ape_bytes(&self) -> Vec<u8> {
-		let mut data: Vec<u8> = Vec::new();
-
-		if let Some(desc) = &self.description {
-			data.extend(desc.as_bytes());
+		match self.description.as_deref() {
+			Some(desc) if is_filename(desc) => self.as_ape_bytes_with_filename(desc),
+			_ => {
+				let filename = match self.mime_type.as_ref().and_then(MimeType::ext) {
+					Some(ext) => format!("cover.{ext}"),
+					None => String::new(),
+				};
+
+				self.as_ape_bytes_with_filename(&filename)
+			},
 		}
+	}
 
+	/// Convert a [`Picture`] to an APE Cover Art byte vec, with the given filename
+	///
+	/// Anything in `filename` after a null byte is discarded.
+	///
+	/// See [`Picture::as_ape_bytes`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::{MimeType, Picture, PictureType};
+	///
+	/// let picture = Picture::new_unchecked(
+	/// 	PictureType::CoverFront,
+	/// 	Some(MimeType::Png),
+	/// 	None,
+	/// 	vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A],
+	/// );
+	///
+	/// let bytes = picture.as_ape_bytes_with_filename("folder.png");
+	/// assert!(bytes.starts_with(b"folder.png\0"));
+	/// ```
+	pub fn as_ape_bytes_with_filename(&self, filename: &str) -> Vec<u8> {
+		let filename = filename.split('\0').next().unwrap_or_default();
+
+		let mut data: Vec<u8> = Vec::with_capacity(filename.len() + 1 + self.data.len());
+
+		data.extend(filename.as_bytes());
 		data.push(0);
 		data.extend(self.data.iter());
 
@@ -774,25 +1191,15 @@ impl Picture {
 
 		let pic_type = PictureType::from_ape_key(key);
 
-		let reader = &mut &*bytes;
-		let mut pos = 0;
-
-		let mut description = None;
-		let mut desc_text = String::new();
-
-		while let Ok(ch) = reader.read_u8() {
-			pos += 1;
+		// The picture data is prefixed with its (null terminated) filename
+		let Some(filename_len) = bytes.iter().position(|&b| b == 0) else {
+			err!(NotAPicture);
+		};
 
-			if ch == b'\0' {
-				break;
-			}
+		let description = ape_filename_description(&bytes[..filename_len]);
 
-			desc_text.push(char::from(ch));
-		}
-
-		if !desc_text.is_empty() {
-			description = Some(Cow::from(desc_text));
-		}
+		let pos = filename_len + 1;
+		let reader = &mut &bytes[pos..];
 
 		let mime_type = {
 			let mut identifier = [0; 8];
@@ -808,12 +1215,14 @@ impl Picture {
 			mime_type: Some(mime_type),
 			description,
 			data,
+			skipped_data_len: None,
 		})
 	}
 
 	pub(crate) fn mimetype_from_bin(bytes: &[u8]) -> Result<MimeType> {
-		match bytes[..8] {
-			[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A] => Ok(MimeType::Png),
+		match bytes {
+			[b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Ok(MimeType::WebP),
+			[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Ok(MimeType::Png),
 			[0xFF, 0xD8, ..] => Ok(MimeType::Jpeg),
 			[b'G', b'I', b'F', 0x38, 0x37 | 0x39, b'a', ..] => Ok(MimeType::Gif),
 			[b'B', b'M', ..] => Ok(MimeType::Bmp),
@@ -823,10 +1232,75 @@ impl Picture {
 	}
 }
 
+// The description of a picture read from the filename of an APE cover art item
+pub(crate) fn ape_filename_description(filename: &[u8]) -> Option<Cow<'static, str>> {
+	if filename.is_empty() {
+		return None;
+	}
+
+	// APE text is UTF-8, but some taggers write the filename in the system encoding
+	let filename = match utf8_decode_str(filename) {
+		Ok(filename) => String::from(filename),
+		Err(_) => latin1_decode(filename),
+	};
+
+	Some(Cow::from(filename))
+}
+
+// Whether a picture description can be used as the filename of an APE cover art item
+//
+// This only needs to be good enough to tell "cover.jpg" apart from "Front cover".
+fn is_filename(description: &str) -> bool {
+	if description
+		.chars()
+		.any(|c| c.is_control() || matches!(c, '/' | '\\'))
+	{
+		return false;
+	}
+
+	let Some((stem, ext)) = description.rsplit_once('.') else {
+		return false;
+	};
+
+	!stem.trim().is_empty()
+		&& (1..=4).contains(&ext.len())
+		&& ext.bytes().all(|b| b.is_ascii_alphanumeric())
+}
+
 // A placeholder that is needed during conversions.
 pub(crate) const TOMBSTONE_PICTURE: Picture = Picture {
 	pic_type: PictureType::Other,
 	mime_type: None,
 	description: None,
 	data: Cow::Owned(Vec::new()),
+	skipped_data_len: None,
 };
+
+/// Decode base64 as found in Vorbis Comments
+///
+/// Some encoders wrap the encoded data across multiple lines, or omit the padding.
+pub(crate) fn decode_base64(bytes: &[u8]) -> Option<Vec<u8>> {
+	let bytes = if bytes.iter().any(u8::is_ascii_whitespace) {
+		Cow::Owned(
+			bytes
+				.iter()
+				.copied()
+				.filter(|b| !b.is_ascii_whitespace())
+				.collect::<Vec<_>>(),
+		)
+	} else {
+		Cow::Borrowed(bytes)
+	};
+
+	if bytes.len() % 4 == 0 {
+		if let Ok(data) = BASE64.decode(&bytes) {
+			return Some(data);
+		}
+	}
+
+	let end = bytes
+		.iter()
+		.rposition(|b| *b != b'=')
+		.map_or(0, |pos| pos + 1);
+	BASE64_NOPAD.decode(&bytes[..end]).ok()
+}
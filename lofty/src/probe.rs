@@ -1,27 +1,40 @@
 //! Format-agonostic file parsing tools
 
+use crate::aac::header::is_adts_header;
 use crate::aac::AacFile;
 use crate::ape::ApeFile;
-use crate::config::{global_options, ParseOptions};
-use crate::error::Result;
-use crate::file::{AudioFile, FileType, FileTypeGuessResult, TaggedFile};
+use crate::asf::AsfFile;
+use crate::config::{global_options, ParseOptions, PictureDataPolicy};
+use crate::dff::DffFile;
+use crate::dsf::DsfFile;
+use crate::error::{ErrorKind, LoftyError, Result};
+use crate::file::{AudioFile, FileType, FileTypeGuessResult, TaggedFile, AC3_SYNC};
 use crate::flac::FlacFile;
+use crate::id3::v2::util::synchsafe::SynchsafeInteger;
+use crate::id3::MAX_STACKED_ID3V2_TAGS;
 use crate::iff::aiff::AiffFile;
 use crate::iff::wav::WavFile;
 use crate::macros::err;
 use crate::mp4::Mp4File;
-use crate::mpeg::header::search_for_frame_sync;
+use crate::mpeg::header::{search_for_frame_sync, verify_frames};
 use crate::mpeg::MpegFile;
 use crate::musepack::MpcFile;
+use crate::ogg::flac::OggFlacFile;
 use crate::ogg::opus::OpusFile;
 use crate::ogg::speex::SpeexFile;
 use crate::ogg::vorbis::VorbisFile;
+use crate::picture::Picture;
+use crate::properties::StreamVerification;
 use crate::resolve::custom_resolvers;
+use crate::shn::ShnFile;
+use crate::tta::TtaFile;
+use crate::util::{diagnostics, picture_sink, tag_locations};
 use crate::wavpack::WavPackFile;
 
 use std::fs::File;
-use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::time::Duration;
 
 /// A format agnostic reader
 ///
@@ -79,7 +92,7 @@ use std::path::Path;
 /// # Ok(())
 /// # }
 /// ```
-pub struct Probe<R: Read> {
+pub struct Probe<R> {
 	inner: R,
 	options: Option<ParseOptions>,
 	f_ty: Option<FileType>,
@@ -145,7 +158,9 @@ impl<R: Read> Probe<R> {
 			f_ty: Some(file_type),
 		}
 	}
+}
 
+impl<R> Probe<R> {
 	/// Returns the current [`FileType`]
 	///
 	/// # Examples
@@ -304,13 +319,9 @@ impl<R: Read + Seek> Probe<R> {
 	/// # Ok(()) }
 	/// ```
 	pub fn guess_file_type(mut self) -> std::io::Result<Self> {
-		let max_junk_bytes = self
-			.options
-			.map_or(ParseOptions::DEFAULT_MAX_JUNK_BYTES, |options| {
-				options.max_junk_bytes
-			});
+		let options = self.options.unwrap_or_default();
 
-		let f_ty = self.guess_inner(max_junk_bytes)?;
+		let f_ty = self.guess_inner(options)?;
 		self.f_ty = f_ty.or(self.f_ty);
 
 		log::debug!("Probe: Guessed file type: {:?}", self.f_ty);
@@ -319,7 +330,7 @@ impl<R: Read + Seek> Probe<R> {
 	}
 
 	#[allow(clippy::shadow_unrelated)]
-	fn guess_inner(&mut self, max_junk_bytes: usize) -> std::io::Result<Option<FileType>> {
+	fn guess_inner(&mut self, options: ParseOptions) -> std::io::Result<Option<FileType>> {
 		// temporary buffer for storing 36 bytes
 		// (36 is just a guess as to how long the data for estimating the file type might be)
 		let mut buf = [0; 36];
@@ -357,10 +368,36 @@ impl<R: Read + Seek> Probe<R> {
 			FileTypeGuessResult::MaybePrecededById3(id3_len) => {
 				// `id3_len` is the size of the tag, not including the header (10 bytes)
 				log::debug!("Probe: ID3v2 tag detected, skipping {} bytes", 10 + id3_len);
-				let position_after_id3_block = self
+				let mut position_after_id3_block = self
 					.inner
 					.seek(SeekFrom::Current(i64::from(10 + id3_len)))?;
 
+				// Some software writes a new tag in front of the existing one, skip any that follow
+				for _ in 1..MAX_STACKED_ID3V2_TAGS {
+					let mut id3v2_header = [0; 10];
+					if self.inner.read_exact(&mut id3v2_header).is_err()
+						|| &id3v2_header[..3] != b"ID3"
+					{
+						break;
+					}
+
+					let size = u32::from_be_bytes([
+						id3v2_header[6],
+						id3v2_header[7],
+						id3v2_header[8],
+						id3v2_header[9],
+					])
+					.unsynch();
+
+					log::debug!(
+						"Probe: Stacked ID3v2 tag detected, skipping {} bytes",
+						10 + size
+					);
+					position_after_id3_block += 10 + u64::from(size);
+				}
+
+				self.inner.seek(SeekFrom::Start(position_after_id3_block))?;
+
 				// try to guess the file type after the ID3 block by inspecting the first 4 bytes
 				let mut ident = [0; 4];
 				std::io::copy(
@@ -374,8 +411,12 @@ impl<R: Read + Seek> Probe<R> {
 					[b'M', b'A', b'C', ..] => Ok(Some(FileType::Ape)),
 					b"fLaC" => Ok(Some(FileType::Flac)),
 					b"MPCK" | [b'M', b'P', b'+', ..] => Ok(Some(FileType::Mpc)),
+					b"TTA1" => Ok(Some(FileType::Tta)),
+					b"ajkg" => Ok(Some(FileType::Shn)),
+					// Unsupported, and not to be searched through for an MPEG frame sync
+					[a, b, ..] if [*a, *b] == AC3_SYNC => Ok(None),
 					// Search for a frame sync, which may be preceded by junk
-					_ => self.check_mpeg_or_aac(max_junk_bytes),
+					_ => self.check_mpeg_or_aac(options),
 				};
 
 				// before returning any result for a file type, seek back to the front
@@ -387,10 +428,10 @@ impl<R: Read + Seek> Probe<R> {
 			FileTypeGuessResult::MaybePrecededByJunk => {
 				log::debug!(
 					"Probe: Possible junk bytes detected, searching up to {} bytes",
-					max_junk_bytes
+					options.max_junk_bytes
 				);
 
-				let ret = self.check_mpeg_or_aac(max_junk_bytes);
+				let ret = self.check_mpeg_or_aac(options);
 
 				// before returning any result for a file type, seek back to the front
 				self.inner.seek(SeekFrom::Start(starting_position))?;
@@ -401,27 +442,57 @@ impl<R: Read + Seek> Probe<R> {
 	}
 
 	/// Searches for an MPEG/AAC frame sync, which may be preceded by junk bytes
-	fn check_mpeg_or_aac(&mut self, max_junk_bytes: usize) -> std::io::Result<Option<FileType>> {
+	///
+	/// Junk may contain bytes that look like a frame sync, so the frames following each one are checked as well.
+	/// If no frame sync can be verified, the first one found is used.
+	fn check_mpeg_or_aac(&mut self, options: ParseOptions) -> std::io::Result<Option<FileType>> {
+		let start = self.inner.stream_position()?;
+
+		let mut first_guess = None;
+		let mut search_pos = start;
+		while let Some(remaining_window) =
+			(options.max_junk_bytes as u64).checked_sub(search_pos - start)
 		{
-			let mut restricted_reader = self.inner.by_ref().take(max_junk_bytes as u64);
-			if search_for_frame_sync(&mut restricted_reader)?.is_none() {
-				return Ok(None);
+			// Up to `max_junk_bytes` may precede the frame sync, so allow for the 2 bytes of the sync itself
+			let Some(sync_relative) =
+				search_for_frame_sync(&mut self.inner.by_ref().take(remaining_window + 2))?
+			else {
+				break;
+			};
+
+			// Seek back to the start of the frame sync to check if we are dealing with
+			// an AAC or MPEG file. See `FileType::quick_type_guess` for explanation.
+			let sync_pos = search_pos + sync_relative;
+			self.inner.seek(SeekFrom::Start(sync_pos))?;
+			log::debug!("Probe: Found possible frame sync at position {}", sync_pos);
+
+			let mut buf = [0; 2];
+			self.inner.read_exact(&mut buf)?;
+			self.inner.seek(SeekFrom::Start(sync_pos))?;
+
+			let (file_type, verified) = if buf[1] & 0b10000 > 0 && buf[1] & 0b110 == 0 {
+				(
+					FileType::Aac,
+					verify_adts_frames(&mut self.inner, options.mpeg_sync_frames)?,
+				)
+			} else {
+				let run = verify_frames(&mut self.inner, options.mpeg_sync_frames)?;
+				(
+					FileType::Mpeg,
+					run.is_some_and(|run| run.is_synced(options.mpeg_sync_frames)),
+				)
+			};
+
+			if verified {
+				return Ok(Some(file_type));
 			}
-		}
-
-		// Seek back to the start of the frame sync to check if we are dealing with
-		// an AAC or MPEG file. See `FileType::quick_type_guess` for explanation.
-		let sync_pos = self.inner.seek(SeekFrom::Current(-2))?;
-		log::debug!("Probe: Found possible frame sync at position {}", sync_pos);
 
-		let mut buf = [0; 2];
-		self.inner.read_exact(&mut buf)?;
+			first_guess.get_or_insert(file_type);
 
-		if buf[1] & 0b10000 > 0 && buf[1] & 0b110 == 0 {
-			Ok(Some(FileType::Aac))
-		} else {
-			Ok(Some(FileType::Mpeg))
+			search_pos = self.inner.seek(SeekFrom::Start(sync_pos + 1))?;
 		}
+
+		Ok(first_guess)
 	}
 
 	/// Attempts to extract a [`TaggedFile`] from the reader
@@ -463,34 +534,338 @@ impl<R: Read + Seek> Probe<R> {
 			log::warn!("Skipping both tag and property reading, file will be empty");
 		}
 
-		match self.f_ty {
-			Some(f_type) => Ok(match f_type {
-				FileType::Aac => AacFile::read_from(reader, options)?.into(),
-				FileType::Aiff => AiffFile::read_from(reader, options)?.into(),
-				FileType::Ape => ApeFile::read_from(reader, options)?.into(),
-				FileType::Flac => FlacFile::read_from(reader, options)?.into(),
-				FileType::Mpeg => MpegFile::read_from(reader, options)?.into(),
-				FileType::Opus => OpusFile::read_from(reader, options)?.into(),
-				FileType::Vorbis => VorbisFile::read_from(reader, options)?.into(),
-				FileType::Wav => WavFile::read_from(reader, options)?.into(),
-				FileType::Mp4 => Mp4File::read_from(reader, options)?.into(),
-				FileType::Mpc => MpcFile::read_from(reader, options)?.into(),
-				FileType::Speex => SpeexFile::read_from(reader, options)?.into(),
-				FileType::WavPack => WavPackFile::read_from(reader, options)?.into(),
-				FileType::Custom(c) => {
-					if !unsafe { global_options().use_custom_resolvers } {
-						err!(UnknownFormat)
-					}
+		let ((tagged_file, diagnostics), tag_locations) = tag_locations::collect(|| {
+			diagnostics::collect(|| match self.f_ty {
+				Some(f_type) => Ok(match f_type {
+					FileType::Aac => AacFile::read_from(reader, options)?.into(),
+					FileType::Aiff => AiffFile::read_from(reader, options)?.into(),
+					FileType::Ape => ApeFile::read_from(reader, options)?.into(),
+					FileType::Asf => AsfFile::read_from(reader, options)?.into(),
+					FileType::Dff => DffFile::read_from(reader, options)?.into(),
+					FileType::Dsf => DsfFile::read_from(reader, options)?.into(),
+					FileType::Flac => FlacFile::read_from(reader, options)?.into(),
+					FileType::Mpeg => MpegFile::read_from(reader, options)?.into(),
+					FileType::OggFlac => OggFlacFile::read_from(reader, options)?.into(),
+					FileType::Opus => OpusFile::read_from(reader, options)?.into(),
+					FileType::Vorbis => VorbisFile::read_from(reader, options)?.into(),
+					FileType::Wav => WavFile::read_from(reader, options)?.into(),
+					FileType::Mp4 => Mp4File::read_from(reader, options)?.into(),
+					FileType::Mpc => MpcFile::read_from(reader, options)?.into(),
+					FileType::Speex => SpeexFile::read_from(reader, options)?.into(),
+					FileType::Shn => ShnFile::read_from(reader, options)?.into(),
+					FileType::Tta => TtaFile::read_from(reader, options)?.into(),
+					FileType::WavPack => WavPackFile::read_from(reader, options)?.into(),
+					FileType::Custom(c) => {
+						if !unsafe { global_options().use_custom_resolvers } {
+							err!(UnknownFormat)
+						}
+
+						let resolver = crate::resolve::lookup_resolver(c);
+						resolver.read_from(reader, options)?
+					},
+				}),
+				None => err!(UnknownFormat),
+			})
+		});
+
+		let mut tagged_file: TaggedFile = tagged_file?;
+		tagged_file.diagnostics = diagnostics;
+		tagged_file.tag_locations = tag_locations;
+
+		Ok(tagged_file)
+	}
+
+	/// Attempts to extract a [`TaggedFile`] from the reader, streaming picture data to writers
+	///
+	/// This reads with [`PictureDataPolicy::Skip`], so no picture data is kept in memory. Instead, `f`
+	/// is called with the (data-less) [`Picture`] whenever one is encountered, and may return a writer
+	/// to receive its data. Returning `None` discards the data.
+	///
+	/// All other options are taken from [`Probe::options`].
+	///
+	/// # Errors
+	///
+	/// See [`Probe::read`]. Errors from the writers are also propagated.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::PictureType;
+	/// use lofty::prelude::*;
+	/// use lofty::probe::Probe;
+	/// use std::fs::File;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.flac";
+	/// # let out_dir = std::env::temp_dir();
+	/// let tagged_file = Probe::open(path)?.pictures_to_writers(move |picture| {
+	/// 	if picture.pic_type() != PictureType::CoverFront {
+	/// 		return None;
+	/// 	}
+	///
+	/// 	File::create(out_dir.join("cover")).ok()
+	/// })?;
+	///
+	/// // The pictures are still available, just without their data
+	/// for picture in tagged_file.primary_tag().into_iter().flat_map(|tag| tag.pictures()) {
+	/// 	assert!(picture.data().is_empty());
+	/// 	assert!(picture.skipped_data_len().is_some());
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn pictures_to_writers<F, W>(mut self, mut f: F) -> Result<TaggedFile>
+	where
+		F: FnMut(&Picture) -> Option<W> + 'static,
+		W: Write + 'static,
+	{
+		let mut options = self.options.unwrap_or_default();
+		options.picture_data_policy = PictureDataPolicy::Skip;
+		self.options = Some(options);
 
-					let resolver = crate::resolve::lookup_resolver(c);
-					resolver.read_from(reader, options)?
-				},
+		picture_sink::with_sink(
+			Box::new(move |picture| {
+				let writer: Box<dyn Write> = Box::new(f(picture)?);
+				Some(writer)
 			}),
+			|| self.read(),
+		)
+	}
+
+	/// Read only the duration of the stream
+	///
+	/// The duration is the same as [`Probe::read`] would report, but only the headers needed for it
+	/// are read:
+	///
+	/// * AIFF: The `COMM` chunk
+	/// * FLAC: The `STREAMINFO` block
+	/// * MP4: The `mdhd` atom of the audio track, the `ilst` atom is never visited
+	/// * MPEG: The first frame and its Xing/VBRI header. Streams without one still need to search for
+	///   the last frame, as the duration is estimated from the stream length.
+	/// * Ogg Vorbis/Opus/Speex/FLAC: The identification packet, and the granule position of the last
+	///   page
+	/// * WAV: The `fmt `, `fact`, and `data` chunks
+	///
+	/// Any other format is read in full, with [`ParseOptions::read_tags`] and
+	/// [`ParseOptions::read_cover_art`] disabled.
+	///
+	/// # Errors
+	///
+	/// * No file type, see [`Probe::read`]
+	/// * The headers of the stream are invalid
+	///
+	/// # Panics
+	///
+	/// See [`Probe::read`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::probe::Probe;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.flac";
+	/// let duration = Probe::open(path)?.duration()?;
+	/// println!("{} seconds", duration.as_secs());
+	/// # Ok(()) }
+	/// ```
+	pub fn duration(mut self) -> Result<Duration> {
+		let options = self
+			.options
+			.unwrap_or_default()
+			.read_tags(false)
+			.read_cover_art(false)
+			.read_properties(true);
+
+		let reader = &mut self.inner;
+
+		match self.f_ty {
+			Some(FileType::Aiff) => crate::iff::aiff::read::read_duration(reader),
+			Some(FileType::Flac) => crate::flac::read::read_duration(reader, options),
+			Some(FileType::Mp4) => crate::mp4::read::read_duration(reader, options),
+			Some(FileType::Mpeg) => crate::mpeg::read::read_duration(reader, options),
+			Some(
+				file_type @ (FileType::OggFlac
+				| FileType::Opus
+				| FileType::Speex
+				| FileType::Vorbis),
+			) => crate::ogg::read_duration(reader, file_type, options),
+			Some(FileType::Wav) => crate::iff::wav::read::read_duration(reader),
+			Some(_) => {
+				let tagged_file = self.options(options).read()?;
+				Ok(tagged_file.properties().duration())
+			},
+			None => err!(UnknownFormat),
+		}
+	}
+
+	/// Walk the audio frames of the stream, checking that they're all there
+	///
+	/// A truncated file will usually still report its full duration, as that is read from a header
+	/// (e.g. FLAC's `STREAMINFO` block). This compares that duration to the frames actually in the
+	/// stream, without decoding them.
+	///
+	/// What is checked depends on the format:
+	///
+	/// * FLAC: The CRC of every frame, and the total sample count in `STREAMINFO`
+	/// * MPEG: The frame headers, and the frame count in the Xing/VBRI header
+	/// * Ogg Vorbis/Opus/Speex/FLAC: The CRC and sequence number of every page, and the end of
+	///   stream flag. Only the first logical stream is checked.
+	///
+	/// Tags are skipped, and [`Probe::options`] has no effect.
+	///
+	/// # Errors
+	///
+	/// * No file type, see [`Probe::read`]
+	/// * The file type isn't supported ([`ErrorKind::UnsupportedVerification`])
+	/// * The headers of the stream are invalid
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::probe::Probe;
+	/// use std::io::Cursor;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.flac";
+	/// let mut file = std::fs::read(path)?;
+	///
+	/// // Cut the file short
+	/// file.truncate(file.len() / 2);
+	///
+	/// let verification = Probe::new(Cursor::new(file))
+	/// 	.guess_file_type()?
+	/// 	.verify_stream()?;
+	/// assert!(verification.truncated);
+	/// assert!(verification.found < verification.expected);
+	/// # Ok(()) }
+	/// ```
+	pub fn verify_stream(mut self) -> Result<StreamVerification> {
+		let reader = &mut self.inner;
+
+		match self.f_ty {
+			Some(FileType::Flac) => crate::flac::verify::verify_stream(reader),
+			Some(FileType::Mpeg) => crate::mpeg::verify::verify_stream(reader),
+			Some(FileType::OggFlac | FileType::Opus | FileType::Speex | FileType::Vorbis) => {
+				crate::ogg::verify::verify_stream(reader)
+			},
+			Some(file_type) => Err(LoftyError::new(ErrorKind::UnsupportedVerification(
+				file_type,
+			))),
 			None => err!(UnknownFormat),
 		}
 	}
 }
 
+#[cfg(feature = "async")]
+impl<R> Probe<R>
+where
+	R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin,
+{
+	/// Create a new `Probe` from an async reader
+	///
+	/// This is the async equivalent of [`Probe::new`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::probe::Probe;
+	///
+	/// # #[tokio::main(flavor = "current_thread")]
+	/// # async fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let file = tokio::fs::File::open(path).await?;
+	///
+	/// let probe = Probe::new_async(file);
+	/// # Ok(()) }
+	/// ```
+	#[must_use]
+	pub const fn new_async(reader: R) -> Self {
+		Self {
+			inner: reader,
+			options: None,
+			f_ty: None,
+		}
+	}
+
+	/// Attempts to get the [`FileType`] based on the data in an async reader
+	///
+	/// This is the async equivalent of [`Probe::guess_file_type`].
+	///
+	/// # Errors
+	///
+	/// See [`Probe::guess_file_type`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::file::FileType;
+	/// use lofty::probe::Probe;
+	///
+	/// # #[tokio::main(flavor = "current_thread")]
+	/// # async fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let file = tokio::fs::File::open(path).await?;
+	/// let probe = Probe::new_async(file).guess_file_type_async().await?;
+	///
+	/// // Determined the file is MP3 from the content
+	/// assert_eq!(probe.file_type(), Some(FileType::Mpeg));
+	/// # Ok(()) }
+	/// ```
+	pub async fn guess_file_type_async(mut self) -> std::io::Result<Self> {
+		let options = self.options.unwrap_or_default();
+
+		let f_ty = crate::util::async_io::read_from_async(&mut self.inner, |reader| {
+			Probe::new(reader)
+				.options(options)
+				.guess_file_type()
+				.map(|probe| probe.file_type())
+		})
+		.await?;
+		self.f_ty = f_ty.or(self.f_ty);
+
+		log::debug!("Probe: Guessed file type: {:?}", self.f_ty);
+
+		Ok(self)
+	}
+
+	/// Attempts to extract a [`TaggedFile`] from an async reader
+	///
+	/// This is the async equivalent of [`Probe::read`]. Parsing itself is not async, the data
+	/// is fetched from the reader ahead of time, as it is needed.
+	///
+	/// # Errors
+	///
+	/// See [`Probe::read`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::probe::Probe;
+	///
+	/// # #[tokio::main(flavor = "current_thread")]
+	/// # async fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.mp3";
+	/// let file = tokio::fs::File::open(path).await?;
+	/// let probe = Probe::new_async(file).guess_file_type_async().await?;
+	///
+	/// let parsed_file = probe.read_async().await?;
+	/// # Ok(()) }
+	/// ```
+	pub async fn read_async(mut self) -> Result<TaggedFile> {
+		let options = self.options;
+		let f_ty = self.f_ty;
+
+		crate::util::async_io::read_from_async(&mut self.inner, |reader| {
+			let mut probe = Probe::new(reader);
+			probe.options = options;
+			probe.f_ty = f_ty;
+
+			probe.read()
+		})
+		.await
+	}
+}
+
 /// Read a [`TaggedFile`] from a [File]
 ///
 /// # Errors
@@ -545,6 +920,122 @@ where
 	Probe::open(path)?.read()
 }
 
+/// Read the duration of a file from a path
+///
+/// This is for when only the duration is needed, such as when building playlists. Only the headers
+/// needed for the duration are read, see [`Probe::duration`] for what that means for each format.
+///
+/// NOTE: This will determine the [`FileType`] from the extension
+///
+/// # Errors
+///
+/// See:
+///
+/// * [`Probe::open`]
+/// * [`Probe::duration`]
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::file::AudioFile;
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// let duration = lofty::duration(path)?;
+///
+/// // The same duration as a full read
+/// let tagged_file = lofty::read_from_path(path)?;
+/// assert_eq!(duration, tagged_file.properties().duration());
+/// # Ok(()) }
+/// ```
+pub fn duration<P>(path: P) -> Result<Duration>
+where
+	P: AsRef<Path>,
+{
+	Probe::open(path)?.duration()
+}
+
+/// Walk the audio frames of a file from a path, checking that they're all there
+///
+/// NOTE: This will determine the [`FileType`] from the extension
+///
+/// See [`Probe::verify_stream`] for what is checked.
+///
+/// # Errors
+///
+/// See:
+///
+/// * [`Probe::open`]
+/// * [`Probe::verify_stream`]
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> lofty::error::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// let verification = lofty::verify_stream(path)?;
+///
+/// if verification.truncated {
+/// 	println!(
+/// 		"Only {:?} of {:?} is there",
+/// 		verification.found, verification.expected
+/// 	);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn verify_stream<P>(path: P) -> Result<StreamVerification>
+where
+	P: AsRef<Path>,
+{
+	Probe::open(path)?.verify_stream()
+}
+
+// Checks that the ADTS frame at the current position is followed by others with a matching header
+//
+// If the stream ends before `max_frames` frames are found, the available frames are accepted.
+fn verify_adts_frames<R>(reader: &mut R, max_frames: u8) -> std::io::Result<bool>
+where
+	R: Read + Seek,
+{
+	let mut first_header: Option<[u8; 6]> = None;
+	for _ in 0..max_frames {
+		let start = reader.stream_position()?;
+
+		let mut header = [0; 6];
+		if reader.read_exact(&mut header).is_err() {
+			return Ok(first_header.is_some());
+		}
+
+		if !is_adts_header(&header) {
+			return Ok(false);
+		}
+
+		// The MPEG version, layer, profile, sample rate, and channel configuration must match
+		if let Some(first_header) = first_header {
+			if header[1] & 0xF6 != first_header[1] & 0xF6
+				|| header[2] & 0xFD != first_header[2] & 0xFD
+				|| header[3] & 0xC0 != first_header[3] & 0xC0
+			{
+				return Ok(false);
+			}
+		} else {
+			first_header = Some(header);
+		}
+
+		// The 13 bit frame length, including the header
+		let frame_len = (u64::from(header[3] & 0b11) << 11)
+			| (u64::from(header[4]) << 3)
+			| (u64::from(header[5]) >> 5);
+		if frame_len < 7 {
+			return Ok(false);
+		}
+
+		reader.seek(SeekFrom::Start(start + frame_len))?;
+	}
+
+	Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::config::{GlobalOptions, ParseOptions};
@@ -671,6 +1162,46 @@ mod tests {
 		assert!(probe.read().is_ok());
 	}
 
+	#[test_log::test]
+	fn ac3_not_searched_for_mpeg_frames() {
+		// The start of an MP3 frame
+		let mpeg_frame = [
+			0xFF, 0xFB, 0x50, 0xC4, 0x00, 0x03, 0xC0, 0x00, 0x01, 0xA4, 0x00, 0x00, 0x00, 0x20,
+		];
+
+		let mut ac3 = vec![0x0B, 0x77, 0x12, 0x34, 0x56, 0x78];
+		ac3.extend_from_slice(&mpeg_frame);
+
+		let probe = Probe::new(std::io::Cursor::new(&ac3))
+			.guess_file_type()
+			.unwrap();
+		assert_eq!(probe.file_type(), None);
+
+		// Preceded by an empty ID3v2.4 tag
+		let mut id3v2_and_ac3 = vec![b'I', b'D', b'3', 4, 0, 0, 0, 0, 0, 0];
+		id3v2_and_ac3.extend_from_slice(&ac3);
+
+		let probe = Probe::new(std::io::Cursor::new(&id3v2_and_ac3))
+			.guess_file_type()
+			.unwrap();
+		assert_eq!(probe.file_type(), None);
+	}
+
+	#[test_log::test]
+	fn adts_invalid_header_is_junk() {
+		// A sampling frequency index of 15 is forbidden
+		let invalid_adts_header = [0xFF, 0xF1, 0xFC, 0x80, 0x01, 0xBF, 0xFC];
+		assert_eq!(FileType::from_buffer(&invalid_adts_header), None);
+
+		let mut data = invalid_adts_header.to_vec();
+		data.extend(std::fs::read("tests/files/assets/minimal/untagged.aac").unwrap());
+
+		let probe = Probe::new(std::io::Cursor::new(&data))
+			.guess_file_type()
+			.unwrap();
+		assert_eq!(probe.file_type(), Some(FileType::Aac));
+	}
+
 	fn test_probe(path: &str, expected_file_type_guess: FileType) {
 		test_probe_file(path, expected_file_type_guess);
 		test_probe_path(path, expected_file_type_guess);
@@ -734,6 +1265,14 @@ mod tests {
 		test_probe("tests/files/assets/minimal/full_test.ogg", FileType::Vorbis);
 	}
 
+	#[test_log::test]
+	fn probe_ogg_flac() {
+		test_probe(
+			"tests/files/assets/minimal/full_test.oga",
+			FileType::OggFlac,
+		);
+	}
+
 	#[test_log::test]
 	fn probe_opus() {
 		test_probe("tests/files/assets/minimal/full_test.opus", FileType::Opus);
@@ -26,6 +26,7 @@ macro_rules! define_channels {
 /// * WAV default channel ordering: <https://learn.microsoft.com/en-us/previous-versions/windows/hardware/design/dn653308(v=vs.85)>
 /// * FFmpeg: <https://ffmpeg.org/doxygen/trunk/group__channel__masks.html>
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct ChannelMask(pub(crate) u32);
 
@@ -72,16 +73,36 @@ impl ChannelMask {
 		Self(Self::FRONT_LEFT.0 | Self::FRONT_RIGHT.0 | Self::FRONT_CENTER.0)
 	}
 
+	/// Front left+right+center, low frequency, and back left+right channels
+	#[must_use]
+	pub const fn five_point_one() -> Self {
+		Self(
+			Self::linear_surround().bits()
+				| Self::LOW_FREQUENCY.bits()
+				| Self::BACK_LEFT.bits()
+				| Self::BACK_RIGHT.bits(),
+		)
+	}
+
+	/// Front left+right+center, low frequency, back left+right, and side left+right channels
+	#[must_use]
+	pub const fn seven_point_one() -> Self {
+		Self(Self::five_point_one().bits() | Self::SIDE_LEFT.bits() | Self::SIDE_RIGHT.bits())
+	}
+
 	/// The bit mask
 	#[must_use]
 	pub const fn bits(self) -> u32 {
 		self.0
 	}
 
-	/// Create a channel mask from the number of channels in an Opus file
+	/// Create a channel mask from the number of channels in an OGG Vorbis file
 	///
-	/// See <https://datatracker.ietf.org/doc/html/rfc7845#section-5.1.1.2> for the mapping.
-	pub const fn from_opus_channels(channels: u8) -> Option<Self> {
+	/// See <https://xiph.org/vorbis/doc/Vorbis_I_spec.html#x1-810004.3.9> for the mapping.
+	///
+	/// The same channels are used by [FLAC](https://www.rfc-editor.org/rfc/rfc9639.html#section-9.1.3),
+	/// in a different order.
+	pub const fn from_vorbis_channels(channels: u8) -> Option<Self> {
 		match channels {
 			1 => Some(Self::mono()),
 			2 => Some(Self::stereo()),
@@ -95,12 +116,7 @@ impl ChannelMask {
 			5 => Some(Self(
 				Self::linear_surround().bits() | Self::BACK_LEFT.bits() | Self::BACK_RIGHT.bits(),
 			)),
-			6 => Some(Self(
-				Self::linear_surround().bits()
-					| Self::BACK_LEFT.bits()
-					| Self::BACK_RIGHT.bits()
-					| Self::LOW_FREQUENCY.bits(),
-			)),
+			6 => Some(Self::five_point_one()),
 			7 => Some(Self(
 				Self::linear_surround().bits()
 					| Self::SIDE_LEFT.bits()
@@ -108,18 +124,21 @@ impl ChannelMask {
 					| Self::BACK_CENTER.bits()
 					| Self::LOW_FREQUENCY.bits(),
 			)),
-			8 => Some(Self(
-				Self::linear_surround().bits()
-					| Self::SIDE_LEFT.bits()
-					| Self::SIDE_RIGHT.bits()
-					| Self::BACK_LEFT.bits()
-					| Self::BACK_RIGHT.bits()
-					| Self::LOW_FREQUENCY.bits(),
-			)),
+			8 => Some(Self::seven_point_one()),
 			_ => None,
 		}
 	}
 
+	/// Create a channel mask from the number of channels in an Opus file
+	///
+	/// See <https://datatracker.ietf.org/doc/html/rfc7845#section-5.1.1.2> for the mapping.
+	///
+	/// This is only applicable to channel mapping families 0 and 1, which use the same channels
+	/// as [`ChannelMask::from_vorbis_channels()`].
+	pub const fn from_opus_channels(channels: u8) -> Option<Self> {
+		Self::from_vorbis_channels(channels)
+	}
+
 	/// Create a channel mask from the number of channels in an MP4 file
 	///
 	/// See <https://wiki.multimedia.cx/index.php/MPEG-4_Audio#Channel_Configurations> for the mapping.
@@ -137,20 +156,8 @@ impl ChannelMask {
 			5 => Some(Self(
 				Self::linear_surround().bits() | Self::BACK_LEFT.bits() | Self::BACK_RIGHT.bits(),
 			)),
-			6 => Some(Self(
-				Self::linear_surround().bits()
-					| Self::BACK_LEFT.bits()
-					| Self::BACK_RIGHT.bits()
-					| Self::LOW_FREQUENCY.bits(),
-			)),
-			7 => Some(Self(
-				Self::linear_surround().bits()
-					| Self::SIDE_LEFT.bits()
-					| Self::SIDE_RIGHT.bits()
-					| Self::BACK_LEFT.bits()
-					| Self::BACK_RIGHT.bits()
-					| Self::LOW_FREQUENCY.bits(),
-			)),
+			6 => Some(Self::five_point_one()),
+			7 => Some(Self::seven_point_one()),
 			_ => None,
 		}
 	}
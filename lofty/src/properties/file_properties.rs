@@ -1,8 +1,14 @@
 use super::channel_mask::ChannelMask;
+use crate::config::PrettyPrintOptions;
+use crate::util::pretty_print::{PrettyPrinter, Value};
+
+use std::borrow::Cow;
+use std::fmt;
 use std::time::Duration;
 
 /// Various *immutable* audio properties
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct FileProperties {
 	pub(crate) duration: Duration,
@@ -86,6 +92,76 @@ impl FileProperties {
 		self.channel_mask
 	}
 
+	/// Writes a line-oriented listing of the properties to `writer`
+	///
+	/// The duration is always printed, all other properties only if they are known.
+	///
+	/// See [`PrettyPrintOptions`] for the format.
+	///
+	/// # Errors
+	///
+	/// * Writing to `writer` fails
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::PrettyPrintOptions;
+	/// use lofty::properties::FileProperties;
+	/// use std::time::Duration;
+	///
+	/// # fn main() -> std::fmt::Result {
+	/// let properties = FileProperties::new(
+	/// 	Duration::from_millis(1500),
+	/// 	None,
+	/// 	Some(128),
+	/// 	Some(44100),
+	/// 	None,
+	/// 	Some(2),
+	/// 	None,
+	/// );
+	///
+	/// let mut output = String::new();
+	/// properties.pretty_print(&mut output, PrettyPrintOptions::new())?;
+	///
+	/// assert_eq!(
+	/// 	output,
+	/// 	"Duration\t1500 ms\nAudioBitrate\t128 kbps\nSampleRate\t44100 Hz\nChannels\t2\n"
+	/// );
+	/// # Ok(()) }
+	/// ```
+	pub fn pretty_print(
+		&self,
+		writer: &mut impl fmt::Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		let mut lines = vec![("Duration", format!("{} ms", self.duration.as_millis()))];
+		if let Some(overall_bitrate) = self.overall_bitrate {
+			lines.push(("OverallBitrate", format!("{overall_bitrate} kbps")));
+		}
+		if let Some(audio_bitrate) = self.audio_bitrate {
+			lines.push(("AudioBitrate", format!("{audio_bitrate} kbps")));
+		}
+		if let Some(sample_rate) = self.sample_rate {
+			lines.push(("SampleRate", format!("{sample_rate} Hz")));
+		}
+		if let Some(bit_depth) = self.bit_depth {
+			lines.push(("BitDepth", bit_depth.to_string()));
+		}
+		if let Some(channels) = self.channels {
+			lines.push(("Channels", channels.to_string()));
+		}
+		if let Some(channel_mask) = self.channel_mask {
+			lines.push(("ChannelMask", format!("0x{:08X}", channel_mask.bits())));
+		}
+
+		let mut printer = PrettyPrinter::default();
+		for (key, value) in lines {
+			printer.push_untyped(key, Value::Text(Cow::Owned(value)));
+		}
+
+		printer.finish(writer, options)
+	}
+
 	/// Used for tests
 	#[doc(hidden)]
 	pub fn is_empty(&self) -> bool {
@@ -6,9 +6,37 @@
 
 mod channel_mask;
 mod file_properties;
+mod stream_verification;
 
 #[cfg(test)]
 mod tests;
 
 pub use channel_mask::ChannelMask;
 pub use file_properties::FileProperties;
+pub use stream_verification::StreamVerification;
+
+use crate::config::ParsingMode;
+use crate::error::{ParseDiagnostic, Result};
+use crate::util::diagnostics;
+
+/// Handle the result of reading a file's properties
+///
+/// In [`ParsingMode::Relaxed`], an error (most likely from a truncated stream) shouldn't discard
+/// the tags that were already read. The error is reported as a diagnostic, and `T::default()` is
+/// returned instead.
+pub(crate) fn or_incomplete<T>(result: Result<T>, parsing_mode: ParsingMode) -> Result<T>
+where
+	T: Default,
+{
+	match result {
+		Err(e) if parsing_mode == ParsingMode::Relaxed => {
+			log::warn!("Unable to read properties, they may be incomplete: {e}");
+			diagnostics::report(ParseDiagnostic::IncompleteProperties {
+				message: e.to_string(),
+			});
+
+			Ok(T::default())
+		},
+		result => result,
+	}
+}
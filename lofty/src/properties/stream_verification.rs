@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+/// The result of walking a file's audio frames
+///
+/// The duration reported by a file's headers (e.g. a FLAC `STREAMINFO` block or an MPEG Xing
+/// header) says nothing about whether the audio is actually there. This compares it to the audio
+/// that was found, without decoding it.
+///
+/// See [`Probe::verify_stream`](crate::probe::Probe::verify_stream).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct StreamVerification {
+	/// The duration declared by the file's headers
+	pub expected: Duration,
+	/// The duration of the frames that were found
+	pub found: Duration,
+	/// Whether the stream ends early, either in the middle of a frame or short of [`Self::expected`]
+	pub truncated: bool,
+	/// The offset of the first corrupt frame (or page), if any
+	///
+	/// A frame is corrupt if it fails its checksum, or if frames are missing before it.
+	pub corrupt_at: Option<u64>,
+}
+
+impl StreamVerification {
+	/// Whether the stream is complete and has no corrupt frames
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # fn main() -> lofty::error::Result<()> {
+	/// # let path = "tests/files/assets/minimal/full_test.flac";
+	/// let verification = lofty::verify_stream(path)?;
+	/// assert!(verification.is_intact());
+	/// # Ok(()) }
+	/// ```
+	pub fn is_intact(&self) -> bool {
+		!self.truncated && self.corrupt_at.is_none()
+	}
+}
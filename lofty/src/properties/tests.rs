@@ -1,9 +1,12 @@
 use crate::aac::{AACProperties, AacFile};
 use crate::ape::{ApeFile, ApeProperties};
+use crate::asf::{AsfCodec, AsfFile, AsfProperties};
 use crate::config::ParseOptions;
+use crate::dff::{DffCompressionType, DffFile, DffProperties};
+use crate::dsf::{DsfFile, DsfProperties};
 use crate::file::AudioFile;
 use crate::flac::{FlacFile, FlacProperties};
-use crate::iff::aiff::{AiffFile, AiffProperties};
+use crate::iff::aiff::{AiffCompressionType, AiffFile, AiffProperties};
 use crate::iff::wav::{WavFile, WavFormat, WavProperties};
 use crate::mp4::{AudioObjectType, Mp4Codec, Mp4File, Mp4Properties};
 use crate::mpeg::{ChannelMode, Layer, MpegFile, MpegProperties, MpegVersion};
@@ -12,9 +15,11 @@ use crate::musepack::sv7::{Link, MpcSv7Properties, Profile};
 use crate::musepack::sv8::{EncoderInfo, MpcSv8Properties, ReplayGain, StreamHeader};
 use crate::musepack::{MpcFile, MpcProperties};
 use crate::ogg::{
-	OpusFile, OpusProperties, SpeexFile, SpeexProperties, VorbisFile, VorbisProperties,
+	OggFlacFile, OpusFile, OpusProperties, SpeexFile, SpeexProperties, VorbisFile, VorbisProperties,
 };
-use crate::properties::ChannelMask;
+use crate::properties::{ChannelMask, FileProperties};
+use crate::shn::{ShnFile, ShnProperties};
+use crate::tta::{TtaFile, TtaProperties};
 use crate::wavpack::{WavPackFile, WavPackProperties};
 
 use std::fs::File;
@@ -47,6 +52,36 @@ const AIFF_PROPERTIES: AiffProperties = AiffProperties {
 	compression_type: None,
 };
 
+const AIFC_SOWT_PROPERTIES: AiffProperties = AiffProperties {
+	duration: Duration::from_millis(100),
+	overall_bitrate: 1542,
+	audio_bitrate: 1537,
+	sample_rate: 48000,
+	sample_size: 16,
+	channels: 2,
+	compression_type: Some(AiffCompressionType::sowt),
+};
+
+const AIFC_ULAW_PROPERTIES: AiffProperties = AiffProperties {
+	duration: Duration::from_millis(100),
+	overall_bitrate: 774,
+	audio_bitrate: 769,
+	sample_rate: 48000,
+	sample_size: 16,
+	channels: 2,
+	compression_type: Some(AiffCompressionType::ulaw),
+};
+
+const AIFC_IMA4_PROPERTIES: AiffProperties = AiffProperties {
+	duration: Duration::from_millis(100),
+	overall_bitrate: 414,
+	audio_bitrate: 409,
+	sample_rate: 48000,
+	sample_size: 16,
+	channels: 2,
+	compression_type: Some(AiffCompressionType::ima4),
+};
+
 const APE_PROPERTIES: ApeProperties = ApeProperties {
 	version: 3990,
 	duration: Duration::from_millis(1428),
@@ -57,6 +92,38 @@ const APE_PROPERTIES: ApeProperties = ApeProperties {
 	channels: 2,
 };
 
+const ASF_PROPERTIES: AsfProperties = AsfProperties {
+	codec: AsfCodec::WmaV2,
+	duration: Duration::from_millis(1500),
+	overall_bitrate: 15,
+	audio_bitrate: 128,
+	sample_rate: 48000,
+	bit_depth: 16,
+	channels: 2,
+};
+
+const DFF_PROPERTIES: DffProperties = DffProperties {
+	duration: Duration::from_millis(50),
+	overall_bitrate: 5689,
+	audio_bitrate: 5644,
+	sample_rate: 2_822_400,
+	bit_depth: 1,
+	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
+	compression_type: DffCompressionType::Dsd,
+};
+
+const DSF_PROPERTIES: DsfProperties = DsfProperties {
+	duration: Duration::from_millis(50),
+	overall_bitrate: 6573,
+	audio_bitrate: 5644,
+	sample_rate: 2_822_400,
+	bit_depth: 1,
+	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
+	sample_count: 141_120,
+};
+
 const FLAC_PROPERTIES: FlacProperties = FlacProperties {
 	duration: Duration::from_millis(1428),
 	overall_bitrate: 321,
@@ -64,6 +131,18 @@ const FLAC_PROPERTIES: FlacProperties = FlacProperties {
 	sample_rate: 48000,
 	bit_depth: 16,
 	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
+	signature: 164_506_065_180_489_231_127_156_351_872_182_799_315,
+};
+
+const OGG_FLAC_PROPERTIES: FlacProperties = FlacProperties {
+	duration: Duration::from_millis(1428),
+	overall_bitrate: 279,
+	audio_bitrate: 278,
+	sample_rate: 48000,
+	bit_depth: 16,
+	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
 	signature: 164_506_065_180_489_231_127_156_351_872_182_799_315,
 };
 
@@ -112,6 +191,22 @@ const MP3_PROPERTIES: MpegProperties = MpegProperties {
 	emphasis: None,
 };
 
+// A mono Fraunhofer-style VBR stream, whose first frame (128 kbps) is much larger than the average
+const MP3_VBRI_PROPERTIES: MpegProperties = MpegProperties {
+	version: MpegVersion::V1,
+	layer: Layer::Layer3,
+	channel_mode: ChannelMode::SingleChannel,
+	mode_extension: None,
+	copyright: false,
+	original: true,
+	duration: Duration::from_secs(6),
+	overall_bitrate: 43,
+	audio_bitrate: 43,
+	sample_rate: 48000,
+	channels: 1,
+	emphasis: None,
+};
+
 const MP4_AAC_PROPERTIES: Mp4Properties = Mp4Properties {
 	codec: Mp4Codec::AAC,
 	extended_audio_object_type: Some(AudioObjectType::AacLowComplexity),
@@ -121,6 +216,7 @@ const MP4_AAC_PROPERTIES: Mp4Properties = Mp4Properties {
 	sample_rate: 48000,
 	bit_depth: None,
 	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
 	drm_protected: false,
 };
 
@@ -133,6 +229,7 @@ const MP4_ALAC_PROPERTIES: Mp4Properties = Mp4Properties {
 	sample_rate: 48000,
 	bit_depth: Some(16),
 	channels: 2,
+	channel_mask: None,
 	drm_protected: false,
 };
 
@@ -145,6 +242,7 @@ const MP4_ALS_PROPERTIES: Mp4Properties = Mp4Properties {
 	sample_rate: 48000,
 	bit_depth: None,
 	channels: 2,
+	channel_mask: None,
 	drm_protected: false,
 };
 
@@ -157,6 +255,7 @@ const MP4_FLAC_PROPERTIES: Mp4Properties = Mp4Properties {
 	sample_rate: 48000,
 	bit_depth: Some(16),
 	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
 	drm_protected: false,
 };
 
@@ -232,6 +331,9 @@ const OPUS_PROPERTIES: OpusProperties = OpusProperties {
 	channel_mask: ChannelMask::stereo(),
 	version: 1,
 	input_sample_rate: 48000,
+	output_gain: 0,
+	channel_mapping_family: 0,
+	is_chained: false,
 };
 
 const SPEEX_PROPERTIES: SpeexProperties = SpeexProperties {
@@ -244,6 +346,7 @@ const SPEEX_PROPERTIES: SpeexProperties = SpeexProperties {
 	overall_bitrate: 32,
 	audio_bitrate: 29,
 	nominal_bitrate: 29600,
+	is_chained: false,
 };
 
 const VORBIS_PROPERTIES: VorbisProperties = VorbisProperties {
@@ -252,10 +355,12 @@ const VORBIS_PROPERTIES: VorbisProperties = VorbisProperties {
 	audio_bitrate: 112,
 	sample_rate: 48000,
 	channels: 2,
+	channel_mask: Some(ChannelMask::stereo()),
 	version: 0,
 	bitrate_maximum: 0,
 	bitrate_nominal: 112_000,
 	bitrate_minimum: 0,
+	is_chained: false,
 };
 
 const WAV_PROPERTIES: WavProperties = WavProperties {
@@ -269,6 +374,26 @@ const WAV_PROPERTIES: WavProperties = WavProperties {
 	channel_mask: None,
 };
 
+const SHN_PROPERTIES: ShnProperties = ShnProperties {
+	version: 2,
+	duration: Duration::from_secs(1),
+	overall_bitrate: 10,
+	audio_bitrate: 8,
+	sample_rate: 44100,
+	bit_depth: 16,
+	channels: 2,
+};
+
+const TTA_PROPERTIES: TtaProperties = TtaProperties {
+	duration: Duration::from_secs(1),
+	overall_bitrate: 10,
+	audio_bitrate: 8,
+	sample_rate: 44100,
+	bit_depth: 16,
+	channels: 2,
+	total_samples: 44100,
+};
+
 const WAVPACK_PROPERTIES: WavPackProperties = WavPackProperties {
 	version: 1040,
 	duration: Duration::from_millis(1428),
@@ -279,6 +404,7 @@ const WAVPACK_PROPERTIES: WavPackProperties = WavPackProperties {
 	channel_mask: ChannelMask::stereo(),
 	bit_depth: 16,
 	lossless: true,
+	hybrid: false,
 };
 
 fn get_properties<T>(path: &str) -> T::Properties
@@ -309,6 +435,30 @@ fn aiff_properties() {
 	);
 }
 
+#[test_log::test]
+fn aifc_sowt_properties() {
+	let properties = get_properties::<AiffFile>("tests/files/assets/aifc_sowt.aiff");
+	assert_eq!(properties, AIFC_SOWT_PROPERTIES);
+	assert!(!properties.is_compressed());
+	assert_eq!(FileProperties::from(properties).bit_depth(), Some(16));
+}
+
+#[test_log::test]
+fn aifc_ulaw_properties() {
+	let properties = get_properties::<AiffFile>("tests/files/assets/aifc_ulaw.aiff");
+	assert_eq!(properties, AIFC_ULAW_PROPERTIES);
+	assert!(properties.is_compressed());
+	assert_eq!(FileProperties::from(properties).bit_depth(), None);
+}
+
+#[test_log::test]
+fn aifc_ima4_properties() {
+	// The COMM chunk holds the number of packets, each containing 64 sample frames
+	let properties = get_properties::<AiffFile>("tests/files/assets/aifc_ima4.aiff");
+	assert_eq!(properties, AIFC_IMA4_PROPERTIES);
+	assert!(properties.is_compressed());
+}
+
 #[test_log::test]
 fn ape_properties() {
 	assert_eq!(
@@ -317,6 +467,30 @@ fn ape_properties() {
 	);
 }
 
+#[test_log::test]
+fn asf_properties() {
+	assert_eq!(
+		get_properties::<AsfFile>("tests/files/assets/minimal/full_test.wma"),
+		ASF_PROPERTIES
+	)
+}
+
+#[test_log::test]
+fn dff_properties() {
+	assert_eq!(
+		get_properties::<DffFile>("tests/files/assets/minimal/full_test.dff"),
+		DFF_PROPERTIES
+	)
+}
+
+#[test_log::test]
+fn dsf_properties() {
+	assert_eq!(
+		get_properties::<DsfFile>("tests/files/assets/minimal/full_test.dsf"),
+		DSF_PROPERTIES
+	)
+}
+
 #[test_log::test]
 fn flac_properties() {
 	assert_eq!(
@@ -349,6 +523,15 @@ fn mp3_properties() {
 	)
 }
 
+#[test_log::test]
+fn mp3_vbri_properties() {
+	// Estimating from the bitrate of the first frame would give ~2 seconds
+	assert_eq!(
+		get_properties::<MpegFile>("tests/files/assets/vbri.mp3"),
+		MP3_VBRI_PROPERTIES
+	)
+}
+
 #[test_log::test]
 fn mp4_aac_properties() {
 	assert_eq!(
@@ -405,6 +588,14 @@ fn mpc_sv8_properties() {
 	)
 }
 
+#[test_log::test]
+fn ogg_flac_properties() {
+	assert_eq!(
+		get_properties::<OggFlacFile>("tests/files/assets/minimal/full_test.oga"),
+		OGG_FLAC_PROPERTIES
+	)
+}
+
 #[test_log::test]
 fn opus_properties() {
 	assert_eq!(
@@ -429,6 +620,22 @@ fn vorbis_properties() {
 	)
 }
 
+#[test_log::test]
+fn shn_properties() {
+	assert_eq!(
+		get_properties::<ShnFile>("tests/files/assets/minimal/full_test.shn"),
+		SHN_PROPERTIES
+	)
+}
+
+#[test_log::test]
+fn tta_properties() {
+	assert_eq!(
+		get_properties::<TtaFile>("tests/files/assets/minimal/full_test.tta"),
+		TTA_PROPERTIES
+	)
+}
+
 #[test_log::test]
 fn wav_properties() {
 	assert_eq!(
@@ -0,0 +1,313 @@
+use crate::config::ParsingMode;
+use crate::error::Result;
+use crate::macros::decode_err;
+use crate::properties::FileProperties;
+
+use std::io::Read;
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
+
+// Sizes of the Rice coded fields, taken from the reference implementation
+const TYPE_SIZE: u8 = 4;
+const CHANNELS_SIZE: u8 = 0;
+const FUNCTION_SIZE: u8 = 2;
+const ULONG_SIZE: u8 = 2;
+const VERBATIM_CHUNK_SIZE: u8 = 5;
+const VERBATIM_BYTE_SIZE: u8 = 8;
+const SKIP_BYTE_SIZE: u8 = 7;
+
+const FN_VERBATIM: u32 = 9;
+
+// Anything larger than this is not a sane WAV header
+const MAX_VERBATIM_LEN: u32 = 1 << 16;
+
+/// A Shorten file's audio properties
+///
+/// Shorten streams do not store their sample rate or length. These are only available when the
+/// stream starts with the original RIFF WAVE header, as is the case for nearly all files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ShnProperties {
+	pub(crate) version: u8,
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+}
+
+impl From<ShnProperties> for FileProperties {
+	fn from(input: ShnProperties) -> Self {
+		let known = |value: u32| (value > 0).then_some(value);
+
+		Self {
+			duration: input.duration,
+			overall_bitrate: known(input.overall_bitrate),
+			audio_bitrate: known(input.audio_bitrate),
+			sample_rate: known(input.sample_rate),
+			bit_depth: (input.bit_depth > 0).then_some(input.bit_depth),
+			channels: Some(input.channels),
+			channel_mask: None,
+		}
+	}
+}
+
+impl ShnProperties {
+	/// Duration of the audio
+	///
+	/// This will be zero if the stream has no embedded WAVE header.
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	///
+	/// This will be zero if the stream has no embedded WAVE header.
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	///
+	/// This will be zero if the stream has no embedded WAVE header.
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Shorten version
+	pub fn version(&self) -> u8 {
+		self.version
+	}
+}
+
+// The stream is made up of Rice coded values, read MSB first
+struct BitReader<'a, R> {
+	reader: &'a mut R,
+	current: u8,
+	bits_left: u8,
+}
+
+impl<'a, R: Read> BitReader<'a, R> {
+	fn new(reader: &'a mut R) -> Self {
+		Self {
+			reader,
+			current: 0,
+			bits_left: 0,
+		}
+	}
+
+	fn read_bit(&mut self) -> Result<bool> {
+		if self.bits_left == 0 {
+			self.current = self.reader.read_u8()?;
+			self.bits_left = 8;
+		}
+
+		self.bits_left -= 1;
+		Ok((self.current >> self.bits_left) & 1 == 1)
+	}
+
+	fn uvar(&mut self, size: u8) -> Result<u32> {
+		let mut high = 0u32;
+		while !self.read_bit()? {
+			high += 1;
+			if high > 32 {
+				decode_err!(@BAIL Shn, "Encountered an invalid Rice coded value");
+			}
+		}
+
+		let mut low = 0u32;
+		for _ in 0..size {
+			low = (low << 1) | u32::from(self.read_bit()?);
+		}
+
+		let Some(value) = high
+			.checked_shl(u32::from(size))
+			.filter(|v| v >> size == high)
+		else {
+			decode_err!(@BAIL Shn, "Encountered an invalid Rice coded value");
+		};
+
+		Ok(value | low)
+	}
+
+	fn ulong(&mut self) -> Result<u32> {
+		let size = self.uvar(ULONG_SIZE)?;
+		if size > 32 {
+			decode_err!(@BAIL Shn, "Encountered an invalid Rice coded value");
+		}
+
+		self.uvar(size as u8)
+	}
+}
+
+// The header consists of:
+//
+// Signature ("ajkg") (4)
+// Version (1)
+//
+// Followed by the Rice coded stream parameters, and usually a verbatim block holding the
+// header of the original file.
+pub(super) fn read_properties<R>(
+	reader: &mut R,
+	stream_len: u64,
+	file_length: u64,
+	parse_mode: ParsingMode,
+) -> Result<ShnProperties>
+where
+	R: Read,
+{
+	let mut signature = [0; 4];
+	reader.read_exact(&mut signature)?;
+	if &signature != b"ajkg" {
+		decode_err!(@BAIL Shn, "File has an invalid signature");
+	}
+
+	let version = reader.read_u8()?;
+	if version > 3 {
+		decode_err!(@BAIL Shn, "File has an unsupported version");
+	}
+
+	let mut properties = ShnProperties {
+		version,
+		..ShnProperties::default()
+	};
+
+	let mut bits = BitReader::new(reader);
+
+	// Version 0 streams use fixed size values, later versions prefix each value with its size
+	let uint = |bits: &mut BitReader<'_, R>, size| match version {
+		0 => bits.uvar(size),
+		_ => bits.ulong(),
+	};
+
+	let _file_type = uint(&mut bits, TYPE_SIZE)?;
+	let channels = uint(&mut bits, CHANNELS_SIZE)?;
+	if channels == 0 || channels > u32::from(u8::MAX) {
+		decode_err!(@BAIL Shn, "File has an invalid channel count");
+	}
+
+	properties.channels = channels as u8;
+
+	if version > 0 {
+		let _block_size = bits.ulong()?;
+		let _max_lpc_order = bits.ulong()?;
+		let _mean_blocks = bits.ulong()?;
+
+		let skip_bytes = bits.ulong()?;
+		for _ in 0..skip_bytes {
+			bits.uvar(SKIP_BYTE_SIZE)?;
+		}
+	}
+
+	if bits.uvar(FUNCTION_SIZE)? != FN_VERBATIM {
+		log::warn!("Shorten stream has no verbatim header, unable to determine the duration");
+		return Ok(properties);
+	}
+
+	let verbatim_len = bits.uvar(VERBATIM_CHUNK_SIZE)?;
+	if verbatim_len > MAX_VERBATIM_LEN {
+		decode_err!(@BAIL Shn, "Verbatim header is too large");
+	}
+
+	let mut verbatim = Vec::with_capacity(verbatim_len as usize);
+	for _ in 0..verbatim_len {
+		verbatim.push(bits.uvar(VERBATIM_BYTE_SIZE)? as u8);
+	}
+
+	let Some(wave) = parse_wave_header(&verbatim) else {
+		if parse_mode == ParsingMode::Strict {
+			decode_err!(@BAIL Shn, "Verbatim header is not a valid RIFF WAVE header");
+		}
+
+		log::warn!("Shorten stream has no usable WAVE header, unable to determine the duration");
+		return Ok(properties);
+	};
+
+	if wave.channels != u16::from(properties.channels) {
+		log::warn!(
+			"Shorten channel count ({}) differs from the WAVE header ({})",
+			properties.channels,
+			wave.channels
+		);
+	}
+
+	properties.sample_rate = wave.sample_rate;
+	properties.bit_depth = wave.bit_depth as u8;
+
+	if wave.sample_rate == 0 || wave.block_align == 0 {
+		return Ok(properties);
+	}
+
+	let total_samples = wave.data_size / u32::from(wave.block_align);
+	let length = (f64::from(total_samples) * 1000.0) / f64::from(wave.sample_rate);
+	properties.duration = Duration::from_millis((length + 0.5) as u64);
+
+	if length > 0.0 {
+		properties.audio_bitrate = ((stream_len as f64) * 8.0 / length + 0.5) as u32;
+		properties.overall_bitrate = ((file_length as f64) * 8.0 / length + 0.5) as u32;
+	}
+
+	Ok(properties)
+}
+
+struct WaveHeader {
+	channels: u16,
+	sample_rate: u32,
+	block_align: u16,
+	bit_depth: u16,
+	data_size: u32,
+}
+
+fn parse_wave_header(header: &[u8]) -> Option<WaveHeader> {
+	if header.len() < 12 || &header[..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+		return None;
+	}
+
+	let mut fmt = None;
+	let mut chunks = &header[12..];
+	while chunks.len() >= 8 {
+		let id = &chunks[..4];
+		let size = LittleEndian::read_u32(&chunks[4..8]);
+		chunks = &chunks[8..];
+
+		match id {
+			b"fmt " if size >= 16 && chunks.len() >= 16 => fmt = Some(&chunks[..16]),
+			// The audio data directly follows the data chunk's header
+			b"data" => {
+				let fmt = fmt?;
+				return Some(WaveHeader {
+					channels: LittleEndian::read_u16(&fmt[2..4]),
+					sample_rate: LittleEndian::read_u32(&fmt[4..8]),
+					block_align: LittleEndian::read_u16(&fmt[12..14]),
+					bit_depth: LittleEndian::read_u16(&fmt[14..16]),
+					data_size: size,
+				});
+			},
+			_ => {},
+		}
+
+		// Chunks are padded to an even size
+		let skip = size as usize + (size as usize & 1);
+		chunks = chunks.get(skip..)?;
+	}
+
+	None
+}
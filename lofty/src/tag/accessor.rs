@@ -1,3 +1,5 @@
+use crate::tag::items::Popularimeter;
+
 use std::borrow::Cow;
 
 // This defines the `Accessor` trait, used to define unified getters/setters for commonly
@@ -116,4 +118,5 @@ accessor_trait! {
 	[track ]<u32>,                  [track total]<u32>,
 	[disk  ]<u32>,                  [disk total ]<u32>,
 	[year  ]<u32>,                  [comment    ]<Cow<'_, str>, String>,
+	[popularimeter]<Popularimeter>,
 }
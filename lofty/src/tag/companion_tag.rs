@@ -1,14 +1,14 @@
-use crate::id3::v2::Id3v2Tag;
+use crate::id3::v2::tag::SplitTagRemainder as Id3v2Remainder;
 use crate::mp4::Ilst;
 
 #[derive(Debug, Clone)]
 pub(crate) enum CompanionTag {
-	Id3v2(Id3v2Tag),
+	Id3v2(Id3v2Remainder),
 	Ilst(Ilst),
 }
 
 impl CompanionTag {
-	pub(crate) fn id3v2(self) -> Option<Id3v2Tag> {
+	pub(crate) fn id3v2(self) -> Option<Id3v2Remainder> {
 		match self {
 			CompanionTag::Id3v2(tag) => Some(tag),
 			_ => None,
@@ -0,0 +1,221 @@
+use super::{ItemValue, Tag, TagItem, TagType};
+use crate::ape::ApeTag;
+use crate::asf::AsfTag;
+use crate::config::WriteOptions;
+use crate::id3::v1::Id3v1Tag;
+use crate::id3::v2::Id3v2Tag;
+use crate::iff::aiff::AiffTextChunks;
+use crate::iff::wav::RiffInfoList;
+use crate::mp4::Ilst;
+use crate::ogg::VorbisComments;
+use crate::picture::Picture;
+
+/// Something that will not survive the conversion of a [`Tag`] to another [`TagType`]
+///
+/// See [`Tag::conversion_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionLoss {
+	/// The item can't be represented in the target format
+	DroppedItem(TagItem),
+	/// The item's text will be cut short
+	///
+	/// For example, ID3v1 only has 30 bytes for the title.
+	TruncatedItem {
+		/// The original item
+		original: TagItem,
+		/// The text that remains after the conversion
+		converted: String,
+	},
+	/// The item's value will be changed, other than by being cut short
+	///
+	/// For example, ID3v1 genres are stored as an index into a list of known genres.
+	ChangedItem {
+		/// The original item
+		original: TagItem,
+		/// The value after the conversion
+		converted: ItemValue,
+	},
+	/// The picture can't be represented in the target format
+	DroppedPicture(Picture),
+	/// The picture's data is kept, but some of its information (e.g. the description) is lost
+	ChangedPicture {
+		/// The original picture
+		original: Picture,
+		/// The picture after the conversion
+		converted: Picture,
+	},
+}
+
+/// A report of what will be lost when converting a [`Tag`] to another [`TagType`]
+///
+/// See [`Tag::conversion_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionReport {
+	target: TagType,
+	losses: Vec<ConversionLoss>,
+}
+
+impl ConversionReport {
+	/// The [`TagType`] the report is for
+	pub fn target(&self) -> TagType {
+		self.target
+	}
+
+	/// Everything that will be lost, in the order of the items and pictures in the original tag
+	pub fn losses(&self) -> &[ConversionLoss] {
+		&self.losses
+	}
+
+	/// Whether the conversion will keep every item and picture as-is
+	pub fn is_lossless(&self) -> bool {
+		self.losses.is_empty()
+	}
+
+	/// The items that can't be represented in the target format
+	pub fn dropped_items(&self) -> impl Iterator<Item = &TagItem> + Clone {
+		self.losses.iter().filter_map(|loss| match loss {
+			ConversionLoss::DroppedItem(item) => Some(item),
+			_ => None,
+		})
+	}
+
+	/// The items that will be cut short, along with the text that remains
+	pub fn truncated_items(&self) -> impl Iterator<Item = (&TagItem, &str)> + Clone {
+		self.losses.iter().filter_map(|loss| match loss {
+			ConversionLoss::TruncatedItem {
+				original,
+				converted,
+			} => Some((original, converted.as_str())),
+			_ => None,
+		})
+	}
+
+	/// The pictures that can't be represented in the target format
+	pub fn dropped_pictures(&self) -> impl Iterator<Item = &Picture> + Clone {
+		self.losses.iter().filter_map(|loss| match loss {
+			ConversionLoss::DroppedPicture(picture) => Some(picture),
+			_ => None,
+		})
+	}
+}
+
+/// Create a [`ConversionReport`] by comparing `tag` to the result of converting it to `target` and back
+pub(super) fn create_report(tag: &Tag, target: TagType) -> ConversionReport {
+	let converted = round_trip(tag.clone(), target);
+
+	let mut losses = Vec::new();
+	compare_items(tag, &converted, &mut losses);
+	compare_pictures(tag, &converted, &mut losses);
+
+	ConversionReport { target, losses }
+}
+
+/// Convert `tag` to the concrete tag of `target`, and back
+///
+/// This uses the same conversions as when a `Tag` is converted or saved. For ID3v1, the tag is
+/// also encoded and read back, since its fixed size fields are only cut short when writing.
+fn round_trip(tag: Tag, target: TagType) -> Tag {
+	match target {
+		TagType::Ape => ApeTag::from(tag).into(),
+		TagType::Id3v1 => {
+			let id3v1 = Id3v1Tag::from(tag);
+
+			match crate::id3::v1::write::encode(&(&id3v1).into(), WriteOptions::default()) {
+				Ok(encoded) => {
+					let encoded: [u8; 128] = encoded
+						.try_into()
+						.expect("ID3v1 tags should always be 128 bytes");
+					crate::id3::v1::read::parse_id3v1(encoded).into()
+				},
+				// Writing to a `Vec` can't fail, and the default options never reject a field
+				Err(_) => id3v1.into(),
+			}
+		},
+		TagType::Id3v2 => Id3v2Tag::from(tag).into(),
+		TagType::Mp4Ilst => Ilst::from(tag).into(),
+		TagType::VorbisComments => VorbisComments::from(tag).into(),
+		TagType::RiffInfo => RiffInfoList::from(tag).into(),
+		TagType::AiffText => AiffTextChunks::from(tag).into(),
+		TagType::Asf => AsfTag::from(tag).into(),
+	}
+}
+
+fn compare_items(original: &Tag, converted: &Tag, losses: &mut Vec<ConversionLoss>) {
+	let mut remaining = converted.items().map(Some).collect::<Vec<_>>();
+
+	// First, match the items that are kept as-is
+	let mut unmatched = Vec::new();
+	for item in original.items() {
+		let exact_match = remaining.iter_mut().find(|converted| {
+			converted.is_some_and(|converted| {
+				converted.key() == item.key() && converted.value() == item.value()
+			})
+		});
+
+		match exact_match {
+			Some(slot) => *slot = None,
+			None => unmatched.push(item),
+		}
+	}
+
+	// Anything else either kept its key, but not its value, or was dropped entirely
+	for item in unmatched {
+		let same_key = remaining
+			.iter_mut()
+			.find(|converted| converted.is_some_and(|converted| converted.key() == item.key()))
+			.and_then(Option::take);
+
+		let Some(converted) = same_key else {
+			losses.push(ConversionLoss::DroppedItem(item.clone()));
+			continue;
+		};
+
+		match (item.value(), converted.value()) {
+			(ItemValue::Text(original), ItemValue::Text(converted_text))
+				if converted_text.len() < original.len()
+					&& original.starts_with(converted_text.as_str()) =>
+			{
+				losses.push(ConversionLoss::TruncatedItem {
+					original: item.clone(),
+					converted: converted_text.clone(),
+				});
+			},
+			_ => losses.push(ConversionLoss::ChangedItem {
+				original: item.clone(),
+				converted: converted.value().clone(),
+			}),
+		}
+	}
+}
+
+fn compare_pictures(original: &Tag, converted: &Tag, losses: &mut Vec<ConversionLoss>) {
+	let mut remaining = converted.pictures().iter().map(Some).collect::<Vec<_>>();
+
+	let mut unmatched = Vec::new();
+	for picture in original.pictures() {
+		let exact_match = remaining
+			.iter_mut()
+			.find(|converted| *converted == &Some(picture));
+
+		match exact_match {
+			Some(slot) => *slot = None,
+			None => unmatched.push(picture),
+		}
+	}
+
+	for picture in unmatched {
+		let same_data = remaining
+			.iter_mut()
+			.find(|converted| converted.is_some_and(|converted| converted.data() == picture.data()))
+			.and_then(Option::take);
+
+		match same_data {
+			Some(converted) => losses.push(ConversionLoss::ChangedPicture {
+				original: picture.clone(),
+				converted: converted.clone(),
+			}),
+			None => losses.push(ConversionLoss::DroppedPicture(picture.clone())),
+		}
+	}
+}
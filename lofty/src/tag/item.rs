@@ -1,3 +1,4 @@
+use crate::config::{global_options, KeyStyle};
 use crate::tag::items::{Lang, UNKNOWN_LANGUAGE};
 use crate::tag::TagType;
 
@@ -102,8 +103,8 @@ gen_map!(
 	"Producer"                     => Producer,
 	"Label"                        => Label,
 	"MixArtist"                    => Remixer,
-	"Disc"                         => DiscNumber,
-	"Disc"                         => DiscTotal,
+	"Disc" | "Disk"                => DiscNumber,
+	"Disc" | "Disk"                => DiscTotal,
 	"Track"                        => TrackNumber,
 	"Track"                        => TrackTotal,
 	"Year"                         => Year,
@@ -122,11 +123,21 @@ gen_map!(
 	"Genre"                        => Genre,
 	"Color"                        => Color,
 	"Mood"                         => Mood,
+	"Rating"                       => Popularimeter,
 	"Copyright"                    => CopyrightMessage,
 	"Comment"                      => Comment,
 	"language"                     => Language,
 	"Script"                       => Script,
 	"Lyrics"                       => Lyrics,
+	// The APEv2 specification only defines some of the URL keys, the rest are those used by Mp3tag
+	"File URL"                     => AudioFileUrl,
+	"Related"                      => AudioSourceUrl,
+	"WWWCOMMERCIALINFO"            => CommercialInformationUrl,
+	"Copyright URL"                => CopyrightUrl,
+	"Artist URL"                   => TrackArtistUrl,
+	"WWWRADIOPAGE"                 => RadioStationUrl,
+	"Buy URL"                      => PaymentUrl,
+	"Publisher URL"                => PublisherUrl,
 	"MUSICBRAINZ_TRACKID"          => MusicBrainzRecordingId,
 	"MUSICBRAINZ_RELEASETRACKID"   => MusicBrainzTrackId,
 	"MUSICBRAINZ_ALBUMID"          => MusicBrainzReleaseId,
@@ -136,6 +147,67 @@ gen_map!(
 	"MUSICBRAINZ_WORKID"           => MusicBrainzWorkId
 );
 
+gen_map!(
+	ASF_MAP;
+
+	"WM/AlbumTitle"                                     => AlbumTitle,
+	"WM/SetSubTitle"                                    => SetSubtitle,
+	"WM/ContentGroupDescription"                        => ContentGroup,
+	"Title"                                             => TrackTitle,
+	"WM/SubTitle"                                       => TrackSubtitle,
+	"WM/Work"                                           => Work,
+	"WM/OriginalAlbumTitle"                             => OriginalAlbumTitle,
+	"WM/OriginalArtist"                                 => OriginalArtist,
+	"WM/OriginalLyricist"                               => OriginalLyricist,
+	"WM/AlbumSortOrder"                                 => AlbumTitleSortOrder,
+	"WM/AlbumArtistSortOrder"                           => AlbumArtistSortOrder,
+	"WM/TitleSortOrder"                                 => TrackTitleSortOrder,
+	"WM/ArtistSortOrder"                                => TrackArtistSortOrder,
+	"WM/ComposerSortOrder"                              => ComposerSortOrder,
+	"WM/AlbumArtist"                                    => AlbumArtist,
+	"Author"                                            => TrackArtist,
+	"WM/Writer"                                         => Lyricist,
+	"WM/Composer"                                       => Composer,
+	"WM/Conductor"                                      => Conductor,
+	"WM/Director"                                       => Director,
+	"WM/Producer"                                       => Producer,
+	"WM/Publisher"                                      => Label,
+	"WM/ModifiedBy"                                     => Remixer,
+	"WM/PartOfSet"                                      => DiscNumber,
+	"WM/PartOfSet"                                      => DiscTotal,
+	"WM/TrackNumber"                                    => TrackNumber,
+	"WM/Year"                                           => Year,
+	"WM/OriginalReleaseTime" | "WM/OriginalReleaseYear" => OriginalReleaseDate,
+	"WM/ISRC"                                           => Isrc,
+	"WM/Barcode"                                        => Barcode,
+	"WM/CatalogNo"                                      => CatalogNumber,
+	"WM/IsCompilation"                                  => FlagCompilation,
+	"WM/Media"                                          => OriginalMediaType,
+	"WM/EncodedBy"                                      => EncodedBy,
+	"WM/ToolName"                                       => EncoderSoftware,
+	"WM/EncodingSettings"                               => EncoderSettings,
+	"REPLAYGAIN_ALBUM_GAIN"                             => ReplayGainAlbumGain,
+	"REPLAYGAIN_ALBUM_PEAK"                             => ReplayGainAlbumPeak,
+	"REPLAYGAIN_TRACK_GAIN"                             => ReplayGainTrackGain,
+	"REPLAYGAIN_TRACK_PEAK"                             => ReplayGainTrackPeak,
+	"WM/Genre"                                          => Genre,
+	"WM/Mood"                                           => Mood,
+	"WM/BeatsPerMinute"                                 => Bpm,
+	"WM/InitialKey"                                     => InitialKey,
+	"Copyright"                                         => CopyrightMessage,
+	"Description"                                       => Comment,
+	"WM/Language"                                       => Language,
+	"WM/Script"                                         => Script,
+	"WM/Lyrics"                                         => Lyrics,
+	"MusicBrainz/Track Id"                              => MusicBrainzRecordingId,
+	"MusicBrainz/Release Track Id"                      => MusicBrainzTrackId,
+	"MusicBrainz/Album Id"                              => MusicBrainzReleaseId,
+	"MusicBrainz/Release Group Id"                      => MusicBrainzReleaseGroupId,
+	"MusicBrainz/Artist Id"                             => MusicBrainzArtistId,
+	"MusicBrainz/Album Artist Id"                       => MusicBrainzReleaseArtistId,
+	"MusicBrainz/Work Id"                               => MusicBrainzWorkId
+);
+
 gen_map!(
 	ID3V2_MAP;
 
@@ -301,6 +373,14 @@ gen_map!(
 	"----:com.apple.iTunes:LANGUAGE"                     => Language,
 	"----:com.apple.iTunes:SCRIPT"                       => Script,
 	"\u{a9}lyr"                                          => Lyrics,
+	"----:com.apple.iTunes:WWWAUDIOFILE"                 => AudioFileUrl,
+	"----:com.apple.iTunes:WWWAUDIOSOURCE"               => AudioSourceUrl,
+	"----:com.apple.iTunes:WWWCOMMERCIALINFO"            => CommercialInformationUrl,
+	"----:com.apple.iTunes:WWWCOPYRIGHT"                 => CopyrightUrl,
+	"----:com.apple.iTunes:WWWARTIST"                    => TrackArtistUrl,
+	"----:com.apple.iTunes:WWWRADIOPAGE"                 => RadioStationUrl,
+	"----:com.apple.iTunes:WWWPAYMENT"                   => PaymentUrl,
+	"----:com.apple.iTunes:WWWPUBLISHER"                 => PublisherUrl,
 	"xid "                                               => AppleXid,
 	"----:com.apple.iTunes:MusicBrainz Track Id"         => MusicBrainzRecordingId,
 	"----:com.apple.iTunes:MusicBrainz Release Track Id" => MusicBrainzTrackId,
@@ -331,7 +411,8 @@ gen_map!(
 	"IGNR"          => Genre,
 	"ICOP"          => CopyrightMessage,
 	"ICMT"          => Comment,
-	"ILNG"          => Language
+	"ILNG"          => Language,
+	"IENG"          => Engineer
 );
 
 gen_map!(
@@ -402,7 +483,18 @@ gen_map!(
 	"COMMENT"                                 => Comment,
 	"LANGUAGE"                                => Language,
 	"SCRIPT"                                  => Script,
-	"LYRICS"                                  => Lyrics,
+	"LYRICS" | "UNSYNCEDLYRICS"               => Lyrics,
+	// There are no standard fields for URLs, "WEBSITE" is used by MusicBrainz Picard, the rest by Mp3tag.
+	// <https://picard-docs.musicbrainz.org/en/appendices/tag_mapping.html#website>
+	"WWWAUDIOFILE"                            => AudioFileUrl,
+	"WWWAUDIOSOURCE"                          => AudioSourceUrl,
+	"WWWCOMMERCIALINFO"                       => CommercialInformationUrl,
+	"WWWCOPYRIGHT"                            => CopyrightUrl,
+	"WEBSITE" | "WWWARTIST"                   => TrackArtistUrl,
+	"WWWRADIOPAGE"                            => RadioStationUrl,
+	"WWWPAYMENT"                              => PaymentUrl,
+	// "CONTACT" is a standard field, though it isn't necessarily a URL
+	"WWWPUBLISHER" | "CONTACT"                => PublisherUrl,
 	"MUSICBRAINZ_TRACKID"                     => MusicBrainzRecordingId,
 	"MUSICBRAINZ_RELEASETRACKID"              => MusicBrainzTrackId,
 	"MUSICBRAINZ_ALBUMID"                     => MusicBrainzReleaseId,
@@ -429,6 +521,7 @@ macro_rules! gen_item_keys {
 		]
 	) => {
 		#[derive(PartialEq, Clone, Debug, Eq, Hash)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 		#[allow(missing_docs)]
 		#[non_exhaustive]
 		/// A generic representation of a tag's key
@@ -480,6 +573,16 @@ macro_rules! gen_item_keys {
 
 				None
 			}
+
+			// The name of the variant, used for `KeyStyle::TitleCase` and pretty printing
+			pub(crate) fn variant_name(&self) -> Option<&'static str> {
+				match self {
+					$(
+						ItemKey::$variant_ident => Some(stringify!($variant_ident)),
+					)+
+					ItemKey::Unknown(_) => None,
+				}
+			}
 		}
 	}
 }
@@ -490,6 +593,8 @@ gen_item_keys!(
 
 		[TagType::Ape, APE_MAP],
 
+		[TagType::Asf, ASF_MAP],
+
 		[TagType::Id3v2, ID3V2_MAP],
 
 		[TagType::Mp4Ilst, ILST_MAP],
@@ -718,8 +823,65 @@ gen_item_keys!(
 	]
 );
 
+impl ItemKey {
+	// Same as `ItemKey::map_key`, with the key spelled according to `GlobalOptions::key_style`
+	//
+	// `original` is the spelling of the key when it was read, if known.
+	pub(crate) fn map_key_styled<'a>(
+		&'a self,
+		tag_type: TagType,
+		allow_unknown: bool,
+		original: Option<&'a str>,
+	) -> Option<Cow<'a, str>> {
+		let key = self.map_key(tag_type, allow_unknown)?;
+		Some(self.style_key(key, original))
+	}
+
+	// Respell `key`, a format-specific key for this variant, according to `GlobalOptions::key_style`
+	pub(crate) fn style_key<'a>(&self, key: &'a str, original: Option<&'a str>) -> Cow<'a, str> {
+		match key_style() {
+			KeyStyle::Format => Cow::Borrowed(key),
+			KeyStyle::Uppercase if !key.bytes().any(|b| b.is_ascii_lowercase()) => {
+				Cow::Borrowed(key)
+			},
+			KeyStyle::Uppercase => Cow::Owned(key.to_ascii_uppercase()),
+			KeyStyle::TitleCase => Cow::Owned(self.title_case(key)),
+			KeyStyle::Preserve => Cow::Borrowed(original.unwrap_or(key)),
+		}
+	}
+
+	fn title_case(&self, key: &str) -> String {
+		// A key like `ALBUMARTIST` has no separators, so borrow the word boundaries of `AlbumArtist`
+		if let Some(name) = self.variant_name() {
+			if name.eq_ignore_ascii_case(key) {
+				return name.to_string();
+			}
+		}
+
+		let mut title = String::with_capacity(key.len());
+		let mut word_start = true;
+		for c in key.chars() {
+			if word_start {
+				title.push(c.to_ascii_uppercase());
+			} else {
+				title.push(c.to_ascii_lowercase());
+			}
+
+			word_start = matches!(c, ' ' | '_' | '-');
+		}
+
+		title
+	}
+}
+
+// Not a method of `ItemKey`, since it needs `unsafe` and `ItemKey` derives `Deserialize`
+fn key_style() -> KeyStyle {
+	unsafe { global_options().key_style }
+}
+
 /// Represents a tag item's value
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemValue {
 	/// Any UTF-8 encoded text
 	Text(String),
@@ -729,7 +891,7 @@ pub enum ItemValue {
 	/// as a normal string in other tags
 	Locator(String),
 	/// Binary information
-	Binary(Vec<u8>),
+	Binary(#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_bytes"))] Vec<u8>),
 }
 
 impl ItemValue {
@@ -786,7 +948,7 @@ impl ItemValue {
 pub(crate) enum ItemValueRef<'a> {
 	Text(Cow<'a, str>),
 	Locator(&'a str),
-	Binary(&'a [u8]),
+	Binary(Cow<'a, [u8]>),
 }
 
 impl<'a> Into<ItemValueRef<'a>> for &'a ItemValue {
@@ -794,13 +956,14 @@ impl<'a> Into<ItemValueRef<'a>> for &'a ItemValue {
 		match self {
 			ItemValue::Text(text) => ItemValueRef::Text(Cow::Borrowed(text)),
 			ItemValue::Locator(locator) => ItemValueRef::Locator(locator),
-			ItemValue::Binary(binary) => ItemValueRef::Binary(binary),
+			ItemValue::Binary(binary) => ItemValueRef::Binary(Cow::Borrowed(binary)),
 		}
 	}
 }
 
 /// Represents a tag item (key/value)
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TagItem {
 	pub(crate) lang: Lang,
 	pub(crate) description: String,
@@ -902,6 +1065,11 @@ impl TagItem {
 			return VALID_ITEMKEYS.contains(&self.item_key);
 		}
 
+		// Stored in a `UFID` frame, so there is no key to map it to
+		if tag_type == TagType::Id3v2 && self.item_key == ItemKey::MusicBrainzRecordingId {
+			return true;
+		}
+
 		self.item_key.map_key(tag_type, false).is_some()
 	}
 }
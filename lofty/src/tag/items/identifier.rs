@@ -0,0 +1,122 @@
+/// Checks if `isrc` is a well-formed International Standard Recording Code
+///
+/// An ISRC consists of 12 characters:
+///
+/// * A 2 letter country code
+/// * A 3 character alphanumeric registrant code
+/// * The last 2 digits of the year of reference
+/// * A 5 digit designation code
+///
+/// The hyphenated form used for display (`CC-XXX-YY-NNNNN`) is also accepted. Letters must be uppercase.
+///
+/// NOTE: This only checks the format of the code, not whether it was ever assigned.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::tag::items::is_valid_isrc;
+///
+/// assert!(is_valid_isrc("USRC17607839"));
+/// assert!(is_valid_isrc("US-RC1-76-07839"));
+///
+/// // Too short
+/// assert!(!is_valid_isrc("USRC1760783"));
+/// // The country code can't contain digits
+/// assert!(!is_valid_isrc("U5RC17607839"));
+/// ```
+pub fn is_valid_isrc(isrc: &str) -> bool {
+	let bytes = isrc.as_bytes();
+
+	let mut code = [0; 12];
+	match bytes.len() {
+		12 => code.copy_from_slice(bytes),
+		15 => {
+			if bytes[2] != b'-' || bytes[6] != b'-' || bytes[9] != b'-' {
+				return false;
+			}
+
+			code[..2].copy_from_slice(&bytes[..2]);
+			code[2..5].copy_from_slice(&bytes[3..6]);
+			code[5..7].copy_from_slice(&bytes[7..9]);
+			code[7..].copy_from_slice(&bytes[10..]);
+		},
+		_ => return false,
+	}
+
+	code[..2].iter().all(u8::is_ascii_uppercase)
+		&& code[2..5]
+			.iter()
+			.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+		&& code[5..].iter().all(u8::is_ascii_digit)
+}
+
+/// Checks if `barcode` is a valid EAN-13, UPC-A, or EAN-8 barcode
+///
+/// The barcode must only consist of digits, and its final check digit must match.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::tag::items::is_valid_barcode;
+///
+/// // EAN-13
+/// assert!(is_valid_barcode("0602445790272"));
+/// // UPC-A
+/// assert!(is_valid_barcode("036000291452"));
+///
+/// // The check digit doesn't match
+/// assert!(!is_valid_barcode("036000291453"));
+/// ```
+pub fn is_valid_barcode(barcode: &str) -> bool {
+	let Some((&check_digit, digits)) = barcode.as_bytes().split_last() else {
+		return false;
+	};
+
+	if !matches!(barcode.len(), 8 | 12 | 13) || !barcode.bytes().all(|b| b.is_ascii_digit()) {
+		return false;
+	}
+
+	// Starting from the digit before the check digit, the digits are weighted 3 and 1 alternately
+	let sum = digits
+		.iter()
+		.rev()
+		.enumerate()
+		.map(|(i, digit)| {
+			let weight = if i % 2 == 0 { 3 } else { 1 };
+			u32::from(digit - b'0') * weight
+		})
+		.sum::<u32>();
+
+	u32::from(check_digit - b'0') == (10 - sum % 10) % 10
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{is_valid_barcode, is_valid_isrc};
+
+	#[test_log::test]
+	fn isrc() {
+		assert!(is_valid_isrc("GBAYE9700001"));
+		assert!(is_valid_isrc("GB-AYE-97-00001"));
+		assert!(is_valid_isrc("QZ1A22100001"));
+
+		assert!(!is_valid_isrc(""));
+		assert!(!is_valid_isrc("gbaye9700001"));
+		assert!(!is_valid_isrc("GBAYE97000O1"));
+		assert!(!is_valid_isrc("GBA-YE-97-00001"));
+		assert!(!is_valid_isrc("GB-AYE-9700001"));
+		assert!(!is_valid_isrc("GBAYE97000011"));
+	}
+
+	#[test_log::test]
+	fn barcode() {
+		// EAN-8
+		assert!(is_valid_barcode("96385074"));
+		assert!(!is_valid_barcode("96385075"));
+
+		assert!(!is_valid_barcode(""));
+		assert!(!is_valid_barcode("06024457902"));
+		assert!(!is_valid_barcode("060244579027a"));
+		assert!(!is_valid_barcode("+060244579027"));
+	}
+}
@@ -1,7 +1,11 @@
 //! Various generic representations of tag items
 
+mod identifier;
 mod lang;
+mod popularimeter;
 mod timestamp;
 
+pub use identifier::{is_valid_barcode, is_valid_isrc};
 pub use lang::*;
+pub use popularimeter::Popularimeter;
 pub use timestamp::Timestamp;
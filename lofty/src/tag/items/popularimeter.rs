@@ -0,0 +1,332 @@
+use crate::id3::v2::{FrameFlags, PopularimeterFrame};
+use crate::tag::{ItemKey, ItemValue, TagItem, TagType};
+
+/// The highest possible normalized rating
+const MAX_RATING: u8 = 100;
+
+/// A format-agnostic rating and play counter
+///
+/// Ratings are stored very differently between formats:
+///
+/// * ID3v2 - A `POPM` frame, with a rating of 1-255 (0 is unknown), an email, and a play counter
+/// * Vorbis Comments - A `RATING` field, with no agreed upon scale
+/// * APE - A `Rating` item, usually 0-100
+/// * MP4 - A `rate` atom, usually 0-100
+/// * RIFF INFO - An `IRTD` field, usually 0-100
+///
+/// A [`Popularimeter`] normalizes the rating to a scale of 0-100, where 0 is unknown/unrated.
+///
+/// ## Conversions
+///
+/// ### ID3v2
+///
+/// `POPM` ratings are scaled to 0-100, rounding to the nearest value. A non-zero rating
+/// will never become 0, so that it remains distinguishable from an unknown rating.
+///
+/// When converting back, the rating is scaled to 1-255, rounding to the nearest value. The only
+/// exception is a rating of 1, which will remain 1, so that the lowest possible rating of players such as
+/// Windows Media Player survives a round trip.
+///
+/// The email and play counter are only retained in ID3v2, and will be discarded for all other formats.
+///
+/// ### All other formats
+///
+/// The rating is stored as a plain integer in the range 0-100.
+///
+/// When reading, integers in the range 0-100 are used as-is, and decimal numbers in the range
+/// 0.0-1.0 (as used by some Vorbis Comments writers) are scaled to 0-100. Anything else is
+/// considered invalid.
+///
+/// # Examples
+///
+/// ```rust
+/// use lofty::id3::v2::PopularimeterFrame;
+/// use lofty::tag::items::Popularimeter;
+///
+/// // A 4 star rating in Windows Media Player
+/// let frame = PopularimeterFrame::new(String::from("Windows Media Player 9 Series"), 196, 3);
+///
+/// let popularimeter = Popularimeter::from(frame);
+/// assert_eq!(popularimeter.rating(), 77);
+/// assert_eq!(popularimeter.counter, 3);
+///
+/// // And back again
+/// let frame = PopularimeterFrame::from(popularimeter);
+/// assert_eq!(frame.rating, 196);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Popularimeter {
+	/// An identifier for the user performing the rating (ID3v2 only)
+	pub email: String,
+	rating: u8,
+	/// A play counter for the user (ID3v2 only)
+	pub counter: u64,
+}
+
+impl Popularimeter {
+	/// Create a new [`Popularimeter`]
+	///
+	/// NOTE: `rating` will be clamped to 0-100
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Popularimeter;
+	///
+	/// let popularimeter = Popularimeter::new(String::new(), 120, 0);
+	/// assert_eq!(popularimeter.rating(), 100);
+	/// ```
+	pub fn new(email: String, rating: u8, counter: u64) -> Self {
+		Self {
+			email,
+			rating: rating.min(MAX_RATING),
+			counter,
+		}
+	}
+
+	/// The rating, in the range 0-100
+	///
+	/// A rating of 0 is unknown.
+	pub fn rating(&self) -> u8 {
+		self.rating
+	}
+
+	/// Set the rating
+	///
+	/// NOTE: `rating` will be clamped to 0-100
+	pub fn set_rating(&mut self, rating: u8) {
+		self.rating = rating.min(MAX_RATING);
+	}
+
+	/// Convert a `POPM` rating (0-255) to the normalized scale
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Popularimeter;
+	///
+	/// assert_eq!(Popularimeter::rating_from_popm(0), 0);
+	/// assert_eq!(Popularimeter::rating_from_popm(1), 1);
+	/// assert_eq!(Popularimeter::rating_from_popm(128), 50);
+	/// assert_eq!(Popularimeter::rating_from_popm(255), 100);
+	/// ```
+	pub fn rating_from_popm(rating: u8) -> u8 {
+		if rating == 0 {
+			return 0;
+		}
+
+		let scaled = (u16::from(rating) * u16::from(MAX_RATING) + 127) / 255;
+		(scaled as u8).max(1)
+	}
+
+	/// Convert a normalized rating (0-100) to the `POPM` scale
+	///
+	/// NOTE: `rating` will be clamped to 0-100
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Popularimeter;
+	///
+	/// assert_eq!(Popularimeter::rating_to_popm(0), 0);
+	/// assert_eq!(Popularimeter::rating_to_popm(1), 1);
+	/// assert_eq!(Popularimeter::rating_to_popm(50), 128);
+	/// assert_eq!(Popularimeter::rating_to_popm(100), 255);
+	/// ```
+	pub fn rating_to_popm(rating: u8) -> u8 {
+		match rating.min(MAX_RATING) {
+			r @ (0 | 1) => r,
+			r => ((u16::from(r) * 255 + 50) / u16::from(MAX_RATING)) as u8,
+		}
+	}
+
+	/// Create a [`Popularimeter`] from an [`ItemKey::Popularimeter`] item
+	///
+	/// This accepts both binary `POPM` content, and text ratings. See [the type docs](Self) for
+	/// how ratings are interpreted.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Popularimeter;
+	/// use lofty::tag::{ItemKey, ItemValue, TagItem};
+	///
+	/// let item = TagItem::new(ItemKey::Popularimeter, ItemValue::Text(String::from("0.8")));
+	///
+	/// let popularimeter = Popularimeter::from_tag_item(&item).unwrap();
+	/// assert_eq!(popularimeter.rating(), 80);
+	/// ```
+	pub fn from_tag_item(item: &TagItem) -> Option<Self> {
+		if item.key() != &ItemKey::Popularimeter {
+			return None;
+		}
+
+		match item.value() {
+			ItemValue::Binary(content) => {
+				match PopularimeterFrame::parse(&mut &content[..], FrameFlags::default()) {
+					Ok(frame) => Some(frame.into()),
+					Err(e) => {
+						log::warn!("Failed to parse POPM content: {e}");
+						None
+					},
+				}
+			},
+			ItemValue::Text(text) => Self::from_text(text),
+			ItemValue::Locator(_) => None,
+		}
+	}
+
+	// Parse a text rating, as used by every format other than ID3v2
+	pub(crate) fn from_text(text: &str) -> Option<Self> {
+		let rating = parse_text_rating(text.trim());
+		if rating.is_none() {
+			log::warn!("Invalid rating: {text:?}, discarding");
+		}
+
+		rating.map(|rating| Self {
+			rating,
+			..Self::default()
+		})
+	}
+
+	/// Create an [`ItemKey::Popularimeter`] item for `tag_type`
+	///
+	/// For [`TagType::Id3v2`], this will be the binary content of a `POPM` frame. For all other formats,
+	/// this will be the text representation of the rating.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Popularimeter;
+	/// use lofty::tag::{ItemValue, TagType};
+	///
+	/// let popularimeter = Popularimeter::new(String::from("foo@bar.com"), 60, 5);
+	///
+	/// let item = popularimeter.to_tag_item(TagType::VorbisComments);
+	/// assert_eq!(item.value(), &ItemValue::Text(String::from("60")));
+	/// ```
+	pub fn to_tag_item(self, tag_type: TagType) -> TagItem {
+		let rating = self.rating;
+		let value = match tag_type {
+			TagType::Id3v2 => match PopularimeterFrame::from(self).as_bytes() {
+				Ok(content) => ItemValue::Binary(content),
+				Err(_) => ItemValue::Text(rating.to_string()),
+			},
+			_ => ItemValue::Text(rating.to_string()),
+		};
+
+		TagItem::new(ItemKey::Popularimeter, value)
+	}
+}
+
+fn parse_text_rating(text: &str) -> Option<u8> {
+	if let Ok(rating) = text.parse::<u8>() {
+		return (rating <= MAX_RATING).then_some(rating);
+	}
+
+	// Some writers use a scale of 0.0-1.0
+	match text.parse::<f32>() {
+		Ok(rating) if (0.0..=1.0).contains(&rating) => {
+			Some((rating * f32::from(MAX_RATING)).round() as u8)
+		},
+		_ => None,
+	}
+}
+
+impl From<PopularimeterFrame<'_>> for Popularimeter {
+	fn from(frame: PopularimeterFrame<'_>) -> Self {
+		Self {
+			email: frame.email,
+			rating: Self::rating_from_popm(frame.rating),
+			counter: frame.counter,
+		}
+	}
+}
+
+impl From<Popularimeter> for PopularimeterFrame<'static> {
+	fn from(popularimeter: Popularimeter) -> Self {
+		PopularimeterFrame::new(
+			popularimeter.email,
+			Popularimeter::rating_to_popm(popularimeter.rating),
+			popularimeter.counter,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::id3::v2::PopularimeterFrame;
+	use crate::tag::items::Popularimeter;
+	use crate::tag::{ItemKey, ItemValue, TagItem, TagType};
+
+	const WMP_EMAIL: &str = "Windows Media Player 9 Series";
+
+	#[test_log::test]
+	fn wmp_ratings_round_trip() {
+		// The ratings written by Windows Media Player for 1-5 stars
+		for (popm_rating, normalized) in [(1, 1), (64, 25), (128, 50), (196, 77), (255, 100)] {
+			let frame = PopularimeterFrame::new(String::from(WMP_EMAIL), popm_rating, 42);
+
+			let popularimeter = Popularimeter::from(frame);
+			assert_eq!(popularimeter.rating(), normalized);
+			assert_eq!(popularimeter.email, WMP_EMAIL);
+			assert_eq!(popularimeter.counter, 42);
+
+			let frame = PopularimeterFrame::from(popularimeter);
+			assert_eq!(frame.rating, popm_rating);
+			assert_eq!(frame.email, WMP_EMAIL);
+			assert_eq!(frame.counter, 42);
+		}
+	}
+
+	#[test_log::test]
+	fn normalized_ratings_round_trip() {
+		for rating in 0..=100 {
+			let popm_rating = Popularimeter::rating_to_popm(rating);
+			assert_eq!(Popularimeter::rating_from_popm(popm_rating), rating);
+		}
+	}
+
+	#[test_log::test]
+	fn text_ratings() {
+		for (text, expected) in [
+			("0", Some(0)),
+			("100", Some(100)),
+			(" 42 ", Some(42)),
+			("0.5", Some(50)),
+			("1.0", Some(100)),
+			("101", None),
+			("1.5", None),
+			("-1", None),
+			("five", None),
+		] {
+			let item = TagItem::new(ItemKey::Popularimeter, ItemValue::Text(String::from(text)));
+			assert_eq!(
+				Popularimeter::from_tag_item(&item).map(|p| p.rating()),
+				expected,
+				"{text}"
+			);
+		}
+	}
+
+	#[test_log::test]
+	fn tag_item_round_trip() {
+		let popularimeter =
+			Popularimeter::new(String::from(WMP_EMAIL), 77, u64::from(u32::MAX) + 1);
+
+		let item = popularimeter.clone().to_tag_item(TagType::Id3v2);
+		assert!(matches!(item.value(), ItemValue::Binary(_)));
+		assert_eq!(
+			Popularimeter::from_tag_item(&item),
+			Some(popularimeter.clone())
+		);
+
+		// Only the rating is retained in other formats
+		let item = popularimeter.to_tag_item(TagType::Ape);
+		assert_eq!(item.value(), &ItemValue::Text(String::from("77")));
+		assert_eq!(
+			Popularimeter::from_tag_item(&item),
+			Some(Popularimeter::new(String::new(), 77, 0))
+		);
+	}
+}
@@ -0,0 +1,95 @@
+use super::TagType;
+
+/// Where a tag is placed in a file
+///
+/// See [`TagLocation::placement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TagPlacement {
+	/// The tag is at the start of the file, before the audio (e.g. ID3v2 in MP3)
+	Head,
+	/// The tag is at the end of the file, after the audio (e.g. ID3v1, or APE in MP3)
+	Tail,
+	/// The tag is stored within the file's own structure (e.g. a RIFF chunk, FLAC metadata block, or MP4 atom)
+	Embedded,
+}
+
+/// The location of a tag in a file
+///
+/// See [`TaggedFile::tag_locations`](crate::file::TaggedFile::tag_locations).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TagLocation {
+	tag_type: TagType,
+	offset: u64,
+	len: u64,
+	placement: TagPlacement,
+	has_header: bool,
+	has_footer: bool,
+}
+
+impl TagLocation {
+	pub(crate) fn new(tag_type: TagType, offset: u64, len: u64, placement: TagPlacement) -> Self {
+		Self {
+			tag_type,
+			offset,
+			len,
+			placement,
+			has_header: false,
+			has_footer: false,
+		}
+	}
+
+	pub(crate) fn with_header(mut self, has_header: bool) -> Self {
+		self.has_header = has_header;
+		self
+	}
+
+	pub(crate) fn with_footer(mut self, has_footer: bool) -> Self {
+		self.has_footer = has_footer;
+		self
+	}
+
+	/// The type of the tag
+	pub fn tag_type(&self) -> TagType {
+		self.tag_type
+	}
+
+	/// The offset of the start of the tag
+	///
+	/// For [`TagPlacement::Embedded`] tags, this is the start of the structure containing the tag
+	/// (e.g. the chunk header).
+	pub fn offset(&self) -> u64 {
+		self.offset
+	}
+
+	/// The length of the tag, including any header and footer
+	///
+	/// For [`TagPlacement::Embedded`] tags, this is the length of the structure containing the tag.
+	pub fn len(&self) -> u64 {
+		self.len
+	}
+
+	/// Whether the tag has a length of 0
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Where the tag is placed in the file
+	pub fn placement(&self) -> TagPlacement {
+		self.placement
+	}
+
+	/// Whether the tag has its own header
+	///
+	/// This is only applicable to ID3v2 and APE tags.
+	pub fn has_header(&self) -> bool {
+		self.has_header
+	}
+
+	/// Whether the tag has its own footer
+	///
+	/// This is only applicable to ID3v2 and APE tags.
+	pub fn has_footer(&self) -> bool {
+		self.has_footer
+	}
+}
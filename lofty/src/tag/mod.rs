@@ -2,27 +2,39 @@
 
 mod accessor;
 pub(crate) mod companion_tag;
+mod conversion_report;
 pub(crate) mod item;
 pub mod items;
+mod location;
+mod normalize;
+mod patch;
 mod split_merge_tag;
 mod tag_ext;
 mod tag_type;
 pub(crate) mod utils;
 
-use crate::config::WriteOptions;
+use crate::config::{NormalizeOptions, PrettyPrintOptions, WriteOptions};
 use crate::error::{LoftyError, Result};
 use crate::macros::err;
 use crate::picture::{Picture, PictureType};
 use crate::probe::Probe;
+use crate::tag::items::{Popularimeter, Timestamp};
 use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::pretty_print::{self, PrettyPrinter, Value};
 
 use std::borrow::Cow;
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::Write;
 use std::path::Path;
+use std::str::FromStr;
 
 // Exports
 pub use accessor::Accessor;
+pub use conversion_report::{ConversionLoss, ConversionReport};
 pub use item::{ItemKey, ItemValue, TagItem};
+pub use location::{TagLocation, TagPlacement};
+pub use patch::{apply_patch, PatchChange, PatchOperation, PatchSummary, TagPatch};
 pub use split_merge_tag::{MergeTag, SplitTag};
 pub use tag_ext::TagExt;
 pub use tag_type::TagType;
@@ -108,11 +120,19 @@ macro_rules! impl_accessor {
 /// let id3v2_tag: Id3v2Tag = tag.into();
 /// ```
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tag {
 	tag_type: TagType,
 	pub(crate) pictures: Vec<Picture>,
 	pub(crate) items: Vec<TagItem>,
+	#[cfg_attr(feature = "serde", serde(skip))]
 	pub(crate) companion_tag: Option<companion_tag::CompanionTag>,
+	// The spelling of each key as it was read from the concrete tag, for `KeyStyle::Preserve`
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub(crate) original_keys: Vec<(ItemKey, String)>,
+	// A hash of the tag's content at the last call to `Tag::mark_clean`
+	#[cfg_attr(feature = "serde", serde(skip))]
+	clean_state: Option<u64>,
 }
 
 #[must_use]
@@ -219,6 +239,39 @@ impl Accessor for Tag {
 		self.remove_key(&ItemKey::Year);
 		self.remove_key(&ItemKey::RecordingDate);
 	}
+
+	/// Returns the first [`Popularimeter`] in the tag
+	///
+	/// This will parse the first [`ItemKey::Popularimeter`] item, see [`Popularimeter::from_tag_item`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Popularimeter;
+	/// use lofty::tag::{Accessor, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// assert_eq!(tag.popularimeter(), None);
+	///
+	/// tag.set_popularimeter(Popularimeter::new(String::new(), 80, 0));
+	/// assert_eq!(tag.popularimeter().map(|p| p.rating()), Some(80));
+	/// ```
+	fn popularimeter(&self) -> Option<Popularimeter> {
+		self.get_items(&ItemKey::Popularimeter)
+			.find_map(Popularimeter::from_tag_item)
+	}
+
+	/// Set the [`Popularimeter`], replacing any existing ones
+	///
+	/// The item will be stored in the format appropriate for the tag's [`TagType`],
+	/// see [`Popularimeter::to_tag_item`].
+	fn set_popularimeter(&mut self, value: Popularimeter) {
+		self.insert(value.to_tag_item(self.tag_type));
+	}
+
+	fn remove_popularimeter(&mut self) {
+		self.remove_key(&ItemKey::Popularimeter);
+	}
 }
 
 impl Tag {
@@ -230,6 +283,8 @@ impl Tag {
 			pictures: Vec::new(),
 			items: Vec::new(),
 			companion_tag: None,
+			original_keys: Vec::new(),
+			clean_state: None,
 		}
 	}
 
@@ -261,10 +316,54 @@ impl Tag {
 			drop(companion_tag);
 		}
 
+		self.original_keys.clear();
 		self.retain(|i| i.re_map(tag_type));
+
+		// Ratings are stored differently between ID3v2 and all other formats
+		for item in &mut self.items {
+			if item.key() != &ItemKey::Popularimeter {
+				continue;
+			}
+
+			if let Some(popularimeter) = Popularimeter::from_tag_item(item) {
+				item.item_value = popularimeter.to_tag_item(tag_type).item_value;
+			}
+		}
+
 		self.tag_type = tag_type
 	}
 
+	/// Find what would be lost when converting the tag to another [`TagType`]
+	///
+	/// The tag is converted to `target` and back, using the same conversions as when the tag is
+	/// saved or converted into a concrete tag (e.g. [`Id3v1Tag::from`](crate::id3::v1::Id3v1Tag)).
+	/// Any items and pictures that don't survive are reported.
+	///
+	/// NOTE: This doesn't modify the tag.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{Accessor, ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// tag.set_title(String::from("A title that is much too long for ID3v1"));
+	/// tag.insert_text(ItemKey::Composer, String::from("Composer"));
+	///
+	/// let report = tag.conversion_report(TagType::Id3v1);
+	///
+	/// // ID3v1 titles are limited to 30 bytes
+	/// let (_, truncated_title) = report.truncated_items().next().unwrap();
+	/// assert_eq!(truncated_title, "A title that is much too long ");
+	///
+	/// // And there is no composer field
+	/// let dropped_item = report.dropped_items().next().unwrap();
+	/// assert_eq!(dropped_item.key(), &ItemKey::Composer);
+	/// ```
+	pub fn conversion_report(&self, target: TagType) -> ConversionReport {
+		conversion_report::create_report(self, target)
+	}
+
 	/// Check if the tag contains any format-specific items
 	///
 	/// See [`GlobalOptions::preserve_format_specific_items`].
@@ -287,6 +386,97 @@ impl Tag {
 		self.companion_tag.is_some()
 	}
 
+	/// Whether the tag has changed since the last call to [`Tag::mark_clean`]
+	///
+	/// Tags are compared by their content, so a change that is later undone will not make the tag
+	/// dirty. Tags that were never marked clean are always dirty.
+	///
+	/// The tags of a [`BoundTaggedFile`](crate::file::BoundTaggedFile) are marked clean when they
+	/// are read and saved, so that [`BoundTaggedFile::save`](crate::file::BoundTaggedFile::save)
+	/// only needs to write the tags that have changed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{Accessor, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// assert!(tag.is_dirty());
+	///
+	/// tag.set_title(String::from("Foo title"));
+	/// tag.mark_clean();
+	/// assert!(!tag.is_dirty());
+	///
+	/// tag.set_title(String::from("Bar title"));
+	/// assert!(tag.is_dirty());
+	///
+	/// // Restoring the original content makes the tag clean again
+	/// tag.set_title(String::from("Foo title"));
+	/// assert!(!tag.is_dirty());
+	/// ```
+	pub fn is_dirty(&self) -> bool {
+		self.clean_state != Some(self.content_hash())
+	}
+
+	/// Mark the current content of the tag as unchanged
+	///
+	/// See [`Tag::is_dirty`].
+	pub fn mark_clean(&mut self) {
+		self.clean_state = Some(self.content_hash());
+	}
+
+	pub(crate) fn mark_dirty(&mut self) {
+		self.clean_state = None;
+	}
+
+	// Remember how a key was spelled in the concrete tag, see `KeyStyle::Preserve`
+	pub(crate) fn record_original_key(&mut self, item_key: &ItemKey, key: &str) {
+		if matches!(item_key, ItemKey::Unknown(_))
+			|| self.original_keys.iter().any(|(k, _)| k == item_key)
+		{
+			return;
+		}
+
+		self.original_keys.push((item_key.clone(), key.to_string()));
+	}
+
+	// Map `item_key` to a key of `tag_type`, spelled according to `GlobalOptions::key_style`
+	pub(crate) fn styled_key<'a>(
+		&'a self,
+		item_key: &'a ItemKey,
+		tag_type: TagType,
+		allow_unknown: bool,
+	) -> Option<Cow<'a, str>> {
+		item_key.map_key_styled(
+			tag_type,
+			allow_unknown,
+			self.original_key(tag_type, item_key),
+		)
+	}
+
+	// The spelling of `item_key` when this tag was split from a concrete tag of `tag_type`
+	pub(crate) fn original_key(&self, tag_type: TagType, item_key: &ItemKey) -> Option<&str> {
+		if self.tag_type != tag_type {
+			return None;
+		}
+
+		self.original_keys
+			.iter()
+			.find(|(k, _)| k == item_key)
+			.map(|(_, key)| key.as_str())
+	}
+
+	fn content_hash(&self) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		self.tag_type.hash(&mut hasher);
+		self.items.hash(&mut hasher);
+		self.pictures.hash(&mut hasher);
+		// The companion tag can only be discarded, see `Tag::re_map`
+		self.companion_tag.is_some().hash(&mut hasher);
+
+		hasher.finish()
+	}
+
 	/// Returns the [`TagType`]
 	pub fn tag_type(&self) -> TagType {
 		self.tag_type
@@ -410,6 +600,75 @@ impl Tag {
 		self.take_filter(key, |_| true)
 	}
 
+	/// Removes the first item with the specified [`ItemKey`], and returns it
+	///
+	/// The ordering of the remaining items is preserved.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.insert_text(ItemKey::TrackTitle, String::from("Foo title"));
+	///
+	/// let title = tag.take_first(&ItemKey::TrackTitle).unwrap();
+	/// assert_eq!(title.value().text(), Some("Foo title"));
+	/// assert!(tag.get(&ItemKey::TrackTitle).is_none());
+	/// ```
+	pub fn take_first(&mut self, key: &ItemKey) -> Option<TagItem> {
+		let idx = self.items.iter().position(|i| i.key() == key)?;
+		Some(self.items.remove(idx))
+	}
+
+	/// Insert a [`TagItem`], returning the item it replaced
+	///
+	/// Unlike [`Tag::insert`], the item takes the position of the first existing item of the same
+	/// [`ItemKey`]. Any additional items of the same [`ItemKey`] are removed and dropped.
+	///
+	/// NOTE: This **will** verify an [`ItemKey`] mapping exists for the target [`TagType`]
+	///
+	/// # Errors
+	///
+	/// If no [`ItemKey`] mapping exists for the target [`TagType`], the item is not inserted and
+	/// is handed back as `Err(item)`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.insert_text(ItemKey::TrackTitle, String::from("Foo title"));
+	///
+	/// let new_title = TagItem::new(ItemKey::TrackTitle, ItemValue::Text(String::from("Bar title")));
+	/// let old_title = tag.replace(new_title).unwrap().unwrap();
+	///
+	/// assert_eq!(old_title.value().text(), Some("Foo title"));
+	/// assert_eq!(tag.get_string(&ItemKey::TrackTitle), Some("Bar title"));
+	/// ```
+	pub fn replace(&mut self, item: TagItem) -> std::result::Result<Option<TagItem>, TagItem> {
+		if !item.re_map(self.tag_type) {
+			return Err(item);
+		}
+
+		let Some(idx) = self.items.iter().position(|i| i.key() == item.key()) else {
+			self.items.push(item);
+			return Ok(None);
+		};
+
+		let replaced = std::mem::replace(&mut self.items[idx], item);
+
+		let mut current_idx = 0;
+		self.items.retain(|i| {
+			let retain = current_idx <= idx || i.key() != replaced.key();
+			current_idx += 1;
+			retain
+		});
+
+		Ok(Some(replaced))
+	}
+
 	/// Removes selected items with the specified [`ItemKey`], and returns them
 	///
 	/// Only takes items for which `filter()` returns `true`. All other items are retained.
@@ -523,11 +782,142 @@ impl Tag {
 		self.items.retain(f)
 	}
 
+	/// Returns the recording date
+	///
+	/// This will parse the first [`ItemKey::RecordingDate`] item, falling back to [`ItemKey::Year`].
+	/// Partial dates, such as `1994-06`, keep only the components that are present.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Timestamp;
+	/// use lofty::tag::{ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::VorbisComments);
+	/// tag.insert_text(ItemKey::RecordingDate, String::from("1994-06"));
+	///
+	/// let date = tag.recording_date().unwrap();
+	/// assert_eq!(date.year, 1994);
+	/// assert_eq!(date.month, Some(6));
+	/// assert_eq!(date.day, None);
+	/// ```
+	pub fn recording_date(&self) -> Option<Timestamp> {
+		[ItemKey::RecordingDate, ItemKey::Year]
+			.iter()
+			.filter_map(|key| self.get_string(key))
+			.find_map(|date| Timestamp::from_str(date.trim()).ok())
+	}
+
+	/// Set the recording date, replacing any existing ones
+	///
+	/// The date is stored as an [`ItemKey::RecordingDate`] item, or an [`ItemKey::Year`] item for formats
+	/// without a dedicated date (such as APE). It is formatted as `YYYY-MM-DDTHH:MM:SS`, omitting any
+	/// components that aren't set.
+	///
+	/// This will return `true` if the item was inserted. Timestamps with out of range
+	/// components will not be inserted.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::items::Timestamp;
+	/// use lofty::tag::{ItemKey, Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Mp4Ilst);
+	/// tag.set_recording_date(Timestamp {
+	/// 	year: 1994,
+	/// 	month: Some(6),
+	/// 	..Timestamp::default()
+	/// });
+	///
+	/// assert_eq!(tag.get_string(&ItemKey::RecordingDate), Some("1994-06"));
+	/// ```
+	pub fn set_recording_date(&mut self, timestamp: Timestamp) -> bool {
+		if timestamp.verify().is_err() {
+			return false;
+		}
+
+		let value = ItemValue::Text(timestamp.to_string());
+		if ItemKey::RecordingDate
+			.map_key(self.tag_type, false)
+			.is_some()
+		{
+			// A separate year would take precedence in `Accessor::year`
+			self.remove_key(&ItemKey::Year);
+			return self.insert(TagItem::new(ItemKey::RecordingDate, value));
+		}
+
+		self.insert(TagItem::new(ItemKey::Year, value))
+	}
+
+	/// Returns the first [`ItemKey::Lyrics`] item
+	///
+	/// Lyrics are mapped for ID3v2 (`USLT`), Vorbis Comments (`LYRICS` or `UNSYNCEDLYRICS`), MP4 (`\u{a9}lyr`), APE (`Lyrics`),
+	/// and ASF (`WM/Lyrics`).
+	///
+	/// ID3v2 tags may have lyrics in multiple languages, which are stored as separate items. To access them,
+	/// use [`Tag::get_items`] and [`TagItem::lang`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Mp4Ilst);
+	/// assert_eq!(tag.lyrics(), None);
+	///
+	/// tag.set_lyrics(String::from("Lorem ipsum"));
+	/// assert_eq!(tag.lyrics(), Some("Lorem ipsum"));
+	/// ```
+	pub fn lyrics(&self) -> Option<&str> {
+		self.get_string(&ItemKey::Lyrics)
+	}
+
+	/// Set the lyrics, replacing any existing ones
+	///
+	/// This will return `true` if the item was inserted.
+	pub fn set_lyrics(&mut self, lyrics: String) -> bool {
+		self.insert_text(ItemKey::Lyrics, lyrics)
+	}
+
+	/// Remove all [`ItemKey::Lyrics`] items
+	pub fn remove_lyrics(&mut self) {
+		self.remove_key(&ItemKey::Lyrics);
+	}
+
 	/// Remove all items with empty values
 	pub fn remove_empty(&mut self) {
 		self.items.retain(|item| !item.value().is_empty());
 	}
 
+	/// Clean up the tag's items
+	///
+	/// This fixes common problems in real-world tags, such as stray whitespace, zero-padded numbers,
+	/// and duplicated items. See [`NormalizeOptions`] for what each step does.
+	///
+	/// Binary values and pictures are never modified.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::NormalizeOptions;
+	/// use lofty::prelude::*;
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	/// tag.set_title(String::from(" Foo title "));
+	/// tag.insert_text(ItemKey::TrackNumber, String::from("03"));
+	///
+	/// tag.normalize(NormalizeOptions::new());
+	///
+	/// assert_eq!(tag.title().as_deref(), Some("Foo title"));
+	/// assert_eq!(tag.track(), Some(3));
+	/// assert_eq!(tag.get_string(&ItemKey::TrackNumber), Some("3"));
+	/// ```
+	pub fn normalize(&mut self, options: NormalizeOptions) {
+		normalize::normalize_items(&mut self.items, options);
+	}
+
 	/// Returns the stored [`Picture`]s as a slice
 	pub fn pictures(&self) -> &[Picture] {
 		&self.pictures
@@ -634,6 +1024,150 @@ impl Tag {
 	pub fn remove_picture(&mut self, index: usize) -> Picture {
 		self.pictures.remove(index)
 	}
+
+	/// Removes pictures with identical data, keeping a single instance of each
+	///
+	/// If any of the duplicates is a [`PictureType::CoverFront`], that one is kept. Otherwise, the
+	/// first occurrence is kept.
+	///
+	/// NOTE: Only the picture data is compared, so duplicates with a different type or
+	///       description are removed as well.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::{MimeType, Picture, PictureType};
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	///
+	/// let data = vec![1, 2, 3];
+	/// for pic_type in [PictureType::Other, PictureType::CoverFront, PictureType::Other] {
+	/// 	tag.push_picture(Picture::new_unchecked(
+	/// 		pic_type,
+	/// 		Some(MimeType::Png),
+	/// 		None,
+	/// 		data.clone(),
+	/// 	));
+	/// }
+	///
+	/// tag.dedupe_pictures();
+	///
+	/// assert_eq!(tag.pictures().len(), 1);
+	/// assert_eq!(tag.pictures()[0].pic_type(), PictureType::CoverFront);
+	/// ```
+	pub fn dedupe_pictures(&mut self) {
+		if self.pictures.len() < 2 {
+			return;
+		}
+
+		// The pictures are only hashed once, the full data is only compared on a hash match
+		let hashes = self
+			.pictures
+			.iter()
+			.map(|picture| {
+				let mut hasher = DefaultHasher::new();
+				picture.data().hash(&mut hasher);
+				hasher.finish()
+			})
+			.collect::<Vec<_>>();
+
+		// The index of the picture to keep for each distinct data
+		let mut survivors: Vec<usize> = Vec::new();
+		for (index, picture) in self.pictures.iter().enumerate() {
+			let existing = survivors.iter_mut().find(|survivor| {
+				hashes[**survivor] == hashes[index]
+					&& self.pictures[**survivor].data() == picture.data()
+			});
+
+			match existing {
+				Some(survivor) => {
+					if picture.pic_type() == PictureType::CoverFront
+						&& self.pictures[*survivor].pic_type() != PictureType::CoverFront
+					{
+						*survivor = index;
+					}
+				},
+				None => survivors.push(index),
+			}
+		}
+
+		if survivors.len() == self.pictures.len() {
+			return;
+		}
+
+		let mut index = 0;
+		self.pictures.retain(|_| {
+			let keep = survivors.contains(&index);
+			index += 1;
+			keep
+		});
+	}
+
+	/// Removes all pictures with more than `bytes` bytes of data
+	///
+	/// This returns the number of pictures removed.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::picture::{MimeType, Picture, PictureType};
+	/// use lofty::tag::{Tag, TagType};
+	///
+	/// let mut tag = Tag::new(TagType::Id3v2);
+	///
+	/// for size in [100, 5000] {
+	/// 	tag.push_picture(Picture::new_unchecked(
+	/// 		PictureType::CoverFront,
+	/// 		Some(MimeType::Png),
+	/// 		None,
+	/// 		vec![0; size],
+	/// 	));
+	/// }
+	///
+	/// assert_eq!(tag.remove_pictures_larger_than(1024), 1);
+	/// assert_eq!(tag.pictures().len(), 1);
+	/// ```
+	pub fn remove_pictures_larger_than(&mut self, bytes: usize) -> usize {
+		let len = self.pictures.len();
+		self.pictures
+			.retain(|picture| picture.data().len() <= bytes);
+
+		len - self.pictures.len()
+	}
+
+	/// Writes a line-oriented listing of the items and pictures to `writer`
+	///
+	/// Items are keyed by the name of their [`ItemKey`] variant (ex. `TrackTitle`), or by the key itself
+	/// for [`ItemKey::Unknown`]. Pictures are keyed by `Picture`.
+	///
+	/// See [`PrettyPrintOptions`] for the format.
+	///
+	/// # Errors
+	///
+	/// * Writing to `writer` fails
+	pub fn pretty_print(
+		&self,
+		writer: &mut impl fmt::Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		let mut printer = PrettyPrinter::default();
+		for item in &self.items {
+			let key = match item.key() {
+				ItemKey::Unknown(key) => key.as_str(),
+				key => key.variant_name().unwrap_or_default(),
+			};
+
+			let (ty, value) = pretty_print::item_value(item.value());
+			printer.push(key, ty, value);
+		}
+
+		for picture in &self.pictures {
+			printer.push("Picture", "Picture", Value::Picture(picture));
+		}
+
+		printer.finish(writer, options)
+	}
 }
 
 impl TagExt for Tag {
@@ -744,14 +1278,158 @@ impl MergeTag for SplitTagRemainder {
 mod tests {
 	use super::try_parse_year;
 	use crate::config::WriteOptions;
-	use crate::picture::{Picture, PictureType};
+	use crate::picture::{MimeType, Picture, PictureType};
 	use crate::prelude::*;
 	use crate::tag::utils::test_utils::read_path;
-	use crate::tag::{Tag, TagType};
+	use crate::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
 
 	use std::io::{Seek, Write};
 	use std::process::Command;
 
+	fn picture(pic_type: PictureType, data: &[u8]) -> Picture {
+		Picture::new_unchecked(pic_type, Some(MimeType::Jpeg), None, data.to_vec())
+	}
+
+	#[test_log::test]
+	fn dedupe_pictures() {
+		let cover = [1; 64];
+		let other = [2; 64];
+
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_picture(picture(PictureType::Other, &cover));
+		tag.push_picture(picture(PictureType::Artist, &other));
+		tag.push_picture(picture(PictureType::CoverFront, &cover));
+		tag.push_picture(picture(PictureType::CoverBack, &cover));
+		tag.push_picture(picture(PictureType::Band, &other));
+
+		tag.dedupe_pictures();
+
+		// The front cover is preferred, otherwise the first instance is kept
+		let remaining = tag
+			.pictures()
+			.iter()
+			.map(|picture| (picture.pic_type(), picture.data()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			remaining,
+			[
+				(PictureType::Artist, &other[..]),
+				(PictureType::CoverFront, &cover[..])
+			]
+		);
+
+		// Nothing left to remove
+		tag.dedupe_pictures();
+		assert_eq!(tag.picture_count(), 2);
+	}
+
+	#[test_log::test]
+	fn remove_pictures_larger_than() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_picture(picture(PictureType::CoverFront, &[0; 3000]));
+		tag.push_picture(picture(PictureType::CoverBack, &[0; 1024]));
+		tag.push_picture(picture(PictureType::Other, &[0; 1025]));
+
+		assert_eq!(tag.remove_pictures_larger_than(1024), 2);
+		assert_eq!(tag.picture_count(), 1);
+		assert_eq!(tag.pictures()[0].pic_type(), PictureType::CoverBack);
+
+		assert_eq!(tag.remove_pictures_larger_than(1024), 0);
+	}
+
+	#[test_log::test]
+	fn move_items_without_cloning() {
+		let mut source = Tag::new(TagType::Id3v2);
+		source.insert(TagItem::new(
+			ItemKey::Comment,
+			ItemValue::Binary(vec![1; 2 * 1024 * 1024]),
+		));
+		let buffer_ptr = source.get_bytes(&ItemKey::Comment).next().unwrap().as_ptr();
+
+		let mut dest = Tag::new(TagType::Id3v2);
+		dest.insert_text(ItemKey::Comment, String::from("Foo comment"));
+
+		let item = source.take_first(&ItemKey::Comment).unwrap();
+		assert!(source.is_empty());
+
+		let replaced = dest.replace(item).unwrap().unwrap();
+		assert_eq!(replaced.value().text(), Some("Foo comment"));
+
+		// The buffer was moved, not cloned
+		let moved_ptr = dest.get_bytes(&ItemKey::Comment).next().unwrap().as_ptr();
+		assert_eq!(buffer_ptr, moved_ptr);
+		assert_eq!(dest.item_count(), 1);
+
+		// And back again
+		let item = dest.take(&ItemKey::Comment).next().unwrap();
+		assert!(source.replace(item).unwrap().is_none());
+		assert_eq!(
+			source.get_bytes(&ItemKey::Comment).next().unwrap().as_ptr(),
+			buffer_ptr
+		);
+	}
+
+	#[test_log::test]
+	fn replace_keeps_position() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.insert_text(ItemKey::TrackTitle, String::from("Foo title"));
+		tag.push(TagItem::new(
+			ItemKey::TrackArtist,
+			ItemValue::Text(String::from("Foo artist")),
+		));
+		tag.push(TagItem::new(
+			ItemKey::TrackArtist,
+			ItemValue::Text(String::from("Bar artist")),
+		));
+		tag.insert_text(ItemKey::AlbumTitle, String::from("Foo album"));
+
+		let replaced = tag.replace(TagItem::new(
+			ItemKey::TrackArtist,
+			ItemValue::Text(String::from("Baz artist")),
+		));
+		assert_eq!(
+			replaced.unwrap().unwrap().value().text(),
+			Some("Foo artist")
+		);
+
+		let keys = tag.items().map(TagItem::key).collect::<Vec<_>>();
+		assert_eq!(
+			keys,
+			[
+				&ItemKey::TrackTitle,
+				&ItemKey::TrackArtist,
+				&ItemKey::AlbumTitle
+			]
+		);
+		assert_eq!(tag.get_string(&ItemKey::TrackArtist), Some("Baz artist"));
+
+		// No mapping exists, so nothing is replaced
+		let unknown = TagItem::new(
+			ItemKey::Unknown(String::from("FOO")),
+			ItemValue::Text(String::from("Foo")),
+		);
+		assert!(tag.replace(unknown).is_err());
+		assert_eq!(tag.item_count(), 3);
+	}
+
+	#[test_log::test]
+	fn replace_returns_rejected_item() {
+		let mut tag = Tag::new(TagType::Id3v1);
+		tag.insert_text(ItemKey::TrackTitle, String::from("Foo title"));
+
+		// ID3v1 has no field for a composer
+		let composer = TagItem::new(
+			ItemKey::Composer,
+			ItemValue::Text(String::from("Foo composer")),
+		);
+		let rejected = tag.replace(composer).unwrap_err();
+
+		assert_eq!(rejected.key(), &ItemKey::Composer);
+		assert_eq!(rejected.value().text(), Some("Foo composer"));
+		assert!(tag.get(&ItemKey::Composer).is_none());
+		assert_eq!(tag.item_count(), 1);
+	}
+
 	#[test_log::test]
 	fn issue_37() {
 		let file_contents = read_path("tests/files/assets/issue_37.ogg");
@@ -837,6 +1515,89 @@ mod tests {
 		assert_eq!(tag.title(), None);
 	}
 
+	#[test_log::test]
+	fn recording_date_round_trip() {
+		use crate::ape::ApeTag;
+		use crate::id3::v2::Id3v2Tag;
+		use crate::iff::wav::RiffInfoList;
+		use crate::mp4::Ilst;
+		use crate::ogg::VorbisComments;
+		use crate::tag::items::Timestamp;
+
+		fn round_trip(tag: Tag) -> Tag {
+			match tag.tag_type() {
+				TagType::Ape => ApeTag::from(tag).into(),
+				TagType::Id3v2 => Id3v2Tag::from(tag).into(),
+				TagType::Mp4Ilst => Ilst::from(tag).into(),
+				TagType::RiffInfo => RiffInfoList::from(tag).into(),
+				TagType::VorbisComments => VorbisComments::from(tag).into(),
+				_ => unreachable!(),
+			}
+		}
+
+		let dates = [
+			Timestamp {
+				year: 1994,
+				..Timestamp::default()
+			},
+			// Partial dates should never be padded
+			Timestamp {
+				year: 1994,
+				month: Some(6),
+				..Timestamp::default()
+			},
+			Timestamp {
+				year: 1994,
+				month: Some(6),
+				day: Some(3),
+				hour: Some(14),
+				minute: Some(8),
+				second: Some(49),
+			},
+		];
+
+		for tag_type in [
+			TagType::Ape,
+			TagType::Id3v2,
+			TagType::Mp4Ilst,
+			TagType::RiffInfo,
+			TagType::VorbisComments,
+		] {
+			for date in dates {
+				let mut tag = Tag::new(tag_type);
+				assert!(tag.set_recording_date(date));
+
+				let tag = round_trip(tag);
+				assert_eq!(tag.recording_date(), Some(date), "{tag_type:?}");
+				assert_eq!(tag.year(), Some(1994), "{tag_type:?}");
+			}
+		}
+	}
+
+	#[test_log::test]
+	fn recording_date_invalid() {
+		use crate::tag::items::Timestamp;
+
+		let mut tag = Tag::new(TagType::VorbisComments);
+		assert!(!tag.set_recording_date(Timestamp {
+			year: 1994,
+			month: Some(13),
+			..Timestamp::default()
+		}));
+		assert!(tag.is_empty());
+
+		// A stale year would shadow the new date
+		tag.insert_text(ItemKey::Year, String::from("1990"));
+		assert!(tag.set_recording_date(Timestamp {
+			year: 1994,
+			..Timestamp::default()
+		}));
+		assert_eq!(tag.year(), Some(1994));
+
+		tag.insert_text(ItemKey::RecordingDate, String::from("not a date"));
+		assert_eq!(tag.recording_date(), None);
+	}
+
 	#[test_log::test]
 	fn try_parse_year_with_leading_trailing_whitespace_and_various_formats() {
 		assert_eq!(Some(1983), try_parse_year("\t 1983\n"));
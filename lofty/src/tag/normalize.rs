@@ -0,0 +1,356 @@
+use crate::config::NormalizeOptions;
+use crate::tag::{ItemKey, ItemValue, TagItem};
+
+// The number keys, and the keys of their totals
+const NUMBER_PAIRS: [(ItemKey, ItemKey); 3] = [
+	(ItemKey::TrackNumber, ItemKey::TrackTotal),
+	(ItemKey::DiscNumber, ItemKey::DiscTotal),
+	(ItemKey::MovementNumber, ItemKey::MovementTotal),
+];
+
+const DATE_KEYS: [ItemKey; 4] = [
+	ItemKey::Year,
+	ItemKey::RecordingDate,
+	ItemKey::ReleaseDate,
+	ItemKey::OriginalReleaseDate,
+];
+
+pub(super) fn normalize_items(items: &mut Vec<TagItem>, options: NormalizeOptions) {
+	if options.trim_whitespace {
+		for item in items.iter_mut() {
+			if let Some(text) = text_mut(&mut item.item_value) {
+				trim_whitespace(text);
+			}
+		}
+	}
+
+	if options.fix_zero_padded_dates {
+		for item in items.iter_mut() {
+			if !DATE_KEYS.contains(&item.item_key) {
+				continue;
+			}
+
+			if let ItemValue::Text(date) = &mut item.item_value {
+				let len = zero_padded_date_len(date).unwrap_or(date.len());
+				date.truncate(len);
+			}
+		}
+	}
+
+	if options.canonicalize_numbers {
+		canonicalize_numbers(items);
+	}
+
+	if options.remove_empty {
+		items.retain(|item| !text(&item.item_value).is_some_and(str::is_empty));
+	}
+
+	if options.remove_duplicates {
+		let mut kept: Vec<TagItem> = Vec::with_capacity(items.len());
+		for item in items.drain(..) {
+			if text(&item.item_value).is_some() && kept.contains(&item) {
+				continue;
+			}
+
+			kept.push(item);
+		}
+
+		*items = kept;
+	}
+}
+
+// Binary values are never touched, so only text and locators are normalized
+fn text(value: &ItemValue) -> Option<&str> {
+	match value {
+		ItemValue::Text(text) | ItemValue::Locator(text) => Some(text),
+		ItemValue::Binary(_) => None,
+	}
+}
+
+fn text_mut(value: &mut ItemValue) -> Option<&mut String> {
+	match value {
+		ItemValue::Text(text) | ItemValue::Locator(text) => Some(text),
+		ItemValue::Binary(_) => None,
+	}
+}
+
+fn trim_whitespace(text: &mut String) {
+	let trimmed = text.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+	if trimmed.len() != text.len() {
+		*text = trimmed.to_string();
+	}
+}
+
+// The length of `date` without its zeroed components, if it has any
+//
+// This only handles dates starting with "YYYY-MM", everything after a zeroed component is dropped.
+fn zero_padded_date_len(date: &str) -> Option<usize> {
+	let bytes = date.as_bytes();
+	let component = |start: usize| {
+		let digits = bytes.get(start + 1..start + 3)?;
+		(bytes[start] == b'-' && digits.iter().all(u8::is_ascii_digit)).then_some(digits)
+	};
+
+	if bytes.len() < 4 || !bytes[..4].iter().all(u8::is_ascii_digit) {
+		return None;
+	}
+
+	if component(4)? == b"00" {
+		return Some(4);
+	}
+
+	(component(7)? == b"00").then_some(7)
+}
+
+// "007" becomes "7", and "000" becomes "0". Anything other than digits isn't a number.
+fn strip_leading_zeros(number: &str) -> Option<&str> {
+	if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+
+	match number.trim_start_matches('0') {
+		"" => Some("0"),
+		stripped => Some(stripped),
+	}
+}
+
+fn canonicalize_numbers(items: &mut Vec<TagItem>) {
+	let mut split_totals = Vec::new();
+	for item in items.iter_mut() {
+		let total_key = NUMBER_PAIRS
+			.iter()
+			.find(|(number_key, _)| *number_key == item.item_key)
+			.map(|(_, total_key)| total_key);
+		let is_total = NUMBER_PAIRS
+			.iter()
+			.any(|(_, total_key)| *total_key == item.item_key);
+
+		let ItemValue::Text(value) = &mut item.item_value else {
+			continue;
+		};
+
+		let (number, total) = match (total_key, value.split_once('/')) {
+			(Some(total_key), Some((number, total))) => (number, Some((total_key, total))),
+			_ if total_key.is_some() || is_total => (value.as_str(), None),
+			_ => continue,
+		};
+
+		let Some(number) = strip_leading_zeros(number) else {
+			continue;
+		};
+
+		if let Some((total_key, total)) = total {
+			let Some(total) = strip_leading_zeros(total) else {
+				continue;
+			};
+
+			split_totals.push(TagItem::new(
+				total_key.clone(),
+				ItemValue::Text(total.to_string()),
+			));
+		}
+
+		let number = number.to_string();
+		*value = number;
+	}
+
+	for total in split_totals {
+		if !items.iter().any(|item| item.item_key == total.item_key) {
+			items.push(total);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::config::NormalizeOptions;
+	use crate::id3::v2::{Frame, FrameId, Id3v2Tag, TextInformationFrame};
+	use crate::picture::{MimeType, Picture, PictureType};
+	use crate::prelude::*;
+	use crate::tag::{ItemValue, Tag, TagItem, TagType};
+	use crate::TextEncoding;
+
+	use std::borrow::Cow;
+
+	fn only(option: fn(NormalizeOptions, bool) -> NormalizeOptions) -> NormalizeOptions {
+		let none = NormalizeOptions::new()
+			.trim_whitespace(false)
+			.remove_empty(false)
+			.canonicalize_numbers(false)
+			.fix_zero_padded_dates(false)
+			.remove_duplicates(false);
+		option(none, true)
+	}
+
+	fn text_item(key: ItemKey, value: &str) -> TagItem {
+		TagItem::new(key, ItemValue::Text(value.to_string()))
+	}
+
+	fn values(tag: &Tag, key: &ItemKey) -> Vec<String> {
+		tag.get_items(key)
+			.map(|item| match item.value() {
+				ItemValue::Text(text) | ItemValue::Locator(text) => text.clone(),
+				ItemValue::Binary(_) => String::from("<binary>"),
+			})
+			.collect()
+	}
+
+	#[test_log::test]
+	fn trim_whitespace() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_unchecked(text_item(ItemKey::TrackTitle, "  Foo title\t"));
+		tag.push_unchecked(text_item(ItemKey::Genre, "Rock\0\0"));
+		tag.push_unchecked(TagItem::new(
+			ItemKey::AudioFileUrl,
+			ItemValue::Locator(String::from(" https://example.com ")),
+		));
+		tag.push_unchecked(text_item(ItemKey::Comment, "   "));
+
+		tag.normalize(only(NormalizeOptions::trim_whitespace));
+
+		assert_eq!(values(&tag, &ItemKey::TrackTitle), ["Foo title"]);
+		assert_eq!(values(&tag, &ItemKey::Genre), ["Rock"]);
+		assert_eq!(
+			values(&tag, &ItemKey::AudioFileUrl),
+			["https://example.com"]
+		);
+		// Trimmed, but not removed
+		assert_eq!(values(&tag, &ItemKey::Comment), [""]);
+	}
+
+	#[test_log::test]
+	fn remove_empty() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_unchecked(text_item(ItemKey::TrackTitle, "Foo title"));
+		tag.push_unchecked(text_item(ItemKey::Genre, ""));
+		tag.push_unchecked(text_item(ItemKey::Comment, "   "));
+
+		tag.normalize(only(NormalizeOptions::remove_empty));
+
+		assert_eq!(tag.item_count(), 2);
+		assert!(tag.get(&ItemKey::Genre).is_none());
+
+		// Whitespace only values are removed together with `trim_whitespace`
+		tag.normalize(only(NormalizeOptions::remove_empty).trim_whitespace(true));
+		assert_eq!(tag.item_count(), 1);
+		assert_eq!(tag.title().as_deref(), Some("Foo title"));
+	}
+
+	#[test_log::test]
+	fn canonicalize_numbers() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_unchecked(text_item(ItemKey::TrackNumber, "03/12"));
+		tag.push_unchecked(text_item(ItemKey::DiscNumber, "01"));
+		tag.push_unchecked(text_item(ItemKey::DiscTotal, "002"));
+		tag.push_unchecked(text_item(ItemKey::MovementNumber, "000"));
+		tag.push_unchecked(text_item(ItemKey::Bpm, "0120"));
+
+		tag.normalize(only(NormalizeOptions::canonicalize_numbers));
+
+		assert_eq!(values(&tag, &ItemKey::TrackNumber), ["3"]);
+		assert_eq!(values(&tag, &ItemKey::TrackTotal), ["12"]);
+		assert_eq!(values(&tag, &ItemKey::DiscNumber), ["1"]);
+		assert_eq!(values(&tag, &ItemKey::DiscTotal), ["2"]);
+		assert_eq!(values(&tag, &ItemKey::MovementNumber), ["0"]);
+		// Not a number pair
+		assert_eq!(values(&tag, &ItemKey::Bpm), ["0120"]);
+	}
+
+	#[test_log::test]
+	fn canonicalize_numbers_keeps_existing_total() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_unchecked(text_item(ItemKey::TrackNumber, "3/12"));
+		tag.push_unchecked(text_item(ItemKey::TrackTotal, "10"));
+		tag.push_unchecked(text_item(ItemKey::DiscNumber, "1/foo"));
+		tag.push_unchecked(text_item(ItemKey::DiscTotal, "two"));
+
+		tag.normalize(only(NormalizeOptions::canonicalize_numbers));
+
+		assert_eq!(values(&tag, &ItemKey::TrackNumber), ["3"]);
+		assert_eq!(values(&tag, &ItemKey::TrackTotal), ["10"]);
+		// Values that aren't numbers are left alone
+		assert_eq!(values(&tag, &ItemKey::DiscNumber), ["1/foo"]);
+		assert_eq!(values(&tag, &ItemKey::DiscTotal), ["two"]);
+	}
+
+	#[test_log::test]
+	fn fix_zero_padded_dates() {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push_unchecked(text_item(ItemKey::RecordingDate, "2004-00-00"));
+		tag.push_unchecked(text_item(ItemKey::ReleaseDate, "2004-05-00"));
+		tag.push_unchecked(text_item(ItemKey::OriginalReleaseDate, "2004-05-00T12:00"));
+		tag.push_unchecked(text_item(ItemKey::Year, "2004-05-06"));
+		tag.push_unchecked(text_item(ItemKey::Comment, "2004-00-00"));
+
+		tag.normalize(only(NormalizeOptions::fix_zero_padded_dates));
+
+		assert_eq!(values(&tag, &ItemKey::RecordingDate), ["2004"]);
+		assert_eq!(values(&tag, &ItemKey::ReleaseDate), ["2004-05"]);
+		assert_eq!(values(&tag, &ItemKey::OriginalReleaseDate), ["2004-05"]);
+		assert_eq!(values(&tag, &ItemKey::Year), ["2004-05-06"]);
+		// Not a date
+		assert_eq!(values(&tag, &ItemKey::Comment), ["2004-00-00"]);
+	}
+
+	#[test_log::test]
+	fn remove_duplicates() {
+		let mut tag = Tag::new(TagType::VorbisComments);
+		tag.push_unchecked(text_item(ItemKey::Genre, "Rock"));
+		tag.push_unchecked(text_item(ItemKey::Genre, "Pop"));
+		tag.push_unchecked(text_item(ItemKey::Genre, "Rock"));
+		tag.push_unchecked(text_item(ItemKey::Comment, "Rock"));
+
+		let mut described = text_item(ItemKey::Comment, "Rock");
+		described.set_description(String::from("Foo"));
+		tag.push_unchecked(described);
+
+		tag.normalize(only(NormalizeOptions::remove_duplicates));
+
+		assert_eq!(values(&tag, &ItemKey::Genre), ["Rock", "Pop"]);
+		assert_eq!(values(&tag, &ItemKey::Comment), ["Rock", "Rock"]);
+	}
+
+	#[test_log::test]
+	fn binary_values_and_pictures_untouched() {
+		let picture = Picture::new_unchecked(
+			PictureType::CoverFront,
+			Some(MimeType::Png),
+			Some(String::from("  Front cover  ")),
+			vec![1, 2, 3],
+		);
+
+		let mut tag = Tag::new(TagType::Ape);
+		tag.push_picture(picture.clone());
+		for _ in 0..2 {
+			tag.push_unchecked(TagItem::new(
+				ItemKey::Unknown(String::from("Foo")),
+				ItemValue::Binary(Vec::new()),
+			));
+		}
+
+		tag.normalize(NormalizeOptions::new());
+
+		assert_eq!(tag.pictures(), [picture]);
+		assert_eq!(tag.item_count(), 2);
+	}
+
+	#[test_log::test]
+	fn normalize_after_split() {
+		let mut id3v2 = Id3v2Tag::new();
+		id3v2.set_title(String::from(" Foo title "));
+		id3v2.set_comment(String::from(" "));
+		id3v2.insert(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TDRC")),
+			TextEncoding::UTF8,
+			String::from("2004-00-00"),
+		)));
+
+		let (remainder, mut tag) = id3v2.split_tag();
+		tag.normalize(NormalizeOptions::new());
+		let id3v2 = remainder.merge_tag(tag);
+
+		assert_eq!(id3v2.title().as_deref(), Some("Foo title"));
+		assert_eq!(id3v2.comment(), None);
+		assert_eq!(id3v2.year(), Some(2004));
+	}
+}
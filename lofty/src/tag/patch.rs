@@ -0,0 +1,379 @@
+use super::{ItemKey, ItemValue, Tag, TagItem, TagType};
+use crate::config::{ParseOptions, WriteOptions};
+use crate::error::{LoftyError, Result};
+use crate::file::write_plan::{self, PlannedTag};
+use crate::macros::err;
+use crate::picture::{Picture, PictureType};
+use crate::probe::Probe;
+use crate::util::io::{FileLike, Length, Truncate};
+use crate::util::tag_locations;
+
+use std::fs::OpenOptions;
+use std::path::Path;
+
+/// A single change to make to a tag, see [`TagPatch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatchOperation {
+	/// Insert an item, replacing any existing items with the same key
+	///
+	/// See [`Tag::insert`].
+	SetItem(ItemKey, ItemValue),
+	/// Remove all items with a key
+	RemoveItem(ItemKey),
+	/// Append a picture
+	AddPicture(Picture),
+	/// Remove all pictures of a type
+	RemovePictureType(PictureType),
+	/// Remove all items and pictures from the tag of a type
+	///
+	/// This applies to any tag in the file, not just the one being patched. Tags left empty are
+	/// removed from the file.
+	ClearTag(TagType),
+}
+
+/// A change made by [`TagPatch::apply_to`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatchChange {
+	/// An item was set
+	ItemSet(ItemKey),
+	/// Items with this key were removed
+	ItemRemoved(ItemKey),
+	/// A picture of this type was added
+	PictureAdded(PictureType),
+	/// Pictures of this type were removed
+	PicturesRemoved(PictureType),
+	/// A tag was cleared
+	TagCleared(TagType),
+}
+
+/// A set of changes to apply to a file's tag in a single write
+///
+/// The operations are applied in order to the tag of [`TagPatch::tag_type`], or the file's
+/// [primary tag](crate::file::TaggedFileExt::primary_tag_type) if none is set. The tag is created
+/// if the file doesn't have one.
+///
+/// The operations are applied to the tags as they're stored in the file, so anything a [`Tag`]
+/// can't represent (e.g. unmapped ID3v2 frames or MP4 freeform atoms) is kept. Only the tags that
+/// actually changed are written, all at once. If nothing changed, the file is left untouched.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lofty::picture::PictureType;
+/// use lofty::tag::{ItemKey, ItemValue, PatchOperation, TagPatch};
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// let patch = TagPatch::new()
+/// 	.operation(PatchOperation::SetItem(
+/// 		ItemKey::TrackTitle,
+/// 		ItemValue::Text(String::from("Foo title")),
+/// 	))
+/// 	.operation(PatchOperation::RemoveItem(ItemKey::Comment))
+/// 	.operation(PatchOperation::RemovePictureType(PictureType::CoverBack));
+///
+/// let summary = lofty::apply_patch(path, &patch)?;
+/// println!("Changed the {:?} tag: {:?}", summary.tag_type(), summary.changes());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagPatch {
+	tag_type: Option<TagType>,
+	operations: Vec<PatchOperation>,
+	parse_options: ParseOptions,
+	write_options: WriteOptions,
+}
+
+impl TagPatch {
+	/// Create a new, empty `TagPatch`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::TagPatch;
+	///
+	/// let patch = TagPatch::new();
+	/// assert!(patch.operations().is_empty());
+	/// ```
+	pub fn new() -> Self {
+		Self {
+			tag_type: None,
+			operations: Vec::new(),
+			parse_options: ParseOptions::new().read_properties(false),
+			write_options: WriteOptions::new(),
+		}
+	}
+
+	/// The [`TagType`] to patch, rather than the file's primary tag
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{TagPatch, TagType};
+	///
+	/// let patch = TagPatch::new().tag_type(TagType::Ape);
+	/// ```
+	pub fn tag_type(mut self, tag_type: TagType) -> Self {
+		self.tag_type = Some(tag_type);
+		self
+	}
+
+	/// Append an operation to the patch
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::tag::{ItemKey, PatchOperation, TagPatch};
+	///
+	/// let patch = TagPatch::new().operation(PatchOperation::RemoveItem(ItemKey::Comment));
+	/// assert_eq!(patch.operations().len(), 1);
+	/// ```
+	pub fn operation(mut self, operation: PatchOperation) -> Self {
+		self.operations.push(operation);
+		self
+	}
+
+	/// The [`ParseOptions`] used to read the file
+	///
+	/// By default, this is [`ParseOptions::new`], without reading the properties.
+	pub fn parse_options(mut self, parse_options: ParseOptions) -> Self {
+		self.parse_options = parse_options;
+		self
+	}
+
+	/// The [`WriteOptions`] used to write the file
+	pub fn write_options(mut self, write_options: WriteOptions) -> Self {
+		self.write_options = write_options;
+		self
+	}
+
+	/// The operations of the patch, in the order they are applied
+	pub fn operations(&self) -> &[PatchOperation] {
+		&self.operations
+	}
+
+	/// Apply the patch to a file
+	///
+	/// The file type is guessed from the content of `file`.
+	///
+	/// # Errors
+	///
+	/// * The file can't be read, see [`Probe::read`]
+	/// * The file is an ASF or [custom](crate::file::FileType::Custom) file, which can't be patched
+	/// * The file doesn't support the [`TagType`] to patch
+	/// * The file can't be written, see [`AudioFile::save_to`](crate::file::AudioFile::save_to)
+	///
+	/// No changes are written if an error occurs before writing.
+	pub fn apply_to<F>(&self, file: &mut F) -> Result<PatchSummary>
+	where
+		F: FileLike,
+		LoftyError: From<<F as Truncate>::Error>,
+		LoftyError: From<<F as Length>::Error>,
+	{
+		let probe = Probe::new(&mut *file)
+			.options(self.parse_options)
+			.guess_file_type()?;
+		let Some(file_type) = probe.file_type() else {
+			err!(UnknownFormat);
+		};
+
+		// The tags are patched as they're stored, so nothing is lost to the conversion to a `Tag`
+		let (tags, tag_locations) = tag_locations::collect(|| {
+			write_plan::read_tags(probe.into_inner(), file_type, self.parse_options)
+		});
+		let mut tags = tags?;
+
+		let tag_type = self
+			.tag_type
+			.unwrap_or_else(|| file_type.primary_tag_type());
+		if !file_type.supports_tag_type(tag_type) {
+			err!(UnsupportedTag);
+		}
+
+		let created_tag = !tags.iter().any(|tag| tag.tag_type() == tag_type);
+		if created_tag {
+			tags.push(PlannedTag::empty(tag_type)?);
+		}
+
+		let mut summary = PatchSummary {
+			tag_type,
+			created_tag,
+			changes: Vec::new(),
+			unsupported_items: Vec::new(),
+			written: false,
+		};
+
+		// Only the tags changed by the patch should be written
+		let mut changed = vec![false; tags.len()];
+		for operation in &self.operations {
+			if let PatchOperation::ClearTag(clear_type) = operation {
+				if let Some(pos) = tags.iter().position(|tag| tag.tag_type() == *clear_type) {
+					if !tags[pos].is_empty() {
+						tags[pos] = PlannedTag::empty(*clear_type)?;
+						changed[pos] = true;
+						summary.changes.push(PatchChange::TagCleared(*clear_type));
+					}
+				}
+
+				continue;
+			}
+
+			// Cleared tags are kept, so this always exists
+			if let Some(pos) = tags.iter().position(|tag| tag.tag_type() == tag_type) {
+				changed[pos] |= tags[pos].edit(|tag| {
+					tag.mark_clean();
+					apply_operation(tag, operation.clone(), &mut summary);
+					tag.is_dirty()
+				});
+			}
+		}
+
+		// A tag that was never populated doesn't need to be written
+		let changed_tags = tags
+			.into_iter()
+			.zip(changed)
+			.filter_map(|(tag, changed)| {
+				let unpopulated = tag.tag_type() == tag_type && created_tag && tag.is_empty();
+				(changed && !unpopulated).then_some(tag)
+			})
+			.collect::<Vec<_>>();
+		if changed_tags.is_empty() {
+			log::debug!("Patch made no changes, skipping the write");
+			return Ok(summary);
+		}
+
+		file.rewind()?;
+		write_plan::write_tags(
+			file,
+			file_type,
+			&tag_locations,
+			&changed_tags,
+			self.write_options,
+		)?;
+		summary.written = true;
+
+		Ok(summary)
+	}
+}
+
+fn apply_operation(tag: &mut Tag, operation: PatchOperation, summary: &mut PatchSummary) {
+	match operation {
+		PatchOperation::SetItem(key, value) => {
+			if tag.insert(TagItem::new(key.clone(), value)) {
+				summary.changes.push(PatchChange::ItemSet(key));
+			} else {
+				log::warn!(
+					"Unable to set {key:?}, not supported by {:?}",
+					tag.tag_type()
+				);
+				summary.unsupported_items.push(key);
+			}
+		},
+		PatchOperation::RemoveItem(key) => {
+			if tag.get(&key).is_some() {
+				tag.remove_key(&key);
+				summary.changes.push(PatchChange::ItemRemoved(key));
+			}
+		},
+		PatchOperation::AddPicture(picture) => {
+			let pic_type = picture.pic_type();
+			tag.push_picture(picture);
+			summary.changes.push(PatchChange::PictureAdded(pic_type));
+		},
+		PatchOperation::RemovePictureType(pic_type) => {
+			if tag.get_picture_type(pic_type).is_some() {
+				tag.remove_picture_type(pic_type);
+				summary.changes.push(PatchChange::PicturesRemoved(pic_type));
+			}
+		},
+		PatchOperation::ClearTag(_) => unreachable!("handled by the caller"),
+	}
+}
+
+impl Default for TagPatch {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A summary of the changes made by [`TagPatch::apply_to`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchSummary {
+	tag_type: TagType,
+	created_tag: bool,
+	changes: Vec<PatchChange>,
+	unsupported_items: Vec<ItemKey>,
+	written: bool,
+}
+
+impl PatchSummary {
+	/// The [`TagType`] that was patched
+	pub fn tag_type(&self) -> TagType {
+		self.tag_type
+	}
+
+	/// Whether the file didn't have a tag of [`PatchSummary::tag_type`]
+	///
+	/// If the patch didn't add anything to it, the tag was not written.
+	pub fn created_tag(&self) -> bool {
+		self.created_tag
+	}
+
+	/// The changes made, in the order of the operations
+	///
+	/// Operations that had no effect (e.g. removing an item that doesn't exist) are not included.
+	pub fn changes(&self) -> &[PatchChange] {
+		&self.changes
+	}
+
+	/// The keys of [`PatchOperation::SetItem`]s that the tag type doesn't support
+	///
+	/// These items were not set.
+	pub fn unsupported_items(&self) -> &[ItemKey] {
+		&self.unsupported_items
+	}
+
+	/// Whether the file was written
+	///
+	/// This is `false` if the tags already had the content of the patch.
+	pub fn written(&self) -> bool {
+		self.written
+	}
+}
+
+/// Apply a [`TagPatch`] to the file at `path`
+///
+/// See [`TagPatch::apply_to`].
+///
+/// # Errors
+///
+/// * `path` can't be opened for reading and writing
+/// * See [`TagPatch::apply_to`]
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lofty::tag::{ItemKey, ItemValue, PatchOperation, TagPatch, TagType};
+///
+/// # fn main() -> lofty::error::Result<()> {
+/// # let path = "tests/files/assets/minimal/full_test.mp3";
+/// let patch = TagPatch::new()
+/// 	.operation(PatchOperation::SetItem(
+/// 		ItemKey::TrackArtist,
+/// 		ItemValue::Text(String::from("Foo artist")),
+/// 	))
+/// 	.operation(PatchOperation::ClearTag(TagType::Id3v1));
+///
+/// let summary = lofty::apply_patch(path, &patch)?;
+/// assert!(summary.written());
+/// # Ok(()) }
+/// ```
+pub fn apply_patch<P>(path: P, patch: &TagPatch) -> Result<PatchSummary>
+where
+	P: AsRef<Path>,
+{
+	let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+	patch.apply_to(&mut file)
+}
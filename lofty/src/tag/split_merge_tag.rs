@@ -85,6 +85,9 @@ mod private {
 	impl Sealed for ApeTag {}
 	impl Sealed for crate::ape::tag::SplitTagRemainder {}
 
+	impl Sealed for crate::asf::AsfTag {}
+	impl Sealed for crate::asf::tag::SplitTagRemainder {}
+
 	impl Sealed for Id3v1Tag {}
 	impl Sealed for crate::id3::v1::tag::SplitTagRemainder {}
 
@@ -2,6 +2,7 @@ use crate::config::WriteOptions;
 use crate::error::LoftyError;
 use crate::io::{FileLike, Length, Truncate};
 use crate::tag::{Accessor, Tag, TagType};
+use crate::util::io::ByteCounter;
 
 use std::path::Path;
 
@@ -119,6 +120,41 @@ pub trait TagExt: Accessor + Into<Tag> + Sized + private::Sealed {
 		write_options: WriteOptions,
 	) -> std::result::Result<(), Self::Err>;
 
+	/// Get the size of the tag, as it would be written by [`TagExt::dump_to`]
+	///
+	/// This doesn't keep the serialized tag in memory, making it useful for checking whether a tag
+	/// will fit in existing padding before saving.
+	///
+	/// # Errors
+	///
+	/// See [`TagExt::dump_to`]
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::config::WriteOptions;
+	/// use lofty::id3::v2::Id3v2Tag;
+	/// use lofty::tag::{Accessor, TagExt};
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let mut tag = Id3v2Tag::new();
+	/// tag.set_artist(String::from("Foo artist"));
+	///
+	/// let write_options = WriteOptions::default();
+	///
+	/// let mut dumped = Vec::new();
+	/// tag.dump_to(&mut dumped, write_options)?;
+	///
+	/// assert_eq!(tag.serialized_len(write_options)?, dumped.len() as u64);
+	/// # Ok(()) }
+	/// ```
+	fn serialized_len(&self, write_options: WriteOptions) -> std::result::Result<u64, Self::Err> {
+		let mut counter = ByteCounter::default();
+		self.dump_to(&mut counter, write_options)?;
+
+		Ok(counter.0)
+	}
+
 	/// Remove a tag from a [`Path`]
 	///
 	/// # Errors
@@ -165,6 +201,7 @@ mod private {
 
 	impl Sealed for AiffTextChunks {}
 	impl Sealed for ApeTag {}
+	impl Sealed for crate::asf::AsfTag {}
 	impl Sealed for Id3v1Tag {}
 	impl Sealed for Id3v2Tag {}
 	impl Sealed for Ilst {}
@@ -10,7 +10,8 @@ use std::fs::OpenOptions;
 use std::path::Path;
 
 /// The tag's format
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum TagType {
 	/// This covers both APEv1 and APEv2 as it doesn't matter much
@@ -27,9 +28,87 @@ pub enum TagType {
 	RiffInfo,
 	/// Represents AIFF text chunks
 	AiffText,
+	/// Represents ASF attributes
+	Asf,
 }
 
 impl TagType {
+	/// All tag types, in the order they are declared
+	pub(crate) const ALL: [TagType; 8] = [
+		TagType::Ape,
+		TagType::Id3v1,
+		TagType::Id3v2,
+		TagType::Mp4Ilst,
+		TagType::VorbisComments,
+		TagType::RiffInfo,
+		TagType::AiffText,
+		TagType::Asf,
+	];
+
+	/// Returns if the tag type can be written to a [`FileType`]
+	///
+	/// Some file types can only have a tag read or removed, such as [`TagType::Id3v2`] in [`FileType::Flac`].
+	/// Use [`FileType::supports_tag_type`] to check if a tag can be read.
+	///
+	/// # Panics
+	///
+	/// If an unregistered `FileType` ([`FileType::Custom`]) is encountered. See [`register_custom_resolver`](crate::resolve::register_custom_resolver).
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use lofty::file::FileType;
+	/// use lofty::tag::TagType;
+	///
+	/// assert!(TagType::Id3v2.supports_write_for(FileType::Mpeg));
+	///
+	/// // ID3v2 tags in FLAC files can be read, but not written
+	/// assert!(FileType::Flac.supports_tag_type(TagType::Id3v2));
+	/// assert!(!TagType::Id3v2.supports_write_for(FileType::Flac));
+	/// ```
+	pub fn supports_write_for(&self, file_type: FileType) -> bool {
+		if let FileType::Custom(c) = file_type {
+			let resolver = crate::resolve::lookup_resolver(c);
+			return resolver.supported_tag_types().contains(self);
+		}
+
+		self.writable_formats().contains(&file_type)
+	}
+
+	// Tags can be removed from read only formats as well (e.g. ID3v2 in FLAC), as long as the file
+	// type can be written at all
+	pub(crate) fn supports_removal_from(self, file_type: FileType) -> bool {
+		file_type.supports_tag_type(self) && !file_type.writable_tag_types().is_empty()
+	}
+
+	// The file types that this tag type can be read from
+	pub(crate) fn supported_formats(self) -> &'static [FileType] {
+		match self {
+			TagType::Ape => crate::ape::ApeTag::SUPPORTED_FORMATS,
+			TagType::Id3v1 => crate::id3::v1::Id3v1Tag::SUPPORTED_FORMATS,
+			TagType::Id3v2 => crate::id3::v2::Id3v2Tag::SUPPORTED_FORMATS,
+			TagType::Mp4Ilst => crate::mp4::Ilst::SUPPORTED_FORMATS,
+			TagType::VorbisComments => crate::ogg::VorbisComments::SUPPORTED_FORMATS,
+			TagType::RiffInfo => crate::iff::wav::RiffInfoList::SUPPORTED_FORMATS,
+			TagType::AiffText => crate::iff::aiff::AiffTextChunks::SUPPORTED_FORMATS,
+			TagType::Asf => crate::asf::AsfTag::SUPPORTED_FORMATS,
+		}
+	}
+
+	// The file types that this tag type can be written to, a subset of `supported_formats`
+	pub(crate) fn writable_formats(self) -> &'static [FileType] {
+		match self {
+			TagType::Ape => crate::ape::ApeTag::WRITABLE_FORMATS,
+			TagType::Id3v1 => crate::id3::v1::Id3v1Tag::WRITABLE_FORMATS,
+			TagType::Id3v2 => crate::id3::v2::Id3v2Tag::WRITABLE_FORMATS,
+			TagType::Mp4Ilst => crate::mp4::Ilst::WRITABLE_FORMATS,
+			TagType::VorbisComments => crate::ogg::VorbisComments::WRITABLE_FORMATS,
+			TagType::RiffInfo => crate::iff::wav::RiffInfoList::WRITABLE_FORMATS,
+			TagType::AiffText => crate::iff::aiff::AiffTextChunks::WRITABLE_FORMATS,
+			TagType::Asf => crate::asf::AsfTag::WRITABLE_FORMATS,
+		}
+	}
+
 	/// Remove a tag from a [`Path`]
 	///
 	/// # Errors
@@ -59,14 +138,8 @@ impl TagType {
 			err!(UnknownFormat);
 		};
 
-		// TODO: This should not have to be manually updated
-		let special_exceptions = ((file_type == FileType::Ape
-			|| file_type == FileType::Mpc
-			|| file_type == FileType::Flac)
-			&& *self == TagType::Id3v2)
-			|| file_type == FileType::Mpc && *self == TagType::Id3v1;
-
-		if !special_exceptions && !file_type.supports_tag_type(*self) {
+		// Tags can be removed from read only formats as well
+		if !file_type.supports_tag_type(*self) {
 			err!(UnsupportedTag);
 		}
 
@@ -4,7 +4,7 @@ use crate::file::FileType;
 use crate::macros::err;
 use crate::tag::{Tag, TagType};
 use crate::util::io::{FileLike, Length, Truncate};
-use crate::{aac, ape, flac, iff, mpeg, musepack, wavpack};
+use crate::{aac, ape, dff, dsf, flac, iff, mpeg, musepack, shn, tta, wavpack};
 
 use crate::id3::v1::tag::Id3v1TagRef;
 use crate::id3::v2::tag::Id3v2TagRef;
@@ -34,8 +34,10 @@ where
 		FileType::Aac => aac::write::write_to(file, tag, write_options),
 		FileType::Aiff => iff::aiff::write::write_to(file, tag, write_options),
 		FileType::Ape => ape::write::write_to(file, tag, write_options),
+		FileType::Dff => dff::write::write_to(file, tag, write_options),
+		FileType::Dsf => dsf::write::write_to(file, tag, write_options),
 		FileType::Flac => flac::write::write_to(file, tag, write_options),
-		FileType::Opus | FileType::Speex | FileType::Vorbis => {
+		FileType::OggFlac | FileType::Opus | FileType::Speex | FileType::Vorbis => {
 			crate::ogg::write::write_to(file, tag, file_type, write_options)
 		},
 		FileType::Mpc => musepack::write::write_to(file, tag, write_options),
@@ -45,6 +47,8 @@ where
 			&mut Into::<Ilst>::into(tag.clone()).as_ref(),
 			write_options,
 		),
+		FileType::Shn => shn::write::write_to(file, tag, write_options),
+		FileType::Tta => tta::write::write_to(file, tag, write_options),
 		FileType::Wav => iff::wav::write::write_to(file, tag, write_options),
 		FileType::WavPack => wavpack::write::write_to(file, tag, write_options),
 		_ => err!(UnsupportedTag),
@@ -75,12 +79,13 @@ pub(crate) fn dump_tag<W: Write>(
 		TagType::VorbisComments => {
 			let (vendor, items, pictures) = create_vorbis_comments_ref(tag);
 
-			VorbisCommentsRef {
+			let mut comments_ref = VorbisCommentsRef {
 				vendor: Cow::from(vendor),
-				items,
+				items: items.iter().map(|(k, v)| (&**k, *v)),
 				pictures,
-			}
-			.dump_to(writer, write_options)
+			};
+
+			comments_ref.dump_to(writer, write_options)
 		},
 		TagType::RiffInfo => RIFFInfoListRef {
 			items: iff::wav::tag::tagitems_into_riff(tag.items()),
@@ -107,7 +112,7 @@ pub(crate) fn dump_tag<W: Write>(
 pub(crate) mod test_utils {
 	use crate::tag::{ItemKey, Tag, TagType};
 	use std::fs::File;
-	use std::io::Read;
+	use std::io::{Read, Seek, SeekFrom};
 
 	pub(crate) fn create_tag(tag_type: TagType) -> Tag {
 		let mut tag = Tag::new(tag_type);
@@ -148,4 +153,33 @@ pub(crate) mod test_utils {
 
 		tag
 	}
+
+	/// A reader that keeps track of how many bytes have been read from it
+	pub(crate) struct CountingReader<R> {
+		pub(crate) inner: R,
+		pub(crate) bytes_read: u64,
+	}
+
+	impl<R> CountingReader<R> {
+		pub(crate) fn new(inner: R) -> Self {
+			Self {
+				inner,
+				bytes_read: 0,
+			}
+		}
+	}
+
+	impl<R: Read> Read for CountingReader<R> {
+		fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+			let read = self.inner.read(buf)?;
+			self.bytes_read += read as u64;
+			Ok(read)
+		}
+	}
+
+	impl<R: Seek> Seek for CountingReader<R> {
+		fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+			self.inner.seek(pos)
+		}
+	}
 }
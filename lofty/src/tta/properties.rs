@@ -0,0 +1,141 @@
+use crate::config::ParsingMode;
+use crate::error::Result;
+use crate::macros::decode_err;
+use crate::properties::FileProperties;
+
+use std::io::Read;
+use std::time::Duration;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+/// A TTA file's audio properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct TtaProperties {
+	pub(crate) duration: Duration,
+	pub(crate) overall_bitrate: u32,
+	pub(crate) audio_bitrate: u32,
+	pub(crate) sample_rate: u32,
+	pub(crate) bit_depth: u8,
+	pub(crate) channels: u8,
+	pub(crate) total_samples: u32,
+}
+
+impl From<TtaProperties> for FileProperties {
+	fn from(input: TtaProperties) -> Self {
+		Self {
+			duration: input.duration,
+			overall_bitrate: Some(input.overall_bitrate),
+			audio_bitrate: Some(input.audio_bitrate),
+			sample_rate: Some(input.sample_rate),
+			bit_depth: Some(input.bit_depth),
+			channels: Some(input.channels),
+			channel_mask: None,
+		}
+	}
+}
+
+impl TtaProperties {
+	/// Duration of the audio
+	pub fn duration(&self) -> Duration {
+		self.duration
+	}
+
+	/// Overall bitrate (kbps)
+	pub fn overall_bitrate(&self) -> u32 {
+		self.overall_bitrate
+	}
+
+	/// Audio bitrate (kbps)
+	pub fn audio_bitrate(&self) -> u32 {
+		self.audio_bitrate
+	}
+
+	/// Sample rate (Hz)
+	pub fn sample_rate(&self) -> u32 {
+		self.sample_rate
+	}
+
+	/// Bits per sample
+	pub fn bit_depth(&self) -> u8 {
+		self.bit_depth
+	}
+
+	/// Channel count
+	pub fn channels(&self) -> u8 {
+		self.channels
+	}
+
+	/// Total number of samples (per channel)
+	pub fn total_samples(&self) -> u32 {
+		self.total_samples
+	}
+}
+
+// The header is 22 bytes long, all little endian:
+//
+// Signature ("TTA1") (4)
+// Audio format (2)
+// Channels (2)
+// Bits per sample (2)
+// Sample rate (4)
+// Total samples (4)
+// CRC32 of the preceding bytes (4)
+pub(super) fn read_properties<R>(
+	reader: &mut R,
+	stream_len: u64,
+	file_length: u64,
+	parse_mode: ParsingMode,
+) -> Result<TtaProperties>
+where
+	R: Read,
+{
+	let mut signature = [0; 4];
+	reader.read_exact(&mut signature)?;
+	if &signature != b"TTA1" {
+		decode_err!(@BAIL Tta, "File has an invalid signature");
+	}
+
+	let _audio_format = reader.read_u16::<LittleEndian>()?;
+	let channels = reader.read_u16::<LittleEndian>()?;
+	let bit_depth = reader.read_u16::<LittleEndian>()?;
+	let sample_rate = reader.read_u32::<LittleEndian>()?;
+	let total_samples = reader.read_u32::<LittleEndian>()?;
+
+	let mut properties = TtaProperties {
+		sample_rate,
+		bit_depth: bit_depth as u8,
+		channels: channels as u8,
+		total_samples,
+		..TtaProperties::default()
+	};
+
+	match verify(channels, sample_rate) {
+		Err(e) if parse_mode == ParsingMode::Strict => return Err(e),
+		Err(_) => return Ok(properties),
+		_ => {},
+	}
+
+	let length = (f64::from(total_samples) * 1000.0) / f64::from(sample_rate);
+	properties.duration = Duration::from_millis((length + 0.5) as u64);
+
+	if length > 0.0 {
+		properties.audio_bitrate = ((stream_len as f64) * 8.0 / length + 0.5) as u32;
+		properties.overall_bitrate = ((file_length as f64) * 8.0 / length + 0.5) as u32;
+	}
+
+	Ok(properties)
+}
+
+fn verify(channels: u16, sample_rate: u32) -> Result<()> {
+	if channels == 0 || channels > u16::from(u8::MAX) {
+		decode_err!(@BAIL Tta, "File has an invalid channel count");
+	}
+
+	if sample_rate == 0 {
+		decode_err!(@BAIL Tta, "File has a sample rate of 0");
+	}
+
+	Ok(())
+}
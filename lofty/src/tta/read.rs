@@ -0,0 +1,81 @@
+use super::properties;
+use super::TtaFile;
+use crate::config::ParseOptions;
+use crate::error::Result;
+use crate::id3::{find_id3v1, find_id3v2, find_lyrics3v2, FindId3v2Config, ID3FindResults};
+use crate::macros::err;
+use crate::util::io::SeekStreamLen;
+
+use std::io::{Read, Seek, SeekFrom};
+
+pub(super) fn read_from<R>(reader: &mut R, parse_options: ParseOptions) -> Result<TtaFile>
+where
+	R: Read + Seek,
+{
+	log::debug!("Attempting to read TTA file");
+
+	let mut file = TtaFile::default();
+
+	let file_length = reader.stream_len_hack()?;
+	let mut stream_length = file_length;
+
+	let find_id3v2_config = if parse_options.read_tags {
+		FindId3v2Config::READ_TAG
+	} else {
+		FindId3v2Config::NO_READ_TAG
+	};
+
+	let id3v2_tags = find_id3v2(reader, find_id3v2_config)?;
+	if !id3v2_tags.is_empty() {
+		let Some(new_stream_length) = stream_length.checked_sub(id3v2_tags.size()) else {
+			err!(SizeMismatch);
+		};
+
+		stream_length = new_stream_length;
+		file.id3v2_tag = id3v2_tags.parse(parse_options)?;
+	}
+
+	// The TTA header immediately follows the ID3v2 tag
+	let pos_past_id3v2 = reader.stream_position()?;
+
+	let ID3FindResults(header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
+	if let Some(id3v1_size) = header {
+		file.id3v1_tag = id3v1;
+		let Some(new_stream_length) = stream_length.checked_sub(id3v1_size) else {
+			err!(SizeMismatch);
+		};
+
+		stream_length = new_stream_length;
+	}
+
+	let ID3FindResults(_, lyrics3v2_size) = find_lyrics3v2(reader)?;
+	let Some(new_stream_length) = stream_length.checked_sub(u64::from(lyrics3v2_size)) else {
+		err!(SizeMismatch);
+	};
+
+	stream_length = new_stream_length;
+
+	reader.seek(SeekFrom::Current(-32))?;
+
+	if let (tag, Some(header)) = crate::ape::tag::read::read_ape_tag(reader, true, parse_options)? {
+		file.ape_tag = tag;
+
+		let Some(new_stream_length) = stream_length.checked_sub(u64::from(header.size)) else {
+			err!(SizeMismatch);
+		};
+
+		stream_length = new_stream_length;
+	}
+
+	if parse_options.read_properties {
+		reader.seek(SeekFrom::Start(pos_past_id3v2))?;
+		file.properties = properties::read_properties(
+			reader,
+			stream_length,
+			file_length,
+			parse_options.parsing_mode,
+		)?;
+	}
+
+	Ok(file)
+}
@@ -0,0 +1,214 @@
+//! Support for reading from async sources
+//!
+//! None of the parsers are async. Instead, the data they need is fetched ahead of time into a
+//! [`PrefetchReader`], which the synchronous parsers then read from. If a parser reaches data
+//! that hasn't been fetched yet, the read fails, the missing range is fetched, and the parser is
+//! run again.
+//!
+//! Since most of the metadata in a file is located at its start or end, only a few attempts are
+//! usually needed.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+/// The amount of data to fetch from the start and end of the source up front
+const INITIAL_FETCH_SIZE: u64 = 64 * 1024;
+
+/// The minimum amount of data to fetch when a parser reaches unfetched data
+const MIN_FETCH_SIZE: u64 = 64 * 1024;
+
+/// A [`Read`] + [`Seek`] view of the fetched parts of an async source
+pub(crate) struct PrefetchReader {
+	/// Fetched data, keyed by the offset of its first byte
+	chunks: BTreeMap<u64, Vec<u8>>,
+	/// The total number of bytes fetched
+	fetched: u64,
+	/// The first range a parser attempted to read that hasn't been fetched yet
+	missing: Option<(u64, u64)>,
+	len: u64,
+	pos: u64,
+}
+
+impl PrefetchReader {
+	fn new(len: u64) -> Self {
+		Self {
+			chunks: BTreeMap::new(),
+			fetched: 0,
+			missing: None,
+			len,
+			pos: 0,
+		}
+	}
+
+	/// Fetch `len` bytes from `source`, starting at `offset`
+	///
+	/// The offset is relative to `base`, the position of the source when reading started.
+	async fn fetch<R>(
+		&mut self,
+		source: &mut R,
+		base: u64,
+		offset: u64,
+		len: u64,
+	) -> std::io::Result<()>
+	where
+		R: AsyncRead + AsyncSeek + Unpin,
+	{
+		let start = offset.min(self.len);
+		let end = offset.saturating_add(len).min(self.len);
+		if start == end {
+			return Ok(());
+		}
+
+		log::trace!("Fetching {} bytes at offset {start}", end - start);
+
+		source.seek(SeekFrom::Start(base + start)).await?;
+
+		let mut chunk = vec![0; (end - start) as usize];
+		source.read_exact(&mut chunk).await?;
+
+		self.fetched += end - start;
+		self.chunks.insert(start, chunk);
+
+		Ok(())
+	}
+
+	/// Find the fetched data at the current position
+	fn current_chunk(&self) -> Option<&[u8]> {
+		self.chunks
+			.range(..=self.pos)
+			.rev()
+			.find_map(|(start, chunk)| {
+				let offset = (self.pos - start) as usize;
+				(offset < chunk.len()).then(|| &chunk[offset..])
+			})
+	}
+}
+
+impl Read for PrefetchReader {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if self.pos >= self.len || buf.is_empty() {
+			return Ok(0);
+		}
+
+		let Some(chunk) = self.current_chunk() else {
+			// Only the first miss is recorded, since any later reads may depend on the missing data
+			if self.missing.is_none() {
+				self.missing = Some((self.pos, buf.len() as u64));
+			}
+
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::WouldBlock,
+				"data has not been fetched yet",
+			));
+		};
+
+		let read = chunk.len().min(buf.len());
+		buf[..read].copy_from_slice(&chunk[..read]);
+
+		self.pos += read as u64;
+		Ok(read)
+	}
+}
+
+impl Seek for PrefetchReader {
+	fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+		let new_pos = match pos {
+			SeekFrom::Start(pos) => Some(pos),
+			SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+			SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+		};
+
+		let Some(new_pos) = new_pos else {
+			return Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				"invalid seek to a negative or overflowing position",
+			));
+		};
+
+		self.pos = new_pos;
+		Ok(new_pos)
+	}
+}
+
+/// Run `parse` over the data of `source`, starting at its current position
+///
+/// `parse` may be called multiple times, and should have no side effects. Once it succeeds,
+/// `source` will be at the position `parse` stopped reading at.
+pub(crate) async fn read_from_async<R, T, E, F>(source: &mut R, mut parse: F) -> Result<T, E>
+where
+	R: AsyncRead + AsyncSeek + Unpin,
+	E: From<std::io::Error>,
+	F: FnMut(&mut PrefetchReader) -> Result<T, E>,
+{
+	let base = source.stream_position().await?;
+	let len = source.seek(SeekFrom::End(0)).await?.saturating_sub(base);
+
+	let mut reader = PrefetchReader::new(len);
+	reader.fetch(source, base, 0, INITIAL_FETCH_SIZE).await?;
+
+	let tail_start = len
+		.saturating_sub(INITIAL_FETCH_SIZE)
+		.max(INITIAL_FETCH_SIZE);
+	reader
+		.fetch(source, base, tail_start, INITIAL_FETCH_SIZE)
+		.await?;
+
+	loop {
+		reader.pos = 0;
+
+		let (offset, requested) = {
+			let result = parse(&mut reader);
+			match reader.missing.take() {
+				Some(missing) => missing,
+				None => {
+					// Leave the source where the parser left off, as it would be with a synchronous reader
+					source.seek(SeekFrom::Start(base + reader.pos)).await?;
+					return result;
+				},
+			}
+		};
+
+		// Fetch at least as much as has been fetched so far, so the number of attempts stays
+		// low for parsers that need to read through most of the file
+		let fetch_size = requested.max(MIN_FETCH_SIZE).max(reader.fetched);
+		reader.fetch(source, base, offset, fetch_size).await?;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::read_from_async;
+
+	use std::io::{Cursor, Read, Seek, SeekFrom};
+
+	#[test_log::test]
+	fn fetches_missing_ranges() {
+		let data = (0..=u8::MAX).cycle().take(1024 * 1024).collect::<Vec<u8>>();
+
+		let runtime = tokio::runtime::Builder::new_current_thread()
+			.build()
+			.unwrap();
+
+		let mut attempts = 0;
+		let content = runtime
+			.block_on(read_from_async(
+				&mut Cursor::new(&data),
+				|reader| -> std::io::Result<Vec<u8>> {
+					attempts += 1;
+
+					// Spans the initial fetch, and requires data from the middle of the source
+					reader.seek(SeekFrom::Start(60 * 1024))?;
+
+					let mut content = vec![0; 512 * 1024];
+					reader.read_exact(&mut content)?;
+					Ok(content)
+				},
+			))
+			.unwrap();
+
+		assert_eq!(content, data[60 * 1024..][..512 * 1024]);
+		assert_eq!(attempts, 2);
+	}
+}
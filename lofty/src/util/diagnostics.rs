@@ -0,0 +1,79 @@
+//! Collection of [`ParseDiagnostic`]s
+//!
+//! Diagnostics are collected per thread, for the duration of a [`collect`] call. Outside of one,
+//! reporting a diagnostic does nothing.
+
+use crate::error::ParseDiagnostic;
+
+use std::cell::RefCell;
+
+thread_local! {
+	static DIAGNOSTICS: RefCell<Option<Vec<ParseDiagnostic>>> = const { RefCell::new(None) };
+}
+
+/// Record a [`ParseDiagnostic`], if they are currently being collected
+pub(crate) fn report(diagnostic: ParseDiagnostic) {
+	DIAGNOSTICS.with_borrow_mut(|diagnostics| {
+		if let Some(diagnostics) = diagnostics {
+			diagnostics.push(diagnostic);
+		}
+	});
+}
+
+/// Restores the enclosing collection when dropped, even if `f` panics
+struct CollectGuard {
+	outer: Option<Vec<ParseDiagnostic>>,
+}
+
+impl Drop for CollectGuard {
+	fn drop(&mut self) {
+		DIAGNOSTICS.set(self.outer.take());
+	}
+}
+
+/// Run `f`, collecting any [`ParseDiagnostic`]s reported during it
+///
+/// Calls can be nested, in which case the inner call receives its own diagnostics, which are then
+/// passed on to the enclosing call.
+pub(crate) fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<ParseDiagnostic>) {
+	let guard = CollectGuard {
+		outer: DIAGNOSTICS.replace(Some(Vec::new())),
+	};
+
+	let ret = f();
+	let diagnostics = DIAGNOSTICS.take().unwrap_or_default();
+	drop(guard);
+
+	// The enclosing call sees the diagnostics as well
+	DIAGNOSTICS.with_borrow_mut(|outer| {
+		if let Some(outer) = outer {
+			outer.extend_from_slice(&diagnostics);
+		}
+	});
+
+	(ret, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{collect, report, DIAGNOSTICS};
+	use crate::error::ParseDiagnostic;
+
+	const DIAGNOSTIC: ParseDiagnostic = ParseDiagnostic::LegacyApeDiscItem { merged: false };
+
+	#[test_log::test]
+	fn nested() {
+		let ((_, inner), outer) = collect(|| collect(|| report(DIAGNOSTIC)));
+		assert_eq!(inner, [DIAGNOSTIC]);
+		assert_eq!(outer, [DIAGNOSTIC]);
+	}
+
+	#[test_log::test]
+	fn panic_safe() {
+		let result = std::panic::catch_unwind(|| collect(|| panic!("oh no")));
+		assert!(result.is_err());
+
+		// The collection from the panicking call shouldn't be left behind
+		assert!(DIAGNOSTICS.with_borrow(Option::is_none));
+	}
+}
@@ -1,11 +1,13 @@
 //! Various traits for reading and writing to file-like objects
 
-use crate::error::{LoftyError, Result};
+use crate::error::{ErrorKind, LoftyError, Result};
 use crate::util::math::F80;
 
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{Cursor, Read, Seek, Write};
+use std::fs::{File, OpenOptions};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 // TODO: https://github.com/rust-lang/rust/issues/59359
 pub(crate) trait SeekStreamLen: Seek {
@@ -230,6 +232,134 @@ where
 {
 }
 
+/// A writer that discards its input, only counting the number of bytes written
+///
+/// This is used to determine the size of a tag without keeping it in memory.
+#[derive(Default)]
+pub(crate) struct ByteCounter(pub(crate) u64);
+
+impl Write for ByteCounter {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.0 += buf.len() as u64;
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+/// Replace `range` of `file` with `replacement`, shifting the remainder of the file as needed
+///
+/// The remainder is copied in chunks, so the file is never fully loaded into memory.
+pub(crate) fn splice<F>(file: &mut F, range: Range<u64>, replacement: &[u8]) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+{
+	splice_all(file, &[(range, replacement.to_vec())])
+}
+
+/// Replace multiple ranges of `file` at once, see [`splice`]
+///
+/// The ranges refer to the file as it is before any of them are replaced. They must be sorted
+/// and must not overlap, though an empty range (an insertion) may start where another one ends.
+///
+/// Every byte outside of the ranges is moved at most once, and the file is truncated at most once.
+pub(crate) fn splice_all<F>(file: &mut F, edits: &[(Range<u64>, Vec<u8>)]) -> Result<()>
+where
+	F: FileLike,
+	LoftyError: From<<F as Truncate>::Error>,
+	LoftyError: From<<F as Length>::Error>,
+{
+	const CHUNK_SIZE: u64 = 64 * 1024;
+
+	let file_len = file.len()?;
+
+	// The parts of the file that are kept, and how far they move
+	let mut segments = Vec::with_capacity(edits.len() + 1);
+	let mut replacements = Vec::with_capacity(edits.len());
+	let mut kept_start = 0;
+	let mut shift = 0_i64;
+	for (range, replacement) in edits {
+		debug_assert!(range.start <= range.end && kept_start <= range.start);
+
+		let start = range.start.min(file_len);
+		let end = range.end.min(file_len);
+
+		segments.push((kept_start..start, shift));
+		replacements.push(((start as i64 + shift) as u64, replacement));
+
+		shift += replacement.len() as i64 - (end - start) as i64;
+		kept_start = end;
+	}
+	segments.push((kept_start..file_len, shift));
+
+	let new_len = (file_len as i64 + shift) as u64;
+
+	let largest_segment = segments
+		.iter()
+		.filter(|(_, shift)| *shift != 0)
+		.map(|(range, _)| range.end - range.start)
+		.max()
+		.unwrap_or(0);
+	let mut buf = vec![0; largest_segment.min(CHUNK_SIZE) as usize];
+
+	// A segment moving towards the start can only overwrite the segments before it, and one moving
+	// towards the end can only overwrite the segments after it. Moving them in that order ensures
+	// nothing is overwritten before it's copied.
+	for (range, shift) in segments.iter().filter(|(_, shift)| *shift < 0) {
+		let shift = shift.unsigned_abs();
+
+		let mut copied = 0;
+		let len = range.end - range.start;
+		while copied < len {
+			let chunk = &mut buf[..(len - copied).min(CHUNK_SIZE) as usize];
+			let src = range.start + copied;
+
+			file.seek(SeekFrom::Start(src))?;
+			file.read_exact(chunk)?;
+			file.seek(SeekFrom::Start(src - shift))?;
+			file.write_all(chunk)?;
+
+			copied += chunk.len() as u64;
+		}
+	}
+
+	for (range, shift) in segments.iter().rev().filter(|(_, shift)| *shift > 0) {
+		let shift = *shift as u64;
+
+		let mut remaining = range.end - range.start;
+		while remaining > 0 {
+			let chunk = &mut buf[..remaining.min(CHUNK_SIZE) as usize];
+			let src = range.start + remaining - chunk.len() as u64;
+
+			file.seek(SeekFrom::Start(src))?;
+			file.read_exact(chunk)?;
+			file.seek(SeekFrom::Start(src + shift))?;
+			file.write_all(chunk)?;
+
+			remaining -= chunk.len() as u64;
+		}
+	}
+
+	for (position, replacement) in replacements {
+		if replacement.is_empty() {
+			continue;
+		}
+
+		file.seek(SeekFrom::Start(position))?;
+		file.write_all(replacement)?;
+	}
+
+	if new_len < file_len {
+		file.truncate(new_len)?;
+	}
+
+	Ok(())
+}
+
 pub(crate) trait ReadExt: Read {
 	fn read_f80(&mut self) -> Result<F80>;
 }
@@ -246,6 +376,89 @@ where
 	}
 }
 
+/// Removes the temporary file of an atomic save, unless it was successfully renamed
+struct TempFileGuard {
+	path: PathBuf,
+	armed: bool,
+}
+
+impl Drop for TempFileGuard {
+	fn drop(&mut self) {
+		if self.armed {
+			let _ = std::fs::remove_file(&self.path);
+		}
+	}
+}
+
+/// Write to a copy of the file at `path`, replacing the original only once everything succeeded
+///
+/// The copy is a hidden sibling of the original, so that the final `rename` stays on the same
+/// file system. It is synced to disk before the rename, which means that a crash at any point
+/// leaves either the original or the fully written file behind, never something in between.
+///
+/// The permissions (and optionally the modification time) of the original are carried over.
+pub(crate) fn save_atomic<W>(path: &Path, preserve_modified_time: bool, write: W) -> Result<()>
+where
+	W: FnOnce(&mut File) -> Result<()>,
+{
+	let mut original = File::open(path)?;
+	let metadata = original.metadata()?;
+
+	let file_name = path
+		.file_name()
+		.ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+	let mut temp_name = std::ffi::OsString::from(".");
+	temp_name.push(file_name);
+	temp_name.push(format!(".lofty-{}.tmp", std::process::id()));
+
+	let mut guard = TempFileGuard {
+		path: path.with_file_name(temp_name),
+		armed: true,
+	};
+
+	let mut temp = OpenOptions::new()
+		.read(true)
+		.write(true)
+		.create_new(true)
+		.open(&guard.path)?;
+
+	std::io::copy(&mut original, &mut temp)?;
+	drop(original);
+	temp.seek(SeekFrom::Start(0))?;
+
+	write(&mut temp)?;
+
+	temp.set_permissions(metadata.permissions())?;
+	if preserve_modified_time {
+		temp.set_modified(metadata.modified()?)?;
+	}
+
+	temp.sync_all()?;
+	drop(temp);
+
+	if let Err(e) = std::fs::rename(&guard.path, path) {
+		return Err(LoftyError::new(ErrorKind::AtomicReplace(e)));
+	}
+	guard.armed = false;
+
+	// Make sure the rename itself is persisted. Not every platform allows opening a
+	// directory, so this is only done on a best-effort basis.
+	#[cfg(unix)]
+	if let Some(parent) = path.parent() {
+		let parent = if parent.as_os_str().is_empty() {
+			Path::new(".")
+		} else {
+			parent
+		};
+
+		if let Ok(dir) = File::open(parent) {
+			let _ = dir.sync_all();
+		}
+	}
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::config::{ParseOptions, WriteOptions};
@@ -368,4 +581,104 @@ mod tests {
 		let current_file_contents = f.buf;
 		assert_eq!(current_file_contents, test_asset_contents());
 	}
+
+	#[test_log::test]
+	fn save_atomic_failed_write_keeps_original() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("full_test.mp3");
+		std::fs::write(&path, test_asset_contents()).unwrap();
+
+		let result = super::save_atomic(&path, false, |file| {
+			// Get partway through before failing, so the copy really differs from the original
+			file.write_all(b"garbage")?;
+			Err(std::io::Error::other("failing writer").into())
+		});
+		assert!(result.is_err());
+
+		assert_eq!(std::fs::read(&path).unwrap(), test_asset_contents());
+
+		// The temporary file is cleaned up
+		let entries = std::fs::read_dir(dir.path()).unwrap().count();
+		assert_eq!(entries, 1);
+	}
+
+	#[test_log::test]
+	fn save_atomic() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("full_test.mp3");
+		std::fs::write(&path, test_asset_contents()).unwrap();
+
+		let mut file = file();
+		alter_tag(&mut file);
+
+		super::save_atomic(&path, false, |f| file.save_to(f, WriteOptions::default())).unwrap();
+
+		let mut reader = std::fs::File::open(&path).unwrap();
+		let updated_file = MpegFile::read_from(&mut reader, ParseOptions::new()).unwrap();
+		assert_eq!(
+			updated_file.id3v2().unwrap().artist().as_deref(),
+			Some("Bar artist")
+		);
+
+		let entries = std::fs::read_dir(dir.path()).unwrap().count();
+		assert_eq!(entries, 1);
+	}
+
+	#[test_log::test]
+	fn splice() {
+		// Larger than a single chunk, to cover copying the remainder in multiple steps
+		let original = (0..200_000u32).map(|i| i as u8).collect::<Vec<_>>();
+
+		for (range, replacement) in [
+			(10..20, vec![0xFF; 10]),
+			(10..20, vec![0xFF; 100_000]),
+			(10..100_010, vec![0xFF; 3]),
+			(200_000..200_000, vec![0xFF; 4]),
+			(0..200_000, Vec::new()),
+		] {
+			let mut expected = original.clone();
+			let _ = expected.splice(
+				range.start as usize..range.end as usize,
+				replacement.clone(),
+			);
+
+			let mut file = Cursor::new(original.clone());
+			super::splice(&mut file, range, &replacement).unwrap();
+			assert_eq!(file.into_inner(), expected);
+		}
+	}
+
+	#[test_log::test]
+	fn splice_all() {
+		let original = (0..200_000u32).map(|i| i as u8).collect::<Vec<_>>();
+
+		// Segments that move in both directions, in either order
+		for edits in [
+			vec![(0..10, Vec::new()), (150_000..150_000, vec![0xFF; 80_000])],
+			vec![(0..0, vec![0xFF; 80_000]), (100..70_000, Vec::new())],
+			vec![
+				(0..10, vec![0xFF; 3]),
+				(10..10, vec![0xEE; 100_000]),
+				(50_000..120_000, vec![0xDD; 4]),
+				(199_872..200_000, vec![0xCC; 128]),
+			],
+			vec![(4..8, vec![0xFF; 4]), (100_000..200_000, Vec::new())],
+			vec![
+				(199_000..200_000, Vec::new()),
+				(200_000..200_000, vec![0xFF; 2]),
+			],
+		] {
+			let mut expected = original.clone();
+			for (range, replacement) in edits.iter().rev() {
+				let _ = expected.splice(
+					range.start as usize..range.end as usize,
+					replacement.clone(),
+				);
+			}
+
+			let mut file = Cursor::new(original.clone());
+			super::splice_all(&mut file, &edits).unwrap();
+			assert_eq!(file.into_inner(), expected);
+		}
+	}
 }
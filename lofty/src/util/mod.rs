@@ -1,6 +1,14 @@
 pub(crate) mod alloc;
+#[cfg(feature = "async")]
+pub(crate) mod async_io;
+pub(crate) mod diagnostics;
 pub mod io;
 pub(crate) mod math;
+pub(crate) mod picture_sink;
+pub(crate) mod pretty_print;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_bytes;
+pub(crate) mod tag_locations;
 pub(crate) mod text;
 
 pub(crate) fn flag_item(item: &str) -> Option<bool> {
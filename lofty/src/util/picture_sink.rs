@@ -0,0 +1,80 @@
+//! Destination for skipped picture data
+//!
+//! When reading with [`PictureDataPolicy::Skip`](crate::config::PictureDataPolicy::Skip), readers hand
+//! the picture data to [`consume`] rather than reading it into memory. The data is discarded, unless
+//! a sink is installed for the current thread, for the duration of a [`with_sink`] call.
+
+use crate::error::Result;
+use crate::picture::Picture;
+
+use std::cell::RefCell;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+pub(crate) type Sink = Box<dyn FnMut(&Picture) -> Option<Box<dyn Write>>>;
+
+thread_local! {
+	static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, with `sink` receiving the data of any pictures skipped during it
+pub(crate) fn with_sink<T>(sink: Sink, f: impl FnOnce() -> T) -> T {
+	let outer = SINK.replace(Some(sink));
+	let ret = f();
+	SINK.set(outer);
+
+	ret
+}
+
+// The sink is taken out for the duration of the call, in case it reads another file
+fn writer_for(picture: &Picture) -> Option<Box<dyn Write>> {
+	let mut sink = SINK.take()?;
+	let writer = sink(picture);
+	SINK.set(Some(sink));
+
+	writer
+}
+
+/// Consume the skipped data of `picture` from `reader`
+///
+/// This expects the reader to be positioned at the start of the data, the length is taken
+/// from [`Picture::skipped_data_len`].
+pub(crate) fn consume<R>(picture: &Picture, reader: &mut R) -> Result<()>
+where
+	R: Read + ?Sized,
+{
+	let len = picture.skipped_data_len.unwrap_or_default();
+	match writer_for(picture) {
+		Some(mut writer) => copy_exact(reader, len, &mut writer),
+		None => copy_exact(reader, len, &mut std::io::sink()),
+	}
+}
+
+/// Same as [`consume`], seeking over the data if it is discarded
+pub(crate) fn consume_seekable<R>(picture: &Picture, reader: &mut R) -> Result<()>
+where
+	R: Read + Seek,
+{
+	let len = picture.skipped_data_len.unwrap_or_default();
+	match writer_for(picture) {
+		Some(mut writer) => copy_exact(reader, len, &mut writer),
+		None => {
+			reader.seek(SeekFrom::Current(len as i64))?;
+			Ok(())
+		},
+	}
+}
+
+fn copy_exact<R, W>(reader: &mut R, len: u64, writer: &mut W) -> Result<()>
+where
+	R: Read + ?Sized,
+	W: Write + ?Sized,
+{
+	let copied = std::io::copy(&mut reader.take(len), writer)?;
+	writer.flush()?;
+
+	if copied != len {
+		return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+	}
+
+	Ok(())
+}
@@ -0,0 +1,118 @@
+//! Shared implementation of the `pretty_print` methods
+//!
+//! See [`PrettyPrintOptions`] for a description of the format.
+
+use crate::config::PrettyPrintOptions;
+use crate::picture::Picture;
+use crate::tag::ItemValue;
+
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+pub(crate) enum Value<'a> {
+	Text(Cow<'a, str>),
+	Bytes(usize),
+	Picture(&'a Picture),
+}
+
+impl Value<'_> {
+	fn write(&self, writer: &mut impl Write, max_len: usize) -> fmt::Result {
+		match self {
+			Value::Text(text) => {
+				let mut chars = text.chars();
+				for c in chars.by_ref().take(max_len) {
+					write_escaped(writer, c)?;
+				}
+
+				if chars.next().is_some() {
+					writer.write_str("...")?;
+				}
+
+				Ok(())
+			},
+			Value::Bytes(len) => write!(writer, "{len} bytes"),
+			Value::Picture(picture) => {
+				write!(writer, "{:?}, ", picture.pic_type())?;
+				match picture.mime_type() {
+					Some(mime_type) => write!(writer, "{mime_type}, ")?,
+					None => writer.write_str("unknown, ")?,
+				}
+
+				let len = picture
+					.skipped_data_len()
+					.unwrap_or(picture.data().len() as u64);
+				write!(writer, "{len} bytes")
+			},
+		}
+	}
+}
+
+/// The type and value of an [`ItemValue`]
+pub(crate) fn item_value(value: &ItemValue) -> (&'static str, Value<'_>) {
+	match value {
+		ItemValue::Text(text) => ("Text", Value::Text(Cow::Borrowed(text))),
+		ItemValue::Locator(locator) => ("Locator", Value::Text(Cow::Borrowed(locator))),
+		ItemValue::Binary(binary) => ("Binary", Value::Bytes(binary.len())),
+	}
+}
+
+fn write_escaped(writer: &mut impl Write, c: char) -> fmt::Result {
+	match c {
+		'\\' => writer.write_str("\\\\"),
+		'\t' => writer.write_str("\\t"),
+		'\n' => writer.write_str("\\n"),
+		'\r' => writer.write_str("\\r"),
+		'\0' => writer.write_str("\\0"),
+		c if c.is_control() => write!(writer, "\\u{{{:x}}}", u32::from(c)),
+		c => writer.write_char(c),
+	}
+}
+
+/// Collects the lines of a tag, so they can be sorted before printing
+#[derive(Default)]
+pub(crate) struct PrettyPrinter<'a> {
+	lines: Vec<(Cow<'a, str>, Option<&'static str>, Value<'a>)>,
+}
+
+impl<'a> PrettyPrinter<'a> {
+	/// Add a `<key>\t<type>\t<value>` line
+	pub(crate) fn push(
+		&mut self,
+		key: impl Into<Cow<'a, str>>,
+		ty: &'static str,
+		value: Value<'a>,
+	) {
+		self.lines.push((key.into(), Some(ty), value));
+	}
+
+	/// Add a `<key>\t<value>` line
+	pub(crate) fn push_untyped(&mut self, key: impl Into<Cow<'a, str>>, value: Value<'a>) {
+		self.lines.push((key.into(), None, value));
+	}
+
+	pub(crate) fn finish(
+		mut self,
+		writer: &mut impl Write,
+		options: PrettyPrintOptions,
+	) -> fmt::Result {
+		if options.sort_keys {
+			self.lines.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+		}
+
+		for (key, ty, value) in self.lines {
+			for c in key.chars() {
+				write_escaped(writer, c)?;
+			}
+
+			if let Some(ty) = ty {
+				write!(writer, "\t{ty}")?;
+			}
+
+			writer.write_char('\t')?;
+			value.write(writer, options.max_value_len)?;
+			writer.write_char('\n')?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,69 @@
+//! Binary data for `serde`
+//!
+//! Human readable formats (e.g. JSON) store the data as a base64 string, rather than as an array
+//! of numbers. Other formats store it as bytes.
+
+use std::fmt::Formatter;
+
+use data_encoding::BASE64;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub(crate) fn serialize<T, S>(data: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: AsRef<[u8]>,
+	S: Serializer,
+{
+	if serializer.is_human_readable() {
+		serializer.serialize_str(&BASE64.encode(data.as_ref()))
+	} else {
+		serializer.serialize_bytes(data.as_ref())
+	}
+}
+
+pub(crate) fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: From<Vec<u8>>,
+	D: Deserializer<'de>,
+{
+	if deserializer.is_human_readable() {
+		let encoded = String::deserialize(deserializer)?;
+		return BASE64
+			.decode(encoded.as_bytes())
+			.map(T::from)
+			.map_err(D::Error::custom);
+	}
+
+	deserializer.deserialize_byte_buf(BytesVisitor).map(T::from)
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+	type Value = Vec<u8>;
+
+	fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.write_str("a byte array")
+	}
+
+	fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+		Ok(v.to_vec())
+	}
+
+	fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+		Ok(v)
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		// The size hint comes from the input, so it can't be trusted with large allocations
+		let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+		while let Some(byte) = seq.next_element()? {
+			data.push(byte);
+		}
+
+		Ok(data)
+	}
+}
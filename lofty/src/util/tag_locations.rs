@@ -0,0 +1,47 @@
+//! Collection of [`TagLocation`]s
+//!
+//! Locations are collected per thread, for the duration of a [`collect`] call. Outside of one,
+//! recording a location does nothing.
+
+use crate::tag::TagLocation;
+
+use std::cell::RefCell;
+
+thread_local! {
+	static TAG_LOCATIONS: RefCell<Option<Vec<TagLocation>>> = const { RefCell::new(None) };
+}
+
+/// Record a [`TagLocation`], if they are currently being collected
+pub(crate) fn record(location: TagLocation) {
+	TAG_LOCATIONS.with_borrow_mut(|locations| {
+		if let Some(locations) = locations {
+			locations.push(location);
+		}
+	});
+}
+
+/// Restores the enclosing collection when dropped, even if `f` panics
+struct CollectGuard {
+	outer: Option<Vec<TagLocation>>,
+}
+
+impl Drop for CollectGuard {
+	fn drop(&mut self) {
+		TAG_LOCATIONS.set(self.outer.take());
+	}
+}
+
+/// Run `f`, collecting any [`TagLocation`]s recorded during it
+///
+/// Calls can be nested, in which case the inner call receives its own locations.
+pub(crate) fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<TagLocation>) {
+	let guard = CollectGuard {
+		outer: TAG_LOCATIONS.replace(Some(Vec::new())),
+	};
+
+	let ret = f();
+	let locations = TAG_LOCATIONS.take().unwrap_or_default();
+	drop(guard);
+
+	(ret, locations)
+}
@@ -1,5 +1,7 @@
-use crate::error::{ErrorKind, LoftyError, Result};
+use crate::config::TextDecodeFallback;
+use crate::error::{ErrorKind, LoftyError, ParseDiagnostic, Result};
 use crate::macros::err;
+use crate::util::diagnostics;
 
 use std::io::Read;
 
@@ -82,11 +84,13 @@ const EMPTY_DECODED_TEXT: DecodeTextResult = DecodeTextResult {
 /// * Use [`TextEncoding::UTF8`] as the encoding
 /// * Not expect the text to be null terminated
 /// * Have no byte order mark
+/// * Not use a fallback for text that fails to decode
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct TextDecodeOptions {
 	pub encoding: TextEncoding,
 	pub terminated: bool,
 	pub bom: [u8; 2],
+	pub fallback: TextDecodeFallback,
 }
 
 impl TextDecodeOptions {
@@ -108,6 +112,11 @@ impl TextDecodeOptions {
 		self.bom = bom;
 		self
 	}
+
+	pub(crate) fn fallback(mut self, fallback: TextDecodeFallback) -> Self {
+		self.fallback = fallback;
+		self
+	}
 }
 
 impl Default for TextDecodeOptions {
@@ -116,6 +125,7 @@ impl Default for TextDecodeOptions {
 			encoding: TextEncoding::UTF8,
 			terminated: false,
 			bom: [0, 0],
+			fallback: TextDecodeFallback::None,
 		}
 	}
 }
@@ -149,50 +159,119 @@ where
 	}
 
 	let mut bom = [0, 0];
-	let read_string = match options.encoding {
-		TextEncoding::Latin1 => latin1_decode(&raw_bytes),
+	let read_string = match decode_raw_text(&raw_bytes, options, &mut bom) {
+		Ok(text) => text,
+		Err(e) => match fallback_decode(&raw_bytes, options, bom) {
+			Some(text) => {
+				log::warn!(
+					"Failed to decode {:?} text, using fallback {:?}",
+					options.encoding,
+					options.fallback
+				);
+				diagnostics::report(ParseDiagnostic::TextDecodeFallback {
+					encoding: options.encoding,
+				});
+				text
+			},
+			None => return Err(e),
+		},
+	};
+
+	if read_string.is_empty() {
+		return Ok(EMPTY_DECODED_TEXT);
+	}
+
+	Ok(DecodeTextResult {
+		content: read_string,
+		bytes_read,
+		bom,
+	})
+}
+
+fn decode_raw_text(
+	raw_bytes: &[u8],
+	options: TextDecodeOptions,
+	bom: &mut [u8; 2],
+) -> Result<String> {
+	let text = match options.encoding {
+		TextEncoding::Latin1 => match options.fallback {
+			// ISO-8859-1 can't fail to decode, so a custom decoder has to be used up front. Plain
+			// ASCII is the same in any legacy encoding, so there's no need to bother it with that.
+			TextDecodeFallback::Custom(decode) if !raw_bytes.is_ascii() => {
+				diagnostics::report(ParseDiagnostic::TextDecodeFallback {
+					encoding: TextEncoding::Latin1,
+				});
+
+				let mut text = decode(raw_bytes);
+				trim_end_nulls(&mut text);
+				text
+			},
+			_ => latin1_decode(raw_bytes),
+		},
 		TextEncoding::UTF16 => {
 			if raw_bytes.len() < 2 {
 				err!(TextDecode("UTF-16 string has an invalid length (< 2)"));
 			}
 
-			if raw_bytes.len() % 2 != 0 {
+			if !raw_bytes.len().is_multiple_of(2) {
 				err!(TextDecode("UTF-16 string has an odd length"));
 			}
 
-			let bom_to_check;
-			if options.bom == [0, 0] {
-				bom_to_check = [raw_bytes[0], raw_bytes[1]];
-			} else {
-				bom_to_check = options.bom;
-			}
-
-			match bom_to_check {
-				[0xFE, 0xFF] => {
-					bom = [0xFE, 0xFF];
+			match ([raw_bytes[0], raw_bytes[1]], options.bom) {
+				([0xFE, 0xFF], _) => {
+					*bom = [0xFE, 0xFF];
 					utf16_decode_bytes(&raw_bytes[2..], u16::from_be_bytes)?
 				},
-				[0xFF, 0xFE] => {
-					bom = [0xFF, 0xFE];
+				([0xFF, 0xFE], _) => {
+					*bom = [0xFF, 0xFE];
 					utf16_decode_bytes(&raw_bytes[2..], u16::from_le_bytes)?
 				},
-				_ => err!(TextDecode("UTF-16 string has an invalid byte order mark")),
+				// Strings following the first may only rely on its byte order mark
+				(_, [0xFE, 0xFF]) => {
+					*bom = [0xFE, 0xFF];
+					utf16_decode_bytes(raw_bytes, u16::from_be_bytes)?
+				},
+				(_, [0xFF, 0xFE]) => {
+					*bom = [0xFF, 0xFE];
+					utf16_decode_bytes(raw_bytes, u16::from_le_bytes)?
+				},
+				// Plenty of software forgets the BOM, and most of it runs on little-endian machines
+				_ => {
+					log::warn!("UTF-16 string has no byte order mark, assuming little-endian");
+					diagnostics::report(ParseDiagnostic::MissingUtf16Bom);
+					utf16_decode_bytes(raw_bytes, u16::from_le_bytes)?
+				},
 			}
 		},
-		TextEncoding::UTF16BE => utf16_decode_bytes(raw_bytes.as_slice(), u16::from_be_bytes)?,
-		TextEncoding::UTF8 => utf8_decode(raw_bytes)
+		TextEncoding::UTF16BE => utf16_decode_bytes(raw_bytes, u16::from_be_bytes)?,
+		TextEncoding::UTF8 => utf8_decode(raw_bytes.to_vec())
 			.map_err(|_| LoftyError::new(ErrorKind::TextDecode("Expected a UTF-8 string")))?,
 	};
 
-	if read_string.is_empty() {
-		return Ok(EMPTY_DECODED_TEXT);
-	}
+	Ok(text)
+}
 
-	Ok(DecodeTextResult {
-		content: read_string,
-		bytes_read,
-		bom,
-	})
+/// Decode text that failed to decode with its declared encoding, using [`TextDecodeOptions::fallback`]
+///
+/// `bom` is the byte order mark found while decoding, if any.
+fn fallback_decode(raw_bytes: &[u8], options: TextDecodeOptions, bom: [u8; 2]) -> Option<String> {
+	let mut text = match options.fallback {
+		TextDecodeFallback::None => return None,
+		TextDecodeFallback::Latin1 => return Some(latin1_decode(raw_bytes)),
+		TextDecodeFallback::Lossy => match options.encoding {
+			TextEncoding::Latin1 => latin1_decode(raw_bytes),
+			TextEncoding::UTF16 if bom == [0xFE, 0xFF] => {
+				utf16_decode_bytes_lossy(raw_bytes, u16::from_be_bytes)
+			},
+			TextEncoding::UTF16 => utf16_decode_bytes_lossy(raw_bytes, u16::from_le_bytes),
+			TextEncoding::UTF16BE => utf16_decode_bytes_lossy(raw_bytes, u16::from_be_bytes),
+			TextEncoding::UTF8 => String::from_utf8_lossy(raw_bytes).into_owned(),
+		},
+		TextDecodeFallback::Custom(decode) => decode(raw_bytes),
+	};
+
+	trim_end_nulls(&mut text);
+	Some(text)
 }
 
 pub(crate) fn read_to_terminator<R>(reader: &mut R, encoding: TextEncoding) -> (Vec<u8>, usize)
@@ -278,6 +357,26 @@ pub(crate) fn utf16_decode_bytes(bytes: &[u8], endianness: fn([u8; 2]) -> u16) -
 	utf16_decode(&unverified)
 }
 
+/// Same as [`utf16_decode_bytes`], replacing invalid code units and any trailing odd byte with `U+FFFD`
+fn utf16_decode_bytes_lossy(bytes: &[u8], endianness: fn([u8; 2]) -> u16) -> String {
+	let chunks = bytes.chunks_exact(2);
+	let has_remainder = !chunks.remainder().is_empty();
+
+	let words: Vec<u16> = chunks
+		.filter_map(|c| match c {
+			[0xFF, 0xFE] | [0xFE, 0xFF] => None,
+			_ => Some(endianness(c.try_into().unwrap())), // Infallible
+		})
+		.collect();
+
+	let mut text = String::from_utf16_lossy(&words);
+	if has_remainder {
+		text.push(char::REPLACEMENT_CHARACTER);
+	}
+
+	text
+}
+
 pub(crate) fn encode_text(text: &str, text_encoding: TextEncoding, terminated: bool) -> Vec<u8> {
 	match text_encoding {
 		TextEncoding::Latin1 => {
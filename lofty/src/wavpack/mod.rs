@@ -5,6 +5,8 @@ mod read;
 use crate::ape::tag::ApeTag;
 use crate::id3::v1::tag::Id3v1Tag;
 
+use std::path::{Path, PathBuf};
+
 use lofty_attr::LoftyFile;
 
 // Exports
@@ -24,3 +26,40 @@ pub struct WavPackFile {
 	/// The file's audio properties
 	pub(crate) properties: WavPackProperties,
 }
+
+impl WavPackFile {
+	/// Get the path of the correction file for a WavPack file
+	///
+	/// [Hybrid](WavPackProperties::is_hybrid) streams store the data needed to restore the lossless
+	/// audio in a separate correction file, next to the `.wv` file with a `.wvc` extension.
+	///
+	/// This will return `None` if no correction file exists for `path`.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use lofty::file::AudioFile;
+	/// use lofty::wavpack::WavPackFile;
+	///
+	/// # fn main() -> lofty::error::Result<()> {
+	/// let path = "song.wv";
+	///
+	/// let mut file = std::fs::File::open(path)?;
+	/// let wavpack_file = WavPackFile::read_from(&mut file, Default::default())?;
+	///
+	/// if wavpack_file.properties().is_hybrid() {
+	/// 	match WavPackFile::correction_file_path(path) {
+	/// 		Some(correction_file) => println!("Correction file: {}", correction_file.display()),
+	/// 		None => println!("No correction file, the audio is lossy"),
+	/// 	}
+	/// }
+	/// # Ok(()) }
+	/// ```
+	pub fn correction_file_path<P>(path: P) -> Option<PathBuf>
+	where
+		P: AsRef<Path>,
+	{
+		let correction_file = path.as_ref().with_extension("wvc");
+		correction_file.is_file().then_some(correction_file)
+	}
+}
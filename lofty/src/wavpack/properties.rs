@@ -10,6 +10,7 @@ use byteorder::{LittleEndian, ReadBytesExt};
 
 /// A WavPack file's audio properties
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct WavPackProperties {
 	pub(crate) version: u16,
@@ -21,6 +22,7 @@ pub struct WavPackProperties {
 	pub(crate) channel_mask: ChannelMask,
 	pub(crate) bit_depth: u8,
 	pub(crate) lossless: bool,
+	pub(crate) hybrid: bool,
 }
 
 impl From<WavPackProperties> for FileProperties {
@@ -75,6 +77,8 @@ impl WavPackProperties {
 	}
 
 	/// WavPack version
+	///
+	/// This is the version of the stream format written by the encoder (e.g. `0x410`).
 	pub fn version(&self) -> u16 {
 		self.version
 	}
@@ -85,9 +89,29 @@ impl WavPackProperties {
 	}
 
 	/// Whether the audio is lossless
+	///
+	/// NOTE: This will be `false` for [hybrid](Self::is_hybrid) streams, even if a correction
+	///       file is available to restore the lossless audio.
 	pub fn is_lossless(&self) -> bool {
 		self.lossless
 	}
+
+	/// Whether the stream was encoded in hybrid mode
+	///
+	/// Hybrid streams are lossy, with the data needed to restore the lossless audio being stored
+	/// in a separate correction (`.wvc`) file. See [`WavPackFile::correction_file_path`].
+	///
+	/// [`WavPackFile::correction_file_path`]: crate::wavpack::WavPackFile::correction_file_path
+	pub fn is_hybrid(&self) -> bool {
+		self.hybrid
+	}
+
+	/// Average bitrate of the lossy portion of a [hybrid](Self::is_hybrid) stream (kbps)
+	///
+	/// This will be `None` for non-hybrid streams.
+	pub fn lossy_bitrate(&self) -> Option<u32> {
+		self.hybrid.then_some(self.audio_bitrate)
+	}
 }
 
 // Thanks MultimediaWiki :)
@@ -190,7 +214,8 @@ where
 			properties.bit_depth = (((flags & BYTES_PER_SAMPLE_MASK) + 1) * 8).saturating_sub((flags & BIT_DEPTH_SHIFT_MASK) >> BIT_DEPTH_SHL) as u8;
 
 			properties.version = block_header.version;
-			properties.lossless = flags & FLAG_HYBRID_COMPRESSION == 0;
+			properties.hybrid = flags & FLAG_HYBRID_COMPRESSION == FLAG_HYBRID_COMPRESSION;
+			properties.lossless = !properties.hybrid;
 
 
 			// https://web.archive.org/web/20150424062034/https://www.wavpack.com/file_format.txt:
@@ -20,9 +20,9 @@ where
 
 	let ID3FindResults(id3v1_header, id3v1) = find_id3v1(reader, parse_options.read_tags)?;
 
-	if id3v1_header.is_some() {
+	if let Some(id3v1_size) = id3v1_header {
 		id3v1_tag = id3v1;
-		let Some(new_stream_length) = stream_length.checked_sub(128) else {
+		let Some(new_stream_length) = stream_length.checked_sub(id3v1_size) else {
 			err!(SizeMismatch);
 		};
 
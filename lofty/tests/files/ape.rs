@@ -1,6 +1,9 @@
+use crate::util::SharedBuffer;
 use crate::{set_artist, temp_file, verify_artist};
-use lofty::config::ParseOptions;
+use lofty::ape::{ApeFile, ApeItem};
+use lofty::config::{ParseOptions, WriteOptions};
 use lofty::file::FileType;
+use lofty::picture::{MimeType, Picture, PictureType};
 use lofty::prelude::*;
 use lofty::probe::Probe;
 use lofty::tag::TagType;
@@ -86,3 +89,43 @@ fn read_no_properties() {
 fn read_no_tags() {
 	crate::no_tag_test!("tests/files/assets/minimal/full_test.ape");
 }
+
+#[test_log::test]
+fn pictures_to_writers() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.ape");
+	let mut ape_file = ApeFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	// A PNG signature, followed by the rest of the "image"
+	let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+	data.extend([1; 1024]);
+	let picture = Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		Some(String::from("cover.png")),
+		data.clone(),
+	);
+	let ape_tag = ape_file.ape_mut().unwrap();
+	ape_tag.insert(ApeItem::try_from(&picture).unwrap());
+
+	file.rewind().unwrap();
+	ape_tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let buffer = SharedBuffer::default();
+	let writer = buffer.clone();
+	let tagged_file = Probe::new(&mut file)
+		.guess_file_type()
+		.unwrap()
+		.pictures_to_writers(move |picture| {
+			assert_eq!(picture.pic_type(), PictureType::CoverFront);
+			assert_eq!(picture.mime_type(), Some(&MimeType::Png));
+			assert_eq!(picture.description(), Some("cover.png"));
+			Some(writer.clone())
+		})
+		.unwrap();
+
+	assert_eq!(buffer.contents(), data);
+
+	// APE items can't hold a picture without its data
+	assert_eq!(tagged_file.tag(TagType::Ape).unwrap().picture_count(), 0);
+}
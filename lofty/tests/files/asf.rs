@@ -0,0 +1,132 @@
+use crate::{temp_file, verify_artist};
+use lofty::asf::{AsfAttributeValue, AsfFile};
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::error::ErrorKind;
+use lofty::file::{FileType, TaggedFile};
+use lofty::picture::{MimeType, PictureType};
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::TagType;
+
+use std::io::Seek;
+
+#[test_log::test]
+fn read() {
+	let file = Probe::open("tests/files/assets/minimal/full_test.wma")
+		.unwrap()
+		.read()
+		.unwrap();
+
+	assert_eq!(file.file_type(), FileType::Asf);
+
+	let properties = file.properties();
+	assert_eq!(properties.sample_rate(), Some(48000));
+	assert_eq!(properties.channels(), Some(2));
+	assert_eq!(properties.audio_bitrate(), Some(128));
+
+	// The content description, extended content description, and metadata library are merged
+	let tag = crate::verify_artist!(file, primary_tag, "Bar artist", 9);
+
+	assert_eq!(tag.tag_type(), TagType::Asf);
+	assert_eq!(tag.title().as_deref(), Some("Foo title"));
+	assert_eq!(tag.album().as_deref(), Some("Baz album"));
+	assert_eq!(tag.comment().as_deref(), Some("Qux comment"));
+	assert_eq!(tag.genre().as_deref(), Some("Classical"));
+	assert_eq!(tag.track(), Some(1));
+	assert_eq!(tag.year(), Some(1984));
+	assert_eq!(
+		tag.get_string(&ItemKey::AlbumArtist),
+		Some("Foo album artist")
+	);
+
+	assert_eq!(tag.picture_count(), 1);
+	let picture = &tag.pictures()[0];
+	assert_eq!(picture.pic_type(), PictureType::CoverFront);
+	assert_eq!(picture.mime_type(), Some(&MimeType::Png));
+	assert_eq!(picture.description(), Some("Cover"));
+	assert!(picture.data().starts_with(b"\x89PNG"));
+}
+
+#[test_log::test]
+fn read_attributes() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.wma");
+	let asf_file = AsfFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let tag = asf_file.asf().unwrap();
+	assert_eq!(
+		tag.get("WM/TrackNumber"),
+		Some(&AsfAttributeValue::DWord(1))
+	);
+	assert_eq!(
+		tag.get("WM/IsCompilation"),
+		Some(&AsfAttributeValue::Bool(true))
+	);
+
+	// Empty content description fields are skipped
+	assert!(tag.get("Copyright").is_none());
+	assert_eq!(tag.pictures().len(), 1);
+}
+
+#[test_log::test]
+fn read_no_cover_art() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.wma");
+	let asf_file =
+		AsfFile::read_from(&mut file, ParseOptions::new().read_cover_art(false)).unwrap();
+
+	assert!(asf_file.asf().unwrap().pictures().is_empty());
+}
+
+#[test_log::test]
+fn write_is_unsupported() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.wma");
+
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let tag = tagged_file.primary_tag_mut().unwrap();
+	tag.set_artist(String::from("Foo artist"));
+
+	file.rewind().unwrap();
+	let err = tag.save_to(&mut file, WriteOptions::default()).unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::UnsupportedTag));
+}
+
+#[test_log::test]
+fn read_only_tags_are_kept() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.wma");
+
+	// Nothing can be written, so nothing is stripped
+	assert!(TaggedFile::strip_all_tags(&mut file).unwrap().is_empty());
+
+	// Removing the tag only affects the `TaggedFile`
+	file.rewind().unwrap();
+	let mut tagged_file = Probe::new(&mut file)
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	assert!(tagged_file.remove(TagType::Asf).is_some());
+
+	file.rewind().unwrap();
+	tagged_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let asf_file = AsfFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(asf_file.asf().is_some());
+}
+
+#[test_log::test]
+fn read_no_properties() {
+	crate::no_properties_test!("tests/files/assets/minimal/full_test.wma");
+}
+
+#[test_log::test]
+fn read_no_tags() {
+	crate::no_tag_test!("tests/files/assets/minimal/full_test.wma");
+}
@@ -0,0 +1,84 @@
+use lofty::config::ParseOptions;
+use lofty::file::FileType;
+use lofty::flac::FlacFile;
+use lofty::mp4::Mp4File;
+use lofty::mpeg::MpegFile;
+use lofty::prelude::*;
+use lofty::probe::Probe;
+
+async fn read_with_probe(path: &str, file_type: FileType) {
+	let file = tokio::fs::File::open(path).await.unwrap();
+
+	let probe = Probe::new_async(file)
+		.guess_file_type_async()
+		.await
+		.unwrap();
+	assert_eq!(probe.file_type(), Some(file_type));
+
+	let tagged_file = probe.read_async().await.unwrap();
+	let sync_tagged_file = lofty::read_from_path(path).unwrap();
+
+	assert_eq!(tagged_file.file_type(), file_type);
+	assert_eq!(tagged_file.tags().len(), sync_tagged_file.tags().len());
+	for (tag, sync_tag) in tagged_file.tags().iter().zip(sync_tagged_file.tags()) {
+		assert_eq!(tag.tag_type(), sync_tag.tag_type());
+		assert!(tag.items().eq(sync_tag.items()));
+	}
+	assert_eq!(tagged_file.properties(), sync_tagged_file.properties());
+	assert!(tagged_file.primary_tag().is_some());
+}
+
+#[tokio::test]
+async fn read_flac() {
+	read_with_probe("tests/files/assets/minimal/full_test.flac", FileType::Flac).await;
+
+	let mut file = tokio::fs::File::open("tests/files/assets/minimal/full_test.flac")
+		.await
+		.unwrap();
+	let flac_file = FlacFile::read_from_async(&mut file, ParseOptions::new())
+		.await
+		.unwrap();
+
+	assert_eq!(
+		flac_file.vorbis_comments().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
+
+#[tokio::test]
+async fn read_mpeg() {
+	read_with_probe("tests/files/assets/minimal/full_test.mp3", FileType::Mpeg).await;
+
+	let mut file = tokio::fs::File::open("tests/files/assets/minimal/full_test.mp3")
+		.await
+		.unwrap();
+	let mpeg_file = MpegFile::read_from_async(&mut file, ParseOptions::new())
+		.await
+		.unwrap();
+
+	assert_eq!(
+		mpeg_file.id3v2().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
+
+#[tokio::test]
+async fn read_mp4() {
+	read_with_probe(
+		"tests/files/assets/minimal/m4a_codec_aac.m4a",
+		FileType::Mp4,
+	)
+	.await;
+
+	let mut file = tokio::fs::File::open("tests/files/assets/minimal/m4a_codec_aac.m4a")
+		.await
+		.unwrap();
+	let mp4_file = Mp4File::read_from_async(&mut file, ParseOptions::new())
+		.await
+		.unwrap();
+
+	assert_eq!(
+		mp4_file.ilst().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
@@ -0,0 +1,197 @@
+use crate::temp_file;
+use lofty::config::WriteOptions;
+use lofty::error::ErrorKind;
+use lofty::file::FileType;
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::{Tag, TagType};
+
+use std::io::Seek;
+
+const ALL_TAG_TYPES: [TagType; 8] = [
+	TagType::Ape,
+	TagType::Id3v1,
+	TagType::Id3v2,
+	TagType::Mp4Ilst,
+	TagType::VorbisComments,
+	TagType::RiffInfo,
+	TagType::AiffText,
+	TagType::Asf,
+];
+
+// Attempt to write every tag type, and verify that only the writable tag types are actually written
+fn verify_writers(path: &str, file_type: FileType) {
+	for tag_type in ALL_TAG_TYPES {
+		let mut file = temp_file!(path);
+
+		let mut tag = Tag::new(tag_type);
+		tag.set_artist(String::from("Capabilities artist"));
+
+		let result = tag.save_to(&mut file, WriteOptions::default());
+		if !file_type.supports_tag_type(tag_type) {
+			let err = result.unwrap_err();
+			assert!(
+				matches!(err.kind(), ErrorKind::UnsupportedTag),
+				"{tag_type:?} in {file_type:?}: {err}"
+			);
+		}
+
+		file.rewind().unwrap();
+		let tagged_file = Probe::new(&mut file)
+			.guess_file_type()
+			.unwrap()
+			.read()
+			.unwrap();
+
+		let written = tagged_file
+			.tag(tag_type)
+			.and_then(Accessor::artist)
+			.is_some_and(|artist| artist == "Capabilities artist");
+
+		assert_eq!(
+			written,
+			tag_type.supports_write_for(file_type),
+			"{tag_type:?} in {file_type:?}"
+		);
+	}
+}
+
+#[test_log::test]
+fn flac() {
+	let file_type = FileType::Flac;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::VorbisComments);
+	assert_eq!(
+		file_type.supported_tag_types(),
+		[TagType::Id3v2, TagType::VorbisComments]
+	);
+	assert_eq!(file_type.writable_tag_types(), [TagType::VorbisComments]);
+
+	verify_writers("tests/files/assets/minimal/full_test.flac", file_type);
+}
+
+#[test_log::test]
+fn mpeg() {
+	let file_type = FileType::Mpeg;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Id3v2);
+	assert_eq!(
+		file_type.supported_tag_types(),
+		[TagType::Ape, TagType::Id3v1, TagType::Id3v2]
+	);
+	assert_eq!(
+		file_type.writable_tag_types(),
+		[TagType::Ape, TagType::Id3v1, TagType::Id3v2]
+	);
+
+	verify_writers("tests/files/assets/minimal/full_test.mp3", file_type);
+}
+
+#[test_log::test]
+fn mp4() {
+	let file_type = FileType::Mp4;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Mp4Ilst);
+	assert_eq!(file_type.supported_tag_types(), [TagType::Mp4Ilst]);
+	assert_eq!(file_type.writable_tag_types(), [TagType::Mp4Ilst]);
+
+	verify_writers("tests/files/assets/minimal/m4a_codec_aac.m4a", file_type);
+}
+
+#[test_log::test]
+fn wav() {
+	let file_type = FileType::Wav;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Id3v2);
+	assert_eq!(
+		file_type.supported_tag_types(),
+		[TagType::Id3v2, TagType::RiffInfo]
+	);
+	assert_eq!(
+		file_type.writable_tag_types(),
+		[TagType::Id3v2, TagType::RiffInfo]
+	);
+
+	verify_writers("tests/files/assets/minimal/wav_format_pcm.wav", file_type);
+}
+
+#[test_log::test]
+fn ape() {
+	let file_type = FileType::Ape;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Ape);
+	assert_eq!(
+		file_type.supported_tag_types(),
+		[TagType::Ape, TagType::Id3v1, TagType::Id3v2]
+	);
+	assert_eq!(
+		file_type.writable_tag_types(),
+		[TagType::Ape, TagType::Id3v1]
+	);
+
+	verify_writers("tests/files/assets/minimal/full_test.ape", file_type);
+}
+
+#[test_log::test]
+fn mpc() {
+	let file_type = FileType::Mpc;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Ape);
+	assert_eq!(
+		file_type.supported_tag_types(),
+		[TagType::Ape, TagType::Id3v1, TagType::Id3v2]
+	);
+	assert_eq!(file_type.writable_tag_types(), [TagType::Ape]);
+
+	verify_writers("tests/files/assets/minimal/mpc_sv8.mpc", file_type);
+}
+
+#[test_log::test]
+fn dsf() {
+	let file_type = FileType::Dsf;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Id3v2);
+	assert_eq!(file_type.supported_tag_types(), [TagType::Id3v2]);
+	assert_eq!(file_type.writable_tag_types(), [TagType::Id3v2]);
+
+	verify_writers("tests/files/assets/minimal/full_test.dsf", file_type);
+}
+
+#[test_log::test]
+fn dff() {
+	let file_type = FileType::Dff;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Id3v2);
+	assert_eq!(file_type.supported_tag_types(), [TagType::Id3v2]);
+	assert!(file_type.writable_tag_types().is_empty());
+
+	verify_writers("tests/files/assets/minimal/full_test.dff", file_type);
+}
+
+#[test_log::test]
+fn shn() {
+	let file_type = FileType::Shn;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Ape);
+	assert_eq!(
+		file_type.supported_tag_types(),
+		[TagType::Ape, TagType::Id3v1, TagType::Id3v2]
+	);
+	assert_eq!(
+		file_type.writable_tag_types(),
+		[TagType::Ape, TagType::Id3v1]
+	);
+
+	verify_writers("tests/files/assets/minimal/full_test.shn", file_type);
+}
+
+#[test_log::test]
+fn asf() {
+	let file_type = FileType::Asf;
+
+	assert_eq!(file_type.primary_tag_type(), TagType::Asf);
+	assert_eq!(file_type.supported_tag_types(), [TagType::Asf]);
+	assert!(file_type.writable_tag_types().is_empty());
+
+	verify_writers("tests/files/assets/minimal/full_test.wma", file_type);
+}
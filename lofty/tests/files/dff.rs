@@ -0,0 +1,72 @@
+use crate::{temp_file, verify_artist};
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::dff::{DffCompressionType, DffFile};
+use lofty::error::ErrorKind;
+use lofty::file::FileType;
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::TagType;
+
+use std::io::Seek;
+
+#[test_log::test]
+fn read() {
+	let file = Probe::open("tests/files/assets/minimal/full_test.dff")
+		.unwrap()
+		.options(ParseOptions::new().read_properties(false))
+		.read()
+		.unwrap();
+
+	assert_eq!(file.file_type(), FileType::Dff);
+
+	// The artist in the `ID3 ` chunk takes priority over the one in the `DIIN` chunk
+	let tag = crate::verify_artist!(file, primary_tag, "Foo artist", 3);
+
+	// The rest is filled in from the `DIIN` and `COMT` chunks
+	assert_eq!(tag.title().as_deref(), Some("Foo title"));
+	assert_eq!(tag.comment().as_deref(), Some("Foo comment"));
+}
+
+#[test_log::test]
+fn read_properties() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.dff");
+	let dff_file = DffFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let properties = dff_file.properties();
+	assert_eq!(properties.compression_type(), DffCompressionType::Dsd);
+	assert_eq!(properties.bit_depth(), 1);
+	assert_eq!(properties.sample_rate(), 2_822_400);
+}
+
+#[test_log::test]
+fn write_is_unsupported() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.dff");
+
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let tag = tagged_file.primary_tag_mut().unwrap();
+	tag.set_artist(String::from("Bar artist"));
+
+	file.rewind().unwrap();
+	let err = tag.save_to(&mut file, WriteOptions::default()).unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::UnsupportedTag));
+
+	file.rewind().unwrap();
+	let err = TagType::Id3v2.remove_from(&mut file).unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::UnsupportedTag));
+}
+
+#[test_log::test]
+fn read_no_properties() {
+	crate::no_properties_test!("tests/files/assets/minimal/full_test.dff");
+}
+
+#[test_log::test]
+fn read_no_tags() {
+	crate::no_tag_test!("tests/files/assets/minimal/full_test.dff");
+}
@@ -0,0 +1,133 @@
+use crate::{set_artist, temp_file, verify_artist};
+use lofty::config::ParseOptions;
+use lofty::dsf::DsfFile;
+use lofty::file::FileType;
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::TagType;
+
+use std::io::{Read, Seek, SeekFrom};
+
+#[test_log::test]
+fn read() {
+	let file = Probe::open("tests/files/assets/minimal/full_test.dsf")
+		.unwrap()
+		.options(ParseOptions::new().read_properties(false))
+		.read()
+		.unwrap();
+
+	assert_eq!(file.file_type(), FileType::Dsf);
+
+	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
+}
+
+#[test_log::test]
+fn write() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.dsf");
+
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	assert_eq!(tagged_file.file_type(), FileType::Dsf);
+
+	crate::set_artist!(tagged_file, primary_tag_mut, "Foo artist", 1 => file, "Bar artist");
+
+	// Now reread the file
+	file.rewind().unwrap();
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	crate::set_artist!(tagged_file, primary_tag_mut, "Bar artist", 1 => file, "Foo artist");
+}
+
+#[test_log::test]
+fn write_updates_header() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.dsf");
+	let original_properties = *DsfFile::read_from(&mut file, ParseOptions::new())
+		.unwrap()
+		.properties();
+
+	let mut tag = lofty::tag::Tag::new(TagType::Id3v2);
+	tag.set_artist(String::from("A much longer artist name than the original"));
+	tag.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, lofty::config::WriteOptions::default())
+		.unwrap();
+
+	// The total file size and metadata offset must point to the new tag
+	let file_len = file.seek(SeekFrom::End(0)).unwrap();
+	file.seek(SeekFrom::Start(12)).unwrap();
+
+	let mut header = [0; 16];
+	file.read_exact(&mut header).unwrap();
+	assert_eq!(
+		u64::from_le_bytes(header[..8].try_into().unwrap()),
+		file_len
+	);
+
+	let metadata_offset = u64::from_le_bytes(header[8..].try_into().unwrap());
+	file.seek(SeekFrom::Start(metadata_offset)).unwrap();
+
+	let mut id3 = [0; 3];
+	file.read_exact(&mut id3).unwrap();
+	assert_eq!(&id3, b"ID3");
+
+	file.rewind().unwrap();
+	let dsf_file = DsfFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(
+		dsf_file.properties().duration(),
+		original_properties.duration()
+	);
+	assert_eq!(
+		dsf_file.properties().sample_rate(),
+		original_properties.sample_rate()
+	);
+
+	let id3v2 = dsf_file.id3v2().unwrap();
+	assert_eq!(
+		id3v2.artist().as_deref(),
+		Some("A much longer artist name than the original")
+	);
+	assert_eq!(id3v2.title().as_deref(), Some("Foo title"));
+}
+
+#[test_log::test]
+fn remove_id3v2() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.dsf", TagType::Id3v2);
+}
+
+#[test_log::test]
+fn remove_id3v2_clears_metadata_offset() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.dsf");
+	TagType::Id3v2.remove_from(&mut file).unwrap();
+
+	let file_len = file.seek(SeekFrom::End(0)).unwrap();
+	file.seek(SeekFrom::Start(12)).unwrap();
+
+	let mut header = [0; 16];
+	file.read_exact(&mut header).unwrap();
+	assert_eq!(
+		u64::from_le_bytes(header[..8].try_into().unwrap()),
+		file_len
+	);
+	assert_eq!(u64::from_le_bytes(header[8..].try_into().unwrap()), 0);
+}
+
+#[test_log::test]
+fn read_no_properties() {
+	crate::no_properties_test!("tests/files/assets/minimal/full_test.dsf");
+}
+
+#[test_log::test]
+fn read_no_tags() {
+	crate::no_tag_test!("tests/files/assets/minimal/full_test.dsf");
+}
@@ -0,0 +1,65 @@
+use lofty::file::{AudioFile, FileType};
+use lofty::probe::Probe;
+
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+	for entry in std::fs::read_dir(dir).unwrap() {
+		let path = entry.unwrap().path();
+		if path.is_dir() {
+			collect_files(&path, files);
+		} else if FileType::from_path(&path).is_some() {
+			files.push(path);
+		}
+	}
+}
+
+#[test_log::test]
+fn duration_matches_full_read() {
+	let mut files = Vec::new();
+	collect_files(Path::new("tests/files/assets"), &mut files);
+
+	let mut compared = 0;
+	for path in files {
+		// Files that fail a full read aren't of interest here
+		let Ok(tagged_file) = lofty::read_from_path(&path) else {
+			continue;
+		};
+
+		let duration = lofty::duration(&path)
+			.unwrap_or_else(|e| panic!("{}: failed to read duration: {e}", path.display()));
+		assert_eq!(
+			duration,
+			tagged_file.properties().duration(),
+			"{}",
+			path.display()
+		);
+
+		compared += 1;
+	}
+
+	assert!(compared > 0);
+}
+
+#[test_log::test]
+fn duration_from_stream_info_only() {
+	let path = "tests/files/assets/minimal/full_test.flac";
+	let file = std::fs::read(path).unwrap();
+
+	// "fLaC" (4) + STREAMINFO block header (4) + STREAMINFO (34), the rest of the metadata is gone
+	let truncated = &file[..42];
+	assert!(
+		Probe::with_file_type(Cursor::new(truncated), FileType::Flac)
+			.read()
+			.is_err()
+	);
+
+	let duration = Probe::with_file_type(Cursor::new(truncated), FileType::Flac)
+		.duration()
+		.unwrap();
+	assert_eq!(
+		duration,
+		lofty::read_from_path(path).unwrap().properties().duration()
+	);
+}
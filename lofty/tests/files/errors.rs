@@ -0,0 +1,207 @@
+use crate::temp_file;
+use lofty::ape::{ApeFile, ApeTag};
+use lofty::config::{ParseOptions, ParsingMode, WriteOptions};
+use lofty::error::{ErrorCategory, ErrorKind, LoftyError};
+use lofty::file::{AudioFile, FileType};
+use lofty::flac::FlacFile;
+use lofty::id3::v2::{FrameFlags, SynchronizedTextFrame};
+use lofty::ogg::VorbisComments;
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::{Tag, TagType};
+
+use std::error::Error;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+fn io_source(err: &LoftyError) -> std::io::ErrorKind {
+	err.source()
+		.and_then(|source| source.downcast_ref::<std::io::Error>())
+		.expect("error should have an IO error source")
+		.kind()
+}
+
+struct PermissionDenied;
+
+impl Read for PermissionDenied {
+	fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+		Err(std::io::ErrorKind::PermissionDenied.into())
+	}
+}
+
+impl Seek for PermissionDenied {
+	fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+		Ok(0)
+	}
+}
+
+#[test_log::test]
+fn io() {
+	let Err(err) = FlacFile::read_from(&mut PermissionDenied, ParseOptions::new()) else {
+		panic!("reading should fail");
+	};
+
+	assert_eq!(err.category(), ErrorCategory::Io);
+	assert_eq!(err.category().code(), "io");
+	assert_eq!(io_source(&err), std::io::ErrorKind::PermissionDenied);
+}
+
+#[test_log::test]
+fn unsupported_format() {
+	let Err(err) = Probe::new(Cursor::new(vec![0; 64]))
+		.guess_file_type()
+		.unwrap()
+		.read()
+	else {
+		panic!("reading should fail");
+	};
+
+	assert!(matches!(err.kind(), ErrorKind::UnknownFormat));
+	assert_eq!(err.category(), ErrorCategory::UnsupportedFormat);
+	assert!(err.source().is_none());
+}
+
+#[test_log::test]
+fn malformed_file() {
+	let Err(err) = FlacFile::read_from(&mut Cursor::new(vec![0; 64]), ParseOptions::new()) else {
+		panic!("reading should fail");
+	};
+
+	assert_eq!(
+		err.category(),
+		ErrorCategory::MalformedFile {
+			file_type: Some(FileType::Flac)
+		}
+	);
+	assert_eq!(err.category().code(), "malformed_file");
+}
+
+#[test_log::test]
+fn malformed_file_io_source() {
+	// A MAC header that ends before its descriptor, but leaves room for the APE tag footer search
+	let mut header = b"MAC ".to_vec();
+	header.extend(3990_u16.to_le_bytes());
+	header.extend([0; 34]);
+
+	let Err(err) = ApeFile::read_from(
+		&mut Cursor::new(header),
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	) else {
+		panic!("reading should fail");
+	};
+
+	assert_eq!(
+		err.category(),
+		ErrorCategory::MalformedFile {
+			file_type: Some(FileType::Ape)
+		}
+	);
+	assert_eq!(io_source(&err), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test_log::test]
+fn malformed_ape_tag() {
+	let mut tag = ApeTag::new();
+	tag.set_title(String::from("Foo title"));
+
+	let mut bytes = Vec::new();
+	tag.dump_to(&mut bytes, WriteOptions::default()).unwrap();
+
+	// Make the title invalid UTF-8
+	let title_pos = bytes
+		.windows(9)
+		.position(|window| window == b"Foo title")
+		.unwrap();
+	bytes[title_pos] = 0xFF;
+
+	let err = ApeTag::parse(&mut Cursor::new(bytes), ParseOptions::new()).unwrap_err();
+
+	assert_eq!(
+		err.category(),
+		ErrorCategory::MalformedTag {
+			tag_type: Some(TagType::Ape)
+		}
+	);
+	assert_eq!(err.category().code(), "malformed_tag");
+
+	// The UTF-8 error is kept
+	let source = err.source().expect("error should have a source");
+	assert!(source.source().is_some());
+}
+
+#[test_log::test]
+fn malformed_id3v2_frame() {
+	let mut content = vec![0]; // Latin-1
+	content.extend(b"eng");
+	content.push(2); // Absolute milliseconds
+	content.push(1); // Lyrics
+	content.push(0); // Empty description
+	content.extend(b"Foo\0");
+	content.extend([0, 0]); // Truncated timestamp
+
+	let err = SynchronizedTextFrame::parse(&content, FrameFlags::default()).unwrap_err();
+
+	assert_eq!(
+		err.category(),
+		ErrorCategory::MalformedTag {
+			tag_type: Some(TagType::Id3v2)
+		}
+	);
+	assert_eq!(io_source(&err), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test_log::test]
+fn malformed_vorbis_comments() {
+	let comment = b"T\x7ETLE=Foo title";
+
+	let mut bytes = Vec::new();
+	bytes.extend(0_u32.to_le_bytes()); // Empty vendor
+	bytes.extend(1_u32.to_le_bytes());
+	bytes.extend((comment.len() as u32).to_le_bytes());
+	bytes.extend(comment);
+
+	let len = bytes.len() as u64;
+	let err = VorbisComments::parse(
+		&mut Cursor::new(bytes),
+		len,
+		ParseOptions::new().parsing_mode(ParsingMode::Strict),
+	)
+	.unwrap_err();
+
+	assert_eq!(
+		err.category(),
+		ErrorCategory::MalformedTag {
+			tag_type: Some(TagType::VorbisComments)
+		}
+	);
+}
+
+#[test_log::test]
+fn too_large() {
+	let Err(err) = Probe::open("tests/files/assets/minimal/full_test.flac")
+		.unwrap()
+		.options(
+			ParseOptions::new()
+				.max_tag_size(16)
+				.parsing_mode(ParsingMode::Strict),
+		)
+		.read()
+	else {
+		panic!("reading should fail");
+	};
+
+	assert_eq!(err.category(), ErrorCategory::TooLarge);
+	assert_eq!(err.category().code(), "too_large");
+}
+
+#[test_log::test]
+fn not_writable() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let mut tag = Tag::new(TagType::Ape);
+	tag.set_artist(String::from("Foo artist"));
+
+	let err = tag.save_to(&mut file, WriteOptions::default()).unwrap_err();
+
+	assert_eq!(err.category(), ErrorCategory::NotWritable);
+	assert_eq!(err.category().code(), "not_writable");
+}
@@ -1,12 +1,22 @@
 use crate::temp_file;
+use crate::util::SharedBuffer;
 
 use std::fs::File;
-use std::io::Seek;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
-use lofty::config::{ParseOptions, ParsingMode, WriteOptions};
-use lofty::flac::FlacFile;
-use lofty::ogg::VorbisComments;
+use lofty::config::{
+	InvalidFieldNamePolicy, IoHint, ParseOptions, ParsingMode, PictureDataPolicy, WriteOptions,
+};
+use lofty::error::{ErrorKind, ParseDiagnostic};
+use lofty::file::TaggedFile;
+use lofty::flac::{CommentPicturePolicy, CueSheet, FlacFile};
+use lofty::ogg::{OggPictureStorage, VorbisComments};
+use lofty::picture::{MimeType, Picture, PictureInformation, PictureType};
 use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::tag::{
+	ItemKey, ItemValue, PatchChange, PatchOperation, Tag, TagItem, TagPatch, TagPlacement, TagType,
+};
 
 #[test_log::test]
 fn multiple_vorbis_comments() {
@@ -34,6 +44,30 @@ fn multiple_vorbis_comments() {
 	);
 }
 
+#[test_log::test]
+fn multiple_vorbis_comments_diagnostics() {
+	let file = Probe::open("tests/files/assets/two_vorbis_comments.flac")
+		.unwrap()
+		.options(ParseOptions::new().read_properties(false))
+		.read()
+		.unwrap();
+
+	assert_eq!(
+		file.diagnostics(),
+		&[ParseDiagnostic::MultipleVorbisCommentBlocks { offset: 73 }]
+	);
+
+	// Reading the file directly reports the same diagnostics
+	let mut file = File::open("tests/files/assets/two_vorbis_comments.flac").unwrap();
+	let (_, diagnostics) =
+		FlacFile::read_from_with_diagnostics(&mut file, ParseOptions::new().read_properties(false))
+			.unwrap();
+	assert_eq!(
+		diagnostics,
+		[ParseDiagnostic::MultipleVorbisCommentBlocks { offset: 73 }]
+	);
+}
+
 #[test_log::test]
 fn read_no_properties() {
 	crate::no_properties_test!("tests/files/assets/minimal/full_test.flac");
@@ -44,6 +78,32 @@ fn read_no_tags() {
 	crate::no_tag_test!("tests/files/assets/minimal/full_test.flac");
 }
 
+#[test_log::test]
+fn tag_locations() {
+	let content = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+	let file = Probe::new(std::io::Cursor::new(&content))
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let [location] = file.tag_locations() else {
+		panic!("Expected a single tag location");
+	};
+
+	assert_eq!(location.tag_type(), TagType::VorbisComments);
+	assert_eq!(location.placement(), TagPlacement::Embedded);
+
+	// The span covers the entire metadata block, including its header
+	let start = location.offset() as usize;
+	let block_header = &content[start..start + 4];
+	assert_eq!(block_header[0] & 0x7F, 4);
+
+	let block_size = u32::from_be_bytes([0, block_header[1], block_header[2], block_header[3]]);
+	assert_eq!(location.len(), u64::from(block_size) + 4);
+}
+
 #[test_log::test]
 fn retain_vendor_string() {
 	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
@@ -64,3 +124,1008 @@ fn retain_vendor_string() {
 	// The vendor string should be retained
 	assert_eq!(f.vorbis_comments().unwrap().vendor(), "Lavf58.76.100");
 }
+
+#[test_log::test]
+fn cuesheet_round_trip() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let cue_sheet = CueSheet::parse_text(
+		"PERFORMER \"Foo Artist\"\n\
+		 TITLE \"Bar \"Live\" Album\"\n\
+		 FILE \"full_test.flac\" WAVE\n\
+		 TRACK 01 AUDIO\n\
+		 INDEX 01 00:00:00\n\
+		 TRACK 02 AUDIO\n\
+		 PREGAP 00:01:00\n\
+		 INDEX 01 00:00:37\n",
+	)
+	.unwrap();
+
+	let mut tag = VorbisComments::new();
+	tag.set_cuesheet(&cue_sheet);
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let read_cue_sheet = f.vorbis_comments().unwrap().cuesheet().unwrap().unwrap();
+	assert_eq!(read_cue_sheet, cue_sheet);
+	assert_eq!(read_cue_sheet.title.as_deref(), Some("Bar \"Live\" Album"));
+}
+
+// A file with a front cover in a picture block, and a different one in a `METADATA_BLOCK_PICTURE` entry
+fn conflicting_pictures_file() -> File {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+	let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	f.insert_picture(
+		icon(PictureType::CoverFront, vec![1; 16]),
+		Some(PictureInformation::default()),
+	)
+	.unwrap();
+
+	// Some encoders wrap the base64 across multiple lines
+	let encoded = icon(PictureType::CoverFront, vec![2; 16])
+		.as_flac_bytes(PictureInformation::default(), true);
+	let wrapped = encoded
+		.chunks(20)
+		.map(|line| std::str::from_utf8(line).unwrap())
+		.collect::<Vec<_>>()
+		.join("\r\n");
+	f.vorbis_comments_mut()
+		.unwrap()
+		.push(String::from("METADATA_BLOCK_PICTURE"), wrapped);
+
+	file.rewind().unwrap();
+	f.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	file
+}
+
+fn picture_data(pictures: &[&(Picture, PictureInformation)]) -> Vec<(PictureType, Vec<u8>)> {
+	pictures
+		.iter()
+		.map(|(picture, _)| (picture.pic_type(), picture.data().to_vec()))
+		.collect()
+}
+
+#[test_log::test]
+fn all_pictures_with_conflicting_pair() {
+	let mut file = conflicting_pictures_file();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	assert_eq!(f.pictures().len(), 1);
+	assert_eq!(f.vorbis_comments().unwrap().pictures().len(), 1);
+
+	// Both pictures are exposed, the picture block first
+	assert_eq!(
+		picture_data(&f.all_pictures()),
+		[
+			(PictureType::CoverFront, vec![1; 16]),
+			(PictureType::CoverFront, vec![2; 16])
+		]
+	);
+}
+
+#[test_log::test]
+fn all_pictures_dedupes() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+	let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let cover = icon(PictureType::CoverFront, vec![1; 16]);
+	f.insert_picture(cover.clone(), Some(PictureInformation::default()))
+		.unwrap();
+	f.vorbis_comments_mut()
+		.unwrap()
+		.insert_picture(cover, Some(PictureInformation::default()))
+		.unwrap();
+
+	assert_eq!(
+		picture_data(&f.all_pictures()),
+		[(PictureType::CoverFront, vec![1; 16])]
+	);
+
+	// The picture is only written once
+	file.rewind().unwrap();
+	f.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(f.pictures().len(), 1);
+	assert!(f.vorbis_comments().unwrap().pictures().is_empty());
+}
+
+#[test_log::test]
+fn replace_picture_with_conflicting_pair() {
+	for policy in [CommentPicturePolicy::Remove, CommentPicturePolicy::Update] {
+		let mut file = conflicting_pictures_file();
+		let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+		f.replace_picture(
+			icon(PictureType::CoverFront, vec![3; 16]),
+			Some(PictureInformation::default()),
+			policy,
+		)
+		.unwrap();
+
+		assert_eq!(
+			picture_data(&f.all_pictures()),
+			[(PictureType::CoverFront, vec![3; 16])]
+		);
+
+		let comment_pictures = f.vorbis_comments().unwrap().pictures().len();
+		match policy {
+			CommentPicturePolicy::Remove => assert_eq!(comment_pictures, 0),
+			_ => assert_eq!(comment_pictures, 1),
+		}
+
+		// No stale picture is left behind in the file
+		file.rewind().unwrap();
+		f.save_to(&mut file, WriteOptions::new()).unwrap();
+
+		file.rewind().unwrap();
+		let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+		assert_eq!(
+			picture_data(&f.all_pictures()),
+			[(PictureType::CoverFront, vec![3; 16])]
+		);
+		assert_eq!(f.pictures().len(), 1);
+	}
+}
+
+#[test_log::test]
+fn remove_all_pictures_of_type() {
+	let mut file = conflicting_pictures_file();
+	let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	f.remove_all_pictures_of_type(PictureType::CoverFront);
+	assert!(f.all_pictures().is_empty());
+}
+
+// Large enough that reading it into memory would be noticeable
+const LARGE_PICTURE_SIZE: usize = 8 * 1024 * 1024;
+
+fn large_picture_file() -> (File, Vec<u8>) {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+	let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let data = (0..LARGE_PICTURE_SIZE).map(|i| i as u8).collect::<Vec<_>>();
+	f.insert_picture(
+		icon(PictureType::CoverFront, data.clone()),
+		Some(PictureInformation::default()),
+	)
+	.unwrap();
+
+	file.rewind().unwrap();
+	f.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	(file, data)
+}
+
+#[test_log::test]
+fn skip_picture_data() {
+	let (mut file, _) = large_picture_file();
+	let f = FlacFile::read_from(
+		&mut file,
+		ParseOptions::new().picture_data_policy(PictureDataPolicy::Skip),
+	)
+	.unwrap();
+
+	let (picture, _) = &f.pictures()[0];
+	assert_eq!(picture.pic_type(), PictureType::CoverFront);
+	assert_eq!(picture.mime_type(), Some(&MimeType::Png));
+	assert!(picture.data().is_empty());
+	assert_eq!(picture.skipped_data_len(), Some(LARGE_PICTURE_SIZE as u64));
+
+	// The rest of the file is unaffected
+	assert_eq!(f.properties().sample_rate(), 48000);
+	assert!(f.vorbis_comments().is_some());
+
+	// The picture can't be written back without its data
+	file.rewind().unwrap();
+	let err = f.save_to(&mut file, WriteOptions::new()).unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::SkippedPictureData));
+}
+
+#[test_log::test]
+fn pictures_to_writers() {
+	let (mut file, data) = large_picture_file();
+
+	let buffer = SharedBuffer::default();
+	let writer = buffer.clone();
+	let tagged_file = Probe::new(&mut file)
+		.guess_file_type()
+		.unwrap()
+		.pictures_to_writers(move |picture| {
+			assert_eq!(picture.skipped_data_len(), Some(LARGE_PICTURE_SIZE as u64));
+			Some(writer.clone())
+		})
+		.unwrap();
+
+	assert_eq!(buffer.contents(), data);
+
+	// The pictures are still exposed, without their data
+	let tag = tagged_file.tag(TagType::VorbisComments).unwrap();
+	assert_eq!(tag.picture_count(), 1);
+	assert!(tag.pictures()[0].data().is_empty());
+}
+
+fn icon(pic_type: PictureType, data: Vec<u8>) -> Picture {
+	Picture::new_unchecked(pic_type, Some(MimeType::Png), None, data)
+}
+
+#[test_log::test]
+fn duplicate_icons() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let mut tag = Tag::new(TagType::VorbisComments);
+	for _ in 0..2 {
+		tag.push_picture(icon(PictureType::Icon, vec![0; 16]));
+		tag.push_picture(icon(PictureType::OtherIcon, vec![1; 16]));
+	}
+	tag.push_picture(icon(PictureType::CoverFront, vec![2; 16]));
+
+	// Only one `Icon` and `OtherIcon` are allowed
+	let err = tag.save_to(&mut file, WriteOptions::new()).unwrap_err();
+	match err.kind() {
+		ErrorKind::DuplicatePictureTypes(types) => {
+			assert_eq!(types, &[PictureType::Icon, PictureType::OtherIcon])
+		},
+		kind => panic!("unexpected error: {kind:?}"),
+	}
+
+	// The extras can be dropped automatically
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new().drop_invalid_icons(true))
+		.unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let pictures = f.pictures();
+	assert_eq!(pictures.len(), 3);
+	assert_eq!(pictures[0].0.pic_type(), PictureType::Icon);
+	assert_eq!(pictures[1].0.pic_type(), PictureType::OtherIcon);
+	assert_eq!(pictures[2].0.pic_type(), PictureType::CoverFront);
+}
+
+#[test_log::test]
+fn invalid_file_icon() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	// A 640x628 PNG, the dimensions of which are known
+	let png = std::fs::read("tests/picture/assets/png_640x628.png").unwrap();
+
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.push_picture(icon(PictureType::Icon, png));
+
+	let err = tag.save_to(&mut file, WriteOptions::new()).unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::InvalidFileIcon));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new().drop_invalid_icons(true))
+		.unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(f.pictures().is_empty());
+}
+
+fn invalid_field_names_tag() -> Tag {
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.set_artist(String::from("Foo artist"));
+	for key in ["MY=KEY", "TÍTULO"] {
+		tag.push_unchecked(TagItem::new(
+			ItemKey::Unknown(String::from(key)),
+			ItemValue::Text(String::from("Foo")),
+		));
+	}
+
+	tag
+}
+
+#[test_log::test]
+fn invalid_field_name_policy() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+	let tag = invalid_field_names_tag();
+
+	let err = tag
+		.save_to(
+			&mut file,
+			WriteOptions::new().invalid_field_name_policy(InvalidFieldNamePolicy::Error),
+		)
+		.unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::InvalidVorbisFieldName(key) if key == "MY=KEY"));
+
+	// Invalid fields are skipped by default
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let vorbis_comments = f.vorbis_comments().unwrap();
+	assert_eq!(vorbis_comments.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(vorbis_comments.len(), 1);
+
+	file.rewind().unwrap();
+	tag.save_to(
+		&mut file,
+		WriteOptions::new().invalid_field_name_policy(InvalidFieldNamePolicy::Sanitize),
+	)
+	.unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let vorbis_comments = f.vorbis_comments().unwrap();
+	assert_eq!(vorbis_comments.get("MY_KEY"), Some("Foo"));
+	assert_eq!(vorbis_comments.get("T_TULO"), Some("Foo"));
+}
+
+#[test_log::test]
+fn read_invalid_field_name() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.push_unchecked(TagItem::new(
+		ItemKey::Unknown(String::from("MY_KEY")),
+		ItemValue::Text(String::from("Foo")),
+	));
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	// Replace the '_' with '~', which is outside of the valid range
+	let mut content = read_to_end(&mut file);
+	let pos = content
+		.windows(6)
+		.position(|window| window == b"MY_KEY")
+		.unwrap();
+	content[pos + 2] = b'~';
+
+	// Invalid fields are discarded by default...
+	let tagged_file = Probe::new(std::io::Cursor::new(&content))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	let tag = tagged_file.tag(TagType::VorbisComments).unwrap();
+	assert!(tag
+		.get_string(&ItemKey::Unknown(String::from("MY~KEY")))
+		.is_none());
+	assert_eq!(
+		tagged_file.diagnostics(),
+		[ParseDiagnostic::InvalidVorbisCommentField {
+			key: Some(String::from("MY~KEY"))
+		}]
+	);
+
+	// ...but kept with `ParsingMode::Relaxed`
+	let tagged_file = Probe::new(std::io::Cursor::new(&content))
+		.options(ParseOptions::new().parsing_mode(ParsingMode::Relaxed))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	let tag = tagged_file.tag(TagType::VorbisComments).unwrap();
+	assert_eq!(
+		tag.get_string(&ItemKey::Unknown(String::from("MY~KEY"))),
+		Some("Foo")
+	);
+	assert_eq!(
+		tagged_file.diagnostics(),
+		[ParseDiagnostic::InvalidVorbisFieldName {
+			key: String::from("MY~KEY")
+		}]
+	);
+}
+
+#[test_log::test]
+fn strip_all_tags() {
+	let content = std::fs::read("tests/files/assets/flac_with_id3v2.flac").unwrap();
+	let mut file = temp_file!("tests/files/assets/flac_with_id3v2.flac");
+
+	let original = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	file.rewind().unwrap();
+	assert!(original.id3v2().is_some());
+	assert!(original.vorbis_comments().is_some());
+
+	let removed = TaggedFile::strip_all_tags(&mut file).unwrap();
+	assert_eq!(removed, [TagType::Id3v2, TagType::VorbisComments]);
+
+	// The ID3v2 tag is cut out, but the metadata blocks holding a tag become padding of the
+	// same size, so the audio doesn't move
+	let mut original_stream = tempfile::tempfile().unwrap();
+	let flac_start = content.windows(4).position(|w| w == b"fLaC").unwrap();
+	original_stream.write_all(&content[flac_start..]).unwrap();
+
+	let (original_blocks, original_audio_start) = metadata_blocks(&mut original_stream);
+	let (blocks, audio_start) = metadata_blocks(&mut file);
+	assert_eq!(audio_start, original_audio_start);
+	assert_eq!(blocks.len(), original_blocks.len());
+	for ((block_type, size), (original_type, original_size)) in
+		blocks.into_iter().zip(original_blocks)
+	{
+		assert_eq!(size, original_size);
+		match original_type {
+			4 | 6 => assert_eq!(block_type, 1),
+			_ => assert_eq!(block_type, original_type),
+		}
+	}
+
+	assert_eq!(
+		read_to_end(&mut file)[audio_start..],
+		content[flac_start + audio_start..]
+	);
+
+	file.rewind().unwrap();
+	let stripped = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(stripped.id3v2().is_none());
+	assert!(stripped.vorbis_comments().is_none());
+	assert_eq!(stripped.properties(), original.properties());
+}
+
+#[test_log::test]
+fn size_limits() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let mut flac_file = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	file.rewind().unwrap();
+
+	flac_file
+		.insert_picture(icon(PictureType::CoverFront, vec![0; 100]), None)
+		.unwrap();
+	flac_file.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	let mut read = |mut parse_options: ParseOptions| {
+		file.rewind().unwrap();
+		FlacFile::read_from(&mut file, parse_options.read_properties(false))
+	};
+
+	// Pictures are items
+	let item_limit = || ParseOptions::new().max_item_size(64);
+	let Err(err) = read(item_limit().parsing_mode(ParsingMode::Strict)) else {
+		panic!("expected the size limit to be enforced");
+	};
+	assert!(matches!(err.kind(), ErrorKind::TooLarge { limit: 64, .. }));
+
+	let f = read(item_limit()).unwrap();
+	assert!(f.pictures().is_empty());
+	assert!(f.vorbis_comments().is_some());
+
+	// The Vorbis Comments block is a tag
+	let tag_limit = || ParseOptions::new().max_tag_size(16);
+	let Err(err) = read(tag_limit().parsing_mode(ParsingMode::Strict)) else {
+		panic!("expected the size limit to be enforced");
+	};
+	assert!(matches!(err.kind(), ErrorKind::TooLarge { limit: 16, .. }));
+
+	let f = read(tag_limit()).unwrap();
+	assert!(f.vorbis_comments().is_none());
+	assert_eq!(f.pictures().len(), 1);
+}
+
+// Returns the (type, size) of each metadata block, and the offset of the first audio frame
+fn metadata_blocks(file: &mut File) -> (Vec<(u8, u32)>, usize) {
+	let bytes = read_to_end(file);
+	assert_eq!(&bytes[..4], b"fLaC");
+
+	let mut blocks = Vec::new();
+	let mut pos = 4;
+	loop {
+		let header = bytes[pos];
+		let size = u32::from_be_bytes([0, bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]);
+		blocks.push((header & 0x7F, size));
+		pos += 4 + size as usize;
+
+		if header & 0x80 != 0 {
+			break;
+		}
+	}
+
+	(blocks, pos)
+}
+
+fn read_to_end(file: &mut File) -> Vec<u8> {
+	use std::io::Read;
+
+	file.rewind().unwrap();
+	let mut bytes = Vec::new();
+	file.read_to_end(&mut bytes).unwrap();
+	bytes
+}
+
+#[test_log::test]
+fn write_in_place_with_padding() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	// STREAMINFO, VORBIS_COMMENT, PADDING
+	let (blocks, audio_start) = metadata_blocks(&mut file);
+	assert_eq!(blocks, [(0, 34), (4, 42), (1, 8171)]);
+	let original = read_to_end(&mut file);
+
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.set_artist(String::from("Foo artist"));
+	tag.push_picture(Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		None,
+		vec![1; 4096],
+	));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	// The new blocks are absorbed by the padding, so the audio stays where it was
+	let (blocks, new_audio_start) = metadata_blocks(&mut file);
+	assert_eq!(new_audio_start, audio_start);
+	assert_eq!(
+		blocks.iter().map(|(ty, _)| *ty).collect::<Vec<_>>(),
+		[0, 4, 6, 1]
+	);
+
+	let written = read_to_end(&mut file);
+	assert_eq!(written.len(), original.len());
+	assert_eq!(written[audio_start..], original[audio_start..]);
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(
+		f.vorbis_comments().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert_eq!(f.pictures().len(), 1);
+
+	// Shrinking the tag grows the padding
+	file.rewind().unwrap();
+	Tag::new(TagType::VorbisComments)
+		.save_to(&mut file, WriteOptions::new())
+		.unwrap();
+
+	let (blocks, new_audio_start) = metadata_blocks(&mut file);
+	assert_eq!(new_audio_start, audio_start);
+	assert_eq!(blocks, [(0, 34), (1, 8171 + 42 + 4)]);
+	assert_eq!(
+		read_to_end(&mut file)[audio_start..],
+		original[audio_start..]
+	);
+}
+
+#[test_log::test]
+fn write_without_enough_padding() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let (_, audio_start) = metadata_blocks(&mut file);
+	let original = read_to_end(&mut file);
+
+	// Larger than the existing padding
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.push_picture(Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		None,
+		vec![1; 16384],
+	));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new().preferred_padding(2048))
+		.unwrap();
+
+	// The file is rewritten with fresh padding
+	let (blocks, new_audio_start) = metadata_blocks(&mut file);
+	assert_eq!(
+		blocks.iter().map(|(ty, _)| *ty).collect::<Vec<_>>(),
+		[0, 6, 1]
+	);
+	assert_eq!(blocks.last(), Some(&(1, 2048)));
+	assert!(new_audio_start > audio_start);
+	assert_eq!(
+		read_to_end(&mut file)[new_audio_start..],
+		original[audio_start..]
+	);
+}
+
+#[test_log::test]
+fn write_without_padding() {
+	let mut file = temp_file!("tests/files/assets/zero/zero.flac");
+
+	// STREAMINFO, SEEKTABLE, VORBIS_COMMENT
+	let (blocks, audio_start) = metadata_blocks(&mut file);
+	assert_eq!(blocks, [(0, 34), (3, 18), (4, 40)]);
+	let original = read_to_end(&mut file);
+
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.set_artist(String::from("Foo artist"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	// The SEEKTABLE is kept in place, and 4 KiB of padding is added for future edits
+	let (blocks, new_audio_start) = metadata_blocks(&mut file);
+	assert_eq!(
+		blocks.iter().map(|(ty, _)| *ty).collect::<Vec<_>>(),
+		[0, 3, 4, 1]
+	);
+	assert_eq!(blocks.last(), Some(&(1, 4096)));
+	assert_eq!(
+		read_to_end(&mut file)[new_audio_start..],
+		original[audio_start..]
+	);
+
+	// The next edit can now be done in place
+	let len = read_to_end(&mut file).len();
+	tag.set_title(String::from("Foo title"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+	assert_eq!(metadata_blocks(&mut file).1, new_audio_start);
+	assert_eq!(read_to_end(&mut file).len(), len);
+}
+
+#[test_log::test]
+fn write_without_padding_explicit_size() {
+	let mut file = temp_file!("tests/files/assets/zero/zero.flac");
+
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.set_artist(String::from("Foo artist"));
+
+	// An explicit size is used as-is, even if it matches `WriteOptions::DEFAULT_PREFERRED_PADDING`
+	tag.save_to(
+		&mut file,
+		WriteOptions::new().preferred_padding(WriteOptions::DEFAULT_PREFERRED_PADDING),
+	)
+	.unwrap();
+
+	let (blocks, _) = metadata_blocks(&mut file);
+	assert_eq!(blocks.last(), Some(&(1, 1024)));
+}
+
+#[test_log::test]
+fn read_channel_mask() {
+	use lofty::properties::ChannelMask;
+	use std::io::Write;
+
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+
+	// Change the STREAMINFO channel count from 2 to 6
+	content[20] = (content[20] & 0xF1) | (5 << 1);
+
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&content).unwrap();
+	file.rewind().unwrap();
+
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(f.properties().channels(), 6);
+	assert_eq!(
+		f.properties().channel_mask(),
+		Some(ChannelMask::five_point_one())
+	);
+
+	// The default layout can be overridden by a Vorbis Comment, here with 6.0
+	let mut tag = f.vorbis_comments().unwrap().clone();
+	tag.insert(
+		String::from("WAVEFORMATEXTENSIBLE_CHANNEL_MASK"),
+		String::from("0x0707"),
+	);
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(
+		f.properties().channel_mask(),
+		Some(
+			ChannelMask::linear_surround()
+				| ChannelMask::BACK_CENTER
+				| ChannelMask::SIDE_LEFT
+				| ChannelMask::SIDE_RIGHT
+		)
+	);
+}
+
+#[test_log::test]
+fn streaming_remote_seeks() {
+	// A reader where every seek would be a new request
+	struct SeekCounter<R> {
+		inner: R,
+		seeks: usize,
+	}
+
+	impl<R: Read> Read for SeekCounter<R> {
+		fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+			self.inner.read(buf)
+		}
+	}
+
+	impl<R: Seek> Seek for SeekCounter<R> {
+		fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+			// Getting the current position doesn't require a request
+			if pos != SeekFrom::Current(0) {
+				self.seeks += 1;
+			}
+
+			self.inner.seek(pos)
+		}
+	}
+
+	fn read(parse_options: ParseOptions) -> (TaggedFile, usize) {
+		let mut reader = SeekCounter {
+			inner: File::open("tests/files/assets/minimal/full_test.flac").unwrap(),
+			seeks: 0,
+		};
+
+		let file = Probe::new(&mut reader)
+			.options(parse_options)
+			.guess_file_type()
+			.unwrap()
+			.read()
+			.unwrap();
+
+		(file, reader.seeks)
+	}
+
+	let (local, local_seeks) = read(ParseOptions::new());
+	let (remote, remote_seeks) = read(ParseOptions::new().io_hint(IoHint::StreamingRemote));
+
+	// Rewinding after probing, checking for an ID3v2 tag, and skipping the PADDING block
+	assert!(remote_seeks <= 3);
+	assert!(remote_seeks < local_seeks);
+
+	// The tags and duration are still available, but the bitrates need the length of the file
+	assert_eq!(remote.tags().len(), local.tags().len());
+	assert_eq!(
+		remote.primary_tag().unwrap().title(),
+		local.primary_tag().unwrap().title()
+	);
+	assert_eq!(
+		remote.properties().duration(),
+		local.properties().duration()
+	);
+	assert_eq!(
+		remote.properties().sample_rate(),
+		local.properties().sample_rate()
+	);
+	assert_eq!(remote.properties().overall_bitrate(), Some(0));
+	assert_eq!(remote.properties().audio_bitrate(), Some(0));
+}
+
+#[test_log::test]
+fn truncated_stream_relaxed() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+
+	// Cut the file off in the padding block, after the Vorbis Comments
+	content.truncate(4096);
+
+	let read = |parsing_mode| {
+		Probe::new(std::io::Cursor::new(&content))
+			.options(ParseOptions::new().parsing_mode(parsing_mode))
+			.guess_file_type()
+			.unwrap()
+			.read()
+	};
+
+	assert!(read(ParsingMode::Strict).is_err());
+
+	// The tags are still available, with the padding block truncated
+	let tagged_file = read(ParsingMode::Relaxed).unwrap();
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert_eq!(tagged_file.properties().audio_bitrate(), Some(0));
+	assert!(matches!(
+		tagged_file.diagnostics(),
+		[ParseDiagnostic::OversizedFlacBlock { .. }]
+	));
+}
+
+// A picture block claiming the maximum size, in a 2KB file
+fn oversized_block_file() -> Vec<u8> {
+	let content = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+
+	// "fLaC" + STREAMINFO
+	let mut file = content[..42].to_vec();
+	file.extend([0x86, 0xFF, 0xFF, 0xFF]);
+	file.extend([0; 2048]);
+	file
+}
+
+#[test_log::test]
+fn oversized_block() {
+	let content = oversized_block_file();
+
+	for read_cover_art in [true, false] {
+		let read = |parsing_mode| {
+			Probe::new(std::io::Cursor::new(&content))
+				.options(
+					ParseOptions::new()
+						.parsing_mode(parsing_mode)
+						.read_cover_art(read_cover_art),
+				)
+				.guess_file_type()
+				.unwrap()
+				.read()
+		};
+
+		let Err(err) = read(ParsingMode::Strict) else {
+			panic!("Oversized block should be an error");
+		};
+		assert!(matches!(err.kind(), ErrorKind::SizeMismatch));
+
+		// The block is truncated to the end of the stream
+		let tagged_file = read(ParsingMode::Relaxed).unwrap();
+		assert!(tagged_file
+			.diagnostics()
+			.contains(&ParseDiagnostic::OversizedFlacBlock {
+				offset: 42,
+				size: 0xFF_FFFF,
+				remaining: 2048,
+			}));
+		assert_eq!(tagged_file.properties().sample_rate(), Some(48000));
+	}
+}
+
+// A `CUESHEET` block with a track for each ISRC, followed by the lead-out
+fn cue_sheet_block(isrcs: &[&str]) -> Vec<u8> {
+	const LEAD_OUT_TRACK_NUMBER: u8 = 170;
+
+	// Media catalog number, lead-in samples, flags and reserved
+	let mut content = vec![0; 395];
+	content.push(isrcs.len() as u8 + 1);
+
+	let mut push_track = |number: u8, isrc: &str, index_points: u8| {
+		// Offset
+		content.extend(0_u64.to_be_bytes());
+		content.push(number);
+
+		let mut isrc_bytes = [0; 12];
+		isrc_bytes[..isrc.len()].copy_from_slice(isrc.as_bytes());
+		content.extend(isrc_bytes);
+
+		// Flags and reserved
+		content.extend([0; 14]);
+
+		content.push(index_points);
+		content.extend(vec![0; usize::from(index_points) * 12]);
+	};
+
+	for (number, isrc) in (1..).zip(isrcs) {
+		push_track(number, isrc, 1);
+	}
+	push_track(LEAD_OUT_TRACK_NUMBER, "", 0);
+
+	let mut block = vec![5];
+	block.extend(&(content.len() as u32).to_be_bytes()[1..]);
+	block.extend(content);
+	block
+}
+
+// `full_test.flac`, with a `CUESHEET` block following the `STREAMINFO` block
+fn flac_with_cue_sheet(isrcs: &[&str]) -> Cursor<Vec<u8>> {
+	let mut file = std::fs::read("tests/files/assets/minimal/full_test.flac").unwrap();
+
+	// "fLaC" (4), STREAMINFO header (4), STREAMINFO (34)
+	const STREAMINFO_END: usize = 42;
+	file.splice(STREAMINFO_END..STREAMINFO_END, cue_sheet_block(isrcs));
+
+	Cursor::new(file)
+}
+
+#[test_log::test]
+fn isrc_from_cue_sheet() {
+	let mut file = flac_with_cue_sheet(&["GBAYE9700001"]);
+	let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	assert!(f.vorbis_comments().unwrap().get("ISRC").is_none());
+	assert_eq!(f.isrc(), Some("GBAYE9700001"));
+
+	// The tags take precedence
+	f.vorbis_comments_mut()
+		.unwrap()
+		.insert(String::from("ISRC"), String::from("USRC17607839"));
+	assert_eq!(f.isrc(), Some("USRC17607839"));
+
+	// The cue sheet is left untouched when writing
+	file.rewind().unwrap();
+	f.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let mut f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	f.vorbis_comments_mut()
+		.unwrap()
+		.remove("ISRC")
+		.for_each(drop);
+	assert_eq!(f.isrc(), Some("GBAYE9700001"));
+
+	// And it's never part of the generic tags
+	let tagged_file: TaggedFile = f.into();
+	assert!(tagged_file
+		.tags()
+		.iter()
+		.all(|tag| tag.get(&ItemKey::Isrc).is_none()));
+}
+
+#[test_log::test]
+fn isrc_from_cue_sheet_multiple_tracks() {
+	// The ISRCs belong to individual tracks, not the entire file
+	let mut file = flac_with_cue_sheet(&["GBAYE9700001", "GBAYE9700002"]);
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(f.isrc(), None);
+
+	// A single track without an ISRC
+	let mut file = flac_with_cue_sheet(&[""]);
+	let f = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(f.isrc(), None);
+}
+
+#[test_log::test]
+fn apply_patch() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.flac");
+
+	let png = std::fs::read("tests/picture/assets/png_640x628.png").unwrap();
+	let picture = |pic_type| {
+		let mut picture = Picture::from_reader(&mut &png[..]).unwrap();
+		picture.set_pic_type(pic_type);
+		picture
+	};
+
+	let patch = TagPatch::new()
+		.operation(PatchOperation::SetItem(
+			ItemKey::TrackTitle,
+			ItemValue::Text(String::from("Patched title")),
+		))
+		.operation(PatchOperation::RemoveItem(ItemKey::TrackArtist))
+		.operation(PatchOperation::AddPicture(picture(PictureType::CoverFront)))
+		.operation(PatchOperation::AddPicture(picture(PictureType::CoverBack)))
+		.operation(PatchOperation::RemovePictureType(PictureType::CoverBack))
+		.operation(PatchOperation::SetItem(
+			ItemKey::Comment,
+			ItemValue::Text(String::from("Patched comment")),
+		));
+
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert_eq!(summary.tag_type(), TagType::VorbisComments);
+	assert!(summary.written());
+	assert_eq!(
+		summary.changes(),
+		&[
+			PatchChange::ItemSet(ItemKey::TrackTitle),
+			PatchChange::ItemRemoved(ItemKey::TrackArtist),
+			PatchChange::PictureAdded(PictureType::CoverFront),
+			PatchChange::PictureAdded(PictureType::CoverBack),
+			PatchChange::PicturesRemoved(PictureType::CoverBack),
+			PatchChange::ItemSet(ItemKey::Comment),
+		]
+	);
+
+	file.rewind().unwrap();
+	let flac_file = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let vorbis_comments = flac_file.vorbis_comments().unwrap();
+	assert_eq!(vorbis_comments.title().as_deref(), Some("Patched title"));
+	assert_eq!(
+		vorbis_comments.comment().as_deref(),
+		Some("Patched comment")
+	);
+	assert!(vorbis_comments.artist().is_none());
+
+	// The operations are applied in order, so only the front cover is left
+	let pictures = flac_file.all_pictures();
+	assert_eq!(pictures.len(), 1);
+	assert_eq!(pictures[0].0, picture(PictureType::CoverFront));
+
+	// The audio is untouched
+	let original = FlacFile::read_from(
+		&mut File::open("tests/files/assets/minimal/full_test.flac").unwrap(),
+		ParseOptions::new(),
+	)
+	.unwrap();
+	assert_eq!(
+		flac_file.properties().signature(),
+		original.properties().signature()
+	);
+	assert_eq!(
+		flac_file.properties().audio_bitrate(),
+		original.properties().audio_bitrate()
+	);
+}
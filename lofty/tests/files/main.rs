@@ -3,12 +3,23 @@
 mod aac;
 mod aiff;
 mod ape;
+mod asf;
+#[cfg(feature = "async")]
+mod async_io;
+mod capabilities;
+mod dff;
+mod dsf;
+mod duration;
+mod errors;
 mod flac;
 mod mp4;
 mod mpc;
 mod mpeg;
 mod ogg;
+mod shn;
+mod tta;
 pub(crate) mod util;
+mod verify;
 mod wav;
 mod wavpack;
 mod zero_sized;
@@ -1,11 +1,15 @@
 use crate::{set_artist, temp_file, verify_artist};
-use lofty::config::ParseOptions;
+use lofty::config::{apply_global_options, GlobalOptions, ParseOptions, ParsingMode, WriteOptions};
+use lofty::error::ParseDiagnostic;
 use lofty::file::FileType;
+use lofty::mp4::{Atom, AtomData, AtomIdent, DataType, GaplessInfo, Ilst, Mp4Chapter, Mp4File};
 use lofty::prelude::*;
 use lofty::probe::Probe;
-use lofty::tag::TagType;
+use lofty::tag::{ItemKey, ItemValue, PatchOperation, TagPatch, TagType};
 
+use std::borrow::Cow;
 use std::io::Seek;
+use std::time::Duration;
 
 #[test_log::test]
 fn read() {
@@ -68,3 +72,315 @@ fn read_no_properties() {
 fn read_no_tags() {
 	crate::no_tag_test!("tests/files/assets/minimal/m4a_codec_aac.m4a");
 }
+
+fn qt_chapters() -> Vec<Mp4Chapter> {
+	vec![
+		Mp4Chapter::new(Duration::ZERO, String::from("Intro")),
+		Mp4Chapter::new(Duration::from_millis(500), String::from("Chapter 1")),
+		Mp4Chapter::new(Duration::from_secs(1), String::from("Épilogue")),
+	]
+}
+
+#[test_log::test]
+fn read_quicktime_chapters() {
+	// This file has a text track with 3 chapters, referenced by `tref.chap` in the audio track
+	let mut file = std::fs::File::open("tests/files/assets/qt_chapters.m4b").unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	assert_eq!(mp4_file.ftyp(), "M4B ");
+	assert_eq!(mp4_file.chapters(), qt_chapters());
+
+	// The chapter track shouldn't be mistaken for the audio track
+	assert_eq!(
+		mp4_file.properties().duration(),
+		Duration::from_millis(1449)
+	);
+}
+
+#[test_log::test]
+fn read_chapters_no_tags() {
+	let mut file = std::fs::File::open("tests/files/assets/qt_chapters.m4b").unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new().read_tags(false)).unwrap();
+
+	assert!(mp4_file.chapters().is_empty());
+}
+
+#[test_log::test]
+fn write_chapters() {
+	let mut file = temp_file!("tests/files/assets/qt_chapters.m4b");
+	let mut mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let chapters = vec![
+		Mp4Chapter::new(Duration::ZERO, String::from("Opening")),
+		Mp4Chapter::new(Duration::from_millis(750), String::from("Finale")),
+	];
+
+	file.rewind().unwrap();
+	mp4_file.set_chapters(&mut file, chapters.clone()).unwrap();
+	assert_eq!(mp4_file.chapters(), chapters);
+
+	// The `chpl` atom is preferred over the chapter track
+	file.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(mp4_file.chapters(), chapters);
+
+	// The rest of the file is intact
+	assert_eq!(
+		mp4_file.properties().duration(),
+		Duration::from_millis(1449)
+	);
+	assert_eq!(
+		mp4_file.ilst().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+
+	// Writing the tag doesn't affect the chapters
+	let mut mp4_file = mp4_file;
+	mp4_file
+		.ilst_mut()
+		.unwrap()
+		.set_artist(String::from("Bar artist"));
+
+	file.rewind().unwrap();
+	mp4_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let mut mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(mp4_file.chapters(), chapters);
+	assert_eq!(
+		mp4_file.ilst().unwrap().artist().as_deref(),
+		Some("Bar artist")
+	);
+
+	// Removing the `chpl` atom exposes the chapter track again
+	file.rewind().unwrap();
+	mp4_file.set_chapters(&mut file, Vec::new()).unwrap();
+
+	file.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(mp4_file.chapters(), qt_chapters());
+}
+
+#[test_log::test]
+fn read_gapless_info() {
+	let mut file = temp_file!("tests/files/assets/minimal/m4a_codec_aac.m4a");
+
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(mp4_file.gapless_info().is_none());
+
+	// The value from an iTunes-encoded file, stored as a plain freeform atom
+	let mut ilst = Ilst::new();
+	ilst.insert(Atom::new(
+		AtomIdent::Freeform {
+			mean: Cow::Borrowed("com.apple.iTunes"),
+			name: Cow::Borrowed("iTunSMPB"),
+		},
+		AtomData::UTF8(String::from(
+			" 00000000 00000840 000001B0 000000000000C610 00000000 00000000 00000000 00000000 \
+			 00000000 00000000 00000000 00000000",
+		)),
+	));
+
+	file.rewind().unwrap();
+	ilst.save_to(&mut file, WriteOptions::default()).unwrap();
+
+	file.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(
+		mp4_file.gapless_info(),
+		Some(GaplessInfo {
+			delay: 2112,
+			padding: 432,
+			valid_samples: 50704,
+		})
+	);
+}
+
+#[test_log::test]
+fn apply_patch_keeps_freeform_atoms() {
+	// Without a companion tag, a binary freeform atom would be lost when going through a `Tag`
+	apply_global_options(GlobalOptions::new().preserve_format_specific_items(false));
+
+	let mut file = temp_file!("tests/files/assets/minimal/m4a_codec_aac.m4a");
+
+	let freeform_atom = Atom::new(
+		AtomIdent::Freeform {
+			mean: Cow::Borrowed("com.example"),
+			name: Cow::Borrowed("BINARY"),
+		},
+		AtomData::Unknown {
+			code: DataType::Reserved,
+			data: vec![1, 2, 3, 4],
+		},
+	);
+
+	let mut mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	mp4_file.ilst_mut().unwrap().insert(freeform_atom.clone());
+
+	file.rewind().unwrap();
+	mp4_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let patch = TagPatch::new().operation(PatchOperation::SetItem(
+		ItemKey::TrackTitle,
+		ItemValue::Text(String::from("Patched title")),
+	));
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert!(summary.written());
+
+	file.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut file, ParseOptions::new()).unwrap();
+	let ilst = mp4_file.ilst().unwrap();
+	assert_eq!(ilst.title().as_deref(), Some("Patched title"));
+	assert_eq!(ilst.get(freeform_atom.ident()), Some(&freeform_atom));
+
+	apply_global_options(GlobalOptions::default());
+}
+
+#[test_log::test]
+fn truncated_mdat_relaxed() {
+	let original = std::fs::read("tests/files/assets/minimal/m4a_codec_aac.m4a").unwrap();
+
+	// Move the `moov` atom in front of the `mdat` atom (ftyp (28) + free (8) + mdat (22497) + moov),
+	// as is common for streamable files. This leaves the chunk offsets incorrect, but
+	// they aren't needed here.
+	let (head, rest) = original.split_at(36);
+	let (mdat, moov) = rest.split_at(22497);
+
+	let mut content = [head, moov, mdat].concat();
+
+	// Then cut the file off in the middle of the `mdat` atom
+	content.truncate(head.len() + moov.len() + 1024);
+
+	let read = |parsing_mode| {
+		Probe::new(std::io::Cursor::new(&content))
+			.options(ParseOptions::new().parsing_mode(parsing_mode))
+			.guess_file_type()
+			.unwrap()
+			.read()
+	};
+
+	assert!(read(ParsingMode::Strict).is_err());
+
+	let tagged_file = read(ParsingMode::Relaxed).unwrap();
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert_eq!(tagged_file.properties().duration(), Duration::ZERO);
+	assert!(matches!(
+		tagged_file.diagnostics(),
+		[ParseDiagnostic::IncompleteProperties { .. }]
+	));
+}
+
+// Records how a file was accessed, to verify that the media data is left alone
+struct AccessTrackingFile {
+	inner: std::io::Cursor<Vec<u8>>,
+	bytes_read: u64,
+	first_write: Option<u64>,
+}
+
+impl AccessTrackingFile {
+	fn record_write(&mut self, pos: u64) {
+		self.first_write = Some(self.first_write.map_or(pos, |first| first.min(pos)));
+	}
+}
+
+impl std::io::Read for AccessTrackingFile {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let read = self.inner.read(buf)?;
+		self.bytes_read += read as u64;
+		Ok(read)
+	}
+}
+
+impl std::io::Write for AccessTrackingFile {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.record_write(self.inner.position());
+		self.inner.write(buf)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+impl Seek for AccessTrackingFile {
+	fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+		self.inner.seek(pos)
+	}
+}
+
+impl lofty::io::Truncate for AccessTrackingFile {
+	type Error = std::convert::Infallible;
+
+	fn truncate(&mut self, new_len: u64) -> Result<(), Self::Error> {
+		self.record_write(new_len);
+		self.inner.get_mut().truncate(new_len as usize);
+		Ok(())
+	}
+}
+
+impl lofty::io::Length for AccessTrackingFile {
+	type Error = std::convert::Infallible;
+
+	fn len(&self) -> Result<u64, Self::Error> {
+		Ok(self.inner.get_ref().len() as u64)
+	}
+}
+
+#[test_log::test]
+fn write_trailing_moov() {
+	let original = std::fs::read("tests/files/assets/minimal/m4a_codec_aac.m4a").unwrap();
+
+	// ftyp (28) + free (8) + mdat (22497) + moov
+	let moov_start = 36 + 22497;
+
+	let original_properties =
+		Mp4File::read_from(&mut std::io::Cursor::new(&original), ParseOptions::new())
+			.unwrap()
+			.properties()
+			.clone();
+
+	// Large enough that it can't fit in any existing padding
+	let comment = "Foo comment ".repeat(512);
+
+	let mut ilst = Ilst::default();
+	ilst.set_artist(String::from("Foo artist"));
+	ilst.set_comment(comment.clone());
+
+	let mut file = AccessTrackingFile {
+		inner: std::io::Cursor::new(original.clone()),
+		bytes_read: 0,
+		first_write: None,
+	};
+	ilst.save_to(&mut file, WriteOptions::default()).unwrap();
+
+	// Only the `moov` atom (and some atom headers) should have been read
+	assert!(file.bytes_read < (original.len() - moov_start) as u64 + 128);
+	assert!(file.first_write.unwrap() >= moov_start as u64);
+
+	let content = file.inner.into_inner();
+	assert!(content.len() > original.len());
+	assert_eq!(content[..moov_start], original[..moov_start]);
+
+	let mp4_file =
+		Mp4File::read_from(&mut std::io::Cursor::new(&content), ParseOptions::new()).unwrap();
+	let ilst = mp4_file.ilst().unwrap();
+	assert_eq!(ilst.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(ilst.comment().as_deref(), Some(comment.as_str()));
+
+	// The overall bitrate depends on the file size, but the audio is the same
+	let properties = mp4_file.properties();
+	assert_eq!(properties.duration(), original_properties.duration());
+	assert_eq!(
+		properties.audio_bitrate(),
+		original_properties.audio_bitrate()
+	);
+	assert_eq!(properties.sample_rate(), original_properties.sample_rate());
+}
@@ -1,12 +1,13 @@
 use crate::{set_artist, temp_file, verify_artist};
 use lofty::config::ParseOptions;
 use lofty::file::{FileType, TaggedFile};
-use lofty::musepack::MpcFile;
+use lofty::musepack::sv8::ReplayGain;
+use lofty::musepack::{MpcFile, MpcProperties, MpcStreamVersion};
 use lofty::prelude::*;
 use lofty::probe::Probe;
 use lofty::tag::TagType;
 
-use std::io::Seek;
+use std::io::{Read, Seek};
 
 // Marker test so IntelliJ Rust recognizes this as a test module
 #[test_log::test]
@@ -118,3 +119,120 @@ fn read_sv5() {
 	// Finally, verify ID3v2 (read only)
 	crate::verify_artist!(file, tag, TagType::Id3v2, "Baz artist", 1);
 }
+
+fn set_replay_gain(path: &str, replay_gain: ReplayGain) -> (MpcFile, MpcFile, Vec<u8>, Vec<u8>) {
+	let mut file = temp_file!(path);
+
+	let mut original_content = Vec::new();
+	file.read_to_end(&mut original_content).unwrap();
+	file.rewind().unwrap();
+
+	let mut mpc_file = MpcFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	mpc_file.set_replay_gain(&mut file, replay_gain).unwrap();
+
+	file.rewind().unwrap();
+	let re_read = MpcFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let mut new_content = Vec::new();
+	file.read_to_end(&mut new_content).unwrap();
+
+	(mpc_file, re_read, original_content, new_content)
+}
+
+// Only `len` consecutive bytes may differ, the tags and audio packets are untouched
+fn assert_only_changed(original_content: &[u8], new_content: &[u8], len: usize) {
+	assert_eq!(original_content.len(), new_content.len());
+
+	let changed = original_content
+		.iter()
+		.zip(new_content)
+		.enumerate()
+		.filter(|(_, (a, b))| a != b)
+		.map(|(i, _)| i)
+		.collect::<Vec<_>>();
+
+	assert!(!changed.is_empty());
+	assert!(changed[changed.len() - 1] - changed[0] < len);
+}
+
+#[test_log::test]
+fn set_replay_gain_sv8() {
+	let replay_gain = ReplayGain {
+		version: 1,
+		title_gain: 0x4E8F,
+		title_peak: 0x60C1,
+		album_gain: 0x4D00,
+		album_peak: 0x59F5,
+	};
+
+	let (mpc_file, re_read, original_content, new_content) =
+		set_replay_gain("tests/files/assets/minimal/mpc_sv8.mpc", replay_gain);
+	assert_eq!(mpc_file.stream_version(), MpcStreamVersion::Sv8);
+
+	let MpcProperties::Sv8(properties) = mpc_file.properties() else {
+		unreachable!()
+	};
+	assert_eq!(properties.replay_gain, replay_gain);
+
+	let MpcProperties::Sv8(re_read_properties) = re_read.properties() else {
+		unreachable!()
+	};
+	assert_eq!(re_read_properties, properties);
+
+	// Version (1) and the gain and peak values (8)
+	assert_only_changed(&original_content, &new_content, 9);
+	assert_eq!(
+		re_read.ape().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
+
+#[test_log::test]
+fn set_replay_gain_sv7() {
+	// These values survive the conversion to and from the stream version 7 header exactly
+	let replay_gain = ReplayGain {
+		version: 1,
+		title_gain: 1920,
+		title_peak: 20000,
+		album_gain: 2560,
+		album_peak: 21000,
+	};
+
+	let (mpc_file, re_read, original_content, new_content) =
+		set_replay_gain("tests/files/assets/minimal/mpc_sv7.mpc", replay_gain);
+	assert_eq!(mpc_file.stream_version(), MpcStreamVersion::Sv7);
+
+	let MpcProperties::Sv7(properties) = mpc_file.properties() else {
+		unreachable!()
+	};
+	assert_eq!(properties.title_gain(), 1920);
+	assert_eq!(properties.title_peak(), 20000);
+	assert_eq!(properties.album_gain(), 2560);
+	assert_eq!(properties.album_peak(), 21000);
+
+	let MpcProperties::Sv7(re_read_properties) = re_read.properties() else {
+		unreachable!()
+	};
+	assert_eq!(re_read_properties, properties);
+
+	// Sections 3 and 4 of the header
+	assert_only_changed(&original_content, &new_content, 8);
+	assert_eq!(
+		re_read.ape().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
+
+#[test_log::test]
+fn set_replay_gain_sv5() {
+	let mut file = temp_file!("tests/files/assets/minimal/mpc_sv5.mpc");
+	let mut mpc_file = MpcFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	assert!(mpc_file
+		.set_replay_gain(&mut file, ReplayGain::default())
+		.is_err());
+}
@@ -1,14 +1,27 @@
+use crate::util::SharedBuffer;
 use crate::{set_artist, temp_file, verify_artist};
-use lofty::config::{ParseOptions, WriteOptions};
-use lofty::file::{BoundTaggedFile, FileType};
-use lofty::id3::v2::{Frame, FrameId, Id3v2Tag, KeyValueFrame};
+use lofty::config::{
+	apply_global_options, DuplicateTagPolicy, GlobalOptions, IoHint, ParseOptions, ParsingMode,
+	WriteOptions,
+};
+use lofty::error::ParseDiagnostic;
+use lofty::file::{BoundTaggedFile, FileType, TaggedFile};
+use lofty::id3::v2::{
+	AttachedPictureFrame, ChapterFrame, CommercialFrame, Frame, FrameId, Id3v2Header, Id3v2Tag,
+	KeyValueFrame, PrivateFrame, ReceivedAs, TableOfContentsFrame, TextInformationFrame,
+};
 use lofty::mpeg::MpegFile;
+use lofty::picture::{MimeType, Picture, PictureType};
 use lofty::prelude::*;
 use lofty::probe::Probe;
-use lofty::tag::{Tag, TagType};
+use lofty::tag::{
+	ItemValue, PatchChange, PatchOperation, Tag, TagLocation, TagPatch, TagPlacement, TagType,
+};
+use lofty::TextEncoding;
 
 use std::borrow::Cow;
-use std::io::Seek;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, Write};
 
 #[test_log::test]
 fn read() {
@@ -31,6 +44,130 @@ fn read() {
 	crate::verify_artist!(file, tag, TagType::Ape, "Baz artist", 1);
 }
 
+#[test_log::test]
+fn read_streaming_remote() {
+	let file = Probe::open("tests/files/assets/minimal/full_test.mp3")
+		.unwrap()
+		.options(ParseOptions::new().io_hint(IoHint::StreamingRemote))
+		.read()
+		.unwrap();
+
+	// Only the ID3v2 tag at the start of the file is read
+	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
+	assert!(!file.contains_tag_type(TagType::Id3v1));
+	assert!(!file.contains_tag_type(TagType::Ape));
+
+	let local_file = Probe::open("tests/files/assets/minimal/full_test.mp3")
+		.unwrap()
+		.read()
+		.unwrap();
+	assert_eq!(
+		file.properties().duration(),
+		local_file.properties().duration()
+	);
+}
+
+#[test_log::test]
+fn tag_locations() {
+	let content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+	let file = Probe::new(Cursor::new(&content))
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let locations = file.tag_locations();
+	assert_eq!(locations.len(), 3);
+
+	// ID3v2 at the start of the file, including its 10 byte header
+	let id3v2 = locations
+		.iter()
+		.find(|loc| loc.tag_type() == TagType::Id3v2)
+		.unwrap();
+	let id3v2_header = Id3v2Header::parse(&mut &content[..]).unwrap();
+	assert_eq!(id3v2.placement(), TagPlacement::Head);
+	assert_eq!(id3v2.offset(), 0);
+	assert_eq!(id3v2.len(), u64::from(id3v2_header.size()) + 10);
+	assert!(id3v2.has_header());
+	assert!(!id3v2.has_footer());
+
+	// ID3v1 is always the final 128 bytes
+	let id3v1 = locations
+		.iter()
+		.find(|loc| loc.tag_type() == TagType::Id3v1)
+		.unwrap();
+	assert_eq!(id3v1.placement(), TagPlacement::Tail);
+	assert_eq!(id3v1.offset(), content.len() as u64 - 128);
+	assert_eq!(id3v1.len(), 128);
+
+	// APE sits right before ID3v1, and has both a header and a footer
+	let ape = locations
+		.iter()
+		.find(|loc| loc.tag_type() == TagType::Ape)
+		.unwrap();
+	assert_eq!(ape.placement(), TagPlacement::Tail);
+	assert!(ape.has_header());
+	assert!(ape.has_footer());
+	assert_eq!(ape.offset() + ape.len(), id3v1.offset());
+
+	let ape_start = ape.offset() as usize;
+	let ape_end = (ape.offset() + ape.len()) as usize;
+	assert_eq!(&content[ape_start..ape_start + 8], b"APETAGEX");
+	assert_eq!(&content[ape_end - 32..ape_end - 24], b"APETAGEX");
+}
+
+#[test_log::test]
+fn tag_locations_without_reading_tags() {
+	let file = Probe::open("tests/files/assets/minimal/full_test.mp3")
+		.unwrap()
+		.options(ParseOptions::new().read_tags(false))
+		.read()
+		.unwrap();
+
+	assert!(file.tag_locations().is_empty());
+}
+
+#[test_log::test]
+fn pictures_to_writers() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let mut tagged_file = Probe::new(&mut file)
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let data = vec![1; 1024];
+	tagged_file
+		.tag_mut(TagType::Id3v2)
+		.unwrap()
+		.push_picture(Picture::new_unchecked(
+			PictureType::CoverFront,
+			Some(MimeType::Png),
+			Some(String::from("cover")),
+			data.clone(),
+		));
+
+	file.rewind().unwrap();
+	tagged_file.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let buffer = SharedBuffer::default();
+	let writer = buffer.clone();
+	let tagged_file = Probe::new(&mut file)
+		.guess_file_type()
+		.unwrap()
+		.pictures_to_writers(move |_| Some(writer.clone()))
+		.unwrap();
+
+	assert_eq!(buffer.contents(), data);
+
+	let picture = &tagged_file.tag(TagType::Id3v2).unwrap().pictures()[0];
+	assert_eq!(picture.description(), Some("cover"));
+	assert!(picture.data().is_empty());
+	assert_eq!(picture.skipped_data_len(), Some(data.len() as u64));
+}
+
 #[test_log::test]
 fn read_with_junk_bytes_between_frames() {
 	// Read a file that includes an ID3v2.3 data block followed by four bytes of junk data (0x20)
@@ -93,6 +230,140 @@ fn issue_87_duplicate_id3v2() {
 	assert_eq!(id3v2_tag.title().as_deref(), Some("title test"));
 }
 
+#[test_log::test]
+fn stacked_id3v2() {
+	fn read(policy: DuplicateTagPolicy) -> lofty::error::Result<MpegFile> {
+		let mut file = File::open("tests/files/assets/issue_87_duplicate_id3v2.mp3").unwrap();
+		MpegFile::read_from(&mut file, ParseOptions::new().duplicate_tag_policy(policy))
+	}
+
+	let merged = read(DuplicateTagPolicy::Merge).unwrap();
+	let id3v2 = merged.id3v2().unwrap();
+	assert_eq!(id3v2.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(id3v2.title().as_deref(), Some("title test"));
+
+	// The second tag isn't treated as junk before the audio
+	assert_eq!(merged.leading_junk(), 0);
+
+	let first = read(DuplicateTagPolicy::FirstWins).unwrap();
+	let id3v2 = first.id3v2().unwrap();
+	assert_eq!(id3v2.artist().as_deref(), Some("artist test"));
+	assert_eq!(id3v2.title().as_deref(), Some("title test"));
+
+	let last = read(DuplicateTagPolicy::LastWins).unwrap();
+	let id3v2 = last.id3v2().unwrap();
+	assert_eq!(id3v2.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(id3v2.title(), None);
+
+	assert!(read(DuplicateTagPolicy::Error).is_err());
+
+	// The properties are the same, regardless of whether the tags are read
+	let mut file = File::open("tests/files/assets/issue_87_duplicate_id3v2.mp3").unwrap();
+	let untagged = MpegFile::read_from(&mut file, ParseOptions::new().read_tags(false)).unwrap();
+	assert_eq!(untagged.leading_junk(), 0);
+	assert_eq!(untagged.properties(), merged.properties());
+
+	let tagged_file = Probe::open("tests/files/assets/issue_87_duplicate_id3v2.mp3")
+		.unwrap()
+		.read()
+		.unwrap();
+	assert_eq!(
+		tagged_file.diagnostics(),
+		&[ParseDiagnostic::StackedId3v2Tag { offset: 283 }]
+	);
+}
+
+#[test_log::test]
+fn remove_id3v2_updates_xing_byte_count() {
+	fn xing_byte_count(bytes: &[u8]) -> (usize, u32) {
+		let pos = bytes.windows(4).position(|w| w == b"Xing").unwrap();
+
+		// Flags (4), frame count (4), byte count (4)
+		let count = u32::from_be_bytes(bytes[pos + 12..pos + 16].try_into().unwrap());
+		(pos, count)
+	}
+
+	let original = std::fs::read("tests/files/assets/issue_87_duplicate_id3v2.mp3").unwrap();
+	let expected_properties = *MpegFile::read_from(
+		&mut Cursor::new(&original),
+		ParseOptions::new().read_tags(false),
+	)
+	.unwrap()
+	.properties();
+
+	// Make the byte count include the leading tags, as some encoders do
+	let mut bytes = original.clone();
+	let (xing_pos, _) = xing_byte_count(&bytes);
+	bytes[xing_pos + 12..xing_pos + 16].copy_from_slice(&(original.len() as u32).to_be_bytes());
+
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&bytes).unwrap();
+	file.rewind().unwrap();
+
+	TagType::Id3v2.remove_from(&mut file).unwrap();
+
+	let mut bytes = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut bytes).unwrap();
+
+	// The file is now only the audio stream, starting with the Xing frame
+	assert_eq!(&bytes[..2], &[0xFF, 0xFB]);
+	assert_eq!(xing_byte_count(&bytes).1, bytes.len() as u32);
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(
+		mpeg_file.properties().audio_bitrate(),
+		expected_properties.audio_bitrate()
+	);
+}
+
+#[test_log::test]
+fn write_removes_stacked_id3v2() {
+	let mut file = temp_file!("tests/files/assets/issue_87_duplicate_id3v2.mp3");
+
+	let mut mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let properties = *mpeg_file.properties();
+
+	let id3v2 = mpeg_file.id3v2_mut().unwrap();
+	id3v2.set_album(String::from("Bar album"));
+
+	file.rewind().unwrap();
+	mpeg_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	let mut bytes = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut bytes).unwrap();
+
+	// Only a single tag remains, directly followed by the audio
+	let header = Id3v2Header::parse(&mut &bytes[..]).unwrap();
+	let tag_end = 10 + header.size() as usize;
+	assert_ne!(&bytes[tag_end..tag_end + 3], b"ID3");
+
+	file.rewind().unwrap();
+	let tagged_file = Probe::new(&mut file)
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	assert!(tagged_file.diagnostics().is_empty());
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+	assert_eq!(id3v2.album().as_deref(), Some("Bar album"));
+	assert_eq!(id3v2.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(id3v2.title().as_deref(), Some("title test"));
+	assert_eq!(mpeg_file.leading_junk(), 0);
+	assert_eq!(mpeg_file.properties().duration(), properties.duration());
+	assert_eq!(
+		mpeg_file.properties().audio_bitrate(),
+		properties.audio_bitrate()
+	);
+}
+
 #[test_log::test]
 fn write() {
 	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
@@ -131,6 +402,55 @@ fn write() {
 	crate::set_artist!(tagged_file, tag_mut, TagType::Ape, "Qux artist", 1 => file, "Baz artist");
 }
 
+#[test_log::test]
+fn save_to_path_atomic() {
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("full_test.mp3");
+	std::fs::copy("tests/files/assets/minimal/full_test.mp3", &path).unwrap();
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+	}
+
+	let modified = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+	File::options()
+		.write(true)
+		.open(&path)
+		.unwrap()
+		.set_modified(modified)
+		.unwrap();
+
+	let mut tagged_file = lofty::read_from_path(&path).unwrap();
+	tagged_file
+		.primary_tag_mut()
+		.unwrap()
+		.set_artist(String::from("Bar artist"));
+
+	tagged_file
+		.save_to_path_atomic(&path, WriteOptions::default().preserve_modified_time(true))
+		.unwrap();
+
+	let tagged_file = lofty::read_from_path(&path).unwrap();
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().artist().as_deref(),
+		Some("Bar artist")
+	);
+
+	let metadata = std::fs::metadata(&path).unwrap();
+	assert_eq!(metadata.modified().unwrap(), modified);
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		assert_eq!(metadata.permissions().mode() & 0o777, 0o640);
+	}
+
+	// Only the original file remains
+	assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
 #[test_log::test]
 fn save_to_id3v2() {
 	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
@@ -233,6 +553,71 @@ fn test_bound_tagged_into_inner() {
 	assert_eq!(tag.disk(), Some(123));
 }
 
+#[test_log::test]
+fn bound_tagged_file_only_writes_changed_tags() {
+	fn id3v2_tag_end(file_content: &[u8]) -> usize {
+		let header = Id3v2Header::parse(&mut &file_content[..]).unwrap();
+		10 + header.size() as usize
+	}
+
+	let mut original_content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+
+	// Leave junk after the null terminator of the ID3v1 title, which would be zeroed if the tag
+	// were rewritten
+	let id3v1_title = original_content.len() - 125;
+	let title_end = id3v1_title
+		+ original_content[id3v1_title..]
+			.iter()
+			.position(|b| *b == 0)
+			.unwrap();
+	original_content[title_end + 1..title_end + 5].copy_from_slice(b"junk");
+
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&original_content).unwrap();
+	file.rewind().unwrap();
+
+	let mut bounded = BoundTaggedFile::read_from(file, ParseOptions::default()).unwrap();
+	assert_eq!(bounded.tags().len(), 3);
+	assert!(bounded.tags().iter().all(|tag| !tag.is_dirty()));
+
+	bounded
+		.tag_mut(TagType::Id3v2)
+		.unwrap()
+		.set_title(String::from("Bar title"));
+
+	assert!(bounded.tag(TagType::Id3v2).unwrap().is_dirty());
+	assert!(!bounded.tag(TagType::Id3v1).unwrap().is_dirty());
+	assert!(!bounded.tag(TagType::Ape).unwrap().is_dirty());
+
+	bounded.save(WriteOptions::default()).unwrap();
+	assert!(bounded.tags().iter().all(|tag| !tag.is_dirty()));
+
+	let mut file = bounded.into_inner();
+	file.rewind().unwrap();
+
+	let mut new_content = Vec::new();
+	file.read_to_end(&mut new_content).unwrap();
+
+	// Only the ID3v2 tag was rewritten, the audio, APE tag, and ID3v1 tag are untouched
+	let original_id3v2_end = id3v2_tag_end(&original_content);
+	let new_id3v2_end = id3v2_tag_end(&new_content);
+	assert_ne!(
+		original_content[..original_id3v2_end],
+		new_content[..new_id3v2_end]
+	);
+	assert_eq!(
+		original_content[original_id3v2_end..],
+		new_content[new_id3v2_end..]
+	);
+
+	file.rewind().unwrap();
+	let tagged_file = lofty::read_from(&mut file).unwrap();
+	assert_eq!(
+		tagged_file.tag(TagType::Id3v2).unwrap().title().as_deref(),
+		Some("Bar title")
+	);
+}
+
 #[test_log::test]
 fn save_total_of_track_and_disk_to_id3v2() {
 	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
@@ -330,6 +715,73 @@ fn remove_id3v1() {
 	crate::remove_tag!("tests/files/assets/minimal/full_test.mp3", TagType::Id3v1);
 }
 
+#[test_log::test]
+fn extended_id3v1() {
+	fn field(value: &[u8], len: usize) -> Vec<u8> {
+		let mut field = value.to_vec();
+		field.resize(len, 0);
+		field
+	}
+
+	// Insert a "TAG+" block, continuing the ID3v1 title, directly before the ID3v1 tag
+	let original = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+	let id3v1_start = original.len() - 128;
+
+	let mut extended = b"TAG+".to_vec();
+	extended.extend(field(b", with a much longer title", 60));
+	extended.extend(field(b"", 60));
+	extended.extend(field(b"", 60));
+	extended.push(0);
+	extended.extend(field(b"Chiptune", 30));
+	extended.extend(field(b"000:00", 6));
+	extended.extend(field(b"000:01", 6));
+	assert_eq!(extended.len(), 227);
+
+	let mut content = original[..id3v1_start].to_vec();
+	content.extend(extended);
+	content.extend(&original[id3v1_start..]);
+
+	// Give the ID3v1 tag a title that fills its field
+	content[id3v1_start + 227 + 3..id3v1_start + 227 + 33].copy_from_slice(&[b'T'; 30]);
+
+	let file = Probe::new(Cursor::new(&content))
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let expected_title = format!("{}, with a much longer title", "T".repeat(30));
+	let id3v1 = file.tag(TagType::Id3v1).unwrap();
+	assert_eq!(id3v1.title().as_deref(), Some(expected_title.as_str()));
+	assert_eq!(id3v1.artist().as_deref(), Some("Bar artist"));
+
+	// Both blocks are covered by the tag's location, and the APE tag is still found
+	let location = file
+		.tag_locations()
+		.iter()
+		.find(|loc| loc.tag_type() == TagType::Id3v1)
+		.unwrap();
+	assert_eq!(location.offset(), id3v1_start as u64);
+	assert_eq!(location.len(), 128 + 227);
+	crate::verify_artist!(file, tag, TagType::Ape, "Baz artist", 1);
+
+	// Writing the tag replaces both blocks with a standard tag
+	let mut cursor = Cursor::new(content.clone());
+	id3v1.save_to(&mut cursor, WriteOptions::default()).unwrap();
+
+	let written = cursor.into_inner();
+	assert_eq!(written.len(), original.len());
+	assert_eq!(&written[id3v1_start..id3v1_start + 3], b"TAG");
+
+	// Removing the tag leaves nothing of either block behind
+	let mut cursor = Cursor::new(content);
+	TagType::Id3v1.remove_from(&mut cursor).unwrap();
+
+	let content = cursor.into_inner();
+	assert_eq!(content, &original[..id3v1_start]);
+}
+
 #[test_log::test]
 fn remove_ape() {
 	crate::remove_tag!("tests/files/assets/minimal/full_test.mp3", TagType::Ape);
@@ -392,3 +844,765 @@ fn read_no_properties() {
 fn read_no_tags() {
 	crate::no_tag_test!("tests/files/assets/minimal/full_test.mp3");
 }
+
+#[test_log::test]
+fn strip_all_tags() {
+	let content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let original = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	file.rewind().unwrap();
+
+	let mut locations = Probe::new(Cursor::new(&content))
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap()
+		.tag_locations()
+		.to_vec();
+	locations.sort_by_key(TagLocation::offset);
+
+	// An ID3v2, ID3v1, and an APEv2 tag should be removed at once
+	let removed = TaggedFile::strip_all_tags(&mut file).unwrap();
+	assert_eq!(removed, [TagType::Ape, TagType::Id3v1, TagType::Id3v2]);
+
+	file.rewind().unwrap();
+	let stripped = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(!stripped.contains_tag());
+
+	// The ID3v1 and APEv2 tags at the end of the file are cut out along with the ID3v2 tag,
+	// leaving everything else byte for byte
+	let mut expected = Vec::new();
+	let mut pos = 0;
+	for location in &locations {
+		expected.extend(&content[pos..location.offset() as usize]);
+		pos = (location.offset() + location.len()) as usize;
+	}
+	expected.extend(&content[pos..]);
+
+	let mut stripped_content = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut stripped_content).unwrap();
+	assert_eq!(stripped_content, expected);
+
+	// The audio should be untouched
+	let (properties, original_properties) = (stripped.properties(), original.properties());
+	assert_eq!(properties.duration(), original_properties.duration());
+	assert_eq!(
+		properties.audio_bitrate(),
+		original_properties.audio_bitrate()
+	);
+	assert_eq!(properties.sample_rate(), original_properties.sample_rate());
+	assert_eq!(properties.channels(), original_properties.channels());
+
+	// Nothing left to remove
+	file.rewind().unwrap();
+	assert!(TaggedFile::strip_all_tags(&mut file).unwrap().is_empty());
+}
+
+#[test_log::test]
+fn strip_selected_tags() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// Tag types that aren't present should be ignored
+	let removed =
+		TaggedFile::strip_tags(&mut file, &[TagType::Id3v1, TagType::Ape, TagType::Mp4Ilst])
+			.unwrap();
+	assert_eq!(removed, [TagType::Id3v1, TagType::Ape]);
+
+	file.rewind().unwrap();
+	let stripped = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(stripped.id3v2().is_some());
+	assert!(stripped.id3v1().is_none());
+	assert!(stripped.ape().is_none());
+}
+
+#[test_log::test]
+fn tagged_file_save_removes_cleared_tags() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// An MP3 file with an ID3v2, ID3v1, and an APEv2 tag
+	let mut tagged_file = lofty::read_from(&mut file).unwrap();
+	assert!(tagged_file.contains_tag_type(TagType::Id3v2));
+	assert!(tagged_file.contains_tag_type(TagType::Ape));
+
+	// Edit the ID3v2 tag through the generic `Tag`, and drop the APE tag
+	let mut id3v2 = tagged_file.remove(TagType::Id3v2).unwrap();
+	id3v2.set_title(String::from("Bar title"));
+	assert!(tagged_file.insert_tag(id3v2).is_none());
+
+	let ape = tagged_file.remove(TagType::Ape).unwrap();
+	assert_eq!(ape.artist().as_deref(), Some("Baz artist"));
+
+	file.rewind().unwrap();
+	tagged_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	assert_eq!(
+		mpeg_file.id3v2().unwrap().title().as_deref(),
+		Some("Bar title")
+	);
+	assert!(mpeg_file.ape().is_none());
+
+	// Untouched tags are kept
+	assert!(mpeg_file.id3v1().is_some());
+}
+
+#[test_log::test]
+fn tagged_file_save_writes_all_tags_at_once() {
+	let content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// An MP3 file with an ID3v2, ID3v1, and an APEv2 tag, each of which is changed
+	let mut tagged_file = lofty::read_from(&mut file).unwrap();
+	for tag_type in [TagType::Id3v2, TagType::Id3v1, TagType::Ape] {
+		let tag = tagged_file.tag_mut(tag_type).unwrap();
+		tag.set_title(String::from("A longer title than before"));
+	}
+
+	file.rewind().unwrap();
+	tagged_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	let mut saved = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut saved).unwrap();
+
+	let saved_file = TaggedFile::read_from(&mut Cursor::new(&saved), ParseOptions::new()).unwrap();
+	for tag_type in [TagType::Id3v2, TagType::Id3v1, TagType::Ape] {
+		let tag = saved_file.tag(tag_type).unwrap();
+		assert_eq!(tag.title().as_deref(), Some("A longer title than before"));
+	}
+
+	// Everything in between the tags is untouched
+	let audio = |content: &[u8]| {
+		let file = TaggedFile::read_from(&mut Cursor::new(content), ParseOptions::new()).unwrap();
+		let locations = file.tag_locations();
+		let start = locations
+			.iter()
+			.filter(|location| location.placement() == TagPlacement::Head)
+			.map(|location| location.offset() + location.len())
+			.max()
+			.unwrap();
+		let end = locations
+			.iter()
+			.filter(|location| location.placement() == TagPlacement::Tail)
+			.map(TagLocation::offset)
+			.min()
+			.unwrap();
+
+		content[start as usize..end as usize].to_vec()
+	};
+	assert_eq!(audio(&saved), audio(&content));
+}
+
+#[test_log::test]
+fn tagged_file_save_keeps_reinserted_tags() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let mut tagged_file = lofty::read_from(&mut file).unwrap();
+
+	// Replacing the tag after removing it should not remove it from the file
+	let mut ape = tagged_file.remove(TagType::Ape).unwrap();
+	ape.set_artist(String::from("Bar artist"));
+	tagged_file.insert_tag(ape);
+
+	file.rewind().unwrap();
+	tagged_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	assert_eq!(
+		mpeg_file.ape().unwrap().artist().as_deref(),
+		Some("Bar artist")
+	);
+	assert!(mpeg_file.id3v2().is_some());
+	assert!(mpeg_file.id3v1().is_some());
+}
+
+#[test_log::test]
+fn bound_tagged_file_save_removes_cleared_tags() {
+	let file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let mut bounded = BoundTaggedFile::read_from(file, ParseOptions::default()).unwrap();
+	bounded.clear();
+	bounded.save(WriteOptions::default()).unwrap();
+
+	let mut file = bounded.into_inner();
+	file.rewind().unwrap();
+
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(!mpeg_file.contains_tag());
+}
+
+#[test_log::test]
+fn read_with_leading_junk() {
+	// The audio of `full_test.mp3`, preceded by 1 KiB of random bytes
+	let file = Probe::open("tests/files/assets/junk_prefix.mp3")
+		.unwrap()
+		.read()
+		.unwrap();
+	assert_eq!(file.file_type(), FileType::Mpeg);
+
+	let original = Probe::open("tests/files/assets/minimal/full_test.mp3")
+		.unwrap()
+		.read()
+		.unwrap();
+	assert_eq!(
+		file.properties().duration(),
+		original.properties().duration()
+	);
+
+	// Tail tags are unaffected
+	crate::verify_artist!(file, tag, TagType::Id3v1, "Bar artist", 1);
+	crate::verify_artist!(file, tag, TagType::Ape, "Baz artist", 1);
+
+	let mut file = temp_file!("tests/files/assets/junk_prefix.mp3");
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(mpeg_file.leading_junk(), 1024);
+}
+
+#[test_log::test]
+fn read_with_junk_after_id3v2() {
+	let content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+	let header = Id3v2Header::parse(&mut &content[..]).unwrap();
+	let tag_end = 10 + header.size() as usize;
+
+	// Both larger than `ParseOptions::max_junk_bytes`, which only limits the search when probing
+	for junk_len in [1500, 4096] {
+		let mut junked = content[..tag_end].to_vec();
+		junked.extend(vec![0x55; junk_len]);
+		junked.extend_from_slice(&content[tag_end..]);
+
+		let file = Probe::with_file_type(Cursor::new(&junked), FileType::Mpeg)
+			.read()
+			.unwrap();
+		assert_eq!(file.properties().duration().as_millis(), 1464);
+
+		let mpeg_file =
+			MpegFile::read_from(&mut Cursor::new(&junked), ParseOptions::new()).unwrap();
+		assert_eq!(mpeg_file.leading_junk(), junk_len as u64);
+	}
+}
+
+#[test_log::test]
+fn write_with_leading_junk() {
+	let mut file = temp_file!("tests/files/assets/junk_prefix.mp3");
+	let mut mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let duration = mpeg_file.properties().duration();
+
+	let mut tag = Id3v2Tag::default();
+	tag.set_artist(String::from("Foo artist"));
+	mpeg_file.set_id3v2(tag);
+
+	file.rewind().unwrap();
+	mpeg_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	// The junk is retained after the new tag
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(
+		mpeg_file.id3v2().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert_eq!(mpeg_file.leading_junk(), 1024);
+	assert_eq!(mpeg_file.properties().duration(), duration);
+}
+
+#[test_log::test]
+fn truncated_stream_relaxed() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap();
+
+	// Only keep the ID3v2 tag, and a couple bytes of the first frame
+	let header = Id3v2Header::parse(&mut &content[..]).unwrap();
+	content.truncate(10 + header.size() as usize + 2);
+
+	let read = |parsing_mode| {
+		Probe::new(Cursor::new(&content))
+			.options(ParseOptions::new().parsing_mode(parsing_mode))
+			.guess_file_type()
+			.unwrap()
+			.read()
+	};
+
+	assert!(read(ParsingMode::Strict).is_err());
+
+	let tagged_file = read(ParsingMode::Relaxed).unwrap();
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert!(matches!(
+		tagged_file.diagnostics(),
+		[ParseDiagnostic::IncompleteProperties { .. }]
+	));
+}
+
+#[test_log::test]
+fn accessor_falls_back_to_other_tags() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	// The genre is only stored in the ID3v1 tag
+	{
+		let mut tagged_file = Probe::new(&mut file)
+			.options(ParseOptions::new().read_properties(false))
+			.guess_file_type()
+			.unwrap()
+			.read()
+			.unwrap();
+
+		for tag_type in [TagType::Id3v2, TagType::Ape] {
+			tagged_file.tag_mut(tag_type).unwrap().remove_genre();
+		}
+		tagged_file
+			.tag_mut(TagType::Id3v1)
+			.unwrap()
+			.set_genre(String::from("Jazz"));
+
+		file.rewind().unwrap();
+		tagged_file
+			.save_to(&mut file, WriteOptions::default())
+			.unwrap();
+	}
+
+	file.rewind().unwrap();
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	assert!(tagged_file.primary_tag().unwrap().genre().is_none());
+	assert_eq!(tagged_file.genre().as_deref(), Some("Jazz"));
+
+	// Items present in the primary tag take precedence, unless the order is changed
+	tagged_file
+		.tag_mut(TagType::Id3v1)
+		.unwrap()
+		.set_artist(String::from("Bar artist"));
+	assert_eq!(tagged_file.artist().as_deref(), Some("Foo artist"));
+
+	tagged_file.set_tag_precedence(&[TagType::Id3v1]);
+	assert_eq!(tagged_file.artist().as_deref(), Some("Bar artist"));
+
+	// Setters only write to the primary tag
+	tagged_file.set_genre(String::from("Rock"));
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().genre().as_deref(),
+		Some("Rock")
+	);
+	assert_eq!(
+		tagged_file.tag(TagType::Id3v1).unwrap().genre().as_deref(),
+		Some("Jazz")
+	);
+
+	// Removals apply to all tags
+	tagged_file.remove_genre();
+	assert!(tagged_file.genre().is_none());
+	assert!(tagged_file.tags().iter().all(|tag| tag.genre().is_none()));
+}
+
+#[test_log::test]
+fn accessor_creates_primary_tag() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	tagged_file.remove(TagType::Id3v2);
+	assert!(tagged_file.primary_tag().is_none());
+
+	tagged_file.set_title(String::from("New title"));
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().title().as_deref(),
+		Some("New title")
+	);
+	assert_eq!(tagged_file.title().as_deref(), Some("New title"));
+}
+
+#[test_log::test]
+fn read_vbri_header() {
+	let mut file = File::open("tests/files/assets/vbri.mp3").unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let vbri_header = mpeg_file.vbri_header().unwrap();
+	assert_eq!(vbri_header.version, 1);
+	assert_eq!(vbri_header.frames, 250);
+	assert_eq!(vbri_header.size, 32784);
+	assert_eq!(vbri_header.frames_per_entry, 25);
+	assert_eq!(vbri_header.seek_table, [3240; 10]);
+
+	assert_eq!(vbri_header.frame_offset(50), Some(6480));
+	assert_eq!(vbri_header.frame_offset(250), None);
+
+	assert_eq!(mpeg_file.properties().duration().as_millis(), 6000);
+
+	// Not read without properties
+	file.rewind().unwrap();
+	let mpeg_file =
+		MpegFile::read_from(&mut file, ParseOptions::new().read_properties(false)).unwrap();
+	assert!(mpeg_file.vbri_header().is_none());
+}
+
+#[test_log::test]
+fn write_updates_vbri_byte_count() {
+	// Some encoders include the ID3v2 tag in the byte count
+	let mut content = std::fs::read("tests/files/assets/vbri.mp3").unwrap();
+	let size_start = content.windows(4).position(|w| w == b"VBRI").unwrap() + 10;
+	content[size_start..size_start + 4].copy_from_slice(&40000_u32.to_be_bytes());
+
+	let mut file = Cursor::new(content);
+	let mut tag = Tag::new(TagType::Id3v2);
+	tag.set_title(String::from("Foo title"));
+	tag.save_to(&mut file, WriteOptions::default()).unwrap();
+
+	// The VBRI header of this mono stream isn't located after the side information
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(mpeg_file.vbri_header().unwrap().size, 32784);
+	assert_eq!(mpeg_file.properties().duration().as_millis(), 6000);
+}
+
+#[test_log::test]
+fn apply_patch() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let picture = Picture::from_reader(
+		&mut &std::fs::read("tests/picture/assets/png_640x628.png").unwrap()[..],
+	)
+	.unwrap();
+
+	let patch = TagPatch::new()
+		.operation(PatchOperation::SetItem(
+			ItemKey::TrackTitle,
+			ItemValue::Text(String::from("Patched title")),
+		))
+		.operation(PatchOperation::RemoveItem(ItemKey::TrackArtist))
+		.operation(PatchOperation::RemoveItem(ItemKey::Composer))
+		.operation(PatchOperation::AddPicture(picture.clone()))
+		.operation(PatchOperation::ClearTag(TagType::Id3v1));
+
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert_eq!(summary.tag_type(), TagType::Id3v2);
+	assert!(!summary.created_tag());
+	assert!(summary.written());
+	assert_eq!(
+		summary.changes(),
+		&[
+			PatchChange::ItemSet(ItemKey::TrackTitle),
+			PatchChange::ItemRemoved(ItemKey::TrackArtist),
+			PatchChange::PictureAdded(PictureType::Other),
+			PatchChange::TagCleared(TagType::Id3v1),
+		]
+	);
+
+	file.rewind().unwrap();
+	let tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let id3v2 = tagged_file.tag(TagType::Id3v2).unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("Patched title"));
+	assert!(id3v2.artist().is_none());
+	assert_eq!(id3v2.pictures(), &[picture]);
+
+	// The cleared tag is removed, and the others are left alone
+	assert!(!tagged_file.contains_tag_type(TagType::Id3v1));
+	crate::verify_artist!(tagged_file, tag, TagType::Ape, "Baz artist", 1);
+
+	// Applying the same patch again changes nothing, so nothing is written
+	let patch = TagPatch::new().operation(PatchOperation::SetItem(
+		ItemKey::TrackTitle,
+		ItemValue::Text(String::from("Patched title")),
+	));
+
+	file.rewind().unwrap();
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert!(!summary.written());
+}
+
+#[test_log::test]
+fn apply_patch_keeps_unmapped_frames() {
+	// Without a companion tag, a PRIV frame would be lost when going through a `Tag`
+	apply_global_options(GlobalOptions::new().preserve_format_specific_items(false));
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+
+	let mut mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let private_frame = Frame::Private(PrivateFrame::new(String::from("lofty"), vec![1, 2, 3]));
+	mpeg_file.id3v2_mut().unwrap().insert(private_frame.clone());
+
+	file.rewind().unwrap();
+	mpeg_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let patch = TagPatch::new().operation(PatchOperation::SetItem(
+		ItemKey::TrackTitle,
+		ItemValue::Text(String::from("Patched title")),
+	));
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert!(summary.written());
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("Patched title"));
+	assert!(id3v2.into_iter().any(|frame| *frame == private_frame));
+
+	apply_global_options(GlobalOptions::default());
+}
+
+#[test_log::test]
+fn apply_patch_creates_tag() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	TagType::Ape.remove_from(&mut file).unwrap();
+
+	// Removing from a tag that doesn't exist doesn't create it
+	file.rewind().unwrap();
+	let patch = TagPatch::new()
+		.tag_type(TagType::Ape)
+		.operation(PatchOperation::RemoveItem(ItemKey::TrackTitle));
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert!(summary.created_tag());
+	assert!(summary.changes().is_empty());
+	assert!(!summary.written());
+
+	file.rewind().unwrap();
+	let patch = TagPatch::new()
+		.tag_type(TagType::Ape)
+		.operation(PatchOperation::SetItem(
+			ItemKey::TrackArtist,
+			ItemValue::Text(String::from("Qux artist")),
+		));
+	let summary = patch.apply_to(&mut file).unwrap();
+	assert!(summary.created_tag());
+	assert_eq!(
+		summary.changes(),
+		&[PatchChange::ItemSet(ItemKey::TrackArtist)]
+	);
+	assert!(summary.written());
+
+	file.rewind().unwrap();
+	let tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	crate::verify_artist!(tagged_file, tag, TagType::Ape, "Qux artist", 1);
+	crate::verify_artist!(tagged_file, primary_tag, "Foo artist", 1);
+}
+
+#[test_log::test]
+fn preserve_id3v2_size() {
+	let mut file = Cursor::new(std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap());
+	let parse_options = ParseOptions::new().read_properties(false);
+
+	// Give the tag 8 KiB of padding to work with
+	let mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	file.rewind().unwrap();
+	mpeg_file
+		.id3v2()
+		.unwrap()
+		.save_to(&mut file, WriteOptions::new().preferred_padding(8192))
+		.unwrap();
+
+	let original_content = file.get_ref().clone();
+
+	file.rewind().unwrap();
+	let mut mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2_mut().unwrap();
+
+	let tag_size = id3v2.original_size().unwrap() as usize;
+	assert!(tag_size > 8192);
+	assert_eq!(&original_content[tag_size..tag_size + 2], &[0xFF, 0xFB]);
+	assert!(!id3v2.original_flags().unwrap().footer);
+
+	id3v2.set_title(String::from("A longer title than before"));
+
+	file.rewind().unwrap();
+	id3v2
+		.save_to(&mut file, WriteOptions::new().preserve_id3v2_size(true))
+		.unwrap();
+
+	// Only the tag itself has changed
+	let content = file.get_ref();
+	assert_eq!(content.len(), original_content.len());
+	assert_ne!(content[..tag_size], original_content[..tag_size]);
+	assert_eq!(content[tag_size..], original_content[tag_size..]);
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("A longer title than before"));
+	assert_eq!(id3v2.original_size(), Some(tag_size as u32));
+}
+
+#[test_log::test]
+fn preserve_id3v2_size_too_large() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let parse_options = ParseOptions::new().read_properties(false);
+
+	let mut mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2_mut().unwrap();
+	let tag_size = id3v2.original_size().unwrap();
+
+	// The new tag doesn't fit, so it's written with the preferred padding instead
+	id3v2.set_comment("Foo comment ".repeat(200));
+
+	file.rewind().unwrap();
+	id3v2
+		.save_to(&mut file, WriteOptions::new().preserve_id3v2_size(true))
+		.unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+	assert!(id3v2.original_size().unwrap() > tag_size);
+	assert_eq!(
+		id3v2.comment().as_deref(),
+		Some(&*"Foo comment ".repeat(200))
+	);
+}
+
+#[test_log::test]
+fn chapters_round_trip() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let parse_options = ParseOptions::new().read_properties(false);
+
+	let mut mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2_mut().unwrap();
+
+	id3v2.insert(Frame::TableOfContents(TableOfContentsFrame::new(
+		String::from("toc"),
+		vec![String::from("chp0"), String::from("chp1")],
+	)));
+
+	for (element_id, start_time, title) in [("chp0", 0, "Intro"), ("chp1", 1000, "Outro")] {
+		let mut chapter =
+			ChapterFrame::new(String::from(element_id), start_time, start_time + 1000);
+		chapter.frames.push(Frame::Text(TextInformationFrame::new(
+			FrameId::Valid(Cow::Borrowed("TIT2")),
+			TextEncoding::UTF8,
+			String::from(title),
+		)));
+		chapter
+			.frames
+			.push(Frame::Picture(AttachedPictureFrame::new(
+				TextEncoding::UTF8,
+				Picture::new_unchecked(
+					PictureType::Other,
+					Some(MimeType::Png),
+					Some(String::from(title)),
+					vec![1, 2, 3],
+				),
+			)));
+		id3v2.insert_chapter(chapter);
+	}
+
+	let expected = id3v2.chapters().cloned().collect::<Vec<_>>();
+
+	file.rewind().unwrap();
+	mpeg_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+
+	let chapters = id3v2.chapters().collect::<Vec<_>>();
+	assert_eq!(chapters.len(), expected.len());
+	for (chapter, expected) in chapters.into_iter().zip(expected) {
+		assert_eq!(chapter.element_id, expected.element_id);
+		assert_eq!(chapter.start_time, expected.start_time);
+		assert_eq!(chapter.end_time, expected.end_time);
+		assert_eq!(chapter.frames, expected.frames);
+	}
+
+	let toc = id3v2.tables_of_contents().next().unwrap();
+	assert_eq!(toc.child_element_ids, ["chp0", "chp1"]);
+}
+
+#[test_log::test]
+fn commercial_frame_preserved() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.mp3");
+	let parse_options = ParseOptions::new().read_properties(false);
+
+	let mut commercial = CommercialFrame::new(
+		TextEncoding::UTF8,
+		String::from("USD9.99"),
+		String::from("20301231"),
+		String::from("https://example.com"),
+		ReceivedAs::FileOverInternet,
+		String::from("Foo Store"),
+		String::from("Bar album"),
+	);
+	commercial.logo_mime_type = String::from("image/png");
+	commercial.logo = vec![1, 2, 3];
+
+	let mut mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	mpeg_file
+		.id3v2_mut()
+		.unwrap()
+		.insert(Frame::from(commercial.clone()));
+
+	file.rewind().unwrap();
+	mpeg_file
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	// Saving through a generic `Tag` should keep the frame as well
+	file.rewind().unwrap();
+	let mut tagged_file = Probe::new(&mut file)
+		.options(parse_options)
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	tagged_file
+		.tag_mut(TagType::Id3v2)
+		.unwrap()
+		.set_title(String::from("Baz title"));
+
+	file.rewind().unwrap();
+	tagged_file.save_to(&mut file, WriteOptions::new()).unwrap();
+
+	file.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("Baz title"));
+
+	let Some(Frame::Commercial(re_read)) = id3v2
+		.frames()
+		.find(|frame| matches!(frame, Frame::Commercial(_)))
+	else {
+		panic!("expected a COMR frame");
+	};
+	assert_eq!(**re_read, commercial);
+	assert_eq!(
+		re_read.seller_logo().unwrap().mime_type(),
+		Some(&MimeType::Png)
+	);
+}
@@ -1,5 +1,6 @@
 use crate::{set_artist, temp_file, verify_artist};
-use lofty::config::{ParseOptions, WriteOptions};
+use lofty::config::{ParseOptions, ParsingMode, WriteOptions};
+use lofty::error::ParseDiagnostic;
 use lofty::file::FileType;
 use lofty::prelude::*;
 use lofty::probe::Probe;
@@ -47,6 +48,30 @@ fn flac_remove_vorbis_comments() {
 	);
 }
 
+#[test_log::test]
+fn ogg_flac_read() {
+	read(
+		"tests/files/assets/minimal/full_test.oga",
+		FileType::OggFlac,
+	)
+}
+
+#[test_log::test]
+fn ogg_flac_write() {
+	write(
+		"tests/files/assets/minimal/full_test.oga",
+		FileType::OggFlac,
+	)
+}
+
+#[test_log::test]
+fn ogg_flac_remove() {
+	remove(
+		"tests/files/assets/minimal/full_test.oga",
+		TagType::VorbisComments,
+	)
+}
+
 #[test_log::test]
 fn vorbis_read() {
 	read("tests/files/assets/minimal/full_test.ogg", FileType::Vorbis)
@@ -195,6 +220,151 @@ fn opus_issue_499_vinyl_track_number() {
 	assert_eq!(comments.get("TRACKNUMBER"), Some("a5"));
 }
 
+#[test_log::test]
+fn opus_set_output_gain() {
+	use lofty::ogg::OpusFile;
+	use std::io::Read;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.opus");
+
+	let mut original_content = Vec::new();
+	file.read_to_end(&mut original_content).unwrap();
+	file.rewind().unwrap();
+
+	let mut opus_file = OpusFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(opus_file.properties().output_gain(), 0);
+	assert_eq!(opus_file.properties().channel_mapping_family(), 0);
+
+	file.rewind().unwrap();
+	opus_file.set_output_gain(&mut file, -1234).unwrap();
+	assert_eq!(opus_file.properties().output_gain(), -1234);
+
+	file.rewind().unwrap();
+	let opus_file = OpusFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(opus_file.properties().output_gain(), -1234);
+	assert_eq!(opus_file.properties().duration().as_millis(), 1428);
+
+	file.rewind().unwrap();
+	let mut new_content = Vec::new();
+	file.read_to_end(&mut new_content).unwrap();
+
+	// Only the first page may change, every other page (and its checksum) must be identical
+	let segment_count = usize::from(original_content[26]);
+	let first_page_len = 27
+		+ segment_count
+		+ original_content[27..27 + segment_count]
+			.iter()
+			.map(|s| usize::from(*s))
+			.sum::<usize>();
+
+	assert_eq!(new_content.len(), original_content.len());
+	assert_ne!(
+		new_content[..first_page_len],
+		original_content[..first_page_len]
+	);
+	assert_eq!(
+		new_content[first_page_len..],
+		original_content[first_page_len..]
+	);
+}
+
+#[test_log::test]
+fn vorbis_chained_write() {
+	use lofty::ogg::VorbisFile;
+	use std::io::{Read, Write};
+
+	let original_content = std::fs::read("tests/files/assets/minimal/full_test.ogg").unwrap();
+
+	// Two complete streams, one after the other
+	let mut file = tempfile::tempfile().unwrap();
+	file.write_all(&original_content).unwrap();
+	file.write_all(&original_content).unwrap();
+	file.rewind().unwrap();
+
+	let vorbis_file = VorbisFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(vorbis_file.properties().is_chained());
+	// Only the first stream is used for the properties
+	assert_eq!(vorbis_file.properties().duration().as_millis(), 1451);
+
+	let mut tag = vorbis_file.vorbis_comments().clone();
+	tag.set_artist(String::from("Bar artist"));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::default()).unwrap();
+
+	file.rewind().unwrap();
+	let mut new_content = Vec::new();
+	file.read_to_end(&mut new_content).unwrap();
+
+	// The second stream must be copied as-is
+	assert!(new_content.len() > original_content.len());
+	assert_eq!(
+		new_content[new_content.len() - original_content.len()..],
+		original_content[..]
+	);
+
+	file.rewind().unwrap();
+	let vorbis_file = VorbisFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(vorbis_file.properties().is_chained());
+	assert_eq!(vorbis_file.properties().duration().as_millis(), 1451);
+	assert_eq!(
+		vorbis_file.vorbis_comments().artist().as_deref(),
+		Some("Bar artist")
+	);
+}
+
+#[test_log::test]
+fn vorbis_not_chained() {
+	use lofty::ogg::VorbisFile;
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.ogg");
+	let vorbis_file = VorbisFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(!vorbis_file.properties().is_chained());
+}
+
+#[test_log::test]
+fn vorbis_channel_mask() {
+	use lofty::ogg::VorbisFile;
+	use lofty::properties::ChannelMask;
+	use std::io::Cursor;
+
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.ogg").unwrap();
+
+	// Change the channel count in the identification header from 2 to 6
+	let header_pos = content.windows(7).position(|w| w == b"\x01vorbis").unwrap();
+	content[header_pos + 11] = 6;
+
+	let vorbis_file =
+		VorbisFile::read_from(&mut Cursor::new(content), ParseOptions::new()).unwrap();
+	assert_eq!(vorbis_file.properties().channels(), 6);
+	assert_eq!(
+		vorbis_file.properties().channel_mask(),
+		Some(ChannelMask::five_point_one())
+	);
+}
+
+#[test_log::test]
+fn opus_channel_mask_unknown_mapping_family() {
+	use lofty::ogg::OpusFile;
+	use lofty::properties::ChannelMask;
+	use std::io::Cursor;
+
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.opus").unwrap();
+
+	// Change the channel count to 12 and the channel mapping family to 255, which has no
+	// defined layout
+	let header_pos = content.windows(8).position(|w| w == b"OpusHead").unwrap();
+	content[header_pos + 9] = 12;
+	content[header_pos + 18] = 255;
+
+	let opus_file = OpusFile::read_from(&mut Cursor::new(content), ParseOptions::new()).unwrap();
+	assert_eq!(opus_file.properties().channels(), 12);
+	assert_eq!(
+		opus_file.properties().channel_mask(),
+		ChannelMask::default()
+	);
+}
+
 #[test_log::test]
 fn flac_remove_id3v2() {
 	crate::remove_tag!("tests/files/assets/flac_with_id3v2.flac", TagType::Id3v2);
@@ -235,6 +405,16 @@ fn read_no_tags_vorbis() {
 	crate::no_tag_test!(@MANDATORY_TAG "tests/files/assets/minimal/full_test.ogg", expected_len: 1);
 }
 
+#[test_log::test]
+fn read_no_properties_ogg_flac() {
+	crate::no_properties_test!("tests/files/assets/minimal/full_test.oga");
+}
+
+#[test_log::test]
+fn read_no_tags_ogg_flac() {
+	crate::no_tag_test!(@MANDATORY_TAG "tests/files/assets/minimal/full_test.oga", expected_len: 1);
+}
+
 #[test_log::test]
 fn read_no_properties_speex() {
 	crate::no_properties_test!("tests/files/assets/minimal/full_test.spx");
@@ -244,3 +424,148 @@ fn read_no_properties_speex() {
 fn read_no_tags_speex() {
 	crate::no_tag_test!(@MANDATORY_TAG "tests/files/assets/minimal/full_test.spx", expected_len: 1);
 }
+
+#[test_log::test]
+fn truncated_vorbis_setup_header_relaxed() {
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.ogg").unwrap();
+
+	// Cut the stream off in the middle of the setup header, which isn't needed for
+	// the tags or properties
+	content.truncate(2048);
+
+	let read = |parsing_mode| {
+		Probe::new(std::io::Cursor::new(&content))
+			.options(ParseOptions::new().parsing_mode(parsing_mode))
+			.guess_file_type()
+			.unwrap()
+			.read()
+	};
+
+	assert!(read(ParsingMode::Strict).is_err());
+
+	let tagged_file = read(ParsingMode::Relaxed).unwrap();
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+	assert!(matches!(
+		tagged_file.diagnostics(),
+		[ParseDiagnostic::IncompleteProperties { .. }]
+	));
+}
+
+#[test_log::test]
+fn ogg_flac_multi_page_header_write() {
+	use lofty::ogg::{OggFlacFile, OggPictureStorage};
+	use lofty::picture::{MimeType, Picture, PictureInformation, PictureType};
+	use std::io::{Cursor, Read};
+
+	let original_content = std::fs::read("tests/files/assets/minimal/full_test.oga").unwrap();
+
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.oga");
+	let ogg_flac_file = OggFlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let original_properties = *ogg_flac_file.properties();
+
+	// Both the comment and picture blocks are too large to fit in a single page
+	let picture_data = (0..150_000_u32)
+		.map(|i| (i % 251) as u8)
+		.collect::<Vec<_>>();
+	let long_comment = "a".repeat(100_000);
+
+	let mut tag = ogg_flac_file.vorbis_comments().clone();
+	tag.set_artist(String::from("Bar artist"));
+	tag.set_comment(long_comment.clone());
+	tag.insert_picture(
+		Picture::new_unchecked(
+			PictureType::CoverFront,
+			Some(MimeType::Png),
+			None,
+			picture_data.clone(),
+		),
+		Some(PictureInformation::default()),
+	)
+	.unwrap();
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::default()).unwrap();
+
+	file.rewind().unwrap();
+	let mut new_content = Vec::new();
+	file.read_to_end(&mut new_content).unwrap();
+
+	// Every page must be in sequence, with a valid checksum
+	let mut reader = Cursor::new(&new_content[..]);
+	let mut sequence_number = 0;
+	let mut audio_start = None;
+	while reader.position() < new_content.len() as u64 {
+		let page_start = reader.position() as usize;
+		let page = ogg_pager::Page::read(&mut reader).unwrap();
+		assert_eq!(page.header().sequence_number, sequence_number);
+
+		let mut verified_page = page.clone();
+		verified_page.gen_crc();
+		assert_eq!(verified_page.header().checksum(), page.header().checksum());
+
+		// Header pages have a granule position of 0, or -1 if no packet ends on them
+		let abgp = page.header().abgp;
+		if audio_start.is_none() && abgp != 0 && abgp != u64::MAX {
+			audio_start = Some(page_start);
+		}
+
+		sequence_number += 1;
+	}
+
+	// Only the sequence numbers and checksums of the audio pages may change, the first audio
+	// page in the original file is the third
+	let audio_start = audio_start.unwrap();
+	let original_audio_start = {
+		let mut reader = Cursor::new(&original_content[..]);
+		ogg_pager::Page::read(&mut reader).unwrap();
+		ogg_pager::Page::read(&mut reader).unwrap();
+		reader.position() as usize
+	};
+	assert_eq!(
+		new_content.len() - audio_start,
+		original_content.len() - original_audio_start
+	);
+
+	file.rewind().unwrap();
+	let ogg_flac_file = OggFlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let tag = ogg_flac_file.vorbis_comments();
+	assert_eq!(tag.artist().as_deref(), Some("Bar artist"));
+	assert_eq!(tag.comment().as_deref(), Some(long_comment.as_str()));
+	assert_eq!(tag.pictures().len(), 1);
+	assert_eq!(tag.pictures()[0].0.data(), &picture_data[..]);
+
+	assert_eq!(
+		ogg_flac_file.properties().duration(),
+		original_properties.duration()
+	);
+	assert_eq!(
+		ogg_flac_file.properties().sample_rate(),
+		original_properties.sample_rate()
+	);
+	assert_eq!(
+		ogg_flac_file.properties().signature(),
+		original_properties.signature()
+	);
+}
+
+#[test_log::test]
+fn ogg_flac_unknown_header_packet_count() {
+	use lofty::ogg::OggFlacFile;
+
+	let mut content = std::fs::read("tests/files/assets/minimal/full_test.oga").unwrap();
+
+	// The header packet count is stored in the mapping header packet, which starts after the
+	// 28 byte page header. A count of 0 means it's unknown.
+	content[28 + 7..28 + 9].copy_from_slice(&[0, 0]);
+
+	let ogg_flac_file =
+		OggFlacFile::read_from(&mut std::io::Cursor::new(content), ParseOptions::new()).unwrap();
+	assert_eq!(
+		ogg_flac_file.vorbis_comments().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
@@ -0,0 +1,131 @@
+use crate::{set_artist, temp_file, verify_artist};
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::file::FileType;
+use lofty::id3::v2::Id3v2Tag;
+use lofty::prelude::*;
+use lofty::probe::Probe;
+use lofty::shn::ShnFile;
+use lofty::tag::TagType;
+
+use std::io::{Cursor, Seek};
+use std::time::Duration;
+
+#[test_log::test]
+fn read() {
+	// Here we have a SHN file with both an ID3v1 tag and an APE tag
+	let file = Probe::open("tests/files/assets/minimal/full_test.shn")
+		.unwrap()
+		.options(ParseOptions::new().read_properties(false))
+		.read()
+		.unwrap();
+
+	assert_eq!(file.file_type(), FileType::Shn);
+
+	// Verify the APE tag first
+	crate::verify_artist!(file, primary_tag, "Foo artist", 1);
+
+	// Now verify the ID3v1 tag
+	crate::verify_artist!(file, tag, TagType::Id3v1, "Bar artist", 1);
+}
+
+#[test_log::test]
+fn write() {
+	let mut file = temp_file!("tests/files/assets/minimal/full_test.shn");
+
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	assert_eq!(tagged_file.file_type(), FileType::Shn);
+
+	// APE
+	set_artist!(tagged_file, primary_tag_mut, "Foo artist", 1 => file, "Bar artist");
+
+	// ID3v1
+	set_artist!(tagged_file, tag_mut, TagType::Id3v1, "Bar artist", 1 => file, "Baz artist");
+
+	// Now reread the file
+	file.rewind().unwrap();
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	set_artist!(tagged_file, primary_tag_mut, "Bar artist", 1 => file, "Foo artist");
+
+	set_artist!(tagged_file, tag_mut, TagType::Id3v1, "Baz artist", 1 => file, "Bar artist");
+}
+
+#[test_log::test]
+fn remove_id3v1() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.shn", TagType::Id3v1);
+}
+
+#[test_log::test]
+fn remove_ape() {
+	crate::remove_tag!("tests/files/assets/minimal/full_test.shn", TagType::Ape);
+}
+
+#[test_log::test]
+fn read_no_properties() {
+	crate::no_properties_test!("tests/files/assets/minimal/full_test.shn");
+}
+
+#[test_log::test]
+fn read_no_tags() {
+	crate::no_tag_test!("tests/files/assets/minimal/full_test.shn");
+}
+
+#[test_log::test]
+fn read_without_wave_header() {
+	// ajkg, version 2, followed by Rice coded values:
+	// file type (5), channels (2), block size (256), max LPC order (0), mean blocks (4),
+	// skip bytes (0), and finally a FN_QUIT command in place of a verbatim block
+	let mut stream = vec![
+		b'a', b'j', b'k', b'g', 2, 0xFB, 0xB1, 0x70, 0x09, 0xF9, 0x28,
+	];
+	stream.resize(64, 0);
+
+	let file = ShnFile::read_from(&mut Cursor::new(stream), ParseOptions::new()).unwrap();
+	let properties = file.properties();
+
+	assert_eq!(properties.channels(), 2);
+	assert_eq!(properties.sample_rate(), 0);
+	assert_eq!(properties.duration(), Duration::ZERO);
+}
+
+#[test_log::test]
+fn remove_id3v2() {
+	let mut id3v2 = Id3v2Tag::default();
+	id3v2.set_artist(String::from("Foo artist"));
+
+	let mut file_bytes = Vec::new();
+	id3v2
+		.dump_to(&mut file_bytes, WriteOptions::default())
+		.unwrap();
+	file_bytes.extend(std::fs::read("tests/files/assets/minimal/full_test.shn").unwrap());
+
+	let mut file = Cursor::new(file_bytes);
+	TagType::Id3v2.remove_from(&mut file).unwrap();
+
+	// Only the ID3v2 tag should be gone
+	file.rewind().unwrap();
+	let tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	assert!(tagged_file.tag(TagType::Id3v2).is_none());
+	crate::verify_artist!(tagged_file, primary_tag, "Foo artist", 1);
+	assert_eq!(
+		file.into_inner(),
+		std::fs::read("tests/files/assets/minimal/full_test.shn").unwrap()
+	);
+}
@@ -146,3 +146,24 @@ macro_rules! remove_tag {
 		assert!(tagged_file.tag($tag_type).is_none());
 	};
 }
+
+/// A writer whose contents remain accessible after it is handed off, see `Probe::pictures_to_writers`
+#[derive(Clone, Default)]
+pub(crate) struct SharedBuffer(pub(crate) std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+	pub(crate) fn contents(&self) -> Vec<u8> {
+		self.0.borrow().clone()
+	}
+}
+
+impl std::io::Write for SharedBuffer {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.0.borrow_mut().extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
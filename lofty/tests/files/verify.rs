@@ -0,0 +1,137 @@
+use lofty::error::ErrorKind;
+use lofty::file::FileType;
+use lofty::probe::Probe;
+use lofty::properties::StreamVerification;
+
+use std::io::Cursor;
+use std::path::Path;
+
+const FILES: [&str; 6] = [
+	"tests/files/assets/minimal/full_test.flac",
+	"tests/files/assets/minimal/full_test.mp3",
+	"tests/files/assets/minimal/full_test.ogg",
+	"tests/files/assets/minimal/full_test.opus",
+	"tests/files/assets/minimal/full_test.spx",
+	"tests/files/assets/minimal/full_test.oga",
+];
+
+fn verify(bytes: Vec<u8>) -> StreamVerification {
+	Probe::new(Cursor::new(bytes))
+		.guess_file_type()
+		.unwrap()
+		.verify_stream()
+		.unwrap()
+}
+
+// The offset of the audio, after any tags
+fn audio_start(path: &str, bytes: &[u8]) -> usize {
+	let find = |pattern: &[u8]| {
+		bytes
+			.windows(pattern.len())
+			.rposition(|window| window == pattern)
+			.unwrap()
+	};
+
+	match path.rsplit('.').next().unwrap() {
+		"flac" => {
+			// The last metadata block
+			let mut pos = 4;
+			loop {
+				let last = bytes[pos] & 0x80 != 0;
+				let size = u32::from_be_bytes([0, bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]);
+				pos += 4 + size as usize;
+				if last {
+					return pos;
+				}
+			}
+		},
+		"mp3" => find(b"Xing"),
+		// Anything after the first page
+		_ => bytes[4..].windows(4).position(|w| w == b"OggS").unwrap() + 4,
+	}
+}
+
+#[test_log::test]
+fn intact() {
+	for path in FILES {
+		let verification = lofty::verify_stream(path).unwrap();
+
+		assert!(verification.is_intact(), "{path}: {verification:?}");
+		assert_eq!(verification.found, verification.expected, "{path}");
+		assert!(!verification.expected.is_zero(), "{path}");
+
+		// Matches the duration read from the headers
+		let duration = lofty::duration(path).unwrap();
+		assert_eq!(verification.expected, duration, "{path}");
+	}
+}
+
+#[test_log::test]
+fn truncated() {
+	for path in FILES {
+		let mut bytes = std::fs::read(path).unwrap();
+
+		// Cut somewhere in the middle of the audio, making sure trailing tags are gone as well
+		let start = audio_start(path, &bytes);
+		bytes.truncate(start + (bytes.len() - start) / 2 + 7);
+
+		let verification = verify(bytes);
+		assert!(verification.truncated, "{path}: {verification:?}");
+		assert!(
+			verification.found < verification.expected,
+			"{path}: {verification:?}"
+		);
+		assert_eq!(verification.corrupt_at, None, "{path}");
+	}
+}
+
+#[test_log::test]
+fn corrupt() {
+	for path in FILES {
+		let mut bytes = std::fs::read(path).unwrap();
+
+		let start = audio_start(path, &bytes);
+		let end = bytes
+			.windows(3)
+			.rposition(|window| window == b"TAG" || window == b"APE")
+			.filter(|pos| *pos > start)
+			.unwrap_or(bytes.len());
+
+		let mut middle = start + (end - start) / 2;
+
+		// MPEG frames usually don't have a CRC, so the next frame header has to be damaged instead
+		if Path::new(path)
+			.extension()
+			.is_some_and(|e| e.eq_ignore_ascii_case("mp3"))
+		{
+			middle += bytes[middle..]
+				.windows(2)
+				.position(|window| window[0] == 0xFF && window[1] & 0xE0 == 0xE0)
+				.unwrap();
+		}
+
+		// Flip some bits in the middle of the audio
+		for byte in &mut bytes[middle..middle + 4] {
+			*byte ^= 0x55;
+		}
+
+		let verification = verify(bytes);
+		let corrupt_at = verification
+			.corrupt_at
+			.unwrap_or_else(|| panic!("{path}: {verification:?}"));
+		assert!(
+			(start as u64..=middle as u64).contains(&corrupt_at),
+			"{path}: {verification:?}"
+		);
+		assert!(!verification.truncated, "{path}: {verification:?}");
+	}
+}
+
+#[test_log::test]
+fn unsupported() {
+	let err = lofty::verify_stream("tests/files/assets/minimal/full_test.wv").unwrap_err();
+	assert!(matches!(
+		err.kind(),
+		ErrorKind::UnsupportedVerification(FileType::WavPack)
+	));
+}
@@ -1,11 +1,14 @@
 use crate::{set_artist, temp_file, verify_artist};
-use lofty::config::ParseOptions;
-use lofty::file::FileType;
+use lofty::config::{DuplicateTagPolicy, ParseOptions, RiffInfoPlacement, WriteOptions};
+use lofty::error::ParseDiagnostic;
+use lofty::file::{FileType, TaggedFile};
+use lofty::iff::wav::{BextChunk, BextLoudness, RiffInfoList, WavFile};
 use lofty::prelude::*;
 use lofty::probe::Probe;
-use lofty::tag::TagType;
+use lofty::tag::{ItemValue, TagItem, TagType};
 
-use std::io::Seek;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
 
 #[test_log::test]
 fn read() {
@@ -58,6 +61,64 @@ fn write() {
 	crate::set_artist!(tagged_file, tag_mut, TagType::RiffInfo, "Baz artist", 1 => file, "Bar artist");
 }
 
+#[test_log::test]
+fn riff_info_custom_keys() {
+	let mut file = temp_file!("tests/files/assets/minimal/wav_format_pcm.wav");
+
+	let mut tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let tag = tagged_file.tag_mut(TagType::RiffInfo).unwrap();
+	tag.insert_text(ItemKey::EncoderSoftware, String::from("Foo encoder"));
+	tag.insert_text(ItemKey::Engineer, String::from("Bar engineer"));
+	tag.insert_unchecked(TagItem::new(
+		ItemKey::Unknown(String::from("XMPT")),
+		ItemValue::Text(String::from("Baz value")),
+	));
+	// Not a valid four-character code, dropped when converting
+	tag.insert_unchecked(TagItem::new(
+		ItemKey::Unknown(String::from("XMPTX")),
+		ItemValue::Text(String::from("Qux value")),
+	));
+
+	file.rewind().unwrap();
+	tag.save_to(&mut file, WriteOptions::default()).unwrap();
+
+	file.rewind().unwrap();
+	let wav_file =
+		WavFile::read_from(&mut file, ParseOptions::new().read_properties(false)).unwrap();
+	let riff_info = wav_file.riff_info().unwrap();
+	assert_eq!(riff_info.get("IART"), Some("Bar artist"));
+	assert_eq!(riff_info.get("ISFT"), Some("Foo encoder"));
+	assert_eq!(riff_info.get("IENG"), Some("Bar engineer"));
+	assert_eq!(riff_info.get("XMPT"), Some("Baz value"));
+	assert_eq!(riff_info.get("XMPTX"), None);
+
+	// And back to `Tag`
+	file.rewind().unwrap();
+	let tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+
+	let tag = tagged_file.tag(TagType::RiffInfo).unwrap();
+	assert_eq!(
+		tag.get_string(&ItemKey::EncoderSoftware),
+		Some("Foo encoder")
+	);
+	assert_eq!(tag.get_string(&ItemKey::Engineer), Some("Bar engineer"));
+	assert_eq!(
+		tag.get_string(&ItemKey::Unknown(String::from("XMPT"))),
+		Some("Baz value")
+	);
+}
+
 #[test_log::test]
 fn remove_id3v2() {
 	crate::remove_tag!(
@@ -74,6 +135,43 @@ fn remove_riff_info() {
 	);
 }
 
+#[test_log::test]
+fn strip_all_tags() {
+	let content = std::fs::read("tests/files/assets/minimal/wav_format_pcm.wav").unwrap();
+	let mut file = temp_file!("tests/files/assets/minimal/wav_format_pcm.wav");
+
+	// Both the ID3v2 and RIFF INFO chunks should be removed at once
+	let removed = TaggedFile::strip_all_tags(&mut file).unwrap();
+	assert_eq!(removed, [TagType::Id3v2, TagType::RiffInfo]);
+
+	let mut stripped = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut stripped).unwrap();
+
+	// Every other chunk is left as is, and the RIFF size reflects the removals
+	let expected = riff_chunks(&content)
+		.into_iter()
+		.filter(|(fourcc, _)| !matches!(fourcc, b"ID3 " | b"id3 " | b"LIST"))
+		.collect::<Vec<_>>();
+	assert_eq!(riff_chunks(&stripped), expected);
+	assert_eq!(
+		u32::from_le_bytes(stripped[4..8].try_into().unwrap()) as usize,
+		stripped.len() - 8
+	);
+
+	file.rewind().unwrap();
+	let original = WavFile::read_from(&mut Cursor::new(&content), ParseOptions::new()).unwrap();
+	let stripped = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(!stripped.contains_tag());
+
+	// The overall bitrate shrinks with the file, but the audio should be untouched
+	let (properties, original_properties) = (stripped.properties(), original.properties());
+	assert_eq!(properties.duration(), original_properties.duration());
+	assert_eq!(properties.bitrate(), original_properties.bitrate());
+	assert_eq!(properties.sample_rate(), original_properties.sample_rate());
+	assert_eq!(properties.channels(), original_properties.channels());
+}
+
 #[test_log::test]
 fn issue_174_divide_by_zero() {
 	let file = Probe::open(
@@ -95,3 +193,459 @@ fn read_no_properties() {
 fn read_no_tags() {
 	crate::no_tag_test!("tests/files/assets/minimal/wav_format_pcm.wav");
 }
+
+#[test_log::test]
+fn duplicate_id3_chunks() {
+	fn read(policy: DuplicateTagPolicy) -> lofty::error::Result<WavFile> {
+		let mut file = File::open("tests/files/assets/duplicate_id3_chunks.wav").unwrap();
+		WavFile::read_from(
+			&mut file,
+			ParseOptions::new()
+				.read_properties(false)
+				.duplicate_tag_policy(policy),
+		)
+	}
+
+	// The first chunk has the title "First title" and artist "Foo artist", the second has the
+	// title "Second title" and album "Bar album"
+	let merged = read(DuplicateTagPolicy::Merge).unwrap();
+	let id3v2 = merged.id3v2().unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("Second title"));
+	assert_eq!(id3v2.artist().as_deref(), Some("Foo artist"));
+	assert_eq!(id3v2.album().as_deref(), Some("Bar album"));
+
+	let first = read(DuplicateTagPolicy::FirstWins).unwrap();
+	let id3v2 = first.id3v2().unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("First title"));
+	assert_eq!(id3v2.album(), None);
+
+	let last = read(DuplicateTagPolicy::LastWins).unwrap();
+	let id3v2 = last.id3v2().unwrap();
+	assert_eq!(id3v2.title().as_deref(), Some("Second title"));
+	assert_eq!(id3v2.artist(), None);
+
+	assert!(read(DuplicateTagPolicy::Error).is_err());
+
+	let tagged_file = Probe::open("tests/files/assets/duplicate_id3_chunks.wav")
+		.unwrap()
+		.options(ParseOptions::new().read_properties(false))
+		.read()
+		.unwrap();
+	assert_eq!(
+		tagged_file.diagnostics(),
+		&[ParseDiagnostic::DuplicateId3Chunk { offset: 4906 }]
+	);
+}
+
+#[test_log::test]
+fn write_removes_duplicate_id3_chunks() {
+	let mut file = temp_file!("tests/files/assets/duplicate_id3_chunks.wav");
+
+	let wav_file = WavFile::read_from(
+		&mut file,
+		ParseOptions::new().duplicate_tag_policy(DuplicateTagPolicy::LastWins),
+	)
+	.unwrap();
+
+	file.rewind().unwrap();
+	wav_file
+		.id3v2()
+		.unwrap()
+		.save_to(&mut file, WriteOptions::default())
+		.unwrap();
+
+	let mut bytes = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut bytes).unwrap();
+
+	assert_eq!(
+		bytes.windows(4).filter(|window| window == b"ID3 ").count(),
+		1
+	);
+
+	// The RIFF size is updated
+	assert_eq!(
+		u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize,
+		bytes.len() - 8
+	);
+
+	// And the saved chunk is read without any diagnostics
+	file.rewind().unwrap();
+	let tagged_file = Probe::new(&mut file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()
+		.unwrap()
+		.read()
+		.unwrap();
+	assert!(tagged_file.diagnostics().is_empty());
+	assert_eq!(
+		tagged_file.primary_tag().unwrap().title().as_deref(),
+		Some("Second title")
+	);
+}
+
+#[test_log::test]
+fn preserve_id3v2_size() {
+	let mut file =
+		Cursor::new(std::fs::read("tests/files/assets/minimal/wav_format_pcm.wav").unwrap());
+	let parse_options = ParseOptions::new().read_properties(false);
+
+	// Give the tag 8 KiB of padding to work with
+	let wav_file = WavFile::read_from(&mut file, parse_options).unwrap();
+	file.rewind().unwrap();
+	wav_file
+		.id3v2()
+		.unwrap()
+		.save_to(&mut file, WriteOptions::new().preferred_padding(8192))
+		.unwrap();
+
+	let original_content = file.get_ref().clone();
+	let chunk_start = original_content
+		.windows(4)
+		.position(|window| window == b"ID3 ")
+		.unwrap();
+	let chunk_size = u32::from_le_bytes(
+		original_content[chunk_start + 4..chunk_start + 8]
+			.try_into()
+			.unwrap(),
+	);
+	let chunk_end = chunk_start + 8 + chunk_size as usize;
+
+	file.rewind().unwrap();
+	let mut wav_file = WavFile::read_from(&mut file, parse_options).unwrap();
+	let id3v2 = wav_file.id3v2_mut().unwrap();
+	assert_eq!(id3v2.original_size(), Some(chunk_size));
+
+	id3v2.set_title(String::from("A longer title than before"));
+
+	file.rewind().unwrap();
+	id3v2
+		.save_to(&mut file, WriteOptions::new().preserve_id3v2_size(true))
+		.unwrap();
+
+	// Only the contents of the chunk have changed
+	let content = file.get_ref();
+	assert_eq!(content.len(), original_content.len());
+	assert_eq!(
+		content[..chunk_start + 8],
+		original_content[..chunk_start + 8]
+	);
+	assert_ne!(
+		content[chunk_start + 8..chunk_end],
+		original_content[chunk_start + 8..chunk_end]
+	);
+	assert_eq!(content[chunk_end..], original_content[chunk_end..]);
+
+	file.rewind().unwrap();
+	let wav_file = WavFile::read_from(&mut file, parse_options).unwrap();
+	assert_eq!(
+		wav_file.id3v2().unwrap().title().as_deref(),
+		Some("A longer title than before")
+	);
+}
+
+#[test_log::test]
+fn id3v24_footer_in_chunk() {
+	// An ID3v2.4 tag with a footer (and the unsynchronisation flag), followed by a RIFF INFO chunk
+	let file = Probe::open("tests/files/assets/id3v24_footer_chunk.wav")
+		.unwrap()
+		.options(ParseOptions::new().read_properties(false))
+		.read()
+		.unwrap();
+
+	assert_eq!(
+		file.primary_tag().unwrap().title().as_deref(),
+		Some("Foo title")
+	);
+	crate::verify_artist!(file, tag, TagType::RiffInfo, "Bar artist", 1);
+}
+
+fn riff_chunk(fourcc: [u8; 4], content: &[u8]) -> Vec<u8> {
+	let mut chunk = Vec::new();
+	chunk.extend(&fourcc);
+	chunk.extend(&(content.len() as u32).to_le_bytes());
+	chunk.extend(content);
+	if content.len() % 2 != 0 {
+		chunk.push(0);
+	}
+
+	chunk
+}
+
+fn riff_file(chunks: &[&[u8]]) -> Vec<u8> {
+	let body = chunks.concat();
+
+	let mut file = Vec::new();
+	file.extend(b"RIFF");
+	file.extend(&(body.len() as u32 + 4).to_le_bytes());
+	file.extend(b"WAVE");
+	file.extend(body);
+	file
+}
+
+// Every top level chunk, as (fourcc, chunk bytes)
+fn riff_chunks(file: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+	let mut chunks = Vec::new();
+
+	let mut pos = 12;
+	while pos + 8 <= file.len() {
+		let fourcc: [u8; 4] = file[pos..pos + 4].try_into().unwrap();
+		let size = u32::from_le_bytes(file[pos + 4..pos + 8].try_into().unwrap()) as usize;
+		let end = pos + 8 + size + size % 2;
+
+		chunks.push((fourcc, file[pos..end].to_vec()));
+		pos = end;
+	}
+
+	chunks
+}
+
+#[test_log::test]
+fn riff_info_placement() {
+	let mut fmt = Vec::new();
+	File::open("tests/files/assets/minimal/wav_format_pcm.wav")
+		.unwrap()
+		.read_to_end(&mut fmt)
+		.unwrap();
+	let fmt = fmt[12..36].to_vec();
+
+	// An odd sized chunk, to make sure the padding is preserved
+	let bext = riff_chunk(*b"bext", b"Foo bext");
+	let data = riff_chunk(*b"data", &[1; 1000]);
+
+	let mut old_info = RiffInfoList::default();
+	old_info.set_artist(String::from("Foo artist"));
+	let mut old_info_bytes = Vec::new();
+	old_info
+		.dump_to(&mut old_info_bytes, WriteOptions::default())
+		.unwrap();
+
+	let layouts: [(&str, Vec<&[u8]>); 4] = [
+		("before data", vec![&fmt, &bext, &old_info_bytes, &data]),
+		("after data", vec![&fmt, &bext, &data, &old_info_bytes]),
+		("before bext", vec![&fmt, &old_info_bytes, &bext, &data]),
+		("missing", vec![&fmt, &bext, &data]),
+	];
+
+	for (layout, chunks) in layouts {
+		for placement in [
+			RiffInfoPlacement::PreserveExisting,
+			RiffInfoPlacement::BeforeData,
+			RiffInfoPlacement::AfterData,
+		] {
+			let original = riff_file(&chunks);
+			let mut file = Cursor::new(original.clone());
+
+			let mut tag = RiffInfoList::default();
+			tag.set_artist(String::from("Bar artist"));
+			tag.save_to(
+				&mut file,
+				WriteOptions::new().riff_info_placement(placement),
+			)
+			.unwrap();
+
+			let written = file.into_inner();
+			let written_chunks = riff_chunks(&written);
+
+			let riff_size = u32::from_le_bytes(written[4..8].try_into().unwrap());
+			assert_eq!(
+				riff_size as usize,
+				written.len() - 8,
+				"{layout}, {placement:?}"
+			);
+
+			let order = written_chunks
+				.iter()
+				.map(|(fourcc, _)| fourcc)
+				.collect::<Vec<_>>();
+			let info_pos = order.iter().position(|f| *f == b"LIST").unwrap();
+			let data_pos = order.iter().position(|f| *f == b"data").unwrap();
+			match placement {
+				RiffInfoPlacement::BeforeData => {
+					assert_eq!(info_pos + 1, data_pos, "{layout}, {placement:?}")
+				},
+				RiffInfoPlacement::AfterData => {
+					assert_eq!(data_pos + 1, info_pos, "{layout}, {placement:?}")
+				},
+				_ => {
+					// The list stays where it was, or is appended
+					let original_order = riff_chunks(&original)
+						.into_iter()
+						.map(|(fourcc, _)| fourcc)
+						.collect::<Vec<_>>();
+					let expected_pos = original_order
+						.iter()
+						.position(|f| f == b"LIST")
+						.unwrap_or(original_order.len());
+					assert_eq!(info_pos, expected_pos, "{layout}, {placement:?}");
+				},
+			}
+
+			// Everything other than the list is untouched
+			let other_chunks = written_chunks
+				.iter()
+				.filter(|(fourcc, _)| fourcc != b"LIST")
+				.map(|(_, chunk)| &chunk[..])
+				.collect::<Vec<_>>();
+			assert_eq!(
+				other_chunks,
+				[&fmt[..], &bext, &data],
+				"{layout}, {placement:?}"
+			);
+
+			let mut reader = Cursor::new(written);
+			let wav_file = WavFile::read_from(&mut reader, ParseOptions::new()).unwrap();
+			let riff_info = wav_file.riff_info().unwrap();
+			assert_eq!(riff_info.artist().as_deref(), Some("Bar artist"));
+		}
+	}
+}
+
+#[test_log::test]
+fn read_bext() {
+	let mut file = File::open("tests/files/assets/bext.wav").unwrap();
+	let wav_file = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+	let bext = wav_file.bext().unwrap();
+	assert_eq!(bext.description, "Foo description");
+	assert_eq!(bext.originator, "Bar originator");
+	assert_eq!(bext.originator_reference, "BAR1234567890");
+	assert_eq!(bext.origination_date, "2024-01-15");
+	assert_eq!(bext.origination_time, "12:34:56");
+	assert_eq!(bext.time_reference, 158_760_000);
+	assert_eq!(bext.version, 2);
+	assert_eq!(bext.umid, std::array::from_fn(|i| i as u8));
+	assert_eq!(
+		bext.loudness,
+		Some(BextLoudness {
+			loudness_value: -2300,
+			loudness_range: 450,
+			max_true_peak_level: -100,
+			max_momentary_loudness: -1800,
+			max_short_term_loudness: -2000,
+		})
+	);
+	assert_eq!(
+		bext.coding_history,
+		"A=PCM,F=44100,W=16,M=stereo,T=Foo recorder\r\n"
+	);
+
+	// The description and originator are mapped to the RIFF INFO tag
+	let tagged_file = Probe::open("tests/files/assets/bext.wav")
+		.unwrap()
+		.read()
+		.unwrap();
+	let tag = tagged_file.tag(TagType::RiffInfo).unwrap();
+	assert_eq!(tag.get_string(&ItemKey::Comment), Some("Foo description"));
+	assert_eq!(tag.get_string(&ItemKey::EncodedBy), Some("Bar originator"));
+}
+
+#[test_log::test]
+fn write_bext() {
+	let mut file = temp_file!("tests/files/assets/bext.wav");
+
+	let mut original = Vec::new();
+	file.read_to_end(&mut original).unwrap();
+
+	// Writing the chunk back unchanged is lossless
+	file.rewind().unwrap();
+	let mut wav_file = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let mut bext = wav_file.bext().cloned().unwrap();
+
+	file.rewind().unwrap();
+	wav_file.set_bext(&mut file, Some(bext.clone())).unwrap();
+
+	let mut written = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut written).unwrap();
+	assert_eq!(written, original);
+
+	// Growing the coding history resizes the chunk, with an odd size to check the padding
+	bext.coding_history
+		.push_str("A=PCM,F=44100,W=16,M=stereo,T=Baz encoder\r\n!");
+	file.rewind().unwrap();
+	wav_file.set_bext(&mut file, Some(bext.clone())).unwrap();
+
+	let mut written = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut written).unwrap();
+	assert_eq!(
+		u32::from_le_bytes(written[4..8].try_into().unwrap()) as usize,
+		written.len() - 8
+	);
+
+	let chunks = riff_chunks(&written);
+	assert_eq!(
+		chunks.iter().map(|(fourcc, _)| fourcc).collect::<Vec<_>>(),
+		[b"fmt ", b"bext", b"data"]
+	);
+	assert_eq!(chunks.last().unwrap().1, riff_chunks(&original)[2].1);
+
+	file.rewind().unwrap();
+	let reread = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert_eq!(reread.bext(), Some(&bext));
+	assert_eq!(wav_file.bext(), Some(&bext));
+
+	// Fields that don't fit are rejected, leaving the file untouched
+	let mut too_long = bext.clone();
+	too_long.originator = "a".repeat(33);
+	file.rewind().unwrap();
+	assert!(wav_file.set_bext(&mut file, Some(too_long)).is_err());
+
+	let mut unchanged = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut unchanged).unwrap();
+	assert_eq!(unchanged, written);
+
+	// And finally remove it
+	file.rewind().unwrap();
+	wav_file.set_bext(&mut file, None).unwrap();
+	assert!(wav_file.bext().is_none());
+
+	file.rewind().unwrap();
+	let reread = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(reread.bext().is_none());
+}
+
+#[test_log::test]
+fn create_bext() {
+	let mut file = temp_file!("tests/files/assets/minimal/wav_format_pcm.wav");
+
+	let mut wav_file = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	assert!(wav_file.bext().is_none());
+
+	let bext = BextChunk {
+		description: String::from("Foo description"),
+		loudness: Some(BextLoudness::default()),
+		..BextChunk::default()
+	};
+
+	file.rewind().unwrap();
+	wav_file.set_bext(&mut file, Some(bext.clone())).unwrap();
+
+	let mut written = Vec::new();
+	file.rewind().unwrap();
+	file.read_to_end(&mut written).unwrap();
+
+	// Placed right before the data chunk
+	let order = riff_chunks(&written)
+		.into_iter()
+		.map(|(fourcc, _)| fourcc)
+		.collect::<Vec<_>>();
+	let bext_pos = order.iter().position(|f| f == b"bext").unwrap();
+	assert_eq!(order[bext_pos + 1], *b"data");
+
+	// Loudness values require version 2
+	file.rewind().unwrap();
+	let reread = WavFile::read_from(&mut file, ParseOptions::new()).unwrap();
+	let reread_bext = reread.bext().unwrap();
+	assert_eq!(reread_bext.version, 2);
+	assert_eq!(reread_bext.loudness, bext.loudness);
+	assert_eq!(reread_bext.description, bext.description);
+
+	// The existing tags are still intact
+	assert_eq!(
+		reread.riff_info().unwrap().artist().as_deref(),
+		Some("Bar artist")
+	);
+}
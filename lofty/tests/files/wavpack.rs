@@ -4,8 +4,10 @@ use lofty::file::FileType;
 use lofty::prelude::*;
 use lofty::probe::Probe;
 use lofty::tag::TagType;
+use lofty::wavpack::WavPackFile;
 
-use std::io::Seek;
+use std::io::{Cursor, Seek};
+use std::time::Duration;
 
 #[test_log::test]
 fn read() {
@@ -77,3 +79,59 @@ fn read_no_properties() {
 fn read_no_tags() {
 	crate::no_tag_test!("tests/files/assets/minimal/full_test.wv");
 }
+
+// A single WavPack block header, with no audio data
+fn wavpack_block(flags: u32) -> Vec<u8> {
+	const SAMPLES: u32 = 44100;
+
+	let mut block = Vec::new();
+	block.extend(b"wvpk");
+	block.extend(24_u32.to_le_bytes()); // Block size
+	block.extend(0x410_u16.to_le_bytes()); // Version
+	block.extend([0, 0]); // Track number, track sub index
+	block.extend(SAMPLES.to_le_bytes()); // Total samples
+	block.extend(0_u32.to_le_bytes()); // Block index
+	block.extend(SAMPLES.to_le_bytes()); // Block samples
+	block.extend(flags.to_le_bytes());
+	block.extend(0_u32.to_le_bytes()); // CRC
+	block
+}
+
+#[test_log::test]
+fn hybrid_properties() {
+	// 16-bit, stereo, 44.1kHz, initial and final block
+	const FLAGS: u32 = 0x1 | 0x800 | 0x1000 | (9 << 23);
+	const HYBRID_FLAG: u32 = 0x8;
+
+	let lossless =
+		WavPackFile::read_from(&mut Cursor::new(wavpack_block(FLAGS)), ParseOptions::new())
+			.unwrap();
+	let properties = lossless.properties();
+	assert!(properties.is_lossless());
+	assert!(!properties.is_hybrid());
+	assert_eq!(properties.lossy_bitrate(), None);
+
+	let hybrid = WavPackFile::read_from(
+		&mut Cursor::new(wavpack_block(FLAGS | HYBRID_FLAG)),
+		ParseOptions::new(),
+	)
+	.unwrap();
+	let properties = hybrid.properties();
+	assert!(!properties.is_lossless());
+	assert!(properties.is_hybrid());
+	assert_eq!(properties.duration(), Duration::from_secs(1));
+	assert_eq!(properties.lossy_bitrate(), Some(properties.audio_bitrate()));
+}
+
+#[test_log::test]
+fn correction_file_path() {
+	let dir = tempfile::tempdir().unwrap();
+
+	let wv_path = dir.path().join("hybrid.wv");
+	std::fs::write(&wv_path, wavpack_block(0)).unwrap();
+	assert_eq!(WavPackFile::correction_file_path(&wv_path), None);
+
+	let wvc_path = dir.path().join("hybrid.wvc");
+	std::fs::write(&wvc_path, []).unwrap();
+	assert_eq!(WavPackFile::correction_file_path(&wv_path), Some(wvc_path));
+}
@@ -2,6 +2,7 @@ use crate::{get_reader, oom_test};
 use lofty::config::ParseOptions;
 use lofty::file::AudioFile;
 use lofty::flac::FlacFile;
+use lofty::prelude::*;
 
 #[test_log::test]
 fn oom1() {
@@ -14,3 +15,26 @@ fn panic1() {
 		get_reader("flacfile_read_from/flac_with_id3v2_IDX_39_RAND_108668567929800767822112.flac");
 	let _ = FlacFile::read_from(&mut reader, ParseOptions::default());
 }
+
+#[test_log::test]
+fn oversized_block1() {
+	let mut reader = get_reader("flacfile_read_from/oversized_picture_block.flac");
+	let _ = FlacFile::read_from(&mut reader, ParseOptions::default());
+}
+
+#[test_log::test]
+fn vorbis_comments_too_many_fields() {
+	let mut reader = get_reader("flacfile_read_from/vorbis_comments_too_many_fields.flac");
+	let file = FlacFile::read_from(&mut reader, ParseOptions::default()).unwrap();
+	assert_eq!(
+		file.vorbis_comments().unwrap().artist().as_deref(),
+		Some("Foo artist")
+	);
+}
+
+#[test_log::test]
+fn vorbis_comments_oversized_field() {
+	let mut reader = get_reader("flacfile_read_from/vorbis_comments_oversized_field.flac");
+	let file = FlacFile::read_from(&mut reader, ParseOptions::default()).unwrap();
+	assert!(file.vorbis_comments().unwrap().is_empty());
+}
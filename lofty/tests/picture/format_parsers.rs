@@ -1,6 +1,8 @@
+use lofty::ape::ApeItem;
 use lofty::config::ParsingMode;
 use lofty::id3::v2::{AttachedPictureFrame, FrameFlags, Id3v2Version};
 use lofty::picture::{Picture, PictureInformation, PictureType};
+use lofty::tag::ItemValue;
 use lofty::TextEncoding;
 
 use std::fs::File;
@@ -90,6 +92,49 @@ fn as_ape_bytes() {
 	assert_eq!(buf, original_as_ape);
 }
 
+#[test_log::test]
+fn ape_filename_round_trip() {
+	let mut picture = create_original_picture();
+	picture.set_description(Some(String::from("pochette_été.png")));
+
+	let bytes = picture.as_ape_bytes();
+	assert!(bytes.starts_with("pochette_été.png\0".as_bytes()));
+
+	let parsed = Picture::from_ape_bytes("Cover Art (Front)", &bytes).unwrap();
+	assert_eq!(parsed, picture);
+
+	// An explicit filename
+	let bytes = picture.as_ape_bytes_with_filename("封面.png");
+	let parsed = Picture::from_ape_bytes("Cover Art (Front)", &bytes).unwrap();
+	assert_eq!(parsed.description(), Some("封面.png"));
+	assert_eq!(parsed.data(), picture.data());
+}
+
+#[test_log::test]
+fn ape_filename_from_mime_type() {
+	let mut picture = create_original_picture();
+
+	// Not a filename
+	picture.set_description(Some(String::from("The front cover")));
+	assert!(picture.as_ape_bytes().starts_with(b"cover.png\0"));
+
+	picture.set_description(None);
+	assert!(picture.as_ape_bytes().starts_with(b"cover.png\0"));
+}
+
+#[test_log::test]
+fn ape_item_from_picture() {
+	let picture = create_original_picture();
+
+	let item = ApeItem::try_from(&picture).unwrap();
+	assert_eq!(item.key(), "Cover Art (Front)");
+	assert_eq!(item.value(), &ItemValue::Binary(picture.as_ape_bytes()));
+
+	let mut picture = picture;
+	picture.set_pic_type(PictureType::Undefined(50));
+	assert!(ApeItem::try_from(&picture).is_err());
+}
+
 #[test_log::test]
 fn flac_metadata_block_picture() {
 	let buf = get_buf("tests/picture/assets/png_640x628.vorbis");
@@ -99,6 +144,33 @@ fn flac_metadata_block_picture() {
 	assert_eq!(create_original_picture(), pic);
 }
 
+#[test_log::test]
+fn flac_metadata_block_picture_lenient_base64() {
+	let buf = get_buf("tests/picture/assets/png_640x628.vorbis");
+
+	// Line breaks are tolerated
+	let wrapped = buf
+		.chunks(76)
+		.flat_map(|line| line.iter().copied().chain(*b"\r\n"))
+		.collect::<Vec<_>>();
+
+	let (pic, _) = Picture::from_flac_bytes(&wrapped, true, ParsingMode::Strict).unwrap();
+	assert_eq!(create_original_picture(), pic);
+
+	// As is missing padding
+	let mut original_picture = create_original_picture();
+	original_picture.set_description(Some(String::from("png_640x628.png!")));
+
+	let mut encoded = original_picture.as_flac_bytes(PictureInformation::default(), true);
+	assert_eq!(encoded.last(), Some(&b'='));
+	while encoded.last() == Some(&b'=') {
+		encoded.pop();
+	}
+
+	let (pic, _) = Picture::from_flac_bytes(&encoded, true, ParsingMode::Strict).unwrap();
+	assert_eq!(original_picture, pic);
+}
+
 #[test_log::test]
 fn as_flac_bytes() {
 	let buf = get_buf("tests/picture/assets/png_640x628.vorbis");
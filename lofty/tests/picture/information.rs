@@ -56,3 +56,87 @@ fn read_jpeg() {
 	// Always 0, not applicable for JPEG
 	assert_eq!(information.num_colors, 0);
 }
+
+#[test_log::test]
+fn read_progressive_jpeg() {
+	// SOI, APP0 (JFIF), SOF2 (progressive DCT), 8-bit precision, 3 components
+	let data = [
+		0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00, 0x01, 0x01, 0x00, 0x00,
+		0x01, 0x00, 0x01, 0x00, 0x00, 0xFF, 0xFF, 0xC2, 0x00, 0x11, 0x08, 0x02, 0x74, 0x02, 0x80,
+		0x03, 0x01, 0x22, 0x00, 0x02, 0x11, 0x01, 0x03, 0x11, 0x01, 0xFF, 0xDA,
+	];
+
+	let information = PictureInformation::from_jpeg(&data).unwrap();
+
+	assert_eq!(information.width, 640);
+	assert_eq!(information.height, 628);
+	assert_eq!(information.color_depth, 24);
+	assert_eq!(information.num_colors, 0);
+}
+
+#[test_log::test]
+fn read_gif() {
+	let mut f = File::open("tests/picture/assets/gif_640x628.gif").unwrap();
+
+	let mut buf = Vec::new();
+	f.read_to_end(&mut buf).unwrap();
+
+	let information = PictureInformation::from_gif(&buf).unwrap();
+
+	assert_eq!(information.width, 640);
+	assert_eq!(information.height, 628);
+	assert_eq!(information.color_depth, 8);
+
+	// Size of the global color table
+	assert_eq!(information.num_colors, 256);
+}
+
+#[test_log::test]
+fn read_bmp() {
+	let mut f = File::open("tests/picture/assets/bmp_640x628.bmp").unwrap();
+
+	let mut buf = Vec::new();
+	f.read_to_end(&mut buf).unwrap();
+
+	let information = PictureInformation::from_bmp(&buf).unwrap();
+
+	assert_eq!(information.width, 640);
+	assert_eq!(information.height, 628);
+	assert_eq!(information.color_depth, 32);
+
+	// No palette
+	assert_eq!(information.num_colors, 0);
+}
+
+#[test_log::test]
+fn read_from_picture_data() {
+	for (path, color_depth) in [
+		("tests/picture/assets/png_640x628.png", 32),
+		("tests/picture/assets/png_640x628_plte.png", 8),
+		("tests/picture/assets/jpeg_640x628.jpg", 24),
+		("tests/picture/assets/gif_640x628.gif", 8),
+		("tests/picture/assets/bmp_640x628.bmp", 32),
+	] {
+		let mut f = File::open(path).unwrap();
+
+		let mut buf = Vec::new();
+		f.read_to_end(&mut buf).unwrap();
+
+		let information = PictureInformation::from_picture_data(&buf).unwrap();
+
+		assert_eq!(information.width, 640, "{path}");
+		assert_eq!(information.height, 628, "{path}");
+		assert_eq!(information.color_depth, color_depth, "{path}");
+	}
+
+	// Unsupported formats are zeroed out
+	let mut f = File::open("tests/picture/assets/tiff_640x628.tiff").unwrap();
+
+	let mut buf = Vec::new();
+	f.read_to_end(&mut buf).unwrap();
+
+	assert_eq!(
+		PictureInformation::from_picture_data(&buf).unwrap(),
+		PictureInformation::default()
+	);
+}
@@ -3,3 +3,4 @@
 mod format_parsers;
 mod from_reader;
 mod information;
+mod mime;
@@ -0,0 +1,190 @@
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::error::ErrorKind;
+use lofty::file::TaggedFileExt;
+use lofty::id3::v2::{AttachedPictureFrame, FrameFlags, Id3v2Version};
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::probe::Probe;
+use lofty::tag::{Tag, TagExt, TagType};
+
+use std::io::Cursor;
+
+const PNG_IMAGE: &[u8] = include_bytes!("assets/png_640x628.png");
+
+fn picture(mime_type: Option<MimeType>, data: &[u8]) -> Picture {
+	Picture::new_unchecked(PictureType::CoverFront, mime_type, None, data.to_vec())
+}
+
+fn parse_apic(path: &str) -> Picture {
+	let buf = std::fs::read(path).unwrap();
+	AttachedPictureFrame::parse(&mut &buf[..], FrameFlags::default(), Id3v2Version::V4)
+		.unwrap()
+		.picture
+}
+
+// Writes `pic` to a copy of `path`, returning the picture that was read back
+fn write_and_read_back(
+	path: &str,
+	tag_type: TagType,
+	pic: Picture,
+	write_options: WriteOptions,
+) -> lofty::error::Result<Picture> {
+	let mut file = Cursor::new(std::fs::read(path).unwrap());
+
+	let mut tag = Tag::new(tag_type);
+	tag.push_picture(pic);
+	tag.save_to(&mut file, write_options)?;
+
+	file.set_position(0);
+	let tagged_file = Probe::new(file)
+		.options(ParseOptions::new().read_properties(false))
+		.guess_file_type()?
+		.read()?;
+
+	Ok(tagged_file.tag(tag_type).unwrap().pictures()[0].clone())
+}
+
+#[test_log::test]
+fn detect_mime() {
+	for (path, mime_type) in [
+		("tests/picture/assets/png_640x628.png", MimeType::Png),
+		("tests/picture/assets/jpeg_640x628.jpg", MimeType::Jpeg),
+		("tests/picture/assets/gif_640x628.gif", MimeType::Gif),
+		("tests/picture/assets/bmp_640x628.bmp", MimeType::Bmp),
+		("tests/picture/assets/tiff_640x628.tiff", MimeType::Tiff),
+	] {
+		let pic = picture(None, &std::fs::read(path).unwrap());
+		assert_eq!(pic.detect_mime(), Some(mime_type), "{path}");
+	}
+
+	let webp = picture(None, b"RIFF\x24\x00\x00\x00WEBPVP8 ");
+	assert_eq!(webp.detect_mime(), Some(MimeType::WebP));
+
+	let url = picture(None, b"https://example.com/cover.png");
+	assert_eq!(url.detect_mime(), None);
+}
+
+#[test_log::test]
+fn verify_mismatched_mime() {
+	let pic = parse_apic("tests/picture/assets/png_640x628_as_jpeg.apic");
+	assert_eq!(pic.mime_type(), Some(&MimeType::Jpeg));
+
+	let err = pic.verify().unwrap_err();
+	assert!(matches!(
+		err.kind(),
+		ErrorKind::PictureMimeMismatch {
+			declared: Some(MimeType::Jpeg),
+			detected: Some(MimeType::Png),
+		}
+	));
+
+	// A missing MIME type doesn't match either
+	assert!(picture(None, PNG_IMAGE).verify().is_err());
+
+	// Nor does claiming a known format for data that isn't one
+	assert!(picture(Some(MimeType::Png), b"not a picture")
+		.verify()
+		.is_err());
+}
+
+#[test_log::test]
+fn verify_nonstandard_mime() {
+	// "image/jpg" is understood as "image/jpeg"
+	let pic = parse_apic("tests/picture/assets/jpeg_640x628_nonstandard_mime.apic");
+	assert_eq!(pic.mime_type(), Some(&MimeType::Jpeg));
+	pic.verify().unwrap();
+
+	let jpeg = std::fs::read("tests/picture/assets/jpeg_640x628.jpg").unwrap();
+	let pic = picture(Some(MimeType::Unknown(String::from("image/jpg"))), &jpeg);
+	pic.verify().unwrap();
+
+	// Data in an unknown format can't be verified
+	let url = picture(
+		Some(MimeType::Unknown(String::from("-->"))),
+		b"https://example.com/cover.png",
+	);
+	url.verify().unwrap();
+}
+
+#[test_log::test]
+fn fix_id3v2_mime() {
+	let pic = parse_apic("tests/picture/assets/png_640x628_as_jpeg.apic");
+
+	// The declared MIME type is kept by default
+	let written = write_and_read_back(
+		"tests/files/assets/minimal/full_test.mp3",
+		TagType::Id3v2,
+		pic.clone(),
+		WriteOptions::default(),
+	)
+	.unwrap();
+	assert_eq!(written.mime_type(), Some(&MimeType::Jpeg));
+
+	let written = write_and_read_back(
+		"tests/files/assets/minimal/full_test.mp3",
+		TagType::Id3v2,
+		pic,
+		WriteOptions::new().fix_picture_mime_types(true),
+	)
+	.unwrap();
+	assert_eq!(written.mime_type(), Some(&MimeType::Png));
+	assert_eq!(written.data(), PNG_IMAGE);
+}
+
+#[test_log::test]
+fn fix_nonstandard_mime() {
+	let jpeg = std::fs::read("tests/picture/assets/jpeg_640x628.jpg").unwrap();
+
+	// The MIME type is detected from the data, falling back to normalizing the declared one
+	for (data, expected) in [(&jpeg[..], MimeType::Jpeg), (&b"????"[..], MimeType::Png)] {
+		let pic = picture(Some(MimeType::Unknown(String::from("IMAGE/PNG"))), data);
+		let written = write_and_read_back(
+			"tests/files/assets/minimal/full_test.flac",
+			TagType::VorbisComments,
+			pic,
+			WriteOptions::new().fix_picture_mime_types(true),
+		)
+		.unwrap();
+
+		assert_eq!(written.mime_type(), Some(&expected));
+	}
+}
+
+#[test_log::test]
+fn flac_url_picture_with_binary_data() {
+	let pic = picture(Some(MimeType::Unknown(String::from("-->"))), PNG_IMAGE);
+
+	let err = write_and_read_back(
+		"tests/files/assets/minimal/full_test.flac",
+		TagType::VorbisComments,
+		pic.clone(),
+		WriteOptions::default(),
+	)
+	.unwrap_err();
+	assert!(matches!(err.kind(), ErrorKind::BinaryPictureUrl));
+
+	let written = write_and_read_back(
+		"tests/files/assets/minimal/full_test.flac",
+		TagType::VorbisComments,
+		pic,
+		WriteOptions::new().fix_picture_mime_types(true),
+	)
+	.unwrap();
+	assert_eq!(written.mime_type(), Some(&MimeType::Png));
+
+	// Actual URLs are left alone
+	let url = picture(
+		Some(MimeType::Unknown(String::from("-->"))),
+		b"https://example.com/cover.png",
+	);
+	let written = write_and_read_back(
+		"tests/files/assets/minimal/full_test.flac",
+		TagType::VorbisComments,
+		url,
+		WriteOptions::new().fix_picture_mime_types(true),
+	)
+	.unwrap();
+	assert_eq!(
+		written.mime_type(),
+		Some(&MimeType::Unknown(String::from("-->")))
+	);
+}
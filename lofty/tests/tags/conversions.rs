@@ -1,10 +1,21 @@
 // Tests for special case conversions
 
-use lofty::id3::v2::{CommentFrame, Frame, FrameId, Id3v2Tag, UnsynchronizedTextFrame};
-use lofty::tag::{ItemKey, Tag, TagType};
+use lofty::ape::{ApeItem, ApeTag};
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::id3::v2::{
+	BinaryFrame, CommentFrame, ExtendedTextFrame, Frame, FrameId, FrameIter, Id3v2Header, Id3v2Tag,
+	UniqueFileIdentifierFrame, UnsynchronizedTextFrame, UrlLinkFrame,
+};
+use lofty::mp4::{Atom, AtomData, AtomIdent, DataType, Ilst, Mp4File};
+use lofty::mpeg::MpegFile;
+use lofty::ogg::VorbisComments;
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::prelude::*;
+use lofty::tag::{ConversionLoss, ItemKey, ItemValue, Tag, TagItem, TagType};
 use lofty::TextEncoding;
 
 use std::borrow::Cow;
+use std::io::{Cursor, Seek};
 
 #[test_log::test]
 fn tag_to_id3v2_lang_frame() {
@@ -34,3 +45,719 @@ fn tag_to_id3v2_lang_frame() {
 		)))
 	);
 }
+
+const MUSICBRAINZ_UFID_OWNER: &str = "http://musicbrainz.org";
+
+// The MusicBrainz identifiers, along with their keys as written by MusicBrainz Picard
+//
+// (key, Vorbis Comments/APE key, ID3v2 TXXX description, MP4 freeform name, value)
+//
+// <https://picard-docs.musicbrainz.org/en/appendices/tag_mapping.html>
+const MUSICBRAINZ_IDS: [(ItemKey, &str, Option<&str>, &str, &str); 7] = [
+	(
+		ItemKey::MusicBrainzRecordingId,
+		"MUSICBRAINZ_TRACKID",
+		None, // Stored in a UFID frame
+		"MusicBrainz Track Id",
+		"7a5f1b8c-2f4e-4a8e-9d8b-0b6c2f4f9c11",
+	),
+	(
+		ItemKey::MusicBrainzTrackId,
+		"MUSICBRAINZ_RELEASETRACKID",
+		Some("MusicBrainz Release Track Id"),
+		"MusicBrainz Release Track Id",
+		"3d2d5a6e-58a5-4b8e-8c5d-0c0a2c1e8f22",
+	),
+	(
+		ItemKey::MusicBrainzReleaseId,
+		"MUSICBRAINZ_ALBUMID",
+		Some("MusicBrainz Album Id"),
+		"MusicBrainz Album Id",
+		"c1a1e4f2-7f6d-4b8c-9a2e-3e4b5c6d7e33",
+	),
+	(
+		ItemKey::MusicBrainzReleaseGroupId,
+		"MUSICBRAINZ_RELEASEGROUPID",
+		Some("MusicBrainz Release Group Id"),
+		"MusicBrainz Release Group Id",
+		"0f9e8d7c-6b5a-4c3d-8e2f-1a0b9c8d7e44",
+	),
+	(
+		ItemKey::MusicBrainzArtistId,
+		"MUSICBRAINZ_ARTISTID",
+		Some("MusicBrainz Artist Id"),
+		"MusicBrainz Artist Id",
+		"5b11f4ce-a62d-471e-81fc-a69a8278c7da",
+	),
+	(
+		ItemKey::MusicBrainzReleaseArtistId,
+		"MUSICBRAINZ_ALBUMARTISTID",
+		Some("MusicBrainz Album Artist Id"),
+		"MusicBrainz Album Artist Id",
+		"89ad4ac3-39f7-470e-963a-56509c546377",
+	),
+	(
+		ItemKey::MusicBrainzWorkId,
+		"MUSICBRAINZ_WORKID",
+		Some("MusicBrainz Work Id"),
+		"MusicBrainz Work Id",
+		"e2f3a4b5-c6d7-4e8f-9a0b-1c2d3e4f5a66",
+	),
+];
+
+fn picard_vorbis_comments() -> VorbisComments {
+	let mut vorbis_comments = VorbisComments::new();
+	for (_, key, _, _, value) in MUSICBRAINZ_IDS {
+		vorbis_comments.push(String::from(key), String::from(value));
+	}
+
+	vorbis_comments
+}
+
+fn picard_id3v2() -> Id3v2Tag {
+	let mut id3v2 = Id3v2Tag::new();
+	for (_, _, description, _, value) in MUSICBRAINZ_IDS {
+		let frame = match description {
+			Some(description) => Frame::UserText(ExtendedTextFrame::new(
+				TextEncoding::UTF8,
+				String::from(description),
+				String::from(value),
+			)),
+			None => Frame::UniqueFileIdentifier(UniqueFileIdentifierFrame::new(
+				String::from(MUSICBRAINZ_UFID_OWNER),
+				value.as_bytes().to_vec(),
+			)),
+		};
+
+		id3v2.insert(frame);
+	}
+
+	id3v2
+}
+
+fn picard_ilst() -> Ilst {
+	let mut ilst = Ilst::new();
+	for (_, _, _, name, value) in MUSICBRAINZ_IDS {
+		ilst.insert(Atom::new(
+			AtomIdent::Freeform {
+				mean: Cow::Borrowed("com.apple.iTunes"),
+				name: Cow::Borrowed(name),
+			},
+			AtomData::UTF8(String::from(value)),
+		));
+	}
+
+	ilst
+}
+
+fn assert_musicbrainz_items(tag: &Tag) {
+	for (key, _, _, _, value) in MUSICBRAINZ_IDS {
+		assert_eq!(tag.get_string(&key), Some(value), "{key:?}");
+	}
+}
+
+fn assert_picard_id3v2_frames<'a>(frames: impl IntoIterator<Item = &'a Frame<'static>>) {
+	let frames = frames.into_iter().collect::<Vec<_>>();
+
+	for (key, _, description, _, value) in MUSICBRAINZ_IDS {
+		let found = frames.iter().any(|frame| match (frame, description) {
+			(Frame::UserText(frame), Some(description)) => {
+				frame.description == description && frame.content == value
+			},
+			(Frame::UniqueFileIdentifier(frame), None) => {
+				frame.owner == MUSICBRAINZ_UFID_OWNER && frame.identifier == value.as_bytes()
+			},
+			_ => false,
+		});
+
+		assert!(found, "{key:?}");
+	}
+
+	// There should be no duplicates, such as a TXXX frame for the recording ID
+	let identifier_frames = frames
+		.iter()
+		.filter(|frame| matches!(frame, Frame::UserText(_) | Frame::UniqueFileIdentifier(_)))
+		.count();
+	assert_eq!(identifier_frames, MUSICBRAINZ_IDS.len());
+}
+
+fn assert_picard_ilst_atoms(ilst: &Ilst) {
+	for (key, _, _, name, value) in MUSICBRAINZ_IDS {
+		let ident = AtomIdent::Freeform {
+			mean: Cow::Borrowed("com.apple.iTunes"),
+			name: Cow::Borrowed(name),
+		};
+
+		let atom = ilst.get(&ident);
+		assert!(atom.is_some(), "{key:?}");
+		assert_eq!(
+			atom.unwrap().data().collect::<Vec<_>>(),
+			[&AtomData::UTF8(String::from(value))],
+			"{key:?}"
+		);
+	}
+}
+
+#[test_log::test]
+fn musicbrainz_ids_from_vorbis_comments() {
+	let tag: Tag = picard_vorbis_comments().into();
+	assert_musicbrainz_items(&tag);
+
+	// Converting to a concrete tag
+	let id3v2 = Id3v2Tag::from(tag.clone());
+	assert_picard_id3v2_frames(&id3v2);
+
+	// Writing the generic tag directly
+	let mut id3v2_tag = tag.clone();
+	id3v2_tag.re_map(TagType::Id3v2);
+
+	let mut tag_bytes = Vec::new();
+	id3v2_tag
+		.dump_to(&mut tag_bytes, WriteOptions::default())
+		.unwrap();
+
+	let mut reader = Cursor::new(tag_bytes);
+	let header = Id3v2Header::parse(&mut reader).unwrap();
+	let frames = FrameIter::new(&mut reader, header, ParseOptions::new())
+		.collect::<Result<Vec<_>, _>>()
+		.unwrap();
+	assert_picard_id3v2_frames(&frames);
+
+	assert_picard_ilst_atoms(&Ilst::from(tag));
+}
+
+#[test_log::test]
+fn musicbrainz_ids_from_id3v2() {
+	let tag: Tag = picard_id3v2().into();
+	assert_musicbrainz_items(&tag);
+
+	let vorbis_comments = VorbisComments::from(tag.clone());
+	for (key, vorbis_key, _, _, value) in MUSICBRAINZ_IDS {
+		assert_eq!(vorbis_comments.get(vorbis_key), Some(value), "{key:?}");
+	}
+
+	// APE uses the same keys as Vorbis Comments
+	let ape = ApeTag::from(tag.clone());
+	for (key, ape_key, _, _, value) in MUSICBRAINZ_IDS {
+		assert_eq!(
+			ape.get(ape_key).map(ApeItem::value),
+			Some(&ItemValue::Text(String::from(value))),
+			"{key:?}"
+		);
+	}
+
+	assert_picard_ilst_atoms(&Ilst::from(tag));
+}
+
+#[test_log::test]
+fn musicbrainz_ids_from_ilst() {
+	let tag: Tag = picard_ilst().into();
+	assert_musicbrainz_items(&tag);
+
+	let id3v2 = Id3v2Tag::from(tag.clone());
+	assert_picard_id3v2_frames(&id3v2);
+
+	let vorbis_comments = VorbisComments::from(tag);
+	for (key, vorbis_key, _, _, value) in MUSICBRAINZ_IDS {
+		assert_eq!(vorbis_comments.get(vorbis_key), Some(value), "{key:?}");
+	}
+}
+
+const LONG_TITLE: &str = "A title that is far too long to fit in an ID3v1 tag";
+
+#[test_log::test]
+fn flac_to_id3v1_conversion_report() {
+	let tagged_file = lofty::read_from_path("tests/files/assets/minimal/full_test.flac").unwrap();
+	let mut tag = tagged_file.primary_tag().unwrap().clone();
+	assert_eq!(tag.tag_type(), TagType::VorbisComments);
+
+	tag.set_title(String::from(LONG_TITLE));
+	tag.insert_text(ItemKey::Composer, String::from("Foo composer"));
+
+	let picture = Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		None,
+		vec![1, 2, 3, 4],
+	);
+	tag.push_picture(picture.clone());
+
+	let report = tag.conversion_report(TagType::Id3v1);
+	assert_eq!(report.target(), TagType::Id3v1);
+	assert!(!report.is_lossless());
+
+	let truncated = report.truncated_items().collect::<Vec<_>>();
+	assert_eq!(truncated.len(), 1);
+	assert_eq!(truncated[0].0.key(), &ItemKey::TrackTitle);
+	assert_eq!(truncated[0].1, &LONG_TITLE[..30]);
+
+	assert!(report
+		.dropped_items()
+		.any(|item| item.key() == &ItemKey::Composer));
+	assert_eq!(report.dropped_pictures().collect::<Vec<_>>(), [&picture]);
+
+	// The report matches what is actually written
+	let mut id3v1_tag = tag.clone();
+	id3v1_tag.re_map(TagType::Id3v1);
+
+	let mut id3v1 = Vec::new();
+	id3v1_tag
+		.dump_to(&mut id3v1, WriteOptions::default())
+		.unwrap();
+	assert_eq!(&id3v1[3..33], &LONG_TITLE.as_bytes()[..30]);
+}
+
+#[test_log::test]
+fn lossless_conversion_report() {
+	let mut tag = Tag::new(TagType::VorbisComments);
+	tag.set_title(String::from("Foo title"));
+	tag.set_artist(String::from("Bar artist"));
+	tag.push_picture(Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		Some(String::from("Front")),
+		vec![1, 2, 3, 4],
+	));
+
+	let report = tag.conversion_report(TagType::Id3v2);
+	assert!(report.is_lossless(), "{:?}", report.losses());
+
+	// `ilst` pictures have no description or type
+	let report = tag.conversion_report(TagType::Mp4Ilst);
+	assert_eq!(report.losses().len(), 1);
+	assert!(matches!(
+		report.losses()[0],
+		ConversionLoss::ChangedPicture { .. }
+	));
+}
+
+const PODCAST_ITEMS: [(ItemKey, &str, &str, &str); 5] = [
+	(
+		ItemKey::PodcastDescription,
+		"ldes",
+		"TDES",
+		"A longer description of the episode",
+	),
+	(ItemKey::PodcastSeriesCategory, "catg", "TCAT", "Technology"),
+	(
+		ItemKey::PodcastUrl,
+		"purl",
+		"WFED",
+		"https://example.com/feed.xml",
+	),
+	(
+		ItemKey::PodcastGlobalUniqueId,
+		"egid",
+		"TGID",
+		"https://example.com/episodes/1",
+	),
+	(ItemKey::PodcastKeywords, "keyw", "TKWD", "rust,audio"),
+];
+
+#[test_log::test]
+fn itunes_podcast_round_trip() {
+	// iTunes stores `purl` and `egid` without a type
+	let mut ilst = Ilst::new();
+	for (_, fourcc, _, value) in PODCAST_ITEMS {
+		let data = match fourcc {
+			"purl" | "egid" => AtomData::Unknown {
+				code: DataType::Reserved,
+				data: value.as_bytes().to_vec(),
+			},
+			_ => AtomData::UTF8(String::from(value)),
+		};
+
+		ilst.insert(Atom::new(
+			AtomIdent::Fourcc(fourcc.as_bytes().try_into().unwrap()),
+			data,
+		));
+	}
+	ilst.insert(Atom::new(AtomIdent::Fourcc(*b"pcst"), AtomData::Bool(true)));
+
+	let mut m4a =
+		Cursor::new(std::fs::read("tests/files/assets/minimal/m4a_codec_aac.m4a").unwrap());
+	ilst.save_to(&mut m4a, WriteOptions::default()).unwrap();
+
+	m4a.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut m4a, ParseOptions::new()).unwrap();
+	let tag: Tag = mp4_file.ilst().unwrap().clone().into();
+
+	for (key, _, _, value) in PODCAST_ITEMS {
+		assert_eq!(tag.get_string(&key), Some(value), "{key:?}");
+	}
+	assert_eq!(tag.get_string(&ItemKey::FlagPodcast), Some("1"));
+
+	// Each item lands in the frame iTunes uses
+	let mut mp3 = Cursor::new(std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap());
+	Id3v2Tag::from(tag)
+		.save_to(&mut mp3, WriteOptions::default())
+		.unwrap();
+
+	mp3.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut mp3, ParseOptions::new()).unwrap();
+	let id3v2 = mpeg_file.id3v2().unwrap();
+
+	for (_, _, frame_id, value) in PODCAST_ITEMS {
+		assert_eq!(
+			id3v2.get_text(&FrameId::Valid(Cow::Borrowed(frame_id))),
+			Some(value),
+			"{frame_id}"
+		);
+	}
+	assert_eq!(
+		id3v2.get(&FrameId::Valid(Cow::Borrowed("PCST"))),
+		Some(&Frame::Binary(BinaryFrame::new(
+			FrameId::Valid(Cow::Borrowed("PCST")),
+			vec![0; 4]
+		)))
+	);
+
+	// And back again
+	let ilst = Ilst::from(Tag::from(id3v2.clone()));
+	for (_, fourcc, _, value) in PODCAST_ITEMS {
+		let ident = AtomIdent::Fourcc(fourcc.as_bytes().try_into().unwrap());
+		assert_eq!(
+			ilst.get(&ident).and_then(|atom| atom.data().next()),
+			Some(&AtomData::UTF8(String::from(value))),
+			"{fourcc}"
+		);
+	}
+	assert_eq!(
+		ilst.get(&AtomIdent::Fourcc(*b"pcst"))
+			.and_then(|atom| atom.data().next()),
+		Some(&AtomData::Bool(true))
+	);
+}
+
+const URL_ITEMS: [(ItemKey, &str, &str, &str); 8] = [
+	(
+		ItemKey::AudioFileUrl,
+		"WOAF",
+		"WWWAUDIOFILE",
+		"https://example.com/file",
+	),
+	(
+		ItemKey::AudioSourceUrl,
+		"WOAS",
+		"WWWAUDIOSOURCE",
+		"https://example.com/source",
+	),
+	(
+		ItemKey::CommercialInformationUrl,
+		"WCOM",
+		"WWWCOMMERCIALINFO",
+		"https://example.com/shop",
+	),
+	(
+		ItemKey::CopyrightUrl,
+		"WCOP",
+		"WWWCOPYRIGHT",
+		"https://example.com/copyright",
+	),
+	(
+		ItemKey::TrackArtistUrl,
+		"WOAR",
+		"WEBSITE",
+		"https://example.com/artist",
+	),
+	(
+		ItemKey::RadioStationUrl,
+		"WORS",
+		"WWWRADIOPAGE",
+		"https://example.com/radio",
+	),
+	(
+		ItemKey::PaymentUrl,
+		"WPAY",
+		"WWWPAYMENT",
+		"https://example.com/pay",
+	),
+	(
+		ItemKey::PublisherUrl,
+		"WPUB",
+		"WWWPUBLISHER",
+		"https://example.com/publisher",
+	),
+];
+
+#[test_log::test]
+fn url_items_round_trip() {
+	let mut vorbis_comments = VorbisComments::new();
+	for (_, _, field, value) in URL_ITEMS {
+		vorbis_comments.push(String::from(field), String::from(value));
+	}
+
+	let tag: Tag = vorbis_comments.into();
+	for (key, _, _, value) in URL_ITEMS {
+		assert_eq!(tag.get_string(&key), Some(value), "{key:?}");
+	}
+
+	// Vorbis Comments store URLs as text, which still end up in URL frames
+	let id3v2 = Id3v2Tag::from(tag);
+	for (_, frame_id, _, value) in URL_ITEMS {
+		assert_eq!(
+			id3v2.get(&FrameId::Valid(Cow::Borrowed(frame_id))),
+			Some(&Frame::Url(UrlLinkFrame::new(
+				FrameId::Valid(Cow::Borrowed(frame_id)),
+				String::from(value)
+			))),
+			"{frame_id}"
+		);
+	}
+
+	let mut mp3 = Cursor::new(std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap());
+	id3v2.save_to(&mut mp3, WriteOptions::default()).unwrap();
+
+	mp3.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut mp3, ParseOptions::new()).unwrap();
+	let tag: Tag = mpeg_file.id3v2().unwrap().clone().into();
+	for (key, _, _, value) in URL_ITEMS {
+		assert_eq!(
+			tag.get(&key).map(TagItem::value),
+			Some(&ItemValue::Locator(String::from(value))),
+			"{key:?}"
+		);
+	}
+
+	// And back again
+	let vorbis_comments = VorbisComments::from(tag);
+	for (_, _, field, value) in URL_ITEMS {
+		assert_eq!(vorbis_comments.get(field), Some(value), "{field}");
+	}
+}
+
+#[test_log::test]
+fn url_items_ape_keys() {
+	for (key, ape_key) in [
+		(ItemKey::AudioFileUrl, "File URL"),
+		(ItemKey::AudioSourceUrl, "Related"),
+		(ItemKey::CopyrightUrl, "Copyright URL"),
+		(ItemKey::TrackArtistUrl, "Artist URL"),
+		(ItemKey::PaymentUrl, "Buy URL"),
+		(ItemKey::PublisherUrl, "Publisher URL"),
+	] {
+		let mut ape = ApeTag::new();
+		ape.insert(
+			ApeItem::new(
+				String::from(ape_key),
+				ItemValue::Locator(String::from("https://example.com")),
+			)
+			.unwrap(),
+		);
+
+		let tag: Tag = ape.into();
+		assert_eq!(
+			tag.get_locators(&key).next(),
+			Some("https://example.com"),
+			"{key:?}"
+		);
+
+		let ape = ApeTag::from(tag);
+		assert!(ape.get(ape_key).is_some(), "{ape_key}");
+	}
+}
+
+#[test_log::test]
+fn id3v2_url_frame_non_latin1() {
+	let mut tag = Tag::new(TagType::Id3v2);
+	tag.insert_text(
+		ItemKey::TrackArtistUrl,
+		String::from("https://example.com/café/€"),
+	);
+
+	// Latin-1 characters are kept, everything else is percent-encoded
+	let id3v2 = Id3v2Tag::from(tag);
+	assert_eq!(
+		id3v2.get(&FrameId::Valid(Cow::Borrowed("WOAR"))),
+		Some(&Frame::Url(UrlLinkFrame::new(
+			FrameId::Valid(Cow::Borrowed("WOAR")),
+			String::from("https://example.com/café/%E2%82%AC")
+		)))
+	);
+}
+
+const LYRICS: [(&[u8; 3], &str, &str); 2] = [
+	(b"eng", "", "English lyrics"),
+	(b"jpn", "Romaji", "Nihongo no kashi"),
+];
+
+#[test_log::test]
+fn id3v2_multi_language_lyrics_round_trip() {
+	let mut id3v2 = Id3v2Tag::new();
+	for (lang, description, content) in LYRICS {
+		id3v2.insert(Frame::UnsynchronizedText(UnsynchronizedTextFrame::new(
+			TextEncoding::UTF8,
+			*lang,
+			String::from(description),
+			String::from(content),
+		)));
+	}
+	assert_eq!(id3v2.unsync_text().count(), 2);
+
+	let tag: Tag = id3v2.into();
+	let items = tag.get_items(&ItemKey::Lyrics).collect::<Vec<_>>();
+	assert_eq!(items.len(), 2);
+	for (item, (lang, description, content)) in items.into_iter().zip(LYRICS) {
+		assert_eq!(item.lang(), lang);
+		assert_eq!(item.description(), description);
+		assert_eq!(item.value(), &ItemValue::Text(String::from(content)));
+	}
+	assert_eq!(tag.lyrics(), Some("English lyrics"));
+
+	let mut mp3 = Cursor::new(std::fs::read("tests/files/assets/minimal/full_test.mp3").unwrap());
+	Id3v2Tag::from(tag)
+		.save_to(&mut mp3, WriteOptions::default())
+		.unwrap();
+
+	mp3.rewind().unwrap();
+	let mpeg_file = MpegFile::read_from(&mut mp3, ParseOptions::new()).unwrap();
+	let frames = mpeg_file.id3v2().unwrap().unsync_text().collect::<Vec<_>>();
+	assert_eq!(frames.len(), 2);
+	for (lang, description, content) in LYRICS {
+		assert!(
+			frames.iter().any(|frame| &frame.language == lang
+				&& frame.description == description
+				&& frame.content == content),
+			"{lang:?}"
+		);
+	}
+}
+
+#[test_log::test]
+fn mp4_lyrics() {
+	let mut tag = Tag::new(TagType::Mp4Ilst);
+	assert!(tag.set_lyrics(String::from("Line one\nLine two")));
+
+	let ilst = Ilst::from(tag);
+	assert_eq!(
+		ilst.get(&AtomIdent::Fourcc(*b"\xa9lyr"))
+			.and_then(|atom| atom.data().next()),
+		Some(&AtomData::UTF8(String::from("Line one\nLine two")))
+	);
+
+	let mut m4a =
+		Cursor::new(std::fs::read("tests/files/assets/minimal/m4a_codec_aac.m4a").unwrap());
+	ilst.save_to(&mut m4a, WriteOptions::default()).unwrap();
+
+	m4a.rewind().unwrap();
+	let mp4_file = Mp4File::read_from(&mut m4a, ParseOptions::new()).unwrap();
+	let mut tag: Tag = mp4_file.ilst().unwrap().clone().into();
+	assert_eq!(tag.lyrics(), Some("Line one\nLine two"));
+
+	// The lyrics are carried over to the other formats
+	let vorbis_comments = VorbisComments::from(tag.clone());
+	assert_eq!(vorbis_comments.get("LYRICS"), Some("Line one\nLine two"));
+
+	tag.remove_lyrics();
+	assert_eq!(tag.lyrics(), None);
+}
+
+#[test_log::test]
+fn vorbis_comments_unsynced_lyrics() {
+	let mut vorbis_comments = VorbisComments::new();
+	vorbis_comments.push(String::from("UNSYNCEDLYRICS"), String::from("Lyrics"));
+
+	let tag: Tag = vorbis_comments.into();
+	assert_eq!(tag.lyrics(), Some("Lyrics"));
+
+	let ape = ApeTag::from(tag);
+	assert!(ape.get("Lyrics").is_some());
+}
+
+// (key, Vorbis Comments key, ID3v2 frame, MP4 freeform name, value)
+const RELEASE_IDENTIFIERS: [(ItemKey, &str, &str, &str, &str); 3] = [
+	(ItemKey::Isrc, "ISRC", "TSRC", "ISRC", "USRC17607839"),
+	(
+		ItemKey::Barcode,
+		"BARCODE",
+		"TXXX:BARCODE",
+		"BARCODE",
+		"0602445790272",
+	),
+	(
+		ItemKey::CatalogNumber,
+		"CATALOGNUMBER",
+		"TXXX:CATALOGNUMBER",
+		"CATALOGNUMBER",
+		"B0036488-02",
+	),
+];
+
+fn release_identifiers_tag(tag_type: TagType) -> Tag {
+	let mut tag = Tag::new(tag_type);
+	for (key, _, _, _, value) in RELEASE_IDENTIFIERS {
+		tag.insert_text(key, String::from(value));
+	}
+
+	tag
+}
+
+fn assert_release_identifiers(tag: &Tag) {
+	for (key, _, _, _, value) in RELEASE_IDENTIFIERS {
+		assert_eq!(tag.get_string(&key), Some(value), "{key:?}");
+	}
+}
+
+#[test_log::test]
+fn release_identifiers_id3v2_round_trip() {
+	let id3v2: Id3v2Tag = release_identifiers_tag(TagType::Id3v2).into();
+
+	for (key, _, frame, _, value) in RELEASE_IDENTIFIERS {
+		let content = match frame.split_once(':') {
+			Some((_, description)) => id3v2.get_user_text(description),
+			None => id3v2.get_text(&FrameId::Valid(Cow::Borrowed(frame))),
+		};
+		assert_eq!(content, Some(value), "{key:?}");
+	}
+
+	let mut bytes = Vec::new();
+	id3v2.dump_to(&mut bytes, WriteOptions::default()).unwrap();
+
+	let mut reader = Cursor::new(bytes);
+	let header = Id3v2Header::parse(&mut reader).unwrap();
+
+	let mut id3v2 = Id3v2Tag::new();
+	for frame in FrameIter::new(&mut reader, header, ParseOptions::new()) {
+		id3v2.insert(frame.unwrap());
+	}
+
+	assert_release_identifiers(&id3v2.into());
+}
+
+#[test_log::test]
+fn release_identifiers_vorbis_comments_round_trip() {
+	let vorbis_comments: VorbisComments = release_identifiers_tag(TagType::VorbisComments).into();
+
+	for (key, vorbis_key, _, _, value) in RELEASE_IDENTIFIERS {
+		assert!(
+			vorbis_comments
+				.items()
+				.any(|(k, v)| k == vorbis_key && v == value),
+			"{key:?}"
+		);
+	}
+
+	assert_release_identifiers(&vorbis_comments.into());
+}
+
+#[test_log::test]
+fn release_identifiers_mp4_round_trip() {
+	let ilst: Ilst = release_identifiers_tag(TagType::Mp4Ilst).into();
+
+	for (key, _, _, name, value) in RELEASE_IDENTIFIERS {
+		let ident = AtomIdent::Freeform {
+			mean: Cow::Borrowed("com.apple.iTunes"),
+			name: Cow::Borrowed(name),
+		};
+
+		let atom = ilst.get(&ident);
+		assert!(atom.is_some(), "{key:?}");
+		assert_eq!(
+			atom.unwrap().data().collect::<Vec<_>>(),
+			[&AtomData::UTF8(String::from(value))],
+			"{key:?}"
+		);
+	}
+
+	assert_release_identifiers(&ilst.into());
+}
@@ -1,3 +1,7 @@
 #![allow(missing_docs)]
 
 mod conversions;
+mod parse;
+mod pretty_print;
+mod serialization;
+mod serialized_len;
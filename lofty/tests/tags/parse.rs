@@ -0,0 +1,122 @@
+// Tests for the standalone tag parsers, which must accept the output of `TagExt::dump_to`
+
+use lofty::ape::ApeTag;
+use lofty::config::{ParseOptions, WriteOptions};
+use lofty::id3::v2::{Id3v2Tag, Id3v2TagFlags};
+use lofty::mp4::Ilst;
+use lofty::ogg::VorbisComments;
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::prelude::*;
+use lofty::tag::{ItemKey, Tag, TagType};
+
+use std::io::Cursor;
+
+fn dump<T: TagExt>(tag: &T, write_options: WriteOptions) -> Vec<u8>
+where
+	T::Err: std::fmt::Debug,
+{
+	let mut dumped = Vec::new();
+	tag.dump_to(&mut dumped, write_options).unwrap();
+	dumped
+}
+
+fn test_tag(tag_type: TagType) -> Tag {
+	let data = std::fs::read("tests/picture/assets/png_640x628.png").unwrap();
+
+	let mut tag = Tag::new(tag_type);
+	tag.set_title(String::from("Foo title"));
+	tag.set_artist(String::from("Bar artist ünïcödé"));
+	tag.set_album(String::from("Baz album"));
+	tag.set_track(1);
+	tag.set_track_total(12);
+	tag.set_disk(2);
+	tag.insert_text(ItemKey::Comment, String::from("Qux comment"));
+	tag.push_picture(Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		// Descriptions can't be stored in `ilst`
+		None,
+		data,
+	));
+
+	tag
+}
+
+#[test_log::test]
+fn ape() {
+	let tag: ApeTag = test_tag(TagType::Ape).into();
+	let dumped = dump(&tag, WriteOptions::default());
+
+	let mut reader = Cursor::new(&dumped);
+	let parsed = ApeTag::parse(&mut reader, ParseOptions::new()).unwrap();
+	assert_eq!(parsed, tag);
+	assert_eq!(reader.position(), dumped.len() as u64);
+
+	// Not an APE tag
+	assert!(ApeTag::parse(&mut Cursor::new(&dumped[1..]), ParseOptions::new()).is_err());
+}
+
+#[test_log::test]
+fn id3v2() {
+	let mut tag: Id3v2Tag = test_tag(TagType::Id3v2).into();
+
+	for write_options in [
+		WriteOptions::default(),
+		WriteOptions::default().preferred_padding(0),
+	] {
+		let dumped = dump(&tag, write_options);
+
+		let mut reader = Cursor::new(&dumped);
+		let parsed = Id3v2Tag::parse(&mut reader, ParseOptions::new()).unwrap();
+		assert_eq!(parsed, tag);
+		assert_eq!(reader.position(), dumped.len() as u64);
+	}
+
+	// Footer
+	let mut flags = Id3v2TagFlags::default();
+	flags.footer = true;
+	tag.set_flags(flags);
+
+	let dumped = dump(&tag, WriteOptions::default());
+
+	let mut reader = Cursor::new(&dumped);
+	let parsed = Id3v2Tag::parse(&mut reader, ParseOptions::new()).unwrap();
+	assert_eq!(parsed, tag);
+	assert_eq!(reader.position(), dumped.len() as u64);
+
+	// Not an ID3v2 tag
+	assert!(Id3v2Tag::parse(&mut Cursor::new(&dumped[1..]), ParseOptions::new()).is_err());
+}
+
+#[test_log::test]
+fn vorbis_comments() {
+	let mut tag: VorbisComments = test_tag(TagType::VorbisComments).into();
+	tag.set_vendor(String::from("Lofty"));
+
+	let dumped = dump(&tag, WriteOptions::default());
+
+	let mut reader = Cursor::new(&dumped);
+	let parsed =
+		VorbisComments::parse(&mut reader, dumped.len() as u64, ParseOptions::new()).unwrap();
+	assert_eq!(parsed, tag);
+	assert_eq!(reader.position(), dumped.len() as u64);
+
+	// Vendor string extends past the block
+	assert!(VorbisComments::parse(&mut Cursor::new(&dumped), 4, ParseOptions::new()).is_err());
+}
+
+#[test_log::test]
+fn ilst() {
+	let tag: Ilst = test_tag(TagType::Mp4Ilst).into();
+	let dumped = dump(&tag, WriteOptions::default());
+
+	let mut reader = Cursor::new(&dumped);
+	let parsed = Ilst::parse(&mut reader, ParseOptions::new()).unwrap();
+	assert_eq!(parsed, tag);
+	assert_eq!(reader.position(), dumped.len() as u64);
+
+	// Not an ilst atom
+	let mut not_ilst = dumped.clone();
+	not_ilst[4..8].copy_from_slice(b"free");
+	assert!(Ilst::parse(&mut Cursor::new(not_ilst), ParseOptions::new()).is_err());
+}
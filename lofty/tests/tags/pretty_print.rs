@@ -0,0 +1,205 @@
+use lofty::ape::ApeTag;
+use lofty::config::PrettyPrintOptions;
+use lofty::id3::v2::{ExtendedTextFrame, Frame, Id3v2Tag, PopularimeterFrame, PrivateFrame};
+use lofty::mp4::{Atom, AtomData, AtomIdent, Ilst};
+use lofty::ogg::VorbisComments;
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::prelude::*;
+use lofty::properties::{ChannelMask, FileProperties};
+use lofty::tag::{Tag, TagType};
+use lofty::TextEncoding;
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+fn picture() -> Picture {
+	// PNG signature, followed by some filler
+	let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+	data.extend([0; 8]);
+
+	Picture::new_unchecked(PictureType::CoverFront, Some(MimeType::Png), None, data)
+}
+
+fn test_tag(tag_type: TagType) -> Tag {
+	let mut tag = Tag::new(tag_type);
+	tag.set_title(String::from("Foo title"));
+	tag.set_artist(String::from("Bar artist"));
+	tag.set_comment(String::from("Line 1\nLine 2\tTabbed"));
+	tag.push_picture(picture());
+
+	tag
+}
+
+fn pretty_print(f: impl FnOnce(&mut String) -> std::fmt::Result) -> String {
+	let mut output = String::new();
+	f(&mut output).unwrap();
+	output
+}
+
+#[test_log::test]
+fn tag() {
+	let tag = test_tag(TagType::Id3v2);
+
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::default())),
+		"TrackTitle\tText\tFoo title\n\
+		 TrackArtist\tText\tBar artist\n\
+		 Comment\tText\tLine 1\\nLine 2\\tTabbed\n\
+		 Picture\tPicture\tCoverFront, image/png, 16 bytes\n"
+	);
+}
+
+#[test_log::test]
+fn id3v2() {
+	let mut tag: Id3v2Tag = test_tag(TagType::Id3v2).into();
+	tag.insert(Frame::UserText(ExtendedTextFrame::new(
+		TextEncoding::UTF8,
+		String::from("BARCODE"),
+		String::from("012345678905"),
+	)));
+	tag.insert(Frame::Popularimeter(PopularimeterFrame::new(
+		String::from("foo@bar.com"),
+		196,
+		3,
+	)));
+	tag.insert(Frame::Private(PrivateFrame::new(
+		String::from("com.example"),
+		vec![1, 2, 3],
+	)));
+
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::default())),
+		"TPE1\tText\tBar artist\n\
+		 TIT2\tText\tFoo title\n\
+		 COMM:XXX:\tComment\tLine 1\\nLine 2\\tTabbed\n\
+		 APIC\tPicture\tCoverFront, image/png, 16 bytes\n\
+		 TXXX:BARCODE\tUserText\t012345678905\n\
+		 POPM:foo@bar.com\tPopularimeter\trating 196, counter 3\n\
+		 PRIV:com.example\tPrivate\t3 bytes\n"
+	);
+}
+
+#[test_log::test]
+fn ape() {
+	let tag: ApeTag = test_tag(TagType::Ape).into();
+
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::default())),
+		"Title\tText\tFoo title\n\
+		 Artist\tText\tBar artist\n\
+		 Comment\tText\tLine 1\\nLine 2\\tTabbed\n\
+		 Cover Art (Front)\tPicture\tCoverFront, image/png, 16 bytes\n"
+	);
+}
+
+#[test_log::test]
+fn vorbis_comments() {
+	let mut tag: VorbisComments = test_tag(TagType::VorbisComments).into();
+	tag.set_vendor(String::from("Lofty"));
+
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::default())),
+		"TITLE\tText\tFoo title\n\
+		 ARTIST\tText\tBar artist\n\
+		 COMMENT\tText\tLine 1\\nLine 2\\tTabbed\n\
+		 METADATA_BLOCK_PICTURE\tPicture\tCoverFront, image/png, 16 bytes\n"
+	);
+}
+
+#[test_log::test]
+fn ilst() {
+	let mut tag: Ilst = test_tag(TagType::Mp4Ilst).into();
+	tag.insert(Atom::new(
+		AtomIdent::Freeform {
+			mean: Cow::Borrowed("com.apple.iTunes"),
+			name: Cow::Borrowed("FOO"),
+		},
+		AtomData::UTF8(String::from("Bar")),
+	));
+	tag.insert(Atom::new(AtomIdent::Fourcc(*b"cpil"), AtomData::Bool(true)));
+	tag.insert(Atom::new(
+		AtomIdent::Fourcc(*b"tmpo"),
+		AtomData::UnsignedInteger(120),
+	));
+
+	// MP4 pictures don't store a type
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::default())),
+		"©nam\tUTF8\tFoo title\n\
+		 ©ART\tUTF8\tBar artist\n\
+		 ©cmt\tUTF8\tLine 1\\nLine 2\\tTabbed\n\
+		 covr\tPicture\tOther, image/png, 16 bytes\n\
+		 ----:com.apple.iTunes:FOO\tUTF8\tBar\n\
+		 cpil\tBool\ttrue\n\
+		 tmpo\tUnsignedInteger\t120\n"
+	);
+}
+
+#[test_log::test]
+fn sort_keys() {
+	let mut tag = VorbisComments::default();
+	tag.push(String::from("TITLE"), String::from("Foo title"));
+	tag.push(String::from("ARTIST"), String::from("Bar artist"));
+	tag.push(String::from("ARTIST"), String::from("Baz artist"));
+
+	// Lines with the same key keep their original order
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::new().sort_keys(true))),
+		"ARTIST\tText\tBar artist\n\
+		 ARTIST\tText\tBaz artist\n\
+		 TITLE\tText\tFoo title\n"
+	);
+}
+
+#[test_log::test]
+fn max_value_len() {
+	let mut tag = VorbisComments::default();
+	tag.push(String::from("TITLE"), String::from("Foo title"));
+	tag.push(String::from("ARTIST"), String::from("Bär\u{7}"));
+
+	// Truncation counts characters, before escaping
+	assert_eq!(
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::new().max_value_len(4))),
+		"TITLE\tText\tFoo ...\n\
+		 ARTIST\tText\tBär\\u{7}\n"
+	);
+
+	let long = "a".repeat(PrettyPrintOptions::DEFAULT_MAX_VALUE_LEN + 1);
+	tag.insert(String::from("TITLE"), long.clone());
+
+	let output = pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::default()));
+	assert!(output.contains(&format!("\t{}...\n", &long[1..])));
+
+	let output =
+		pretty_print(|w| tag.pretty_print(w, PrettyPrintOptions::new().max_value_len(usize::MAX)));
+	assert!(output.contains(&format!("\t{long}\n")));
+}
+
+#[test_log::test]
+fn file_properties() {
+	let properties = FileProperties::new(
+		Duration::from_millis(1500),
+		Some(1411),
+		Some(1411),
+		Some(44100),
+		Some(16),
+		Some(2),
+		Some(ChannelMask::stereo()),
+	);
+
+	assert_eq!(
+		pretty_print(|w| properties.pretty_print(w, PrettyPrintOptions::default())),
+		"Duration\t1500 ms\n\
+		 OverallBitrate\t1411 kbps\n\
+		 AudioBitrate\t1411 kbps\n\
+		 SampleRate\t44100 Hz\n\
+		 BitDepth\t16\n\
+		 Channels\t2\n\
+		 ChannelMask\t0x00000003\n"
+	);
+
+	assert_eq!(
+		pretty_print(|w| FileProperties::default().pretty_print(w, PrettyPrintOptions::default())),
+		"Duration\t0 ms\n"
+	);
+}
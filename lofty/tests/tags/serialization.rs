@@ -0,0 +1,141 @@
+#[cfg(feature = "serde")]
+mod serde_impls {
+	use lofty::config::ParseOptions;
+	use lofty::file::AudioFile;
+	use lofty::flac::{FlacFile, FlacProperties};
+	use lofty::picture::{MimeType, Picture, PictureType};
+	use lofty::properties::FileProperties;
+	use lofty::tag::{ItemKey, ItemValue, Tag, TagItem, TagType};
+
+	use std::fs::File;
+
+	fn full_tag() -> Tag {
+		let mut tag = Tag::new(TagType::Id3v2);
+		tag.push(TagItem::new(
+			ItemKey::TrackTitle,
+			ItemValue::Text(String::from("Foo title")),
+		));
+		tag.push(TagItem::new(
+			ItemKey::AudioFileUrl,
+			ItemValue::Locator(String::from("https://example.com/foo.mp3")),
+		));
+		tag.push_unchecked(TagItem::new(
+			ItemKey::Unknown(String::from("XFOO")),
+			ItemValue::Binary(vec![0, 1, 2, 255]),
+		));
+
+		let mut comment = TagItem::new(
+			ItemKey::Comment,
+			ItemValue::Text(String::from("Bar comment")),
+		);
+		comment.set_lang(*b"deu");
+		comment.set_description(String::from("Baz description"));
+		tag.push(comment);
+
+		tag.push_picture(Picture::new_unchecked(
+			PictureType::CoverFront,
+			Some(MimeType::Png),
+			Some(String::from("Front")),
+			vec![0x89, b'P', b'N', b'G', 0, 1, 2, 3],
+		));
+		tag.push_picture(Picture::new_unchecked(
+			PictureType::Undefined(200),
+			Some(MimeType::Unknown(String::from("image/x-custom"))),
+			None,
+			vec![4, 5, 6],
+		));
+
+		tag
+	}
+
+	#[test_log::test]
+	fn tag_round_trip() {
+		let tag = full_tag();
+
+		let json = serde_json::to_string(&tag).unwrap();
+		let deserialized: Tag = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(deserialized.tag_type(), tag.tag_type());
+		assert_eq!(
+			deserialized.items().collect::<Vec<_>>(),
+			tag.items().collect::<Vec<_>>()
+		);
+		assert_eq!(deserialized.pictures(), tag.pictures());
+	}
+
+	#[test_log::test]
+	fn extension_variants_round_trip() {
+		let key = ItemKey::Unknown(String::from("MY CUSTOM KEY"));
+		let json = serde_json::to_string(&key).unwrap();
+		assert_eq!(serde_json::from_str::<ItemKey>(&json).unwrap(), key);
+
+		let mime_type = MimeType::Unknown(String::from("image/x-custom"));
+		let json = serde_json::to_string(&mime_type).unwrap();
+		assert_eq!(serde_json::from_str::<MimeType>(&json).unwrap(), mime_type);
+
+		let picture_type = PictureType::Undefined(200);
+		let json = serde_json::to_string(&picture_type).unwrap();
+		assert_eq!(
+			serde_json::from_str::<PictureType>(&json).unwrap(),
+			picture_type
+		);
+	}
+
+	#[test_log::test]
+	fn binary_data_is_base64() {
+		let value = serde_json::to_value(full_tag()).unwrap();
+
+		assert_eq!(value["pictures"][1]["data"], "BAUG");
+		assert_eq!(value["items"][2]["item_value"]["Binary"], "AAEC/w==");
+	}
+
+	#[test_log::test]
+	fn properties_round_trip() {
+		let mut file = File::open("tests/files/assets/minimal/full_test.flac").unwrap();
+		let flac_file = FlacFile::read_from(&mut file, ParseOptions::new()).unwrap();
+
+		let properties = flac_file.properties();
+		let json = serde_json::to_string(properties).unwrap();
+		assert_eq!(
+			&serde_json::from_str::<FlacProperties>(&json).unwrap(),
+			properties
+		);
+
+		let file_properties = FileProperties::from(*properties);
+		let json = serde_json::to_string(&file_properties).unwrap();
+		assert_eq!(
+			serde_json::from_str::<FileProperties>(&json).unwrap(),
+			file_properties
+		);
+	}
+}
+
+// Nothing should be implemented without the feature
+#[cfg(not(feature = "serde"))]
+#[test_log::test]
+fn serde_impls_disabled() {
+	use lofty::tag::Tag;
+
+	use std::marker::PhantomData;
+
+	struct Check<T>(PhantomData<T>);
+
+	// Inherent methods take precedence, but only exist if the bound is satisfied
+	impl<T: serde::Serialize> Check<T> {
+		#[allow(dead_code, clippy::unused_self)]
+		fn implements_serialize(&self) -> bool {
+			true
+		}
+	}
+
+	trait Fallback {
+		#[allow(clippy::unused_self)]
+		fn implements_serialize(&self) -> bool {
+			false
+		}
+	}
+
+	impl<T> Fallback for Check<T> {}
+
+	assert!(!Check::<Tag>(PhantomData).implements_serialize());
+}
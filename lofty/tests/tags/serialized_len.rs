@@ -0,0 +1,159 @@
+// Tests for `TagExt::serialized_len`, which must always match the output of `TagExt::dump_to`
+
+use lofty::ape::ApeTag;
+use lofty::config::WriteOptions;
+use lofty::id3::v1::Id3v1Tag;
+use lofty::id3::v2::{Id3v2Tag, Id3v2TagFlags};
+use lofty::mp4::{Atom, AtomData, AtomIdent, DataType, Ilst};
+use lofty::ogg::VorbisComments;
+use lofty::picture::{MimeType, Picture, PictureType};
+use lofty::prelude::*;
+use lofty::tag::{ItemKey, Tag, TagType};
+
+use std::borrow::Cow;
+
+fn assert_serialized_len<T: TagExt>(tag: &T, write_options: WriteOptions)
+where
+	T::Err: std::fmt::Debug,
+{
+	let mut dumped = Vec::new();
+	tag.dump_to(&mut dumped, write_options).unwrap();
+
+	assert_eq!(
+		tag.serialized_len(write_options).unwrap(),
+		dumped.len() as u64
+	);
+}
+
+fn picture() -> Picture {
+	let data = std::fs::read("tests/picture/assets/png_640x628.png").unwrap();
+	Picture::new_unchecked(
+		PictureType::CoverFront,
+		Some(MimeType::Png),
+		Some(String::from("Cover")),
+		data,
+	)
+}
+
+fn test_tag(tag_type: TagType) -> Tag {
+	let mut tag = Tag::new(tag_type);
+	tag.set_title(String::from("Foo title"));
+	tag.set_artist(String::from("Bar artist ünïcödé"));
+	tag.set_album(String::from("Baz album"));
+	tag.set_track(1);
+	tag.set_track_total(12);
+	tag.set_disk(2);
+	tag.insert_text(ItemKey::Comment, String::from("Qux comment"));
+	tag.insert_text(ItemKey::RecordingDate, String::from("2024-05-06"));
+	tag.push_picture(picture());
+
+	tag
+}
+
+#[test_log::test]
+fn empty_tags() {
+	let write_options = WriteOptions::default();
+
+	assert_serialized_len(&Id3v2Tag::default(), write_options);
+	assert_serialized_len(&ApeTag::default(), write_options);
+	assert_serialized_len(&VorbisComments::default(), write_options);
+	assert_serialized_len(&Ilst::default(), write_options);
+	assert_serialized_len(&Id3v1Tag::default(), write_options);
+}
+
+#[test_log::test]
+fn id3v2() {
+	let tag: Id3v2Tag = test_tag(TagType::Id3v2).into();
+
+	for write_options in [
+		WriteOptions::default(),
+		WriteOptions::default().preferred_padding(0),
+		WriteOptions::default().use_id3v23(true),
+		WriteOptions::default()
+			.use_id3v23(true)
+			.preferred_padding(4096),
+	] {
+		assert_serialized_len(&tag, write_options);
+	}
+
+	// Extended header and footer
+	let mut tag = tag;
+	let mut flags = Id3v2TagFlags::default();
+	flags.crc = true;
+	flags.footer = true;
+	tag.set_flags(flags);
+
+	assert_serialized_len(&tag, WriteOptions::default());
+	assert_serialized_len(&tag, WriteOptions::default().use_id3v23(true));
+}
+
+#[test_log::test]
+fn ape() {
+	let tag: ApeTag = test_tag(TagType::Ape).into();
+
+	assert_serialized_len(&tag, WriteOptions::default());
+	assert_serialized_len(&tag, WriteOptions::default().normalize_ape_items(true));
+}
+
+#[test_log::test]
+fn vorbis_comments() {
+	let mut tag: VorbisComments = test_tag(TagType::VorbisComments).into();
+	tag.set_vendor(String::from("Lofty"));
+
+	assert_serialized_len(&tag, WriteOptions::default());
+}
+
+#[test_log::test]
+fn ilst() {
+	let mut tag: Ilst = test_tag(TagType::Mp4Ilst).into();
+
+	tag.insert(Atom::new(
+		AtomIdent::Freeform {
+			mean: Cow::Borrowed("com.apple.iTunes"),
+			name: Cow::Borrowed("FOO"),
+		},
+		AtomData::UTF8(String::from("Bar")),
+	));
+	tag.insert(Atom::new(AtomIdent::Fourcc(*b"cpil"), AtomData::Bool(true)));
+	tag.insert(Atom::new(
+		AtomIdent::Fourcc(*b"tmpo"),
+		AtomData::UnsignedInteger(120),
+	));
+	tag.insert(Atom::new(
+		AtomIdent::Fourcc(*b"\xa9xyz"),
+		AtomData::Unknown {
+			code: DataType::Utf8,
+			data: b"+00.0000+000.0000/".to_vec(),
+		},
+	));
+
+	assert_serialized_len(&tag, WriteOptions::default());
+}
+
+#[test_log::test]
+fn id3v1() {
+	let mut tag: Id3v1Tag = test_tag(TagType::Id3v1).into();
+
+	assert_serialized_len(&tag, WriteOptions::default());
+
+	// Not representable in Latin-1
+	tag.set_title(String::from("日本"));
+	assert!(tag
+		.serialized_len(WriteOptions::default().strict_id3v1(true))
+		.is_err());
+}
+
+#[test_log::test]
+fn tag() {
+	for tag_type in [
+		TagType::Id3v2,
+		TagType::Ape,
+		TagType::VorbisComments,
+		TagType::Mp4Ilst,
+		TagType::Id3v1,
+		TagType::RiffInfo,
+		TagType::AiffText,
+	] {
+		assert_serialized_len(&test_tag(tag_type), WriteOptions::default());
+	}
+}
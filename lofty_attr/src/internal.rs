@@ -9,12 +9,12 @@ use quote::quote;
 pub(crate) fn opt_internal_file_type(
 	struct_name: String,
 ) -> Option<(proc_macro2::TokenStream, bool)> {
-	const LOFTY_FILE_TYPES: [&str; 12] = [
-		"Aac", "Aiff", "Ape", "Flac", "Mpeg", "Mp4", "Mpc", "Opus", "Vorbis", "Speex", "Wav",
-		"WavPack",
+	const LOFTY_FILE_TYPES: [&str; 18] = [
+		"Aac", "Aiff", "Ape", "Asf", "Dff", "Dsf", "Flac", "Mpeg", "Mp4", "Mpc", "OggFlac", "Opus",
+		"Vorbis", "Speex", "Shn", "Tta", "Wav", "WavPack",
 	];
 
-	const ID3V2_STRIPPABLE: [&str; 2] = ["Flac", "Ape"];
+	const ID3V2_STRIPPABLE: [&str; 4] = ["Flac", "Ape", "Shn", "Tta"];
 
 	let stripped = struct_name.strip_suffix("File");
 	if let Some(prefix) = stripped {
@@ -39,6 +39,13 @@ impl SupportedFormat {
 			_ => None,
 		}
 	}
+
+	fn writable(&self) -> Option<&Path> {
+		match self {
+			SupportedFormat::Full(path) => Some(path),
+			_ => None,
+		}
+	}
 }
 
 pub(crate) struct LoftyTag {
@@ -70,6 +77,11 @@ impl LoftyTag {
 			.supported_formats
 			.iter()
 			.filter_map(SupportedFormat::read_only);
+		let writable_file_types = self
+			.attribute
+			.supported_formats
+			.iter()
+			.filter_map(SupportedFormat::writable);
 
 		let input = &self.input;
 		TokenStream::from(quote! {
@@ -90,6 +102,10 @@ impl LoftyTag {
 				pub(crate) const READ_ONLY_FORMATS: &'static [::lofty::file::FileType] = &[
 					#( ::lofty::file::FileType:: #read_only_file_types ),*
 				];
+
+				pub(crate) const WRITABLE_FORMATS: &'static [::lofty::file::FileType] = &[
+					#( ::lofty::file::FileType:: #writable_file_types ),*
+				];
 			}
 		})
 	}